@@ -0,0 +1,15 @@
+//! Parse a single chunk from arbitrary bytes.
+#![no_main]
+
+use std::io::Cursor;
+
+use binrw::BinRead;
+use libfuzzer_sys::fuzz_target;
+use wavrw::{SizedChunkEnum, Summarizable};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(chunk) = SizedChunkEnum::read(&mut Cursor::new(data)) {
+        let _ = chunk.summary();
+        let _ = chunk.items().count();
+    }
+});
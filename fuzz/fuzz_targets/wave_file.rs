@@ -0,0 +1,18 @@
+//! Parse a whole file from arbitrary bytes.
+#![no_main]
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+use wavrw::{Summarizable, WaveFile};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut wave) = WaveFile::from_reader(Cursor::new(data)) else {
+        return;
+    };
+    for chunk in wave.iter_chunks().flatten() {
+        let _ = chunk.summary();
+        let _ = chunk.items().count();
+    }
+    let _ = wave.resolve().validate();
+});
@@ -2,21 +2,29 @@
 
 #![deny(missing_docs)]
 
+use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::fs;
 use std::fs::File;
 use std::io;
-use std::path::PathBuf;
+use std::io::BufWriter;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use std::{ffi::OsString, io::BufReader};
 
 use anyhow::Result;
-use clap::{crate_version, ArgAction, Parser, Subcommand, ValueEnum};
+use clap::{crate_version, ArgAction, Args, Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
 use tracing::instrument;
 use tracing::Level;
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::FmtSubscriber;
-use wavrw::{ChunkID, SizedChunk, SizedChunkEnum, Summarizable};
+use wavrw::display::{DisplayOptions, OffsetBase};
+use wavrw::time::SampleTime;
+use wavrw::{ChunkID, FourCC, SizedChunk, SizedChunkEnum, Summarizable};
 
 #[derive(Parser, Debug)]
 #[command(author, about, long_about = None,
@@ -41,6 +49,25 @@ struct WavrwArgs {
 enum Commands {
     View(ViewConfig),
     List(ListConfig),
+    Ls(LsConfig),
+    Apply(ApplyConfig),
+    Compare(CompareConfig),
+    Export(ExportConfig),
+    ExportCore(ExportCoreConfig),
+    Import(ImportConfig),
+    ImportCore(ImportCoreConfig),
+    #[cfg(feature = "index")]
+    Index(IndexConfig),
+    Inject(InjectConfig),
+    Loudness(LoudnessConfig),
+    Markers(MarkersConfig),
+    Merge(MergeConfig),
+    Roundtrip(RoundtripConfig),
+    Split(SplitConfig),
+    Stats(StatsConfig),
+    Ucs(UcsConfig),
+    ListChunks(ListChunksConfig),
+    Schema(SchemaConfig),
     #[command(alias = "topics")]
     Topic(TopicConfig),
 }
@@ -50,15 +77,302 @@ enum Format {
     Line,
     Summary,
     Detailed,
+    Tree,
+}
+
+/// Machine readable report formats.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ReportFormat {
+    /// Errors and warnings of each file with stable codes, as JSON
+    Json,
+}
+
+/// Sidecar document formats.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum SidecarFormat {
+    Json,
+    Xml,
+}
+
+/// Output formats of the schema command.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum SchemaFormat {
+    /// One line per type and per field
+    Text,
+    /// JSON Schema document
+    Json,
+}
+
+/// Columns of view summary output.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Column {
+    Offset,
+    Id,
+    Size,
+    Summary,
+}
+
+impl Column {
+    /// Pad `text` to the width of this column, summary is not padded.
+    fn pad(self, text: &str) -> String {
+        match self {
+            Column::Offset => format!("{text:>12}"),
+            Column::Id => format!("{text:9}"),
+            Column::Size => format!("{text:>10}"),
+            Column::Summary => text.to_string(),
+        }
+    }
 }
 
 const WIDTH_DEFAULT: u16 = 80;
 
+/// How sample counts are shown in summaries.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Units {
+    /// Number of samples
+    Samples,
+    /// hh:mm:ss.mmm time, using the fmt sample rate
+    Seconds,
+}
+
+impl From<Units> for wavrw::display::Units {
+    fn from(units: Units) -> Self {
+        match units {
+            Units::Samples => wavrw::display::Units::Samples,
+            Units::Seconds => wavrw::display::Units::Seconds,
+        }
+    }
+}
+
+/// What to do with non-ASCII text in bext and LIST-INFO fields.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum TextPolicy {
+    /// Fail, leaving the file unchanged
+    Strict,
+    /// Replace with similar ASCII characters, ex: é with e
+    Replace,
+    /// Write the text unchanged, new text as UTF-8
+    AllowUtf8,
+}
+
+impl From<TextPolicy> for wavrw::sanitize::TextPolicy {
+    fn from(policy: TextPolicy) -> Self {
+        match policy {
+            TextPolicy::Strict => wavrw::sanitize::TextPolicy::Strict,
+            TextPolicy::Replace => wavrw::sanitize::TextPolicy::Replace,
+            TextPolicy::AllowUtf8 => wavrw::sanitize::TextPolicy::AllowUtf8,
+        }
+    }
+}
+
+/// How chunk offsets are shown.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum Offsets {
+    /// Decimal numbers
+    Decimal,
+    /// Hexadecimal numbers, for cross-referencing with a hex editor
+    Hex,
+    /// From the start of the file, at the chunk id
+    File,
+    /// From the start of the RIFF data, 8 bytes into the file
+    Riff,
+    /// At the start of the chunk data, after the id and size
+    Payload,
+}
+
+/// When to color output.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ColorChoice {
+    /// Color when writing to a terminal and NO_COLOR is not set
+    Auto,
+    /// Always color, also when piping to other programs
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Order of the files in `list`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum SortKey {
+    /// File name
+    Path,
+    /// File size in bytes
+    Size,
+    /// Length of the audio
+    Duration,
+    /// Sample rate from fmt
+    Samplerate,
+}
+
+/// Groups of files in `list`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum GroupKey {
+    /// Sample rate from fmt
+    Samplerate,
+    /// Channel count from fmt
+    Channels,
+    /// Originator from bext
+    Originator,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && io::stdout().is_terminal()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// Output styles, chunks are styled by category.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Style {
+    /// Audio format and data chunks
+    Format,
+    /// Chunks describing or annotating the audio
+    Metadata,
+    /// Filler chunks reserving space
+    Padding,
+    /// Chunks wavrw doesn't parse
+    Unknown,
+    /// Errors reading chunks
+    Error,
+}
+
+impl Style {
+    fn of(chunk: &SizedChunkEnum) -> Style {
+        match chunk {
+            SizedChunkEnum::Fmt(_)
+            | SizedChunkEnum::Data(_)
+            | SizedChunkEnum::Fact(_)
+            | SizedChunkEnum::Ds64(_)
+            | SizedChunkEnum::Wavl(_) => Style::Format,
+            SizedChunkEnum::Cue(_)
+            | SizedChunkEnum::Info(_)
+            | SizedChunkEnum::Adtl(_)
+            | SizedChunkEnum::Cset(_)
+            | SizedChunkEnum::Plst(_)
+            | SizedChunkEnum::Inst(_)
+            | SizedChunkEnum::Smpl(_)
+            | SizedChunkEnum::Bext(_)
+            | SizedChunkEnum::Md5(_)
+            | SizedChunkEnum::Levl(_)
+            | SizedChunkEnum::Ixml(_)
+            | SizedChunkEnum::Smed(_)
+            | SizedChunkEnum::Smrd(_)
+            | SizedChunkEnum::Disp(_) => Style::Metadata,
+            SizedChunkEnum::Fllr(_) | SizedChunkEnum::Junk(_) | SizedChunkEnum::Pad(_) => {
+                Style::Padding
+            }
+            SizedChunkEnum::Unknown(_) => Style::Unknown,
+        }
+    }
+
+    /// Wrap `text` in ANSI escape codes for this style, if `color` is set.
+    /// Trailing padding is left unstyled.
+    fn paint(self, text: &str, color: bool) -> String {
+        if !color {
+            return text.to_string();
+        }
+        let padding = &text[text.trim_end().len()..];
+        let text = text.trim_end();
+        let code = match self {
+            Style::Format => "36",
+            Style::Metadata => "32",
+            Style::Padding => "90",
+            Style::Unknown => "33",
+            Style::Error => "1;31",
+        };
+        format!("\x1b[{code}m{text}\x1b[0m{padding}")
+    }
+}
+
+/// Files, chunks and bytes parsed so far, drawn on one line of stderr.
+#[derive(Debug)]
+struct Progress {
+    files: usize,
+    done: AtomicUsize,
+    chunks: AtomicUsize,
+    bytes: AtomicU64,
+    drawn: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    /// Redraw at most this often.
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    fn new(files: usize) -> Self {
+        Progress {
+            files,
+            done: AtomicUsize::new(0),
+            chunks: AtomicUsize::new(0),
+            bytes: AtomicU64::new(0),
+            drawn: Mutex::new(None),
+        }
+    }
+
+    fn draw(&self) {
+        let Ok(mut drawn) = self.drawn.lock() else {
+            return;
+        };
+        if drawn.is_some_and(|at| at.elapsed() < Self::INTERVAL) {
+            return;
+        }
+        *drawn = Some(Instant::now());
+        let done = self.done.load(Ordering::Relaxed);
+        let chunks = self.chunks.load(Ordering::Relaxed);
+        let mb = self.bytes.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        eprint!(
+            "\r\x1b[K{done}/{} files, {chunks} chunks, {mb:.1} MB",
+            self.files
+        );
+    }
+
+    /// Erase the progress line, if it was drawn.
+    fn clear(&self) {
+        if self.drawn.lock().is_ok_and(|drawn| drawn.is_some()) {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+impl wavrw::scan::ScanProgress for Progress {
+    fn chunk_parsed(&self, _path: &Path, bytes: u64) {
+        self.chunks.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.draw();
+    }
+
+    fn file_finished(&self, _result: &wavrw::scan::ScanResult) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+        self.draw();
+    }
+}
+
+/// Parse `paths` in parallel, showing progress when stderr is a terminal.
+fn scan_paths<P>(paths: &[P]) -> Vec<wavrw::scan::ScanResult>
+where
+    P: AsRef<Path> + Sync,
+{
+    let options = wavrw::scan::ScanOptions::default();
+    if !io::stderr().is_terminal() {
+        return wavrw::scan::scan_paths(paths, &options);
+    }
+    let progress = Progress::new(paths.len());
+    let results = wavrw::scan::scan_paths_with_progress(paths, &options, &progress);
+    progress.clear();
+    results
+}
+
 /// Summarize WAV file structure and metadata
 #[derive(Parser, Debug)]
 #[command(long_about = None)]
 struct ViewConfig {
-    /// One or more paths to WAV files
+    /// One or more paths to WAV files, - reads from stdin. Ex: curl URL | wavrw view -
     wav_path: Vec<OsString>,
 
     /// Output format
@@ -72,10 +386,71 @@ struct ViewConfig {
     #[arg(
         long,
         short = 'w',
+        visible_alias = "max-width",
         default_value_t = WIDTH_DEFAULT,
         help = "Trim output to <WIDTH> columns"
     )]
     width: u16,
+
+    /// Columns of summary output, in order. Ex: --columns id,size,summary
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Column::Offset, Column::Id, Column::Size, Column::Summary]
+    )]
+    columns: Vec<Column>,
+
+    /// Trim long values to <WIDTH>, in detailed output too
+    #[arg(long, conflicts_with = "wrap")]
+    truncate: bool,
+
+    /// Wrap long values onto more lines instead of trimming them
+    #[arg(long)]
+    wrap: bool,
+
+    /// Show times as hh:mm:ss:ff timecode at <FPS> frames per second
+    #[arg(long)]
+    fps: Option<u32>,
+
+    /// Show all bytes of unknown chunks in detailed output, not only the first 256
+    #[arg(long)]
+    full: bool,
+
+    /// Color chunk ids by category and errors in red
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Show sample counts in summaries as samples or as times
+    #[arg(long, value_enum, default_value_t = Units::Samples)]
+    units: Units,
+
+    /// Alias for: --offsets hex
+    #[arg(long, conflicts_with = "offsets")]
+    hex: bool,
+
+    /// How chunk offsets are shown: decimal or hex numbers, counted from the
+    /// start of the file, the RIFF data or the chunk payload. Ex: --offsets hex,riff
+    #[arg(long, value_enum, value_delimiter = ',')]
+    offsets: Vec<Offsets>,
+
+    /// Trim chunk summaries to <MAX_LENGTH> chars
+    #[arg(long)]
+    max_length: Option<usize>,
+
+    /// Also show XXH64 hashes of each chunk and of the audio data
+    #[arg(long)]
+    fingerprints: bool,
+
+    /// Only print errors and warnings, in a machine readable format. Exits
+    /// with an error status if any file has errors.
+    #[arg(long, value_enum, conflicts_with_all = ["output", "fingerprints"])]
+    report: Option<ReportFormat>,
+
+    /// Also check files against a conformance profile: riff, ebu-r98,
+    /// ebu-r99 or adm-bw64
+    #[arg(long)]
+    profile: Option<wavrw::profile::Profile>,
 }
 
 impl Default for ViewConfig {
@@ -85,6 +460,19 @@ impl Default for ViewConfig {
             format: Format::Summary,
             detailed: false,
             width: WIDTH_DEFAULT,
+            columns: vec![Column::Offset, Column::Id, Column::Size, Column::Summary],
+            truncate: false,
+            wrap: false,
+            fps: None,
+            full: false,
+            color: ColorChoice::Auto,
+            units: Units::Samples,
+            hex: false,
+            offsets: vec![],
+            max_length: None,
+            fingerprints: false,
+            report: None,
+            profile: None,
         }
     }
 }
@@ -93,11 +481,78 @@ impl Default for ViewConfig {
 #[derive(Parser, Debug)]
 #[command(long_about = None)]
 struct ListConfig {
-    /// directory to list
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// Color chunk ids by category and errors in red
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Only files matching an expression of chunk item fields.
+    ///
+    /// Fields are `chunk.key`, compared with ==, !=, <, <=, >, >= or ~
+    /// (contains, ignoring case) and combined with &&, || and !.
+    /// Ex: --where 'fmt.samples_per_sec != 48000 && bext.originator ~ "Zoom"'
+    #[arg(long = "where", value_name = "EXPR")]
+    filter: Option<wavrw::query::Query>,
+
+    /// Order of the files, within each directory when recursing
+    #[arg(long, value_enum, default_value_t = SortKey::Path)]
+    sort: SortKey,
+
+    /// Group the files under a header with the file count, within each
+    /// directory when recursing
+    #[arg(long, value_enum, value_name = "KEY")]
+    group_by: Option<GroupKey>,
+}
+
+/// Show one line per file: format, duration and bext and iXML presence
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct LsConfig {
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// Also show the ids of all chunks
+    #[arg(long, short, default_value_t = false)]
+    chunks: bool,
+}
+
+/// Options of the commands which modify WAV files
+#[derive(Args, Debug)]
+struct WriteOptions {
+    /// List the chunks which would be added, removed or resized, without
+    /// writing any files
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Keep the original file as <FILE>.bak when modifying in place
+    #[arg(long, default_value_t = false, overrides_with = "in_place")]
+    backup: bool,
+
+    /// Modify files in place without a backup (default). The result is
+    /// written to a temporary file first, which then replaces the original.
+    /// inject writes directly into the original instead when the chunk fits
+    /// in padding chunks, an error partway through can leave it damaged
+    #[arg(long, default_value_t = false, overrides_with = "backup")]
+    in_place: bool,
+}
+
+impl WriteOptions {
+    /// Keep a backup of files modified in place.
+    fn backup(&self) -> bool {
+        self.backup && !self.in_place
+    }
+}
+
+/// Files and directories of the commands which read many WAV files
+#[derive(Args, Debug)]
+struct PathOptions {
+    /// WAV files or directories
     #[arg(default_value = ".")]
-    path: OsString,
+    paths: Vec<OsString>,
 
-    /// Filter to only these extensions, case insensitive.
+    /// In directories, only these extensions, case insensitive.
     ///
     /// To include multiple extenstions, use commas:
     /// Ex: --ext=wav,wave
@@ -109,6 +564,282 @@ struct ListConfig {
     recurse: bool,
 }
 
+/// Set bext, LIST-INFO and iXML metadata of many files from a template
+#[derive(Parser, Debug)]
+// paths are required, without the default of PathOptions
+#[command(
+    long_about = None,
+    mut_arg("paths", |arg| {
+        arg.required(true)
+            .default_value(None)
+            .help("WAV files or directories to modify in place")
+    })
+)]
+struct ApplyConfig {
+    /// Template file, TOML or JSON
+    ///
+    /// Values can use placeholders, filled in for each file:
+    /// {filename}, {stem}, {dir}, {path}, {index}, {date} and {time}
+    #[arg(long, short)]
+    template: OsString,
+
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    #[command(flatten)]
+    write_options: WriteOptions,
+}
+
+/// Show the chunk fields which differ between two WAV files
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct CompareConfig {
+    /// Path of the first WAV file
+    a: OsString,
+
+    /// Path of the second WAV file
+    b: OsString,
+}
+
+/// Write all metadata to a JSON or XML sidecar file
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct ExportConfig {
+    /// WAV file to read
+    wav_path: OsString,
+
+    /// Sidecar file to write. Default: print to standard output
+    #[arg(long, short)]
+    output: Option<OsString>,
+
+    /// Sidecar format
+    #[arg(long, short, value_enum, default_value_t = SidecarFormat::Json)]
+    format: SidecarFormat,
+}
+
+/// Write bext and LIST-INFO metadata of many files as BWF MetaEdit Core CSV
+#[derive(Parser, Debug)]
+// paths are required, without the default of PathOptions
+#[command(
+    long_about = None,
+    mut_arg("paths", |arg| arg.required(true).default_value(None))
+)]
+struct ExportCoreConfig {
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// CSV file to write. Default: print to standard output
+    #[arg(long, short)]
+    output: Option<OsString>,
+}
+
+/// Write metadata from a BWF MetaEdit Core CSV file into the files it lists
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct ImportCoreConfig {
+    /// Core CSV file, one row per file. Files are modified in place
+    csv_path: OsString,
+
+    /// What to do with non-ASCII text, which bext and LIST-INFO specify as
+    /// ASCII. Fields with non-ASCII text are listed as warnings
+    #[arg(long, value_enum, default_value_t = TextPolicy::AllowUtf8)]
+    text_policy: TextPolicy,
+
+    #[command(flatten)]
+    write_options: WriteOptions,
+}
+
+/// Write metadata from a sidecar file into a WAV file
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct ImportConfig {
+    /// Sidecar file written by the export command, JSON or XML. - reads
+    /// from stdin
+    sidecar_path: OsString,
+
+    /// WAV file to modify. - reads from stdin and writes the result to
+    /// stdout
+    wav_path: OsString,
+
+    /// Write the result to this path instead of modifying <WAV_PATH> in
+    /// place. - writes to stdout
+    #[arg(long, short)]
+    output: Option<OsString>,
+
+    #[command(flatten)]
+    write_options: WriteOptions,
+}
+
+/// Write parsed metadata to a SQLite catalog, using the sqlite3 program
+///
+/// The catalog has `files`, `chunks` and `items` tables, keyed on the path
+/// relative to the directory of the catalog. Files with the same
+/// modification time and size as in the catalog are not parsed again, and
+/// files which no longer exist are removed.
+#[cfg(feature = "index")]
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct IndexConfig {
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// Catalog database. Default: wavrw.db in the first directory
+    #[arg(long)]
+    db: Option<PathBuf>,
+
+    /// Parse all files again, also those unchanged since the last run
+    #[arg(long, default_value_t = false)]
+    full: bool,
+
+    /// The sqlite3 program to run
+    #[arg(long, default_value_os = "sqlite3")]
+    sqlite3: OsString,
+}
+
+/// Insert or replace a chunk with raw bytes from a file
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct InjectConfig {
+    /// Chunk id to insert or replace. Ex: iXML
+    #[arg(long, short)]
+    chunk: FourCC,
+
+    /// File containing the raw chunk data (without chunk id and size). -
+    /// reads from stdin
+    payload_path: OsString,
+
+    /// WAV file to modify. - reads from stdin and writes the result to
+    /// stdout. Ex: curl URL | wavrw inject -c iXML ixml.xml - > out.wav
+    wav_path: OsString,
+
+    /// Write the result to this path instead of modifying <WAV_PATH> in
+    /// place. - writes to stdout
+    #[arg(long, short)]
+    output: Option<OsString>,
+
+    #[command(flatten)]
+    write_options: WriteOptions,
+}
+
+/// Measure EBU R 128 loudness and true peak of the audio
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct LoudnessConfig {
+    /// One or more paths to WAV files
+    wav_path: Vec<OsString>,
+}
+
+/// Show cue points, labels and loops as a timeline
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct MarkersConfig {
+    /// One or more paths to WAV files
+    wav_path: Vec<OsString>,
+
+    /// Show times as hh:mm:ss:ff timecode at <FPS> frames per second
+    #[arg(long)]
+    fps: Option<u32>,
+}
+
+/// Split a multichannel WAV file into mono files, or extract channels
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct SplitConfig {
+    /// WAV file to split
+    wav_path: OsString,
+
+    /// Channels to write, counted from 1. Default: all channels
+    ///
+    /// To select multiple channels, use commas:
+    /// Ex: --channels=1,3
+    #[arg(long, short, value_delimiter = ',', value_parser = clap::value_parser!(u16).range(1..))]
+    channels: Vec<u16>,
+
+    /// Write the selected channels to this single file instead of one mono
+    /// file per channel
+    #[arg(long, short, conflicts_with = "dir")]
+    output: Option<OsString>,
+
+    /// Directory for the mono files, named <STEM>_<CHANNEL>.wav. Default:
+    /// the directory of <WAV_PATH>
+    #[arg(long, short)]
+    dir: Option<OsString>,
+}
+
+/// Merge mono WAV files of one recording into a multichannel file
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct MergeConfig {
+    /// WAV files to merge, their channels are written in this order
+    #[arg(required = true, num_args = 2..)]
+    wav_path: Vec<OsString>,
+
+    /// Path of the new multichannel file
+    #[arg(long, short)]
+    output: OsString,
+}
+
+/// Check that every chunk writes back to the bytes it was read from
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct RoundtripConfig {
+    /// One or more paths to WAV files
+    wav_path: Vec<OsString>,
+}
+
+/// Show bytes used by audio, headers, padding and metadata
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct StatsConfig {
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// Only show the total of all files
+    #[arg(long, short, default_value_t = false)]
+    aggregate: bool,
+
+    /// Only files matching an expression of chunk item fields.
+    ///
+    /// Fields are `chunk.key`, compared with ==, !=, <, <=, >, >= or ~
+    /// (contains, ignoring case) and combined with &&, || and !.
+    /// Ex: --where 'fmt.samples_per_sec != 48000 && bext.originator ~ "Zoom"'
+    #[arg(long = "where", value_name = "EXPR")]
+    filter: Option<wavrw::query::Query>,
+}
+
+/// Check UCS file names against the UCS fields in iXML
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct UcsConfig {
+    #[command(flatten)]
+    path_options: PathOptions,
+
+    /// Write the UCS fields from the file name to iXML, modifying files in
+    /// place
+    #[arg(long, short, default_value_t = false)]
+    write: bool,
+
+    #[command(flatten)]
+    write_options: WriteOptions,
+}
+
+/// List chunk types wavrw can parse
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct ListChunksConfig {}
+
+/// Describe the chunk types and their fields, for building metadata editors
+#[derive(Parser, Debug)]
+#[command(long_about = None)]
+struct SchemaConfig {
+    /// Only these types. Ex: Bext CuePoint
+    types: Vec<String>,
+
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = SchemaFormat::Text)]
+    format: SchemaFormat,
+}
+
 /// Print additional help and reference topics.
 #[derive(Parser, Debug)]
 #[command()]
@@ -154,9 +885,80 @@ fn trim(text: &str, width: u16) -> String {
     text
 }
 
+/// Word wrap `text` into lines of at most `width` chars, continuation lines
+/// start with `prefix`. Words longer than a line are split.
+fn wrap(text: &str, width: u16, prefix: &str) -> String {
+    let text = text.replace('\r', "");
+    let text = text.replace('\n', " ");
+    let line_width = usize::from(width)
+        .saturating_sub(prefix.chars().count())
+        .max(1);
+
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    for word in text.split(' ') {
+        let line_len = line.chars().count();
+        if line_len > 0 && line_len + 1 + word.chars().count() > line_width {
+            lines.push(core::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+        while line.chars().count() > line_width {
+            let rest: String = line.chars().skip(line_width).collect();
+            lines.push(line.chars().take(line_width).collect());
+            line = rest;
+        }
+    }
+    lines.push(line);
+    lines.join(&format!("\n{prefix}"))
+}
+
+impl ViewConfig {
+    /// Options for rendering chunk summaries and offsets.
+    fn display_options(&self, sample_rate: u32) -> DisplayOptions {
+        let mut options = DisplayOptions::new()
+            .with_units(self.units.into())
+            .with_hex_offsets(self.hex)
+            .with_sample_rate(sample_rate);
+        for offsets in &self.offsets {
+            options = match offsets {
+                Offsets::Decimal => options.with_hex_offsets(false),
+                Offsets::Hex => options.with_hex_offsets(true),
+                Offsets::File => options.with_offset_base(OffsetBase::File),
+                Offsets::Riff => options.with_offset_base(OffsetBase::Riff),
+                Offsets::Payload => options.with_offset_base(OffsetBase::Payload),
+            };
+        }
+        match self.max_length {
+            Some(max) => options.with_max_string_length(max),
+            None => options,
+        }
+    }
+
+    /// Fit a value into the output width after `prefix`, trimmed or wrapped
+    /// as configured. Wrapped lines start with `prefix` too.
+    fn fit(&self, text: &str, prefix: &str) -> String {
+        let indent = u16::try_from(prefix.chars().count()).unwrap_or(u16::MAX);
+        if self.wrap {
+            wrap(text, self.width, prefix)
+        } else {
+            trim(text, self.width.saturating_sub(indent))
+        }
+    }
+}
+
 #[instrument]
 fn view(config: &ViewConfig) -> Result<()> {
+    if let Some(ReportFormat::Json) = config.report {
+        return view_report(config);
+    }
     for path in &config.wav_path {
+        if path == "-" {
+            println!("-: {}", view_stream(io::stdin().lock(), config)?);
+            continue;
+        }
         let path = PathBuf::from(path);
         if path.is_dir() {
             println!(
@@ -167,113 +969,386 @@ fn view(config: &ViewConfig) -> Result<()> {
         }
 
         print!("{}: ", path.to_string_lossy());
-        let file = File::open(path)?;
+        let file = File::open(&path)?;
         let file = BufReader::new(file);
 
         match config.format {
             Format::Line => {
-                println!("{}", view_line(file)?);
+                println!("{}", view_line(file, config.color.enabled())?);
             }
             Format::Summary => {
                 println!("{}", view_summary(file, config)?);
             }
             Format::Detailed => {
-                println!("{}", view_detailed(file)?);
+                println!("{}", view_detailed(file, config)?);
+            }
+            Format::Tree => {
+                println!("{}", view_tree(file, config)?);
             }
         }
+        if config.fingerprints {
+            let file = BufReader::new(File::open(&path)?);
+            println!("{}", view_fingerprints(file)?);
+        }
     }
     Ok(())
 }
 
+/// Print the errors and warnings of each file as a JSON report, fails if
+/// any file has errors.
 #[instrument]
-fn view_line(file: BufReader<File>) -> Result<String> {
-    let mut out = String::new();
-    let mut chunk_strings: Vec<String> = vec![];
-
-    let mut wave = wavrw::WaveFile::from_reader(file)?;
+fn view_report(config: &ViewConfig) -> Result<()> {
+    use wavrw::report::{Problem, ProblemReport};
 
-    for result in wave.iter_chunks() {
-        match result {
-            // special case smpl to show loop count
-            Ok(SizedChunkEnum::Smpl(chunk)) => {
-                chunk_strings.push(format!(
-                    "{}[{}]",
-                    chunk.name(),
-                    chunk.data.sample_loops.len()
-                ));
+    let mut reports = Vec::new();
+    for path in &config.wav_path {
+        let path_name = path.to_string_lossy();
+        let wave = File::open(path)
+            .map_err(wavrw::WaveFileError::from)
+            .and_then(|file| wavrw::WaveFile::from_reader(BufReader::new(file)));
+        reports.push(match wave {
+            Ok(mut wave) => {
+                let mut report = ProblemReport::from_wave(path_name, &mut wave);
+                if let Some(profile) = config.profile {
+                    let riff_id = wave.riff().id;
+                    let warnings = profile.check(riff_id, wave.chunks());
+                    report.warnings.extend(warnings.iter().map(Problem::from));
+                }
+                report
             }
-            // match on id() to catch all current and future LIST variants
+            Err(err) => ProblemReport::from_error(path_name, &err),
+        });
+    }
+    print!("{}", wavrw::report::problems_to_json(&reports));
+    let failed = reports.iter().filter(|r| !r.errors.is_empty()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} file(s) have errors");
+    }
+    Ok(())
+}
+
+/// Hashes of each chunk and the audio data, for finding duplicates.
+#[instrument]
+fn view_fingerprints(file: BufReader<File>) -> Result<String> {
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let fingerprints = wavrw::fingerprint::fingerprints(&mut wave)?;
+    let mut out = String::new();
+    writeln!(out, "fingerprints (xxh64):")?;
+    for chunk in &fingerprints.chunks {
+        writeln!(out, "    {chunk}")?;
+    }
+    if let Some(audio) = fingerprints.audio {
+        writeln!(out, "    {audio:016x} audio data")?;
+    }
+    Ok(out)
+}
+
+#[instrument]
+fn view_line(file: BufReader<File>, color: bool) -> Result<String> {
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    Ok(chunks_line(wave.iter_chunks(), color))
+}
+
+/// Single line list of chunks, for view --format line and list commands.
+fn chunks_line(
+    chunks: impl Iterator<Item = Result<SizedChunkEnum, wavrw::WaveFileError>>,
+    color: bool,
+) -> String {
+    let mut out = String::new();
+    let mut chunk_strings: Vec<String> = vec![];
+
+    for result in chunks {
+        match result {
+            // special case smpl to show loop count
+            Ok(SizedChunkEnum::Smpl(chunk)) => {
+                let name = Style::Metadata.paint(&chunk.name(), color);
+                chunk_strings.push(format!("{name}[{}]", chunk.data.sample_loops.len()));
+            }
+            // match on id() to catch all current and future LIST variants
             Ok(chunk) if chunk.id() == b"LIST" => {
-                chunk_strings.push(format!("{}[{}]", chunk.name(), chunk.summary()));
+                let name = Style::of(&chunk).paint(&chunk.name(), color);
+                chunk_strings.push(format!("{name}[{}]", chunk.summary()));
             }
             Ok(chunk) => {
-                chunk_strings.push(chunk.name());
+                chunk_strings.push(Style::of(&chunk).paint(&chunk.name(), color));
             }
             Err(_) => {
-                chunk_strings.push("ERROR".to_string());
+                chunk_strings.push(Style::Error.paint("ERROR", color));
             }
         }
     }
     out.push_str(&chunk_strings.iter().join(", "));
 
+    out
+}
+
+/// Duration and rates for the first line of view output, followed by any
+/// validation warnings.
+fn view_header<R>(
+    wave: &mut wavrw::WaveFile<R>,
+    fps: Option<u32>,
+    profile: Option<wavrw::profile::Profile>,
+) -> Result<String>
+where
+    R: io::Read + io::Seek + io::BufRead + std::fmt::Debug,
+{
+    let riff_id = wave.riff().id;
+    chunks_header(wave.chunks(), riff_id, fps, profile)
+}
+
+/// [`view_header`] for already parsed chunks.
+fn chunks_header(
+    chunks: &[SizedChunkEnum],
+    riff_id: FourCC,
+    fps: Option<u32>,
+    profile: Option<wavrw::profile::Profile>,
+) -> Result<String> {
+    let metadata = wavrw::WaveMetadata::new(chunks);
+    let mut out = String::new();
+    if let (Some(samples), Some(sample_rate)) =
+        (metadata.duration_samples(), metadata.sample_rate())
+    {
+        write!(
+            out,
+            "{} samples, {}",
+            samples,
+            sample_time(samples, sample_rate, fps)
+        )?;
+        if let Some(byte_rate) = metadata.byte_rate() {
+            write!(out, ", {byte_rate} bytes/sec")?;
+        }
+    }
+    writeln!(out)?;
+    for warning in wavrw::ResolvedWave::new(chunks).validate() {
+        writeln!(out, "    warning: {warning}")?;
+    }
+    if let Some(profile) = profile {
+        for warning in profile.check(riff_id, chunks) {
+            writeln!(out, "    {profile}: {warning}")?;
+        }
+    }
     Ok(out)
 }
 
 #[instrument]
 fn view_summary(file: BufReader<File>, config: &ViewConfig) -> Result<String> {
-    let mut out = "\n".to_string();
-    writeln!(out, "      offset id              size summary")?;
-
     let mut wave = wavrw::WaveFile::from_reader(file)?;
-    for result in wave.iter_chunks() {
-        match result {
-            Ok(chunk) => {
-                writeln!(
-                    out,
-                    "{:>12} {:9} {:10} {}",
-                    chunk.offset().map_or("???".to_string(), |v| v.to_string()),
-                    chunk.name(),
-                    chunk.size(),
-                    trim(&chunk.summary(), config.width.saturating_sub(29))
-                )?;
+    let mut out = view_header(&mut wave, config.fps, config.profile)?;
+    let samples_per_sec = samples_per_sec(&mut wave);
+    summary_table(&mut out, wave.iter_chunks(), config, samples_per_sec)?;
+    Ok(out)
+}
+
+/// Summary output from a stream which can't seek, like stdin. Only the line
+/// and summary formats are supported.
+#[instrument]
+fn view_stream<R: io::Read + std::fmt::Debug>(reader: R, config: &ViewConfig) -> Result<String> {
+    if matches!(config.format, Format::Detailed | Format::Tree) || config.fingerprints {
+        anyhow::bail!("only --format line and summary are supported when reading from stdin");
+    }
+    let mut chunks = wavrw::stream::metadata_chunks_streaming(reader)?;
+    let riff_id = chunks.riff().id;
+    let results: Vec<_> = chunks.by_ref().collect();
+    if config.format == Format::Line {
+        return Ok(chunks_line(results.into_iter(), config.color.enabled()));
+    }
+    let parsed: Vec<SizedChunkEnum> = results
+        .iter()
+        .filter_map(|r| r.as_ref().ok())
+        .cloned()
+        .collect();
+    let mut out = chunks_header(&parsed, riff_id, config.fps, config.profile)?;
+    let samples_per_sec = wavrw::WaveMetadata::new(&parsed).sample_rate().unwrap_or(0);
+    summary_table(&mut out, results.into_iter(), config, samples_per_sec)?;
+    Ok(out)
+}
+
+/// Column headers and one line per chunk, for view --format summary.
+fn summary_table(
+    out: &mut String,
+    results: impl Iterator<Item = Result<SizedChunkEnum, wavrw::WaveFileError>>,
+    config: &ViewConfig,
+    samples_per_sec: u32,
+) -> Result<()> {
+    let header = config.columns.iter().map(|c| {
+        let name = match c {
+            Column::Offset => "offset",
+            Column::Id => "id",
+            Column::Size => "size",
+            Column::Summary => "summary",
+        };
+        c.pad(name)
+    });
+    writeln!(out, "{}", header.format(" "))?;
+
+    let color = config.color.enabled();
+    let options = config.display_options(samples_per_sec);
+    for result in results {
+        let (offset, id, size, summary, style) = match result {
+            Ok(chunk) => (
+                options.chunk_offset(&chunk),
+                chunk.name(),
+                chunk.size().to_string(),
+                chunk.summary_with(&options),
+                Style::of(&chunk),
+            ),
+            Err(err) => (
+                "???".to_string(),
+                "ERROR".to_string(),
+                "".to_string(),
+                err.to_string(),
+                Style::Error,
+            ),
+        };
+        let mut line = String::new();
+        // line width without color escape codes
+        let mut used = 0;
+        for (i, column) in config.columns.iter().enumerate() {
+            if i > 0 {
+                line.push(' ');
+                used += 1;
             }
+            let cell = match column {
+                Column::Offset => column.pad(&offset),
+                Column::Id => column.pad(&id),
+                Column::Size => column.pad(&size),
+                Column::Summary => config.fit(&summary, &" ".repeat(used)),
+            };
+            used += cell.chars().count();
+            match (column, style) {
+                (Column::Id, _) | (Column::Summary, Style::Error) => {
+                    line.push_str(&style.paint(&cell, color));
+                }
+                _ => line.push_str(&cell),
+            }
+        }
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// Chunks as a tree below the RIFF header, with fields or `LIST` sub-chunks
+/// as children of each chunk.
+#[instrument]
+fn view_tree(file: BufReader<File>, config: &ViewConfig) -> Result<String> {
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let mut out = view_header(&mut wave, config.fps, config.profile)?;
+    writeln!(out, "RIFF-WAVE")?;
+    let color = config.color.enabled();
+
+    let samples_per_sec = samples_per_sec(&mut wave);
+    let options = config.display_options(samples_per_sec);
+    let labels = wave.metadata().cue_labels();
+    let results: Vec<_> = wave.iter_chunks().collect();
+    let results_len = results.len();
+    for (i, result) in results.into_iter().enumerate() {
+        let last = i + 1 == results_len;
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let mut chunk = match result {
+            Ok(chunk) => chunk,
             Err(err) => {
-                writeln!(
-                    out,
-                    "{:>12} {:9} {:10} {}",
-                    "???".to_string(),
-                    "ERROR".to_string(),
-                    "".to_string(),
-                    trim(&err.to_string(), config.width.saturating_sub(29))
-                )?;
+                let line = format!("{branch}ERROR {err}");
+                let line = trim(&line, config.width);
+                writeln!(out, "{}", Style::Error.paint(&line, color))?;
+                continue;
             }
         };
+        // plst segments refer to cue points, show their labels
+        if let SizedChunkEnum::Plst(plst) = &mut chunk {
+            plst.data.set_labels(&labels);
+        }
+        let name = chunk.name();
+        let line = format!(
+            "{} ({} bytes at {}) {}",
+            name,
+            chunk.size(),
+            options.chunk_offset(&chunk),
+            chunk.summary_with(&options)
+        );
+        let line = trim(&line, config.width.saturating_sub(4));
+        // color only the name, unless trimming cut it short
+        let line = match line.strip_prefix(name.as_str()) {
+            Some(rest) => format!("{}{rest}", Style::of(&chunk).paint(&name, color)),
+            None => line,
+        };
+        writeln!(out, "{branch}{line}")?;
+
+        let mut items: Vec<(String, String)> = match &chunk {
+            SizedChunkEnum::Unknown(unknown) if config.full => unknown.hexdump(None).collect(),
+            _ => chunk.items().collect(),
+        };
+        // time_reference is more useful as a time, needs the fmt sample rate
+        if let SizedChunkEnum::Bext(bext) = &chunk {
+            let time = sample_time(bext.data.time_reference, samples_per_sec, config.fps);
+            items.push(("time_reference (time)".to_string(), time.to_string()));
+        }
+        for (j, (key, value)) in items.iter().enumerate() {
+            let (twig, stem) = if j + 1 == items.len() {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            let item = config.fit(&format!("{key}: {value}"), &format!("{indent}{stem}"));
+            writeln!(out, "{indent}{twig}{item}")?;
+        }
     }
     Ok(out)
 }
 
 #[instrument]
-fn view_detailed(file: BufReader<File>) -> Result<String> {
-    let mut out = "\n".to_string();
+fn view_detailed(file: BufReader<File>, config: &ViewConfig) -> Result<String> {
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let mut out = view_header(&mut wave, config.fps, config.profile)?;
     writeln!(out, "      offset id              size summary")?;
 
-    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let samples_per_sec = samples_per_sec(&mut wave);
+    let options = config.display_options(samples_per_sec);
+    let labels = wave.metadata().cue_labels();
+    let color = config.color.enabled();
     for result in wave.iter_chunks() {
         match result {
-            Ok(chunk) => {
+            Ok(mut chunk) => {
+                // plst segments refer to cue points, show their labels
+                if let SizedChunkEnum::Plst(plst) = &mut chunk {
+                    plst.data.set_labels(&labels);
+                }
                 writeln!(
                     out,
-                    "{:>12} {:9} {:10} {}",
-                    chunk.offset().map_or("???".to_string(), |v| v.to_string()),
-                    chunk.name(),
+                    "{:>12} {} {:10} {}",
+                    options.chunk_offset(&chunk),
+                    Style::of(&chunk).paint(&format!("{:9}", chunk.name()), color),
                     chunk.size(),
                     chunk.item_summary_header()
                 )?;
+                let items = match &chunk {
+                    SizedChunkEnum::Unknown(unknown) if config.full => {
+                        Box::new(unknown.hexdump(None))
+                    }
+                    _ => chunk.items(),
+                };
                 let mut had_items = false;
-                for (key, value) in chunk.items() {
+                for (key, value) in items {
                     had_items = true;
+                    let value = if config.truncate || config.wrap {
+                        config.fit(&value, &format!("             |{:23}   ", ""))
+                    } else {
+                        value
+                    };
                     writeln!(out, "             |{key:>23} : {value}")?;
                 }
+                // time_reference is more useful as a time, needs the fmt sample rate
+                if let SizedChunkEnum::Bext(bext) = &chunk {
+                    let time = sample_time(bext.data.time_reference, samples_per_sec, config.fps);
+                    writeln!(
+                        out,
+                        "             |{:>23} : {time}",
+                        "time_reference (time)"
+                    )?;
+                }
                 if had_items {
                     writeln!(out, "             --------------------------------------")?;
                 }
@@ -281,11 +1356,11 @@ fn view_detailed(file: BufReader<File>) -> Result<String> {
             Err(err) => {
                 writeln!(
                     out,
-                    "{:>12} {:9} {:10} {}",
+                    "{:>12} {} {:10} {}",
                     "???".to_string(),
-                    "ERROR".to_string(),
+                    Style::Error.paint(&format!("{:9}", "ERROR"), color),
                     "".to_string(),
-                    err,
+                    Style::Error.paint(&err.to_string(), color),
                 )?;
             }
         }
@@ -295,7 +1370,19 @@ fn view_detailed(file: BufReader<File>) -> Result<String> {
 
 #[instrument]
 fn list(config: &ListConfig) -> Result<()> {
-    walk_paths(&config.path.clone().into(), config)?;
+    // files are listed in batches between directories, in the order given
+    let mut files = Vec::new();
+    for path in &config.path_options.paths {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            list_files(&files, config);
+            files.clear();
+            walk_paths(&path, config)?;
+        } else {
+            files.push(path);
+        }
+    }
+    list_files(&files, config);
     Ok(())
 }
 
@@ -305,36 +1392,1281 @@ fn walk_paths(base_path: &PathBuf, config: &ListConfig) -> Result<()> {
         .map(|res| res.map(|e| e.path()))
         .collect::<Result<Vec<_>, io::Error>>()?;
     paths.sort_unstable();
+    // files are parsed in parallel, in batches between subdirectories
+    let mut batch = Vec::new();
     for path in paths {
-        if path.is_dir() & config.recurse {
+        if path.is_dir() & config.path_options.recurse {
+            list_files(&batch, config);
+            batch.clear();
             eprintln!("directory: {}", path.to_string_lossy());
             walk_paths(&path, config)?;
         } else if let Some(ext) = path.extension() {
-            // config.ext entries are assumed to have been converted to lowercase already.
-            let ext = ext.to_ascii_lowercase();
-            if !config.ext.contains(&ext) {
+            if config
+                .path_options
+                .ext
+                .iter()
+                .any(|e| e.eq_ignore_ascii_case(ext))
+            {
+                batch.push(path);
+            }
+        }
+    }
+    list_files(&batch, config);
+    Ok(())
+}
+
+/// A file in `list` output, with the values it can be sorted and grouped by.
+struct ListEntry {
+    result: wavrw::scan::ScanResult,
+    size: u64,
+    duration: Option<f64>,
+    sample_rate: Option<u32>,
+    channels: Option<u16>,
+    originator: Option<String>,
+}
+
+impl ListEntry {
+    fn new(result: wavrw::scan::ScanResult) -> Self {
+        let size = fs::metadata(&result.path).map_or(0, |m| m.len());
+        let parsed: Vec<_> = match &result.chunks {
+            Ok(chunks) => chunks.iter().flatten().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+        let metadata = wavrw::WaveMetadata::new(&parsed);
+        let originator = metadata
+            .bext()
+            .map(|bext| bext.originator.to_string().trim().to_string())
+            .filter(|originator| !originator.is_empty());
+        ListEntry {
+            size,
+            duration: metadata.duration_seconds(),
+            sample_rate: metadata.sample_rate(),
+            channels: metadata.channels(),
+            originator,
+            result,
+        }
+    }
+
+    /// Number to order the groups by, if any, and group header text.
+    fn group(&self, key: GroupKey) -> (Option<u32>, String) {
+        let value = match key {
+            GroupKey::Samplerate => self.sample_rate,
+            GroupKey::Channels => self.channels.map(u32::from),
+            GroupKey::Originator => None,
+        };
+        let header = match (key, value, &self.originator) {
+            (GroupKey::Samplerate, Some(rate), _) => format!("{rate} Hz"),
+            (GroupKey::Channels, Some(channels), _) => format!("{channels} chan"),
+            (GroupKey::Samplerate | GroupKey::Channels, None, _) => "no fmt".to_string(),
+            (GroupKey::Originator, _, Some(originator)) => originator.clone(),
+            (GroupKey::Originator, _, None) => "no originator".to_string(),
+        };
+        (value, header)
+    }
+}
+
+fn list_files(paths: &[PathBuf], config: &ListConfig) {
+    let mut entries: Vec<_> = scan_paths(paths)
+        .into_iter()
+        .filter(|result| {
+            let Some(filter) = &config.filter else {
+                return true;
+            };
+            let Ok(chunks) = &result.chunks else {
+                return false;
+            };
+            let parsed: Vec<_> = chunks.iter().flatten().cloned().collect();
+            filter.matches(&parsed)
+        })
+        .map(ListEntry::new)
+        .collect();
+    // stable sorts, paths are already in order
+    match config.sort {
+        SortKey::Path => (),
+        SortKey::Size => entries.sort_by_key(|e| e.size),
+        SortKey::Duration => {
+            entries.sort_by(|a, b| {
+                a.duration
+                    .partial_cmp(&b.duration)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        SortKey::Samplerate => entries.sort_by_key(|e| e.sample_rate),
+    }
+
+    let Some(key) = config.group_by else {
+        for entry in entries {
+            list_entry(entry, "", config);
+        }
+        return;
+    };
+    let mut groups: BTreeMap<_, Vec<ListEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups.entry(entry.group(key)).or_default().push(entry);
+    }
+    for ((_, header), entries) in groups {
+        let count = entries.len();
+        let files = if count == 1 { "file" } else { "files" };
+        println!("{header}: {count} {files}");
+        for entry in entries {
+            list_entry(entry, "    ", config);
+        }
+    }
+}
+
+/// Print the chunks line of one file of `list`.
+fn list_entry(entry: ListEntry, indent: &str, config: &ListConfig) {
+    let color = config.color.enabled();
+    let path_name = entry.result.path.to_string_lossy();
+    match entry.result.chunks {
+        Ok(chunks) => println!(
+            "{indent}{path_name}: {}",
+            chunks_line(chunks.into_iter(), color)
+        ),
+        Err(err) => println!(
+            "{indent}{path_name}: {}",
+            Style::Error.paint(&format!("ERROR: {err}"), color)
+        ),
+    }
+}
+
+#[instrument]
+fn ls(config: &LsConfig) -> Result<()> {
+    let paths = expand_paths(&config.path_options)?;
+    for path in paths {
+        let path_name = path.to_string_lossy();
+        let overview = File::open(&path)
+            .map_err(wavrw::WaveFileError::from)
+            .and_then(|file| wavrw::WaveFile::from_reader(BufReader::new(file)))
+            .and_then(|mut wave| wave.overview());
+        match overview {
+            Ok(overview) if config.chunks => println!(
+                "{path_name}: {overview} [{}]",
+                overview
+                    .chunk_ids
+                    .iter()
+                    .map(|id| id.to_string().trim_end().to_string())
+                    .join(", ")
+            ),
+            Ok(overview) => println!("{path_name}: {overview}"),
+            Err(err) => println!("{path_name}: ERROR: {err}"),
+        }
+    }
+    Ok(())
+}
+
+#[instrument]
+fn apply(config: &ApplyConfig) -> Result<()> {
+    let text = fs::read_to_string(&config.template)?;
+    let template: wavrw::template::Template = text.parse()?;
+    let paths = expand_paths(&config.path_options)?;
+
+    let mut failed = 0;
+    for (index, path) in paths.iter().enumerate() {
+        let path_name = path.to_string_lossy();
+        match apply_file(&template, path, index + 1, &config.write_options) {
+            Ok(edited) => print_edited(&edited, "applied template"),
+            Err(err) => {
+                failed += 1;
+                println!("{path_name}: ERROR: {err}");
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} file(s) could not be updated");
+    }
+    Ok(())
+}
+
+/// Apply `template` to the file at `path`, number `index` of the batch,
+/// replacing it when done.
+fn apply_file(
+    template: &wavrw::template::Template,
+    path: &Path,
+    index: usize,
+    options: &WriteOptions,
+) -> Result<Edited> {
+    let placeholders = wavrw::template::Placeholders::for_file(path, index);
+    replace_file(path, options, |reader, writer| {
+        let mut wave = wavrw::WaveFile::from_reader(reader)?;
+        Ok(template.apply(&mut wave, writer, &placeholders)?)
+    })
+}
+
+#[instrument]
+fn export(config: &ExportConfig) -> Result<()> {
+    let file = BufReader::new(File::open(&config.wav_path)?);
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let sidecar = wavrw::sidecar::Sidecar::from_wave(&mut wave)?;
+    let document = match config.format {
+        SidecarFormat::Json => sidecar.to_json(),
+        SidecarFormat::Xml => sidecar.to_xml(),
+    };
+    match &config.output {
+        Some(output) => {
+            // never overwrite existing files
+            let mut file = File::options().write(true).create_new(true).open(output)?;
+            io::Write::write_all(&mut file, document.as_bytes())?;
+            println!(
+                "{}: wrote {} chunks",
+                output.to_string_lossy(),
+                sidecar.chunks.len()
+            );
+        }
+        None => print!("{document}"),
+    }
+    Ok(())
+}
+
+#[instrument]
+fn export_core(config: &ExportCoreConfig) -> Result<()> {
+    let paths = expand_paths(&config.path_options)?;
+    let mut rows = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let mut wave = wavrw::WaveFile::from_reader(BufReader::new(File::open(path)?))?;
+        rows.push(wavrw::metaedit::CoreRow::from_wave(
+            &path.to_string_lossy(),
+            &mut wave,
+        )?);
+    }
+    let document = wavrw::metaedit::write_core_csv(&rows);
+    match &config.output {
+        Some(output) => {
+            // never overwrite existing files
+            let mut file = File::options().write(true).create_new(true).open(output)?;
+            io::Write::write_all(&mut file, document.as_bytes())?;
+            println!("{}: wrote {} rows", output.to_string_lossy(), rows.len());
+        }
+        None => print!("{document}"),
+    }
+    Ok(())
+}
+
+#[instrument]
+fn import_core(config: &ImportCoreConfig) -> Result<()> {
+    let rows = wavrw::metaedit::read_core_csv(&fs::read_to_string(&config.csv_path)?)?;
+    let mut failed = 0;
+    for row in &rows {
+        match import_core_row(row, config.text_policy, &config.write_options) {
+            Ok((edited, warnings)) => {
+                print_edited(&edited, "updated");
+                for warning in warnings {
+                    println!("{}: WARNING: {warning}", row.file_name);
+                }
+            }
+            Err(err) => {
+                failed += 1;
+                println!("{}: ERROR: {err}", row.file_name);
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} file(s) could not be updated");
+    }
+    Ok(())
+}
+
+/// Apply `row` to the file it names, replacing it when done. Also returns
+/// the fields with non-ASCII text.
+fn import_core_row(
+    row: &wavrw::metaedit::CoreRow,
+    policy: TextPolicy,
+    options: &WriteOptions,
+) -> Result<(Edited, Vec<wavrw::Warning>)> {
+    let mut sanitizer = wavrw::sanitize::Sanitizer::new(policy.into());
+    let edited = replace_file(Path::new(&row.file_name), options, |reader, writer| {
+        let mut wave = wavrw::WaveFile::from_reader(reader)?;
+        Ok(row.apply_sanitized(&mut wave, writer, &mut sanitizer)?)
+    })?;
+    Ok((edited, sanitizer.into()))
+}
+
+/// Path argument for stdin or stdout.
+const STDIO_PATH: &str = "-";
+
+/// Contents of the file at `path`, or of stdin for `-`.
+fn read_path(path: &OsString) -> Result<Vec<u8>> {
+    if path != STDIO_PATH {
+        return Ok(fs::read(path)?);
+    }
+    let mut bytes = Vec::new();
+    io::Read::read_to_end(&mut io::stdin().lock(), &mut bytes)?;
+    Ok(bytes)
+}
+
+/// True if an edit command reads from stdin or writes to stdout.
+fn uses_stdio(wav_path: &OsString, output: Option<&OsString>) -> bool {
+    wav_path == STDIO_PATH || output.is_some_and(|o| o == STDIO_PATH)
+}
+
+/// Run `edit` for a command reading from stdin or writing to stdout, see
+/// [`uses_stdio`]. Reading from stdin writes to stdout unless `output` is
+/// set. Editing needs to seek in the input and output, so both are buffered
+/// in memory.
+fn edit_stdio(
+    wav_path: &OsString,
+    output: Option<&OsString>,
+    options: &WriteOptions,
+    edit: impl FnOnce(io::Cursor<Vec<u8>>, &mut io::Cursor<Vec<u8>>) -> Result<()>,
+) -> Result<Edited> {
+    let input = read_path(wav_path)?;
+    let original = options.dry_run.then(|| input.clone());
+    let mut result = io::Cursor::new(Vec::new());
+    edit(io::Cursor::new(input), &mut result)?;
+    if let Some(original) = original {
+        return Ok(Edited::DryRun {
+            name: wav_path.to_string_lossy().into_owned(),
+            changes: planned_changes(io::Cursor::new(original), result.into_inner())?,
+        });
+    }
+    match output.filter(|o| *o != STDIO_PATH) {
+        Some(output) => {
+            // never overwrite an existing file
+            let mut file = File::options().write(true).create_new(true).open(output)?;
+            if let Err(err) = io::Write::write_all(&mut file, result.get_ref()) {
+                drop(file);
+                fs::remove_file(output)?;
+                return Err(err.into());
+            }
+            Ok(Edited::Written(output.to_string_lossy().into_owned()))
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            io::Write::write_all(&mut stdout, result.get_ref())?;
+            io::Write::flush(&mut stdout)?;
+            Ok(Edited::Written(STDIO_PATH.to_string()))
+        }
+    }
+}
+
+/// Outcome of an edit command.
+#[derive(Debug)]
+enum Edited {
+    /// The result was written to this path, `-` for stdout.
+    Written(String),
+    /// Nothing was written, with `--dry-run`. Lines describing how the
+    /// result would differ from the file `name`.
+    DryRun { name: String, changes: Vec<String> },
+}
+
+/// Print the outcome of an edit command, `message` once written, to stderr
+/// when the file was written to stdout.
+fn print_edited(edited: &Edited, message: &str) {
+    match edited {
+        Edited::Written(output) if output == STDIO_PATH => eprintln!("{output}: {message}"),
+        Edited::Written(output) => println!("{output}: {message}"),
+        Edited::DryRun { name, changes } => {
+            for change in changes {
+                println!("{name}: {change}");
+            }
+        }
+    }
+}
+
+/// Writer of an edit, a file or memory with `--dry-run`.
+trait WriteSeek: io::Write + io::Seek {}
+
+impl<T: io::Write + io::Seek> WriteSeek for T {}
+
+/// `path` with `suffix` appended to the file name. Ex: `a.wav.bak`
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Where `--backup` keeps the original of `path`. Existing backups are never
+/// overwritten, an error if one exists.
+fn backup_path(path: &Path) -> Result<PathBuf> {
+    let backup = with_suffix(path, ".bak");
+    if backup.exists() {
+        anyhow::bail!("backup {} already exists", backup.to_string_lossy());
+    }
+    Ok(backup)
+}
+
+/// Replace the file at `path` with the output of `edit`, according to
+/// `options`.
+///
+/// `edit` writes to a temporary file next to `path`, which then replaces
+/// it, so a failed edit leaves the original untouched. With `--backup` the
+/// original is kept as `<path>.bak`. With `--dry-run` nothing is written,
+/// see [`dry_run_file`].
+fn replace_file(
+    path: &Path,
+    options: &WriteOptions,
+    edit: impl FnOnce(BufReader<File>, &mut dyn WriteSeek) -> Result<()>,
+) -> Result<Edited> {
+    if options.dry_run {
+        return dry_run_file(path, edit);
+    }
+    let backup = options.backup().then(|| backup_path(path)).transpose()?;
+    let tmp = with_suffix(path, ".wavrw-tmp");
+
+    let reader = BufReader::new(File::open(path)?);
+    let mut writer = BufWriter::new(File::options().write(true).create_new(true).open(&tmp)?);
+    let result = edit(reader, &mut writer).and_then(|()| Ok(io::Write::flush(&mut writer)?));
+    drop(writer);
+    if let Err(err) = result {
+        fs::remove_file(&tmp)?;
+        return Err(err);
+    }
+    if let Some(backup) = backup {
+        fs::rename(path, backup)?;
+    }
+    fs::rename(&tmp, path)?;
+    Ok(Edited::Written(path.to_string_lossy().into_owned()))
+}
+
+/// Run `edit` on the file at `path` for `--dry-run`, writing the result to
+/// memory and comparing it to the original.
+fn dry_run_file(
+    path: &Path,
+    edit: impl FnOnce(BufReader<File>, &mut dyn WriteSeek) -> Result<()>,
+) -> Result<Edited> {
+    let mut result = io::Cursor::new(Vec::new());
+    edit(BufReader::new(File::open(path)?), &mut result)?;
+    Ok(Edited::DryRun {
+        name: path.to_string_lossy().into_owned(),
+        changes: planned_changes(BufReader::new(File::open(path)?), result.into_inner())?,
+    })
+}
+
+/// Lines describing the chunks added, removed, resized or modified from the
+/// WAV data in `original` to `result`, and the change of the file size.
+fn planned_changes<R>(mut original: R, result: Vec<u8>) -> Result<Vec<String>>
+where
+    R: io::Read + io::Seek + std::fmt::Debug + io::BufRead,
+{
+    let old_size = io::Seek::seek(&mut original, io::SeekFrom::End(0))?;
+    io::Seek::rewind(&mut original)?;
+    let new_size = u64::try_from(result.len())?;
+    let mut before = wavrw::WaveFile::from_reader(original)?;
+    let mut after = wavrw::WaveFile::from_reader(io::Cursor::new(result))?;
+
+    let mut lines: Vec<String> = wavrw::edit::chunk_changes(&mut before, &mut after)?
+        .iter()
+        .map(|change| format!("would {change}"))
+        .collect();
+    if lines.is_empty() {
+        lines.push("would not change any chunks".to_string());
+    }
+    if new_size == old_size {
+        lines.push(format!("file size unchanged, {new_size} bytes"));
+    } else {
+        let delta = i128::from(new_size) - i128::from(old_size);
+        lines.push(format!(
+            "file size {old_size} -> {new_size} bytes ({delta:+})"
+        ));
+    }
+    Ok(lines)
+}
+
+#[instrument]
+fn import(config: &ImportConfig) -> Result<()> {
+    if config.sidecar_path == STDIO_PATH && config.wav_path == STDIO_PATH {
+        anyhow::bail!("<SIDECAR_PATH> and <WAV_PATH> can't both be read from stdin");
+    }
+    let sidecar: wavrw::sidecar::Sidecar =
+        String::from_utf8(read_path(&config.sidecar_path)?)?.parse()?;
+    let message = format!("wrote {} chunks", sidecar.chunks.len());
+    if uses_stdio(&config.wav_path, config.output.as_ref()) {
+        let edited = edit_stdio(
+            &config.wav_path,
+            config.output.as_ref(),
+            &config.write_options,
+            |input, output| {
+                let mut wave = wavrw::WaveFile::from_reader(input)?;
+                Ok(sidecar.apply(&mut wave, output)?)
+            },
+        )?;
+        print_edited(&edited, &message);
+        return Ok(());
+    }
+    let wav_path = PathBuf::from(&config.wav_path);
+    let edit = |reader, writer: &mut dyn WriteSeek| {
+        let mut wave = wavrw::WaveFile::from_reader(reader)?;
+        Ok(sidecar.apply(&mut wave, writer)?)
+    };
+    let edited = match &config.output {
+        None => replace_file(&wav_path, &config.write_options, edit)?,
+        Some(_) if config.write_options.dry_run => dry_run_file(&wav_path, edit)?,
+        Some(output) => {
+            let reader = BufReader::new(File::open(&wav_path)?);
+            let mut writer =
+                BufWriter::new(File::options().write(true).create_new(true).open(output)?);
+            if let Err(err) = edit(reader, &mut writer) {
+                drop(writer);
+                fs::remove_file(output)?;
+                return Err(err);
+            }
+            Edited::Written(output.to_string_lossy().into_owned())
+        }
+    };
+    print_edited(&edited, &message);
+    Ok(())
+}
+
+#[instrument]
+fn inject(config: &InjectConfig) -> Result<()> {
+    if config.payload_path == STDIO_PATH && config.wav_path == STDIO_PATH {
+        anyhow::bail!("<PAYLOAD_PATH> and <WAV_PATH> can't both be read from stdin");
+    }
+    let payload = read_path(&config.payload_path)?;
+    let message = format!("wrote {} chunk, {} bytes", config.chunk, payload.len());
+    if uses_stdio(&config.wav_path, config.output.as_ref()) {
+        let edited = edit_stdio(
+            &config.wav_path,
+            config.output.as_ref(),
+            &config.write_options,
+            |input, output| {
+                Ok(wavrw::edit::inject_chunk(
+                    input,
+                    output,
+                    config.chunk,
+                    &payload,
+                )?)
+            },
+        )?;
+        print_edited(&edited, &message);
+        return Ok(());
+    }
+    let wav_path = PathBuf::from(&config.wav_path);
+    if config.output.is_none() {
+        // use filler chunks if possible, to avoid rewriting large files
+        if let Some(edited) =
+            inject_in_place(&wav_path, config.chunk, &payload, &config.write_options)?
+        {
+            let message = format!(
+                "wrote {} chunk in place, {} bytes",
+                config.chunk,
+                payload.len()
+            );
+            print_edited(&edited, &message);
+            return Ok(());
+        }
+    }
+    let edit = |reader, writer: &mut dyn WriteSeek| {
+        Ok(wavrw::edit::inject_chunk(
+            reader,
+            writer,
+            config.chunk,
+            &payload,
+        )?)
+    };
+    let edited = match &config.output {
+        None => replace_file(&wav_path, &config.write_options, edit)?,
+        Some(_) if config.write_options.dry_run => dry_run_file(&wav_path, edit)?,
+        Some(output) => {
+            if same_file(&wav_path, Path::new(output)) {
+                anyhow::bail!(
+                    "--output is the same file as <WAV_PATH>, leave out --output to modify it in place"
+                );
+            }
+            let reader = BufReader::new(File::open(&wav_path)?);
+            // never truncate an existing file, it may be the input under another name
+            let mut writer =
+                BufWriter::new(File::options().write(true).create_new(true).open(output)?);
+            if let Err(err) = edit(reader, &mut writer) {
+                drop(writer);
+                fs::remove_file(output)?;
+                return Err(err);
+            }
+            Edited::Written(output.to_string_lossy().into_owned())
+        }
+    };
+    print_edited(&edited, &message);
+    Ok(())
+}
+
+/// Insert or replace chunk `id` of the file at `path` using filler chunks,
+/// see [`wavrw::edit::inject_chunk_in_place`]. `None` without writing
+/// anything if there isn't enough filler space.
+///
+/// With `--backup` the original is copied to `<path>.bak` first, as the file
+/// is modified directly.
+fn inject_in_place(
+    path: &Path,
+    id: FourCC,
+    payload: &[u8],
+    options: &WriteOptions,
+) -> Result<Option<Edited>> {
+    if options.dry_run {
+        let original = fs::read(path)?;
+        let mut result = io::Cursor::new(original.clone());
+        if !wavrw::edit::inject_chunk_in_place(&mut result, id, payload)? {
+            return Ok(None);
+        }
+        return Ok(Some(Edited::DryRun {
+            name: path.to_string_lossy().into_owned(),
+            changes: planned_changes(io::Cursor::new(original), result.into_inner())?,
+        }));
+    }
+    let backup = options.backup().then(|| backup_path(path)).transpose()?;
+    if let Some(backup) = &backup {
+        fs::copy(path, backup)?;
+    }
+    let mut file = File::options().read(true).write(true).open(path)?;
+    if !wavrw::edit::inject_chunk_in_place(&mut file, id, payload)? {
+        if let Some(backup) = &backup {
+            fs::remove_file(backup)?;
+        }
+        return Ok(None);
+    }
+    Ok(Some(Edited::Written(path.to_string_lossy().into_owned())))
+}
+
+/// Sample rate from the first `fmt ` chunk, 0 if not found.
+fn samples_per_sec<R>(wave: &mut wavrw::WaveFile<R>) -> u32
+where
+    R: io::Read + io::Seek + io::BufRead + std::fmt::Debug,
+{
+    wave.iter_chunks()
+        .find_map(|c| match c {
+            Ok(SizedChunkEnum::Fmt(fmt)) => Some(fmt.data.samples_per_sec()),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+fn sample_time(samples: u64, samples_per_sec: u32, fps: Option<u32>) -> SampleTime {
+    let time = SampleTime::new(samples, samples_per_sec);
+    match fps {
+        Some(fps) => time.with_frame_rate(fps),
+        None => time,
+    }
+}
+
+#[instrument]
+fn markers(config: &MarkersConfig) -> Result<()> {
+    for path in &config.wav_path {
+        let path = PathBuf::from(path);
+        println!("{}:", path.to_string_lossy());
+        let file = BufReader::new(File::open(path)?);
+        let mut wave = wavrw::WaveFile::from_reader(file)?;
+
+        let samples_per_sec = samples_per_sec(&mut wave);
+        // overcranked or pulled down recordings: also show real time
+        let real_time_rate = wave.metadata().real_time_sample_rate();
+        if let Some(rate) = real_time_rate {
+            println!("    recorded at {rate} Hz, plays at {samples_per_sec} Hz");
+        }
+
+        // timecode with frame rate is wider than hh:mm:ss.mmm
+        let width = if config.fps.is_some() { 20 } else { 12 };
+        let real_time_header = match real_time_rate {
+            Some(_) => format!(" {:width$}", "real time"),
+            None => String::new(),
+        };
+        println!(
+            "    position {:width$}{real_time_header} duration source label",
+            "time"
+        );
+        for marker in wavrw::markers(&mut wave) {
+            let label = [&marker.label, &marker.text, &marker.note]
+                .into_iter()
+                .flatten()
+                .join(" / ");
+            let time = sample_time(marker.position.into(), samples_per_sec, config.fps);
+            let real_time = real_time_rate.map(|rate| time.at_sample_rate(rate));
+            // orphaned annotations have no position
+            let (position, time, real_time) = if marker.is_orphaned() {
+                let real_time = real_time.map(|_| "-".to_string());
+                ("-".to_string(), "-".to_string(), real_time)
+            } else {
+                let real_time = real_time.map(|t| t.to_string());
+                (marker.position.to_string(), time.to_string(), real_time)
+            };
+            let real_time = match real_time {
+                Some(real_time) => format!(" {real_time:width$}"),
+                None => String::new(),
+            };
+            println!(
+                "{:>12} {:width$}{real_time} {:>8} {:6} {}",
+                position,
+                time,
+                marker.duration.map_or(String::new(), |d| d.to_string()),
+                marker.source,
+                label
+            );
+        }
+        for warning in wave.resolve().dangling_references() {
+            println!("    warning: {warning}");
+        }
+    }
+    Ok(())
+}
+
+fn loudness(config: &LoudnessConfig) -> Result<()> {
+    for path in &config.wav_path {
+        let path = PathBuf::from(path);
+        let file = BufReader::new(File::open(&path)?);
+        let mut wave = wavrw::WaveFile::from_reader(file)?;
+        let loudness = wavrw::loudness::measure(&mut wave)?;
+        println!("{}:", path.to_string_lossy());
+        println!("    integrated:     {:6.1} LUFS", loudness.integrated);
+        println!("    range:          {:6.1} LU", loudness.range);
+        println!("    true peak:      {:6.1} dBTP", loudness.true_peak);
+        println!("    max momentary:  {:6.1} LUFS", loudness.max_momentary);
+        println!("    max short-term: {:6.1} LUFS", loudness.max_short_term);
+    }
+    Ok(())
+}
+
+fn merge(config: &MergeConfig) -> Result<()> {
+    let mut inputs = Vec::with_capacity(config.wav_path.len());
+    for path in &config.wav_path {
+        let file = BufReader::new(File::open(path)?);
+        inputs.push(wavrw::WaveFile::from_reader(file)?);
+    }
+    let out_path = PathBuf::from(&config.output);
+    // never overwrite existing files
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(&out_path)?;
+    if let Err(err) = wavrw::merge::merge(&mut inputs, BufWriter::new(file)) {
+        fs::remove_file(&out_path)?;
+        return Err(err.into());
+    }
+    println!("{}", out_path.to_string_lossy());
+    Ok(())
+}
+
+fn split(config: &SplitConfig) -> Result<()> {
+    let wav_path = PathBuf::from(&config.wav_path);
+    let file = BufReader::new(File::open(&wav_path)?);
+    let mut wave = wavrw::WaveFile::from_reader(file)?;
+    let count = wave.metadata().channels().unwrap_or(0);
+    if let Some(channel) = config.channels.iter().find(|c| **c > count) {
+        anyhow::bail!(
+            "{}: channel {channel} out of range, the file has {count} channel(s)",
+            wav_path.to_string_lossy()
+        );
+    }
+    let channels: Vec<usize> = if config.channels.is_empty() {
+        (0..usize::from(count)).collect()
+    } else {
+        config
+            .channels
+            .iter()
+            .map(|c| usize::from(*c) - 1)
+            .collect()
+    };
+
+    let paths: Vec<PathBuf> = match &config.output {
+        Some(output) => vec![PathBuf::from(output)],
+        None => {
+            let dir = match &config.dir {
+                Some(dir) => PathBuf::from(dir),
+                None => wav_path.parent().map(PathBuf::from).unwrap_or_default(),
+            };
+            let stem = wav_path.file_stem().unwrap_or_default().to_string_lossy();
+            channels
+                .iter()
+                .map(|c| dir.join(format!("{stem}_{}.wav", c + 1)))
+                .collect()
+        }
+    };
+    // never overwrite existing files
+    let mut outputs = Vec::with_capacity(paths.len());
+    for (index, path) in paths.iter().enumerate() {
+        let selected = match &config.output {
+            Some(_) => channels.clone(),
+            None => vec![channels[index]],
+        };
+        match File::options().write(true).create_new(true).open(path) {
+            Ok(file) => outputs.push((selected, BufWriter::new(file))),
+            Err(err) => {
+                for path in &paths[..index] {
+                    fs::remove_file(path)?;
+                }
+                return Err(anyhow::anyhow!("{}: {err}", path.to_string_lossy()));
+            }
+        }
+    }
+
+    if let Err(err) = wavrw::split::split(&mut wave, &mut outputs) {
+        drop(outputs);
+        for path in &paths {
+            fs::remove_file(path)?;
+        }
+        return Err(err.into());
+    }
+    for path in &paths {
+        println!("{}", path.to_string_lossy());
+    }
+    Ok(())
+}
+
+fn compare(config: &CompareConfig) -> Result<()> {
+    let read_chunks = |path: &OsString| -> Result<Vec<SizedChunkEnum>> {
+        let file = BufReader::new(File::open(path)?);
+        let mut wave = wavrw::WaveFile::from_reader(file)?;
+        Ok(wave.iter_chunks().collect::<Result<_, _>>()?)
+    };
+    let a = read_chunks(&config.a)?;
+    let b = read_chunks(&config.b)?;
+    // the nth chunk of a name in one file is compared to the nth in the other
+    let nth = |chunks: &[SizedChunkEnum], index: usize| {
+        chunks[..index]
+            .iter()
+            .filter(|c| c.name() == chunks[index].name())
+            .count()
+    };
+    fn find<'a>(chunks: &'a [SizedChunkEnum], name: &str, n: usize) -> Option<&'a SizedChunkEnum> {
+        chunks.iter().filter(|c| c.name() == name).nth(n)
+    }
+
+    let mut differences = 0;
+    for (index, chunk) in a.iter().enumerate() {
+        let name = chunk.name();
+        let Some(other) = find(&b, &name, nth(&a, index)) else {
+            differences += 1;
+            println!("{name}: only in A");
+            continue;
+        };
+        for change in wavrw::diff::diff(chunk, other) {
+            differences += 1;
+            println!("{name}: {change}");
+        }
+    }
+    for (index, chunk) in b.iter().enumerate() {
+        let name = chunk.name();
+        if find(&a, &name, nth(&b, index)).is_none() {
+            differences += 1;
+            println!("{name}: only in B");
+        }
+    }
+    if differences == 0 {
+        println!("no differences");
+    }
+    Ok(())
+}
+
+fn roundtrip(config: &RoundtripConfig) -> Result<()> {
+    let mut failed = 0;
+    for path in &config.wav_path {
+        let path = PathBuf::from(path);
+        let file = BufReader::new(File::open(&path)?);
+        let mut wave = wavrw::WaveFile::from_reader(file)?;
+        let differences = wavrw::roundtrip::verify(&mut wave)?;
+        if differences.is_empty() {
+            println!("{}: ok", path.to_string_lossy());
+        } else {
+            failed += 1;
+            println!("{}:", path.to_string_lossy());
+            for difference in differences {
+                println!("    {difference}");
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} file(s) do not write back unchanged");
+    }
+    Ok(())
+}
+
+#[instrument]
+fn stats(config: &StatsConfig) -> Result<()> {
+    let paths = expand_paths(&config.path_options)?;
+
+    let mut total = wavrw::stats::SizeStats::default();
+    for result in scan_paths(&paths) {
+        let path_name = result.path.to_string_lossy();
+        let chunks = match result.chunks {
+            Ok(chunks) => chunks,
+            Err(err) => {
+                println!("{path_name}: ERROR: {err}");
                 continue;
             }
+        };
+        let mut errors = 0;
+        let chunks: Vec<_> = chunks
+            .into_iter()
+            .filter_map(|c| c.inspect_err(|_| errors += 1).ok())
+            .collect();
+        if config.filter.as_ref().is_some_and(|f| !f.matches(&chunks)) {
+            continue;
+        }
+        let file_stats = wavrw::stats::SizeStats::from_chunks(&chunks);
+        if !config.aggregate {
+            println!("{path_name}:");
+            if errors > 0 {
+                println!("    {errors} chunk(s) could not be parsed and are not counted");
+            }
+            println!("{}", indent(&file_stats.to_string()));
+        }
+        total.add(&file_stats);
+    }
+    if config.aggregate || total.files > 1 {
+        println!("total of {} files:", total.files);
+        println!("{}", indent(&total.to_string()));
+    }
+    Ok(())
+}
 
-            let path_name = path.clone();
-            let path_name = path_name.to_string_lossy();
-            let file = File::open(path)?;
-            let file = BufReader::new(file);
+/// Check the UCS file name of each file against its iXML, or write the
+/// name's fields to iXML with `--write`. Fails if any file could not be
+/// checked.
+#[instrument]
+fn ucs(config: &UcsConfig) -> Result<()> {
+    let paths = expand_paths(&config.path_options)?;
 
-            match view_line(file) {
-                Ok(output) => println!("{path_name}: {output}"),
-                Err(err) => println!("{path_name}: ERROR: {}", err),
+    let mut failed = 0;
+    for path in &paths {
+        let path_name = path.to_string_lossy();
+        let write = config.write.then_some(&config.write_options);
+        match ucs_file(path, write) {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{path_name}: {line}");
+                }
+            }
+            Err(err) => {
+                failed += 1;
+                println!("{path_name}: ERROR: {err}");
             }
         }
     }
+    if failed > 0 {
+        anyhow::bail!("{failed} file(s) could not be checked");
+    }
     Ok(())
 }
 
+/// Compare the UCS name of `path` with its iXML, or write the name to iXML
+/// with `write`, replacing the file when done. Returns lines to print.
+fn ucs_file(path: &Path, write: Option<&WriteOptions>) -> Result<Vec<String>> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let name: wavrw::ucs::UcsName = file_name.parse()?;
+
+    if let Some(options) = write {
+        let edited = replace_file(path, options, |reader, writer| {
+            let mut wave = wavrw::WaveFile::from_reader(reader)?;
+            Ok(name.apply(&mut wave, writer)?)
+        })?;
+        return Ok(match edited {
+            Edited::Written(_) => vec![format!("wrote UCS fields of {name} to iXML")],
+            Edited::DryRun { changes, .. } => changes,
+        });
+    }
+
+    let mut wave = wavrw::WaveFile::from_reader(BufReader::new(File::open(path)?))?;
+    let metadata = wave.metadata();
+    let Some(ixml) = metadata.ixml() else {
+        return Ok(vec!["no iXML chunk".to_string()]);
+    };
+    let warnings = name.check_ixml(ixml);
+    if warnings.is_empty() {
+        return Ok(vec!["UCS fields match iXML".to_string()]);
+    }
+    Ok(warnings.iter().map(|w| w.message.clone()).collect())
+}
+
+/// Files written to the catalog per transaction by index.
+#[cfg(feature = "index")]
+const INDEX_BATCH: usize = 100;
+
+/// Write the metadata of the files in `config.path_options` to the catalog.
+#[cfg(feature = "index")]
+#[instrument]
+fn index(config: &IndexConfig) -> Result<()> {
+    use std::collections::HashMap;
+    use std::time::UNIX_EPOCH;
+    use wavrw::catalog::{CatalogFile, FILES_QUERY, SCHEMA};
+
+    let paths = expand_paths(&config.path_options)?;
+    let db = match &config.db {
+        Some(db) => db.clone(),
+        None => {
+            let first = PathBuf::from(&config.path_options.paths[0]);
+            let dir = if first.is_dir() {
+                first
+            } else {
+                first.parent().unwrap_or(Path::new("")).to_path_buf()
+            };
+            dir.join("wavrw.db")
+        }
+    };
+    let base = match db.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => fs::canonicalize(dir)?,
+        _ => std::env::current_dir()?,
+    };
+
+    sqlite(&config.sqlite3, &db, SCHEMA)?;
+    let indexed: HashMap<String, CatalogFile> = if config.full {
+        HashMap::new()
+    } else {
+        wavrw::catalog::parse_files(&sqlite(&config.sqlite3, &db, FILES_QUERY)?)
+            .into_iter()
+            .map(|f| (f.path.clone(), f))
+            .collect()
+    };
+
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+    for path in &paths {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
+        let size = metadata.len();
+        let abs_path = fs::canonicalize(path)?;
+        let key = abs_path
+            .strip_prefix(&base)
+            .unwrap_or(&abs_path)
+            .to_string_lossy()
+            .into_owned();
+        if indexed
+            .get(&key)
+            .is_some_and(|f| f.is_unchanged(modified, size))
+        {
+            unchanged += 1;
+            continue;
+        }
+        changed.push((
+            path,
+            CatalogFile {
+                path: key,
+                modified,
+                size,
+                error: None,
+            },
+        ));
+    }
+
+    // parse one file at a time and commit every INDEX_BATCH files, so
+    // neither the parsed chunks nor the SQL of all files are kept in memory
+    let options = wavrw::scan::ScanOptions::default().with_threads(1);
+    let progress = io::stderr()
+        .is_terminal()
+        .then(|| Progress::new(changed.len()));
+    let updated = changed.len();
+    let mut removed = 0;
+    let written = sqlite_with(&config.sqlite3, &db, |stdin| {
+        stdin.write_all(b"BEGIN;\n")?;
+        for (index, (path, mut file)) in changed.into_iter().enumerate() {
+            if index > 0 && index % INDEX_BATCH == 0 {
+                stdin.write_all(b"COMMIT;\nBEGIN;\n")?;
+            }
+            let results = match &progress {
+                Some(progress) => {
+                    wavrw::scan::scan_paths_with_progress(&[path], &options, progress)
+                }
+                None => wavrw::scan::scan_paths(&[path], &options),
+            };
+            for result in results {
+                let chunks = match result.chunks {
+                    Ok(chunks) => chunks
+                        .into_iter()
+                        .filter_map(|c| {
+                            c.inspect_err(|err| {
+                                file.error.get_or_insert_with(|| err.to_string());
+                            })
+                            .ok()
+                        })
+                        .collect(),
+                    Err(err) => {
+                        file.error = Some(err.to_string());
+                        Vec::new()
+                    }
+                };
+                stdin.write_all(wavrw::catalog::file_sql(&file, &chunks).as_bytes())?;
+            }
+        }
+        for path in indexed.keys() {
+            if !base.join(path).exists() {
+                stdin.write_all(wavrw::catalog::delete_sql(path).as_bytes())?;
+                removed += 1;
+            }
+        }
+        stdin.write_all(b"COMMIT;\n")?;
+        Ok(())
+    });
+    if let Some(progress) = &progress {
+        progress.clear();
+    }
+    written?;
+
+    println!(
+        "{}: {updated} updated, {unchanged} unchanged, {removed} removed",
+        db.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Run `sql` with the sqlite3 `program` on the database `db`, returns the
+/// output.
+#[cfg(feature = "index")]
+fn sqlite(program: &OsString, db: &Path, sql: &str) -> Result<String> {
+    sqlite_with(program, db, |stdin| Ok(stdin.write_all(sql.as_bytes())?))
+}
+
+/// Run the SQL `write` writes to stdin of the sqlite3 `program` on the
+/// database `db`, returns the output.
+///
+/// The SQL is streamed, sqlite3 runs the statements while `write` is still
+/// writing.
+#[cfg(feature = "index")]
+fn sqlite_with<F>(program: &OsString, db: &Path, write: F) -> Result<String>
+where
+    F: FnOnce(&mut dyn io::Write) -> Result<()>,
+{
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .arg("-batch")
+        .arg("-bail")
+        .arg(db)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| anyhow::anyhow!("could not run {}: {err}", program.to_string_lossy()))?;
+    // stdin is closed at the end of the block, so sqlite3 exits
+    let written = match child.stdin.take() {
+        Some(stdin) => {
+            let mut stdin = BufWriter::new(stdin);
+            write(&mut stdin).and_then(|()| Ok(stdin.flush()?))
+        }
+        None => Ok(()),
+    };
+    let output = child.wait_with_output()?;
+    // with -bail sqlite3 exits at the first error, which fails writing
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} failed: {}",
+            program.to_string_lossy(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    written?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The files of `options.paths`, and the files in its directories with one
+/// of the extensions `options.ext`.
+fn expand_paths(options: &PathOptions) -> Result<Vec<PathBuf>> {
+    // for case insensitive comparison
+    let ext: Vec<OsString> = options.ext.iter().map(|e| e.to_ascii_lowercase()).collect();
+    let mut found = vec![];
+    for path in &options.paths {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            find_paths(&path, &ext, options.recurse, &mut found)?;
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(found)
+}
+
+/// Files in `base_path` with one of the lowercase extensions `ext`.
+fn find_paths(
+    base_path: &PathBuf,
+    ext: &[OsString],
+    recurse: bool,
+    found: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let mut paths = fs::read_dir(base_path)?
+        .map(|res| res.map(|e| e.path()))
+        .collect::<Result<Vec<_>, io::Error>>()?;
+    paths.sort_unstable();
+    for path in paths {
+        if path.is_dir() {
+            if recurse {
+                find_paths(&path, ext, recurse, found)?;
+            }
+        } else if let Some(path_ext) = path.extension() {
+            if ext.contains(&path_ext.to_ascii_lowercase()) {
+                found.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Indent every line of `text` by 4 spaces.
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("    {line}")).join("\n")
+}
+
+/// Supported chunk types, from [`wavrw::chunk::SUPPORTED`].
+fn chunks_text() -> Result<String> {
+    let mut out = String::new();
+    for chunk in wavrw::chunk::SUPPORTED {
+        let indent = if chunk.parent.is_some() { "    " } else { "" };
+        writeln!(out, "{indent}{:10}{}", chunk.name(), chunk.description)?;
+        writeln!(out, "{indent}{:10}{}: {}", "", chunk.spec, chunk.url)?;
+    }
+    writeln!(out)?;
+    writeln!(
+        out,
+        "For more information about chunks, see wavrw's sibling project WavRef:"
+    )?;
+    writeln!(out, "\thttps://wavref.til.cafe/")?;
+    Ok(out)
+}
+
+#[instrument]
+fn list_chunks(_config: &ListChunksConfig) -> Result<()> {
+    print!("{}", chunks_text()?);
+    Ok(())
+}
+
+#[instrument]
+fn schema(config: &SchemaConfig) -> Result<()> {
+    let types = if config.types.is_empty() {
+        wavrw::schema().to_vec()
+    } else {
+        config
+            .types
+            .iter()
+            .map(|name| {
+                wavrw::schema::type_schema(name)
+                    .copied()
+                    .ok_or_else(|| anyhow::anyhow!("unknown type {name}"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    match config.format {
+        SchemaFormat::Json => print!("{}", wavrw::schema::to_json_schema(&types)),
+        SchemaFormat::Text => {
+            for ty in &types {
+                println!(
+                    "{}::{}: {}",
+                    ty.module,
+                    ty.name,
+                    wavrw::schema::summary(ty.doc)
+                );
+                for member in ty.members {
+                    let line = format!(
+                        "    {:24} {:24} {}",
+                        member.name,
+                        member.ty,
+                        wavrw::schema::summary(member.doc)
+                    );
+                    println!("{}", line.trim_end());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// True if `a` and `b` are paths of the same existing file.
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
 #[instrument]
 fn topic(config: &mut TopicConfig) -> Result<()> {
     match config.topic {
         Topic::Licenses => println!(include_str!("../../generated/licenses.txt")),
-        Topic::Chunks => println!(include_str!("../../static/topic/chunks.txt")),
+        Topic::Chunks => print!("{}", chunks_text()?),
         Topic::GreatWave => {
             print!(include_str!("../../static/topic/wave.ansi"));
             println!("Great Wave by Hokusai");
@@ -349,6 +2681,8 @@ fn main() -> Result<()> {
         // TODO: --option to set log level and span events
         .with_max_level(Level::TRACE)
         .with_span_events(FmtSpan::NONE)
+        // keep stdout for output, like --report json
+        .with_writer(io::stderr)
         .finish();
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 
@@ -363,13 +2697,26 @@ fn main() -> Result<()> {
             view(config)
         }
 
-        Commands::List(config) => {
-            // Convert extensions to lowercase for case insensitive comparison later.
-            for ext in &mut config.ext {
-                ext.make_ascii_lowercase();
-            }
-            list(config)
-        }
+        Commands::List(config) => list(config),
+        Commands::Ls(config) => ls(config),
+        Commands::Apply(config) => apply(config),
+        Commands::Compare(config) => compare(config),
+        Commands::Export(config) => export(config),
+        Commands::ExportCore(config) => export_core(config),
+        Commands::Import(config) => import(config),
+        Commands::ImportCore(config) => import_core(config),
+        #[cfg(feature = "index")]
+        Commands::Index(config) => index(config),
+        Commands::Inject(config) => inject(config),
+        Commands::Loudness(config) => loudness(config),
+        Commands::Markers(config) => markers(config),
+        Commands::Merge(config) => merge(config),
+        Commands::Roundtrip(config) => roundtrip(config),
+        Commands::Split(config) => split(config),
+        Commands::Stats(config) => stats(config),
+        Commands::Ucs(config) => ucs(config),
+        Commands::ListChunks(config) => list_chunks(config),
+        Commands::Schema(config) => schema(config),
         Commands::Topic(config) => topic(config),
     }
 }
@@ -0,0 +1,169 @@
+//! Python bindings for wavrw.
+//!
+//! ```python
+//! import wavrw
+//!
+//! wave = wavrw.WaveFile("test_wavs/example_a.wav")
+//! print(wave.sample_rate, wave.channels, wave.duration_samples)
+//! for chunk in wave.chunks:
+//!     print(chunk.offset, chunk.name, chunk.size, chunk.summary)
+//!     for name, value in chunk.items().items():
+//!         print("   ", name, value)
+//! ```
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use wavrw::report::{ChunkReport, Report};
+use wavrw::WaveFileError;
+
+fn to_py_err(err: WaveFileError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// A parsed chunk.
+#[pyclass(module = "wavrw", frozen)]
+#[derive(Debug, Clone)]
+struct Chunk {
+    /// Byte offset of the chunk from the beginning of the file.
+    #[pyo3(get)]
+    offset: Option<u64>,
+
+    /// Chunk name. Ex: `fmt`, `LIST-INFO`
+    #[pyo3(get)]
+    name: String,
+
+    /// Chunk size in bytes.
+    #[pyo3(get)]
+    size: u32,
+
+    /// One line summary of the chunk contents.
+    #[pyo3(get)]
+    summary: String,
+
+    items: Vec<(String, String)>,
+}
+
+#[pymethods]
+impl Chunk {
+    /// Chunk fields as a dict of strings, in chunk order.
+    fn items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        for (name, value) in &self.items {
+            dict.set_item(name, value)?;
+        }
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Chunk {} {}>", self.name, self.summary)
+    }
+}
+
+impl From<ChunkReport> for Chunk {
+    fn from(chunk: ChunkReport) -> Self {
+        Chunk {
+            offset: chunk.offset,
+            name: chunk.name,
+            size: chunk.size,
+            summary: chunk.summary,
+            items: chunk.items,
+        }
+    }
+}
+
+/// A WAV file with all chunks parsed.
+///
+/// Audio data is not read.
+#[pyclass(module = "wavrw", frozen)]
+#[derive(Debug)]
+struct WaveFile {
+    /// Sample rate in samples per second, `None` without a `fmt ` chunk.
+    #[pyo3(get)]
+    sample_rate: Option<u32>,
+
+    /// Number of channels.
+    #[pyo3(get)]
+    channels: Option<u16>,
+
+    /// Bits per sample.
+    #[pyo3(get)]
+    bit_depth: Option<u16>,
+
+    /// Length of the audio in samples (per channel).
+    #[pyo3(get)]
+    duration_samples: Option<u64>,
+
+    /// Length of the audio in seconds.
+    #[pyo3(get)]
+    duration_seconds: Option<f64>,
+
+    /// Successfully parsed chunks, in file order.
+    #[pyo3(get)]
+    chunks: Vec<Chunk>,
+
+    /// Error which stopped parsing before the end of the file.
+    #[pyo3(get)]
+    error: Option<String>,
+
+    /// Problems found while parsing which did not stop parsing.
+    #[pyo3(get)]
+    warnings: Vec<String>,
+}
+
+#[pymethods]
+impl WaveFile {
+    /// Parse the file at `path`.
+    #[new]
+    fn new(path: PathBuf) -> PyResult<Self> {
+        let file = BufReader::new(File::open(path)?);
+        let mut wave = wavrw::WaveFile::from_reader(file).map_err(to_py_err)?;
+        Ok(Report::from_wave(&mut wave).into())
+    }
+
+    /// Parse a file from `data`.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Report::from_bytes(data).map_err(to_py_err)?.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<WaveFile {} chunks, {}>",
+            self.chunks.len(),
+            self.chunks
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl From<Report> for WaveFile {
+    fn from(report: Report) -> Self {
+        WaveFile {
+            sample_rate: report.sample_rate,
+            channels: report.channels,
+            bit_depth: report.bit_depth,
+            duration_samples: report.duration_samples,
+            duration_seconds: report.duration_seconds,
+            chunks: report.chunks.into_iter().map(Chunk::from).collect(),
+            error: report.error,
+            warnings: report.warnings,
+        }
+    }
+}
+
+/// Read WAV file chunks and metadata.
+#[pymodule]
+#[pyo3(name = "wavrw")]
+fn wavrw_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<WaveFile>()?;
+    m.add_class::<Chunk>()?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Parsing benchmarks, run with `cargo bench -p wavrw`.
+//!
+//! Compares parsing files with large padding and unknown chunks with and
+//! without [`ParseOptions::keep_extra_bytes`], and parsing the example files.
+//!
+//! This is a plain timing loop, not a Criterion benchmark: it reports the mean
+//! time per run only, without warm-up statistics or comparison to earlier runs.
+
+use core::hint::black_box;
+use core::time::Duration;
+use std::fs;
+use std::io::Cursor;
+use std::time::Instant;
+
+use wavrw::{ParseOptions, WaveFile, WaveFileError};
+
+/// Run `f` repeatedly for about a second and print the mean time per run.
+fn bench(
+    name: &str,
+    mut f: impl FnMut() -> Result<usize, WaveFileError>,
+) -> Result<(), WaveFileError> {
+    // warm up, and estimate how many runs fit in the time budget
+    let start = Instant::now();
+    black_box(f()?);
+    let once = start.elapsed().max(Duration::from_nanos(1));
+    let runs = (Duration::from_secs(1).as_nanos() / once.as_nanos()).clamp(10, 100_000);
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        black_box(f()?);
+    }
+    let mean = start.elapsed() / u32::try_from(runs).unwrap_or(u32::MAX);
+    println!("{name:<52} {mean:>12.2?}/iter ({runs} runs)");
+    Ok(())
+}
+
+/// A chunk with `id` and `size` bytes of content, plus padding byte.
+fn chunk(id: &[u8; 4], size: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(id);
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.resize(bytes.len() + size as usize + size as usize % 2, 0);
+    bytes
+}
+
+/// WAV file with `fmt `, large `JUNK`, `FLLR` and unknown chunks and an empty `data` chunk.
+fn padded_wav() -> Vec<u8> {
+    let mut fmt = chunk(b"fmt ", 16);
+    // PCM mono 16 bit 48 kHz
+    fmt[8..].copy_from_slice(&[
+        0x01, 0x00, 0x01, 0x00, 0x80, 0xBB, 0x00, 0x00, 0x00, 0x77, 0x01, 0x00, 0x02, 0x00, 0x10,
+        0x00,
+    ]);
+    let mut chunks = fmt;
+    chunks.extend(chunk(b"JUNK", 1 << 20));
+    chunks.extend(chunk(b"FLLR", 64 * 1024 + 1));
+    chunks.extend(chunk(b"abcd", 4 << 20));
+    chunks.extend(chunk(b"data", 0));
+
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(chunks.len() as u32 + 4).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend(chunks);
+    wav
+}
+
+/// Parse all chunks of `bytes`, returning the number of chunks.
+fn parse(bytes: &[u8], options: &ParseOptions) -> Result<usize, WaveFileError> {
+    let mut wave = WaveFile::from_reader_with_options(Cursor::new(bytes), options.clone())?;
+    let chunks: Result<Vec<_>, _> = wave.iter_chunks().collect();
+    Ok(chunks?.len())
+}
+
+fn main() -> Result<(), WaveFileError> {
+    let padded = padded_wav();
+    let keep = ParseOptions::default();
+    let skip = ParseOptions::default().with_keep_extra_bytes(false);
+    bench("padded: keep_extra_bytes", || parse(&padded, &keep))?;
+    bench("padded: skip extra bytes", || parse(&padded, &skip))?;
+
+    let mut paths: Vec<_> = fs::read_dir("../test_wavs")?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav")))
+        .collect();
+    paths.sort_unstable();
+    for path in paths {
+        let bytes = fs::read(&path)?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        bench(&name, || parse(&bytes, &keep))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Generates the chunk type descriptions of `wavrw::schema::TYPES` from the
+//! definitions in `src/chunk`.
+//!
+//! Data types are the public structs and enums deriving `Hash`, which skips
+//! iterators and builders. Field names, types and doc comments are read from
+//! the source text, so the descriptions can't drift from the code. Feature
+//! gates of the chunk modules are copied from `src/chunk/mod.rs`.
+
+use core::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::{env, fs};
+
+/// A struct field or enum variant.
+struct Member {
+    name: String,
+    ty: String,
+    doc: String,
+}
+
+/// A public data type.
+struct Type {
+    name: String,
+    is_enum: bool,
+    doc: String,
+    members: Vec<Member>,
+}
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=src/chunk");
+    let out_dir = env::var_os("OUT_DIR").ok_or_else(|| io::Error::other("OUT_DIR not set"))?;
+
+    let cfgs = module_cfgs(&fs::read_to_string("src/chunk/mod.rs")?);
+    let mut paths: Vec<_> = fs::read_dir("src/chunk")?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    paths.sort();
+
+    let mut out = String::from("// generated by build.rs from src/chunk\n&[\n");
+    for path in paths {
+        let Some(module) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if path.extension().is_none_or(|e| e != "rs") || module == "mod" {
+            continue;
+        }
+        for ty in parse_types(&fs::read_to_string(&path)?) {
+            if let Some((_, cfg)) = cfgs.iter().find(|(m, _)| m == module) {
+                let _ = writeln!(out, "    {cfg}");
+            }
+            let _ = writeln!(
+                out,
+                "    TypeSchema {{ module: {module:?}, name: {:?}, kind: TypeKind::{}, doc: {:?}, members: &[",
+                ty.name,
+                if ty.is_enum { "Enum" } else { "Struct" },
+                ty.doc,
+            );
+            for member in ty.members {
+                let _ = writeln!(
+                    out,
+                    "        MemberSchema {{ name: {:?}, ty: {:?}, doc: {:?} }},",
+                    member.name, member.ty, member.doc
+                );
+            }
+            out.push_str("    ] },\n");
+        }
+    }
+    out.push_str("]\n");
+    fs::write(Path::new(&out_dir).join("chunk_schema.rs"), out)
+}
+
+/// `#[cfg(...)]` attributes of the modules declared in `source`, as
+/// (module name, attribute) pairs.
+fn module_cfgs(source: &str) -> Vec<(String, String)> {
+    let mut cfgs = Vec::new();
+    let mut cfg = None;
+    for line in source.lines() {
+        if line.starts_with("#[cfg(") {
+            cfg = Some(line.trim().to_string());
+        } else if let Some(module) = line
+            .strip_prefix("pub mod ")
+            .and_then(|m| m.strip_suffix(';'))
+        {
+            if let Some(cfg) = cfg.take() {
+                cfgs.push((module.to_string(), cfg));
+            }
+        } else {
+            cfg = None;
+        }
+    }
+    cfgs
+}
+
+/// Bracket depth change of `text`, for attributes spanning several lines.
+fn depth(text: &str, open: &[char], close: &[char]) -> i32 {
+    text.chars().fold(0, |depth, c| {
+        if open.contains(&c) {
+            depth + 1
+        } else if close.contains(&c) {
+            depth - 1
+        } else {
+            depth
+        }
+    })
+}
+
+/// Text of a doc comment line, `None` for other lines.
+fn doc_line(line: &str) -> Option<&str> {
+    let doc = line.trim().strip_prefix("///")?;
+    Some(doc.strip_prefix(' ').unwrap_or(doc))
+}
+
+/// The public data types defined at the top level of `source`.
+fn parse_types(source: &str) -> Vec<Type> {
+    let mut types = Vec::new();
+    let mut lines = source.lines();
+    let mut docs: Vec<&str> = Vec::new();
+    let mut derives_hash = false;
+    let mut attr_depth = 0;
+    while let Some(line) = lines.next() {
+        if attr_depth > 0 {
+            attr_depth += depth(line, &['['], &[']']);
+            continue;
+        }
+        if let Some(doc) = doc_line(line).filter(|_| line.starts_with("///")) {
+            docs.push(doc);
+            continue;
+        }
+        if line.starts_with("#[") {
+            derives_hash |= line.starts_with("#[derive(") && line.contains("Hash");
+            attr_depth = depth(line, &['['], &[']']);
+            continue;
+        }
+        let header = line
+            .strip_prefix("pub struct ")
+            .map(|rest| (rest, false))
+            .or_else(|| line.strip_prefix("pub enum ").map(|rest| (rest, true)));
+        if let Some((rest, is_enum)) = header.filter(|_| derives_hash && line.ends_with('{')) {
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            let body = lines.by_ref().take_while(|l| !l.starts_with('}'));
+            types.push(Type {
+                name,
+                is_enum,
+                doc: docs.join("\n"),
+                members: parse_members(body, is_enum),
+            });
+        }
+        docs.clear();
+        derives_hash = false;
+    }
+    types
+}
+
+/// Public fields of a struct body, or the variants of an enum body.
+fn parse_members<'a>(body: impl Iterator<Item = &'a str>, is_enum: bool) -> Vec<Member> {
+    let mut members = Vec::new();
+    let mut docs: Vec<&str> = Vec::new();
+    let mut attr_depth = 0;
+    // text of a member spanning several lines, and its bracket depth
+    let mut current = String::new();
+    let mut current_depth = 0;
+    for line in body {
+        let line = line.trim();
+        if attr_depth > 0 {
+            attr_depth += depth(line, &['['], &[']']);
+            continue;
+        }
+        if line.starts_with("#[") {
+            attr_depth = depth(line, &['['], &[']']);
+            continue;
+        }
+        if let Some(doc) = doc_line(line) {
+            if current.is_empty() {
+                docs.push(doc);
+            }
+            continue;
+        }
+        let code = line.split_once("//").map_or(line, |(code, _)| code).trim();
+        if code.is_empty() {
+            continue;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(code);
+        current_depth += depth(code, &['(', '{', '<', '['], &[')', '}', '>', ']']);
+        if current_depth > 0 || !current.ends_with(',') {
+            continue;
+        }
+        let text = current.trim_end_matches(',').replace(", }", " }");
+        let member = if is_enum {
+            parse_variant(&text, &docs)
+        } else {
+            parse_field(&text, &docs)
+        };
+        members.extend(member);
+        current.clear();
+        current_depth = 0;
+        docs.clear();
+    }
+    if !current.is_empty() {
+        let text = current.replace(", }", " }");
+        members.extend(if is_enum {
+            parse_variant(&text, &docs)
+        } else {
+            parse_field(&text, &docs)
+        });
+    }
+    members
+}
+
+/// `pub name: Type`, `None` for private fields.
+fn parse_field(text: &str, docs: &[&str]) -> Option<Member> {
+    let (name, ty) = text.strip_prefix("pub ")?.split_once(':')?;
+    Some(Member {
+        name: name.trim().to_string(),
+        ty: ty.trim().to_string(),
+        doc: docs.join("\n"),
+    })
+}
+
+/// `Name`, `Name = 1`, `Name(Type)` or `Name { field: Type }`. The type of
+/// a variant is its payload, empty for unit variants.
+fn parse_variant(text: &str, docs: &[&str]) -> Option<Member> {
+    let name: String = text
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if name.is_empty() {
+        return None;
+    }
+    let rest = text[name.len()..].trim();
+    let ty = if let Some(inner) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        inner.trim().to_string()
+    } else if rest.starts_with('{') {
+        rest.to_string()
+    } else {
+        String::new()
+    };
+    Some(Member {
+        name,
+        ty,
+        doc: docs.join("\n"),
+    })
+}
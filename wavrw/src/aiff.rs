@@ -0,0 +1,890 @@
+//! AIFF and AIFC files, the big-endian IFF relatives of WAV. Audio Interchange File Format 1.3 (Apple, 1989), AIFF-C draft (Apple, 1991)
+//!
+//! AIFF chunks have the same id, size and padding layout as RIFF chunks,
+//! but sizes and fields are big-endian. [`AiffFile`] reads the `FORM`
+//! header, accepting both `AIFF` and `AIFC` form types, and
+//! [`AiffFile::iter_chunks()`] parses `COMM`, `SSND`, `MARK`, `INST`,
+//! `FVER` and the `NAME`, `AUTH`, `(c) ` and `ANNO` text chunks into
+//! [`AiffChunkEnum`]. Other chunks become [`UnknownChunk`]s. All chunks
+//! implement [`SizedChunk`] and [`Summarizable`], like WAV chunks.
+//!
+//! ```
+//! # use wavrw::testing::hex_to_cursor;
+//! use wavrw::aiff::AiffFile;
+//! use wavrw::Summarizable;
+//!
+//! // FORM AIFF with a COMM chunk: 1 channel, 0 frames, 16 bit, 48 kHz
+//! let data = "464F524D 0000001E 41494646 434F4D4D 00000012 0001 00000000 0010 400EBB80000000000000";
+//! let mut aiff = AiffFile::from_reader(hex_to_cursor(data))?;
+//! let chunks: Vec<_> = aiff.iter_chunks().collect::<Result<_, _>>()?;
+//! assert_eq!(chunks[0].summary(), "PCM, 1 chan, 16/48000");
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use binrw::io::{Read, Seek, SeekFrom, TakeSeekExt};
+use binrw::{binread, BinRead, BinResult};
+
+use crate::{
+    codepage, fourcc, ChunkID, FourCC, KnownChunkID, SizedChunk, Summarizable, UnknownChunk,
+    WaveFileError,
+};
+
+/// IFF `FORM` header of an AIFF or AIFC file.
+#[binread]
+#[br(big)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormChunk {
+    /// IFF chunk id, always `FORM`.
+    #[br(assert(id == FormChunk::ID, "not an IFF file. Expected FORM, found: {}", id))]
+    pub id: FourCC,
+
+    /// Size in bytes of the form type and all chunks.
+    pub size: u32,
+
+    /// Form type: `AIFF` or `AIFC`.
+    pub form_type: FourCC,
+}
+
+impl FormChunk {
+    /// Chunk id constant: `FORM`
+    pub const ID: FourCC = FourCC(*b"FORM");
+
+    /// Form type of uncompressed AIFF files.
+    pub const AIFF: FourCC = FourCC(*b"AIFF");
+
+    /// Form type of AIFF-C files, which may be compressed.
+    pub const AIFC: FourCC = FourCC(*b"AIFC");
+}
+
+/// A generic wrapper around big-endian AIFF chunk data, handling id and size.
+///
+/// The AIFF counterpart of [`KnownChunk`](crate::KnownChunk). Read only,
+/// padding and unparsed bytes are skipped by [`AiffFile::iter_chunks()`].
+#[binread]
+#[br(big, stream = r)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AiffChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)> + KnownChunkID,
+{
+    /// Calculated byte offset from the beginning of the data stream or None.
+    #[br(try_calc = Some(r.stream_position()).transpose())]
+    pub offset: Option<u64>,
+
+    /// IFF chunk id.
+    #[br(temp, assert(id == T::ID))]
+    id: FourCC,
+
+    /// IFF chunk size in bytes.
+    pub size: u32,
+
+    /// Generic inner data struct.
+    #[br(map_stream = |r| r.take_seek(u64::from(size)), args(size))]
+    pub data: T,
+}
+
+impl<T> KnownChunkID for AiffChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)> + KnownChunkID,
+{
+    const ID: FourCC = T::ID;
+}
+
+impl<T> Display for AiffChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)> + KnownChunkID + Summarizable,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.name(), self.data.summary())
+    }
+}
+
+impl<T> SizedChunk for AiffChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)> + KnownChunkID + Summarizable + Debug,
+{
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+}
+
+impl<T> Summarizable for AiffChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)> + KnownChunkID + Summarizable,
+{
+    fn summary(&self) -> String {
+        self.data.summary()
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        self.data.items()
+    }
+
+    fn item_summary_header(&self) -> String {
+        self.data.item_summary_header()
+    }
+
+    fn name(&self) -> String {
+        self.data.name()
+    }
+}
+
+/// 80 bit IEEE 754 extended precision float, used for the AIFF sample rate.
+#[binread]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Extended(pub [u8; 10]);
+
+impl Extended {
+    /// The value as an `f64`. Values outside the `f64` range become zero or
+    /// infinity.
+    ///
+    /// ```
+    /// use wavrw::aiff::Extended;
+    ///
+    /// let rate = Extended([0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(rate.to_f64(), 44100.0);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        let [high, low, mantissa @ ..] = self.0;
+        let sign = u64::from(high >> 7) << 63;
+        let mantissa = u64::from_be_bytes(mantissa);
+        if mantissa == 0 {
+            return f64::from_bits(sign);
+        }
+        // the integer bit is explicit, normalize so it is set
+        let shift = mantissa.leading_zeros();
+        let mantissa = mantissa << shift;
+        let exponent = (i64::from(high & 0x7F) << 8 | i64::from(low)) - 16383 - i64::from(shift);
+        match exponent + 1023 {
+            ..=0 => f64::from_bits(sign),
+            0x7FF.. => f64::from_bits(sign | 0x7FF0_0000_0000_0000),
+            biased => f64::from_bits(sign | biased.unsigned_abs() << 52 | (mantissa << 1) >> 12),
+        }
+    }
+}
+
+/// Read a Pascal style string: a count byte, the text and a padding byte
+/// if needed to make the total length even.
+#[binrw::parser(reader)]
+fn read_pstring() -> BinResult<String> {
+    let mut count = [0_u8; 1];
+    reader.read_exact(&mut count)?;
+    let mut bytes = alloc::vec![0_u8; usize::from(count[0])];
+    reader.read_exact(&mut bytes)?;
+    if count[0] % 2 == 0 {
+        reader.seek(SeekFrom::Current(1))?;
+    }
+    Ok(codepage::decode(&bytes, None))
+}
+
+/// `COMM` Channels, sample frames, sample size and rate. AIFC files add the
+/// compression type.
+#[binread]
+#[br(big, import(size: u32))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comm {
+    /// Number of audio channels.
+    pub channels: u16,
+
+    /// Number of sample frames in the `SSND` chunk.
+    pub sample_frames: u32,
+
+    /// Number of bits per sample.
+    pub sample_size: u16,
+
+    /// Sample frames per second.
+    pub sample_rate: Extended,
+
+    /// AIFC compression type. Ex: `NONE`, `sowt`, `fl32`
+    #[br(if(size >= 22))]
+    pub compression_type: Option<FourCC>,
+
+    /// AIFC human readable compression name. Ex: `not compressed`
+    #[br(if(size >= 23), parse_with = |r, e, a| read_pstring(r, e, a).map(Some))]
+    pub compression_name: Option<String>,
+}
+
+impl KnownChunkID for Comm {
+    const ID: FourCC = FourCC(*b"COMM");
+}
+
+impl Summarizable for Comm {
+    fn summary(&self) -> String {
+        let compression = self
+            .compression_type
+            .map_or("PCM".to_string(), |c| c.to_string());
+        format!(
+            "{}, {} chan, {}/{}",
+            compression.trim(),
+            self.channels,
+            self.sample_size,
+            self.sample_rate.to_f64(),
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let mut items = Vec::from([
+            ("channels".to_string(), self.channels.to_string()),
+            ("sample_frames".to_string(), self.sample_frames.to_string()),
+            ("sample_size".to_string(), self.sample_size.to_string()),
+            (
+                "sample_rate".to_string(),
+                self.sample_rate.to_f64().to_string(),
+            ),
+        ]);
+        if let Some(compression_type) = self.compression_type {
+            items.push(("compression_type".to_string(), compression_type.to_string()));
+        }
+        if let Some(compression_name) = &self.compression_name {
+            items.push(("compression_name".to_string(), compression_name.clone()));
+        }
+        Box::new(items.into_iter())
+    }
+}
+
+/// `COMM` Channels, sample frames, sample size and rate.
+pub type CommChunk = AiffChunk<Comm>;
+
+/// `SSND` Audio samples. This parser skips all audio data.
+#[binread]
+#[br(big, import(size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ssnd {
+    /// Bytes to skip before the first sample frame.
+    pub offset: u32,
+
+    /// Alignment block size in bytes, usually 0.
+    pub block_size: u32,
+
+    /// Bytes of audio after the `offset` and `block_size` fields.
+    #[br(calc = size.saturating_sub(8))]
+    pub audio_size: u32,
+}
+
+impl KnownChunkID for Ssnd {
+    const ID: FourCC = FourCC(*b"SSND");
+}
+
+impl Summarizable for Ssnd {
+    fn summary(&self) -> String {
+        "audio data".to_string()
+    }
+}
+
+/// `SSND` Audio samples. This parser skips all audio data.
+pub type SsndChunk = AiffChunk<Ssnd>;
+
+/// A position in the audio, referenced by `INST` loops.
+#[binread]
+#[br(big)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AiffMarker {
+    /// Unique positive marker id.
+    pub id: u16,
+
+    /// Position in sample frames.
+    pub position: u32,
+
+    /// Marker name.
+    #[br(parse_with = read_pstring)]
+    pub name: String,
+}
+
+/// `MARK` Named positions in the audio.
+#[binread]
+#[br(big, import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mark {
+    /// Count of `markers`.
+    pub num_markers: u16,
+
+    /// Markers, in file order.
+    #[br(count = num_markers)]
+    pub markers: Vec<AiffMarker>,
+}
+
+impl KnownChunkID for Mark {
+    const ID: FourCC = FourCC(*b"MARK");
+}
+
+impl Summarizable for Mark {
+    fn summary(&self) -> String {
+        let label = match self.num_markers {
+            1 => "marker",
+            _ => "markers",
+        };
+        format!("{} {label}", self.num_markers)
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        Box::new(
+            self.markers
+                .iter()
+                .map(|m| (m.id.to_string(), format!("{} {}", m.position, m.name))),
+        )
+    }
+
+    fn item_summary_header(&self) -> String {
+        "id: position name".to_string()
+    }
+}
+
+/// `MARK` Named positions in the audio.
+pub type MarkChunk = AiffChunk<Mark>;
+
+/// A loop between two markers of the `MARK` chunk.
+#[binread]
+#[br(big)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AiffLoop {
+    /// 0: no looping, 1: forward, 2: forward and backward.
+    pub play_mode: u16,
+
+    /// Marker id of the loop start.
+    pub begin_loop: u16,
+
+    /// Marker id of the loop end.
+    pub end_loop: u16,
+}
+
+impl Display for AiffLoop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mode = match self.play_mode {
+            0 => "no looping",
+            1 => "forward",
+            2 => "forward/backward",
+            _ => "unknown",
+        };
+        write!(f, "{mode}, markers {}-{}", self.begin_loop, self.end_loop)
+    }
+}
+
+/// `INST` Pitch, volume, velocity and loops for playback by sampler.
+#[binread]
+#[br(big, import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Inst {
+    /// MIDI note of the sample.
+    pub base_note: i8,
+
+    /// Pitch shift adjustment in cents, -50 to 50.
+    pub detune: i8,
+
+    /// Lowest usable MIDI note.
+    pub low_note: i8,
+
+    /// Highest usable MIDI note.
+    pub high_note: i8,
+
+    /// Lowest usable MIDI velocity.
+    pub low_velocity: i8,
+
+    /// Highest usable MIDI velocity.
+    pub high_velocity: i8,
+
+    /// Suggested volume change in decibels.
+    pub gain: i16,
+
+    /// Loop played while the note is held.
+    pub sustain_loop: AiffLoop,
+
+    /// Loop played after the note is released.
+    pub release_loop: AiffLoop,
+}
+
+impl KnownChunkID for Inst {
+    const ID: FourCC = FourCC(*b"INST");
+}
+
+impl Summarizable for Inst {
+    fn summary(&self) -> String {
+        format!(
+            "note: {} ({}-{}), detune: {:+} cents, gain: {} dB, velocity: {}-{}",
+            self.base_note,
+            self.low_note,
+            self.high_note,
+            self.detune,
+            self.gain,
+            self.low_velocity,
+            self.high_velocity,
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        Box::new(
+            [
+                ("base_note", self.base_note.to_string()),
+                ("detune", self.detune.to_string()),
+                ("low_note", self.low_note.to_string()),
+                ("high_note", self.high_note.to_string()),
+                ("low_velocity", self.low_velocity.to_string()),
+                ("high_velocity", self.high_velocity.to_string()),
+                ("gain", self.gain.to_string()),
+                ("sustain_loop", self.sustain_loop.to_string()),
+                ("release_loop", self.release_loop.to_string()),
+            ]
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value)),
+        )
+    }
+}
+
+/// `INST` Pitch, volume, velocity and loops for playback by sampler.
+pub type InstChunk = AiffChunk<Inst>;
+
+/// `FVER` AIFC format version, a timestamp identifying the specification.
+#[binread]
+#[br(big, import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fver {
+    /// Seconds since January 1, 1904. `0xA2805140` for AIFC version 1.
+    pub timestamp: u32,
+}
+
+impl KnownChunkID for Fver {
+    const ID: FourCC = FourCC(*b"FVER");
+}
+
+impl Summarizable for Fver {
+    fn summary(&self) -> String {
+        format!("{:#X}", self.timestamp)
+    }
+}
+
+/// `FVER` AIFC format version.
+pub type FverChunk = AiffChunk<Fver>;
+
+/// Text chunks: `NAME`, `AUTH`, `(c) ` and `ANNO`.
+#[binread]
+#[br(big, import(size: u32))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Text<const I: u32> {
+    /// Text of the chunk.
+    #[br(count = size, map = |raw: Vec<u8>| codepage::decode(&raw, None))]
+    pub text: String,
+}
+
+impl<const I: u32> KnownChunkID for Text<I> {
+    const ID: FourCC = FourCC(I.to_le_bytes());
+}
+
+impl<const I: u32> Debug for Text<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct(&format!("Text<{}>", Self::ID))
+            .field("text", &self.text)
+            .finish()
+    }
+}
+
+impl<const I: u32> Summarizable for Text<I> {
+    fn summary(&self) -> String {
+        self.text.clone()
+    }
+}
+
+/// `NAME` Name of the sampled sound.
+pub type NameChunk = AiffChunk<Text<{ fourcc(b"NAME") }>>;
+/// `AUTH` Author or authors of the sound.
+pub type AuthChunk = AiffChunk<Text<{ fourcc(b"AUTH") }>>;
+/// `(c) ` Copyright notice.
+pub type CopyrightChunk = AiffChunk<Text<{ fourcc(b"(c) ") }>>;
+/// `ANNO` Comment, a file may have several.
+pub type AnnoChunk = AiffChunk<Text<{ fourcc(b"ANNO") }>>;
+
+/// All AIFF chunks parsed by [`AiffFile::iter_chunks()`].
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_docs)]
+pub enum AiffChunkEnum {
+    Comm(CommChunk),
+    Ssnd(SsndChunk),
+    Mark(MarkChunk),
+    Inst(InstChunk),
+    Fver(FverChunk),
+    Name(NameChunk),
+    Auth(AuthChunk),
+    Copyright(CopyrightChunk),
+    Anno(AnnoChunk),
+    Unknown(UnknownChunk),
+}
+
+impl AiffChunkEnum {
+    /// Parse the chunk at the current position of `reader`.
+    ///
+    /// The reader is left where parsing stopped, which may be before the end
+    /// of the chunk. Unknown chunks keep their data as bytes.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, WaveFileError> {
+        let offset = reader.stream_position()?;
+        let mut header = [0_u8; 8];
+        reader.read_exact(&mut header)?;
+        let [a, b, c, d, size @ ..] = header;
+        let id = FourCC([a, b, c, d]);
+        let size = u32::from_be_bytes(size);
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let chunk = match id {
+            Comm::ID => AiffChunkEnum::Comm(CommChunk::read(reader)?),
+            Ssnd::ID => AiffChunkEnum::Ssnd(SsndChunk::read(reader)?),
+            Mark::ID => AiffChunkEnum::Mark(MarkChunk::read(reader)?),
+            Inst::ID => AiffChunkEnum::Inst(InstChunk::read(reader)?),
+            Fver::ID => AiffChunkEnum::Fver(FverChunk::read(reader)?),
+            NameChunk::ID => AiffChunkEnum::Name(NameChunk::read(reader)?),
+            AuthChunk::ID => AiffChunkEnum::Auth(AuthChunk::read(reader)?),
+            CopyrightChunk::ID => AiffChunkEnum::Copyright(CopyrightChunk::read(reader)?),
+            AnnoChunk::ID => AiffChunkEnum::Anno(AnnoChunk::read(reader)?),
+            _ => {
+                reader.seek(SeekFrom::Current(8))?;
+                let raw = crate::read_bytes(reader, binrw::Endian::Big, (u64::from(size),))?;
+                let padding = crate::read_padding(reader, binrw::Endian::Big, ())?;
+                AiffChunkEnum::Unknown(UnknownChunk {
+                    offset: Some(offset),
+                    id,
+                    size,
+                    large_size: None,
+                    raw,
+                    padding,
+                })
+            }
+        };
+        Ok(chunk)
+    }
+}
+
+impl Display for AiffChunkEnum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AiffChunkEnum::Comm(e) => write!(f, "{e}"),
+            AiffChunkEnum::Ssnd(e) => write!(f, "{e}"),
+            AiffChunkEnum::Mark(e) => write!(f, "{e}"),
+            AiffChunkEnum::Inst(e) => write!(f, "{e}"),
+            AiffChunkEnum::Fver(e) => write!(f, "{e}"),
+            AiffChunkEnum::Name(e) => write!(f, "{e}"),
+            AiffChunkEnum::Auth(e) => write!(f, "{e}"),
+            AiffChunkEnum::Copyright(e) => write!(f, "{e}"),
+            AiffChunkEnum::Anno(e) => write!(f, "{e}"),
+            AiffChunkEnum::Unknown(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl ChunkID for AiffChunkEnum {
+    fn id(&self) -> FourCC {
+        match self {
+            AiffChunkEnum::Comm(e) => e.id(),
+            AiffChunkEnum::Ssnd(e) => e.id(),
+            AiffChunkEnum::Mark(e) => e.id(),
+            AiffChunkEnum::Inst(e) => e.id(),
+            AiffChunkEnum::Fver(e) => e.id(),
+            AiffChunkEnum::Name(e) => e.id(),
+            AiffChunkEnum::Auth(e) => e.id(),
+            AiffChunkEnum::Copyright(e) => e.id(),
+            AiffChunkEnum::Anno(e) => e.id(),
+            AiffChunkEnum::Unknown(e) => e.id(),
+        }
+    }
+}
+
+impl SizedChunk for AiffChunkEnum {
+    fn size(&self) -> u32 {
+        match self {
+            AiffChunkEnum::Comm(e) => e.size(),
+            AiffChunkEnum::Ssnd(e) => e.size(),
+            AiffChunkEnum::Mark(e) => e.size(),
+            AiffChunkEnum::Inst(e) => e.size(),
+            AiffChunkEnum::Fver(e) => e.size(),
+            AiffChunkEnum::Name(e) => e.size(),
+            AiffChunkEnum::Auth(e) => e.size(),
+            AiffChunkEnum::Copyright(e) => e.size(),
+            AiffChunkEnum::Anno(e) => e.size(),
+            AiffChunkEnum::Unknown(e) => e.size(),
+        }
+    }
+
+    fn offset(&self) -> Option<u64> {
+        match self {
+            AiffChunkEnum::Comm(e) => e.offset(),
+            AiffChunkEnum::Ssnd(e) => e.offset(),
+            AiffChunkEnum::Mark(e) => e.offset(),
+            AiffChunkEnum::Inst(e) => e.offset(),
+            AiffChunkEnum::Fver(e) => e.offset(),
+            AiffChunkEnum::Name(e) => e.offset(),
+            AiffChunkEnum::Auth(e) => e.offset(),
+            AiffChunkEnum::Copyright(e) => e.offset(),
+            AiffChunkEnum::Anno(e) => e.offset(),
+            AiffChunkEnum::Unknown(e) => e.offset(),
+        }
+    }
+}
+
+impl Summarizable for AiffChunkEnum {
+    fn summary(&self) -> String {
+        match self {
+            AiffChunkEnum::Comm(e) => e.summary(),
+            AiffChunkEnum::Ssnd(e) => e.summary(),
+            AiffChunkEnum::Mark(e) => e.summary(),
+            AiffChunkEnum::Inst(e) => e.summary(),
+            AiffChunkEnum::Fver(e) => e.summary(),
+            AiffChunkEnum::Name(e) => e.summary(),
+            AiffChunkEnum::Auth(e) => e.summary(),
+            AiffChunkEnum::Copyright(e) => e.summary(),
+            AiffChunkEnum::Anno(e) => e.summary(),
+            AiffChunkEnum::Unknown(e) => e.summary(),
+        }
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        match self {
+            AiffChunkEnum::Comm(e) => e.items(),
+            AiffChunkEnum::Ssnd(e) => e.items(),
+            AiffChunkEnum::Mark(e) => e.items(),
+            AiffChunkEnum::Inst(e) => e.items(),
+            AiffChunkEnum::Fver(e) => e.items(),
+            AiffChunkEnum::Name(e) => e.items(),
+            AiffChunkEnum::Auth(e) => e.items(),
+            AiffChunkEnum::Copyright(e) => e.items(),
+            AiffChunkEnum::Anno(e) => e.items(),
+            AiffChunkEnum::Unknown(e) => e.items(),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            AiffChunkEnum::Comm(e) => e.name(),
+            AiffChunkEnum::Ssnd(e) => e.name(),
+            AiffChunkEnum::Mark(e) => e.name(),
+            AiffChunkEnum::Inst(e) => e.name(),
+            AiffChunkEnum::Fver(e) => e.name(),
+            AiffChunkEnum::Name(e) => e.name(),
+            AiffChunkEnum::Auth(e) => e.name(),
+            AiffChunkEnum::Copyright(e) => e.name(),
+            AiffChunkEnum::Anno(e) => e.name(),
+            AiffChunkEnum::Unknown(e) => e.name(),
+        }
+    }
+
+    fn item_summary_header(&self) -> String {
+        match self {
+            AiffChunkEnum::Comm(e) => e.item_summary_header(),
+            AiffChunkEnum::Ssnd(e) => e.item_summary_header(),
+            AiffChunkEnum::Mark(e) => e.item_summary_header(),
+            AiffChunkEnum::Inst(e) => e.item_summary_header(),
+            AiffChunkEnum::Fver(e) => e.item_summary_header(),
+            AiffChunkEnum::Name(e) => e.item_summary_header(),
+            AiffChunkEnum::Auth(e) => e.item_summary_header(),
+            AiffChunkEnum::Copyright(e) => e.item_summary_header(),
+            AiffChunkEnum::Anno(e) => e.item_summary_header(),
+            AiffChunkEnum::Unknown(e) => e.item_summary_header(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Wrapper around AIFF or AIFC binary data.
+#[derive(Debug)]
+pub struct AiffFile<R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    bytes: R,
+    form: FormChunk,
+    chunks_start: u64,
+}
+
+#[cfg(feature = "std")]
+impl<R> AiffFile<R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    /// Create a new `AiffFile` from a reader, checking for a `FORM` header
+    /// with an `AIFF` or `AIFC` form type.
+    pub fn from_reader(mut reader: R) -> Result<Self, WaveFileError> {
+        let form = FormChunk::read(&mut reader)?;
+        if form.form_type != FormChunk::AIFF && form.form_type != FormChunk::AIFC {
+            return Err(WaveFileError::UnknownFourCC {
+                found: form.form_type,
+                message: format!(
+                    "not an AIFF file. Expected FORM form_type 'AIFF' or 'AIFC', found: {}",
+                    form.form_type
+                ),
+            });
+        }
+        let chunks_start = reader.stream_position()?;
+        Ok(Self {
+            bytes: reader,
+            form,
+            chunks_start,
+        })
+    }
+
+    /// The `FORM` header.
+    pub fn form(&self) -> &FormChunk {
+        &self.form
+    }
+
+    /// True for AIFF-C files, which may hold compressed audio.
+    pub fn is_aifc(&self) -> bool {
+        self.form.form_type == FormChunk::AIFC
+    }
+
+    /// Parses AIFF data, returns an iterator over all chunks.
+    ///
+    /// Chunks end at the `FORM` size or the end of the stream, whichever
+    /// comes first. Iteration stops after the first error.
+    pub fn iter_chunks(&mut self) -> AiffFileIterator<'_, R> {
+        let form_end = 8 + u64::from(self.form.size);
+        let end = self
+            .bytes
+            .seek(SeekFrom::End(0))
+            .map_or(form_end, |len| len.min(form_end));
+        AiffFileIterator {
+            reader: &mut self.bytes,
+            offset: self.chunks_start,
+            end,
+            finished: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Implements [`AiffFile::iter_chunks()`].
+#[derive(Debug)]
+pub struct AiffFileIterator<'a, R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    reader: &'a mut R,
+    // offset of the next chunk
+    offset: u64,
+    // offset where chunk data ends, from FORM size or stream length
+    end: u64,
+    finished: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R> Iterator for AiffFileIterator<'_, R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    type Item = Result<AiffChunkEnum, WaveFileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.offset + 8 > self.end {
+            return None;
+        }
+        let result = self
+            .reader
+            .seek(SeekFrom::Start(self.offset))
+            .map_err(WaveFileError::from)
+            .and_then(|_| AiffChunkEnum::read(self.reader));
+        match result {
+            Ok(chunk) => {
+                // IFF chunks are padded to an even size, like RIFF
+                self.offset += chunk.total_size();
+                Some(Ok(chunk))
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::hex_to_cursor;
+
+    #[test]
+    fn extended_to_f64() {
+        let rate = |hex: &str| {
+            let bytes: [u8; 10] = hex::decode(hex).unwrap().try_into().unwrap();
+            Extended(bytes).to_f64()
+        };
+        assert_eq!(rate("400EBB80000000000000"), 48000.0);
+        assert_eq!(rate("400FBB80000000000000"), 96000.0);
+        assert_eq!(rate("400DAC44000000000000"), 22050.0);
+        assert_eq!(rate("3FFF8000000000000000"), 1.0);
+        assert_eq!(rate("BFFF8000000000000000"), -1.0);
+        assert_eq!(rate("00000000000000000000"), 0.0);
+        assert_eq!(rate("7FFF8000000000000000"), f64::INFINITY);
+    }
+
+    #[test]
+    fn parse_aifc() {
+        // FORM AIFC: FVER, COMM with compression, MARK, odd sized NAME, SSND
+        let data = "464F524D 00000088 41494643 \
+            46564552 00000004 A2805140 \
+            434F4D4D 00000018 0002 00000002 0010 400EBB80000000000000 4E4F4E45 00 00 \
+            4D41524B 0000000E 0001 0001 00000001 05 53746172 74 \
+            4E414D45 00000005 5261696E 21 00 \
+            494E5354 00000014 3C 00 00 7F 01 7F 0000 0001 0001 0002 0000 0000 0000 \
+            53534E44 00000010 00000000 00000000 00010002 00030004";
+        let mut aiff = AiffFile::from_reader(hex_to_cursor(data)).unwrap();
+        assert!(aiff.is_aifc());
+        let chunks: Vec<_> = aiff.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks);
+        let ids: Vec<_> = chunks.iter().map(|c| c.name()).collect();
+        assert_eq!(ids, ["FVER", "COMM", "MARK", "NAME", "INST", "SSND"]);
+
+        let AiffChunkEnum::Comm(comm) = &chunks[1] else {
+            unreachable!("second chunk should have been COMM")
+        };
+        assert_eq!(comm.data.compression_type, Some(FourCC(*b"NONE")));
+        assert_eq!(comm.data.compression_name.as_deref(), Some(""));
+        assert_eq!(comm.summary(), "NONE, 2 chan, 16/48000");
+
+        let AiffChunkEnum::Mark(mark) = &chunks[2] else {
+            unreachable!("third chunk should have been MARK")
+        };
+        assert_eq!(mark.data.markers[0].name, "Start");
+        assert_eq!(mark.data.markers[0].position, 1);
+
+        assert_eq!(chunks[3].summary(), "Rain!");
+        assert_eq!(chunks[3].offset(), Some(78));
+        assert_eq!(chunks[4].offset(), Some(92));
+        let AiffChunkEnum::Inst(inst) = &chunks[4] else {
+            unreachable!("fifth chunk should have been INST")
+        };
+        assert_eq!(inst.data.base_note, 60);
+        assert_eq!(inst.data.sustain_loop.to_string(), "forward, markers 1-2");
+
+        let AiffChunkEnum::Ssnd(ssnd) = &chunks[5] else {
+            unreachable!("last chunk should have been SSND")
+        };
+        assert_eq!(ssnd.data.audio_size, 8);
+    }
+
+    #[test]
+    fn parse_aiff_unknown_chunk() {
+        // FORM AIFF with an APPL chunk, FORM size larger than the data
+        let data = "464F524D 00001000 41494646 4150504C 00000003 616263 00";
+        let mut aiff = AiffFile::from_reader(hex_to_cursor(data)).unwrap();
+        assert!(!aiff.is_aifc());
+        let chunks: Vec<_> = aiff.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks);
+        let [AiffChunkEnum::Unknown(unknown)] = &chunks[..] else {
+            unreachable!("expected a single unknown chunk")
+        };
+        assert_eq!(unknown.id, FourCC(*b"APPL"));
+        assert_eq!(unknown.raw, b"abc");
+
+        // not AIFF
+        let riff = "52494646 04000000 57415645";
+        assert!(AiffFile::from_reader(hex_to_cursor(riff)).is_err());
+        let form = "464F524D 00000004 38535658";
+        assert!(AiffFile::from_reader(hex_to_cursor(form)).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! SQL statements for a catalog of parsed metadata, loaded into SQLite.
+//!
+//! A catalog has three tables: `files`, with the size and modification time
+//! of each file, `chunks`, with the id, size and summary of each chunk, and
+//! `items`, with the [`Summarizable::items()`] of each chunk. Rows are keyed
+//! on the file path, so a file can be replaced with [`file_sql()`] without
+//! touching the rest of the catalog.
+//!
+//! ```
+//! use wavrw::catalog::{file_sql, CatalogFile, SCHEMA};
+//!
+//! let file = CatalogFile {
+//!     path: "door.wav".to_string(),
+//!     modified: 1_700_000_000,
+//!     size: 1024,
+//!     error: None,
+//! };
+//! let sql = format!("{SCHEMA}{}", file_sql(&file, &[]));
+//! assert!(sql.contains("INSERT INTO files"));
+//! ```
+//!
+//! Example query, files with a `bext` description mentioning doors:
+//!
+//! ```sql
+//! SELECT path FROM items
+//! WHERE chunk_id = 'bext' AND key = 'description' AND value LIKE '%door%';
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{ChunkID, SizedChunk, SizedChunkEnum, Summarizable};
+
+/// Creates the catalog tables, if they don't exist yet.
+pub const SCHEMA: &str = "\
+CREATE TABLE IF NOT EXISTS files (
+    path TEXT PRIMARY KEY,
+    modified INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    error TEXT
+);
+CREATE TABLE IF NOT EXISTS chunks (
+    path TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    offset INTEGER,
+    chunk_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    PRIMARY KEY (path, chunk_index)
+);
+CREATE TABLE IF NOT EXISTS items (
+    path TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    chunk_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS items_path ON items (path);
+CREATE INDEX IF NOT EXISTS items_key ON items (key);
+";
+
+/// Lists the files in a catalog, one per line, in the format read by
+/// [`parse_files()`]. Paths are hex encoded, so any path can be read back.
+pub const FILES_QUERY: &str =
+    "SELECT hex(CAST(path AS BLOB)) || ' ' || modified || ' ' || size FROM files;";
+
+/// A file in a catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CatalogFile {
+    /// Path of the file, the key of all rows.
+    pub path: String,
+
+    /// Modification time, in seconds since the Unix epoch.
+    pub modified: u64,
+
+    /// Size of the file in bytes.
+    pub size: u64,
+
+    /// Error opening or parsing the file.
+    pub error: Option<String>,
+}
+
+impl CatalogFile {
+    /// True if `modified` and `size` match, and the file doesn't need to be
+    /// parsed again.
+    pub fn is_unchanged(&self, modified: u64, size: u64) -> bool {
+        self.modified == modified && self.size == size
+    }
+}
+
+/// Quote `text` as an SQL string literal.
+///
+/// Control characters, ex: NUL, are not written into the literal, where
+/// sqlite3 could misread them, but appended with `char()`.
+///
+/// ```
+/// use wavrw::catalog::quote;
+///
+/// assert_eq!(quote("Bob's door"), "'Bob''s door'");
+/// assert_eq!(quote("door\0"), "('door' || char(0) || '')");
+/// ```
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::from("'");
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            '\'' => quoted.push_str("''"),
+            c if c.is_control() => {
+                quoted.push_str(&format!("' || char({}) || '", u32::from(c)));
+                escaped = true;
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    if escaped {
+        format!("({quoted})")
+    } else {
+        quoted
+    }
+}
+
+/// Deletes `path` from all tables.
+pub fn delete_sql(path: &str) -> String {
+    let path = quote(path);
+    format!(
+        "DELETE FROM items WHERE path = {path};\n\
+         DELETE FROM chunks WHERE path = {path};\n\
+         DELETE FROM files WHERE path = {path};\n"
+    )
+}
+
+/// Replaces the rows of `file` with `file` and its `chunks`.
+pub fn file_sql(file: &CatalogFile, chunks: &[SizedChunkEnum]) -> String {
+    let mut sql = delete_sql(&file.path);
+    let path = quote(&file.path);
+    let error = file.error.as_deref().map_or("NULL".into(), quote);
+    sql.push_str(&format!(
+        "INSERT INTO files VALUES ({path}, {}, {}, {error});\n",
+        file.modified, file.size
+    ));
+    for (index, chunk) in chunks.iter().enumerate() {
+        let chunk_id = quote(chunk.id().to_string().trim_end());
+        let offset = chunk.offset().map_or("NULL".into(), |o| o.to_string());
+        sql.push_str(&format!(
+            "INSERT INTO chunks VALUES ({path}, {index}, {offset}, {chunk_id}, {}, {}, {});\n",
+            quote(&chunk.name()),
+            chunk.large_size(),
+            quote(&chunk.summary()),
+        ));
+        for (key, value) in chunk.items() {
+            sql.push_str(&format!(
+                "INSERT INTO items VALUES ({path}, {index}, {chunk_id}, {}, {});\n",
+                quote(&key),
+                quote(&value),
+            ));
+        }
+    }
+    sql
+}
+
+/// Parse the output of [`FILES_QUERY`]. Lines which can't be parsed are
+/// skipped.
+///
+/// ```
+/// use wavrw::catalog::parse_files;
+///
+/// let files = parse_files("646F6F722E776176 1700000000 1024\n");
+/// assert_eq!(files[0].path, "door.wav");
+/// assert!(files[0].is_unchanged(1_700_000_000, 1024));
+/// ```
+pub fn parse_files(output: &str) -> Vec<CatalogFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(' ');
+            let hex = fields.next()?;
+            let modified = fields.next()?.parse().ok()?;
+            let size = fields.next()?.parse().ok()?;
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            Some(CatalogFile {
+                path: String::from_utf8(bytes).ok()?,
+                modified,
+                size,
+                error: None,
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chunk::disp::{ClipboardFormat, Disp, DispChunk};
+
+    #[test]
+    fn file_rows() {
+        let file = CatalogFile {
+            path: "it's.wav".to_string(),
+            modified: 10,
+            size: 20,
+            error: Some("bad".to_string()),
+        };
+        let disp = Disp {
+            format: ClipboardFormat::Text,
+            data: b"Door\0".to_vec(),
+        };
+        let chunk = SizedChunkEnum::Disp(DispChunk::new(disp).unwrap());
+        let sql = file_sql(&file, &[chunk]);
+        dbg!(&sql);
+        assert!(sql.starts_with("DELETE FROM items WHERE path = 'it''s.wav';\n"));
+        assert!(sql.contains("INSERT INTO files VALUES ('it''s.wav', 10, 20, 'bad');\n"));
+        assert!(sql.contains(
+            "INSERT INTO chunks VALUES ('it''s.wav', 0, NULL, 'DISP', 'DISP', 9, 'Door');\n"
+        ));
+        assert!(
+            sql.contains("INSERT INTO items VALUES ('it''s.wav', 0, 'DISP', 'text', 'Door');\n")
+        );
+    }
+
+    #[test]
+    fn parse_files_output() {
+        let files = parse_files("C3A92E776176 1 2\nnot hex 1 2\n41 x 2\n\n");
+        dbg!(&files);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "é.wav");
+        assert!(!files[0].is_unchanged(1, 3));
+    }
+}
@@ -1,11 +1,17 @@
 //! `adtl` A `LIST` containing [`CuePoint`](crate::chunk::cue::CuePoint) annotation chunks: file, labl, ltxt, note. [RIFF1991](https://wavref.til.cafe/chunk/adtl/)
 
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use binrw::{binrw, helpers, NullString};
 use itertools::Itertools;
 
-use crate::{ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::chunk::cset::{language_dialect, RiffCountryCode};
+use crate::{codepage, ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
 
 #[binrw]
 #[br(little)]
@@ -26,6 +32,20 @@ pub struct ListAdtl {
 impl ListAdtl {
     /// Chunk id constant: `adtl`
     pub const LIST_TYPE: FourCC = FourCC(*b"adtl");
+
+    /// Text of each `labl` subchunk, keyed by cue point name.
+    pub fn labels(&self) -> BTreeMap<u32, String> {
+        self.chunks
+            .iter()
+            .filter_map(|c| {
+                if let AdtlEnum::Labl(labl) = c {
+                    Some((labl.data.name, labl.data.text.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
 }
 
 impl KnownChunkID for ListAdtl {
@@ -36,11 +56,10 @@ impl Summarizable for ListAdtl {
     fn summary(&self) -> String {
         self.chunks
             .iter()
-            .into_grouping_map_by(|c| c.id())
-            .fold(0, |acc, _key, _value| acc + 1)
-            .iter()
-            .map(|(g, c)| format!("{}({})", g, c))
+            .map(|c| c.id().to_string())
             .sorted_unstable()
+            .dedup_with_count()
+            .map(|(c, g)| format!("{}({})", g, c))
             .join(", ")
     }
 
@@ -125,14 +144,14 @@ pub struct Ltxt {
     /// Specifies the cue point name. This value must match one of the names listed in the `cue` chunk's [CuePoint][super::cue::CuePoint] table.
     pub name: u32,
 
-    /// Specifies the number of samples in the segment of waveform data. 	...>sample_length
+    /// Specifies the number of samples in the segment of waveform data.
     pub sample_length: u32,
 
     /// Specifies the type or purpose of the text. For example, dwPurpose can specify a FOURCC code like `scrp` for script text or `capt` for close-caption text. `rgn ` is commonly used for "region notes"
     pub purpose: FourCC,
 
     /// Specifies the country code for the text. See "Country Codes" in CSET chunk, for a current list of country codes.
-    pub country_code: u16,
+    pub country_code: RiffCountryCode,
 
     /// Specify the language for the text. See "Language and Dialect Codes" in CSET chunk, for a current list of language and dialect codes.
     pub language: u16,
@@ -143,14 +162,62 @@ pub struct Ltxt {
     ///	Specifies the code page for the text. See CSET chunk for details.
     pub code_page: u16,
 
-    /// The text associated with this range.
-    #[br(count = size as u64 -4 -4 -4 -2 -2 -2 -2, 
-        try_map = |v: Vec<u8>|  String::from_utf8(v) 
-    )]
-    #[bw(map = |s: &String| s.as_bytes())]
+    /// Text bytes as read, decoded into `text` using `code_page`. Ignored when writing.
+    #[br(parse_with = crate::read_bytes, args(size.saturating_sub(Ltxt::HEADER_SIZE).into()))]
+    #[bw(ignore)]
+    pub raw: Vec<u8>,
+
+    /// The text associated with this range, without trailing NULL bytes.
+    #[br(calc = codepage::decode(&raw, Some(code_page)))]
+    #[bw(map = |s: &String| Ltxt::text_bytes(s))]
     pub text: String,
 }
 
+impl Ltxt {
+    /// Size of the fields before the text.
+    const HEADER_SIZE: u32 = 20;
+
+    /// Creates a new [`Ltxt`] for the `sample_length` samples following cue point `name`.
+    pub fn new(name: u32, sample_length: u32, purpose: FourCC, text: &str) -> Self {
+        Ltxt {
+            name,
+            sample_length,
+            purpose,
+            country_code: RiffCountryCode::None,
+            language: 0,
+            dialect: 0,
+            code_page: codepage::UTF_8,
+            raw: Ltxt::text_bytes(text),
+            text: text.into(),
+        }
+    }
+
+    /// Text as written: UTF-8 and NULL terminated, nothing for empty text.
+    fn text_bytes(text: &str) -> Vec<u8> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        bytes
+    }
+
+    /// Language and dialect names, see [`language_dialect()`].
+    pub fn language_dialect(&self) -> Option<(&'static str, &'static str)> {
+        language_dialect(self.language, self.dialect)
+    }
+
+    /// Description of well known `purpose` codes.
+    pub fn purpose_name(&self) -> Option<&'static str> {
+        match &self.purpose.0 {
+            b"rgn " => Some("region"),
+            b"scrp" => Some("script"),
+            b"capt" => Some("caption"),
+            _ => None,
+        }
+    }
+}
+
 impl KnownChunkID for Ltxt {
     const ID: FourCC = FourCC(*b"ltxt");
 }
@@ -159,10 +226,34 @@ impl Summarizable for Ltxt {
     fn summary(&self) -> String {
         format!(
             "{:>3}, len:{}, purpose:{}, {}",
-            self.name,
-            self.sample_length,
-            self.purpose,
-            self.text
+            self.name, self.sample_length, self.purpose, self.text
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let (language, dialect) = self.language_dialect().unwrap_or(("Unknown", "Unknown"));
+        let purpose = match self.purpose_name() {
+            Some(purpose_name) => format!("{} ({purpose_name})", self.purpose),
+            None => self.purpose.to_string(),
+        };
+        Box::new(
+            [
+                ("name".to_string(), self.name.to_string()),
+                ("sample_length".to_string(), self.sample_length.to_string()),
+                ("purpose".to_string(), purpose),
+                ("country_code".to_string(), self.country_code.to_string()),
+                (
+                    "language".to_string(),
+                    format!("{language}({})", self.language),
+                ),
+                (
+                    "dialect".to_string(),
+                    format!("{dialect}({})", self.dialect),
+                ),
+                ("code_page".to_string(), self.code_page.to_string()),
+                ("text".to_string(), self.text.clone()),
+            ]
+            .into_iter(),
         )
     }
 }
@@ -185,23 +276,35 @@ pub struct File {
     pub name: u32,
 
     /// Specifies the file type contained in the `file_data` field. If the fileData section contains a RIFF form, the `media_type` field is the same as the RIFF form type for the file. This field can contain a zero value.
-    pub media_type: u32,
+    pub media_type: FourCC,
 
     /// Contains the media file.
-    #[br(count = size as u64 -4 -4 )]
+    #[br(parse_with = crate::read_bytes, args(size.saturating_sub(4 + 4).into()))]
     pub file_data: Vec<u8>,
 }
 
+impl File {
+    /// Form type of `file_data` if it contains a RIFF form. Ex: `RDIB`
+    pub fn riff_form_type(&self) -> Option<FourCC> {
+        let form = self.file_data.strip_prefix(b"RIFF")?.get(4..8)?;
+        Some(FourCC(form.try_into().ok()?))
+    }
+}
+
 impl KnownChunkID for File {
-    const ID: FourCC = FourCC(*b"ltxt");
+    const ID: FourCC = FourCC(*b"file");
 }
 
 impl Summarizable for File {
     fn summary(&self) -> String {
+        let form = match self.riff_form_type() {
+            Some(form) => format!(", RIFF-{form}"),
+            None => String::new(),
+        };
         format!(
-            "{:>3}, media_type:{}, {} bytes",
+            "{:>3}, media_type:{}, {} bytes{form}",
             self.name,
-            FourCC(self.media_type.to_le_bytes()),
+            self.media_type,
             self.file_data.len()
         )
     }
@@ -224,7 +327,7 @@ pub enum AdtlEnum {
         id: FourCC,
         size: u32,
         #[brw(align_after = 2)]
-        #[br(count = size, pad_size_to= size.to_owned())]
+        #[br(parse_with = crate::read_bytes, args(size.into()))]
         raw: Vec<u8>,
     },
 }
@@ -256,7 +359,10 @@ impl Summarizable for AdtlEnum {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
-    use binrw::BinRead;
+    use crate::testing::{Arbitrary, Rng};
+    use std::io::Cursor;
+
+    use binrw::{BinRead, BinWrite};
 
     use super::*;
     use crate::testing::hex_to_cursor;
@@ -276,4 +382,114 @@ mod test {
         assert_eq!(adtl.data.chunks[3].id(), FourCC(*b"labl"));
         assert_eq!(adtl.data.chunks[3].summary(), "  2, 1kHz Test");
     }
+
+    #[test]
+    fn ltxt_typed_fields() {
+        // ltxt with country 0x2c (UK), English UK, code page 1252 and text "Größe\0"
+        let mut buff = hex_to_cursor(
+            "6C747874 1A000000 07000000 80BB0000 72676E20 44000900 0200E404 4772F6DF 6500",
+        );
+        let ltxt = LtxtChunk::read(&mut buff).unwrap();
+        dbg!(&ltxt);
+        assert_eq!(ltxt.data.name, 7);
+        assert_eq!(ltxt.data.sample_length, 48000);
+        assert_eq!(ltxt.data.purpose_name(), Some("region"));
+        assert_eq!(ltxt.data.country_code, RiffCountryCode::UnitedKingdom);
+        assert_eq!(ltxt.data.language_dialect(), Some(("English", "UK")));
+        assert_eq!(ltxt.data.code_page, 1252);
+        assert_eq!(ltxt.data.text, "Größe");
+        assert_eq!(ltxt.data.items().count(), 8);
+    }
+
+    #[test]
+    fn ltxt_roundtrip() {
+        let ltxt = Ltxt::new(1, 240, FourCC(*b"rgn "), "region");
+        let mut buff = Cursor::new(Vec::new());
+        ltxt.write_le(&mut buff).unwrap();
+        let size = u32::try_from(buff.get_ref().len()).unwrap();
+        assert_eq!(size, 20 + 7);
+        buff.set_position(0);
+        let after = Ltxt::read_le_args(&mut buff, (size,)).unwrap();
+        assert_eq!(after, ltxt);
+    }
+
+    #[test]
+    fn file_riff_form_type() {
+        // file chunk, named 3, containing the start of a RIFF-RDIB form
+        let mut buff =
+            hex_to_cursor("66696C65 14000000 03000000 52444942 52494646 04000000 52444942");
+        let file = FileChunk::read(&mut buff).unwrap();
+        dbg!(&file);
+        assert_eq!(file.data.media_type, FourCC(*b"RDIB"));
+        assert_eq!(file.data.riff_form_type(), Some(FourCC(*b"RDIB")));
+        assert_eq!(
+            file.data.summary(),
+            "  3, media_type:RDIB, 12 bytes, RIFF-RDIB"
+        );
+    }
+
+    impl Arbitrary for ListAdtl {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let chunks = (0..rng.below(6))
+                .map(|_| match rng.below(5) {
+                    0 => AdtlEnum::Labl(
+                        LablChunk::new(Labl {
+                            name: rng.next_u32(),
+                            text: rng.ascii(40),
+                        })
+                        .unwrap(),
+                    ),
+                    1 => AdtlEnum::Note(
+                        NoteChunk::new(Note {
+                            name: rng.next_u32(),
+                            text: rng.ascii(40),
+                        })
+                        .unwrap(),
+                    ),
+                    2 => {
+                        let text = rng.ascii(40);
+                        AdtlEnum::Ltxt(
+                            LtxtChunk::new(Ltxt {
+                                name: rng.next_u32(),
+                                sample_length: rng.next_u32(),
+                                purpose: rng.fourcc(),
+                                country_code: RiffCountryCode::from(rng.next_u16()),
+                                language: rng.next_u16(),
+                                dialect: rng.next_u16(),
+                                code_page: 0,
+                                raw: Ltxt::text_bytes(&text),
+                                text,
+                            })
+                            .unwrap(),
+                        )
+                    }
+                    3 => AdtlEnum::File(
+                        FileChunk::new(File {
+                            name: rng.next_u32(),
+                            media_type: rng.fourcc(),
+                            file_data: rng.bytes(40),
+                        })
+                        .unwrap(),
+                    ),
+                    _ => {
+                        let raw = rng.bytes(40);
+                        AdtlEnum::Unknown {
+                            id: FourCC(*b"zzzz"),
+                            size: raw.len() as u32,
+                            raw,
+                        }
+                    }
+                })
+                .collect();
+            // round trip once, so sub-chunks have the offsets they get when read
+            crate::testing::roundtrip(&ListAdtl {
+                list_type: ListAdtl::LIST_TYPE,
+                chunks,
+            })
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        list_adtl_roundtrip: ListAdtl,
+    }
 }
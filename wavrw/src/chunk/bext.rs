@@ -1,26 +1,35 @@
 //! `bext` Broadcast Extension for motion picture, radio and television production. [BEXT1996](https://wavref.til.cafe/spec/bext1996/)
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
+use binrw::io::{ErrorKind, Read, Seek, SeekFrom};
 use binrw::{binrw, helpers};
 
-use crate::{fixedstring::FixedString, FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::codepage::Detection;
+use crate::fixedstring::{FixedString, FixedStringError};
+use crate::time::{Date, Rational, SampleTime, TimeOfDay, Timecode, Timestamp};
+use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable, WaveFileError};
 
 // BEXT, based on https://tech.ebu.ch/docs/tech/tech3285.pdf
 // BEXT is specified to use ASCII for strings, but we're parsing it as utf8,
 // since that is a superset of ASCII and many WAV files contain utf8 strings
 // in `bext` chunks.
-// 
-// Technically, this struct implements a BWF Version 2 parser. Previous versions
-// are specified to always pad extra data with NULL bytes, so loudness fields
-// added in V2 will usually default to 0s when reading a V1 or V0 chunk.
-// However, it is possible to have incorrect data in those fields when `version`
-// field is less than 2.
+//
+// All versions share the same fixed layout: V1 added the UMID and V2 the
+// loudness fields in space which was reserved before. The bytes are always
+// read into the V2 fields, so chunks round trip unchanged, but they only hold
+// meaningful values from the version which added them. Older writers did not
+// always fill the reserved space with NULL bytes, so `has_umid()`,
+// `has_loudness()`, the loudness helpers and `items()` check `version`.
 
 /// `bext` Broadcast Extension for motion picture, radio and television production. [BEXT1996](https://wavref.til.cafe/spec/bext1996/)
 #[binrw]
 #[brw(little)]
-#[br(import(_size: u32))]
+#[br(import(_size: u32), map_stream = |r| ZeroPadded::new(r, u64::from(Bext::FIXED_SIZE)))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Bext {
     /// Description of the sound sequence
@@ -72,6 +81,13 @@ impl KnownChunkID for Bext {
 }
 
 impl Bext {
+    /// Size of the fields before `coding_history`.
+    ///
+    /// Shorter chunks, as written by some portable recorders, are parsed as
+    /// if the missing bytes were zero. This leaves the missing fields empty
+    /// and the iterator records a [`Warning`](crate::Warning).
+    pub const FIXED_SIZE: u32 = 602;
+
     fn new() -> Bext {
         Bext {
             description: FixedString::<256>::default(),
@@ -91,6 +107,173 @@ impl Bext {
             coding_history: String::new(),
         }
     }
+
+    /// Decode all fixed length strings as read using `code_page`.
+    pub(crate) fn set_code_page(&mut self, code_page: u16) {
+        self.description.set_code_page(code_page);
+        self.originator.set_code_page(code_page);
+        self.originator_reference.set_code_page(code_page);
+        self.origination_date.set_code_page(code_page);
+        self.origination_time.set_code_page(code_page);
+    }
+
+    /// `time_reference` as a time since midnight, using the sample rate
+    /// from the `fmt ` chunk.
+    pub fn time_reference_time(&self, samples_per_sec: u32) -> SampleTime {
+        SampleTime::new(self.time_reference, samples_per_sec)
+    }
+
+    /// `time_reference` as a [`Timecode`] at `rate`, using the sample rate
+    /// from the `fmt ` chunk.
+    pub fn time_reference_timecode(
+        &self,
+        samples_per_sec: u32,
+        rate: Rational,
+        drop_frame: bool,
+    ) -> Timecode {
+        Timecode::from_samples(self.time_reference, samples_per_sec, rate, drop_frame)
+    }
+
+    /// Lower 32 bits of `time_reference`, `TimeReferenceLow` in the specification.
+    pub fn time_reference_low(&self) -> u32 {
+        (self.time_reference & 0xFFFF_FFFF) as u32
+    }
+
+    /// Upper 32 bits of `time_reference`, `TimeReferenceHigh` in the specification.
+    pub fn time_reference_high(&self) -> u32 {
+        (self.time_reference >> 32) as u32
+    }
+
+    /// True if `umid` is defined for this `version`, 1 or later. In version
+    /// 0 chunks the bytes are reserved.
+    pub fn has_umid(&self) -> bool {
+        self.version >= 1
+    }
+
+    /// True if the loudness fields are defined for this `version`, 2 or
+    /// later. In older chunks the bytes are reserved.
+    pub fn has_loudness(&self) -> bool {
+        self.version >= 2
+    }
+
+    /// Guessed encodings of the text fields which are not valid UTF-8, as
+    /// (field name, guess) pairs, see [`FixedString::detect_encoding()`].
+    pub fn detect_encodings(&self) -> Vec<(&'static str, Detection)> {
+        [
+            ("description", self.description.detect_encoding()),
+            ("originator", self.originator.detect_encoding()),
+            (
+                "originator_reference",
+                self.originator_reference.detect_encoding(),
+            ),
+            ("origination_date", self.origination_date.detect_encoding()),
+            ("origination_time", self.origination_time.detect_encoding()),
+        ]
+        .into_iter()
+        .filter_map(|(name, guess)| Some((name, guess?)))
+        .collect()
+    }
+
+    /// `umid`, `None` before version 1.
+    pub fn parsed_umid(&self) -> Option<&[u8; 64]> {
+        self.has_umid().then_some(&self.umid)
+    }
+
+    /// A loudness field scaled from hundredths, `None` before version 2.
+    fn scaled_loudness(&self, value: i16) -> Option<f32> {
+        self.has_loudness().then(|| f32::from(value) / 100.0)
+    }
+
+    /// `loudness_value` in LUFS, `None` before version 2. Ex: -23.5
+    pub fn loudness_value_lufs(&self) -> Option<f32> {
+        self.scaled_loudness(self.loudness_value)
+    }
+
+    /// `loudness_range` in LU, `None` before version 2.
+    pub fn loudness_range_lu(&self) -> Option<f32> {
+        self.scaled_loudness(self.loudness_range)
+    }
+
+    /// `max_true_peak_level` in dBTP, `None` before version 2. Ex: -1.5
+    pub fn max_true_peak_level_dbtp(&self) -> Option<f32> {
+        self.scaled_loudness(self.max_true_peak_level)
+    }
+
+    /// `max_momentary_loudness` in LUFS, `None` before version 2.
+    pub fn max_momentary_loudness_lufs(&self) -> Option<f32> {
+        self.scaled_loudness(self.max_momentary_loudness)
+    }
+
+    /// `max_short_term_loudness` in LUFS, `None` before version 2.
+    pub fn max_short_term_loudness_lufs(&self) -> Option<f32> {
+        self.scaled_loudness(self.max_short_term_loudness)
+    }
+
+    /// Parsed `origination_date`, `None` if empty or invalid.
+    pub fn parsed_origination_date(&self) -> Option<Date> {
+        self.origination_date.to_string().parse().ok()
+    }
+
+    /// Parsed `origination_time`, `None` if empty or invalid.
+    pub fn parsed_origination_time(&self) -> Option<TimeOfDay> {
+        self.origination_time.to_string().parse().ok()
+    }
+
+    /// Start of the recording: `origination_date` combined with the time of
+    /// day from `time_reference`.
+    ///
+    /// Returns `None` if `origination_date` can't be parsed or
+    /// `samples_per_sec` is 0. The date is not advanced if `time_reference`
+    /// is more than 24 hours.
+    pub fn start_timestamp(&self, samples_per_sec: u32) -> Option<Timestamp> {
+        let seconds = self.time_reference_time(samples_per_sec).seconds()?;
+        Some(Timestamp {
+            date: self.parsed_origination_date()?,
+            time: TimeOfDay::from_seconds(seconds),
+            sample_offset: self.time_reference,
+        })
+    }
+
+    /// Append `entry` as a line of `coding_history`, ending in CR LF.
+    ///
+    /// A last line without a line ending, or ending in a bare LF or CR, is
+    /// terminated with CR LF first. The chunk size is calculated from the
+    /// new text when the chunk is written, ex: by
+    /// [`KnownChunk::new()`] or `edit::inject_chunk()`.
+    ///
+    /// Returns an error if a field of `entry` can't be written on one line,
+    /// see [`CodingHistoryEntry`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::bext::{Bext, CodingHistoryEntry};
+    ///
+    /// let mut bext = Bext::builder()
+    ///     .coding_history("A=ANALOGUE,M=stereo,T=Studer A820")
+    ///     .build()?;
+    /// bext.append_coding_history(&CodingHistoryEntry::pcm(48_000, 24, 2).with_text("wavrw"))?;
+    /// assert_eq!(
+    ///     bext.coding_history,
+    ///     "A=ANALOGUE,M=stereo,T=Studer A820\r\nA=PCM,F=48000,W=24,M=stereo,T=wavrw\r\n"
+    /// );
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn append_coding_history(
+        &mut self,
+        entry: &CodingHistoryEntry,
+    ) -> Result<(), WaveFileError> {
+        entry.check()?;
+        let history = self.coding_history.trim_end_matches(['\r', '\n']);
+        let mut coding_history = history.to_string();
+        if !coding_history.is_empty() {
+            coding_history.push_str("\r\n");
+        }
+        coding_history.push_str(&entry.to_string());
+        coding_history.push_str("\r\n");
+        self.coding_history = coding_history;
+        Ok(())
+    }
 }
 
 impl Default for Bext {
@@ -99,6 +282,422 @@ impl Default for Bext {
     }
 }
 
+impl Bext {
+    /// Start building a `bext` chunk, see [`BextBuilder`].
+    pub fn builder() -> BextBuilder {
+        BextBuilder::default()
+    }
+}
+
+/// Builds a version 2 [`Bext`], checking text fits the fixed length fields.
+///
+/// Fields not set are empty or zero, reserved bytes are always zero.
+///
+/// # Examples
+///
+/// ```
+/// use wavrw::chunk::bext::Bext;
+/// use wavrw::time::{Date, TimeOfDay};
+///
+/// let bext = Bext::builder()
+///     .description("Rain on a tin roof")
+///     .originator("wavrw")
+///     .origination_date("2024-05-06".parse::<Date>()?)
+///     .origination_time(TimeOfDay::from_seconds(3600))
+///     .time_reference(48_000 * 3600)
+///     .loudness_value(-23.5)
+///     .build()?;
+/// assert_eq!(bext.origination_time.to_string(), "01:00:00");
+/// assert_eq!(bext.loudness_value, -2350);
+///
+/// let err = Bext::builder().originator("a name which is longer than 32 bytes").build();
+/// assert!(err.is_err());
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BextBuilder {
+    description: String,
+    originator: String,
+    originator_reference: String,
+    origination_date: Option<Date>,
+    origination_time: Option<TimeOfDay>,
+    time_reference: u64,
+    umid: Option<[u8; 64]>,
+    loudness_value: Option<f64>,
+    loudness_range: Option<f64>,
+    max_true_peak_level: Option<f64>,
+    max_momentary_loudness: Option<f64>,
+    max_short_term_loudness: Option<f64>,
+    coding_history: String,
+}
+
+impl BextBuilder {
+    /// Description of the sound sequence, at most 256 bytes.
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Name of the originator, at most 32 bytes.
+    #[must_use]
+    pub fn originator(mut self, originator: &str) -> Self {
+        self.originator = originator.to_string();
+        self
+    }
+
+    /// Reference of the originator, at most 32 bytes.
+    #[must_use]
+    pub fn originator_reference(mut self, originator_reference: &str) -> Self {
+        self.originator_reference = originator_reference.to_string();
+        self
+    }
+
+    /// Date of creation, written as `yyyy-mm-dd`.
+    #[must_use]
+    pub fn origination_date(mut self, date: Date) -> Self {
+        self.origination_date = Some(date);
+        self
+    }
+
+    /// Time of creation, written as `hh:mm:ss`.
+    #[must_use]
+    pub fn origination_time(mut self, time: TimeOfDay) -> Self {
+        self.origination_time = Some(time);
+        self
+    }
+
+    /// First sample count since midnight.
+    #[must_use]
+    pub fn time_reference(mut self, time_reference: u64) -> Self {
+        self.time_reference = time_reference;
+        self
+    }
+
+    /// SMPTE UMID, raw bytes.
+    #[must_use]
+    pub fn umid(mut self, umid: [u8; 64]) -> Self {
+        self.umid = Some(umid);
+        self
+    }
+
+    /// Integrated loudness in LUFS, ex: -23.0
+    #[must_use]
+    pub fn loudness_value(mut self, lufs: f64) -> Self {
+        self.loudness_value = Some(lufs);
+        self
+    }
+
+    /// Loudness range in LU.
+    #[must_use]
+    pub fn loudness_range(mut self, lu: f64) -> Self {
+        self.loudness_range = Some(lu);
+        self
+    }
+
+    /// Maximum true peak level in dBTP.
+    #[must_use]
+    pub fn max_true_peak_level(mut self, dbtp: f64) -> Self {
+        self.max_true_peak_level = Some(dbtp);
+        self
+    }
+
+    /// Highest momentary loudness in LUFS.
+    #[must_use]
+    pub fn max_momentary_loudness(mut self, lufs: f64) -> Self {
+        self.max_momentary_loudness = Some(lufs);
+        self
+    }
+
+    /// Highest short-term loudness in LUFS.
+    #[must_use]
+    pub fn max_short_term_loudness(mut self, lufs: f64) -> Self {
+        self.max_short_term_loudness = Some(lufs);
+        self
+    }
+
+    /// Coding history, one line per coding process, each ending in `\r\n`.
+    #[must_use]
+    pub fn coding_history(mut self, coding_history: &str) -> Self {
+        self.coding_history = coding_history.to_string();
+        self
+    }
+
+    /// Create the [`Bext`].
+    ///
+    /// Returns an error if text does not fit its field or contains a zero
+    /// byte, or a loudness value is out of range.
+    pub fn build(self) -> Result<Bext, WaveFileError> {
+        Ok(Bext {
+            description: fixed_string("description", &self.description)?,
+            originator: fixed_string("originator", &self.originator)?,
+            originator_reference: fixed_string("originator_reference", &self.originator_reference)?,
+            origination_date: fixed_string(
+                "origination_date",
+                &self
+                    .origination_date
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+            )?,
+            origination_time: fixed_string(
+                "origination_time",
+                &self
+                    .origination_time
+                    .map(|t| t.to_string())
+                    .unwrap_or_default(),
+            )?,
+            time_reference: self.time_reference,
+            version: 2,
+            umid: self.umid.unwrap_or([0_u8; 64]),
+            loudness_value: loudness("loudness_value", self.loudness_value)?,
+            loudness_range: loudness("loudness_range", self.loudness_range)?,
+            max_true_peak_level: loudness("max_true_peak_level", self.max_true_peak_level)?,
+            max_momentary_loudness: loudness(
+                "max_momentary_loudness",
+                self.max_momentary_loudness,
+            )?,
+            max_short_term_loudness: loudness(
+                "max_short_term_loudness",
+                self.max_short_term_loudness,
+            )?,
+            reserved: [0u8; 180],
+            coding_history: self.coding_history,
+        })
+    }
+}
+
+/// One line of `bext` coding history, as specified by EBU R 98. See
+/// [`Bext::append_coding_history()`].
+///
+/// Formatted with [`Display`](core::fmt::Display) as the fields which are
+/// set, in R 98 order and without line ending. Ex:
+/// `A=PCM,F=48000,W=24,M=stereo,T=wavrw`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CodingHistoryEntry {
+    /// `A=` coding algorithm. Ex: `PCM`, `ANALOGUE`, `MPEG1L2`
+    pub algorithm: String,
+    /// `F=` sample rate in Hz.
+    pub sample_rate: Option<u32>,
+    /// `B=` bit rate in kbit/s, for compressed audio.
+    pub bit_rate: Option<u32>,
+    /// `W=` word length in bits.
+    pub word_length: Option<u16>,
+    /// `M=` mode. Ex: `mono`, `stereo`, `dual-mono`, `multitrack`
+    pub mode: Option<String>,
+    /// `T=` free text, may contain commas.
+    pub text: Option<String>,
+}
+
+impl CodingHistoryEntry {
+    /// An entry for coding `algorithm`, without other fields.
+    pub fn new(algorithm: &str) -> Self {
+        CodingHistoryEntry {
+            algorithm: algorithm.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// A `PCM` entry, with mode `mono` for 1 channel, `stereo` for 2 and
+    /// `multitrack` otherwise.
+    pub fn pcm(sample_rate: u32, word_length: u16, channels: u16) -> Self {
+        let mode = match channels {
+            1 => "mono",
+            2 => "stereo",
+            _ => "multitrack",
+        };
+        CodingHistoryEntry::new("PCM")
+            .with_sample_rate(sample_rate)
+            .with_word_length(word_length)
+            .with_mode(mode)
+    }
+
+    /// Set [`CodingHistoryEntry::sample_rate`].
+    #[must_use]
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Set [`CodingHistoryEntry::bit_rate`].
+    #[must_use]
+    pub fn with_bit_rate(mut self, bit_rate: u32) -> Self {
+        self.bit_rate = Some(bit_rate);
+        self
+    }
+
+    /// Set [`CodingHistoryEntry::word_length`].
+    #[must_use]
+    pub fn with_word_length(mut self, word_length: u16) -> Self {
+        self.word_length = Some(word_length);
+        self
+    }
+
+    /// Set [`CodingHistoryEntry::mode`].
+    #[must_use]
+    pub fn with_mode(mut self, mode: &str) -> Self {
+        self.mode = Some(mode.to_string());
+        self
+    }
+
+    /// Set [`CodingHistoryEntry::text`].
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Check the entry fits on one line: no line breaks or zero bytes, and
+    /// no commas outside of `text`.
+    fn check(&self) -> Result<(), WaveFileError> {
+        let fields = [
+            ("algorithm", Some(&self.algorithm)),
+            ("mode", self.mode.as_ref()),
+            ("text", self.text.as_ref()),
+        ];
+        for (field, value) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+            if value.contains(['\r', '\n', '\0']) || (field != "text" && value.contains(',')) {
+                return Err(WaveFileError::parse(
+                    None,
+                    format!("bext coding_history {field} can't contain {value:?}"),
+                ));
+            }
+        }
+        if self.algorithm.is_empty() {
+            return Err(WaveFileError::parse(
+                None,
+                "bext coding_history algorithm is empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for CodingHistoryEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "A={}", self.algorithm)?;
+        if let Some(sample_rate) = self.sample_rate {
+            write!(f, ",F={sample_rate}")?;
+        }
+        if let Some(bit_rate) = self.bit_rate {
+            write!(f, ",B={bit_rate}")?;
+        }
+        if let Some(word_length) = self.word_length {
+            write!(f, ",W={word_length}")?;
+        }
+        if let Some(mode) = &self.mode {
+            write!(f, ",M={mode}")?;
+        }
+        if let Some(text) = &self.text {
+            write!(f, ",T={text}")?;
+        }
+        Ok(())
+    }
+}
+
+fn fixed_string<const N: usize>(field: &str, text: &str) -> Result<FixedString<N>, WaveFileError> {
+    FixedString::try_from_str(text)
+        .map_err(|err: FixedStringError| WaveFileError::parse(None, format!("bext {field}: {err}")))
+}
+
+/// Loudness stored multiplied by 100, 0 if not set.
+fn loudness(field: &str, value: Option<f64>) -> Result<i16, WaveFileError> {
+    let Some(value) = value else {
+        return Ok(0);
+    };
+    // f64::round() needs std, round half away from zero by hand
+    let scaled = value * 100.0 + if value < 0.0 { -0.5 } else { 0.5 };
+    if !(scaled > f64::from(i16::MIN) - 1.0 && scaled < f64::from(i16::MAX) + 1.0) {
+        return Err(WaveFileError::parse(
+            None,
+            format!("bext {field} out of range: {value}"),
+        ));
+    }
+    // range checked above
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(scaled as i16)
+}
+
+/// A loudness field as stored and scaled, with two decimals whatever the
+/// locale. Ex: `-50 (-0.50 dBTP)`
+fn loudness_text(value: i16, unit: &str) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let abs = value.unsigned_abs();
+    format!("{value} ({sign}{}.{:02} {unit})", abs / 100, abs % 100)
+}
+
+/// Reads `inner`, then zeros until at least `min_len` bytes have been read.
+///
+/// Used to parse truncated `bext` chunks with defaults for missing fields.
+/// `inner` is already limited to the chunk size by [`KnownChunk`].
+#[derive(Debug)]
+struct ZeroPadded<R> {
+    inner: R,
+    min_len: u64,
+    // position within the padded stream
+    pos: u64,
+    // bytes read from `inner`, they end at `inner_len` once known
+    inner_pos: u64,
+    inner_len: Option<u64>,
+}
+
+impl<R: Read + Seek> ZeroPadded<R> {
+    fn new(inner: R, min_len: u64) -> Self {
+        ZeroPadded {
+            inner,
+            min_len,
+            pos: 0,
+            inner_pos: 0,
+            inner_len: None,
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for ZeroPadded<R> {
+    fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+        if self.inner_len.is_none() {
+            let len = self.inner.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                self.pos += len as u64;
+                self.inner_pos += len as u64;
+                return Ok(len);
+            }
+            self.inner_len = Some(self.inner_pos);
+        }
+        let zeros = buf
+            .len()
+            .min(usize::try_from(self.min_len.saturating_sub(self.pos)).unwrap_or(usize::MAX));
+        buf[..zeros].fill(0);
+        self.pos += zeros as u64;
+        Ok(zeros)
+    }
+}
+
+impl<R: Read + Seek> Seek for ZeroPadded<R> {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+            SeekFrom::End(_) => None,
+        }
+        .ok_or_else(|| binrw::io::Error::from(ErrorKind::InvalidInput))?;
+        // past the end of `inner` only the position changes
+        let inner_target = self.inner_len.map_or(target, |len| target.min(len));
+        let delta = i64::try_from(inner_target)
+            .and_then(|t| i64::try_from(self.inner_pos).map(|p| t - p))
+            .map_err(|_| binrw::io::Error::from(ErrorKind::InvalidInput))?;
+        self.inner.seek(SeekFrom::Current(delta))?;
+        self.inner_pos = inner_target;
+        if self.inner_len.is_some_and(|len| target < len) {
+            self.inner_len = None;
+        }
+        self.pos = target;
+        Ok(target)
+    }
+}
+
 /// `bext` Broadcast Extension for motion picture, radio and television production. [BEXT1996](https://wavref.til.cafe/spec/bext1996/)
 pub type BextChunk = KnownChunk<Bext>;
 
@@ -110,8 +709,18 @@ impl Summarizable for Bext {
         )
     }
 
+    /// The fields, see [`BextDataIterator`]. Text which is not valid UTF-8 is
+    /// followed by its guessed encoding, keyed `{field}.encoding`, see
+    /// [`Bext::detect_encodings()`].
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
-        Box::new(self.into_iter())
+        let encodings = self.detect_encodings();
+        Box::new(self.into_iter().flat_map(move |(key, value)| {
+            let encoding = encodings
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(name, guess)| (format!("{name}.encoding"), guess.to_string()));
+            core::iter::once((key, value)).chain(encoding)
+        }))
     }
 
     fn item_summary_header(&self) -> String {
@@ -132,6 +741,8 @@ impl<'a> IntoIterator for &'a Bext {
 }
 
 /// Iterate over fields as tuple of Strings (name, value).
+///
+/// Skips `umid` before version 1 and the loudness fields before version 2.
 #[derive(Debug)]
 pub struct BextDataIterator<'a> {
     data: &'a Bext,
@@ -142,6 +753,12 @@ impl<'a> Iterator for BextDataIterator<'a> {
     type Item = (String, String);
     fn next(&mut self) -> Option<(String, String)> {
         self.index += 1;
+        if self.index == 8 && !self.data.has_umid() {
+            self.index = 9;
+        }
+        if (9..=13).contains(&self.index) && !self.data.has_loudness() {
+            self.index = 14;
+        }
         match self.index {
             1 => Some(("description".to_string(), self.data.description.to_string())),
             2 => Some(("originator".to_string(), self.data.originator.to_string())),
@@ -165,23 +782,23 @@ impl<'a> Iterator for BextDataIterator<'a> {
             8 => Some(("umid".to_string(), hex::encode(self.data.umid))),
             9 => Some((
                 "loudness_value".to_string(),
-                self.data.loudness_value.to_string(),
+                loudness_text(self.data.loudness_value, "LUFS"),
             )),
             10 => Some((
                 "loudness_range".to_string(),
-                self.data.loudness_range.to_string(),
+                loudness_text(self.data.loudness_range, "LU"),
             )),
             11 => Some((
                 "max_true_peak_level".to_string(),
-                self.data.max_true_peak_level.to_string(),
+                loudness_text(self.data.max_true_peak_level, "dBTP"),
             )),
             12 => Some((
                 "max_momentary_loudness".to_string(),
-                self.data.max_momentary_loudness.to_string(),
+                loudness_text(self.data.max_momentary_loudness, "LUFS"),
             )),
             13 => Some((
                 "max_short_term_loudness".to_string(),
-                self.data.max_short_term_loudness.to_string(),
+                loudness_text(self.data.max_short_term_loudness, "LUFS"),
             )),
             14 => Some((
                 "coding_history".to_string(),
@@ -195,7 +812,8 @@ impl<'a> Iterator for BextDataIterator<'a> {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
-    use binrw::BinRead;
+    use crate::testing::{Arbitrary, Rng};
+    use binrw::{BinRead, BinWrite};
     use core::str::FromStr;
     use hex::decode;
 
@@ -258,6 +876,25 @@ mod test {
             "origination_time"
         );
         assert_eq!(bext.data.time_reference, 12345, "time_reference");
+        assert_eq!(
+            bext.data.time_reference_time(48_000).to_string(),
+            "00:00:00.257"
+        );
+        assert_eq!(
+            bext.data
+                .time_reference_timecode(48_000, Rational::from(25), false)
+                .to_string(),
+            "00:00:00:06"
+        );
+        assert_eq!(bext.data.time_reference_low(), 12345);
+        assert_eq!(bext.data.time_reference_high(), 0);
+        assert_eq!(
+            bext.data.parsed_origination_time().unwrap().to_string(),
+            "03:04:05"
+        );
+        let start = bext.data.start_timestamp(10).unwrap();
+        assert_eq!(start.to_string(), "2006-01-02 00:20:34");
+        assert_eq!(start.sample_offset, 12345);
         assert_eq!(bext.data.version, 2);
         assert_eq!(
             bext.data.umid,
@@ -282,6 +919,71 @@ mod test {
         assert_eq!(bext.data.coding_history, "CodingHistory", "coding_history");
     }
 
+    #[test]
+    fn bext_loudness() {
+        let bext = Bext {
+            loudness_value: -2350,
+            loudness_range: 720,
+            max_true_peak_level: -50,
+            max_momentary_loudness: -5,
+            max_short_term_loudness: i16::MIN,
+            version: 2,
+            ..Default::default()
+        };
+        dbg!(&bext);
+        assert_eq!(bext.loudness_value_lufs(), Some(-23.5));
+        assert_eq!(bext.loudness_range_lu(), Some(7.2));
+        assert_eq!(bext.max_true_peak_level_dbtp(), Some(-0.5));
+        let items: Vec<(String, String)> = bext.items().collect();
+        let item = |name: &str| {
+            items
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(item("loudness_value"), Some("-2350 (-23.50 LUFS)"));
+        assert_eq!(item("loudness_range"), Some("720 (7.20 LU)"));
+        assert_eq!(item("max_true_peak_level"), Some("-50 (-0.50 dBTP)"));
+        assert_eq!(item("max_momentary_loudness"), Some("-5 (-0.05 LUFS)"));
+        assert_eq!(
+            item("max_short_term_loudness"),
+            Some("-32768 (-327.68 LUFS)")
+        );
+    }
+
+    #[test]
+    fn bext_versions() {
+        // old writers may leave data in reserved bytes
+        let mut bext = Bext {
+            umid: [0xFF; 64],
+            loudness_value: 0x2020,
+            ..Default::default()
+        };
+        let names = |bext: &Bext| -> Vec<String> { bext.items().map(|(key, _)| key).collect() };
+
+        assert!(!bext.has_umid());
+        assert_eq!(bext.parsed_umid(), None);
+        assert_eq!(bext.loudness_value_lufs(), None);
+        let v0 = names(&bext);
+        dbg!(&v0);
+        assert_eq!(v0.len(), 8);
+        assert!(!v0.contains(&"umid".to_string()));
+        assert!(!v0.contains(&"loudness_value".to_string()));
+        assert_eq!(v0.last().unwrap(), "coding_history");
+
+        bext.version = 1;
+        assert_eq!(bext.parsed_umid(), Some(&[0xFF; 64]));
+        assert!(!bext.has_loudness());
+        let v1 = names(&bext);
+        assert_eq!(v1.len(), 9);
+        assert!(v1.contains(&"umid".to_string()));
+        assert!(!v1.contains(&"max_true_peak_level".to_string()));
+
+        bext.version = 2;
+        assert_eq!(bext.loudness_value_lufs(), Some(82.24));
+        assert_eq!(names(&bext).len(), 14);
+    }
+
     #[test]
     fn parse_h1e_coding_history() {
         // bext chunk from an h1e recorder. Extra \x0 data at the end of coding history
@@ -320,4 +1022,180 @@ mod test {
             "coding_history"
         );
     }
+
+    #[test]
+    fn parse_short_bext() {
+        // cut short after origination_time, no time_reference or later fields
+        let mut chunk = b"bext".to_vec();
+        chunk.extend_from_slice(&338_u32.to_le_bytes());
+        let mut data = vec![0_u8; 338];
+        data[..4].copy_from_slice(b"Desc");
+        data[320..330].copy_from_slice(b"2024-01-02");
+        chunk.extend_from_slice(&data);
+
+        let bext = BextChunk::read(&mut binrw::io::Cursor::new(&chunk)).unwrap();
+        dbg!(&bext);
+        assert_eq!(bext.size, 338);
+        assert_eq!(bext.data.description.to_string(), "Desc");
+        assert_eq!(bext.data.origination_date.to_string(), "2024-01-02");
+        assert_eq!(bext.data.time_reference, 0);
+        assert_eq!(bext.data.version, 0);
+        assert_eq!(bext.data.coding_history, "");
+        assert!(bext.extra_bytes.is_empty());
+
+        let mut empty = hex_to_cursor("62657874 00000000");
+        let bext = BextChunk::read(&mut empty).unwrap();
+        assert_eq!(bext.data, Bext::default());
+
+        // reading the file records a warning
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(4 + 346_u32).to_le_bytes());
+        file.extend_from_slice(b"WAVE");
+        file.extend_from_slice(&chunk);
+        let mut wave = crate::WaveFile::from_reader(binrw::io::Cursor::new(file)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        assert_eq!(chunks.len(), 1);
+        let warnings = wave.warnings();
+        dbg!(warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, Bext::ID);
+        assert!(warnings[0].message.contains("602"));
+    }
+
+    #[test]
+    fn bext_not_utf8() {
+        // description "Café" in Windows-1252, originator "テスト" in Shift-JIS
+        let mut chunk = b"bext".to_vec();
+        chunk.extend_from_slice(&602_u32.to_le_bytes());
+        let mut data = vec![0_u8; 602];
+        data[..4].copy_from_slice(b"Caf\xE9");
+        data[256..262].copy_from_slice(&[0x83, 0x65, 0x83, 0x58, 0x83, 0x67]);
+        chunk.extend_from_slice(&data);
+
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(4 + 610_u32).to_le_bytes());
+        file.extend_from_slice(b"WAVE");
+        file.extend_from_slice(&chunk);
+        let mut wave = crate::WaveFile::from_reader(binrw::io::Cursor::new(file)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        let warnings = wave.warnings();
+        dbg!(warnings);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            warnings[0].message,
+            "description text is not valid UTF-8, decoded as Latin-1, \
+             likely Windows-1252 (code page 1252), 90% confidence"
+        );
+        assert!(warnings[1].message.contains("Shift-JIS (code page 932)"));
+
+        let items: Vec<_> = chunks[0].items().take(4).collect();
+        dbg!(&items);
+        assert_eq!(items[0], ("description".to_string(), "Café".to_string()));
+        assert_eq!(items[1].0, "description.encoding");
+        assert_eq!(items[2].0, "originator");
+        assert_eq!(items[3].0, "originator.encoding");
+
+        // text decoded with a code page isn't annotated
+        let mut chunk = chunks[0].clone();
+        chunk.set_code_page(crate::codepage::WINDOWS_1251);
+        assert!(chunk.items().all(|(key, _)| !key.ends_with(".encoding")));
+    }
+
+    #[test]
+    fn build_bext() {
+        let bext = Bext::builder()
+            .description("Description")
+            .originator("Originator")
+            .origination_date(Date::from_str("2006-01-02").unwrap())
+            .time_reference(12345)
+            .loudness_range(5.5)
+            .coding_history("A=PCM,F=48000,W=24,M=mono\r\n")
+            .build()
+            .unwrap();
+        let chunk = BextChunk::new(bext).unwrap();
+        assert_eq!(chunk.size, 602 + 27);
+        let mut buff = binrw::io::Cursor::new(Vec::new());
+        chunk.write(&mut buff).unwrap();
+        buff.set_position(0);
+        let read = BextChunk::read(&mut buff).unwrap();
+        dbg!(&read);
+        assert_eq!(read.data, chunk.data);
+        assert_eq!(read.data.version, 2);
+        assert_eq!(read.data.loudness_range, 550);
+        assert_eq!(read.data.origination_time.to_string(), "");
+
+        assert!(Bext::builder().description("a\0b").build().is_err());
+        assert!(Bext::builder().loudness_value(-400.0).build().is_err());
+    }
+
+    #[test]
+    fn append_coding_history() {
+        let mut bext = Bext::default();
+        let entry = CodingHistoryEntry::new("MPEG1L2")
+            .with_sample_rate(48_000)
+            .with_bit_rate(384)
+            .with_mode("dual-mono")
+            .with_text("from tape, reel 2");
+        bext.append_coding_history(&entry).unwrap();
+        assert_eq!(
+            bext.coding_history,
+            "A=MPEG1L2,F=48000,B=384,M=dual-mono,T=from tape, reel 2\r\n"
+        );
+
+        // bare LF line endings are fixed up for the previous line
+        bext.coding_history = "A=ANALOGUE\n".to_string();
+        bext.append_coding_history(&CodingHistoryEntry::pcm(44_100, 16, 6))
+            .unwrap();
+        dbg!(&bext.coding_history);
+        assert_eq!(
+            bext.coding_history,
+            "A=ANALOGUE\r\nA=PCM,F=44100,W=16,M=multitrack\r\n"
+        );
+
+        let chunk = BextChunk::new(bext.clone()).unwrap();
+        assert_eq!(chunk.size as usize, 602 + bext.coding_history.len());
+
+        for bad in [
+            CodingHistoryEntry::new(""),
+            CodingHistoryEntry::new("PCM").with_mode("a,b"),
+            CodingHistoryEntry::new("PCM").with_text("two\r\nlines"),
+        ] {
+            assert!(bext.append_coding_history(&bad).is_err(), "{bad:?}");
+        }
+        assert!(bext.coding_history.ends_with("M=multitrack\r\n"));
+    }
+
+    impl Arbitrary for Bext {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let mut umid = [0u8; 64];
+            for b in &mut umid {
+                *b = rng.next_u8();
+            }
+            let mut reserved = [0u8; 180];
+            for b in &mut reserved {
+                *b = rng.next_u8();
+            }
+            Bext {
+                description: FixedString::from_str_lossy(&rng.ascii(256)),
+                originator: FixedString::from_str_lossy(&rng.ascii(32)),
+                originator_reference: FixedString::from_str_lossy(&rng.ascii(32)),
+                origination_date: FixedString::from_str_lossy(&rng.ascii(10)),
+                origination_time: FixedString::from_str_lossy(&rng.ascii(8)),
+                time_reference: rng.next_u64(),
+                version: rng.next_u16(),
+                umid,
+                loudness_value: rng.next_u16() as i16,
+                loudness_range: rng.next_u16() as i16,
+                max_true_peak_level: rng.next_u16() as i16,
+                max_momentary_loudness: rng.next_u16() as i16,
+                max_short_term_loudness: rng.next_u16() as i16,
+                reserved,
+                coding_history: rng.ascii(200),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        bext_roundtrip: Bext,
+    }
 }
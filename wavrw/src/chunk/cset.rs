@@ -1,8 +1,9 @@
 //! `CSET` Character set information. Code page, language, etc. Very Rare. [RIFF1991](https://wavref.til.cafe/chunk/cset/)
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
 use core::fmt::{Debug, Display, Formatter};
-use std::collections::HashMap;
-use std::sync::OnceLock;
 
 use binrw::binrw;
 use num_enum::{FromPrimitive, IntoPrimitive};
@@ -31,14 +32,14 @@ pub struct Cset {
 
     /// Specify the language and dialect used for file elements.
     ///
-    /// See cset_ld_map, for a list of language and dialect codes. If the CSET
+    /// See [`language_dialect()`], for a list of language and dialect codes. If the CSET
     /// chunk is not present, or if these fields have value zero, assume US
     /// English (language code 9, dialect code 1).
     pub language: u16,
 
     /// Specify the language and dialect used for file elements.
     ///
-    /// See cset_ld_map, for a list of language and dialect codes. If the CSET
+    /// See [`language_dialect()`], for a list of language and dialect codes. If the CSET
     /// chunk is not present, or if these fields have value zero, assume US
     /// English (language code 9, dialect code 1).
     pub dialect: u16,
@@ -50,9 +51,8 @@ impl KnownChunkID for Cset {
 
 impl Summarizable for Cset {
     fn summary(&self) -> String {
-        let (language, dialect) = cset_ld_map()
-            .get(&(self.language, self.dialect))
-            .unwrap_or(&("Unknown", "Unknown"));
+        let (language, dialect) =
+            language_dialect(self.language, self.dialect).unwrap_or(("Unknown", "Unknown"));
         format!(
             "code_page: ({}), {}, {language}({}), {dialect}({})",
             self.code_page, self.country_code, self.language, self.dialect,
@@ -96,18 +96,16 @@ impl<'a> Iterator for CsetDataIterator<'a> {
                 self.data.country_code.to_string(),
             )),
             3 => {
-                let (language, _) = cset_ld_map()
-                    .get(&(self.data.language, self.data.dialect))
-                    .unwrap_or(&("Unknown", "Unknown"));
+                let (language, _) = language_dialect(self.data.language, self.data.dialect)
+                    .unwrap_or(("Unknown", "Unknown"));
                 Some((
                     "language".to_string(),
                     format!("{language}({})", self.data.language),
                 ))
             }
             4 => {
-                let (_, dialect) = cset_ld_map()
-                    .get(&(self.data.language, self.data.dialect))
-                    .unwrap_or(&("Unknown", "Unknown"));
+                let (_, dialect) = language_dialect(self.data.language, self.data.dialect)
+                    .unwrap_or(("Unknown", "Unknown"));
                 Some((
                     "dialect".to_string(),
                     format!("{dialect}({})", self.data.dialect),
@@ -124,61 +122,73 @@ impl<'a> Iterator for CsetDataIterator<'a> {
 /// containing this chunk.
 pub type CsetChunk = KnownChunk<Cset>;
 
-#[allow(clippy::type_complexity)]
-fn cset_ld_map() -> &'static HashMap<(u16, u16), (&'static str, &'static str)> {
-    static MAP: OnceLock<HashMap<(u16, u16), (&'static str, &'static str)>> = OnceLock::new();
-    MAP.get_or_init(|| {
-        HashMap::from([
-            ((0, 0), ("None", "")),
-            ((1, 1), ("Arabic", "")),
-            ((2, 1), ("Bulgarian", "")),
-            ((3, 1), ("Catalan", "")),
-            ((4, 1), ("Chinese", "Traditional")),
-            ((4, 2), ("Chinese", "Simplified")),
-            ((5, 1), ("Czech", "")),
-            ((6, 1), ("Danish", "")),
-            ((7, 1), ("German", "")),
-            ((7, 2), ("German", "Swiss")),
-            ((8, 1), ("Greek", "")),
-            ((9, 1), ("English", "US")),
-            ((9, 2), ("English", "UK")),
-            ((10, 1), ("Spanish", "")),
-            ((10, 2), ("Spanish", "Mexican")),
-            ((11, 1), ("Finnish", "")),
-            ((12, 1), ("French", "")),
-            ((12, 2), ("French", "Belgian")),
-            ((12, 3), ("French", "Canadian")),
-            ((12, 4), ("French", "Swiss")),
-            ((13, 1), ("Hebrew", "")),
-            ((14, 1), ("Hungarian", "")),
-            ((15, 1), ("Icelandic", "")),
-            ((16, 1), ("Italian", "")),
-            ((16, 2), ("Italian", "Swiss")),
-            ((17, 1), ("Japanese", "")),
-            ((18, 1), ("Korean", "")),
-            ((19, 1), ("Dutch", "")),
-            ((19, 2), ("Dutch", "Belgian")),
-            ((20, 1), ("Norwegian", "Bokmal")),
-            ((20, 2), ("Norwegian", "Nynorsk")),
-            ((21, 1), ("Polish", "")),
-            ((22, 1), ("Portuguese", "Brazilian")),
-            ((22, 2), ("Portuguese", "")),
-            ((23, 1), ("Rhaeto-Romanic", "")),
-            ((24, 1), ("Romanian", "")),
-            ((25, 1), ("Russian", "")),
-            ((26, 1), ("Serbo-Croatian", "Latin")),
-            ((26, 2), ("Serbo-Croatian", "Cyrillic")),
-            ((27, 1), ("Slovak", "")),
-            ((28, 1), ("Albanian", "")),
-            ((29, 1), ("Swedish", "")),
-            ((30, 1), ("Thai", "")),
-            ((31, 1), ("Turkish", "")),
-            ((32, 1), ("Urdu", "")),
-            ((33, 1), ("Bahasa", "")),
-        ])
-    })
+/// Language and dialect names for `CSET` language and dialect codes.
+///
+/// Returns `None` for unknown codes. The dialect name is empty when a
+/// language has a single dialect.
+///
+/// ```
+/// use wavrw::chunk::cset::language_dialect;
+///
+/// assert_eq!(language_dialect(9, 2), Some(("English", "UK")));
+/// ```
+pub fn language_dialect(language: u16, dialect: u16) -> Option<(&'static str, &'static str)> {
+    CSET_LANGUAGE_DIALECT
+        .iter()
+        .find(|(codes, _)| *codes == (language, dialect))
+        .map(|(_, names)| *names)
 }
 
+#[allow(clippy::type_complexity)]
+const CSET_LANGUAGE_DIALECT: [((u16, u16), (&str, &str)); 46] = [
+    ((0, 0), ("None", "")),
+    ((1, 1), ("Arabic", "")),
+    ((2, 1), ("Bulgarian", "")),
+    ((3, 1), ("Catalan", "")),
+    ((4, 1), ("Chinese", "Traditional")),
+    ((4, 2), ("Chinese", "Simplified")),
+    ((5, 1), ("Czech", "")),
+    ((6, 1), ("Danish", "")),
+    ((7, 1), ("German", "")),
+    ((7, 2), ("German", "Swiss")),
+    ((8, 1), ("Greek", "")),
+    ((9, 1), ("English", "US")),
+    ((9, 2), ("English", "UK")),
+    ((10, 1), ("Spanish", "")),
+    ((10, 2), ("Spanish", "Mexican")),
+    ((11, 1), ("Finnish", "")),
+    ((12, 1), ("French", "")),
+    ((12, 2), ("French", "Belgian")),
+    ((12, 3), ("French", "Canadian")),
+    ((12, 4), ("French", "Swiss")),
+    ((13, 1), ("Hebrew", "")),
+    ((14, 1), ("Hungarian", "")),
+    ((15, 1), ("Icelandic", "")),
+    ((16, 1), ("Italian", "")),
+    ((16, 2), ("Italian", "Swiss")),
+    ((17, 1), ("Japanese", "")),
+    ((18, 1), ("Korean", "")),
+    ((19, 1), ("Dutch", "")),
+    ((19, 2), ("Dutch", "Belgian")),
+    ((20, 1), ("Norwegian", "Bokmal")),
+    ((20, 2), ("Norwegian", "Nynorsk")),
+    ((21, 1), ("Polish", "")),
+    ((22, 1), ("Portuguese", "Brazilian")),
+    ((22, 2), ("Portuguese", "")),
+    ((23, 1), ("Rhaeto-Romanic", "")),
+    ((24, 1), ("Romanian", "")),
+    ((25, 1), ("Russian", "")),
+    ((26, 1), ("Serbo-Croatian", "Latin")),
+    ((26, 2), ("Serbo-Croatian", "Cyrillic")),
+    ((27, 1), ("Slovak", "")),
+    ((28, 1), ("Albanian", "")),
+    ((29, 1), ("Swedish", "")),
+    ((30, 1), ("Thai", "")),
+    ((31, 1), ("Turkish", "")),
+    ((32, 1), ("Urdu", "")),
+    ((33, 1), ("Bahasa", "")),
+];
+
 /// The country codes specified in [RIFF1991](https://wavref.til.cafe/chunk/cset/)
 #[allow(dead_code, missing_docs)]
 #[binrw]
@@ -282,6 +292,7 @@ impl TryFrom<&RiffCountryCode> for u16 {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::{BinRead, BinWrite};
     use hexdump::hexdump;
 
@@ -311,6 +322,9 @@ mod test {
                 dialect: 3,
             },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         println!("{cset:?}");
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
@@ -343,4 +357,19 @@ mod test {
         let unknown: RiffCountryCode = RiffCountryCode::read(&mut buff).unwrap();
         assert_eq!(unknown, RiffCountryCode::Unknown(0x4200));
     }
+
+    impl Arbitrary for Cset {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Cset {
+                code_page: rng.next_u16(),
+                country_code: RiffCountryCode::from(rng.next_u16()),
+                language: rng.next_u16(),
+                dialect: rng.next_u16(),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        cset_arbitrary_roundtrip: Cset,
+    }
 }
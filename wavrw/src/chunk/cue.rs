@@ -1,10 +1,15 @@
 //! `cue ` A series of positions in the waveform `data` chunk. [RIFF1991](https://wavref.til.cafe/chunk/cue/)
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use binrw::binrw;
 
-use crate::{ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::chunk::data::Data;
+use crate::{ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable, WaveFileError};
 
 /// A position in the waveform `data` chunk.
 #[binrw]
@@ -26,11 +31,24 @@ pub struct CuePoint {
 }
 
 impl CuePoint {
-    fn summary(&self) -> String {
-        format!(
-            "{:10}, {}, {:10}, {:10}, {:10}",
-            self.position, self.chunk_id, self.chunk_start, self.block_start, self.sample_offset,
-        )
+    /// Fields as (`[index] field`, value) pairs, for [`Summarizable::items()`].
+    fn push_items(&self, index: usize, items: &mut Vec<(String, String)>) {
+        let chunk_id = match &self.chunk_id.0 {
+            b"data" => format!("{} (samples)", self.chunk_id),
+            b"slnt" => format!("{} (silence)", self.chunk_id),
+            _ => self.chunk_id.to_string(),
+        };
+        let fields = [
+            ("name", self.name.to_string()),
+            ("position", self.position.to_string()),
+            ("chunk_id", chunk_id),
+            ("chunk_start", self.chunk_start.to_string()),
+            ("block_start", self.block_start.to_string()),
+            ("sample_offset", self.sample_offset.to_string()),
+        ];
+        for (field, value) in fields {
+            items.push((format!("[{index}] {field}"), value));
+        }
     }
 }
 
@@ -67,6 +85,73 @@ impl Default for Cue {
     }
 }
 
+impl Cue {
+    /// Start building a `cue ` chunk, see [`CueBuilder`].
+    pub fn builder() -> CueBuilder {
+        CueBuilder::default()
+    }
+}
+
+/// Builds a [`Cue`] from positions, filling in the fields for a file with a
+/// single `data` chunk.
+///
+/// # Examples
+///
+/// ```
+/// use wavrw::chunk::cue::Cue;
+///
+/// let cue = Cue::builder().point(1, 0).point(2, 48_000).build()?;
+/// assert_eq!(cue.cue_points, 2);
+/// assert_eq!(cue.points[1].sample_offset, 48_000);
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CueBuilder {
+    points: Vec<CuePoint>,
+}
+
+impl CueBuilder {
+    /// Add a cue point `name` at sample `position` of the `data` chunk.
+    #[must_use]
+    pub fn point(self, name: u32, position: u32) -> Self {
+        self.cue_point(CuePoint {
+            name,
+            position,
+            chunk_id: Data::ID,
+            chunk_start: 0,
+            block_start: 0,
+            sample_offset: position,
+        })
+    }
+
+    /// Add a cue point with all fields given, ex: for `wavl` files.
+    #[must_use]
+    pub fn cue_point(mut self, point: CuePoint) -> Self {
+        self.points.push(point);
+        self
+    }
+
+    /// Create the [`Cue`], with cue points in the order they were added.
+    ///
+    /// Returns an error if two cue points have the same name.
+    pub fn build(self) -> Result<Cue, WaveFileError> {
+        for (index, point) in self.points.iter().enumerate() {
+            if self.points[..index].iter().any(|p| p.name == point.name) {
+                return Err(WaveFileError::parse(
+                    None,
+                    format!("duplicate cue point name: {}", point.name),
+                ));
+            }
+        }
+        let cue_points = u32::try_from(self.points.len())
+            .map_err(|_| WaveFileError::parse(None, "too many cue points".to_string()))?;
+        Ok(Cue {
+            cue_points,
+            points: self.points,
+        })
+    }
+}
+
 /// `cue ` A series of positions in the waveform `data` chunk. [RIFF1991](https://wavref.til.cafe/chunk/cue/)
 pub type CueChunk = KnownChunk<Cue>;
 
@@ -81,16 +166,12 @@ impl Summarizable for Cue {
 
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         let mut items: Vec<(String, String)> = Vec::new();
-        for point in &self.points {
-            items.push((format!("{}", point.name), point.summary()));
+        for (index, point) in self.points.iter().enumerate() {
+            point.push_items(index, &mut items);
         }
         Box::new(items.into_iter())
     }
 
-    fn item_summary_header(&self) -> String {
-        "name: position, chunk_id, chunk_start, block_start, sample_offset".to_string()
-    }
-
     fn name(&self) -> String {
         self.id().to_string().trim().to_string()
     }
@@ -99,7 +180,8 @@ impl Summarizable for Cue {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
-    use binrw::BinRead;
+    use crate::testing::{Arbitrary, Rng};
+    use binrw::{BinRead, BinWrite};
 
     use super::*;
     use crate::testing::hex_to_cursor;
@@ -125,5 +207,56 @@ mod test {
             },
         );
         assert_eq!(cue.extra_bytes.len(), 0);
+
+        let items: Vec<_> = cue.data.items().collect();
+        dbg!(&items);
+        assert_eq!(items.len(), 3 * 6);
+        assert_eq!(items[6], ("[1] name".to_string(), "2".to_string()));
+        assert_eq!(
+            items[8],
+            ("[1] chunk_id".to_string(), "data (samples)".to_string())
+        );
+        assert_eq!(
+            items[17],
+            ("[2] sample_offset".to_string(), "360".to_string())
+        );
+    }
+
+    #[test]
+    fn build_cue() {
+        let cue = Cue::builder().point(1, 0).point(2, 240).build().unwrap();
+        let chunk = CueChunk::new(cue).unwrap();
+        let mut buff = binrw::io::Cursor::new(Vec::new());
+        chunk.write(&mut buff).unwrap();
+        buff.set_position(0);
+        let read = CueChunk::read(&mut buff).unwrap();
+        dbg!(&read);
+        assert_eq!(read.data, chunk.data);
+        assert_eq!(read.data.points[1].chunk_id, FourCC(*b"data"));
+
+        assert!(Cue::builder().point(1, 0).point(1, 240).build().is_err());
+    }
+
+    impl Arbitrary for Cue {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let points: Vec<CuePoint> = (0..rng.below(8))
+                .map(|_| CuePoint {
+                    name: rng.next_u32(),
+                    position: rng.next_u32(),
+                    chunk_id: rng.fourcc(),
+                    chunk_start: rng.next_u32(),
+                    block_start: rng.next_u32(),
+                    sample_offset: rng.next_u32(),
+                })
+                .collect();
+            Cue {
+                cue_points: points.len() as u32,
+                points,
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        cue_roundtrip: Cue,
     }
 }
@@ -1,5 +1,7 @@
 //! `data` Audio samples. This parser skips all audio data (for now). [RIFF1991](https://wavref.til.cafe/spec/riff1991/)
 
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use binrw::binrw;
 use binrw::io::SeekFrom;
 
@@ -66,6 +68,9 @@ mod test {
                 data: [8_u8; 0].to_vec(),
             },
             extra_bytes: Vec::new(),
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
         data.write(&mut buff).unwrap();
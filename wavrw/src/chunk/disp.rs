@@ -0,0 +1,226 @@
+//! `DISP` Data to display the file, usually a title. [RIFF1991](https://wavref.til.cafe/spec/riff1991/)
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+
+use binrw::binrw;
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use crate::{codepage, FourCC, KnownChunk, KnownChunkID, Summarizable};
+
+/// Windows clipboard format of the data in a [`Disp`] chunk.
+#[binrw]
+#[brw(little, repr = u32)]
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, FromPrimitive)]
+pub enum ClipboardFormat {
+    /// `CF_TEXT` NULL-terminated text.
+    Text = 1,
+    /// `CF_BITMAP` Bitmap handle, not meaningful in a file.
+    Bitmap = 2,
+    /// `CF_METAFILEPICT` Windows metafile picture.
+    MetafilePict = 3,
+    /// `CF_SYLK` Symbolic link format.
+    Sylk = 4,
+    /// `CF_DIF` Data interchange format.
+    Dif = 5,
+    /// `CF_TIFF` TIFF image.
+    Tiff = 6,
+    /// `CF_OEMTEXT` NULL-terminated text in the OEM character set.
+    OemText = 7,
+    /// `CF_DIB` Device independent bitmap, usually an icon.
+    Dib = 8,
+    /// `CF_PALETTE` Color palette.
+    Palette = 9,
+    /// `CF_PENDATA` Pen data.
+    PenData = 10,
+    /// `CF_RIFF` RIFF data.
+    Riff = 11,
+    /// `CF_WAVE` WAVE audio.
+    Wave = 12,
+    /// `CF_UNICODETEXT` NULL-terminated UTF-16LE text.
+    UnicodeText = 13,
+    /// `CF_ENHMETAFILE` Enhanced metafile.
+    EnhMetafile = 14,
+    /// Unknown formats.
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl Display for ClipboardFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        let name = match self {
+            ClipboardFormat::Text => "CF_TEXT",
+            ClipboardFormat::Bitmap => "CF_BITMAP",
+            ClipboardFormat::MetafilePict => "CF_METAFILEPICT",
+            ClipboardFormat::Sylk => "CF_SYLK",
+            ClipboardFormat::Dif => "CF_DIF",
+            ClipboardFormat::Tiff => "CF_TIFF",
+            ClipboardFormat::OemText => "CF_OEMTEXT",
+            ClipboardFormat::Dib => "CF_DIB",
+            ClipboardFormat::Palette => "CF_PALETTE",
+            ClipboardFormat::PenData => "CF_PENDATA",
+            ClipboardFormat::Riff => "CF_RIFF",
+            ClipboardFormat::Wave => "CF_WAVE",
+            ClipboardFormat::UnicodeText => "CF_UNICODETEXT",
+            ClipboardFormat::EnhMetafile => "CF_ENHMETAFILE",
+            ClipboardFormat::Other(value) => return write!(f, "other({value})"),
+        };
+        write!(f, "{name} ({})", u32::from(*self))
+    }
+}
+
+impl TryFrom<&ClipboardFormat> for u32 {
+    type Error = core::num::TryFromIntError;
+
+    // infalible, but binrw seems to need TryFrom?
+    fn try_from(value: &ClipboardFormat) -> Result<Self, Self::Error> {
+        Ok(u32::from(*value))
+    }
+}
+
+/// `DISP` Data to display the file, usually a title. [RIFF1991](https://wavref.til.cafe/spec/riff1991/)
+///
+/// Written by older Windows tools, like Sound Recorder, most often with
+/// `CF_TEXT` data holding a title for the file. Other formats, like
+/// `CF_DIB` icons, are kept as bytes.
+#[binrw]
+#[brw(little)]
+#[br(import(size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Disp {
+    /// Format of `data`.
+    pub format: ClipboardFormat,
+
+    /// Data in `format`, see [`Disp::text()`].
+    #[br(parse_with = crate::read_bytes, args(u64::from(size.saturating_sub(4))))]
+    pub data: Vec<u8>,
+}
+
+impl KnownChunkID for Disp {
+    const ID: FourCC = FourCC(*b"DISP");
+}
+
+impl Disp {
+    /// Text of `CF_TEXT`, `CF_OEMTEXT` and `CF_UNICODETEXT` data, up to the
+    /// first NULL. `None` for other formats.
+    ///
+    /// ```
+    /// use wavrw::chunk::disp::{ClipboardFormat, Disp};
+    ///
+    /// let disp = Disp {
+    ///     format: ClipboardFormat::Text,
+    ///     data: b"Door Slam\0".to_vec(),
+    /// };
+    /// assert_eq!(disp.text().as_deref(), Some("Door Slam"));
+    /// ```
+    pub fn text(&self) -> Option<String> {
+        match self.format {
+            ClipboardFormat::Text | ClipboardFormat::OemText => {
+                let end = self.data.iter().position(|b| *b == 0);
+                let text = &self.data[..end.unwrap_or(self.data.len())];
+                Some(codepage::decode(text, None))
+            }
+            ClipboardFormat::UnicodeText => {
+                let units: Vec<u16> = self
+                    .data
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .take_while(|u| *u != 0)
+                    .collect();
+                Some(String::from_utf16_lossy(&units))
+            }
+            ClipboardFormat::Bitmap
+            | ClipboardFormat::MetafilePict
+            | ClipboardFormat::Sylk
+            | ClipboardFormat::Dif
+            | ClipboardFormat::Tiff
+            | ClipboardFormat::Dib
+            | ClipboardFormat::Palette
+            | ClipboardFormat::PenData
+            | ClipboardFormat::Riff
+            | ClipboardFormat::Wave
+            | ClipboardFormat::EnhMetafile
+            | ClipboardFormat::Other(_) => None,
+        }
+    }
+}
+
+impl Summarizable for Disp {
+    fn summary(&self) -> String {
+        match self.text() {
+            Some(text) => text,
+            None => format!("{}, {} bytes", self.format, self.data.len()),
+        }
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let data = match self.text() {
+            Some(text) => ("text".to_string(), text),
+            None => ("data".to_string(), format!("{} bytes", self.data.len())),
+        };
+        Box::new([("format".to_string(), self.format.to_string()), data].into_iter())
+    }
+}
+
+/// `DISP` Data to display the file, usually a title. [RIFF1991](https://wavref.til.cafe/spec/riff1991/)
+pub type DispChunk = KnownChunk<Disp>;
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use binrw::BinRead;
+
+    use super::*;
+    use crate::testing::{hex_to_cursor, Arbitrary, Rng};
+
+    #[test]
+    fn parse_disp() {
+        // CF_TEXT "Sound Recorder\0" with pad byte
+        let mut buff =
+            hex_to_cursor("44495350 13000000 01000000 536F756E 64205265 636F7264 657200 00");
+        let chunk = DispChunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        assert_eq!(chunk.size, 19);
+        assert_eq!(chunk.data.format, ClipboardFormat::Text);
+        assert_eq!(chunk.data.text().as_deref(), Some("Sound Recorder"));
+        assert_eq!(chunk.data.summary(), "Sound Recorder");
+        assert_eq!(
+            chunk.data.items().next(),
+            Some(("format".to_string(), "CF_TEXT (1)".to_string()))
+        );
+    }
+
+    #[test]
+    fn disp_formats() {
+        let unicode = Disp {
+            format: ClipboardFormat::UnicodeText,
+            data: vec![b'H', 0, b'i', 0, 0, 0, b'x', 0],
+        };
+        assert_eq!(unicode.text().as_deref(), Some("Hi"));
+
+        let icon = Disp {
+            format: ClipboardFormat::from(8),
+            data: vec![0; 40],
+        };
+        assert_eq!(icon.text(), None);
+        assert_eq!(icon.summary(), "CF_DIB (8), 40 bytes");
+        assert_eq!(ClipboardFormat::from(99).to_string(), "other(99)");
+    }
+
+    impl Arbitrary for Disp {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Disp {
+                format: ClipboardFormat::from(rng.next_u32() % 16),
+                data: rng.bytes(32),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        disp_roundtrip: Disp,
+    }
+}
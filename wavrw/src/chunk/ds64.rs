@@ -0,0 +1,205 @@
+//! `ds64` 64 bit sizes of the RIFF, `data` and other chunks in RF64 files. [RF64_2009](https://tech.ebu.ch/docs/tech/tech3306-2009.pdf)
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use binrw::binrw;
+
+use crate::{ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
+
+/// 64 bit size of a chunk other than `data`.
+#[binrw]
+#[brw(little)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ds64Entry {
+    /// RIFF chunk id of the chunk with this size.
+    pub chunk_id: FourCC,
+
+    /// Size of the chunk in bytes.
+    pub chunk_size: u64,
+}
+
+/// `ds64` 64 bit sizes of the RIFF, `data` and other chunks in RF64 files. [RF64_2009](https://tech.ebu.ch/docs/tech/tech3306-2009.pdf)
+///
+/// Chunks larger than 4 GiB store 0xFFFFFFFF in their 32 bit size field. Their
+/// real size is stored here: `riff_size` for the `RF64` header, `data_size` for
+/// the `data` chunk and `table` for any other chunk.
+/// [`WaveFile::iter_chunks()`](crate::WaveFile::iter_chunks) uses these sizes,
+/// see [`SizedChunk::large_size()`](crate::SizedChunk::large_size).
+#[binrw]
+#[brw(little)]
+#[br(import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ds64 {
+    /// Size of the RIFF data, the size of the file minus 8 bytes.
+    pub riff_size: u64,
+
+    /// Size of the `data` chunk in bytes.
+    pub data_size: u64,
+
+    /// Number of samples per channel, the same as a `fact` chunk.
+    pub sample_count: u64,
+
+    /// Count of `table` entries.
+    pub table_length: u32,
+
+    /// Sizes of other chunks larger than 4 GiB.
+    #[br(count = table_length)]
+    pub table: Vec<Ds64Entry>,
+}
+
+impl KnownChunkID for Ds64 {
+    const ID: FourCC = FourCC(*b"ds64");
+}
+
+impl Ds64 {
+    /// 64 bit sizes by chunk id, `data_size` for `data` followed by the
+    /// `table` entries, in the order chunks with 0xFFFFFFFF sizes use them.
+    pub fn sizes(&self) -> Vec<(FourCC, u64)> {
+        let data = (FourCC(*b"data"), self.data_size);
+        core::iter::once(data)
+            .chain(self.table.iter().map(|e| (e.chunk_id, e.chunk_size)))
+            .collect()
+    }
+}
+
+/// Resolves 0xFFFFFFFF chunk sizes to the 64 bit sizes from a `ds64` chunk.
+///
+/// Each size is used once, so several large chunks with the same id get
+/// their sizes in file order.
+///
+/// ```
+/// use wavrw::chunk::ds64::{Ds64, LargeSizes};
+/// use wavrw::FourCC;
+///
+/// let ds64 = Ds64 {
+///     riff_size: 0x1_0000_0024,
+///     data_size: 0x1_0000_0000,
+///     sample_count: 0x4000_0000,
+///     table_length: 0,
+///     table: vec![],
+/// };
+/// let mut sizes = LargeSizes::from(&ds64);
+/// assert_eq!(sizes.resolve(FourCC(*b"data"), 16), None);
+/// assert_eq!(sizes.resolve(FourCC(*b"data"), u32::MAX), Some(0x1_0000_0000));
+/// assert_eq!(sizes.resolve(FourCC(*b"data"), u32::MAX), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LargeSizes {
+    sizes: Vec<(FourCC, u64)>,
+}
+
+impl LargeSizes {
+    /// The 32 bit size field value which means the size is in `ds64`.
+    pub const PLACEHOLDER: u32 = u32::MAX;
+
+    /// 64 bit size of a chunk with `id` and 32 bit `size`.
+    ///
+    /// `None` if `size` is not [`LargeSizes::PLACEHOLDER`] or no size for
+    /// `id` is left.
+    pub fn resolve(&mut self, id: FourCC, size: u32) -> Option<u64> {
+        if size != Self::PLACEHOLDER {
+            return None;
+        }
+        let index = self.sizes.iter().position(|(i, _)| *i == id)?;
+        Some(self.sizes.remove(index).1)
+    }
+}
+
+impl From<&Ds64> for LargeSizes {
+    fn from(ds64: &Ds64) -> Self {
+        LargeSizes {
+            sizes: ds64.sizes(),
+        }
+    }
+}
+
+/// `ds64` 64 bit sizes of the RIFF, `data` and other chunks in RF64 files. [RF64_2009](https://tech.ebu.ch/docs/tech/tech3306-2009.pdf)
+pub type Ds64Chunk = KnownChunk<Ds64>;
+
+impl Summarizable for Ds64 {
+    fn summary(&self) -> String {
+        format!(
+            "riff {}, data {}, {} samples",
+            self.riff_size, self.data_size, self.sample_count
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let mut items = Vec::from([
+            ("riff_size".to_string(), self.riff_size.to_string()),
+            ("data_size".to_string(), self.data_size.to_string()),
+            ("sample_count".to_string(), self.sample_count.to_string()),
+        ]);
+        for entry in &self.table {
+            items.push((entry.chunk_id.to_string(), entry.chunk_size.to_string()));
+        }
+        Box::new(items.into_iter())
+    }
+
+    fn name(&self) -> String {
+        self.id().to_string()
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use crate::testing::{Arbitrary, Rng};
+    use binrw::BinRead;
+
+    use super::*;
+    use crate::testing::hex_to_cursor;
+
+    #[test]
+    fn parse_ds64() {
+        let mut buff = hex_to_cursor(
+            "64733634 28000000
+            24000000 01000000 00000000 01000000 00400000 00000000
+            01000000
+            4A554E4B 10000000 01000000",
+        );
+        let chunk = Ds64Chunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        assert_eq!(chunk.data.riff_size, 0x1_0000_0024);
+        assert_eq!(chunk.data.data_size, 0x1_0000_0000);
+        assert_eq!(chunk.data.sample_count, 0x4000);
+        assert_eq!(chunk.data.table_length, 1);
+        assert_eq!(
+            chunk.data.sizes(),
+            vec![
+                (FourCC(*b"data"), 0x1_0000_0000),
+                (FourCC(*b"JUNK"), 0x1_0000_0010)
+            ]
+        );
+        assert_eq!(
+            chunk.data.summary(),
+            "riff 4294967332, data 4294967296, 16384 samples"
+        );
+    }
+
+    impl Arbitrary for Ds64 {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let mut table = Vec::new();
+            for _ in 0..rng.below(3) {
+                table.push(Ds64Entry {
+                    chunk_id: FourCC(*b"axml"),
+                    chunk_size: rng.next_u64(),
+                });
+            }
+            Ds64 {
+                riff_size: rng.next_u64(),
+                data_size: rng.next_u64(),
+                sample_count: rng.next_u64(),
+                table_length: u32::try_from(table.len()).unwrap(),
+                table,
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        ds64_roundtrip: Ds64,
+    }
+}
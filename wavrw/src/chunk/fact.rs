@@ -1,8 +1,10 @@
 //! `fact` Number of samples for compressed audio in `data`. [RIFF1991](https://wavref.til.cafe/chunk/fact/)
 
+use alloc::format;
+use alloc::string::String;
 use binrw::binrw;
 
-use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::{DisplayOptions, FourCC, KnownChunk, KnownChunkID, Summarizable};
 
 /// `fact` Number of samples for compressed audio in `data`. [RIFF1991](https://wavref.til.cafe/chunk/fact/)
 ///
@@ -26,6 +28,10 @@ impl Summarizable for Fact {
     fn summary(&self) -> String {
         format!("{} samples", self.samples)
     }
+
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        options.text(&options.samples(self.samples.into()))
+    }
 }
 
 /// `fact` Number of samples for compressed audio in `data`. [RIFF1991](https://wavref.til.cafe/chunk/fact/)
@@ -34,6 +40,7 @@ pub type FactChunk = KnownChunk<Fact>;
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::BinRead;
 
     use super::*;
@@ -49,6 +56,10 @@ mod test {
         dbg!(&fact);
         assert_eq!(fact.id(), FourCC(*b"fact"));
         assert_eq!(fact.data.samples, 480);
+        let options = DisplayOptions::new()
+            .with_units(crate::display::Units::Seconds)
+            .with_sample_rate(48_000);
+        assert_eq!(fact.summary_with(&options), "00:00:00.010");
 
         // parse via enum wrapper this time
         buff.set_position(0);
@@ -60,4 +71,16 @@ mod test {
         };
         assert_eq!(fact.data.samples, 480);
     }
+
+    impl Arbitrary for Fact {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Fact {
+                samples: rng.next_u32(),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        fact_roundtrip: Fact,
+    }
 }
@@ -3,6 +3,10 @@
 //! This is a fiddly chunk to parse, since the fields vary depending on the [`FormatTag`].
 //! Currently only the most common formats are implemented with specific structs to parse them. Others will be parsed by [`FmtExtended`].
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::{Display, Formatter};
 
 use binrw::binrw;
@@ -13,7 +17,7 @@ use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
 
 /// A number indicating the WAVE format category of the file.
 ///
-/// The content of the format-specific-fields [ed: everything after block_align]
+/// The content of the format-specific-fields [ed: everything after `block_align`]
 /// portion of the fmt chunk, and the interpretation of the waveform data, depend on
 /// this value. [RIFF1991](https://wavref.til.cafe/chunk/fmt/)
 #[allow(dead_code, missing_docs)]
@@ -592,7 +596,7 @@ pub trait Tag {
 }
 
 //---------------------------
-/// Format of PCM audio samples in `data`. (WAVE_FORMAT_PCM) [RIFF1991](https://wavref.til.cafe/chunk/fmt/)
+/// Format of PCM audio samples in `data`. (`WAVE_FORMAT_PCM`) [RIFF1991](https://wavref.til.cafe/chunk/fmt/)
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
@@ -729,7 +733,7 @@ impl Display for AdpcmCoefficients {
     }
 }
 
-/// Format of ADPCM audio samples in `data`. (WAVE_FORMAT_ADPCM) [RIFF1994](https://wavref.til.cafe/chunk/fmt/)
+/// Format of ADPCM audio samples in `data`. (`WAVE_FORMAT_ADPCM`) [RIFF1994](https://wavref.til.cafe/chunk/fmt/)
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
@@ -781,7 +785,7 @@ pub struct FmtAdpcm {
     /// `format_tag` through `extra_size` inclusive (all fields except `id`, `size` and
     /// the `extra_bytes`))
     #[br()]
-    #[bw(map = |_| (self.coefficient_count * 4 + 4) )]
+    #[bw(map = |_| self.coefficient_count * 4 + 4)]
     pub extra_size: u16,
 
     /// Count of number of samples per block.
@@ -907,7 +911,7 @@ impl<'a> Iterator for FmtAdpcmIterator<'a> {
 
 //---------------------------
 
-/// Format of DVI ADPCM audio samples in `data`. (WAVE_FORMAT_DVI_ADPCM) [RIFF1994](https://wavref.til.cafe/chunk/fmt/)
+/// Format of DVI ADPCM audio samples in `data`. (`WAVE_FORMAT_DVI_ADPCM`) [RIFF1994](https://wavref.til.cafe/chunk/fmt/)
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
@@ -939,7 +943,7 @@ pub struct FmtDviAdpcm {
     /// of data at a time, so the value of `block_align` can be used for
     /// buffer alignment.
     ///
-    /// |bits_per_sample | block_align |
+    /// |`bits_per_sample` | `block_align` |
     /// |-|-|
     /// |3 | (( N * 3 ) + 1 ) * 4 * channels |
     /// |4 | (N + 1) * 4 * channels |
@@ -1058,6 +1062,373 @@ impl<'a> Iterator for FmtDviAdpcmIterator<'a> {
     }
 }
 
+//---------------------------
+
+/// Format of MPEG-1 audio in `data`. (`WAVE_FORMAT_MPEG`, MPEG1WAVEFORMAT) [BWFMPEG1997](https://tech.ebu.ch/docs/tech/tech3285s1.pdf)
+///
+/// Used for MPEG Layer I and Layer II audio, ex: in broadcast archives.
+#[binrw]
+#[brw(little)]
+#[br(import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FmtMpeg {
+    /// A number indicating the WAVE format category of the file.
+    #[br(temp, assert(format_tag == Self::FORMAT_TAG))]
+    #[bw(calc = Self::FORMAT_TAG)]
+    pub format_tag: FormatTag,
+
+    /// The number of channels represented in the waveform data.
+    ///
+    /// Example: 1 for mono or 2 for stereo.
+    pub channels: u16,
+
+    /// The sampling rate at which each channel should be played.
+    pub samples_per_sec: u32,
+
+    /// The average number of bytes per second at which the waveform data should be transferred.
+    pub avg_bytes_per_sec: u32,
+
+    /// The block alignment (in bytes) of the waveform data.
+    ///
+    /// The size of one audio frame, or 1 for a variable frame size
+    /// (padding or free format).
+    pub block_align: u16,
+
+    /// Not used, set to 0.
+    pub bits_per_sample: u16,
+
+    /// The count in bytes of the extended data. 22 for MPEG.
+    pub extra_size: u16,
+
+    /// MPEG audio layer(s) used in the `data` chunk. One or more of the
+    /// flags 0x0001 (Layer I), 0x0002 (Layer II) and 0x0004 (Layer III).
+    pub head_layer: u16,
+
+    /// Bit rate of the MPEG audio in bits per second, or 0 if the bit rate
+    /// is variable.
+    pub head_bitrate: u32,
+
+    /// Channel mode(s) used in the `data` chunk. One or more of the flags
+    /// 0x0001 (stereo), 0x0002 (joint stereo), 0x0004 (dual channel) and
+    /// 0x0008 (single channel).
+    pub head_mode: u16,
+
+    /// Mode extension (joint stereo coding) used in the `data` chunk, one
+    /// or more of the flags 0x0001 to 0x0008.
+    pub head_mode_ext: u16,
+
+    /// De-emphasis required by the decoder. 1 none, 2 50/15 µs, 3 reserved
+    /// and 4 CCITT J.17.
+    pub head_emphasis: u16,
+
+    /// Flags set in the MPEG audio frame headers. 0x0001 private bit,
+    /// 0x0002 copyright, 0x0004 original, 0x0008 protection (CRC),
+    /// 0x0010 MPEG-1 (not MPEG-2 low sample rates).
+    pub head_flags: u16,
+
+    /// Low 32 bits of the 33 bit presentation time stamp (PTS) of the
+    /// first frame.
+    pub pts_low: u32,
+
+    /// High bit of the 33 bit presentation time stamp (PTS) of the first
+    /// frame.
+    pub pts_high: u32,
+}
+
+impl KnownChunkID for FmtMpeg {
+    const ID: FourCC = FourCC(*b"fmt ");
+}
+
+impl FmtMpeg {
+    const FORMAT_TAG: FormatTag = FormatTag::Mpeg;
+
+    /// Names of the layers in `head_layer`. Ex: "Layer II"
+    pub fn layers(&self) -> String {
+        flag_names(
+            self.head_layer,
+            &[
+                (0x0001, "Layer I"),
+                (0x0002, "Layer II"),
+                (0x0004, "Layer III"),
+            ],
+        )
+    }
+
+    /// Names of the channel modes in `head_mode`. Ex: "joint stereo"
+    pub fn modes(&self) -> String {
+        flag_names(
+            self.head_mode,
+            &[
+                (0x0001, "stereo"),
+                (0x0002, "joint stereo"),
+                (0x0004, "dual channel"),
+                (0x0008, "single channel"),
+            ],
+        )
+    }
+
+    /// Names of the flags in `head_flags`. Ex: "copyright, original"
+    pub fn flags(&self) -> String {
+        flag_names(
+            self.head_flags,
+            &[
+                (0x0001, "private"),
+                (0x0002, "copyright"),
+                (0x0004, "original"),
+                (0x0008, "protection"),
+                (0x0010, "MPEG-1"),
+            ],
+        )
+    }
+}
+
+/// Comma separated names of the bits set in `value`, unknown bits as hex.
+fn flag_names(value: u16, names: &[(u16, &str)]) -> String {
+    let mut unknown = value;
+    let mut found: Vec<String> = Vec::new();
+    for (flag, name) in names {
+        if value & flag != 0 {
+            found.push((*name).to_string());
+            unknown &= !flag;
+        }
+    }
+    if unknown != 0 {
+        found.push(format!("0x{unknown:04X}"));
+    }
+    found.join(", ")
+}
+
+impl Tag for FmtMpeg {
+    fn format_tag(&self) -> FormatTag {
+        Self::FORMAT_TAG
+    }
+}
+
+impl Summarizable for FmtMpeg {
+    fn summary(&self) -> String {
+        format!(
+            "{}, {} chan, {}, {}, {} kbps",
+            self.format_tag().to_string().replace("WAVE_FORMAT_", ""),
+            self.channels,
+            self.samples_per_sec,
+            self.layers(),
+            self.head_bitrate / 1000,
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        Box::new(self.into_iter())
+    }
+}
+
+// Iteration based on pattern from https://stackoverflow.com/questions/30218886/how-to-implement-iterator-and-intoiterator-for-a-simple-struct
+
+impl<'a> IntoIterator for &'a FmtMpeg {
+    type Item = (String, String);
+    type IntoIter = FmtMpegIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FmtMpegIterator {
+            data: self,
+            index: 0,
+        }
+    }
+}
+
+/// Iterate over fields as tuple of Strings (name, value).
+#[derive(Debug)]
+pub struct FmtMpegIterator<'a> {
+    data: &'a FmtMpeg,
+    index: usize,
+}
+
+impl<'a> Iterator for FmtMpegIterator<'a> {
+    type Item = (String, String);
+    fn next(&mut self) -> Option<(String, String)> {
+        self.index += 1;
+        match self.index {
+            1 => Some(("format_tag".to_string(), self.data.format_tag().to_string())),
+            2 => Some(("channels".to_string(), self.data.channels.to_string())),
+            3 => Some((
+                "samples_per_sec".to_string(),
+                self.data.samples_per_sec.to_string(),
+            )),
+            4 => Some((
+                "avg_bytes_per_sec".to_string(),
+                self.data.avg_bytes_per_sec.to_string(),
+            )),
+            5 => Some(("block_align".to_string(), self.data.block_align.to_string())),
+            6 => Some((
+                "bits_per_sample".to_string(),
+                self.data.bits_per_sample.to_string(),
+            )),
+            7 => Some(("extra_size".to_string(), self.data.extra_size.to_string())),
+            8 => Some((
+                "head_layer".to_string(),
+                format!("{} ({})", self.data.head_layer, self.data.layers()),
+            )),
+            9 => Some((
+                "head_bitrate".to_string(),
+                self.data.head_bitrate.to_string(),
+            )),
+            10 => Some((
+                "head_mode".to_string(),
+                format!("{} ({})", self.data.head_mode, self.data.modes()),
+            )),
+            11 => Some((
+                "head_mode_ext".to_string(),
+                self.data.head_mode_ext.to_string(),
+            )),
+            12 => Some((
+                "head_emphasis".to_string(),
+                self.data.head_emphasis.to_string(),
+            )),
+            13 => Some((
+                "head_flags".to_string(),
+                format!("0x{:04X} ({})", self.data.head_flags, self.data.flags()),
+            )),
+            14 => Some(("pts_low".to_string(), self.data.pts_low.to_string())),
+            15 => Some(("pts_high".to_string(), self.data.pts_high.to_string())),
+            _ => None,
+        }
+    }
+}
+
+//---------------------------
+
+/// Format of MPEG Layer III (MP3) audio in `data`. (`WAVE_FORMAT_MPEGLAYER3`, MPEGLAYER3WAVEFORMAT) [MMREG](https://learn.microsoft.com/en-us/windows/win32/api/mmreg/ns-mmreg-mpeglayer3waveformat)
+#[binrw]
+#[brw(little)]
+#[br(import(_size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FmtMpegLayer3 {
+    /// A number indicating the WAVE format category of the file.
+    #[br(temp, assert(format_tag == Self::FORMAT_TAG))]
+    #[bw(calc = Self::FORMAT_TAG)]
+    pub format_tag: FormatTag,
+
+    /// The number of channels represented in the waveform data.
+    ///
+    /// Example: 1 for mono or 2 for stereo.
+    pub channels: u16,
+
+    /// The sampling rate at which each channel should be played.
+    pub samples_per_sec: u32,
+
+    /// The average number of bytes per second at which the waveform data should be transferred.
+    pub avg_bytes_per_sec: u32,
+
+    /// The block alignment (in bytes) of the waveform data. Usually 1.
+    pub block_align: u16,
+
+    /// Not used, set to 0.
+    pub bits_per_sample: u16,
+
+    /// The count in bytes of the extended data. 12 for MPEG Layer III.
+    pub extra_size: u16,
+
+    /// Always 1 (`MPEGLAYER3_ID_MPEG`).
+    pub mpeg_id: u16,
+
+    /// Padding of the MPEG frames. 0 ISO (padding as needed), 1 always
+    /// padded, 2 never padded.
+    pub flags: u32,
+
+    /// Size of one block in bytes.
+    pub block_size: u16,
+
+    /// Number of MPEG frames per block.
+    pub frames_per_block: u16,
+
+    /// Encoder delay in samples.
+    pub codec_delay: u16,
+}
+
+impl KnownChunkID for FmtMpegLayer3 {
+    const ID: FourCC = FourCC(*b"fmt ");
+}
+
+impl FmtMpegLayer3 {
+    const FORMAT_TAG: FormatTag = FormatTag::Mpeglayer3;
+}
+
+impl Tag for FmtMpegLayer3 {
+    fn format_tag(&self) -> FormatTag {
+        Self::FORMAT_TAG
+    }
+}
+
+impl Summarizable for FmtMpegLayer3 {
+    fn summary(&self) -> String {
+        format!(
+            "{}, {} chan, {}, {} kbps",
+            self.format_tag().to_string().replace("WAVE_FORMAT_", ""),
+            self.channels,
+            self.samples_per_sec,
+            u64::from(self.avg_bytes_per_sec) * 8 / 1000,
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        Box::new(self.into_iter())
+    }
+}
+
+// Iteration based on pattern from https://stackoverflow.com/questions/30218886/how-to-implement-iterator-and-intoiterator-for-a-simple-struct
+
+impl<'a> IntoIterator for &'a FmtMpegLayer3 {
+    type Item = (String, String);
+    type IntoIter = FmtMpegLayer3Iterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FmtMpegLayer3Iterator {
+            data: self,
+            index: 0,
+        }
+    }
+}
+
+/// Iterate over fields as tuple of Strings (name, value).
+#[derive(Debug)]
+pub struct FmtMpegLayer3Iterator<'a> {
+    data: &'a FmtMpegLayer3,
+    index: usize,
+}
+
+impl<'a> Iterator for FmtMpegLayer3Iterator<'a> {
+    type Item = (String, String);
+    fn next(&mut self) -> Option<(String, String)> {
+        self.index += 1;
+        match self.index {
+            1 => Some(("format_tag".to_string(), self.data.format_tag().to_string())),
+            2 => Some(("channels".to_string(), self.data.channels.to_string())),
+            3 => Some((
+                "samples_per_sec".to_string(),
+                self.data.samples_per_sec.to_string(),
+            )),
+            4 => Some((
+                "avg_bytes_per_sec".to_string(),
+                self.data.avg_bytes_per_sec.to_string(),
+            )),
+            5 => Some(("block_align".to_string(), self.data.block_align.to_string())),
+            6 => Some((
+                "bits_per_sample".to_string(),
+                self.data.bits_per_sample.to_string(),
+            )),
+            7 => Some(("extra_size".to_string(), self.data.extra_size.to_string())),
+            8 => Some(("mpeg_id".to_string(), self.data.mpeg_id.to_string())),
+            9 => Some(("flags".to_string(), self.data.flags.to_string())),
+            10 => Some(("block_size".to_string(), self.data.block_size.to_string())),
+            11 => Some((
+                "frames_per_block".to_string(),
+                self.data.frames_per_block.to_string(),
+            )),
+            12 => Some(("codec_delay".to_string(), self.data.codec_delay.to_string())),
+            _ => None,
+        }
+    }
+}
+
 //---------------------------
 /// `fmt ` Extended format of audio samples in `data`. (WAVEFORMATEX) [RIFF1994](https://wavref.til.cafe/chunk/fmt/)
 ///
@@ -1105,8 +1476,8 @@ pub struct FmtExtended {
     ///
     /// The size in bytes of the extra information in the WAVE format header not
     /// including the size of the `FmtExtended` structure. (size of fields from
-    /// format_tag through extra_size inclusive (all fields except id, size and
-    /// the extra_bytes))
+    /// `format_tag` through `extra_size` inclusive (all fields except `id`, `size` and
+    /// the `extra_bytes`))
     #[br()]
     #[bw(map = |_| self.extra_bytes.len() as u16)]
     pub extra_size: u16,
@@ -1214,6 +1585,8 @@ pub enum FmtEnum {
     Pcm(FmtPcm),
     Adpcm(FmtAdpcm),
     DviAdpcm(FmtDviAdpcm),
+    Mpeg(FmtMpeg),
+    MpegLayer3(FmtMpegLayer3),
     Extended(FmtExtended),
 }
 
@@ -1227,17 +1600,83 @@ impl Tag for FmtEnum {
             FmtEnum::Pcm(e) => e.format_tag(),
             FmtEnum::Adpcm(e) => e.format_tag(),
             FmtEnum::DviAdpcm(e) => e.format_tag(),
+            FmtEnum::Mpeg(e) => e.format_tag(),
+            FmtEnum::MpegLayer3(e) => e.format_tag(),
             FmtEnum::Extended(e) => e.format_tag(),
         }
     }
 }
 
+impl FmtEnum {
+    /// The sampling rate at which each channel should be played.
+    pub fn samples_per_sec(&self) -> u32 {
+        match self {
+            FmtEnum::Pcm(e) => e.samples_per_sec,
+            FmtEnum::Adpcm(e) => e.samples_per_sec,
+            FmtEnum::DviAdpcm(e) => e.samples_per_sec,
+            FmtEnum::Mpeg(e) => e.samples_per_sec,
+            FmtEnum::MpegLayer3(e) => e.samples_per_sec,
+            FmtEnum::Extended(e) => e.samples_per_sec,
+        }
+    }
+
+    /// Number of channels in the audio data.
+    pub fn channels(&self) -> u16 {
+        match self {
+            FmtEnum::Pcm(e) => e.channels,
+            FmtEnum::Adpcm(e) => e.channels,
+            FmtEnum::DviAdpcm(e) => e.channels,
+            FmtEnum::Mpeg(e) => e.channels,
+            FmtEnum::MpegLayer3(e) => e.channels,
+            FmtEnum::Extended(e) => e.channels,
+        }
+    }
+
+    /// The average number of bytes per second at which the waveform data should be transferred.
+    pub fn avg_bytes_per_sec(&self) -> u32 {
+        match self {
+            FmtEnum::Pcm(e) => e.avg_bytes_per_sec,
+            FmtEnum::Adpcm(e) => e.avg_bytes_per_sec,
+            FmtEnum::DviAdpcm(e) => e.avg_bytes_per_sec,
+            FmtEnum::Mpeg(e) => e.avg_bytes_per_sec,
+            FmtEnum::MpegLayer3(e) => e.avg_bytes_per_sec,
+            FmtEnum::Extended(e) => e.avg_bytes_per_sec,
+        }
+    }
+
+    /// The block alignment (in bytes) of the waveform data.
+    pub fn block_align(&self) -> u16 {
+        match self {
+            FmtEnum::Pcm(e) => e.block_align,
+            FmtEnum::Adpcm(e) => e.block_align,
+            FmtEnum::DviAdpcm(e) => e.block_align,
+            FmtEnum::Mpeg(e) => e.block_align,
+            FmtEnum::MpegLayer3(e) => e.block_align,
+            FmtEnum::Extended(e) => e.block_align,
+        }
+    }
+
+    /// The number of bits used to represent each sample of each channel.
+    pub fn bits_per_sample(&self) -> u16 {
+        match self {
+            FmtEnum::Pcm(e) => e.bits_per_sample,
+            FmtEnum::Adpcm(e) => e.bits_per_sample,
+            FmtEnum::DviAdpcm(e) => e.bits_per_sample,
+            FmtEnum::Mpeg(e) => e.bits_per_sample,
+            FmtEnum::MpegLayer3(e) => e.bits_per_sample,
+            FmtEnum::Extended(e) => e.bits_per_sample,
+        }
+    }
+}
+
 impl Summarizable for FmtEnum {
     fn summary(&self) -> String {
         match self {
             FmtEnum::Pcm(e) => e.summary(),
             FmtEnum::Adpcm(e) => e.summary(),
             FmtEnum::DviAdpcm(e) => e.summary(),
+            FmtEnum::Mpeg(e) => e.summary(),
+            FmtEnum::MpegLayer3(e) => e.summary(),
             FmtEnum::Extended(e) => e.summary(),
         }
     }
@@ -1247,6 +1686,8 @@ impl Summarizable for FmtEnum {
             FmtEnum::Pcm(e) => e.items(),
             FmtEnum::Adpcm(e) => e.items(),
             FmtEnum::DviAdpcm(e) => e.items(),
+            FmtEnum::Mpeg(e) => e.items(),
+            FmtEnum::MpegLayer3(e) => e.items(),
             FmtEnum::Extended(e) => e.items(),
         }
     }
@@ -1256,6 +1697,8 @@ impl Summarizable for FmtEnum {
             FmtEnum::Pcm(e) => e.name(),
             FmtEnum::Adpcm(e) => e.name(),
             FmtEnum::DviAdpcm(e) => e.name(),
+            FmtEnum::Mpeg(e) => e.name(),
+            FmtEnum::MpegLayer3(e) => e.name(),
             FmtEnum::Extended(e) => e.name(),
         }
     }
@@ -1265,6 +1708,8 @@ impl Summarizable for FmtEnum {
             FmtEnum::Pcm(e) => e.item_summary_header(),
             FmtEnum::Adpcm(e) => e.item_summary_header(),
             FmtEnum::DviAdpcm(e) => e.item_summary_header(),
+            FmtEnum::Mpeg(e) => e.item_summary_header(),
+            FmtEnum::MpegLayer3(e) => e.item_summary_header(),
             FmtEnum::Extended(e) => e.item_summary_header(),
         }
     }
@@ -1276,6 +1721,7 @@ pub type FmtChunk = KnownChunk<FmtEnum>;
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::BinRead;
 
     use super::*;
@@ -1295,6 +1741,9 @@ mod test {
                 bits_per_sample: 24,
             }),
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         let chunk = FmtChunk::read(&mut buff).expect("error parsing WAV chunks");
         assert_eq!(chunk, expected);
@@ -1358,6 +1807,51 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_fmt_mpeg() {
+        let mut buff = hex_to_cursor(
+            "666D7420 28000000 50000200 80BB0000 007D0000 00030000 16000200 00E80300
+            01000000 01001C00 00000000 00000000",
+        );
+        let chunk = FmtChunk::read(&mut buff).expect("error parsing WAV chunks");
+        dbg!(&chunk);
+        let FmtEnum::Mpeg(fmt) = chunk.data else {
+            unreachable!("variant match failed, expected FmtEnum::Mpeg, got: {chunk:?}");
+        };
+        assert_eq!(fmt.format_tag(), FormatTag::Mpeg);
+        assert_eq!(fmt.extra_size, 22);
+        assert_eq!(fmt.head_bitrate, 256_000);
+        assert_eq!(fmt.layers(), "Layer II");
+        assert_eq!(fmt.modes(), "stereo");
+        assert_eq!(fmt.flags(), "original, protection, MPEG-1");
+        assert_eq!(
+            fmt.summary(),
+            "MPEG (0x0050), 2 chan, 48000, Layer II, 256 kbps"
+        );
+    }
+
+    #[test]
+    fn parse_fmt_mpeg_layer3() {
+        let mut buff = hex_to_cursor(
+            "666D7420 1E000000 55000200 44AC0000 803E0000 01000000 0C000100 02000000
+            A1010100 7105",
+        );
+        let chunk = FmtChunk::read(&mut buff).expect("error parsing WAV chunks");
+        dbg!(&chunk);
+        let FmtEnum::MpegLayer3(fmt) = chunk.data else {
+            unreachable!("variant match failed, expected FmtEnum::MpegLayer3, got: {chunk:?}");
+        };
+        assert_eq!(fmt.format_tag(), FormatTag::Mpeglayer3);
+        assert_eq!(fmt.flags, 2);
+        assert_eq!(fmt.block_size, 417);
+        assert_eq!(fmt.frames_per_block, 1);
+        assert_eq!(fmt.codec_delay, 1393);
+        assert_eq!(
+            fmt.summary(),
+            "MPEGLAYER3 (0x0055), 2 chan, 44100, 128 kbps"
+        );
+    }
+
     #[test]
     fn parse_fmt_extended() {
         let expected = FormatTag::Unknown;
@@ -1386,4 +1880,118 @@ mod test {
         // this Enum transitively ensures the traits of all subchunks
         has_fmt_standard_traits::<FmtEnum>();
     }
+
+    fn arbitrary_fields(rng: &mut Rng) -> (u16, u32, u32, u16, u16) {
+        (
+            rng.next_u16(),
+            rng.next_u32(),
+            rng.next_u32(),
+            rng.next_u16(),
+            rng.next_u16(),
+        )
+    }
+
+    impl Arbitrary for FmtEnum {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let (channels, samples_per_sec, avg_bytes_per_sec, block_align, bits_per_sample) =
+                arbitrary_fields(rng);
+            match rng.below(6) {
+                0 => FmtEnum::Pcm(FmtPcm {
+                    channels,
+                    samples_per_sec,
+                    avg_bytes_per_sec,
+                    block_align,
+                    bits_per_sample,
+                }),
+                1 => {
+                    let coefficients: Vec<AdpcmCoefficients> = (0..rng.below(8))
+                        .map(|_| AdpcmCoefficients {
+                            coef1: rng.next_u16() as i16,
+                            coef2: rng.next_u16() as i16,
+                        })
+                        .collect();
+                    let coefficient_count = coefficients.len() as u16;
+                    FmtEnum::Adpcm(FmtAdpcm {
+                        channels,
+                        samples_per_sec,
+                        avg_bytes_per_sec,
+                        block_align,
+                        bits_per_sample,
+                        extra_size: coefficient_count * 4 + 4,
+                        samples_per_block: rng.next_u16(),
+                        coefficient_count,
+                        coefficients,
+                    })
+                }
+                2 => FmtEnum::DviAdpcm(FmtDviAdpcm {
+                    channels,
+                    samples_per_sec,
+                    avg_bytes_per_sec,
+                    block_align,
+                    bits_per_sample,
+                    extra_size: 2,
+                    samples_per_block: rng.next_u16(),
+                }),
+                3 => FmtEnum::Mpeg(FmtMpeg {
+                    channels,
+                    samples_per_sec,
+                    avg_bytes_per_sec,
+                    block_align,
+                    bits_per_sample,
+                    extra_size: 22,
+                    head_layer: rng.next_u16(),
+                    head_bitrate: rng.next_u32(),
+                    head_mode: rng.next_u16(),
+                    head_mode_ext: rng.next_u16(),
+                    head_emphasis: rng.next_u16(),
+                    head_flags: rng.next_u16(),
+                    pts_low: rng.next_u32(),
+                    pts_high: rng.next_u32(),
+                }),
+                4 => FmtEnum::MpegLayer3(FmtMpegLayer3 {
+                    channels,
+                    samples_per_sec,
+                    avg_bytes_per_sec,
+                    block_align,
+                    bits_per_sample,
+                    extra_size: 12,
+                    mpeg_id: rng.next_u16(),
+                    flags: rng.next_u32(),
+                    block_size: rng.next_u16(),
+                    frames_per_block: rng.next_u16(),
+                    codec_delay: rng.next_u16(),
+                }),
+                _ => {
+                    // tags with their own parser are never read as Extended
+                    let mut format_tag = FormatTag::from(rng.next_u16());
+                    if [
+                        FormatTag::Pcm,
+                        FormatTag::Adpcm,
+                        FormatTag::DviAdpcm,
+                        FormatTag::Mpeg,
+                        FormatTag::Mpeglayer3,
+                    ]
+                    .contains(&format_tag)
+                    {
+                        format_tag = FormatTag::Extensible;
+                    }
+                    let extra_bytes = rng.bytes(24);
+                    FmtEnum::Extended(FmtExtended {
+                        format_tag,
+                        channels,
+                        samples_per_sec,
+                        avg_bytes_per_sec,
+                        block_align,
+                        bits_per_sample,
+                        extra_size: extra_bytes.len() as u16,
+                        extra_bytes,
+                    })
+                }
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        fmt_roundtrip: FmtEnum,
+    }
 }
@@ -1,11 +1,19 @@
 //! `INFO` A `LIST` containing descriptive text chunks: IARL, IGNR, IKEY, ILGT, IMED, INAM, IPLT, IPRD, ISBJ, ISFT, ISHP, IART, ISRC, ISRF, ITCH, ICMS, ICMT, ICOP, ICRD, ICRP, IDPI, IENG, ISMP, IDIT. [RIFF1991](https://wavref.til.cafe/chunk/info/), [RIFF1994](https://wavref.til.cafe/chunk/info/)
 
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::{Debug, Formatter};
 
 use binrw::{binrw, helpers, NullString};
 use itertools::Itertools;
 
-use crate::{fourcc, ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::time::{Date, Rational, TimeOfDay, Timecode};
+use crate::{
+    codepage, fourcc, ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable, WaveFileError,
+};
 
 /// `LIST-INFO` holds subchunks of strings describing the WAVE.
 #[binrw]
@@ -26,12 +34,129 @@ pub struct ListInfo {
 impl ListInfo {
     /// Chunk id constant: `INFO`
     pub const LIST_TYPE: FourCC = FourCC(*b"INFO");
+
+    /// Decode all text as read using `code_page`.
+    pub(crate) fn set_code_page(&mut self, code_page: u16) {
+        for chunk in &mut self.chunks {
+            chunk.set_code_page(code_page);
+        }
+    }
+
+    /// Set the text of subchunk `id`. Ex: `INAM`
+    ///
+    /// Replaces the first subchunk with `id` in place and removes any
+    /// duplicates, or appends a new subchunk. Returns an error if `text`
+    /// contains a zero byte, see [`InfoEnum::new()`].
+    ///
+    /// The size of an enclosing [`ListInfoChunk`] is not updated, wrap the
+    /// result with [`KnownChunk::new()`] before writing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::info::ListInfo;
+    /// use wavrw::FourCC;
+    ///
+    /// let mut info = ListInfo::builder().text(FourCC(*b"INAM"), "Rain").build()?;
+    /// info.set(FourCC(*b"ICMT"), "recorded in the garden")?;
+    /// info.set(FourCC(*b"INAM"), "Rain on a tin roof")?;
+    /// assert_eq!(info.chunks.len(), 2);
+    /// assert_eq!(info.chunks[0].text(), "Rain on a tin roof");
+    ///
+    /// assert!(info.remove(FourCC(*b"ICMT")));
+    /// assert_eq!(info.chunks.len(), 1);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn set(&mut self, id: FourCC, text: &str) -> Result<(), WaveFileError> {
+        let chunk = InfoEnum::new(id, text)?;
+        if let Some(index) = self.chunks.iter().position(|c| c.id() == id) {
+            self.chunks[index] = chunk;
+            let mut seen = false;
+            self.chunks.retain(|c| {
+                if c.id() != id {
+                    return true;
+                }
+                let first = !seen;
+                seen = true;
+                first
+            });
+        } else {
+            self.chunks.push(chunk);
+        }
+        Ok(())
+    }
+
+    /// Remove all subchunks with `id`, returns `true` if any were removed.
+    pub fn remove(&mut self, id: FourCC) -> bool {
+        let len = self.chunks.len();
+        self.chunks.retain(|c| c.id() != id);
+        self.chunks.len() != len
+    }
 }
 
 impl KnownChunkID for ListInfo {
     const ID: FourCC = FourCC(*b"LIST");
 }
 
+impl ListInfo {
+    /// Start building a `LIST-INFO` chunk, see [`ListInfoBuilder`].
+    pub fn builder() -> ListInfoBuilder {
+        ListInfoBuilder::default()
+    }
+}
+
+/// Builds a [`ListInfo`] from (id, text) pairs.
+///
+/// # Examples
+///
+/// ```
+/// use wavrw::chunk::info::ListInfo;
+/// use wavrw::FourCC;
+///
+/// let info = ListInfo::builder()
+///     .text(FourCC(*b"INAM"), "Rain")
+///     .text(FourCC(*b"IART"), "wavrw")
+///     .text(FourCC(*b"INAM"), "Rain on a tin roof")
+///     .build()?;
+/// assert_eq!(info.chunks.len(), 2);
+/// assert_eq!(info.chunks[0].text(), "Rain on a tin roof");
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListInfoBuilder {
+    chunks: Vec<(FourCC, String)>,
+}
+
+impl ListInfoBuilder {
+    /// Set the text for `id`. Ex: `INAM`
+    ///
+    /// Setting the same id again replaces the text, keeping its position.
+    #[must_use]
+    pub fn text(mut self, id: FourCC, text: &str) -> Self {
+        if let Some(entry) = self.chunks.iter_mut().find(|(i, _)| *i == id) {
+            entry.1 = text.to_string();
+        } else {
+            self.chunks.push((id, text.to_string()));
+        }
+        self
+    }
+
+    /// Create the [`ListInfo`], with subchunks in the order they were added.
+    ///
+    /// Returns an error if any text contains a zero byte, see
+    /// [`InfoEnum::new()`].
+    pub fn build(self) -> Result<ListInfo, WaveFileError> {
+        Ok(ListInfo {
+            list_type: ListInfo::LIST_TYPE,
+            chunks: self
+                .chunks
+                .iter()
+                .map(|(id, text)| InfoEnum::new(*id, text))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 impl Summarizable for ListInfo {
     fn summary(&self) -> String {
         self.chunks.iter().map(|c| c.id()).join(", ")
@@ -41,8 +166,16 @@ impl Summarizable for ListInfo {
         format!("{}-{}", self.id(), self.list_type)
     }
 
+    /// The text of each subchunk. Text which is not valid UTF-8 is followed
+    /// by its guessed encoding, keyed `{id}.encoding`, see
+    /// [`InfoEnum::detect_encoding()`].
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
-        Box::new(self.chunks.iter().map(|c| (c.id().to_string(), c.text())))
+        Box::new(self.chunks.iter().flat_map(|c| {
+            let encoding = c
+                .detect_encoding()
+                .map(|guess| (format!("{}.encoding", c.id()), guess.to_string()));
+            core::iter::once((c.id().to_string(), c.text())).chain(encoding)
+        }))
     }
 
     fn item_summary_header(&self) -> String {
@@ -79,9 +212,17 @@ pub type ListInfoChunk = KnownChunk<ListInfo>;
 #[br(import(_size: u32))]
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Info<const I: u32> {
+    /// Text bytes as read, used by [`Info::decode()`].
+    ///
+    /// Written instead of `text` while `text` is unchanged, so text which is
+    /// not valid UTF-8 is written back as read.
+    #[br(map= |ns: NullString| ns.0)]
+    #[bw(ignore)]
+    pub raw: Vec<u8>,
+
     /// Generic container for `info` chunk text.
-    #[br(map= |ns: NullString| ns.to_string())]
-    #[bw(map= |s: &String| NullString::from(s.clone()))]
+    #[br(calc= codepage::decode(&raw, None))]
+    #[bw(map= |s: &String| text_bytes(raw, s))]
     pub text: String,
 }
 
@@ -106,7 +247,20 @@ impl<const I: u32> Summarizable for Info<I> {
 impl<const I: u32> Info<I> {
     /// Creates a new [`Info<I>`] chunk.
     pub fn new(text: &str) -> Self {
-        Info::<I> { text: text.into() }
+        Info::<I> {
+            raw: text.as_bytes().to_vec(),
+            text: text.into(),
+        }
+    }
+
+    /// Decode the text as read using `code_page`, see [`codepage::decode()`].
+    ///
+    /// Returns `text` unchanged if it was modified after reading.
+    pub fn decode(&self, code_page: u16) -> String {
+        if codepage::decode(&self.raw, None) != self.text {
+            return self.text.clone();
+        }
+        codepage::decode(&self.raw, Some(code_page))
     }
 }
 
@@ -276,6 +430,67 @@ pub type IsmpChunk = KnownChunk<Ismp>;
 /// system time at the moment capture is initiated.
 pub type IditChunk = KnownChunk<Idit>;
 
+impl Ismp {
+    /// Parsed `HH:MM:SS.FF` timecode at `rate`, `None` if empty or invalid.
+    ///
+    /// `ISMP` doesn't record the frame rate or drop frame, any separator is
+    /// accepted before the frames.
+    pub fn parsed_timecode(&self, rate: Rational, drop_frame: bool) -> Option<Timecode> {
+        Timecode::parse_with_flag(&self.text, rate, drop_frame).ok()
+    }
+}
+
+impl Idit {
+    /// Parsed digitization date and time, `None` if empty or invalid.
+    ///
+    /// Accepts the `ctime` format from the specification, `Wed Jan 02
+    /// 02:03:55 1990`, and the `yyyy-mm-dd hh:mm:ss` or `yyyy:mm:dd
+    /// hh:mm:ss` written by many cameras, with a space or `T` between date
+    /// and time. The day must exist in the month.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::info::Idit;
+    ///
+    /// let (date, time) = Idit::new("Wed Jan 02 02:03:55 1990\n").parsed_date_time().unwrap();
+    /// assert_eq!(format!("{date} {time}"), "1990-01-02 02:03:55");
+    /// let (date, _) = Idit::new("2019:05:12 14:33:21").parsed_date_time().unwrap();
+    /// assert_eq!(date.to_string(), "2019-05-12");
+    /// assert!(Idit::new("Fri Feb 30 02:03:55 1990").parsed_date_time().is_none());
+    /// ```
+    pub fn parsed_date_time(&self) -> Option<(Date, TimeOfDay)> {
+        let text = self
+            .text
+            .trim_matches(|c: char| c.is_whitespace() || c == '\0');
+        let fields = text.split_whitespace().collect::<Vec<_>>();
+        let (date, time) = if let [weekday, month, day, time, year] = fields[..] {
+            if !WEEKDAYS.iter().any(|w| w.eq_ignore_ascii_case(weekday)) {
+                return None;
+            }
+            let month = MONTHS.iter().position(|m| m.eq_ignore_ascii_case(month))?;
+            let date = Date {
+                year: year.parse().ok()?,
+                month: u8::try_from(month + 1).ok()?,
+                day: day.parse().ok()?,
+            };
+            (date, time.parse().ok()?)
+        } else {
+            let (date, time) = text.split_once([' ', 'T'])?;
+            (date.parse().ok()?, time.parse().ok()?)
+        };
+        date.is_valid().then_some((date, time))
+    }
+}
+
+/// Weekday abbreviations of the `ctime` format used by `IDIT`.
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Month abbreviations of the `ctime` format used by `IDIT`.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
 /// All `LIST-INFO` chunk structs as an enum
 #[allow(missing_docs)]
 #[binrw]
@@ -306,12 +521,18 @@ pub enum InfoEnum {
     Ieng(IengChunk),
     Ismp(IsmpChunk),
     Idit(IditChunk),
+    /// A subchunk with an id not listed above, ex: a vendor tag like
+    /// `ITRK`. `raw` and `text` are the same as for the typed variants.
     Unknown {
         id: FourCC,
         size: u32,
-        #[brw(align_after=2, pad_size_to= size.to_owned())]
-        #[br(map= |ns: NullString| ns.to_string())]
-        #[bw(map= |s: &String| NullString::from(s.clone()))]
+        #[br(align_after = 2, parse_with = crate::read_bytes, args(size.into()))]
+        #[br(map = until_nul)]
+        #[bw(ignore)]
+        raw: Vec<u8>,
+        #[br(calc= codepage::decode(&raw, None))]
+        #[bw(align_after=2, pad_size_to= size.to_owned())]
+        #[bw(map= |s: &String| unknown_text_bytes(raw, s, *size))]
         text: String,
     },
 }
@@ -348,7 +569,89 @@ impl ChunkID for InfoEnum {
     }
 }
 
+/// Bytes to write for `text`: `raw` if `text` was decoded from it unchanged.
+fn text_bytes(raw: &[u8], text: &str) -> NullString {
+    if codepage::decode(raw, None) == text {
+        NullString(raw.to_vec())
+    } else {
+        NullString::from(text)
+    }
+}
+
+/// Bytes to write for the text of an `Unknown` subchunk, see
+/// [`text_bytes()`]. Without a NULL terminator if the text filled `size`
+/// without one when read.
+fn unknown_text_bytes(raw: &[u8], text: &str, size: u32) -> Vec<u8> {
+    let mut bytes = text_bytes(raw, text).0;
+    if bytes.len() < usize::try_from(size).unwrap_or(usize::MAX) {
+        bytes.push(0);
+    }
+    bytes
+}
+
+/// Text bytes of an `Unknown` subchunk, up to the first NULL byte. Unlike
+/// `NullString`, stops at the end of the subchunk if it isn't terminated.
+fn until_nul(mut bytes: Vec<u8>) -> Vec<u8> {
+    if let Some(end) = bytes.iter().position(|&b| b == 0) {
+        bytes.truncate(end);
+    }
+    bytes
+}
+
+/// Wrap text in a sized `Info<I>` chunk.
+fn info_chunk<const I: u32>(text: &str) -> Result<KnownChunk<Info<I>>, WaveFileError> {
+    KnownChunk::new(Info::new(text))
+}
+
 impl InfoEnum {
+    /// Create a subchunk with `id` and `text`, using the typed variant for
+    /// known ids and `Unknown` otherwise.
+    ///
+    /// Returns an error if `text` contains a zero byte, which would end the
+    /// text early when read back.
+    pub fn new(id: FourCC, text: &str) -> Result<InfoEnum, WaveFileError> {
+        if text.contains('\0') {
+            return Err(WaveFileError::parse(
+                None,
+                format!("{id} text contains a zero byte"),
+            ));
+        }
+        Ok(match &id.0 {
+            b"IARL" => InfoEnum::Iarl(info_chunk(text)?),
+            b"IGNR" => InfoEnum::Ignr(info_chunk(text)?),
+            b"IKEY" => InfoEnum::Ikey(info_chunk(text)?),
+            b"ILGT" => InfoEnum::Ilgt(info_chunk(text)?),
+            b"IMED" => InfoEnum::Imed(info_chunk(text)?),
+            b"INAM" => InfoEnum::Inam(info_chunk(text)?),
+            b"IPLT" => InfoEnum::Iplt(info_chunk(text)?),
+            b"IPRD" => InfoEnum::Iprd(info_chunk(text)?),
+            b"ISBJ" => InfoEnum::Isbj(info_chunk(text)?),
+            b"ISFT" => InfoEnum::Isft(info_chunk(text)?),
+            b"ISHP" => InfoEnum::Ishp(info_chunk(text)?),
+            b"IART" => InfoEnum::Iart(info_chunk(text)?),
+            b"ISRC" => InfoEnum::Isrc(info_chunk(text)?),
+            b"ISRF" => InfoEnum::Isrf(info_chunk(text)?),
+            b"ITCH" => InfoEnum::Itch(info_chunk(text)?),
+            b"ICMS" => InfoEnum::Icms(info_chunk(text)?),
+            b"ICMT" => InfoEnum::Icmt(info_chunk(text)?),
+            b"ICOP" => InfoEnum::Icop(info_chunk(text)?),
+            b"ICRD" => InfoEnum::Icrd(info_chunk(text)?),
+            b"ICRP" => InfoEnum::Icrp(info_chunk(text)?),
+            b"IDPI" => InfoEnum::Idpi(info_chunk(text)?),
+            b"IENG" => InfoEnum::Ieng(info_chunk(text)?),
+            b"ISMP" => InfoEnum::Ismp(info_chunk(text)?),
+            b"IDIT" => InfoEnum::Idit(info_chunk(text)?),
+            _ => InfoEnum::Unknown {
+                id,
+                size: u32::try_from(text.len() + 1).map_err(|_| {
+                    WaveFileError::parse(None, format!("{id} text too large for a RIFF chunk"))
+                })?,
+                raw: text.as_bytes().to_vec(),
+                text: text.to_string(),
+            },
+        })
+    }
+
     /// Return a clone of the inner chunks' text field.
     pub fn text(&self) -> String {
         match self {
@@ -376,7 +679,96 @@ impl InfoEnum {
             InfoEnum::Ieng(e) => e.data.text.clone(),
             InfoEnum::Ismp(e) => e.data.text.clone(),
             InfoEnum::Idit(e) => e.data.text.clone(),
-            InfoEnum::Unknown { text, .. } => format!("Unknown(\"{}\")", *text),
+            InfoEnum::Unknown { text, .. } => text.clone(),
+        }
+    }
+    /// Text bytes as read, without the NULL terminator.
+    ///
+    /// [`InfoEnum::text()`] decodes text which is not valid UTF-8 as Latin-1,
+    /// or using the file's code page. The raw bytes are kept to diagnose
+    /// encoding problems, and are written back unchanged.
+    pub fn raw(&self) -> &[u8] {
+        match self {
+            InfoEnum::Iarl(e) => &e.data.raw,
+            InfoEnum::Ignr(e) => &e.data.raw,
+            InfoEnum::Ikey(e) => &e.data.raw,
+            InfoEnum::Ilgt(e) => &e.data.raw,
+            InfoEnum::Imed(e) => &e.data.raw,
+            InfoEnum::Inam(e) => &e.data.raw,
+            InfoEnum::Iplt(e) => &e.data.raw,
+            InfoEnum::Iprd(e) => &e.data.raw,
+            InfoEnum::Isbj(e) => &e.data.raw,
+            InfoEnum::Isft(e) => &e.data.raw,
+            InfoEnum::Ishp(e) => &e.data.raw,
+            InfoEnum::Iart(e) => &e.data.raw,
+            InfoEnum::Isrc(e) => &e.data.raw,
+            InfoEnum::Isrf(e) => &e.data.raw,
+            InfoEnum::Itch(e) => &e.data.raw,
+            InfoEnum::Icms(e) => &e.data.raw,
+            InfoEnum::Icmt(e) => &e.data.raw,
+            InfoEnum::Icop(e) => &e.data.raw,
+            InfoEnum::Icrd(e) => &e.data.raw,
+            InfoEnum::Icrp(e) => &e.data.raw,
+            InfoEnum::Idpi(e) => &e.data.raw,
+            InfoEnum::Ieng(e) => &e.data.raw,
+            InfoEnum::Ismp(e) => &e.data.raw,
+            InfoEnum::Idit(e) => &e.data.raw,
+            InfoEnum::Unknown { raw, .. } => raw,
+        }
+    }
+
+    /// The raw bytes decoded as UTF-8, with invalid sequences replaced by
+    /// `U+FFFD`.
+    pub fn text_lossy(&self) -> String {
+        String::from_utf8_lossy(self.raw()).into_owned()
+    }
+
+    /// Returns true if the raw bytes are valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        core::str::from_utf8(self.raw()).is_ok()
+    }
+
+    /// Guess the encoding of the raw bytes, see [`codepage::detect()`].
+    ///
+    /// Returns `None` for valid UTF-8, and if a code page or an edit changed
+    /// the text.
+    pub fn detect_encoding(&self) -> Option<codepage::Detection> {
+        if self.is_utf8() || codepage::decode(self.raw(), None) != self.text() {
+            return None;
+        }
+        codepage::detect(self.raw())
+    }
+
+    /// Replace the text with the text as read, decoded using `code_page`.
+    ///
+    /// `Unknown` chunks are not changed.
+    pub(crate) fn set_code_page(&mut self, code_page: u16) {
+        match self {
+            InfoEnum::Iarl(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ignr(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ikey(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ilgt(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Imed(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Inam(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Iplt(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Iprd(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Isbj(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Isft(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ishp(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Iart(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Isrc(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Isrf(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Itch(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Icms(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Icmt(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Icop(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Icrd(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Icrp(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Idpi(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ieng(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Ismp(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Idit(e) => e.data.text = e.data.decode(code_page),
+            InfoEnum::Unknown { .. } => (),
         }
     }
 }
@@ -384,6 +776,7 @@ impl InfoEnum {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::{BinRead, BinWrite};
     use hexdump::hexdump;
 
@@ -396,9 +789,13 @@ mod test {
             offset: Some(0),
             size: 8,
             data: Icmt {
+                raw: b"comment".to_vec(),
                 text: String::from("comment"),
             },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         });
         println!("{icmt:?}");
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
@@ -453,9 +850,195 @@ mod test {
         // assert_eq!(
     }
 
+    #[test]
+    fn build_listinfo() {
+        let info = ListInfo::builder()
+            .text(FourCC(*b"ISFT"), "BWF MetaEdit")
+            .text(FourCC(*b"ICMT"), "bext chunk test file")
+            .build()
+            .unwrap();
+        let chunk = ListInfoChunk::new(info).unwrap();
+        let mut buff = std::io::Cursor::new(Vec::<u8>::new());
+        chunk.write(&mut buff).unwrap();
+        // same bytes as listinfochunk_small_valid(), plus the last padding byte
+        let expected = hex_to_cursor(
+            "4C495354 38000000 494E464F 49534654 0D000000 42574620 4D657461 45646974 00004943 4D541500 00006265 78742063 68756E6B 20746573 74206669 6C650000",
+        );
+        assert_eq!(buff.get_ref(), expected.get_ref());
+    }
+
+    #[test]
+    fn listinfo_set_remove() {
+        // duplicate ICMT chunks, as found in some files
+        let mut info = ListInfo::builder()
+            .text(FourCC(*b"ICMT"), "first")
+            .text(FourCC(*b"ISFT"), "wavrw")
+            .build()
+            .unwrap();
+        info.chunks
+            .push(InfoEnum::new(FourCC(*b"ICMT"), "second").unwrap());
+
+        info.set(FourCC(*b"ICMT"), "even").unwrap();
+        dbg!(&info);
+        assert_eq!(
+            info.chunks.iter().map(InfoEnum::text).collect::<Vec<_>>(),
+            vec!["even", "wavrw"]
+        );
+        assert!(info.set(FourCC(*b"ISFT"), "a\0b").is_err());
+        assert_eq!(info.chunks[1].text(), "wavrw");
+
+        // even length text is NUL terminated, then padded
+        let chunk = ListInfoChunk::new(info.clone()).unwrap();
+        let mut buff = std::io::Cursor::new(Vec::<u8>::new());
+        chunk.write(&mut buff).unwrap();
+        assert_eq!(chunk.size, 4 + 14 + 14);
+        buff.set_position(0);
+        let read = ListInfoChunk::read(&mut buff).unwrap();
+        assert_eq!(read.data.chunks[0].text(), "even");
+        assert_eq!(read.data.chunks[1].text(), "wavrw");
+
+        assert!(info.remove(FourCC(*b"ICMT")));
+        assert!(!info.remove(FourCC(*b"ICMT")));
+        assert_eq!(info.chunks.len(), 1);
+    }
+
+    #[test]
+    fn build_info_unknown() {
+        let unknown = InfoEnum::new(FourCC(*b"IXYZ"), "odd").unwrap();
+        let mut buff = std::io::Cursor::new(Vec::<u8>::new());
+        unknown.write(&mut buff).unwrap();
+        assert_eq!(buff.get_ref(), b"IXYZ\x04\0\0\0odd\0");
+        buff.set_position(0);
+        assert_eq!(InfoEnum::read(&mut buff).unwrap().text(), "odd");
+        assert!(InfoEnum::new(FourCC(*b"ICMT"), "a\0b").is_err());
+    }
+
+    #[test]
+    fn info_not_utf8() {
+        // "Café" in Latin-1, in a known and an unknown subchunk
+        let mut list = b"LIST\x20\x00\x00\x00INFO".to_vec();
+        for id in [b"INAM", b"IXYZ"] {
+            list.extend_from_slice(id);
+            list.extend_from_slice(b"\x05\x00\x00\x00Caf\xE9\x00\x00");
+        }
+        let mut buff = std::io::Cursor::new(list.clone());
+        let chunk = ListInfoChunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        let inam = &chunk.data.chunks[0];
+        assert_eq!(inam.text(), "Café");
+        assert_eq!(inam.raw(), b"Caf\xE9");
+        assert_eq!(inam.text_lossy(), "Caf\u{FFFD}");
+        assert!(!inam.is_utf8());
+        assert_eq!(chunk.data.chunks[1].raw(), b"Caf\xE9");
+
+        // written back as read
+        let mut written = std::io::Cursor::new(Vec::<u8>::new());
+        chunk.write(&mut written).unwrap();
+        assert_eq!(written.get_ref(), &list);
+
+        // reading the file records a warning per subchunk
+        let mut file = b"RIFF\x2C\x00\x00\x00WAVE".to_vec();
+        file.extend_from_slice(&list);
+        let mut wave = crate::WaveFile::from_reader(std::io::Cursor::new(file)).unwrap();
+        assert_eq!(wave.iter_chunks().count(), 1);
+        let warnings = wave.warnings();
+        dbg!(warnings);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            warnings[0].message,
+            "INAM text is not valid UTF-8, decoded as Latin-1, \
+             likely Windows-1252 (code page 1252), 90% confidence"
+        );
+
+        // items note the guessed encoding
+        let keys: Vec<_> = chunk.data.items().map(|(key, _)| key).collect();
+        assert_eq!(keys, ["INAM", "INAM.encoding", "IXYZ", "IXYZ.encoding"]);
+    }
+
+    #[test]
+    fn info_unknown_tags() {
+        // vendor tags: track number with a pad byte, a tag without a NULL
+        // terminator and one with bytes after the NULL
+        let mut list = b"LIST\x34\x00\x00\x00INFO".to_vec();
+        list.extend_from_slice(b"ITRK\x02\x00\x00\x007\x00");
+        list.extend_from_slice(b"IXYZ\x04\x00\x00\x00abcd");
+        list.extend_from_slice(b"INAM\x06\x00\x00\x00Rain\x00\x00");
+        list.extend_from_slice(b"IABC\x03\x00\x00\x00x\x00\x00\x00");
+        let mut buff = std::io::Cursor::new(list.clone());
+        let chunk = ListInfoChunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        let items: Vec<_> = chunk.data.items().collect();
+        assert_eq!(
+            items,
+            [
+                ("ITRK", "7"),
+                ("IXYZ", "abcd"),
+                ("INAM", "Rain"),
+                ("IABC", "x")
+            ]
+            .map(|(id, text)| (id.to_string(), text.to_string()))
+        );
+        let InfoEnum::Unknown { id, text, raw, .. } = &chunk.data.chunks[1] else {
+            panic!("expected Unknown, got {:?}", chunk.data.chunks[1]);
+        };
+        assert_eq!(
+            (id, text.as_str(), raw.as_slice()),
+            (&FourCC(*b"IXYZ"), "abcd", &b"abcd"[..])
+        );
+
+        let mut written = std::io::Cursor::new(Vec::<u8>::new());
+        chunk.write(&mut written).unwrap();
+        assert_eq!(written.get_ref(), &list);
+    }
+
+    #[test]
+    fn info_ismp_idit() {
+        let info = ListInfo::builder()
+            .text(FourCC(*b"ISMP"), "01:00:00.12")
+            .text(FourCC(*b"IDIT"), "2023-11-05T08:15:00")
+            .build()
+            .unwrap();
+        let InfoEnum::Ismp(ismp) = &info.chunks[0] else {
+            panic!("expected ISMP, got {:?}", info.chunks[0]);
+        };
+        let timecode = ismp
+            .data
+            .parsed_timecode(Rational::from(25), false)
+            .unwrap();
+        dbg!(&timecode);
+        assert_eq!(timecode.to_string(), "01:00:00:12");
+        assert!(Ismp::new("")
+            .parsed_timecode(Rational::from(25), false)
+            .is_none());
+        assert!(Ismp::new("01:00:00.25")
+            .parsed_timecode(Rational::from(25), false)
+            .is_none());
+
+        let InfoEnum::Idit(idit) = &info.chunks[1] else {
+            panic!("expected IDIT, got {:?}", info.chunks[1]);
+        };
+        let (date, time) = idit.data.parsed_date_time().unwrap();
+        assert_eq!(format!("{date} {time}"), "2023-11-05 08:15:00");
+        let (date, time) = Idit::new("Thu Feb 29 23:59:59 2024\n\0")
+            .parsed_date_time()
+            .unwrap();
+        assert_eq!(format!("{date} {time}"), "2024-02-29 23:59:59");
+        for text in [
+            "",
+            "Thu Feb 29 23:59:59 2023",
+            "Thu Feb 20 24:00:00 2023",
+            "Xyz Feb 20 12:00:00 2023",
+            "2023-13-01 12:00:00",
+            "2023-11-05",
+        ] {
+            assert!(Idit::new(text).parsed_date_time().is_none(), "{text:?}");
+        }
+    }
+
     #[test]
     fn infochunk_debug_string() {
         let icmt = Icmt {
+            raw: b"comment".to_vec(),
             text: "comment".to_string(),
         };
         println!("{icmt:?}");
@@ -469,8 +1052,28 @@ mod test {
             size: 8,
             data: Icmt::new("comment"),
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         // ensure trait bounds are satisfied
         let mut _trt: Box<dyn SizedChunk> = Box::new(icmt);
     }
+
+    impl Arbitrary for ListInfo {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let ids = [b"INAM", b"IART", b"ICMT", b"ICRD", b"ISFT", b"IZZZ"];
+            let mut builder = ListInfo::builder();
+            for _ in 0..rng.below(6) {
+                let id = FourCC(*ids[rng.below(ids.len())]);
+                builder = builder.text(id, &rng.ascii(40));
+            }
+            // round trip once, so sub-chunks have the offsets they get when read
+            crate::testing::roundtrip(&builder.build().expect("text has no zero bytes"))
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        list_info_roundtrip: ListInfo,
+    }
 }
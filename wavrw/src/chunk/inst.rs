@@ -1,8 +1,16 @@
 //! `inst` Pitch, volume, and velocity for playback by sampler. [RIFF1994](https://wavref.til.cafe/chunk/inst/)
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
 use binrw::binrw;
 
-use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::chunk::smpl::{midi_note_name, Smpl};
+use crate::warning::Code;
+use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable, Warning};
 
 /// `inst` Pitch, volume, and velocity for playback by sampler. [RIFF1994](https://wavref.til.cafe/chunk/inst/)
 #[binrw]
@@ -19,7 +27,7 @@ pub struct Inst {
     /// Pitch shift adjustment in cents.
     ///
     /// Pitch shift adjustment in cents. (or 100ths of a semitone) needed to
-    /// hit `unshifted_note` value exactly.  fine_tune can be used to compensate
+    /// hit `unshifted_note` value exactly.  `fine_tune` can be used to compensate
     /// for tuning errors in the sampling process. Valid values range from -50
     /// to 50.
     pub fine_tune: i8,
@@ -55,13 +63,91 @@ impl KnownChunkID for Inst {
     const ID: FourCC = FourCC(*b"inst");
 }
 
+impl Inst {
+    /// Suggested MIDI note range of the sample.
+    pub fn note_range(&self) -> RangeInclusive<u8> {
+        self.low_note..=self.high_note
+    }
+
+    /// Suggested MIDI velocity range of the sample.
+    pub fn velocity_range(&self) -> RangeInclusive<u8> {
+        self.low_velocity..=self.high_velocity
+    }
+
+    /// Check values are within their valid ranges.
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        for (name, value) in [
+            ("unshifted_note", self.unshifted_note),
+            ("low_note", self.low_note),
+            ("high_note", self.high_note),
+            ("low_velocity", self.low_velocity),
+            ("high_velocity", self.high_velocity),
+        ] {
+            if value > 127 {
+                warnings.push(warning(format!("{name} ({value}) is above 127")));
+            }
+        }
+        if !(-50..=50).contains(&self.fine_tune) {
+            warnings.push(warning(format!(
+                "fine_tune ({}) is outside -50 to 50 cents",
+                self.fine_tune
+            )));
+        }
+        if self.note_range().is_empty() {
+            warnings.push(warning(format!(
+                "low_note ({}) is above high_note ({})",
+                self.low_note, self.high_note
+            )));
+        }
+        if self.velocity_range().is_empty() {
+            warnings.push(warning(format!(
+                "low_velocity ({}) is above high_velocity ({})",
+                self.low_velocity, self.high_velocity
+            )));
+        }
+        warnings
+    }
+
+    /// Compare with the `smpl` chunk from the same file.
+    ///
+    /// Both chunks specify the MIDI note of the sample at its original pitch,
+    /// samplers disagree on which one to use when they differ.
+    pub fn check_smpl(&self, smpl: &Smpl) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        if u32::from(self.unshifted_note) != smpl.midi_unity_note {
+            warnings.push(
+                warning(format!(
+                    "unshifted_note ({}) does not match smpl midi_unity_note ({})",
+                    note_display(self.unshifted_note),
+                    smpl.midi_unity_note_name()
+                        .unwrap_or_else(|| smpl.midi_unity_note.to_string()),
+                ))
+                .with_code(Code::MetadataMismatch),
+            );
+        }
+        warnings
+    }
+}
+
+/// A [`Warning`] about an invalid value in the `inst` chunk.
+fn warning(message: String) -> Warning {
+    Warning::new(Inst::ID, None, message).with_code(Code::InvalidValue)
+}
+
+/// MIDI note name, or the number if out of range. Ex: `C3`
+fn note_display(note: u8) -> String {
+    midi_note_name(note.into()).unwrap_or_else(|| note.to_string())
+}
+
 impl Summarizable for Inst {
     fn summary(&self) -> String {
         format!(
-            "note: {} ({}-{}), gain: {}, velocity: {}-{}",
-            self.unshifted_note,
-            self.low_note,
-            self.high_note,
+            "note: {} ({}-{}), fine tune: {:+} cents, gain: {} dB, velocity: {}-{}",
+            note_display(self.unshifted_note),
+            note_display(self.low_note),
+            note_display(self.high_note),
+            self.fine_tune,
             self.gain,
             self.low_velocity,
             self.high_velocity
@@ -103,12 +189,33 @@ impl<'a> Iterator for InstIterator<'a> {
         match self.index {
             1 => Some((
                 "unshifted_note".to_string(),
-                self.data.unshifted_note.to_string(),
+                format!(
+                    "{} ({})",
+                    self.data.unshifted_note,
+                    note_display(self.data.unshifted_note)
+                ),
+            )),
+            2 => Some((
+                "fine_tune".to_string(),
+                format!("{:+} cents", self.data.fine_tune),
+            )),
+            3 => Some(("gain".to_string(), format!("{} dB", self.data.gain))),
+            4 => Some((
+                "low_note".to_string(),
+                format!(
+                    "{} ({})",
+                    self.data.low_note,
+                    note_display(self.data.low_note)
+                ),
+            )),
+            5 => Some((
+                "high_note".to_string(),
+                format!(
+                    "{} ({})",
+                    self.data.high_note,
+                    note_display(self.data.high_note)
+                ),
             )),
-            2 => Some(("fine_tune".to_string(), self.data.fine_tune.to_string())),
-            3 => Some(("gain".to_string(), self.data.gain.to_string())),
-            4 => Some(("low_note".to_string(), self.data.low_note.to_string())),
-            5 => Some(("high_note".to_string(), self.data.high_note.to_string())),
             6 => Some((
                 "low_velocity".to_string(),
                 self.data.low_velocity.to_string(),
@@ -128,6 +235,7 @@ pub type InstChunk = KnownChunk<Inst>;
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::BinRead;
 
     use super::*;
@@ -149,9 +257,79 @@ mod test {
                 high_velocity: 127,
             },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
 
         let chunk = InstChunk::read(&mut buff).expect("error parsing inst chunk");
         assert_eq!(chunk, expected);
     }
+
+    #[test]
+    fn inst_musical_terms() {
+        let inst = Inst {
+            unshifted_note: 60,
+            fine_tune: -12,
+            gain: -6,
+            low_note: 48,
+            high_note: 72,
+            low_velocity: 1,
+            high_velocity: 127,
+        };
+        assert_eq!(
+            inst.summary(),
+            "note: C3 (C2-C4), fine tune: -12 cents, gain: -6 dB, velocity: 1-127"
+        );
+        assert!(inst.note_range().contains(&64));
+        assert!(inst.validate().is_empty());
+
+        let smpl = Smpl {
+            midi_unity_note: 60,
+            ..Smpl::default()
+        };
+        assert!(inst.check_smpl(&smpl).is_empty());
+        let smpl = Smpl {
+            midi_unity_note: 62,
+            ..Smpl::default()
+        };
+        let warnings = inst.check_smpl(&smpl);
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, FourCC(*b"inst"));
+    }
+
+    #[test]
+    fn inst_validate() {
+        let inst = Inst {
+            unshifted_note: 200,
+            fine_tune: 60,
+            gain: 0,
+            low_note: 72,
+            high_note: 48,
+            low_velocity: 1,
+            high_velocity: 127,
+        };
+        let warnings = inst.validate();
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 3);
+    }
+
+    impl Arbitrary for Inst {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Inst {
+                unshifted_note: rng.next_u8(),
+                fine_tune: rng.next_u8() as i8,
+                gain: rng.next_u8() as i8,
+                low_note: rng.next_u8(),
+                high_note: rng.next_u8(),
+                low_velocity: rng.next_u8(),
+                high_velocity: rng.next_u8(),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        inst_roundtrip: Inst,
+    }
 }
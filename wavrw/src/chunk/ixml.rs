@@ -1,22 +1,82 @@
 //! `iXML` Production workflow file & project metadata.  [IXML2021](https://wavref.til.cafe/spec/ixml2021/)
 
-use core::fmt::Debug;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use core::str::FromStr;
 
 use binrw::{binrw, helpers};
+use tracing::warn;
 
-use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::Bext;
+use crate::time::Timecode;
+#[cfg(feature = "broadcast")]
+use crate::warning::Code;
+use crate::xml::Element;
+#[cfg(feature = "broadcast")]
+use crate::Warning;
+use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable, WaveFileError};
+
+pub use crate::time::Rational;
 
 // iXML, based on http://www.gallery.co.uk/ixml/
 
 /// `iXML` Production workflow file & project metadata.  [IXML2021](https://wavref.til.cafe/spec/ixml2021/)
+///
+/// The XML document is kept as `raw_bytes`, known sections are parsed into
+/// fields when reading. Sections are `None` when missing, empty or if the
+/// document isn't valid XML. Only `raw_bytes` is written.
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Ixml {
-    /// temporary....  TODO
+    /// The XML document as read.
     #[br(parse_with = helpers::until_eof)]
     pub raw_bytes: Vec<u8>,
+
+    #[br(temp, calc = Ixml::parse_root(&raw_bytes))]
+    #[bw(ignore)]
+    root: Option<Element>,
+
+    /// `HISTORY` section, where this file came from.
+    #[br(calc = root.as_ref().and_then(History::from_element))]
+    #[bw(ignore)]
+    pub history: Option<History>,
+
+    /// `FILE_SET` section, the group of files recorded together.
+    #[br(calc = root.as_ref().and_then(FileSet::from_element))]
+    #[bw(ignore)]
+    pub file_set: Option<FileSet>,
+
+    /// `LOUDNESS` section, measurements of the audio.
+    #[br(calc = root.as_ref().and_then(Loudness::from_element))]
+    #[bw(ignore)]
+    pub loudness: Option<Loudness>,
+
+    /// `BEXT` section, a copy of the `bext` chunk fields.
+    #[br(calc = root.as_ref().and_then(IxmlBext::from_element))]
+    #[bw(ignore)]
+    pub bext: Option<IxmlBext>,
+
+    /// `USER` section, free-form application specific data.
+    #[br(calc = root.as_ref().and_then(User::from_element))]
+    #[bw(ignore)]
+    pub user: Option<User>,
+
+    /// `SPEED` section, sample rates, timecode and timestamp.
+    #[br(calc = root.as_ref().and_then(Speed::from_element))]
+    #[bw(ignore)]
+    pub speed: Option<Speed>,
+
+    /// `TRACK_LIST` section, names and order of the channels.
+    #[br(calc = root.as_ref().and_then(TrackList::from_element))]
+    #[bw(ignore)]
+    pub track_list: Option<TrackList>,
 }
 
 impl KnownChunkID for Ixml {
@@ -25,7 +85,257 @@ impl KnownChunkID for Ixml {
 
 impl Ixml {
     fn new() -> Ixml {
-        Ixml { raw_bytes: vec![] }
+        Ixml {
+            raw_bytes: vec![],
+            history: None,
+            file_set: None,
+            loudness: None,
+            bext: None,
+            user: None,
+            speed: None,
+            track_list: None,
+        }
+    }
+
+    /// Parse `raw_bytes` as an iXML document, like reading an `iXML` chunk.
+    pub fn from_bytes(raw_bytes: Vec<u8>) -> Ixml {
+        let root = Ixml::parse_root(&raw_bytes);
+        let root = root.as_ref();
+        Ixml {
+            history: root.and_then(History::from_element),
+            file_set: root.and_then(FileSet::from_element),
+            loudness: root.and_then(Loudness::from_element),
+            bext: root.and_then(IxmlBext::from_element),
+            user: root.and_then(User::from_element),
+            speed: root.and_then(Speed::from_element),
+            track_list: root.and_then(TrackList::from_element),
+            raw_bytes,
+        }
+    }
+
+    /// A copy with `edit` applied to the parsed document, which is then
+    /// written back to `raw_bytes`. Comments and formatting are not kept.
+    fn edit(&self, edit: impl FnOnce(&mut Element)) -> Result<Ixml, WaveFileError> {
+        let mut root = Ixml::parse_root(&self.raw_bytes)
+            .ok_or_else(|| WaveFileError::parse(None, "iXML is not valid XML"))?;
+        edit(&mut root);
+        Ok(Ixml::from_bytes(root.to_xml().into_bytes()))
+    }
+
+    /// A copy describing a file with only `channels` of this one, in that
+    /// order. Channels are counted from 0.
+    ///
+    /// `TRACK_LIST` keeps the tracks with a matching `INTERLEAVE_INDEX`,
+    /// renumbered from 1, and updates `TRACK_COUNT`. Tracks keep their
+    /// `CHANNEL_INDEX`, which identifies the recorder track.
+    ///
+    /// ```
+    /// use wavrw::chunk::ixml::Ixml;
+    ///
+    /// let ixml = Ixml::from_bytes(
+    ///     b"<BWFXML><TRACK_LIST><TRACK_COUNT>2</TRACK_COUNT>\
+    ///     <TRACK><CHANNEL_INDEX>1</CHANNEL_INDEX><INTERLEAVE_INDEX>1</INTERLEAVE_INDEX><NAME>Boom</NAME></TRACK>\
+    ///     <TRACK><CHANNEL_INDEX>2</CHANNEL_INDEX><INTERLEAVE_INDEX>2</INTERLEAVE_INDEX><NAME>Lav</NAME></TRACK>\
+    ///     </TRACK_LIST></BWFXML>".to_vec(),
+    /// );
+    /// let lav = ixml.with_channels(&[1])?;
+    /// let tracks = lav.track_list.unwrap();
+    /// assert_eq!(tracks.track_count.as_deref(), Some("1"));
+    /// assert_eq!(tracks.tracks[0].name.as_deref(), Some("Lav"));
+    /// assert_eq!(tracks.tracks[0].interleave_index.as_deref(), Some("1"));
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn with_channels(&self, channels: &[usize]) -> Result<Ixml, WaveFileError> {
+        self.edit(|root| {
+            let Some(track_list) = root.child_mut("TRACK_LIST") else {
+                return;
+            };
+            let (tracks, others): (Vec<Element>, Vec<Element>) = track_list
+                .children
+                .drain(..)
+                .partition(|c| c.name == "TRACK");
+            let mut kept = Vec::new();
+            for (index, channel) in channels.iter().enumerate() {
+                let interleave_index = (channel + 1).to_string();
+                let track = tracks.iter().find(|t| {
+                    t.child_text("INTERLEAVE_INDEX").as_deref() == Some(&interleave_index)
+                });
+                if let Some(track) = track {
+                    let mut track = track.clone();
+                    track.set_child_text("INTERLEAVE_INDEX", &(index + 1).to_string());
+                    kept.push(track);
+                }
+            }
+            track_list.children = others;
+            track_list.set_child_text("TRACK_COUNT", &kept.len().to_string());
+            track_list.children.extend(kept);
+        })
+    }
+
+    /// A copy with `FILE_SET_INDEX` and `TOTAL_FILES` set, for one file of
+    /// a set. Does nothing without a `FILE_SET` section.
+    pub fn with_file_set_index(
+        &self,
+        index: &str,
+        total_files: usize,
+    ) -> Result<Ixml, WaveFileError> {
+        self.edit(|root| {
+            if let Some(file_set) = root.child_mut("FILE_SET") {
+                file_set.set_child_text("TOTAL_FILES", &total_files.to_string());
+                file_set.set_child_text("FILE_SET_INDEX", index);
+            }
+        })
+    }
+
+    /// A copy describing a file made by joining the channels of `files`,
+    /// given as each file's iXML, if any, and number of channels.
+    ///
+    /// `TRACK_LIST` gets the tracks of every file in order, with
+    /// `INTERLEAVE_INDEX` renumbered for the joined file. Channels without a
+    /// `TRACK` get one with only `CHANNEL_INDEX` and `INTERLEAVE_INDEX`.
+    ///
+    /// ```
+    /// use wavrw::chunk::ixml::Ixml;
+    ///
+    /// let boom = Ixml::from_bytes(
+    ///     b"<BWFXML><TRACK_LIST><TRACK_COUNT>1</TRACK_COUNT>\
+    ///     <TRACK><CHANNEL_INDEX>1</CHANNEL_INDEX><INTERLEAVE_INDEX>1</INTERLEAVE_INDEX><NAME>Boom</NAME></TRACK>\
+    ///     </TRACK_LIST></BWFXML>".to_vec(),
+    /// );
+    /// let poly = boom.with_merged_tracks(&[(Some(&boom), 1), (None, 1)])?;
+    /// let tracks = poly.track_list.unwrap();
+    /// assert_eq!(tracks.track_count.as_deref(), Some("2"));
+    /// assert_eq!(tracks.tracks[1].interleave_index.as_deref(), Some("2"));
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn with_merged_tracks(
+        &self,
+        files: &[(Option<&Ixml>, usize)],
+    ) -> Result<Ixml, WaveFileError> {
+        let mut merged = Vec::new();
+        for (ixml, channels) in files {
+            let root = ixml.and_then(|ixml| Ixml::parse_root(&ixml.raw_bytes));
+            let tracks: Vec<&Element> = root
+                .as_ref()
+                .and_then(|root| root.child("TRACK_LIST"))
+                .map(|t| t.children.iter().filter(|c| c.name == "TRACK").collect())
+                .unwrap_or_default();
+            for channel in 0..*channels {
+                let interleave_index = (channel + 1).to_string();
+                let track = tracks.iter().find(|t| {
+                    t.child_text("INTERLEAVE_INDEX").as_deref() == Some(&interleave_index)
+                });
+                let new_index = (merged.len() + 1).to_string();
+                let mut track = track.map_or_else(
+                    || {
+                        let mut track = Element {
+                            name: "TRACK".to_string(),
+                            ..Element::default()
+                        };
+                        track.set_child_text("CHANNEL_INDEX", &new_index);
+                        track
+                    },
+                    |track| (*track).clone(),
+                );
+                track.set_child_text("INTERLEAVE_INDEX", &new_index);
+                merged.push(track);
+            }
+        }
+        self.edit(|root| {
+            if root.child("TRACK_LIST").is_none() {
+                root.children.push(Element {
+                    name: "TRACK_LIST".to_string(),
+                    ..Element::default()
+                });
+            }
+            let track_list = root.child_mut("TRACK_LIST").expect("added above");
+            track_list.children.retain(|c| c.name != "TRACK");
+            track_list.set_child_text("TRACK_COUNT", &merged.len().to_string());
+            track_list.children.extend(merged);
+        })
+    }
+
+    /// Text of the element at `path` below the root, `None` if missing or
+    /// empty. Ex: `["ASWG", "catId"]`
+    pub fn text(&self, path: &[&str]) -> Option<String> {
+        let root = Ixml::parse_root(&self.raw_bytes)?;
+        let mut element = &root;
+        for name in path {
+            element = element.child(name)?;
+        }
+        Some(element.text.clone()).filter(|t| !t.is_empty())
+    }
+
+    /// A copy with the text of the element at `path` below the root set to
+    /// `text`, adding missing elements. Ex: `["USER", "ARCHIVE"]`
+    ///
+    /// ```
+    /// use wavrw::chunk::ixml::Ixml;
+    ///
+    /// let ixml = Ixml::from_bytes(b"<BWFXML/>".to_vec()).with_text(&["FILE_SET", "FAMILY_NAME"], "Scene 1")?;
+    /// assert_eq!(ixml.file_set.unwrap().family_name.as_deref(), Some("Scene 1"));
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn with_text(&self, path: &[&str], text: &str) -> Result<Ixml, WaveFileError> {
+        if path.is_empty() {
+            return Err(WaveFileError::parse(None, "empty iXML element path"));
+        }
+        self.edit(|root| {
+            let mut element = root;
+            for name in path {
+                if element.child(name).is_none() {
+                    element.children.push(Element {
+                        name: (*name).to_string(),
+                        ..Element::default()
+                    });
+                }
+                element = element.child_mut(name).expect("added above");
+            }
+            element.text = text.to_string();
+        })
+    }
+
+    /// Element path of the item `key`, see [`Summarizable::items()`], for
+    /// [`Ixml::with_text()`]. `None` for keys which are not one element,
+    /// like `raw_bytes`, `track_list.track.1` and `speed.start_timecode`.
+    ///
+    /// ```
+    /// use wavrw::chunk::ixml::Ixml;
+    ///
+    /// assert_eq!(Ixml::item_path("bext.description").unwrap(), ["BEXT", "BWF_DESCRIPTION"]);
+    /// assert_eq!(Ixml::item_path("user.CATID").unwrap(), ["USER", "CATID"]);
+    /// assert_eq!(Ixml::item_path("raw_bytes"), None);
+    /// ```
+    pub fn item_path(key: &str) -> Option<Vec<String>> {
+        let (section, field) = key.split_once('.')?;
+        if section == "user" {
+            return (!field.is_empty()).then(|| vec!["USER".to_string(), field.to_string()]);
+        }
+        if !crate::schema::IXML.keys.contains(&key)
+            || section == "track_list"
+            || key == "speed.start_timecode"
+        {
+            return None;
+        }
+        let prefix = if section == "bext" { "BWF_" } else { "" };
+        Some(vec![
+            section.to_ascii_uppercase(),
+            format!("{prefix}{}", field.to_ascii_uppercase()),
+        ])
+    }
+
+    /// Parse the document, the root element is usually `BWFXML`.
+    fn parse_root(raw_bytes: &[u8]) -> Option<Element> {
+        // documents are often padded with NULL bytes or spaces
+        let text = String::from_utf8_lossy(raw_bytes);
+        let text = text.trim_end_matches(|c: char| c == '\0' || c.is_whitespace());
+        match Element::parse(text) {
+            Ok(root) => Some(root),
+            Err(err) => {
+                warn!("iXML: {err}");
+                None
+            }
+        }
     }
 }
 
@@ -38,9 +348,692 @@ impl Default for Ixml {
 /// `iXML` Production workflow file & project metadata.  [IXML2021](https://wavref.til.cafe/spec/ixml2021/)
 pub type IxmlChunk = KnownChunk<Ixml>;
 
+/// Read `fields` from the child elements of `section`, `None` if all are missing.
+fn section_fields<const N: usize>(
+    root: &Element,
+    section: &str,
+    fields: [&str; N],
+) -> Option<[Option<String>; N]> {
+    let section = root.child(section)?;
+    let values = fields.map(|f| section.child_text(f));
+    values.iter().any(Option::is_some).then_some(values)
+}
+
+/// Push `(prefix.name, value)` for each value which is present.
+fn push_items(items: &mut Vec<(String, String)>, prefix: &str, fields: &[(&str, &Option<String>)]) {
+    for (name, value) in fields {
+        if let Some(value) = value {
+            items.push((format!("{prefix}.{name}"), value.clone()));
+        }
+    }
+}
+
+/// Parse the text of field `name`, for the typed accessors of each section.
+fn parse_field<T>(value: Option<&String>, name: &str) -> Result<T, WaveFileError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(value) = value else {
+        return Err(WaveFileError::parse(
+            None,
+            format!("iXML {name} is missing"),
+        ));
+    };
+    value
+        .trim()
+        .parse()
+        .map_err(|err| WaveFileError::parse(None, format!("iXML {name} {value:?}: {err}")))
+}
+
+/// iXML `HISTORY` section: where this file came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct History {
+    /// `ORIGINAL_FILENAME`: name of the file as first recorded.
+    pub original_filename: Option<String>,
+    /// `PARENT_FILENAME`: name of the file this one was derived from.
+    pub parent_filename: Option<String>,
+    /// `PARENT_UID`: `FILE_UID` of the file this one was derived from.
+    pub parent_uid: Option<String>,
+}
+
+impl History {
+    fn from_element(root: &Element) -> Option<Self> {
+        let [original_filename, parent_filename, parent_uid] = section_fields(
+            root,
+            "HISTORY",
+            ["ORIGINAL_FILENAME", "PARENT_FILENAME", "PARENT_UID"],
+        )?;
+        Some(History {
+            original_filename,
+            parent_filename,
+            parent_uid,
+        })
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(
+            items,
+            "history",
+            &[
+                ("original_filename", &self.original_filename),
+                ("parent_filename", &self.parent_filename),
+                ("parent_uid", &self.parent_uid),
+            ],
+        );
+    }
+}
+
+/// iXML `FILE_SET` section: groups the files of a multi file recording.
+///
+/// Recorders writing one mono file per track give every file of a take
+/// the same `family_uid`, so the poly group can be reassembled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FileSet {
+    /// `TOTAL_FILES`: number of files in the set.
+    pub total_files: Option<String>,
+    /// `FAMILY_UID`: unique id shared by all files in the set.
+    pub family_uid: Option<String>,
+    /// `FAMILY_NAME`: name shared by all files in the set.
+    pub family_name: Option<String>,
+    /// `FILE_SET_INDEX`: position of this file in the set. Ex: `A`, `B` or `1`.
+    pub file_set_index: Option<String>,
+}
+
+impl FileSet {
+    /// `total_files` as a number.
+    pub fn parsed_total_files(&self) -> Result<u32, WaveFileError> {
+        parse_field(self.total_files.as_ref(), "TOTAL_FILES")
+    }
+
+    fn from_element(root: &Element) -> Option<Self> {
+        let [total_files, family_uid, family_name, file_set_index] = section_fields(
+            root,
+            "FILE_SET",
+            ["TOTAL_FILES", "FAMILY_UID", "FAMILY_NAME", "FILE_SET_INDEX"],
+        )?;
+        Some(FileSet {
+            total_files,
+            family_uid,
+            family_name,
+            file_set_index,
+        })
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(
+            items,
+            "file_set",
+            &[
+                ("total_files", &self.total_files),
+                ("family_uid", &self.family_uid),
+                ("family_name", &self.family_name),
+                ("file_set_index", &self.file_set_index),
+            ],
+        );
+    }
+}
+
+/// iXML `TRACK_LIST` section: the channels of the file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TrackList {
+    /// `TRACK_COUNT`: number of tracks.
+    pub track_count: Option<String>,
+    /// `TRACK` elements in document order.
+    pub tracks: Vec<Track>,
+}
+
+/// One `TRACK` of an iXML [`TrackList`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Track {
+    /// `CHANNEL_INDEX`: track number on the recorder, from 1.
+    pub channel_index: Option<String>,
+    /// `INTERLEAVE_INDEX`: channel of this file holding the track, from 1.
+    pub interleave_index: Option<String>,
+    /// `NAME`: track name. Ex: `Boom`
+    pub name: Option<String>,
+    /// `FUNCTION`: role of the track. Ex: `M-MID_SIDE`
+    pub function: Option<String>,
+}
+
+impl TrackList {
+    fn from_element(root: &Element) -> Option<Self> {
+        let section = root.child("TRACK_LIST")?;
+        let tracks: Vec<Track> = section
+            .children
+            .iter()
+            .filter(|c| c.name == "TRACK")
+            .map(|track| Track {
+                channel_index: track.child_text("CHANNEL_INDEX"),
+                interleave_index: track.child_text("INTERLEAVE_INDEX"),
+                name: track.child_text("NAME"),
+                function: track.child_text("FUNCTION"),
+            })
+            .collect();
+        let track_count = section.child_text("TRACK_COUNT");
+        (track_count.is_some() || !tracks.is_empty()).then_some(TrackList {
+            track_count,
+            tracks,
+        })
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(items, "track_list", &[("track_count", &self.track_count)]);
+        for (index, track) in self.tracks.iter().enumerate() {
+            let fields = [
+                ("channel_index", &track.channel_index),
+                ("interleave_index", &track.interleave_index),
+                ("name", &track.name),
+                ("function", &track.function),
+            ];
+            let value = fields
+                .iter()
+                .filter_map(|(name, value)| value.as_ref().map(|v| format!("{name}: {v}")))
+                .collect::<Vec<_>>()
+                .join(", ");
+            items.push((format!("track_list.track.{}", index + 1), value));
+        }
+    }
+}
+
+/// iXML `LOUDNESS` section: loudness measurements as in EBU R 128.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Loudness {
+    /// `LOUDNESS_VALUE`: integrated loudness in LUFS.
+    pub loudness_value: Option<String>,
+    /// `LOUDNESS_RANGE`: loudness range in LU.
+    pub loudness_range: Option<String>,
+    /// `MAX_TRUE_PEAK_LEVEL`: maximum true peak in dBTP.
+    pub max_true_peak_level: Option<String>,
+    /// `MAX_MOMENTARY_LOUDNESS`: maximum momentary loudness in LUFS.
+    pub max_momentary_loudness: Option<String>,
+    /// `MAX_SHORT_TERM_LOUDNESS`: maximum short term loudness in LUFS.
+    pub max_short_term_loudness: Option<String>,
+}
+
+impl Loudness {
+    /// `loudness_value` in LUFS.
+    pub fn parsed_loudness_value(&self) -> Result<f64, WaveFileError> {
+        parse_field(self.loudness_value.as_ref(), "LOUDNESS_VALUE")
+    }
+
+    /// `loudness_range` in LU.
+    pub fn parsed_loudness_range(&self) -> Result<f64, WaveFileError> {
+        parse_field(self.loudness_range.as_ref(), "LOUDNESS_RANGE")
+    }
+
+    /// `max_true_peak_level` in dBTP.
+    pub fn parsed_max_true_peak_level(&self) -> Result<f64, WaveFileError> {
+        parse_field(self.max_true_peak_level.as_ref(), "MAX_TRUE_PEAK_LEVEL")
+    }
+
+    /// `max_momentary_loudness` in LUFS.
+    pub fn parsed_max_momentary_loudness(&self) -> Result<f64, WaveFileError> {
+        parse_field(
+            self.max_momentary_loudness.as_ref(),
+            "MAX_MOMENTARY_LOUDNESS",
+        )
+    }
+
+    /// `max_short_term_loudness` in LUFS.
+    pub fn parsed_max_short_term_loudness(&self) -> Result<f64, WaveFileError> {
+        parse_field(
+            self.max_short_term_loudness.as_ref(),
+            "MAX_SHORT_TERM_LOUDNESS",
+        )
+    }
+
+    fn from_element(root: &Element) -> Option<Self> {
+        let [loudness_value, loudness_range, max_true_peak_level, max_momentary_loudness, max_short_term_loudness] =
+            section_fields(
+                root,
+                "LOUDNESS",
+                [
+                    "LOUDNESS_VALUE",
+                    "LOUDNESS_RANGE",
+                    "MAX_TRUE_PEAK_LEVEL",
+                    "MAX_MOMENTARY_LOUDNESS",
+                    "MAX_SHORT_TERM_LOUDNESS",
+                ],
+            )?;
+        Some(Loudness {
+            loudness_value,
+            loudness_range,
+            max_true_peak_level,
+            max_momentary_loudness,
+            max_short_term_loudness,
+        })
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(
+            items,
+            "loudness",
+            &[
+                ("loudness_value", &self.loudness_value),
+                ("loudness_range", &self.loudness_range),
+                ("max_true_peak_level", &self.max_true_peak_level),
+                ("max_momentary_loudness", &self.max_momentary_loudness),
+                ("max_short_term_loudness", &self.max_short_term_loudness),
+            ],
+        );
+    }
+}
+
+/// iXML `BEXT` section: a copy of the [`Bext`] chunk fields.
+///
+/// Recorders write both, but editors sometimes only update one of them, see
+/// [`IxmlBext::compare()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IxmlBext {
+    /// `BWF_DESCRIPTION`
+    pub description: Option<String>,
+    /// `BWF_ORIGINATOR`
+    pub originator: Option<String>,
+    /// `BWF_ORIGINATOR_REFERENCE`
+    pub originator_reference: Option<String>,
+    /// `BWF_ORIGINATION_DATE`
+    pub origination_date: Option<String>,
+    /// `BWF_ORIGINATION_TIME`
+    pub origination_time: Option<String>,
+    /// `BWF_TIME_REFERENCE_LOW`: low 32 bits of the time reference.
+    pub time_reference_low: Option<String>,
+    /// `BWF_TIME_REFERENCE_HIGH`: high 32 bits of the time reference.
+    pub time_reference_high: Option<String>,
+    /// `BWF_VERSION`
+    pub version: Option<String>,
+    /// `BWF_UMID`
+    pub umid: Option<String>,
+    /// `BWF_CODING_HISTORY`
+    pub coding_history: Option<String>,
+}
+
+impl IxmlBext {
+    fn from_element(root: &Element) -> Option<Self> {
+        let [description, originator, originator_reference, origination_date, origination_time, time_reference_low, time_reference_high, version, umid, coding_history] =
+            section_fields(
+                root,
+                "BEXT",
+                [
+                    "BWF_DESCRIPTION",
+                    "BWF_ORIGINATOR",
+                    "BWF_ORIGINATOR_REFERENCE",
+                    "BWF_ORIGINATION_DATE",
+                    "BWF_ORIGINATION_TIME",
+                    "BWF_TIME_REFERENCE_LOW",
+                    "BWF_TIME_REFERENCE_HIGH",
+                    "BWF_VERSION",
+                    "BWF_UMID",
+                    "BWF_CODING_HISTORY",
+                ],
+            )?;
+        Some(IxmlBext {
+            description,
+            originator,
+            originator_reference,
+            origination_date,
+            origination_time,
+            time_reference_low,
+            time_reference_high,
+            version,
+            umid,
+            coding_history,
+        })
+    }
+
+    /// Time reference in samples since midnight, combined from the low and
+    /// high halves. `None` if either is missing or not a number.
+    pub fn time_reference(&self) -> Option<u64> {
+        let low: u32 = self.time_reference_low.as_ref()?.parse().ok()?;
+        let high: u32 = self.time_reference_high.as_ref()?.parse().ok()?;
+        Some(u64::from(high) << 32 | u64::from(low))
+    }
+
+    /// Compare with the `bext` chunk from the same file.
+    ///
+    /// Returns a [`Warning`] for each of `description`, `originator`,
+    /// `originator_reference`, `origination_date`, `origination_time` and
+    /// `time_reference` which differs. Fields missing from iXML are not
+    /// compared. Whitespace at either end of the text is ignored.
+    #[cfg(feature = "broadcast")]
+    pub fn compare(&self, bext: &Bext) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        for (name, ixml, bext) in [
+            (
+                "description",
+                &self.description,
+                bext.description.to_string(),
+            ),
+            ("originator", &self.originator, bext.originator.to_string()),
+            (
+                "originator_reference",
+                &self.originator_reference,
+                bext.originator_reference.to_string(),
+            ),
+            (
+                "origination_date",
+                &self.origination_date,
+                bext.origination_date.to_string(),
+            ),
+            (
+                "origination_time",
+                &self.origination_time,
+                bext.origination_time.to_string(),
+            ),
+        ] {
+            if let Some(ixml) = ixml {
+                if ixml.trim() != bext.trim() {
+                    warnings.push(
+                        Warning::new(
+                            Ixml::ID,
+                            None,
+                            format!("BEXT {name} {ixml:?} does not match bext {bext:?}"),
+                        )
+                        .with_code(Code::MetadataMismatch),
+                    );
+                }
+            }
+        }
+        if self.time_reference_low.is_some() || self.time_reference_high.is_some() {
+            match self.time_reference() {
+                Some(time_reference) if time_reference == bext.time_reference => (),
+                Some(time_reference) => warnings.push(
+                    Warning::new(
+                        Ixml::ID,
+                        None,
+                        format!(
+                            "BEXT time_reference {time_reference} does not match bext {}",
+                            bext.time_reference
+                        ),
+                    )
+                    .with_code(Code::MetadataMismatch),
+                ),
+                None => warnings.push(
+                    Warning::new(
+                        Ixml::ID,
+                        None,
+                        "BEXT time_reference is incomplete or not a number",
+                    )
+                    .with_code(Code::InvalidValue),
+                ),
+            }
+        }
+        warnings
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(
+            items,
+            "bext",
+            &[
+                ("description", &self.description),
+                ("originator", &self.originator),
+                ("originator_reference", &self.originator_reference),
+                ("origination_date", &self.origination_date),
+                ("origination_time", &self.origination_time),
+                ("time_reference_low", &self.time_reference_low),
+                ("time_reference_high", &self.time_reference_high),
+                ("version", &self.version),
+                ("umid", &self.umid),
+                ("coding_history", &self.coding_history),
+            ],
+        );
+    }
+}
+
+/// iXML `SPEED` section: sample rates, timecode rate and the timestamp of
+/// the first sample.
+///
+/// Fields are kept as written, the `parsed_*` methods convert them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Speed {
+    /// `NOTE`: free text about the speed settings.
+    pub note: Option<String>,
+    /// `MASTER_SPEED`: intended playback speed in frames per second. Ex: `24/1`
+    pub master_speed: Option<String>,
+    /// `CURRENT_SPEED`: speed of the recording in frames per second. Ex: `24000/1001`
+    pub current_speed: Option<String>,
+    /// `TIMECODE_RATE`: timecode frames per second. Ex: `30000/1001`
+    pub timecode_rate: Option<String>,
+    /// `TIMECODE_FLAG`: `DF` for drop frame or `NDF` for non drop frame.
+    pub timecode_flag: Option<String>,
+    /// `FILE_SAMPLE_RATE`: sample rate of the file in Hz.
+    pub file_sample_rate: Option<String>,
+    /// `AUDIO_BIT_DEPTH`: bits per sample.
+    pub audio_bit_depth: Option<String>,
+    /// `DIGITIZER_SAMPLE_RATE`: sample rate when recorded, in Hz.
+    pub digitizer_sample_rate: Option<String>,
+    /// `TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI`: high 32 bits of the timestamp.
+    pub timestamp_samples_since_midnight_hi: Option<String>,
+    /// `TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO`: low 32 bits of the timestamp.
+    pub timestamp_samples_since_midnight_lo: Option<String>,
+    /// `TIMESTAMP_SAMPLE_RATE`: sample rate the timestamp is counted in, in Hz.
+    pub timestamp_sample_rate: Option<String>,
+}
+
+impl Speed {
+    fn from_element(root: &Element) -> Option<Self> {
+        let [note, master_speed, current_speed, timecode_rate, timecode_flag, file_sample_rate, audio_bit_depth, digitizer_sample_rate, timestamp_samples_since_midnight_hi, timestamp_samples_since_midnight_lo, timestamp_sample_rate] =
+            section_fields(
+                root,
+                "SPEED",
+                [
+                    "NOTE",
+                    "MASTER_SPEED",
+                    "CURRENT_SPEED",
+                    "TIMECODE_RATE",
+                    "TIMECODE_FLAG",
+                    "FILE_SAMPLE_RATE",
+                    "AUDIO_BIT_DEPTH",
+                    "DIGITIZER_SAMPLE_RATE",
+                    "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI",
+                    "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO",
+                    "TIMESTAMP_SAMPLE_RATE",
+                ],
+            )?;
+        Some(Speed {
+            note,
+            master_speed,
+            current_speed,
+            timecode_rate,
+            timecode_flag,
+            file_sample_rate,
+            audio_bit_depth,
+            digitizer_sample_rate,
+            timestamp_samples_since_midnight_hi,
+            timestamp_samples_since_midnight_lo,
+            timestamp_sample_rate,
+        })
+    }
+
+    /// `master_speed` as a [`Rational`].
+    pub fn parsed_master_speed(&self) -> Result<Rational, WaveFileError> {
+        parse_field(self.master_speed.as_ref(), "MASTER_SPEED")
+    }
+
+    /// `current_speed` as a [`Rational`].
+    pub fn parsed_current_speed(&self) -> Result<Rational, WaveFileError> {
+        parse_field(self.current_speed.as_ref(), "CURRENT_SPEED")
+    }
+
+    /// `timecode_rate` as a [`Rational`].
+    pub fn parsed_timecode_rate(&self) -> Result<Rational, WaveFileError> {
+        parse_field(self.timecode_rate.as_ref(), "TIMECODE_RATE")
+    }
+
+    /// `timecode_flag` as a drop frame flag, see [`Timecode::parse_flag()`].
+    /// Non drop frame if missing.
+    pub fn parsed_drop_frame(&self) -> Result<bool, WaveFileError> {
+        self.timecode_flag.as_ref().map_or(Ok(false), |flag| {
+            Timecode::parse_flag(flag)
+                .map_err(|err| WaveFileError::parse(None, format!("iXML TIMECODE_FLAG: {err}")))
+        })
+    }
+
+    /// `file_sample_rate` in Hz.
+    pub fn parsed_file_sample_rate(&self) -> Result<u32, WaveFileError> {
+        parse_field(self.file_sample_rate.as_ref(), "FILE_SAMPLE_RATE")
+    }
+
+    /// `audio_bit_depth` in bits.
+    pub fn parsed_audio_bit_depth(&self) -> Result<u16, WaveFileError> {
+        parse_field(self.audio_bit_depth.as_ref(), "AUDIO_BIT_DEPTH")
+    }
+
+    /// `digitizer_sample_rate` in Hz.
+    pub fn parsed_digitizer_sample_rate(&self) -> Result<u32, WaveFileError> {
+        parse_field(self.digitizer_sample_rate.as_ref(), "DIGITIZER_SAMPLE_RATE")
+    }
+
+    /// `timestamp_sample_rate` in Hz.
+    pub fn parsed_timestamp_sample_rate(&self) -> Result<u32, WaveFileError> {
+        parse_field(self.timestamp_sample_rate.as_ref(), "TIMESTAMP_SAMPLE_RATE")
+    }
+
+    /// Timestamp of the first sample in samples since midnight, combined
+    /// from the high and low halves.
+    pub fn parsed_timestamp_samples_since_midnight(&self) -> Result<u64, WaveFileError> {
+        let high: u32 = parse_field(
+            self.timestamp_samples_since_midnight_hi.as_ref(),
+            "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI",
+        )?;
+        let low: u32 = parse_field(
+            self.timestamp_samples_since_midnight_lo.as_ref(),
+            "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO",
+        )?;
+        Ok(u64::from(high) << 32 | u64::from(low))
+    }
+
+    /// [`Timecode`] of the first sample, from the timestamp, timecode rate
+    /// and timecode flag.
+    ///
+    /// The timestamp is counted at `timestamp_sample_rate`, or
+    /// `file_sample_rate` if missing.
+    pub fn start_timecode(&self) -> Result<Timecode, WaveFileError> {
+        let samples = self.parsed_timestamp_samples_since_midnight()?;
+        let sample_rate = self
+            .parsed_timestamp_sample_rate()
+            .or_else(|_| self.parsed_file_sample_rate())?;
+        let rate = self.parsed_timecode_rate()?;
+        Ok(Timecode::from_samples(
+            samples,
+            sample_rate,
+            rate,
+            self.parsed_drop_frame()?,
+        ))
+    }
+
+    fn push_items(&self, items: &mut Vec<(String, String)>) {
+        push_items(
+            items,
+            "speed",
+            &[
+                ("note", &self.note),
+                ("master_speed", &self.master_speed),
+                ("current_speed", &self.current_speed),
+                ("timecode_rate", &self.timecode_rate),
+                ("timecode_flag", &self.timecode_flag),
+                ("file_sample_rate", &self.file_sample_rate),
+                ("audio_bit_depth", &self.audio_bit_depth),
+                ("digitizer_sample_rate", &self.digitizer_sample_rate),
+                (
+                    "timestamp_samples_since_midnight_hi",
+                    &self.timestamp_samples_since_midnight_hi,
+                ),
+                (
+                    "timestamp_samples_since_midnight_lo",
+                    &self.timestamp_samples_since_midnight_lo,
+                ),
+                ("timestamp_sample_rate", &self.timestamp_sample_rate),
+            ],
+        );
+        if let Ok(timecode) = self.start_timecode() {
+            items.push((
+                "speed.start_timecode".to_string(),
+                format!("{timecode} @ {}", timecode.rate),
+            ));
+        }
+    }
+}
+
+/// iXML `USER` section: free-form data, where most sound library metadata
+/// ends up in practice.
+///
+/// The specification allows any text. Applications write either
+/// `KEY: value` lines or nested tags like `<CATID>` and `<PUBLISHER>`
+/// (Soundminer), both are collected into `fields`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct User {
+    /// Text directly inside `USER`, not including nested tags.
+    pub text: String,
+
+    /// Nested tags and `KEY: value` lines as (key, value) pairs, in document
+    /// order. Tags nested more than one level deep use `/` separated keys,
+    /// ex: `PARENT/CHILD`.
+    pub fields: Vec<(String, String)>,
+}
+
+impl User {
+    fn from_element(root: &Element) -> Option<Self> {
+        let section = root.child("USER")?;
+        let mut fields = Vec::new();
+        for child in &section.children {
+            push_element_fields(&mut fields, "", child);
+        }
+        for line in section.text.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                let key = key.trim();
+                if !key.is_empty() && !key.contains(char::is_whitespace) {
+                    fields.push((key.to_string(), value.trim().to_string()));
+                }
+            }
+        }
+        if section.text.is_empty() && fields.is_empty() {
+            return None;
+        }
+        Some(User {
+            text: section.text.clone(),
+            fields,
+        })
+    }
+
+    /// Value of the first field named `key`. Ex: `CATID`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Push the text of `element` and its descendants as (path, text) pairs.
+fn push_element_fields(fields: &mut Vec<(String, String)>, prefix: &str, element: &Element) {
+    let key = format!("{prefix}{}", element.name);
+    if !element.text.is_empty() || element.children.is_empty() {
+        fields.push((key.clone(), element.text.clone()));
+    }
+    for child in &element.children {
+        push_element_fields(fields, &format!("{key}/"), child);
+    }
+}
+
 impl Summarizable for Ixml {
     fn summary(&self) -> String {
-        format!("{} bytes of data", self.raw_bytes.len())
+        let mut summary = format!("{} bytes of data", self.raw_bytes.len());
+        if let Some(name) = self.file_set.as_ref().and_then(|f| f.family_name.as_ref()) {
+            summary.push_str(&format!(", family: {name}"));
+        }
+        if let Some(Ok(timecode)) = self.speed.as_ref().map(Speed::start_timecode) {
+            summary.push_str(&format!(", start: {timecode}"));
+        }
+        if let Some(value) = self
+            .loudness
+            .as_ref()
+            .and_then(|l| l.loudness_value.as_ref())
+        {
+            summary.push_str(&format!(", loudness: {value}"));
+        }
+        summary
     }
 
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
@@ -52,78 +1045,310 @@ impl Summarizable for Ixml {
     }
 }
 
-impl<'a> IntoIterator for &'a Ixml {
+impl IntoIterator for &Ixml {
     type Item = (String, String);
-    type IntoIter = IxmlDataIterator<'a>;
+    type IntoIter = IxmlDataIterator;
 
     fn into_iter(self) -> Self::IntoIter {
-        IxmlDataIterator {
-            data: self,
-            index: 0,
+        let mut items = vec![(
+            "raw_bytes".to_string(),
+            format!("{} bytes of data", self.raw_bytes.len()),
+        )];
+        if let Some(history) = &self.history {
+            history.push_items(&mut items);
+        }
+        if let Some(file_set) = &self.file_set {
+            file_set.push_items(&mut items);
         }
+        if let Some(loudness) = &self.loudness {
+            loudness.push_items(&mut items);
+        }
+        if let Some(speed) = &self.speed {
+            speed.push_items(&mut items);
+        }
+        if let Some(track_list) = &self.track_list {
+            track_list.push_items(&mut items);
+        }
+        if let Some(bext) = &self.bext {
+            bext.push_items(&mut items);
+        }
+        if let Some(user) = &self.user {
+            for (key, value) in &user.fields {
+                items.push((format!("user.{key}"), value.clone()));
+            }
+        }
+        IxmlDataIterator { items, index: 0 }
     }
 }
 
 /// Iterate over fields as tuple of Strings (name, value).
 #[derive(Debug)]
-pub struct IxmlDataIterator<'a> {
-    data: &'a Ixml,
+pub struct IxmlDataIterator {
+    items: Vec<(String, String)>,
     index: usize,
 }
 
-impl<'a> Iterator for IxmlDataIterator<'a> {
+impl Iterator for IxmlDataIterator {
     type Item = (String, String);
     fn next(&mut self) -> Option<(String, String)> {
+        let item = self.items.get(self.index).cloned();
         self.index += 1;
-        match self.index {
-            1 => Some((
-                "raw_bytes".to_string(),
-                format!("{} bytes of data", self.data.raw_bytes.len()),
-            )),
-            _ => None,
-        }
+        item
     }
 }
 
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
     use binrw::BinRead;
-    use core::str::FromStr;
-    use hex::decode;
 
     use super::*;
     use crate::testing::hex_to_cursor;
+    use crate::WaveFile;
 
     #[test]
     fn parse_ixml() {
-        // example bext chunk data from BWF MetaEdit
+        // minimal iXML document
         let mut buff = hex_to_cursor(
-            r#"62657874 67020000 44657363 72697074 696F6E00 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 4F726967 696E6174 6F720000 
-            00000000 00000000 00000000 00000000 00000000 4F726967 696E6174 
-            6F725265 66657265 6E636500 00000000 00000000 00000000 32303036 
-            2F30312F 30323033 3A30343A 30353930 00000000 00000200 060A2B34 
-            01010101 01010210 13000000 00FF122A 69370580 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 6400C800 2C019001 F4010000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 00000000 
-            00000000 00000000 00000000 00000000 00000000 00000000 0000436F 
-            64696E67 48697374 6F7279"#,
+            r#"69584D4C 8E000000 3C3F786D 6C207665 7273696F 6E3D2231 2E302220 656E636F
+            64696E67 3D225554 462D3822 3F3E3C42 5746584D 4C3E3C49 584D4C5F 56455253
+            494F4E3E 322E3130 3C2F4958 4D4C5F56 45525349 4F4E3E3C 50524F4A 4543543E
+            77617672 773C2F50 524F4A45 43543E3C 5343454E 453E313C 2F534345 4E453E3C
+            54414B45 3E323C2F 54414B45 3E3C2F42 5746584D 4C3E"#,
         );
         let ixml = IxmlChunk::read(&mut buff).expect("error parsing ixmlchunk");
         print!("{:?}", ixml);
+        assert_eq!(ixml.size, 142);
+        assert_eq!(ixml.data.raw_bytes.len(), 142);
+    }
+
+    #[test]
+    fn ixml_sections() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<BWFXML>
+  <IXML_VERSION>2.10</IXML_VERSION>
+  <HISTORY>
+    <ORIGINAL_FILENAME>T001.WAV</ORIGINAL_FILENAME>
+    <PARENT_UID>ABC123</PARENT_UID>
+  </HISTORY>
+  <FILE_SET>
+    <TOTAL_FILES>2</TOTAL_FILES>
+    <FAMILY_UID>F00D</FAMILY_UID>
+    <FAMILY_NAME>Scene 1 Take 2</FAMILY_NAME>
+    <FILE_SET_INDEX>A</FILE_SET_INDEX>
+  </FILE_SET>
+  <LOUDNESS>
+    <LOUDNESS_VALUE>-23.0</LOUDNESS_VALUE>
+    <MAX_TRUE_PEAK_LEVEL>-1.5</MAX_TRUE_PEAK_LEVEL>
+  </LOUDNESS>
+</BWFXML>
+"#;
+        let mut bytes = b"iXML".to_vec();
+        let mut data = xml.as_bytes().to_vec();
+        data.extend([0, 0, 0]);
+        bytes.extend(u32::try_from(data.len()).unwrap().to_le_bytes());
+        bytes.extend(&data);
+        let ixml = IxmlChunk::read(&mut std::io::Cursor::new(bytes)).unwrap();
+        dbg!(&ixml);
+        let history = ixml.data.history.as_ref().unwrap();
+        assert_eq!(history.original_filename.as_deref(), Some("T001.WAV"));
+        assert_eq!(history.parent_filename, None);
+        let file_set = ixml.data.file_set.as_ref().unwrap();
+        assert_eq!(file_set.total_files.as_deref(), Some("2"));
+        assert_eq!(file_set.file_set_index.as_deref(), Some("A"));
+        let loudness = ixml.data.loudness.as_ref().unwrap();
+        assert_eq!(loudness.loudness_value.as_deref(), Some("-23.0"));
+        assert_eq!(
+            ixml.data.summary(),
+            format!(
+                "{} bytes of data, family: Scene 1 Take 2, loudness: -23.0",
+                data.len()
+            )
+        );
+        let items: Vec<_> = ixml.data.items().collect();
+        assert!(items.contains(&("file_set.family_uid".to_string(), "F00D".to_string())));
+        assert_eq!(items.len(), 1 + 2 + 4 + 2);
+    }
+
+    #[test]
+    fn ixml_track_list() {
+        let ixml = Ixml::from_bytes(
+            br#"<BWFXML>
+  <FILE_SET><TOTAL_FILES>1</TOTAL_FILES><FAMILY_UID>F00D</FAMILY_UID></FILE_SET>
+  <TRACK_LIST>
+    <TRACK_COUNT>3</TRACK_COUNT>
+    <TRACK><CHANNEL_INDEX>1</CHANNEL_INDEX><INTERLEAVE_INDEX>1</INTERLEAVE_INDEX><NAME>Mix</NAME></TRACK>
+    <TRACK><CHANNEL_INDEX>3</CHANNEL_INDEX><INTERLEAVE_INDEX>2</INTERLEAVE_INDEX><NAME>Boom</NAME></TRACK>
+    <TRACK><CHANNEL_INDEX>4</CHANNEL_INDEX><INTERLEAVE_INDEX>3</INTERLEAVE_INDEX><NAME>Lav &amp; 1</NAME><FUNCTION>LAV</FUNCTION></TRACK>
+  </TRACK_LIST>
+</BWFXML>"#
+                .to_vec(),
+        );
+        dbg!(&ixml);
+        let track_list = ixml.track_list.as_ref().unwrap();
+        assert_eq!(track_list.track_count.as_deref(), Some("3"));
+        assert_eq!(track_list.tracks.len(), 3);
+        let items: Vec<_> = ixml.items().collect();
+        assert!(items.contains(&(
+            "track_list.track.3".to_string(),
+            "channel_index: 4, interleave_index: 3, name: Lav & 1, function: LAV".to_string()
+        )));
+
+        let split = ixml
+            .with_channels(&[2, 1])
+            .unwrap()
+            .with_file_set_index("2", 3)
+            .unwrap();
+        dbg!(String::from_utf8_lossy(&split.raw_bytes));
+        let track_list = split.track_list.unwrap();
+        assert_eq!(track_list.track_count.as_deref(), Some("2"));
+        let names: Vec<_> = track_list.tracks.iter().map(|t| t.name.clone()).collect();
+        assert_eq!(
+            names,
+            [Some("Lav & 1".to_string()), Some("Boom".to_string())]
+        );
+        assert_eq!(track_list.tracks[0].channel_index.as_deref(), Some("4"));
+        assert_eq!(track_list.tracks[0].interleave_index.as_deref(), Some("1"));
+        assert_eq!(track_list.tracks[1].interleave_index.as_deref(), Some("2"));
+        let file_set = split.file_set.unwrap();
+        assert_eq!(file_set.total_files.as_deref(), Some("3"));
+        assert_eq!(file_set.file_set_index.as_deref(), Some("2"));
+        assert_eq!(file_set.family_uid.as_deref(), Some("F00D"));
+
+        assert!(Ixml::from_bytes(b"<BWFXML>".to_vec())
+            .with_channels(&[0])
+            .is_err());
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn ixml_bext_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let metadata = wave.metadata();
+        let ixml_bext = metadata.ixml().unwrap().bext.as_ref().unwrap();
+        dbg!(&ixml_bext);
+        assert!(ixml_bext.description.is_some());
+        let bext = metadata.bext().unwrap();
+        assert_eq!(ixml_bext.time_reference(), Some(bext.time_reference));
+        assert!(ixml_bext.compare(bext).is_empty());
+
+        let mut changed = bext.clone();
+        changed.originator = "Someone else".parse().unwrap();
+        changed.time_reference += 1;
+        let warnings = ixml_bext.compare(&changed);
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].id, FourCC(*b"iXML"));
+    }
+
+    #[test]
+    fn ixml_user_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let metadata = wave.metadata();
+        let user = metadata.ixml().unwrap().user.as_ref().unwrap();
+        dbg!(&user);
+        assert_eq!(user.get("CATID"), Some("CatID"));
+        assert_eq!(user.get("EMBEDDER"), Some("Soundminer"));
+        assert_eq!(user.get("MISSING"), None);
+    }
+
+    #[test]
+    fn ixml_user_lines() {
+        let root = Element::parse(
+            "<BWFXML><USER>free text\nCATID: DOORWood\nnot a field\n<GROUP><A>1</A></GROUP>DESIGNER : Someone</USER></BWFXML>",
+        )
+        .unwrap();
+        let user = User::from_element(&root).unwrap();
+        dbg!(&user);
+        assert_eq!(
+            user.fields,
+            vec![
+                ("GROUP/A".to_string(), "1".to_string()),
+                ("CATID".to_string(), "DOORWood".to_string()),
+                ("DESIGNER".to_string(), "Someone".to_string()),
+            ]
+        );
+        assert!(user.text.starts_with("free text"));
+    }
+
+    #[test]
+    fn ixml_speed() {
+        let root = Element::parse(
+            "<BWFXML><SPEED>
+                <MASTER_SPEED>24/1</MASTER_SPEED>
+                <CURRENT_SPEED>24000/1001</CURRENT_SPEED>
+                <TIMECODE_RATE>25</TIMECODE_RATE>
+                <TIMECODE_FLAG>NDF</TIMECODE_FLAG>
+                <FILE_SAMPLE_RATE>48000</FILE_SAMPLE_RATE>
+                <AUDIO_BIT_DEPTH>24</AUDIO_BIT_DEPTH>
+                <TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI>1</TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI>
+                <TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO>2</TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO>
+                <TIMESTAMP_SAMPLE_RATE>bad</TIMESTAMP_SAMPLE_RATE>
+            </SPEED></BWFXML>",
+        )
+        .unwrap();
+        let speed = Speed::from_element(&root).unwrap();
+        dbg!(&speed);
+        let current = speed.parsed_current_speed().unwrap();
+        assert_eq!(current.to_string(), "24000/1001");
+        assert!((current.as_f64() - 23.976).abs() < 0.001);
+        assert_eq!(speed.parsed_timecode_rate().unwrap().to_string(), "25/1");
+        assert_eq!(speed.parsed_file_sample_rate().unwrap(), 48000);
+        assert_eq!(speed.parsed_audio_bit_depth().unwrap(), 24);
+        assert_eq!(
+            speed.parsed_timestamp_samples_since_midnight().unwrap(),
+            (1 << 32) + 2
+        );
+        assert!(speed.parsed_timestamp_sample_rate().is_err());
+        assert!(speed.parsed_digitizer_sample_rate().is_err());
+        assert!("1/0".parse::<Rational>().is_err());
+    }
+
+    #[test]
+    fn ixml_start_timecode() {
+        // 01:00:00:00 at 25 fps, 48 kHz
+        let mut speed = Speed {
+            timecode_rate: Some("25/1".to_string()),
+            timecode_flag: Some("NDF".to_string()),
+            file_sample_rate: Some("48000".to_string()),
+            timestamp_samples_since_midnight_hi: Some("0".to_string()),
+            timestamp_samples_since_midnight_lo: Some("172800000".to_string()),
+            ..Speed::default()
+        };
+        let timecode = speed.start_timecode().unwrap();
+        dbg!(&timecode);
+        assert_eq!(timecode.to_string(), "01:00:00:00");
+
+        // frame 1800 in 29.97 drop frame timecode, frames 0 and 1 of the
+        // first minute are skipped. 1800 frames x 1601.6 samples per frame
+        let samples: u64 = 2_882_880;
+        speed.timecode_rate = Some("30000/1001".to_string());
+        speed.timecode_flag = Some("DF".to_string());
+        speed.timestamp_samples_since_midnight_lo = Some(samples.to_string());
+        let timecode = speed.start_timecode().unwrap();
+        dbg!(&timecode);
+        assert_eq!(timecode.to_string(), "00:01:00;02");
+
+        assert_eq!(timecode.to_samples(48_000), samples);
+
+        speed.timecode_flag = Some("drop".to_string());
+        assert!(speed.start_timecode().is_err());
+        speed.timecode_flag = None;
+        assert!(!speed.start_timecode().unwrap().drop_frame);
+        speed.timecode_rate = None;
+        assert!(speed.start_timecode().is_err());
+    }
+
+    #[test]
+    fn ixml_invalid_xml() {
+        let mut buff = hex_to_cursor("69584D4C 04000000 3C413E3C");
+        let ixml = IxmlChunk::read(&mut buff).unwrap();
+        assert_eq!(ixml.data.raw_bytes.len(), 4);
+        assert_eq!(ixml.data.file_set, None);
     }
 }
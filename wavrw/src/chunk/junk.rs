@@ -1,18 +1,17 @@
 //! `JUNK` Padding, filler or outdated information. Also `PAD `, `FLLR`. [RIFF1991](https://wavref.til.cafe/chunk/junk/)
 
-use binrw::{binrw, helpers};
+use alloc::string::{String, ToString};
+use binrw::binrw;
 
 use crate::{fourcc, FourCC, KnownChunk, KnownChunkID, Summarizable};
 
-/// `data` chunk parser which skips all audio data
+/// Padding chunk parser. Parses nothing, the contents are kept in
+/// [`KnownChunk::extra_bytes`].
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PaddingData<const I: u32> {
-    #[br(parse_with = helpers::until_eof)]
-    data: Vec<u8>,
-}
+pub struct PaddingData<const I: u32> {}
 
 impl<const I: u32> KnownChunkID for PaddingData<I> {
     const ID: FourCC = FourCC(I.to_le_bytes());
@@ -0,0 +1,291 @@
+//! `levl` Peak envelope, a waveform overview of the audio. [EBU3285S3](https://tech.ebu.ch/docs/tech/tech3285s3.pdf)
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+
+use binrw::binrw;
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use crate::fixedstring::FixedString;
+use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
+
+/// Size of a peak point in a [`Levl`] chunk.
+#[binrw]
+#[brw(little, repr = u32)]
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, FromPrimitive)]
+pub enum PeakFormat {
+    /// One byte per point.
+    UnsignedChar = 1,
+    /// Two bytes per point.
+    UnsignedShort = 2,
+    /// Unknown formats.
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl PeakFormat {
+    /// Bytes per point, `None` for unknown formats.
+    pub fn bytes(&self) -> Option<usize> {
+        match self {
+            PeakFormat::UnsignedChar => Some(1),
+            PeakFormat::UnsignedShort => Some(2),
+            PeakFormat::Other(_) => None,
+        }
+    }
+
+    /// Point value of a full scale peak: 127 for bytes, 32767 for shorts.
+    pub fn full_scale(&self) -> Option<u16> {
+        match self {
+            PeakFormat::UnsignedChar => Some(127),
+            PeakFormat::UnsignedShort => Some(32767),
+            PeakFormat::Other(_) => None,
+        }
+    }
+}
+
+impl Display for PeakFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            PeakFormat::UnsignedChar => write!(f, "unsigned char"),
+            PeakFormat::UnsignedShort => write!(f, "unsigned short"),
+            PeakFormat::Other(value) => write!(f, "other({value})"),
+        }
+    }
+}
+
+impl TryFrom<&PeakFormat> for u32 {
+    type Error = core::num::TryFromIntError;
+
+    // infalible, but binrw seems to need TryFrom?
+    fn try_from(value: &PeakFormat) -> Result<Self, Self::Error> {
+        Ok(u32::from(*value))
+    }
+}
+
+/// `levl` Peak envelope, a waveform overview of the audio. [EBU3285S3](https://tech.ebu.ch/docs/tech/tech3285s3.pdf)
+///
+/// Peak points are stored frame by frame: for each block of `block_size`
+/// audio frames, the points of every channel. With two points per value,
+/// the positive peak comes first, followed by the magnitude of the negative
+/// peak. See [`crate::peaks`] to generate this chunk.
+#[binrw]
+#[brw(little)]
+#[br(import(size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Levl {
+    /// Version of the peak envelope chunk, currently 0.
+    pub version: u32,
+
+    /// Size of each peak point.
+    pub format: PeakFormat,
+
+    /// Points per channel and block: 1 for the absolute peak only, 2 for
+    /// the positive and negative peaks.
+    pub points_per_value: u32,
+
+    /// Audio frames per peak frame, usually 256.
+    pub block_size: u32,
+
+    /// Number of channels.
+    pub peak_channels: u32,
+
+    /// Number of peak frames.
+    pub num_peak_frames: u32,
+
+    /// Audio frame of the highest peak, 0xFFFFFFFF if unknown.
+    pub pos_peak_of_peaks: u32,
+
+    /// Offset of the peak points from the start of the chunk header,
+    /// usually 128.
+    pub offset_to_peaks: u32,
+
+    /// Creation time of the peak data. Ex: `2000:08:24:13:55:40:967`
+    pub timestamp: FixedString<28>,
+
+    /// Reserved, zeros.
+    pub reserved: [u8; 60],
+
+    /// Bytes after the header: any gap up to `offset_to_peaks`, then the
+    /// peak points. See [`Levl::points()`].
+    #[br(parse_with = crate::read_bytes, args(u64::from(size.saturating_sub(Levl::HEADER_SIZE))))]
+    pub peak_data: Vec<u8>,
+}
+
+impl KnownChunkID for Levl {
+    const ID: FourCC = FourCC(*b"levl");
+}
+
+impl Levl {
+    /// Size of the fields before [`Levl::peak_data`].
+    pub const HEADER_SIZE: u32 = 120;
+
+    /// `offset_to_peaks` when the points directly follow the header: the
+    /// header plus the 8 byte chunk id and size.
+    pub const PEAKS_OFFSET: u32 = Self::HEADER_SIZE + 8;
+
+    /// Peak points in file order, scaled so a full scale peak is 1.0.
+    ///
+    /// Empty for unknown formats.
+    ///
+    /// ```
+    /// use wavrw::chunk::levl::{Levl, PeakFormat};
+    ///
+    /// let levl = Levl {
+    ///     format: PeakFormat::UnsignedChar,
+    ///     peak_data: vec![127, 0, 64],
+    ///     ..Levl::default()
+    /// };
+    /// assert_eq!(levl.points()[..2], [1.0, 0.0]);
+    /// ```
+    pub fn points(&self) -> Vec<f64> {
+        let (Some(bytes), Some(full_scale)) = (self.format.bytes(), self.format.full_scale())
+        else {
+            return Vec::new();
+        };
+        let gap = usize::try_from(self.offset_to_peaks.saturating_sub(Self::PEAKS_OFFSET))
+            .unwrap_or(usize::MAX);
+        let full_scale = f64::from(full_scale);
+        self.peak_data
+            .get(gap..)
+            .unwrap_or_default()
+            .chunks_exact(bytes)
+            .map(|point| match point {
+                [byte] => f64::from(*byte) / full_scale,
+                _ => f64::from(u16::from_le_bytes([point[0], point[1]])) / full_scale,
+            })
+            .collect()
+    }
+}
+
+impl Default for Levl {
+    fn default() -> Self {
+        Levl {
+            version: 0,
+            format: PeakFormat::UnsignedShort,
+            points_per_value: 2,
+            block_size: 256,
+            peak_channels: 0,
+            num_peak_frames: 0,
+            pos_peak_of_peaks: u32::MAX,
+            offset_to_peaks: Self::PEAKS_OFFSET,
+            timestamp: FixedString::default(),
+            reserved: [0; 60],
+            peak_data: Vec::new(),
+        }
+    }
+}
+
+impl Summarizable for Levl {
+    fn summary(&self) -> String {
+        format!(
+            "{} chan, {} peak frames of {} samples, {}",
+            self.peak_channels, self.num_peak_frames, self.block_size, self.format
+        )
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let pos_peak_of_peaks = match self.pos_peak_of_peaks {
+            u32::MAX => "unknown".to_string(),
+            pos => pos.to_string(),
+        };
+        Box::new(
+            [
+                ("version".to_string(), self.version.to_string()),
+                ("format".to_string(), self.format.to_string()),
+                (
+                    "points_per_value".to_string(),
+                    self.points_per_value.to_string(),
+                ),
+                ("block_size".to_string(), self.block_size.to_string()),
+                ("peak_channels".to_string(), self.peak_channels.to_string()),
+                (
+                    "num_peak_frames".to_string(),
+                    self.num_peak_frames.to_string(),
+                ),
+                ("pos_peak_of_peaks".to_string(), pos_peak_of_peaks),
+                (
+                    "offset_to_peaks".to_string(),
+                    self.offset_to_peaks.to_string(),
+                ),
+                ("timestamp".to_string(), self.timestamp.to_string()),
+                (
+                    "peak_data".to_string(),
+                    format!("{} bytes", self.peak_data.len()),
+                ),
+            ]
+            .into_iter(),
+        )
+    }
+}
+
+/// `levl` Peak envelope, a waveform overview of the audio. [EBU3285S3](https://tech.ebu.ch/docs/tech/tech3285s3.pdf)
+pub type LevlChunk = KnownChunk<Levl>;
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use binrw::BinRead;
+
+    use super::*;
+    use crate::testing::{hex_to_cursor, Arbitrary, Rng};
+
+    #[test]
+    fn parse_levl() {
+        // 1 channel, 2 peak frames of positive and negative 8 bit points
+        let mut buff = hex_to_cursor(
+            "6C65766C 7C000000 00000000 01000000 02000000 00010000 01000000 \
+            02000000 05000000 80000000 \
+            323030303A30383A32343A31333A35353A34303A39363700 00000000 \
+            000000000000000000000000000000000000000000000000000000000000 \
+            000000000000000000000000000000000000000000000000000000000000 \
+            7F40 1020",
+        );
+        let chunk = LevlChunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        assert_eq!(chunk.size, 124);
+        assert_eq!(chunk.data.format, PeakFormat::UnsignedChar);
+        assert_eq!(chunk.data.points_per_value, 2);
+        assert_eq!(chunk.data.block_size, 256);
+        assert_eq!(chunk.data.num_peak_frames, 2);
+        assert_eq!(chunk.data.pos_peak_of_peaks, 5);
+        assert_eq!(chunk.data.timestamp.to_string(), "2000:08:24:13:55:40:967");
+        assert_eq!(
+            chunk.data.points(),
+            [1.0, 64.0 / 127.0, 16.0 / 127.0, 32.0 / 127.0]
+        );
+        assert_eq!(
+            chunk.data.summary(),
+            "1 chan, 2 peak frames of 256 samples, unsigned char"
+        );
+    }
+
+    impl Arbitrary for Levl {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let mut reserved = [0u8; 60];
+            for b in &mut reserved {
+                *b = rng.next_u8();
+            }
+            Levl {
+                version: rng.next_u32(),
+                format: PeakFormat::from(rng.next_u32() % 4),
+                points_per_value: rng.next_u32(),
+                block_size: rng.next_u32(),
+                peak_channels: rng.next_u32(),
+                num_peak_frames: rng.next_u32(),
+                pos_peak_of_peaks: rng.next_u32(),
+                offset_to_peaks: rng.next_u32(),
+                timestamp: FixedString::from_str_lossy(&rng.ascii(28)),
+                reserved,
+                peak_data: rng.bytes(64),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        levl_roundtrip: Levl,
+    }
+}
@@ -1,12 +1,14 @@
 //! `MD5 ` Checksum of audio `data` of the WAVE. [MD5_2017](https://wavref.til.cafe/chunk/md5/)
 
+use alloc::format;
+use alloc::string::String;
 use binrw::binrw;
 
 use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
 
 /// `MD5 ` Checksum of audio `data` of the WAVE. [MD5_2017](https://wavref.til.cafe/chunk/md5/)
 ///
-/// Specified by BWFMetaEdit:  `https://mediaarea.net/BWFMetaEdit/md5`
+/// Specified by `BWFMetaEdit`:  `https://mediaarea.net/BWFMetaEdit/md5`
 #[binrw]
 #[brw(little)]
 #[br(import(_size: u32))]
@@ -32,6 +34,7 @@ pub type Md5Chunk = KnownChunk<Md5>;
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::BinRead;
 
     use super::*;
@@ -47,6 +50,9 @@ mod test {
                 md5: 0x37A5BED4393B8F3708963F5E59C7F483,
             },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
 
         let chunk = Md5Chunk::read(&mut buff).expect("error parsing WAV chunks");
@@ -55,4 +61,16 @@ mod test {
         assert_eq!(chunk, expected);
         // hexdump(remaining_input);
     }
+
+    impl Arbitrary for Md5 {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Md5 {
+                md5: u128::from(rng.next_u64()) << 64 | u128::from(rng.next_u64()),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        md5_roundtrip: Md5,
+    }
 }
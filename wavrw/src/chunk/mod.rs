@@ -11,18 +11,302 @@
 //! TODO: write about architecture
 
 pub mod adtl;
+#[cfg(feature = "broadcast")]
 pub mod bext;
 pub mod cset;
 pub mod cue;
 pub mod data;
+pub mod disp;
+pub mod ds64;
 pub mod fact;
 pub mod fmt;
+#[cfg(feature = "info")]
 pub mod info;
 pub mod inst;
+#[cfg(feature = "ixml")]
 pub mod ixml;
 pub mod junk;
+pub mod levl;
 pub mod md5;
 pub mod plst;
 pub mod riff;
 pub mod smpl;
+pub mod soundminer;
 pub mod wavl;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{FourCC, KnownChunkID};
+
+/// Description of a chunk type wavrw parses. See [`SUPPORTED`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkDescription {
+    /// RIFF chunk id, `LIST` for lists.
+    pub id: FourCC,
+    /// List type for `LIST` chunks. Ex: `INFO`
+    pub list_type: Option<FourCC>,
+    /// List type of the `LIST` containing this chunk, for sub-chunks.
+    pub parent: Option<FourCC>,
+    /// One line description.
+    pub description: &'static str,
+    /// Specification defining the chunk. Ex: `RIFF1991`
+    pub spec: &'static str,
+    /// Page about the chunk in the [Wav Reference book](https://wavref.til.cafe/).
+    pub url: &'static str,
+}
+
+impl ChunkDescription {
+    const fn new(
+        id: FourCC,
+        description: &'static str,
+        spec: &'static str,
+        url: &'static str,
+    ) -> Self {
+        ChunkDescription {
+            id,
+            list_type: None,
+            parent: None,
+            description,
+            spec,
+            url,
+        }
+    }
+
+    const fn list(self, list_type: FourCC) -> Self {
+        ChunkDescription {
+            list_type: Some(list_type),
+            ..self
+        }
+    }
+
+    const fn parent(self, parent: FourCC) -> Self {
+        ChunkDescription {
+            parent: Some(parent),
+            ..self
+        }
+    }
+
+    /// Chunk name, as shown by [`Summarizable::name()`][crate::Summarizable::name]. Ex: `LIST-INFO`
+    pub fn name(&self) -> String {
+        match self.list_type {
+            Some(list_type) => format!("{}-{}", self.id, list_type),
+            None => self.id.to_string().trim().to_string(),
+        }
+    }
+}
+
+/// Every chunk type wavrw parses, in alphabetical order. Sub-chunks follow the
+/// `LIST` containing them.
+///
+/// ```
+/// let info = wavrw::chunk::SUPPORTED
+///     .iter()
+///     .find(|c| c.name() == "LIST-INFO")
+///     .unwrap();
+/// assert_eq!(info.spec, "RIFF1991");
+/// ```
+pub const SUPPORTED: &[ChunkDescription] = &[
+    #[cfg(feature = "broadcast")]
+    ChunkDescription::new(
+        bext::Bext::ID,
+        "Broadcast Extension for motion picture, radio and television production.",
+        "BEXT1996",
+        "https://wavref.til.cafe/spec/bext1996/",
+    ),
+    ChunkDescription::new(
+        cset::Cset::ID,
+        "Character set information. Code page, language, etc. Very Rare.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/cset/",
+    ),
+    ChunkDescription::new(
+        cue::Cue::ID,
+        "A series of positions in the waveform data chunk.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/cue/",
+    ),
+    ChunkDescription::new(
+        data::Data::ID,
+        "Audio samples. This parser skips all audio data (for now).",
+        "RIFF1991",
+        "https://wavref.til.cafe/spec/riff1991/",
+    ),
+    ChunkDescription::new(
+        disp::Disp::ID,
+        "Data to display the file, usually a title. Rare.",
+        "RIFF1991",
+        "https://wavref.til.cafe/spec/riff1991/",
+    ),
+    ChunkDescription::new(
+        ds64::Ds64::ID,
+        "64 bit sizes of the RIFF, data and other chunks in RF64 files.",
+        "RF64_2009",
+        "https://tech.ebu.ch/docs/tech/tech3306-2009.pdf",
+    ),
+    ChunkDescription::new(
+        fact::Fact::ID,
+        "Number of samples for compressed audio in data.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/fact/",
+    ),
+    ChunkDescription::new(
+        junk::Fllr::ID,
+        "Padding, filler or outdated information.",
+        "UNKNOWN",
+        "https://wavref.til.cafe/chunk/fllr/",
+    ),
+    ChunkDescription::new(
+        fmt::FmtEnum::ID,
+        "Format of audio samples in data.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/fmt/",
+    ),
+    ChunkDescription::new(
+        inst::Inst::ID,
+        "Pitch, volume, and velocity for playback by sampler.",
+        "RIFF1994",
+        "https://wavref.til.cafe/chunk/inst/",
+    ),
+    #[cfg(feature = "ixml")]
+    ChunkDescription::new(
+        ixml::Ixml::ID,
+        "Production workflow file & project metadata.",
+        "IXML2021",
+        "https://wavref.til.cafe/spec/ixml2021/",
+    ),
+    ChunkDescription::new(
+        junk::Junk::ID,
+        "Padding, filler or outdated information.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/junk/",
+    ),
+    ChunkDescription::new(
+        levl::Levl::ID,
+        "Peak envelope, a waveform overview of the audio.",
+        "EBU3285S3",
+        "https://tech.ebu.ch/docs/tech/tech3285s3.pdf",
+    ),
+    ChunkDescription::new(
+        md5::Md5::ID,
+        "Checksum of audio data of the WAVE.",
+        "MD5_2017",
+        "https://wavref.til.cafe/chunk/md5/",
+    ),
+    ChunkDescription::new(
+        junk::Pad::ID,
+        "Padding, filler or outdated information.",
+        "RIFF1994",
+        "https://wavref.til.cafe/chunk/pad/",
+    ),
+    ChunkDescription::new(
+        plst::Plst::ID,
+        "Play order for cue points. Very rare.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/plst/",
+    ),
+    ChunkDescription::new(
+        FourCC(*b"RIFF"),
+        "Container structure for multimedia data.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/wave/",
+    ),
+    ChunkDescription::new(
+        soundminer::Smed::ID,
+        "Soundminer metadata, proprietary. Header and readable text only.",
+        "UNKNOWN",
+        "https://soundminer.com/",
+    ),
+    ChunkDescription::new(
+        smpl::Smpl::ID,
+        "Information needed for use as a sampling instrument.",
+        "RIFF1994",
+        "https://wavref.til.cafe/chunk/smpl/",
+    ),
+    ChunkDescription::new(
+        soundminer::Smrd::ID,
+        "Soundminer metadata, proprietary. Header and readable text only.",
+        "UNKNOWN",
+        "https://soundminer.com/",
+    ),
+    ChunkDescription::new(
+        adtl::ListAdtl::ID,
+        "Cue point annotation chunks.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/adtl/",
+    )
+    .list(adtl::ListAdtl::LIST_TYPE),
+    ChunkDescription::new(
+        adtl::File::ID,
+        "Information embedded in other file formats.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/adtl/",
+    )
+    .parent(adtl::ListAdtl::LIST_TYPE),
+    ChunkDescription::new(
+        adtl::Labl::ID,
+        "A label, or title, to associate with a cue point.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/adtl/",
+    )
+    .parent(adtl::ListAdtl::LIST_TYPE),
+    ChunkDescription::new(
+        adtl::Ltxt::ID,
+        "Text associated with a range of data samples.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/adtl/",
+    )
+    .parent(adtl::ListAdtl::LIST_TYPE),
+    ChunkDescription::new(
+        adtl::Note::ID,
+        "Comment text for a cue point.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/adtl/",
+    )
+    .parent(adtl::ListAdtl::LIST_TYPE),
+    #[cfg(feature = "info")]
+    ChunkDescription::new(
+        info::ListInfo::ID,
+        "Descriptive text chunks: IARL, IGNR, IKEY, ILGT, IMED, INAM, IPLT, IPRD, ISBJ, \
+        ISFT, ISHP, IART, ISRC, ISRF, ITCH, ICMS, ICMT, ICOP, ICRD, ICRP, IDPI, IENG, ISMP, IDIT.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/info/",
+    )
+    .list(info::ListInfo::LIST_TYPE),
+    ChunkDescription::new(
+        wavl::ListWavl::ID,
+        "Audio and/or silence chunks. Very rare.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/wavl/",
+    )
+    .list(wavl::ListWavl::LIST_TYPE),
+    ChunkDescription::new(
+        data::Data::ID,
+        "Audio samples. This parser skips all audio data (for now).",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/wavl/",
+    )
+    .parent(wavl::ListWavl::LIST_TYPE),
+    ChunkDescription::new(
+        wavl::Slnt::ID,
+        "A count of samples to be played as silence.",
+        "RIFF1991",
+        "https://wavref.til.cafe/chunk/wavl/",
+    )
+    .parent(wavl::ListWavl::LIST_TYPE),
+];
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn supported_names() {
+        let names: alloc::vec::Vec<_> = SUPPORTED.iter().map(ChunkDescription::name).collect();
+        dbg!(&names);
+        assert!(names.contains(&"fmt".to_string()));
+        assert!(names.contains(&"LIST-adtl".to_string()));
+        assert_eq!(SUPPORTED.iter().filter(|c| c.parent.is_some()).count(), 6);
+    }
+}
@@ -1,5 +1,10 @@
 //! `plst` Play order for cue points. Very rare. [RIFF1991](https://wavref.til.cafe/chunk/plst/)
 
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use binrw::binrw;
@@ -19,11 +24,23 @@ pub struct PlstSegment {
 
     /// The number of times to play the section.
     pub loops: u32,
+
+    /// Label of the cue point from `LIST-adtl`, when resolved.
+    ///
+    /// Filled in by [`Plst::set_labels()`], which
+    /// [`WaveFile::chunks()`](crate::WaveFile::chunks) calls after parsing.
+    /// Ignored when reading and writing.
+    #[brw(ignore)]
+    pub label: Option<String>,
 }
 
 impl PlstSegment {
     fn summary(&self) -> String {
-        format!("{:7}, {:7}, {:7}", self.name, self.length, self.loops,)
+        let cue = match &self.label {
+            Some(label) => format!("'{label}' (cue {})", self.name),
+            None => format!("cue {}", self.name),
+        };
+        format!("{cue} x{}, length {}", self.loops, self.length)
     }
 }
 
@@ -45,6 +62,17 @@ impl KnownChunkID for Plst {
     const ID: FourCC = FourCC(*b"plst");
 }
 
+impl Plst {
+    /// Set each segment's `label` from `labels`, keyed by cue point name.
+    ///
+    /// See [`WaveMetadata::cue_labels()`](crate::WaveMetadata::cue_labels).
+    pub fn set_labels(&mut self, labels: &BTreeMap<u32, String>) {
+        for segment in &mut self.segments {
+            segment.label = labels.get(&segment.name).cloned();
+        }
+    }
+}
+
 /// `plst` Play order for cue points. Very rare. [RIFF1991](https://wavref.til.cafe/chunk/plst/)
 ///
 /// NOTE: Implemented from the spec only, because I couldn't find any files actually
@@ -62,14 +90,14 @@ impl Summarizable for Plst {
 
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         let mut items: Vec<(String, String)> = Vec::new();
-        for segment in &self.segments {
-            items.push((format!("{}", segment.name), segment.summary()));
+        for (index, segment) in self.segments.iter().enumerate() {
+            items.push((format!("segment {}", index + 1), segment.summary()));
         }
         Box::new(items.into_iter())
     }
 
     fn item_summary_header(&self) -> String {
-        "name: length, loops".to_string()
+        "segment: cue x loops, length".to_string()
     }
 
     fn name(&self) -> String {
@@ -80,6 +108,7 @@ impl Summarizable for Plst {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::{BinRead, BinWrite};
     use hexdump::hexdump;
     use std::io::Read;
@@ -101,15 +130,20 @@ mod test {
                         name: 4,
                         length: 5,
                         loops: 6,
+                        label: None,
                     },
                     PlstSegment {
                         name: 7,
                         length: 8,
                         loops: 9,
+                        label: None,
                     },
                 ],
             },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         println!("{plst:?}");
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
@@ -139,8 +173,55 @@ mod test {
                 name: 1,
                 length: 2,
                 loops: 3,
+                label: None,
             },
         );
         assert_eq!(plst.extra_bytes.len(), 0);
     }
+
+    #[test]
+    fn plst_labels() {
+        let mut buff = hex_to_cursor(
+            "706C7374 1C000000 02000000 01000000 00010000 03000000 02000000 80000000 01000000",
+        );
+        let mut plst = PlstChunk::read(&mut buff).unwrap();
+        plst.data
+            .set_labels(&BTreeMap::from([(1, "Chorus".to_string())]));
+        let items: Vec<_> = plst.data.items().collect();
+        dbg!(&items);
+        assert_eq!(
+            items[0],
+            (
+                "segment 1".to_string(),
+                "'Chorus' (cue 1) x3, length 256".to_string()
+            )
+        );
+        assert_eq!(items[1].1, "cue 2 x1, length 128");
+
+        // labels are not written
+        let mut out = std::io::Cursor::new(Vec::<u8>::new());
+        plst.write(&mut out).unwrap();
+        assert_eq!(out.into_inner().len(), 8 + 0x1C);
+    }
+
+    impl Arbitrary for Plst {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let segments: Vec<PlstSegment> = (0..rng.below(8))
+                .map(|_| PlstSegment {
+                    name: rng.next_u32(),
+                    length: rng.next_u32(),
+                    loops: rng.next_u32(),
+                    label: None,
+                })
+                .collect();
+            Plst {
+                segment_count: segments.len() as u32,
+                segments,
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        plst_arbitrary_roundtrip: Plst,
+    }
 }
@@ -1,11 +1,53 @@
 //! `smpl` Information needed for use as a sampling instrument. [RIFF1994](https://wavref.til.cafe/chunk/smpl/)
 
-use core::fmt::Debug;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
 
 use binrw::binrw;
+use num_enum::{FromPrimitive, IntoPrimitive};
 
 use crate::{FourCC, KnownChunk, KnownChunkID, Summarizable};
 
+/// How a [`SmplLoop`] is played.
+#[binrw]
+#[brw(little, repr = u32)]
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoPrimitive, FromPrimitive)]
+pub enum LoopType {
+    /// Loop forward (normal).
+    Forward = 0,
+    /// Alternating loop, forward then backward.
+    PingPong = 1,
+    /// Loop backward.
+    Backward = 2,
+    /// Reserved (3-31) or sampler specific (32 and above) types.
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl Display for LoopType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            LoopType::Forward => write!(f, "forward"),
+            LoopType::PingPong => write!(f, "ping-pong"),
+            LoopType::Backward => write!(f, "backward"),
+            LoopType::Other(value) => write!(f, "other({value})"),
+        }
+    }
+}
+
+impl TryFrom<&LoopType> for u32 {
+    type Error = core::num::TryFromIntError;
+
+    // infalible, but binrw seems to need TryFrom?
+    fn try_from(value: &LoopType) -> Result<Self, Self::Error> {
+        Ok(u32::from(*value))
+    }
+}
+
 /// Loop details from a `smpl` chunk.
 #[binrw]
 #[brw(little)]
@@ -23,7 +65,7 @@ pub struct SmplLoop {
     /// Specifies the loop type: 0 - Loop forward (normal). 1 - Alternating
     /// loop (forward/backward). 2 - Loop backward. 3-31 - reserved for future
     /// standard types. 32-? - sampler specific types (manufacturer defined).
-    pub loop_type: u32,
+    pub loop_type: LoopType,
 
     /// Specifies the startpoint of the loop in samples.
     pub start: u32,
@@ -46,6 +88,13 @@ pub struct SmplLoop {
     pub play_count: u32,
 }
 
+impl SmplLoop {
+    /// Length of the loop in samples, including the `end` sample.
+    pub fn sample_count(&self) -> u32 {
+        self.end.saturating_sub(self.start).saturating_add(1)
+    }
+}
+
 /// `smpl` Information needed for use as a sampling instrument. [RIFF1994](https://wavref.til.cafe/chunk/smpl/)
 #[binrw]
 #[brw(little)]
@@ -73,7 +122,7 @@ pub struct Smpl {
     /// Period of one sample in nanoseconds.
     ///
     /// Specifies the period of one sample in nanoseconds (normally 1/
-    /// samples_per_second from the WAVEFORMAT structure for the RIFF WAVE file
+    /// `samples_per_second` from the WAVEFORMAT structure for the RIFF WAVE file
     /// -- however, this field allows fine tuning). For example, 44.1 kHz would
     /// be specified as 22675 (0x00005893).
     pub sample_period: u32,
@@ -88,15 +137,15 @@ pub struct Smpl {
     /// Fine tune pitch as fraction of a semitone.
     ///
     /// Specifies the fraction of a semitone up from the specified
-    /// midi_unity_note. A value of 0x80000000 is 1/2 semitone (50 cents); a
+    /// `midi_unity_note`. A value of 0x80000000 is 1/2 semitone (50 cents); a
     /// value of 0x00000000 represents no fine tuning between semitones.
     pub midi_pitch_fraction: u32,
 
     /// SMPTE time format.
     ///
-    /// Specifies the SMPTE time format used in the smpte_offset field. Possible
+    /// Specifies the SMPTE time format used in the `smpte_offset` field. Possible
     /// values are (unrecognized formats should be ignored): 0 - specifies
-    /// no SMPTE offset (smpte_offset should also be zero). 24 - specifies 24
+    /// no SMPTE offset (`smpte_offset` should also be zero). 24 - specifies 24
     /// frames per second. 25 - specifies 25 frames per second. 29 - specifies
     /// 30 frames per second with frame dropping ('30 drop'). 30 - specifies 30
     /// frames per second.
@@ -108,7 +157,7 @@ pub struct Smpl {
     /// calibrated according to a start time other than 0. The format of this
     /// value is 0xhhmmssff. hh is a signed Hours value [-23..23]. mm is an
     /// unsigned Minutes value [0..59]. ss is unsigned Seconds value [0..59]. ff
-    /// is an unsigned value [0..(smpte_format - 1)].
+    /// is an unsigned value [0..(`smpte_format` - 1)].
     pub smpte_offset: u32,
 
     /// Count of sample loops (for serialization/deserialization)
@@ -135,7 +184,7 @@ pub struct Smpl {
     pub sample_loops: Vec<SmplLoop>,
 
     /// Sampler specific data bytes.
-    #[br(count = sampler_data_size)]
+    #[br(parse_with = crate::read_bytes, args(sampler_data_size.into()))]
     pub sampler_data: Vec<u8>,
 }
 
@@ -159,6 +208,46 @@ impl Smpl {
     }
 }
 
+impl Smpl {
+    /// Name of `midi_unity_note`, ex: `C3` for middle C (60).
+    ///
+    /// Uses the convention common in samplers and DAWs where middle C is `C3`,
+    /// so note 0 is `C-2`. Returns `None` for values above 127.
+    pub fn midi_unity_note_name(&self) -> Option<String> {
+        midi_note_name(self.midi_unity_note)
+    }
+
+    /// `midi_pitch_fraction` converted to cents (hundredths of a semitone)
+    /// above `midi_unity_note`.
+    pub fn midi_pitch_cents(&self) -> f64 {
+        // divided by 2^32
+        f64::from(self.midi_pitch_fraction) / 4_294_967_296.0 * 100.0
+    }
+}
+
+/// Name of a MIDI note number, with middle C (60) as `C3`.
+///
+/// Returns `None` for values above 127.
+///
+/// ```
+/// use wavrw::chunk::smpl::midi_note_name;
+///
+/// assert_eq!(midi_note_name(60), Some("C3".to_string()));
+/// assert_eq!(midi_note_name(70), Some("A#3".to_string()));
+/// assert_eq!(midi_note_name(0), Some("C-2".to_string()));
+/// ```
+pub fn midi_note_name(note: u32) -> Option<String> {
+    const NAMES: [&str; 12] = [
+        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+    ];
+    if note > 127 {
+        return None;
+    }
+    let name = NAMES.get(usize::try_from(note % 12).ok()?)?;
+    let octave = i64::from(note / 12) - 2;
+    Some(format!("{name}{octave}"))
+}
+
 impl Default for Smpl {
     fn default() -> Self {
         Self::new()
@@ -174,7 +263,14 @@ impl Summarizable for Smpl {
             1 => "loop",
             _ => "loops",
         };
-        format!("{} {label}", self.sample_loops.len())
+        let note = self
+            .midi_unity_note_name()
+            .unwrap_or_else(|| self.midi_unity_note.to_string());
+        format!(
+            "{note} {:+.1} cents, {} {label}",
+            self.midi_pitch_cents(),
+            self.sample_loops.len()
+        )
     }
 
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
@@ -183,13 +279,18 @@ impl Summarizable for Smpl {
         items.push(("product".to_string(), self.product.to_string()));
         // consider adding comparison to expected rate
         items.push(("sample_period".to_string(), self.sample_period.to_string()));
-        items.push((
-            "midi_unity_note".to_string(),
-            self.midi_unity_note.to_string(),
-        ));
+        let note = match self.midi_unity_note_name() {
+            Some(name) => format!("{} ({name})", self.midi_unity_note),
+            None => self.midi_unity_note.to_string(),
+        };
+        items.push(("midi_unity_note".to_string(), note));
         items.push((
             "midi_pitch_fraction".to_string(),
-            self.midi_pitch_fraction.to_string(),
+            format!(
+                "{} ({:+.1} cents)",
+                self.midi_pitch_fraction,
+                self.midi_pitch_cents()
+            ),
         ));
         items.push(("smpte_format".to_string(), self.smpte_format.to_string()));
         items.push(("smpte_offset".to_string(), self.smpte_offset.to_string()));
@@ -206,18 +307,19 @@ impl Summarizable for Smpl {
         items.push((
             "loop identifier".to_string(),
             format!(
-                "{:>5}, {:>10}, {:>10}, {:>10}, {:>5}",
-                "type", "start", "end", "fraction", "play"
+                "{:>9}, {:>10}, {:>10}, {:>10}, {:>10}, {:>5}",
+                "type", "start", "end", "length", "fraction", "play"
             ),
         ));
         for sample_loop in &self.sample_loops {
             items.push((
                 format!("{}", sample_loop.identifier),
                 format!(
-                    "{:5}, {:10}, {:10}, {:10}, {:5}",
-                    sample_loop.loop_type,
+                    "{:>9}, {:10}, {:10}, {:10}, {:10}, {:5}",
+                    sample_loop.loop_type.to_string(),
                     sample_loop.start,
                     sample_loop.end,
+                    sample_loop.sample_count(),
                     sample_loop.fraction,
                     sample_loop.play_count
                 ),
@@ -239,6 +341,7 @@ impl Summarizable for Smpl {
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::BinRead;
 
     use super::*;
@@ -260,7 +363,7 @@ mod test {
             smpl.data.sample_loops[1],
             SmplLoop {
                 identifier: 2,
-                loop_type: 0,
+                loop_type: LoopType::Forward,
                 start: 0,
                 end: 1342701568,
                 fraction: 0,
@@ -268,5 +371,70 @@ mod test {
             },
         );
         assert_eq!(smpl.extra_bytes.len(), 0);
+        assert_eq!(smpl.data.summary(), "C-2 +0.0 cents, 3 loops");
+    }
+
+    #[test]
+    fn parse_smpl_corrupt_sampler_data_size() {
+        // no loops, sampler_data_size of 0xFFFFFFFF and no sampler data
+        let mut buff = hex_to_cursor(
+            r#"736D706C 24000000 00000000 00000000 93580000 3C000000 00000000 00000000 00000000 00000000 FFFFFFFF"#,
+        );
+        let smpl = SmplChunk::read(&mut buff);
+        dbg!(&smpl);
+        assert!(smpl.is_err());
+    }
+
+    #[test]
+    fn smpl_note_and_loop_types() {
+        let smpl = Smpl {
+            midi_unity_note: 69,
+            midi_pitch_fraction: 0x8000_0000,
+            sample_loops: vec![SmplLoop {
+                identifier: 1,
+                loop_type: LoopType::from(1),
+                start: 100,
+                end: 199,
+                fraction: 0,
+                play_count: 0,
+            }],
+            ..Smpl::default()
+        };
+        assert_eq!(smpl.midi_unity_note_name(), Some("A3".to_string()));
+        assert!((smpl.midi_pitch_cents() - 50.0).abs() < f64::EPSILON);
+        assert_eq!(smpl.summary(), "A3 +50.0 cents, 1 loop");
+        assert_eq!(smpl.sample_loops[0].loop_type, LoopType::PingPong);
+        assert_eq!(smpl.sample_loops[0].sample_count(), 100);
+        assert_eq!(LoopType::from(40).to_string(), "other(40)");
+        assert_eq!(midi_note_name(128), None);
+    }
+
+    impl Arbitrary for Smpl {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            Smpl {
+                manufacturer: rng.next_u32(),
+                product: rng.next_u32(),
+                sample_period: rng.next_u32(),
+                midi_unity_note: rng.next_u32(),
+                midi_pitch_fraction: rng.next_u32(),
+                smpte_format: rng.next_u32(),
+                smpte_offset: rng.next_u32(),
+                sample_loops: (0..rng.below(4))
+                    .map(|_| SmplLoop {
+                        identifier: rng.next_u32(),
+                        loop_type: LoopType::from(rng.next_u32() % 4),
+                        start: rng.next_u32(),
+                        end: rng.next_u32(),
+                        fraction: rng.next_u32(),
+                        play_count: rng.next_u32(),
+                    })
+                    .collect(),
+                sampler_data: rng.bytes(16),
+            }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        smpl_roundtrip: Smpl,
     }
 }
@@ -0,0 +1,228 @@
+//! `SMED` Soundminer metadata, partially parsed. Also `SMRD`. [UNKNOWN](https://soundminer.com/)
+//!
+//! Soundminer embeds its database record in these proprietary chunks. The
+//! format is not published. In the files seen so far, `SMED` data starts
+//! with a 4 byte header, followed by what looks like encrypted 8 byte
+//! blocks. This parser keeps all bytes, shows the header and block layout
+//! and any readable text it finds. `SMRD` is handled the same way.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use binrw::binrw;
+
+use crate::{fourcc, FourCC, KnownChunk, KnownChunkID, Summarizable};
+
+/// Soundminer chunk parser. Keeps the data, see the [module documentation](self).
+#[binrw]
+#[brw(little)]
+#[br(import(size: u32))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SoundminerData<const I: u32> {
+    /// All bytes of the chunk data.
+    #[br(parse_with = crate::read_bytes, args(u64::from(size)))]
+    pub raw: Vec<u8>,
+}
+
+impl<const I: u32> KnownChunkID for SoundminerData<I> {
+    const ID: FourCC = FourCC(I.to_le_bytes());
+}
+
+/// Shortest run of characters reported by [`SoundminerData::text()`].
+pub const MIN_TEXT_LENGTH: usize = 8;
+
+impl<const I: u32> SoundminerData<I> {
+    /// Size of the header before the blocks.
+    const HEADER_SIZE: usize = 4;
+
+    /// Size of the blocks after the header.
+    const BLOCK_SIZE: usize = 8;
+
+    /// First two bytes, big endian. Zero in all files seen so far, likely a
+    /// version.
+    pub fn version(&self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.raw.get(0..2)?.try_into().ok()?))
+    }
+
+    /// Next two bytes, big endian. Slightly less than the data size, likely
+    /// the length of the decoded data.
+    pub fn length(&self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.raw.get(2..4)?.try_into().ok()?))
+    }
+
+    /// Number of 8 byte blocks after the header, `None` if the remaining
+    /// data is not a whole number of blocks.
+    pub fn blocks(&self) -> Option<usize> {
+        let len = self.raw.len().checked_sub(Self::HEADER_SIZE)?;
+        (len % Self::BLOCK_SIZE == 0).then_some(len / Self::BLOCK_SIZE)
+    }
+
+    /// Readable text in the data, as (offset, text).
+    ///
+    /// Finds runs of at least [`MIN_TEXT_LENGTH`] letters,
+    /// digits, spaces and common punctuation, with at least half of them
+    /// letters, stored as ASCII or UTF-16LE.
+    ///
+    /// ```
+    /// use wavrw::chunk::soundminer::Smed;
+    ///
+    /// let smed = Smed {
+    ///     raw: b"\0\0\x00\x10\x01Door Slam\0\x02W\0o\0o\0d\0e\0n\0 \0D\0o\0o\0r\0".to_vec(),
+    /// };
+    /// assert_eq!(
+    ///     smed.text(),
+    ///     [(5, "Door Slam".to_string()), (16, "Wooden Door".to_string())]
+    /// );
+    /// ```
+    pub fn text(&self) -> Vec<(usize, String)> {
+        let mut found = Vec::new();
+        let mut offset = 0;
+        while offset < self.raw.len() {
+            let ascii = text_run(&self.raw[offset..], 1);
+            let utf16 = text_run(&self.raw[offset..], 2);
+            let (text, step) = if utf16.len() >= ascii.len() {
+                (utf16, 2)
+            } else {
+                (ascii, 1)
+            };
+            if is_text(&text) {
+                found.push((offset, text.clone()));
+                offset += text.len() * step;
+            } else {
+                offset += 1;
+            }
+        }
+        found
+    }
+}
+
+/// Leading characters of `bytes` which look like text, reading one
+/// character per `step` bytes. With a step of 2, the second byte of each
+/// character must be zero, as in UTF-16LE.
+fn text_run(bytes: &[u8], step: usize) -> String {
+    bytes
+        .chunks_exact(step)
+        .take_while(|c| c[1..].iter().all(|b| *b == 0) && is_text_byte(c[0]))
+        .map(|c| char::from(c[0]))
+        .collect()
+}
+
+fn is_text_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b" ._,:;/()'&!?#+-".contains(&byte)
+}
+
+fn is_text(text: &str) -> bool {
+    let letters = text.chars().filter(char::is_ascii_alphabetic).count();
+    text.len() >= MIN_TEXT_LENGTH && letters * 2 >= text.len()
+}
+
+impl<const I: u32> Summarizable for SoundminerData<I> {
+    fn summary(&self) -> String {
+        let text = self.text();
+        match text.first() {
+            Some((_, first)) => format!("Soundminer metadata: {first}"),
+            None => "Soundminer metadata, no readable text".to_string(),
+        }
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        let mut items = Vec::new();
+        if let (Some(version), Some(length)) = (self.version(), self.length()) {
+            items.push(("version".to_string(), version.to_string()));
+            items.push(("length".to_string(), length.to_string()));
+        }
+        if let Some(blocks) = self.blocks() {
+            items.push((
+                "blocks".to_string(),
+                format!("{blocks} blocks of {} bytes", Self::BLOCK_SIZE),
+            ));
+        }
+        items.push(("raw".to_string(), format!("{} bytes", self.raw.len())));
+        for (offset, text) in self.text() {
+            items.push((format!("text at {offset}"), text));
+        }
+        Box::new(items.into_iter())
+    }
+}
+
+/// `SMED` Soundminer metadata. [UNKNOWN](https://soundminer.com/)
+pub type Smed = SoundminerData<{ fourcc(b"SMED") }>;
+/// `SMRD` Soundminer metadata. [UNKNOWN](https://soundminer.com/)
+pub type Smrd = SoundminerData<{ fourcc(b"SMRD") }>;
+
+/// `SMED` Soundminer metadata. [UNKNOWN](https://soundminer.com/)
+pub type SmedChunk = KnownChunk<Smed>;
+/// `SMRD` Soundminer metadata. [UNKNOWN](https://soundminer.com/)
+pub type SmrdChunk = KnownChunk<Smrd>;
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use binrw::BinRead;
+
+    use super::*;
+    use crate::testing::{hex_to_cursor, Arbitrary, Rng};
+    use crate::{SizedChunkEnum, WaveFile};
+
+    #[test]
+    fn parse_smrd() {
+        let mut buff =
+            hex_to_cursor("534D5244 14000000 0000000F 4E616D65 3A204D61 696E2052 6F61640A");
+        let chunk = SmrdChunk::read(&mut buff).unwrap();
+        dbg!(&chunk);
+        assert_eq!(chunk.size, 20);
+        assert_eq!(chunk.data.version(), Some(0));
+        assert_eq!(chunk.data.length(), Some(15));
+        assert_eq!(chunk.data.blocks(), Some(2));
+        assert_eq!(chunk.data.text(), [(4, "Name: Main Road".to_string())]);
+        assert_eq!(chunk.data.summary(), "Soundminer metadata: Name: Main Road");
+    }
+
+    #[test]
+    fn parse_smed_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let smed = wave
+            .iter_chunks()
+            .find_map(|c| match c {
+                Ok(SizedChunkEnum::Smed(smed)) => Some(smed),
+                _ => None,
+            })
+            .unwrap();
+        let items: Vec<_> = smed.data.items().collect();
+        dbg!(&items);
+        assert_eq!(smed.size, 8812);
+        assert_eq!(smed.data.version(), Some(0));
+        assert_eq!(smed.data.length(), Some(8806));
+        assert_eq!(smed.data.blocks(), Some(1101));
+        assert!(smed.data.text().is_empty());
+        assert_eq!(
+            items[2],
+            ("blocks".to_string(), "1101 blocks of 8 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn short_data() {
+        let smed = Smed { raw: vec![0, 1] };
+        assert_eq!(smed.version(), Some(1));
+        assert_eq!(smed.length(), None);
+        assert_eq!(smed.blocks(), None);
+        assert_eq!(smed.items().count(), 1);
+    }
+
+    impl<const I: u32> Arbitrary for SoundminerData<I> {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            SoundminerData { raw: rng.bytes(64) }
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        smed_roundtrip: Smed,
+    }
+}
@@ -3,13 +3,17 @@
 //! NOTE: Implemented from the spec only, because I couldn't find any files actually
 //! containing this chunk.
 
-use core::fmt::Debug;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
 
 use binrw::{binrw, helpers};
 use itertools::Itertools;
 
-use crate::chunk::data::DataChunk;
-use crate::{ChunkID, FourCC, KnownChunk, KnownChunkID, Summarizable};
+use crate::chunk::data::{Data, DataChunk};
+use crate::{ChunkID, DisplayOptions, FourCC, KnownChunk, KnownChunkID, Summarizable};
 
 #[binrw]
 #[br(little)]
@@ -30,6 +34,98 @@ pub struct ListWavl {
 impl ListWavl {
     /// Chunk id constant: `wavl`
     pub const LIST_TYPE: FourCC = FourCC(*b"wavl");
+
+    /// The `data` and `slnt` subchunks in playback order. Unknown subchunks
+    /// are skipped.
+    pub fn segments(&self) -> Vec<WavlSegment> {
+        self.chunks
+            .iter()
+            .filter_map(|c| match c {
+                WavlEnum::Data(data) => Some(WavlSegment::Data {
+                    offset: data.offset,
+                    size: data.size,
+                }),
+                WavlEnum::Slnt(slnt) => Some(WavlSegment::Silence {
+                    offset: slnt.offset,
+                    samples: slnt.data.samples,
+                }),
+                WavlEnum::Unknown { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Total bytes of audio in all `data` subchunks.
+    pub fn data_size(&self) -> u64 {
+        self.segments()
+            .iter()
+            .map(|s| match s {
+                WavlSegment::Data { size, .. } => u64::from(*size),
+                WavlSegment::Silence { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Total samples of silence in all `slnt` subchunks.
+    pub fn silence_samples(&self) -> u64 {
+        self.segments()
+            .iter()
+            .map(|s| match s {
+                WavlSegment::Data { .. } => 0,
+                WavlSegment::Silence { samples, .. } => u64::from(*samples),
+            })
+            .sum()
+    }
+
+    /// Total length in samples of audio plus silence.
+    ///
+    /// `block_align` comes from the `fmt ` chunk, returns `None` if it is 0.
+    pub fn sample_count(&self, block_align: u16) -> Option<u64> {
+        let audio = self.data_size().checked_div(block_align.into())?;
+        Some(audio + self.silence_samples())
+    }
+}
+
+/// A `data` or `slnt` subchunk of a [`ListWavl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WavlSegment {
+    /// Audio samples from a `data` subchunk.
+    Data {
+        /// Byte offset of the subchunk in the data stream, if known.
+        offset: Option<u64>,
+        /// Size of the audio in bytes.
+        size: u32,
+    },
+    /// Silence from a `slnt` subchunk.
+    Silence {
+        /// Byte offset of the subchunk in the data stream, if known.
+        offset: Option<u64>,
+        /// Length of the silence in samples.
+        samples: u32,
+    },
+}
+
+impl ChunkID for WavlSegment {
+    fn id(&self) -> FourCC {
+        match self {
+            WavlSegment::Data { .. } => Data::ID,
+            WavlSegment::Silence { .. } => Slnt::ID,
+        }
+    }
+}
+
+impl Display for WavlSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (offset, length) = match self {
+            WavlSegment::Data { offset, size } => (offset, format!("{size} bytes audio")),
+            WavlSegment::Silence { offset, samples } => {
+                (offset, format!("{samples} samples silence"))
+            }
+        };
+        match offset {
+            Some(offset) => write!(f, "{length} at {offset}"),
+            None => write!(f, "{length}"),
+        }
+    }
 }
 
 impl KnownChunkID for ListWavl {
@@ -38,14 +134,23 @@ impl KnownChunkID for ListWavl {
 
 impl Summarizable for ListWavl {
     fn summary(&self) -> String {
-        self.chunks
-            .iter()
-            .into_grouping_map_by(|c| c.id())
-            .fold(0, |acc, _key, _value| acc + 1)
+        self.summary_with(&DisplayOptions::default())
+    }
+
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        let summary = self
+            .chunks
             .iter()
-            .map(|(g, c)| format!("{}({})", g, c))
+            .map(|c| c.id().to_string())
             .sorted_unstable()
-            .join(", ")
+            .dedup_with_count()
+            .map(|(c, g)| format!("{}({})", g, c))
+            .chain([format!(
+                "{} silence",
+                options.samples(self.silence_samples())
+            )])
+            .join(", ");
+        options.text(&summary)
     }
 
     fn name(&self) -> String {
@@ -54,9 +159,19 @@ impl Summarizable for ListWavl {
 
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         Box::new(
-            self.chunks
-                .iter()
-                .map(|c| (c.id().to_string(), c.summary())),
+            self.segments()
+                .into_iter()
+                .map(|s| (s.id().to_string(), s.to_string()))
+                .chain([
+                    (
+                        "data size".to_string(),
+                        format!("{} bytes", self.data_size()),
+                    ),
+                    (
+                        "silence".to_string(),
+                        format!("{} samples", self.silence_samples()),
+                    ),
+                ]),
         )
     }
 }
@@ -85,6 +200,10 @@ impl Summarizable for Slnt {
     fn summary(&self) -> String {
         format!("{} samples", self.samples)
     }
+
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        options.text(&options.samples(self.samples.into()))
+    }
 }
 
 /// ‘slnt’ represents silence, not necessarily a repeated zero volume.
@@ -102,7 +221,7 @@ pub enum WavlEnum {
         id: FourCC,
         size: u32,
         #[brw(align_after = 2)]
-        #[br(count = size, pad_size_to= size.to_owned())]
+        #[br(parse_with = crate::read_bytes, args(size.into()))]
         raw: Vec<u8>,
     },
 }
@@ -125,16 +244,25 @@ impl Summarizable for WavlEnum {
             WavlEnum::Unknown { .. } => "...".to_string(),
         }
     }
+
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        match self {
+            WavlEnum::Data(e) => e.summary_with(options),
+            WavlEnum::Slnt(e) => e.summary_with(options),
+            WavlEnum::Unknown { .. } => "...".to_string(),
+        }
+    }
 }
 
 #[allow(clippy::dbg_macro)]
 #[cfg(test)]
 mod test {
+    use crate::testing::{Arbitrary, Rng};
     use binrw::{BinRead, BinWrite};
     use hexdump::hexdump;
 
     use super::*;
-    use crate::chunk::data::Data;
+    use crate::testing::hex_to_cursor;
 
     // couldn't find slnt usage in file collection, so just doing a roundtrip test
     #[test]
@@ -144,6 +272,9 @@ mod test {
             size: 4,
             data: Slnt { samples: 12345 },
             extra_bytes: Vec::new(),
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         println!("{slnt:?}");
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
@@ -165,6 +296,9 @@ mod test {
                 chunks: vec![WavlEnum::Slnt(slnt)],
             },
             extra_bytes: Vec::new(),
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
         wavl.write(&mut buff).unwrap();
@@ -184,6 +318,9 @@ mod test {
                 data: [8_u8; 0].to_vec(),
             },
             extra_bytes: Vec::new(),
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
         data.write(&mut buff).unwrap();
@@ -203,6 +340,9 @@ mod test {
                 chunks: vec![WavlEnum::Data(data)],
             },
             extra_bytes: Vec::new(),
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         let mut buff = std::io::Cursor::new(Vec::<u8>::new());
         wavl.write(&mut buff).unwrap();
@@ -211,4 +351,64 @@ mod test {
         let after = ListWavlChunk::read(&mut buff).unwrap();
         assert_eq!(after, wavl);
     }
+
+    #[test]
+    fn wavl_segments() {
+        // LIST-wavl: slnt 100 samples, data 4 bytes, slnt 50 samples
+        let mut buff = hex_to_cursor(
+            "4C495354 28000000 7761766C 736C6E74 04000000 64000000 64617461 04000000 01020304 736C6E74 04000000 32000000",
+        );
+        let wavl = ListWavlChunk::read(&mut buff).unwrap();
+        dbg!(&wavl);
+        let segments = wavl.data.segments();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(
+            segments[1],
+            WavlSegment::Data {
+                offset: Some(24),
+                size: 4
+            }
+        );
+        assert_eq!(wavl.data.data_size(), 4);
+        assert_eq!(wavl.data.silence_samples(), 150);
+        assert_eq!(wavl.data.sample_count(2), Some(152));
+        assert_eq!(wavl.data.sample_count(0), None);
+        assert_eq!(wavl.data.summary(), "data(1), slnt(2), 150 samples silence");
+        let items: Vec<_> = wavl.data.items().collect();
+        assert_eq!(items[0].1, "100 samples silence at 12");
+        assert_eq!(items[3].1, "4 bytes");
+    }
+
+    impl Arbitrary for ListWavl {
+        fn arbitrary(rng: &mut Rng) -> Self {
+            let chunks = (0..rng.below(6))
+                .map(|_| match rng.below(3) {
+                    0 => WavlEnum::Data(DataChunk::new(Data { data: Vec::new() }).unwrap()),
+                    1 => WavlEnum::Slnt(
+                        SlntChunk::new(Slnt {
+                            samples: rng.next_u32(),
+                        })
+                        .unwrap(),
+                    ),
+                    _ => {
+                        let raw = rng.bytes(40);
+                        WavlEnum::Unknown {
+                            id: FourCC(*b"zzzz"),
+                            size: raw.len() as u32,
+                            raw,
+                        }
+                    }
+                })
+                .collect();
+            // round trip once, so sub-chunks have the offsets they get when read
+            crate::testing::roundtrip(&ListWavl {
+                list_type: ListWavl::LIST_TYPE,
+                chunks,
+            })
+        }
+    }
+
+    crate::testing::roundtrip_tests! {
+        list_wavl_roundtrip: ListWavl,
+    }
 }
@@ -0,0 +1,332 @@
+//! Decode text according to a code page, as specified by a `CSET` chunk.
+//!
+//! Most WAVE text fields are specified as ASCII, with an optional `CSET`
+//! chunk declaring the code page for anything else. In practice `CSET` is very
+//! rare, and most applications write UTF-8. Without a code page, text is
+//! decoded as UTF-8 when valid and as ISO 8859-1 (Latin-1) otherwise.
+//!
+//! Supported code pages: 1250, 1251, 1252 (Windows), 28591 and 1004
+//! (ISO 8859-1) and 65001 (UTF-8). Others fall back to the UTF-8/Latin-1
+//! heuristic.
+//!
+//! ```
+//! use wavrw::codepage::decode;
+//!
+//! // "Größe" encoded as Windows-1252
+//! let bytes = [0x47, 0x72, 0xF6, 0xDF, 0x65];
+//! assert_eq!(decode(&bytes, Some(1252)), "Größe");
+//! // without a code page, non UTF-8 text is decoded as Latin-1
+//! assert_eq!(decode(&bytes, None), "Größe");
+//! assert_eq!(decode("Größe".as_bytes(), None), "Größe");
+//! ```
+//!
+//! Archives collected from many sources mix encodings. [`detect()`] guesses
+//! whether text is UTF-8, Windows-1252 or Shift-JIS, with a confidence, as a
+//! hint of the code page to use:
+//!
+//! ```
+//! use wavrw::codepage::{detect, SHIFT_JIS, WINDOWS_1252};
+//!
+//! // "テスト" (test) encoded as Shift-JIS
+//! let guess = detect(&[0x83, 0x65, 0x83, 0x58, 0x83, 0x67]).unwrap();
+//! assert_eq!(guess.code_page, SHIFT_JIS);
+//! assert_eq!(guess.to_string(), "Shift-JIS (code page 932), 95% confidence");
+//! assert_eq!(detect(&[0x47, 0x72, 0xF6, 0xDF, 0x65]).unwrap().code_page, WINDOWS_1252);
+//! ```
+
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use tracing::warn;
+
+/// Code page 1250: Windows Central European
+pub const WINDOWS_1250: u16 = 1250;
+/// Code page 1251: Windows Cyrillic
+pub const WINDOWS_1251: u16 = 1251;
+/// Code page 1252: Windows Western European
+pub const WINDOWS_1252: u16 = 1252;
+/// Code page 1004: Windows Latin-1, the `CSET` default
+pub const LATIN_1_1004: u16 = 1004;
+/// Code page 28591: ISO 8859-1 Latin-1
+pub const ISO_8859_1: u16 = 28591;
+/// Code page 65001: UTF-8
+pub const UTF_8: u16 = 65001;
+/// Code page 932: Shift-JIS, Japanese. Only detected, not decoded.
+pub const SHIFT_JIS: u16 = 932;
+
+/// Returns true if `code_page` is decoded by [`decode()`].
+pub fn is_supported(code_page: u16) -> bool {
+    matches!(
+        code_page,
+        WINDOWS_1250 | WINDOWS_1251 | WINDOWS_1252 | LATIN_1_1004 | ISO_8859_1 | UTF_8
+    )
+}
+
+/// Decode `bytes` as text in `code_page`.
+///
+/// With `None`, 0 (the `CSET` default) or an unsupported code page, valid
+/// UTF-8 is decoded as UTF-8 and anything else as Latin-1. Trailing NULL
+/// bytes are removed.
+pub fn decode(bytes: &[u8], code_page: Option<u16>) -> String {
+    let bytes = trim_nulls(bytes);
+    match code_page {
+        Some(WINDOWS_1250) => decode_table(bytes, &CP1250),
+        Some(WINDOWS_1251) => decode_table(bytes, &CP1251),
+        Some(WINDOWS_1252) => decode_table(bytes, &CP1252),
+        Some(LATIN_1_1004 | ISO_8859_1) => decode_latin1(bytes),
+        Some(UTF_8) => String::from_utf8_lossy(bytes).into_owned(),
+        Some(0) | None => decode_heuristic(bytes),
+        Some(code_page) => {
+            warn!("unsupported code page {code_page}, decoding as UTF-8 or Latin-1");
+            decode_heuristic(bytes)
+        }
+    }
+}
+
+/// A guess of the encoding of text, see [`detect()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Detection {
+    /// Code page of the most likely encoding: [`UTF_8`], [`WINDOWS_1252`] or
+    /// [`SHIFT_JIS`].
+    pub code_page: u16,
+    /// Confidence in the guess, in percent.
+    pub confidence: u8,
+}
+
+impl Display for Detection {
+    /// Ex: `Windows-1252 (code page 1252), 90% confidence`
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self.code_page {
+            UTF_8 => "UTF-8",
+            WINDOWS_1252 => "Windows-1252",
+            SHIFT_JIS => "Shift-JIS",
+            _ => "unknown",
+        };
+        write!(
+            f,
+            "{name} (code page {}), {}% confidence",
+            self.code_page, self.confidence
+        )
+    }
+}
+
+/// Guess whether `bytes` are UTF-8, Windows-1252 or Shift-JIS text.
+///
+/// Returns `None` for ASCII text, which is the same in all three. Valid
+/// UTF-8 is reported as UTF-8. Otherwise bytes which can't appear in an
+/// encoding rule it out. When both Windows-1252 and Shift-JIS are possible,
+/// two byte Shift-JIS characters which are kana or kanji point to Japanese
+/// text, accented letters between ASCII letters to Western text. Short texts
+/// can be misjudged, the confidence is a hint.
+pub fn detect(bytes: &[u8]) -> Option<Detection> {
+    let bytes = trim_nulls(bytes);
+    if bytes.is_ascii() {
+        return None;
+    }
+    if core::str::from_utf8(bytes).is_ok() {
+        return Some(Detection {
+            code_page: UTF_8,
+            confidence: 100,
+        });
+    }
+    // undefined in Windows-1252
+    let cp1252 = !bytes
+        .iter()
+        .any(|b| matches!(b, 0x81 | 0x8D | 0x8F | 0x90 | 0x9D));
+    let (code_page, confidence) = match (cp1252, shift_jis_pairs(bytes)) {
+        (true, None) => (WINDOWS_1252, 90),
+        (false, Some(_)) => (SHIFT_JIS, 90),
+        // an other code page, decoded as Latin-1
+        (false, None) => (WINDOWS_1252, 20),
+        (true, Some((pairs, japanese))) => {
+            let percent = (japanese * 100).checked_div(pairs).unwrap_or(0);
+            if percent >= 50 {
+                (SHIFT_JIS, 50 + percent * 45 / 100)
+            } else {
+                (WINDOWS_1252, 50 + (100 - percent) * 45 / 100)
+            }
+        }
+    };
+    Some(Detection {
+        code_page,
+        confidence: u8::try_from(confidence).unwrap_or(100),
+    })
+}
+
+/// Number of two byte characters if `bytes` are valid Shift-JIS, and how
+/// many of them look Japanese.
+fn shift_jis_pairs(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut pairs = 0;
+    let mut japanese = 0;
+    let mut iter = bytes.iter();
+    while let Some(lead) = iter.next() {
+        match lead {
+            // ASCII and half width katakana
+            0x00..=0x7F | 0xA1..=0xDF => {}
+            0x81..=0x9F | 0xE0..=0xFC => {
+                let trail = iter.next()?;
+                if !matches!(trail, 0x40..=0x7E | 0x80..=0xFC) {
+                    return None;
+                }
+                pairs += 1;
+                // hiragana and katakana, or kanji ending in a high byte.
+                // In Windows-1252 these are rare punctuation, or two
+                // accented letters in a row
+                if matches!(lead, 0x82 | 0x83)
+                    || (matches!(lead, 0x81..=0x9F | 0xE0..=0xEF) && *trail >= 0x80)
+                {
+                    japanese += 1;
+                }
+            }
+            0x80 | 0xA0 | 0xFD..=0xFF => return None,
+        }
+    }
+    Some((pairs, japanese))
+}
+
+fn trim_nulls(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    &bytes[..end]
+}
+
+fn decode_heuristic(bytes: &[u8]) -> String {
+    match core::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => decode_latin1(bytes),
+    }
+}
+
+fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| char::from(*b)).collect()
+}
+
+fn decode_table(bytes: &[u8], table: &[char; 128]) -> String {
+    bytes
+        .iter()
+        .map(|b| match b.checked_sub(0x80) {
+            Some(i) => table[usize::from(i)],
+            None => char::from(*b),
+        })
+        .collect()
+}
+
+// Tables generated from the Python codecs module. Bytes undefined in a code
+// page map to the same code point, as in Latin-1.
+
+/// Windows-1250, Central European. Bytes 0x80 to 0xFF.
+const CP1250: [char; 128] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0083}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{0088}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{015A}', '\u{0164}', '\u{017D}', '\u{0179}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{0098}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{015B}', '\u{0165}', '\u{017E}', '\u{017A}',
+    '\u{00A0}', '\u{02C7}', '\u{02D8}', '\u{0141}', '\u{00A4}', '\u{0104}', '\u{00A6}', '\u{00A7}',
+    '\u{00A8}', '\u{00A9}', '\u{015E}', '\u{00AB}', '\u{00AC}', '\u{00AD}', '\u{00AE}', '\u{017B}',
+    '\u{00B0}', '\u{00B1}', '\u{02DB}', '\u{0142}', '\u{00B4}', '\u{00B5}', '\u{00B6}', '\u{00B7}',
+    '\u{00B8}', '\u{0105}', '\u{015F}', '\u{00BB}', '\u{013D}', '\u{02DD}', '\u{013E}', '\u{017C}',
+    '\u{0154}', '\u{00C1}', '\u{00C2}', '\u{0102}', '\u{00C4}', '\u{0139}', '\u{0106}', '\u{00C7}',
+    '\u{010C}', '\u{00C9}', '\u{0118}', '\u{00CB}', '\u{011A}', '\u{00CD}', '\u{00CE}', '\u{010E}',
+    '\u{0110}', '\u{0143}', '\u{0147}', '\u{00D3}', '\u{00D4}', '\u{0150}', '\u{00D6}', '\u{00D7}',
+    '\u{0158}', '\u{016E}', '\u{00DA}', '\u{0170}', '\u{00DC}', '\u{00DD}', '\u{0162}', '\u{00DF}',
+    '\u{0155}', '\u{00E1}', '\u{00E2}', '\u{0103}', '\u{00E4}', '\u{013A}', '\u{0107}', '\u{00E7}',
+    '\u{010D}', '\u{00E9}', '\u{0119}', '\u{00EB}', '\u{011B}', '\u{00ED}', '\u{00EE}', '\u{010F}',
+    '\u{0111}', '\u{0144}', '\u{0148}', '\u{00F3}', '\u{00F4}', '\u{0151}', '\u{00F6}', '\u{00F7}',
+    '\u{0159}', '\u{016F}', '\u{00FA}', '\u{0171}', '\u{00FC}', '\u{00FD}', '\u{0163}', '\u{02D9}',
+];
+
+/// Windows-1251, Cyrillic. Bytes 0x80 to 0xFF.
+const CP1251: [char; 128] = [
+    '\u{0402}', '\u{0403}', '\u{201A}', '\u{0453}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{20AC}', '\u{2030}', '\u{0409}', '\u{2039}', '\u{040A}', '\u{040C}', '\u{040B}', '\u{040F}',
+    '\u{0452}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{0098}', '\u{2122}', '\u{0459}', '\u{203A}', '\u{045A}', '\u{045C}', '\u{045B}', '\u{045F}',
+    '\u{00A0}', '\u{040E}', '\u{045E}', '\u{0408}', '\u{00A4}', '\u{0490}', '\u{00A6}', '\u{00A7}',
+    '\u{0401}', '\u{00A9}', '\u{0404}', '\u{00AB}', '\u{00AC}', '\u{00AD}', '\u{00AE}', '\u{0407}',
+    '\u{00B0}', '\u{00B1}', '\u{0406}', '\u{0456}', '\u{0491}', '\u{00B5}', '\u{00B6}', '\u{00B7}',
+    '\u{0451}', '\u{2116}', '\u{0454}', '\u{00BB}', '\u{0458}', '\u{0405}', '\u{0455}', '\u{0457}',
+    '\u{0410}', '\u{0411}', '\u{0412}', '\u{0413}', '\u{0414}', '\u{0415}', '\u{0416}', '\u{0417}',
+    '\u{0418}', '\u{0419}', '\u{041A}', '\u{041B}', '\u{041C}', '\u{041D}', '\u{041E}', '\u{041F}',
+    '\u{0420}', '\u{0421}', '\u{0422}', '\u{0423}', '\u{0424}', '\u{0425}', '\u{0426}', '\u{0427}',
+    '\u{0428}', '\u{0429}', '\u{042A}', '\u{042B}', '\u{042C}', '\u{042D}', '\u{042E}', '\u{042F}',
+    '\u{0430}', '\u{0431}', '\u{0432}', '\u{0433}', '\u{0434}', '\u{0435}', '\u{0436}', '\u{0437}',
+    '\u{0438}', '\u{0439}', '\u{043A}', '\u{043B}', '\u{043C}', '\u{043D}', '\u{043E}', '\u{043F}',
+    '\u{0440}', '\u{0441}', '\u{0442}', '\u{0443}', '\u{0444}', '\u{0445}', '\u{0446}', '\u{0447}',
+    '\u{0448}', '\u{0449}', '\u{044A}', '\u{044B}', '\u{044C}', '\u{044D}', '\u{044E}', '\u{044F}',
+];
+
+/// Windows-1252, Western European. Bytes 0x80 to 0xFF.
+const CP1252: [char; 128] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+    '\u{00A0}', '\u{00A1}', '\u{00A2}', '\u{00A3}', '\u{00A4}', '\u{00A5}', '\u{00A6}', '\u{00A7}',
+    '\u{00A8}', '\u{00A9}', '\u{00AA}', '\u{00AB}', '\u{00AC}', '\u{00AD}', '\u{00AE}', '\u{00AF}',
+    '\u{00B0}', '\u{00B1}', '\u{00B2}', '\u{00B3}', '\u{00B4}', '\u{00B5}', '\u{00B6}', '\u{00B7}',
+    '\u{00B8}', '\u{00B9}', '\u{00BA}', '\u{00BB}', '\u{00BC}', '\u{00BD}', '\u{00BE}', '\u{00BF}',
+    '\u{00C0}', '\u{00C1}', '\u{00C2}', '\u{00C3}', '\u{00C4}', '\u{00C5}', '\u{00C6}', '\u{00C7}',
+    '\u{00C8}', '\u{00C9}', '\u{00CA}', '\u{00CB}', '\u{00CC}', '\u{00CD}', '\u{00CE}', '\u{00CF}',
+    '\u{00D0}', '\u{00D1}', '\u{00D2}', '\u{00D3}', '\u{00D4}', '\u{00D5}', '\u{00D6}', '\u{00D7}',
+    '\u{00D8}', '\u{00D9}', '\u{00DA}', '\u{00DB}', '\u{00DC}', '\u{00DD}', '\u{00DE}', '\u{00DF}',
+    '\u{00E0}', '\u{00E1}', '\u{00E2}', '\u{00E3}', '\u{00E4}', '\u{00E5}', '\u{00E6}', '\u{00E7}',
+    '\u{00E8}', '\u{00E9}', '\u{00EA}', '\u{00EB}', '\u{00EC}', '\u{00ED}', '\u{00EE}', '\u{00EF}',
+    '\u{00F0}', '\u{00F1}', '\u{00F2}', '\u{00F3}', '\u{00F4}', '\u{00F5}', '\u{00F6}', '\u{00F7}',
+    '\u{00F8}', '\u{00F9}', '\u{00FA}', '\u{00FB}', '\u{00FC}', '\u{00FD}', '\u{00FE}', '\u{00FF}',
+];
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_code_pages() {
+        assert_eq!(decode(&[0x80, 0x41], Some(WINDOWS_1252)), "€A");
+        assert_eq!(decode(&[0xCF, 0xF0, 0xE8], Some(WINDOWS_1251)), "При");
+        assert_eq!(decode(&[0x8A, 0xB9], Some(WINDOWS_1250)), "Šą");
+        assert_eq!(decode(&[0xE9], Some(ISO_8859_1)), "é");
+        assert_eq!(decode("é".as_bytes(), Some(UTF_8)), "é");
+    }
+
+    #[test]
+    fn decode_heuristics() {
+        assert_eq!(decode(b"abc\0\0", None), "abc");
+        assert_eq!(decode("é".as_bytes(), Some(0)), "é");
+        assert_eq!(decode(&[0xE9], None), "é");
+        // unsupported code pages use the same heuristic
+        assert_eq!(decode(&[0xE9], Some(932)), "é");
+        assert!(is_supported(1252));
+        assert!(!is_supported(932));
+    }
+
+    #[test]
+    fn detect_encodings() {
+        assert_eq!(detect(b"abc\0"), None);
+        let utf8 = detect("Größe".as_bytes()).unwrap();
+        assert_eq!((utf8.code_page, utf8.confidence), (UTF_8, 100));
+
+        // "Größe", "Düsseldorf" and "don’t" in Windows-1252, also valid
+        // Shift-JIS
+        for bytes in [&b"Gr\xF6\xDFe"[..], b"D\xFCsseldorf", b"don\x92t"] {
+            let guess = detect(bytes).unwrap();
+            dbg!(&guess);
+            assert_eq!((guess.code_page, guess.confidence), (WINDOWS_1252, 95));
+        }
+        // "café au lait", Shift-JIS has no character for 0xE9 0x20
+        let guess = detect(b"caf\xE9 au lait").unwrap();
+        assert_eq!((guess.code_page, guess.confidence), (WINDOWS_1252, 90));
+        // "音声ファイル" (audio file) in Shift-JIS
+        let guess = detect(&[
+            0x89, 0xB9, 0x90, 0xBA, 0x83, 0x74, 0x83, 0x40, 0x83, 0x43, 0x83, 0x8B,
+        ])
+        .unwrap();
+        assert_eq!((guess.code_page, guess.confidence), (SHIFT_JIS, 90));
+        // "Ö" is a half width katakana in Shift-JIS
+        assert_eq!(detect(&[0xD6]).unwrap().code_page, WINDOWS_1252);
+        // a lead byte without a trail byte
+        let guess = detect(&[0x81]).unwrap();
+        assert_eq!((guess.code_page, guess.confidence), (WINDOWS_1252, 20));
+        assert_eq!(
+            guess.to_string(),
+            "Windows-1252 (code page 1252), 20% confidence"
+        );
+    }
+}
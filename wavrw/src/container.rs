@@ -0,0 +1,218 @@
+//! Open WAV and AIFF files without knowing the container type up front.
+//!
+//! [`open()`] sniffs the first bytes of a stream and returns an
+//! [`AudioFile`], which iterates over the chunks of any supported container
+//! as [`SizedChunk`] trait objects.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::Container;
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut audio = wavrw::open(file)?;
+//! assert_eq!(audio.container(), Container::Riff);
+//! for chunk in audio.iter_chunks() {
+//!     let chunk = chunk?;
+//!     println!("{:4} {}", chunk.name(), chunk.summary());
+//! }
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, Read, Seek, SeekFrom};
+
+use crate::aiff::{AiffFile, FormChunk};
+use crate::{FourCC, SizedChunk, WaveFile, WaveFileError};
+
+/// File container types recognized by [`Container::sniff()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Container {
+    /// `RIFF` `WAVE`, 32 bit sizes.
+    Riff,
+    /// `RF64` `WAVE`, 64 bit sizes in a `ds64` chunk. [RF64_2009](https://tech.ebu.ch/docs/tech/tech3306-2009.pdf)
+    Rf64,
+    /// `BW64` `WAVE`, the ITU-R BS.2088 variant of RF64.
+    Bw64,
+    /// Sony Wave64, GUID chunk ids and 64 bit sizes. Recognized, but not
+    /// supported by [`open()`].
+    Wave64,
+    /// `FORM` `AIFF`, big-endian.
+    Aiff,
+    /// `FORM` `AIFC`, big-endian and possibly compressed.
+    Aifc,
+}
+
+impl Container {
+    /// The first 16 bytes of a Wave64 file, the GUID of its `riff` chunk.
+    pub const WAVE64_GUID: [u8; 16] = [
+        0x72, 0x69, 0x66, 0x66, 0x2E, 0x91, 0xCF, 0x11, 0xA5, 0xD6, 0x28, 0xDB, 0x04, 0xC1, 0x00,
+        0x00,
+    ];
+
+    /// Container type of a file starting with `header`, which should hold
+    /// at least the first 16 bytes.
+    ///
+    /// ```
+    /// use wavrw::Container;
+    ///
+    /// assert_eq!(Container::sniff(b"RIFF\x24\x00\x00\x00WAVE"), Some(Container::Riff));
+    /// assert_eq!(Container::sniff(b"FORM\x00\x00\x00\x1EAIFF"), Some(Container::Aiff));
+    /// assert_eq!(Container::sniff(b"RIFF\x24\x00\x00\x00AVI "), None);
+    /// ```
+    pub fn sniff(header: &[u8]) -> Option<Container> {
+        if header.starts_with(&Self::WAVE64_GUID) {
+            return Some(Container::Wave64);
+        }
+        let (id, form_type) = (header.get(..4)?, header.get(8..12)?);
+        match (id, form_type) {
+            (b"RIFF", b"WAVE") => Some(Container::Riff),
+            (b"RF64", b"WAVE") => Some(Container::Rf64),
+            (b"BW64", b"WAVE") => Some(Container::Bw64),
+            (b"FORM", b"AIFF") => Some(Container::Aiff),
+            (b"FORM", b"AIFC") => Some(Container::Aifc),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Container {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Container::Riff => "RIFF",
+            Container::Rf64 => "RF64",
+            Container::Bw64 => "BW64",
+            Container::Wave64 => "Wave64",
+            Container::Aiff => "AIFF",
+            Container::Aifc => "AIFC",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A WAV or AIFF file opened by [`open()`].
+#[derive(Debug)]
+pub enum AudioFile<R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    /// `RIFF`, `RF64` or `BW64` WAVE data.
+    Wave(WaveFile<R>),
+    /// `AIFF` or `AIFC` data.
+    Aiff(AiffFile<R>),
+}
+
+impl<R> AudioFile<R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    /// The container type, from the file header.
+    pub fn container(&self) -> Container {
+        match self {
+            AudioFile::Wave(wave) => match &wave.riff().id.0 {
+                b"RF64" => Container::Rf64,
+                b"BW64" => Container::Bw64,
+                _ => Container::Riff,
+            },
+            AudioFile::Aiff(aiff) if aiff.is_aifc() => Container::Aifc,
+            AudioFile::Aiff(_) => Container::Aiff,
+        }
+    }
+
+    /// Iterate over all chunks, see [`WaveFile::iter_chunks()`] and
+    /// [`AiffFile::iter_chunks()`].
+    pub fn iter_chunks(
+        &mut self,
+    ) -> Box<dyn Iterator<Item = Result<Box<dyn SizedChunk>, WaveFileError>> + '_> {
+        match self {
+            AudioFile::Wave(wave) => Box::new(
+                wave.iter_chunks()
+                    .map(|chunk| chunk.map(|c| Box::new(c) as Box<dyn SizedChunk>)),
+            ),
+            AudioFile::Aiff(aiff) => Box::new(
+                aiff.iter_chunks()
+                    .map(|chunk| chunk.map(|c| Box::new(c) as Box<dyn SizedChunk>)),
+            ),
+        }
+    }
+}
+
+/// Open a WAV or AIFF file, choosing the parser from the first bytes of
+/// `reader`. See the [module documentation](self).
+///
+/// Returns [`WaveFileError::UnknownFourCC`] for Wave64 and unrecognized
+/// data.
+pub fn open<R>(mut reader: R) -> Result<AudioFile<R>, WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    let start = reader.stream_position()?;
+    let mut header = Vec::with_capacity(16);
+    (&mut reader).take(16).read_to_end(&mut header)?;
+    reader.seek(SeekFrom::Start(start))?;
+
+    let id = FourCC(
+        header
+            .get(..4)
+            .and_then(|id| id.try_into().ok())
+            .unwrap_or_default(),
+    );
+    match Container::sniff(&header) {
+        Some(Container::Riff | Container::Rf64 | Container::Bw64) => {
+            Ok(AudioFile::Wave(WaveFile::from_reader(reader)?))
+        }
+        Some(Container::Aiff | Container::Aifc) => {
+            Ok(AudioFile::Aiff(AiffFile::from_reader(reader)?))
+        }
+        Some(Container::Wave64) => Err(WaveFileError::UnknownFourCC {
+            found: id,
+            message: "Wave64 files are not supported".to_string(),
+        }),
+        None if id == FormChunk::ID => Err(WaveFileError::UnknownFourCC {
+            found: id,
+            message: "unsupported IFF form type, expected AIFF or AIFC".to_string(),
+        }),
+        None => Err(WaveFileError::UnknownFourCC {
+            found: id,
+            message: format!("not a WAV or AIFF file, found: {id}"),
+        }),
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::hex_to_cursor;
+
+    #[test]
+    fn open_sniffs_container() {
+        let riff = "52494646 0C000000 57415645 66616374 04000000 01000000";
+        let mut audio = open(hex_to_cursor(riff)).unwrap();
+        assert_eq!(audio.container(), Container::Riff);
+        let ids: Vec<_> = audio.iter_chunks().map(|c| c.unwrap().id()).collect();
+        assert_eq!(ids, [FourCC(*b"fact")]);
+
+        let rf64 = "52463634 FFFFFFFF 57415645 66616374 04000000 01000000";
+        assert_eq!(
+            open(hex_to_cursor(rf64)).unwrap().container(),
+            Container::Rf64
+        );
+
+        let aifc = "464F524D 00000010 41494643 46564552 00000004 A2805140";
+        let mut audio = open(hex_to_cursor(aifc)).unwrap();
+        dbg!(&audio);
+        assert_eq!(audio.container(), Container::Aifc);
+        let ids: Vec<_> = audio.iter_chunks().map(|c| c.unwrap().id()).collect();
+        assert_eq!(ids, [FourCC(*b"FVER")]);
+
+        let wave64 = "72696666 2E91CF11 A5D628DB 04C10000 28000000 00000000";
+        let Err(WaveFileError::UnknownFourCC { message, .. }) = open(hex_to_cursor(wave64)) else {
+            unreachable!("Wave64 should not open")
+        };
+        assert_eq!(message, "Wave64 files are not supported");
+
+        assert!(open(hex_to_cursor("52494646 04000000 41564920")).is_err());
+        assert!(open(hex_to_cursor("5249")).is_err());
+    }
+}
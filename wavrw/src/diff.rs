@@ -0,0 +1,315 @@
+//! Field level differences between chunks, for editors and comparisons.
+//!
+//! [`diff()`] compares the items of two chunks, see
+//! [`Summarizable::items()`], and returns a [`FieldChange`] for each key
+//! with a different value. [`apply_changes()`] sets the new values in a
+//! chunk: changes from one pair of files can be merged into a third.
+//!
+//! Values can be set for the `bext` fields a template can set, see
+//! [`BEXT_FIELDS`](crate::template::BEXT_FIELDS), `LIST-INFO` subchunks and
+//! `iXML` elements, see [`Ixml::item_path()`].
+//!
+//! ```
+//! use wavrw::chunk::info::{ListInfo, ListInfoChunk};
+//! use wavrw::diff::{apply_changes, diff};
+//! use wavrw::{FourCC, SizedChunkEnum, Summarizable};
+//!
+//! let chunk = |name: &str| -> Result<SizedChunkEnum, wavrw::WaveFileError> {
+//!     let info = ListInfo::builder().text(FourCC(*b"INAM"), name).build()?;
+//!     Ok(SizedChunkEnum::Info(ListInfoChunk::new(info)?))
+//! };
+//! let changes = diff(&chunk("Rain")?, &chunk("Rain on a tin roof")?);
+//! assert_eq!(changes[0].to_string(), r#"INAM: "Rain" -> "Rain on a tin roof""#);
+//!
+//! let merged = apply_changes(&chunk("Rain")?, &changes)?;
+//! assert!(diff(&merged, &chunk("Rain on a tin roof")?).is_empty());
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+//!
+//! [`Ixml::item_path()`]: crate::chunk::ixml::Ixml::item_path
+
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::BextChunk;
+#[cfg(feature = "info")]
+use crate::chunk::info::ListInfoChunk;
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::{Ixml, IxmlChunk};
+#[cfg(feature = "broadcast")]
+use crate::template::set_bext_field;
+#[cfg(feature = "info")]
+use crate::FourCC;
+use crate::{SizedChunkEnum, Summarizable, WaveFileError};
+
+/// An item with a different value in two versions of a chunk, see
+/// [`diff()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldChange {
+    /// Item key. Ex: `originator` or `user.CATID`
+    pub key: String,
+    /// Value in the old chunk, `None` if the item was added.
+    pub old: Option<String>,
+    /// Value in the new chunk, `None` if the item was removed.
+    pub new: Option<String>,
+}
+
+impl Display for FieldChange {
+    /// Ex: `originator: "Archive" -> "Sound Archive"`
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, "{}: {old:?} -> {new:?}", self.key),
+            (None, Some(new)) => write!(f, "{}: added {new:?}", self.key),
+            (Some(old), None) => write!(f, "{}: removed {old:?}", self.key),
+            (None, None) => write!(f, "{}: unchanged", self.key),
+        }
+    }
+}
+
+/// The items which differ between `old` and `new`.
+///
+/// The nth item with a key in `old` is compared to the nth item with the
+/// same key in `new`. Changes are listed in the order of `old`, followed by
+/// added items in the order of `new`. Items describing other items are left
+/// out: the `raw_bytes` document length of `iXML`, which changes with every
+/// edit, and guessed text encodings, see
+/// [`codepage::detect()`](crate::codepage::detect).
+pub fn diff(old: &SizedChunkEnum, new: &SizedChunkEnum) -> Vec<FieldChange> {
+    let fields = |chunk: &SizedChunkEnum| -> Vec<(String, String)> {
+        chunk
+            .items()
+            .filter(|(key, _)| key != "raw_bytes" && !key.ends_with(".encoding"))
+            .collect()
+    };
+    let old_items = fields(old);
+    let new_items = fields(new);
+    // index of the item among the earlier items with the same key
+    let nth = |items: &[(String, String)], index: usize| {
+        items[..index]
+            .iter()
+            .filter(|(key, _)| *key == items[index].0)
+            .count()
+    };
+    let find = |items: &[(String, String)], key: &str, n: usize| {
+        items
+            .iter()
+            .filter(|(k, _)| k == key)
+            .nth(n)
+            .map(|(_, value)| value.clone())
+    };
+
+    let mut changes = Vec::new();
+    for (index, (key, value)) in old_items.iter().enumerate() {
+        let new_value = find(&new_items, key, nth(&old_items, index));
+        if new_value.as_ref() != Some(value) {
+            changes.push(FieldChange {
+                key: key.clone(),
+                old: Some(value.clone()),
+                new: new_value,
+            });
+        }
+    }
+    for (index, (key, value)) in new_items.iter().enumerate() {
+        if find(&old_items, key, nth(&new_items, index)).is_none() {
+            changes.push(FieldChange {
+                key: key.clone(),
+                old: None,
+                new: Some(value.clone()),
+            });
+        }
+    }
+    changes
+}
+
+/// A copy of `chunk` with the new values of `changes`.
+///
+/// Changes whose new value is already set are skipped. Returns an error if
+/// an item of `chunk` has neither the old nor the new value of its change,
+/// a conflict, or if an item can't be set.
+pub fn apply_changes(
+    chunk: &SizedChunkEnum,
+    changes: &[FieldChange],
+) -> Result<SizedChunkEnum, WaveFileError> {
+    let items: Vec<(String, String)> = chunk.items().collect();
+    let mut pending = Vec::new();
+    for change in changes {
+        let current = items
+            .iter()
+            .find(|(key, _)| *key == change.key)
+            .map(|(_, value)| value);
+        if current == change.new.as_ref() {
+            continue;
+        }
+        if current != change.old.as_ref() {
+            return Err(WaveFileError::parse(
+                None,
+                format!(
+                    "{} {}: conflict, expected {:?}, found {:?}",
+                    chunk.name(),
+                    change.key,
+                    change.old,
+                    current
+                ),
+            ));
+        }
+        pending.push(change);
+    }
+    if pending.is_empty() {
+        return Ok(chunk.clone());
+    }
+
+    #[cfg(feature = "broadcast")]
+    if let SizedChunkEnum::Bext(bext) = chunk {
+        let mut data = bext.data.clone();
+        for change in pending {
+            let Some(value) = &change.new else {
+                return Err(cannot_set(chunk, change));
+            };
+            set_bext_field(&mut data, &change.key, value)?;
+        }
+        return Ok(SizedChunkEnum::Bext(Box::new(BextChunk::new(data)?)));
+    }
+    #[cfg(feature = "info")]
+    if let SizedChunkEnum::Info(info) = chunk {
+        let mut data = info.data.clone();
+        for change in pending {
+            let id: FourCC = change.key.parse()?;
+            match &change.new {
+                Some(text) => data.set(id, text)?,
+                None => {
+                    data.remove(id);
+                }
+            }
+        }
+        return Ok(SizedChunkEnum::Info(ListInfoChunk::new(data)?));
+    }
+    #[cfg(feature = "ixml")]
+    if let SizedChunkEnum::Ixml(ixml) = chunk {
+        let mut data = ixml.data.clone();
+        for change in pending {
+            let (Some(path), Some(value)) = (Ixml::item_path(&change.key), &change.new) else {
+                return Err(cannot_set(chunk, change));
+            };
+            let path: Vec<&str> = path.iter().map(String::as_str).collect();
+            data = data.with_text(&path, value)?;
+        }
+        return Ok(SizedChunkEnum::Ixml(Box::new(IxmlChunk::new(data)?)));
+    }
+    Err(cannot_set(chunk, pending[0]))
+}
+
+fn cannot_set(chunk: &SizedChunkEnum, change: &FieldChange) -> WaveFileError {
+    let action = if change.new.is_some() {
+        "set"
+    } else {
+        "removed"
+    };
+    WaveFileError::parse(
+        None,
+        format!("{} {} can't be {action}", chunk.name(), change.key),
+    )
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+#[cfg(feature = "info")]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::chunk::fact::{Fact, FactChunk};
+    use crate::WaveFile;
+
+    fn chunk(path: &str, name: &str) -> SizedChunkEnum {
+        let file = BufReader::new(File::open(path).unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        wave.iter_chunks()
+            .map(Result::unwrap)
+            .find(|c| c.name() == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let plain = "../test_wavs/1khz_sine_48k_mono_region_marker.wav";
+        let tagged = "../test_wavs/1khz_sine_48k_mono_region_marker-bext_metadata.wav";
+        let old = chunk(tagged, "LIST-INFO");
+        let new = apply_changes(
+            &old,
+            &[
+                FieldChange {
+                    key: "ICMT".to_string(),
+                    old: Some("bext chunk test file".to_string()),
+                    new: None,
+                },
+                FieldChange {
+                    key: "INAM".to_string(),
+                    old: None,
+                    new: Some("Sine".to_string()),
+                },
+            ],
+        )
+        .unwrap();
+        let changes = diff(&old, &new);
+        dbg!(&changes);
+        let lines: Vec<_> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                r#"ICMT: removed "bext chunk test file""#,
+                r#"INAM: added "Sine""#
+            ]
+        );
+        // applied again, nothing to do
+        assert_eq!(apply_changes(&new, &changes).unwrap(), new);
+
+        let conflict = FieldChange {
+            key: "ISFT".to_string(),
+            old: Some("Other".to_string()),
+            new: Some("wavrw".to_string()),
+        };
+        let err = apply_changes(&old, &[conflict]).unwrap_err();
+        assert!(err.to_string().contains("conflict"), "{err}");
+
+        #[cfg(feature = "ixml")]
+        {
+            let ixml = chunk(tagged, "iXML");
+            let change = FieldChange {
+                key: "user.DESIGNER".to_string(),
+                old: Some("Designer".to_string()),
+                new: Some("Sound Designer".to_string()),
+            };
+            let changed = apply_changes(&ixml, core::slice::from_ref(&change)).unwrap();
+            assert_eq!(diff(&ixml, &changed), [change]);
+            let tracks = FieldChange {
+                key: "track_list.track_count".to_string(),
+                old: Some("1".to_string()),
+                new: Some("2".to_string()),
+            };
+            assert!(apply_changes(&ixml, &[tracks]).is_err());
+        }
+
+        #[cfg(feature = "broadcast")]
+        {
+            let bext = chunk(tagged, "bext");
+            let change = FieldChange {
+                key: "time_reference".to_string(),
+                old: Some("12345".to_string()),
+                new: Some("48000".to_string()),
+            };
+            let changed = apply_changes(&bext, core::slice::from_ref(&change)).unwrap();
+            assert_eq!(diff(&bext, &changed), [change]);
+        }
+
+        // fields of other chunks can't be set
+        let fact = SizedChunkEnum::Fact(FactChunk::new(Fact { samples: 1 }).unwrap());
+        assert!(diff(&chunk(plain, "fmt"), &chunk(tagged, "fmt")).is_empty());
+        let change = FieldChange {
+            key: "samples".to_string(),
+            old: None,
+            new: Some("2".to_string()),
+        };
+        assert!(apply_changes(&fact, &[change]).is_err());
+    }
+}
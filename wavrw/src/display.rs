@@ -0,0 +1,214 @@
+//! Options for rendering chunk summaries.
+//!
+//! [`Summarizable::summary()`](crate::Summarizable::summary) always renders
+//! the same way. [`Summarizable::summary_with()`](crate::Summarizable::summary_with)
+//! takes [`DisplayOptions`] to show sample counts as times, offsets in hex
+//! and to limit the length of the summary. [`DisplayOptions::chunk_offset()`]
+//! renders chunk offsets from the start of the file, the `RIFF` data or the
+//! chunk data.
+//!
+//! ```
+//! use wavrw::chunk::fact::Fact;
+//! use wavrw::display::{DisplayOptions, Units};
+//! use wavrw::Summarizable;
+//!
+//! let fact = Fact { samples: 48_000 * 90 };
+//! assert_eq!(fact.summary(), "4320000 samples");
+//!
+//! let options = DisplayOptions::new()
+//!     .with_units(Units::Seconds)
+//!     .with_sample_rate(48_000);
+//! assert_eq!(fact.summary_with(&options), "00:01:30.000");
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::time::SampleTime;
+use crate::SizedChunk;
+
+/// How sample counts and positions are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Units {
+    /// As a number of samples. Ex: `480 samples`
+    #[default]
+    Samples,
+    /// As a time, when the sample rate is known. Ex: `00:00:00.010`
+    Seconds,
+}
+
+/// Where chunk offsets are counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OffsetBase {
+    /// From the start of the file, the chunk id. Ex: `36`
+    #[default]
+    File,
+    /// From the start of the `RIFF` chunk data, the `WAVE` form type, 8
+    /// bytes into the file. Ex: `28`
+    Riff,
+    /// The start of the chunk data after the id and size, from the start of
+    /// the file, see [`SizedChunk::data_offset()`]. Ex: `44`
+    Payload,
+}
+
+/// Options for [`Summarizable::summary_with()`](crate::Summarizable::summary_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DisplayOptions {
+    /// How sample counts and positions are shown.
+    pub units: Units,
+
+    /// Show byte offsets in hexadecimal instead of decimal.
+    pub hex_offsets: bool,
+
+    /// Where chunk offsets are counted from, see
+    /// [`DisplayOptions::chunk_offset()`].
+    pub offset_base: OffsetBase,
+
+    /// Trim summaries longer than this many chars, ending them with `...`.
+    pub max_string_length: Option<usize>,
+
+    /// Sample rate from the `fmt ` chunk, needed for [`Units::Seconds`].
+    pub sample_rate: Option<u32>,
+}
+
+impl DisplayOptions {
+    /// Default options, the same rendering as [`Summarizable::summary()`](crate::Summarizable::summary).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`DisplayOptions::units`].
+    #[must_use]
+    pub fn with_units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Set [`DisplayOptions::hex_offsets`].
+    #[must_use]
+    pub fn with_hex_offsets(mut self, hex: bool) -> Self {
+        self.hex_offsets = hex;
+        self
+    }
+
+    /// Set [`DisplayOptions::offset_base`].
+    #[must_use]
+    pub fn with_offset_base(mut self, base: OffsetBase) -> Self {
+        self.offset_base = base;
+        self
+    }
+
+    /// Set [`DisplayOptions::max_string_length`].
+    #[must_use]
+    pub fn with_max_string_length(mut self, max: usize) -> Self {
+        self.max_string_length = Some(max);
+        self
+    }
+
+    /// Set [`DisplayOptions::sample_rate`].
+    #[must_use]
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// A byte offset. Ex: `36` or `0x24`
+    pub fn offset(&self, offset: u64) -> String {
+        if self.hex_offsets {
+            format!("{offset:#X}")
+        } else {
+            offset.to_string()
+        }
+    }
+
+    /// The offset of `chunk`, counted from [`DisplayOptions::offset_base`].
+    /// `???` if the offset is unknown.
+    ///
+    /// ```
+    /// use wavrw::display::{DisplayOptions, OffsetBase};
+    /// use wavrw::UnknownChunk;
+    ///
+    /// let chunk = UnknownChunk {
+    ///     offset: Some(36),
+    ///     ..Default::default()
+    /// };
+    /// let options = DisplayOptions::new().with_offset_base(OffsetBase::Payload);
+    /// assert_eq!(options.chunk_offset(&chunk), "44");
+    /// assert_eq!(options.with_hex_offsets(true).chunk_offset(&chunk), "0x2C");
+    /// ```
+    pub fn chunk_offset(&self, chunk: &(impl SizedChunk + ?Sized)) -> String {
+        let offset = match self.offset_base {
+            OffsetBase::File => chunk.offset(),
+            OffsetBase::Riff => chunk.offset().map(|o| o.saturating_sub(8)),
+            OffsetBase::Payload => chunk.data_offset(),
+        };
+        offset.map_or("???".to_string(), |o| self.offset(o))
+    }
+
+    /// A sample count or position. Ex: `480 samples` or `00:00:00.010`
+    ///
+    /// Falls back to samples if the sample rate is unknown.
+    pub fn samples(&self, samples: u64) -> String {
+        match (self.units, self.sample_rate) {
+            (Units::Seconds, Some(sample_rate)) if sample_rate > 0 => {
+                SampleTime::new(samples, sample_rate).to_string()
+            }
+            _ => format!("{samples} samples"),
+        }
+    }
+
+    /// `text` trimmed to [`DisplayOptions::max_string_length`].
+    pub fn text(&self, text: &str) -> String {
+        match self.max_string_length {
+            Some(max) if text.chars().count() > max => {
+                let mut text: String = text.chars().take(max.saturating_sub(3)).collect();
+                text.push_str("...");
+                text
+            }
+            _ => text.to_string(),
+        }
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_options() {
+        let options = DisplayOptions::new();
+        assert_eq!(options.offset(36), "36");
+        assert_eq!(options.samples(480), "480 samples");
+        assert_eq!(options.text("Marker 01"), "Marker 01");
+
+        let options = options
+            .with_hex_offsets(true)
+            .with_units(Units::Seconds)
+            .with_max_string_length(8);
+        dbg!(&options);
+        assert_eq!(options.offset(36), "0x24");
+        // no sample rate, stays in samples
+        assert_eq!(options.samples(480), "480 samples");
+        assert_eq!(options.text("Marker 01"), "Marke...");
+        assert_eq!(options.text("Märker 1"), "Märker 1");
+
+        let options = options.with_sample_rate(48_000);
+        assert_eq!(options.samples(480), "00:00:00.010");
+    }
+
+    #[test]
+    fn chunk_offsets() {
+        let chunk = crate::UnknownChunk {
+            offset: Some(36),
+            ..Default::default()
+        };
+        let options = DisplayOptions::new();
+        assert_eq!(options.chunk_offset(&chunk), "36");
+        let options = options.with_offset_base(OffsetBase::Riff);
+        assert_eq!(options.chunk_offset(&chunk), "28");
+        let options = options.with_offset_base(OffsetBase::Payload);
+        assert_eq!(options.chunk_offset(&chunk), "44");
+        assert_eq!(options.chunk_offset(&crate::UnknownChunk::default()), "???");
+    }
+}
@@ -0,0 +1,949 @@
+//! Modify chunks in existing WAVE data.
+//!
+//! Edits work on raw chunk bytes: every chunk is copied verbatim from a
+//! reader to a writer, except the chunks being changed. Chunk contents are
+//! not parsed, so chunks wavrw does not understand are preserved as is.
+
+use core::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, Cursor, Read, Seek, SeekFrom, Write};
+
+use binrw::{BinRead, BinWrite};
+
+use crate::chunk::data::DataChunk;
+use crate::chunk::fmt::FmtChunk;
+use crate::chunk::junk::{FllrChunk, JunkChunk, PadChunk};
+use crate::chunk::riff::RiffChunk;
+use crate::{FourCC, KnownChunkID, LazyChunk, WaveFile, WaveFileError};
+
+/// Insert or replace a chunk with raw `payload` bytes.
+///
+/// Copies all chunks from `reader` to `writer`. The first chunk with a
+/// matching `id` is replaced in place by a chunk containing `payload`, any
+/// later chunks with the same `id` are copied unchanged. If no chunk matches,
+/// the new chunk is appended after the last chunk. Chunk size, RIFF padding
+/// byte and the RIFF size are calculated from `payload`. `LIST` chunks only
+/// match when their list type, the first 4 bytes, matches `payload`, see
+/// [`inject_chunks()`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Cursor;
+/// # use wavrw::testing::hex_to_cursor;
+/// use wavrw::{edit::inject_chunk, FourCC};
+///
+/// // RIFF WAVE containing only a `fact` chunk
+/// let input = hex_to_cursor("52494646 10000000 57415645 66616374 04000000 E0010000");
+/// let mut output = Cursor::new(Vec::new());
+/// inject_chunk(input, &mut output, FourCC(*b"iXML"), b"<BWFXML/>")?;
+///
+/// // 9 byte payload + 1 padding byte
+/// assert_eq!(output.get_ref().len(), 24 + 8 + 10);
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn inject_chunk<R, W>(
+    reader: R,
+    writer: W,
+    id: FourCC,
+    payload: &[u8],
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek,
+    W: Write + Seek,
+{
+    inject_chunks(reader, writer, &[(id, payload)])
+}
+
+/// Insert or replace several chunks in one pass, see [`inject_chunk()`].
+///
+/// Each `(id, payload)` replaces the first chunk with `id`, or is appended
+/// in the order given. A `LIST` payload starts with its list type, ex:
+/// `INFO`, and only replaces a `LIST` chunk of the same type, so a
+/// `LIST-INFO` chunk can be written without touching `LIST-adtl`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Cursor;
+/// # use wavrw::testing::hex_to_cursor;
+/// use wavrw::{edit::inject_chunks, FourCC};
+///
+/// // RIFF WAVE containing only a `LIST-adtl` chunk
+/// let input = hex_to_cursor("52494646 10000000 57415645 4C495354 04000000 6164746C");
+/// let mut output = Cursor::new(Vec::new());
+/// inject_chunks(
+///     input,
+///     &mut output,
+///     &[(FourCC(*b"LIST"), b"INFO"), (FourCC(*b"iXML"), b"<BWFXML/>")],
+/// )?;
+/// assert_eq!(&output.get_ref()[24..36], b"LIST\x04\0\0\0INFO");
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn inject_chunks<R, W>(
+    mut reader: R,
+    mut writer: W,
+    chunks: &[(FourCC, &[u8])],
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek,
+    W: Write + Seek,
+{
+    let mut payloads = Vec::with_capacity(chunks.len());
+    for (id, payload) in chunks {
+        let size = u32::try_from(payload.len()).map_err(|_| {
+            WaveFileError::parse(None, format!("{id} payload too large for a RIFF chunk"))
+        })?;
+        payloads.push((*id, list_type(*id, payload), size, *payload));
+    }
+
+    let riff = read_riff_header(&mut reader)?;
+    let start = writer.stream_position()?;
+    writer.write_all(&riff.id.0)?;
+    // placeholder, updated after all chunks are written
+    writer.write_all(&0_u32.to_le_bytes())?;
+    writer.write_all(&riff.form_type.0)?;
+
+    let mut injected = vec![false; payloads.len()];
+    let riff_end = 8 + u64::from(riff.size);
+    loop {
+        let offset = reader.stream_position()?;
+        if offset >= riff_end {
+            break;
+        }
+        let Some((chunk_id, chunk_size)) = read_chunk_header(&mut reader)? else {
+            break;
+        };
+        let padded_size = u64::from(chunk_size) + u64::from(chunk_size % 2);
+
+        let chunk_list_type = if chunk_id == FourCC(*b"LIST") && chunk_size >= 4 {
+            let mut buff = [0_u8; 4];
+            reader.read_exact(&mut buff)?;
+            reader.seek(SeekFrom::Current(-4))?;
+            Some(FourCC(buff))
+        } else {
+            None
+        };
+        let matching = payloads.iter().zip(&injected).position(|(p, done)| {
+            !done && p.0 == chunk_id && (p.1.is_none() || p.1 == chunk_list_type)
+        });
+        if let Some(index) = matching {
+            let (id, _, size, payload) = payloads[index];
+            write_chunk(&mut writer, id, size, payload)?;
+            reader.seek(SeekFrom::Current(padded_size.try_into().map_err(|_| {
+                WaveFileError::parse(
+                    Some(offset),
+                    format!("{chunk_id} chunk size too large to seek past"),
+                )
+            })?))?;
+            injected[index] = true;
+            continue;
+        }
+
+        writer.write_all(&chunk_id.0)?;
+        writer.write_all(&chunk_size.to_le_bytes())?;
+        copy_chunk_data(&mut reader, &mut writer, offset, chunk_id, chunk_size)?;
+    }
+
+    for ((id, _, size, payload), done) in payloads.iter().zip(injected) {
+        if !done {
+            write_chunk(&mut writer, *id, *size, payload)?;
+        }
+    }
+
+    finish_riff(&mut writer, start)
+}
+
+/// Data of a parsed chunk as written, without chunk id and size. Ex: a
+/// [`Bext`](crate::chunk::bext::Bext) for [`inject_chunks()`]
+pub(crate) fn chunk_data<T>(data: &T) -> Result<Vec<u8>, WaveFileError>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    let mut cursor = Cursor::new(Vec::new());
+    data.write_le(&mut cursor)?;
+    Ok(cursor.into_inner())
+}
+
+/// List type of a `LIST` `payload`, `None` for other chunks.
+fn list_type(id: FourCC, payload: &[u8]) -> Option<FourCC> {
+    if id != FourCC(*b"LIST") {
+        return None;
+    }
+    let list_type: [u8; 4] = payload.get(..4)?.try_into().ok()?;
+    Some(FourCC(list_type))
+}
+
+/// Rules for the order of chunks when writing a file, see [`reorder()`].
+///
+/// Chunks listed in `first` are written first, in that order, and chunks in
+/// `last` are written last. All other chunks keep their original order in
+/// between. When several chunks have the same id, they stay in their
+/// original order.
+///
+/// # Examples
+///
+/// ```
+/// use wavrw::edit::WritePolicy;
+/// use wavrw::FourCC;
+///
+/// // iXML directly after fmt, for a picky importer
+/// let policy = WritePolicy {
+///     first: vec![FourCC(*b"fmt "), FourCC(*b"iXML")],
+///     ..WritePolicy::default()
+/// };
+/// let ids = [FourCC(*b"data"), FourCC(*b"iXML"), FourCC(*b"fmt ")];
+/// assert_eq!(policy.order(&ids), vec![2, 1, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WritePolicy {
+    /// Chunk ids to write first, in this order.
+    pub first: Vec<FourCC>,
+    /// Chunk ids to write last, in this order.
+    pub last: Vec<FourCC>,
+    /// Size of a `JUNK` chunk reserved directly after the RIFF header, `None`
+    /// for no reservation.
+    ///
+    /// Space for a later upgrade to RF64 needs 28 bytes, the size of a
+    /// `ds64` chunk. A `JUNK` chunk at the start of the original file is
+    /// assumed to be an earlier reservation and is replaced.
+    pub junk_reservation: Option<u32>,
+}
+
+impl Default for WritePolicy {
+    /// `fmt ` first and `data` last.
+    fn default() -> Self {
+        WritePolicy {
+            first: vec![FmtChunk::ID],
+            last: vec![DataChunk::ID],
+            junk_reservation: None,
+        }
+    }
+}
+
+impl WritePolicy {
+    /// Order for Broadcast Wave files: a 28 byte `JUNK` reservation, then
+    /// `bext`, `iXML` and `fmt `, with `data` last.
+    pub fn broadcast() -> Self {
+        WritePolicy {
+            first: vec![FourCC(*b"bext"), FourCC(*b"iXML"), FmtChunk::ID],
+            last: vec![DataChunk::ID],
+            junk_reservation: Some(28),
+        }
+    }
+
+    /// Write order for chunks with `ids`, as indexes into `ids`.
+    pub fn order(&self, ids: &[FourCC]) -> Vec<usize> {
+        let rank = |id: &FourCC| {
+            if let Some(index) = self.first.iter().position(|f| f == id) {
+                (0, index)
+            } else if let Some(index) = self.last.iter().position(|l| l == id) {
+                (2, index)
+            } else {
+                (1, 0)
+            }
+        };
+        let mut order: Vec<usize> = (0..ids.len()).collect();
+        // stable, so equal ranks keep their original order
+        order.sort_by_key(|i| rank(&ids[*i]));
+        order
+    }
+}
+
+/// Copy all chunks from `reader` to `writer` in the order given by `policy`.
+///
+/// Chunks are copied verbatim, padding bytes are added where missing and the
+/// RIFF size is recalculated.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Cursor;
+/// # use wavrw::testing::hex_to_cursor;
+/// use wavrw::edit::{reorder, WritePolicy};
+///
+/// // RIFF WAVE containing a `data` chunk before a `fact` chunk
+/// let input = hex_to_cursor("52494646 18000000 57415645 64617461 00000000 66616374 04000000 E0010000");
+/// let mut output = Cursor::new(Vec::new());
+/// reorder(input, &mut output, &WritePolicy::default())?;
+/// assert_eq!(&output.get_ref()[12..16], b"fact");
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn reorder<R, W>(
+    mut reader: R,
+    mut writer: W,
+    policy: &WritePolicy,
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek,
+    W: Write + Seek,
+{
+    let riff = read_riff_header(&mut reader)?;
+    let mut chunks = read_chunk_list(&mut reader, &riff)?;
+
+    let start = writer.stream_position()?;
+    writer.write_all(&riff.id.0)?;
+    // placeholder, updated after all chunks are written
+    writer.write_all(&0_u32.to_le_bytes())?;
+    writer.write_all(&riff.form_type.0)?;
+
+    if let Some(size) = policy.junk_reservation {
+        if chunks
+            .first()
+            .is_some_and(|(_, id, _)| *id == JunkChunk::ID)
+        {
+            chunks.remove(0);
+        }
+        let padding = vec![0_u8; usize::try_from(size).unwrap_or_default()];
+        write_chunk(&mut writer, JunkChunk::ID, size, &padding)?;
+    }
+
+    let ids: Vec<FourCC> = chunks.iter().map(|(_, id, _)| *id).collect();
+    for index in policy.order(&ids) {
+        let (offset, chunk_id, chunk_size) = chunks[index];
+        reader.seek(SeekFrom::Start(offset + 8))?;
+        writer.write_all(&chunk_id.0)?;
+        writer.write_all(&chunk_size.to_le_bytes())?;
+        copy_chunk_data(&mut reader, &mut writer, offset, chunk_id, chunk_size)?;
+    }
+
+    finish_riff(&mut writer, start)
+}
+
+fn read_riff_header<R: Read + Seek>(reader: &mut R) -> Result<RiffChunk, WaveFileError> {
+    let riff = RiffChunk::read(reader)?;
+    if riff.form_type != FourCC(*b"WAVE") {
+        return Err(WaveFileError::UnknownFourCC {
+            found: riff.form_type,
+            message: format!(
+                "not a wave file. Expected RIFF form_type 'WAVE', found: {}",
+                riff.form_type
+            ),
+        });
+    }
+    Ok(riff)
+}
+
+/// Insert or replace a chunk in place, using filler chunks as elastic space.
+///
+/// `JUNK`, `FLLR` and `PAD ` chunks hold no data, so they can grow or shrink
+/// to absorb a change in size of the chunk before them. Only the bytes of the
+/// changed chunk and the filler are written, the rest of the file including
+/// the `data` chunk stays where it is. This makes metadata edits of large
+/// files fast.
+///
+/// If a chunk with `id` exists, it is replaced when the new chunk fits in
+/// its space plus the filler chunk directly after it. Otherwise the new chunk
+/// is written at the start of the first filler chunk large enough to hold
+/// it. Remaining space becomes a filler chunk, which needs at least 8 bytes
+/// for its header. Like [`inject_chunks()`], a `LIST` payload only replaces a
+/// `LIST` chunk of the same list type.
+///
+/// The RIFF header is read from the current position of `file`. Returns
+/// `Ok(false)` without writing anything if there isn't enough filler space,
+/// use [`inject_chunk()`] to rewrite the file instead.
+///
+/// # Examples
+///
+/// ```
+/// # use wavrw::testing::hex_to_cursor;
+/// use wavrw::{edit::inject_chunk_in_place, FourCC};
+///
+/// // RIFF WAVE containing a `fact` chunk and 24 bytes of `JUNK`
+/// let mut file = hex_to_cursor(
+///     "52494646 30000000 57415645 66616374 04000000 E0010000
+///      4A554E4B 18000000 00000000 00000000 00000000 00000000 00000000 00000000",
+/// );
+/// assert!(inject_chunk_in_place(&mut file, FourCC(*b"iXML"), b"<BWFXML/>")?);
+///
+/// // 9 byte payload + 1 padding byte, the file size is unchanged
+/// assert_eq!(&file.get_ref()[24..28], b"iXML");
+/// assert_eq!(file.get_ref().len(), 8 + 0x30);
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn inject_chunk_in_place<F>(
+    file: &mut F,
+    id: FourCC,
+    payload: &[u8],
+) -> Result<bool, WaveFileError>
+where
+    F: Read + Write + Seek,
+{
+    let payload_size = u32::try_from(payload.len()).map_err(|_| {
+        WaveFileError::parse(None, format!("{id} payload too large for a RIFF chunk"))
+    })?;
+    let new_total = 8 + u64::from(payload_size) + u64::from(payload_size % 2);
+
+    let riff = read_riff_header(file)?;
+    let chunks = read_chunk_list(file, &riff)?;
+    let is_filler = |id: FourCC| [JunkChunk::ID, FllrChunk::ID, PadChunk::ID].contains(&id);
+
+    // a LIST payload only replaces a LIST chunk of the same type
+    let wanted_list_type = list_type(id, payload);
+    let mut existing = None;
+    for (index, &(offset, chunk_id, size)) in chunks.iter().enumerate() {
+        if chunk_id == id
+            && (wanted_list_type.is_none()
+                || read_list_type(file, offset, chunk_id, size)? == wanted_list_type)
+        {
+            existing = Some(index);
+            break;
+        }
+    }
+
+    // (offset, space available, bytes already filler, filler id)
+    let space = if let Some(index) = existing {
+        let (offset, _, size) = chunks[index];
+        let total = chunk_total(size);
+        match chunks.get(index + 1) {
+            Some(&(_, filler_id, filler_size)) if is_filler(filler_id) => (
+                offset,
+                total + chunk_total(filler_size),
+                offset + total + 8,
+                filler_id,
+            ),
+            _ => (offset, total, offset + total, JunkChunk::ID),
+        }
+    } else {
+        let Some(&(offset, filler_id, size)) = chunks.iter().find(|c| {
+            is_filler(c.1)
+                && chunk_total(c.2)
+                    .checked_sub(new_total)
+                    .is_some_and(|rest| rest == 0 || rest >= 8)
+        }) else {
+            return Ok(false);
+        };
+        (offset, chunk_total(size), offset + 8, filler_id)
+    };
+    let (offset, available, filler_start, filler_id) = space;
+    let Some(rest) = available.checked_sub(new_total) else {
+        return Ok(false);
+    };
+    if rest != 0 && rest < 8 {
+        return Ok(false);
+    }
+
+    file.seek(SeekFrom::Start(offset))?;
+    write_chunk(file, id, payload_size, payload)?;
+    if rest >= 8 {
+        let filler_size = u32::try_from(rest - 8).map_err(|_| {
+            WaveFileError::parse(Some(offset), format!("{filler_id} chunk too large"))
+        })?;
+        file.write_all(&filler_id.0)?;
+        file.write_all(&filler_size.to_le_bytes())?;
+        // clear old chunk bytes which are now filler
+        let position = file.stream_position()?;
+        let stale = filler_start.saturating_sub(position);
+        file.write_all(&vec![0_u8; usize::try_from(stale).unwrap_or_default()])?;
+    }
+    file.flush()?;
+    Ok(true)
+}
+
+/// A chunk which differs between two versions of a file, see
+/// [`chunk_changes()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunkChange {
+    /// RIFF chunk id.
+    pub id: FourCC,
+    /// List type of `LIST` chunks. Ex: `INFO`
+    pub list_type: Option<FourCC>,
+    /// Chunk size in the old version, `None` if the chunk was added.
+    pub old_size: Option<u64>,
+    /// Chunk size in the new version, `None` if the chunk was removed.
+    pub new_size: Option<u64>,
+}
+
+impl Display for ChunkChange {
+    /// Ex: `add iXML, 1234 bytes` or `resize bext, 602 -> 640 bytes (+38)`
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self.list_type {
+            Some(list_type) => format!("{} {list_type}", self.id),
+            None => self.id.to_string(),
+        };
+        match (self.old_size, self.new_size) {
+            (None, Some(size)) => write!(f, "add {name}, {size} bytes"),
+            (Some(size), None) => write!(f, "remove {name}, {size} bytes"),
+            (Some(old), Some(new)) if old == new => write!(f, "modify {name}, {new} bytes"),
+            (Some(old), Some(new)) => {
+                let delta = i128::from(new) - i128::from(old);
+                write!(f, "resize {name}, {old} -> {new} bytes ({delta:+})")
+            }
+            (None, None) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// List the chunks which were added, removed, resized or modified between
+/// `before` and `after`.
+///
+/// The nth chunk with an id (and list type for `LIST` chunks) in `before` is
+/// compared to the nth chunk with the same id in `after`, so moving a chunk
+/// is not a change. Chunks of equal size are compared byte by byte. Changes
+/// are listed in the order of `before`, followed by added chunks in the
+/// order of `after`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Cursor;
+/// # use wavrw::testing::hex_to_cursor;
+/// use wavrw::edit::{chunk_changes, inject_chunk};
+/// use wavrw::{FourCC, WaveFile};
+///
+/// // RIFF WAVE containing only a `fact` chunk
+/// let input = "52494646 10000000 57415645 66616374 04000000 E0010000";
+/// let mut output = Cursor::new(Vec::new());
+/// inject_chunk(hex_to_cursor(input), &mut output, FourCC(*b"iXML"), b"<BWFXML/>")?;
+///
+/// let mut before = WaveFile::from_reader(hex_to_cursor(input))?;
+/// let mut after = WaveFile::from_reader(Cursor::new(output.into_inner()))?;
+/// let changes = chunk_changes(&mut before, &mut after)?;
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].to_string(), "add iXML, 9 bytes");
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn chunk_changes<A, B>(
+    before: &mut WaveFile<A>,
+    after: &mut WaveFile<B>,
+) -> Result<Vec<ChunkChange>, WaveFileError>
+where
+    A: Read + Seek + Debug + BufRead,
+    B: Read + Seek + Debug + BufRead,
+{
+    let old_chunks = before.scan_chunks()?;
+    let new_chunks = after.scan_chunks()?;
+    let same_kind = |a: &LazyChunk, b: &LazyChunk| a.id == b.id && a.list_type == b.list_type;
+    // index of the chunk among the earlier chunks of the same kind
+    let nth = |chunks: &[LazyChunk], index: usize| {
+        chunks[..index]
+            .iter()
+            .filter(|c| same_kind(c, &chunks[index]))
+            .count()
+    };
+
+    let mut changes = Vec::new();
+    for (index, old) in old_chunks.iter().enumerate() {
+        let new = new_chunks
+            .iter()
+            .filter(|c| same_kind(c, old))
+            .nth(nth(&old_chunks, index));
+        let old_size = old.large_size();
+        let new_size = new.map(LazyChunk::large_size);
+        let changed = match new {
+            None => true,
+            Some(new) if new.large_size() != old_size => true,
+            Some(new) => !same_contents(
+                &mut before.bytes,
+                old.offset + 8,
+                &mut after.bytes,
+                new.offset + 8,
+                old_size,
+            )?,
+        };
+        if changed {
+            changes.push(ChunkChange {
+                id: old.id,
+                list_type: old.list_type,
+                old_size: Some(old_size),
+                new_size,
+            });
+        }
+    }
+    for (index, new) in new_chunks.iter().enumerate() {
+        let old_count = old_chunks.iter().filter(|c| same_kind(c, new)).count();
+        if nth(&new_chunks, index) >= old_count {
+            changes.push(ChunkChange {
+                id: new.id,
+                list_type: new.list_type,
+                old_size: None,
+                new_size: Some(new.large_size()),
+            });
+        }
+    }
+    Ok(changes)
+}
+
+/// Compare `size` bytes of `a` and `b`, starting at the given offsets.
+fn same_contents<A: Read + Seek, B: Read + Seek>(
+    a: &mut A,
+    a_offset: u64,
+    b: &mut B,
+    b_offset: u64,
+    size: u64,
+) -> Result<bool, WaveFileError> {
+    a.seek(SeekFrom::Start(a_offset))?;
+    b.seek(SeekFrom::Start(b_offset))?;
+    let mut a_buff = [0_u8; 8192];
+    let mut b_buff = [0_u8; 8192];
+    let mut remaining = size;
+    while remaining > 0 {
+        let len = usize::try_from(remaining.min(8192)).unwrap_or(8192);
+        a.read_exact(&mut a_buff[..len])?;
+        b.read_exact(&mut b_buff[..len])?;
+        if a_buff[..len] != b_buff[..len] {
+            return Ok(false);
+        }
+        remaining -= len as u64;
+    }
+    Ok(true)
+}
+
+/// Size of a chunk including its header and padding byte.
+fn chunk_total(size: u32) -> u64 {
+    8 + u64::from(size) + u64::from(size % 2)
+}
+
+/// Read the offset, id and size of every chunk in the RIFF chunk.
+fn read_chunk_list<R: Read + Seek>(
+    reader: &mut R,
+    riff: &RiffChunk,
+) -> Result<Vec<(u64, FourCC, u32)>, WaveFileError> {
+    let riff_end = 8 + u64::from(riff.size);
+    let mut chunks = Vec::new();
+    loop {
+        let offset = reader.stream_position()?;
+        if offset >= riff_end {
+            break;
+        }
+        let Some((chunk_id, chunk_size)) = read_chunk_header(reader)? else {
+            break;
+        };
+        chunks.push((offset, chunk_id, chunk_size));
+        reader.seek(SeekFrom::Start(offset + chunk_total(chunk_size)))?;
+    }
+    Ok(chunks)
+}
+
+/// List type of the chunk at `offset`, `None` for chunks other than `LIST`.
+fn read_list_type<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    chunk_id: FourCC,
+    chunk_size: u32,
+) -> Result<Option<FourCC>, WaveFileError> {
+    if chunk_id != FourCC(*b"LIST") || chunk_size < 4 {
+        return Ok(None);
+    }
+    let mut buff = [0_u8; 4];
+    reader.seek(SeekFrom::Start(offset + 8))?;
+    reader.read_exact(&mut buff)?;
+    Ok(Some(FourCC(buff)))
+}
+
+/// Copy chunk data and its padding byte, after the chunk header.
+pub(crate) fn copy_chunk_data<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    offset: u64,
+    chunk_id: FourCC,
+    chunk_size: u32,
+) -> Result<(), WaveFileError> {
+    let padded_size = u64::from(chunk_size) + u64::from(chunk_size % 2);
+    let copied = std::io::copy(&mut reader.take(padded_size), writer)?;
+    // a missing padding byte on the final chunk is common, write it anyway
+    if copied + 1 == padded_size && chunk_size % 2 == 1 {
+        writer.write_all(&[0])?;
+    } else if copied != padded_size {
+        return Err(WaveFileError::parse(
+            Some(offset),
+            format!("{chunk_id} chunk size ({chunk_size}) extends past the end of the data"),
+        ));
+    }
+    Ok(())
+}
+
+/// Update the size of the RIFF chunk written at `start`.
+pub(crate) fn finish_riff<W: Write + Seek>(
+    writer: &mut W,
+    start: u64,
+) -> Result<(), WaveFileError> {
+    let end = writer.stream_position()?;
+    let riff_size = u32::try_from(end - start - 8)
+        .map_err(|_| WaveFileError::parse(None, "resulting RIFF size too large".to_string()))?;
+    writer.seek(SeekFrom::Start(start + 4))?;
+    writer.write_all(&riff_size.to_le_bytes())?;
+    writer.seek(SeekFrom::Start(end))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read a chunk id and size, returns `None` at the end of the data.
+fn read_chunk_header<R: Read>(reader: &mut R) -> Result<Option<(FourCC, u32)>, WaveFileError> {
+    let mut buff = [0_u8; 8];
+    match reader.read_exact(&mut buff) {
+        Ok(()) => (),
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+    let [a, b, c, d, s0, s1, s2, s3] = buff;
+    Ok(Some((
+        FourCC([a, b, c, d]),
+        u32::from_le_bytes([s0, s1, s2, s3]),
+    )))
+}
+
+pub(crate) fn write_chunk<W: Write>(
+    writer: &mut W,
+    id: FourCC,
+    size: u32,
+    payload: &[u8],
+) -> Result<(), WaveFileError> {
+    writer.write_all(&id.0)?;
+    writer.write_all(&size.to_le_bytes())?;
+    writer.write_all(payload)?;
+    if size % 2 == 1 {
+        writer.write_all(&[0])?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::testing::hex_to_cursor;
+    use crate::{ChunkID, SizedChunk, SizedChunkEnum, WaveFile};
+
+    fn ids_and_sizes(data: Vec<u8>) -> Vec<(FourCC, u32)> {
+        let mut wave = WaveFile::from_reader(Cursor::new(data)).unwrap();
+        wave.iter_chunks()
+            .map(|c| {
+                let c = c.unwrap();
+                (c.id(), c.size())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inject_appends_new_chunk() {
+        let input = hex_to_cursor("52494646 10000000 57415645 66616374 04000000 E0010000");
+        let mut output = Cursor::new(Vec::new());
+        inject_chunk(input, &mut output, FourCC(*b"iXML"), b"abc").unwrap();
+        let data = output.into_inner();
+        assert_eq!(u32::from_le_bytes(data[4..8].try_into().unwrap()), 28);
+        assert_eq!(
+            ids_and_sizes(data),
+            vec![(FourCC(*b"fact"), 4), (FourCC(*b"iXML"), 3)]
+        );
+    }
+
+    #[test]
+    fn inject_in_place_grow_shrink() {
+        // fact chunk followed by 16 bytes of JUNK
+        let original = hex_to_cursor(
+            "52494646 28000000 57415645 66616374 04000000 E0010000
+             4A554E4B 10000000 00000000 00000000 00000000 00000000",
+        )
+        .into_inner();
+
+        // grow fact by 8 bytes, shrinking JUNK
+        let mut file = Cursor::new(original.clone());
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[1; 12]).unwrap());
+        let data = file.into_inner();
+        assert_eq!(data.len(), original.len());
+        assert_eq!(
+            ids_and_sizes(data.clone()),
+            vec![(FourCC(*b"fact"), 12), (FourCC(*b"JUNK"), 8)]
+        );
+
+        // shrink it again, old fact bytes are cleared
+        let mut file = Cursor::new(data);
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[2; 4]).unwrap());
+        let data = file.into_inner();
+        assert_eq!(
+            ids_and_sizes(data.clone()),
+            vec![(FourCC(*b"fact"), 4), (FourCC(*b"JUNK"), 16)]
+        );
+        assert!(data[32..].iter().all(|b| *b == 0));
+
+        // use all of the JUNK
+        let mut file = Cursor::new(data);
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[3; 27]).unwrap());
+        let data = file.into_inner();
+        assert_eq!(ids_and_sizes(data.clone()), vec![(FourCC(*b"fact"), 27)]);
+
+        // no space left, nothing written
+        let mut file = Cursor::new(data.clone());
+        assert!(!inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[4; 30]).unwrap());
+        file.set_position(0);
+        assert!(!inject_chunk_in_place(&mut file, FourCC(*b"iXML"), b"abc").unwrap());
+        assert_eq!(file.into_inner(), data);
+
+        // leaving less than a chunk header of space doesn't fit
+        let mut file = Cursor::new(original);
+        assert!(!inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[5; 22]).unwrap());
+    }
+
+    #[test]
+    fn reorder_broadcast() {
+        // JUNK, data, LIST, odd sized bext and fmt chunks
+        let input = hex_to_cursor(
+            "52494646 3C000000 57415645 4A554E4B 04000000 00000000 64617461 02000000 0000
+            4C495354 04000000 494E464F 62657874 01000000 4100666D 74200400 00000100 0100",
+        );
+        let mut output = Cursor::new(Vec::new());
+        reorder(input, &mut output, &WritePolicy::broadcast()).unwrap();
+        let data = output.into_inner();
+        dbg!(&data);
+        assert_eq!(
+            ids_and_sizes(data),
+            vec![
+                (FourCC(*b"JUNK"), 28),
+                (FourCC(*b"bext"), 1),
+                (FourCC(*b"fmt "), 4),
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"data"), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn inject_chunks_by_list_type() {
+        // LIST-adtl, then LIST-INFO with one INAM
+        let input = hex_to_cursor(
+            "52494646 2A000000 57415645 4C495354 04000000 6164746C \
+            4C495354 0E000000 494E464F 494E414D 02000000 4100",
+        );
+        let mut output = Cursor::new(Vec::new());
+        inject_chunks(
+            input,
+            &mut output,
+            &[
+                (FourCC(*b"iXML"), b"<BWFXML/>"),
+                (FourCC(*b"LIST"), b"INFO"),
+                (FourCC(*b"LIST"), b"exif"),
+            ],
+        )
+        .unwrap();
+        let data = output.into_inner();
+        dbg!(&data);
+        assert_eq!(&data[20..24], b"adtl");
+        assert_eq!(&data[32..36], b"INFO");
+        assert_eq!(
+            ids_and_sizes(data),
+            vec![
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"iXML"), 9),
+                (FourCC(*b"LIST"), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn inject_in_place_by_list_type() {
+        // LIST-adtl, then 16 bytes of JUNK
+        let mut file = hex_to_cursor(
+            "52494646 28000000 57415645 4C495354 04000000 6164746C
+             4A554E4B 10000000 00000000 00000000 00000000 00000000",
+        );
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"LIST"), b"INFO").unwrap());
+        let data = file.into_inner();
+        dbg!(&data);
+        assert_eq!(&data[20..24], b"adtl");
+        assert_eq!(&data[32..36], b"INFO");
+        assert_eq!(
+            ids_and_sizes(data),
+            vec![
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"JUNK"), 4),
+            ]
+        );
+
+        // LIST-adtl, LIST-INFO with one INAM, then 16 bytes of JUNK
+        let mut file = hex_to_cursor(
+            "52494646 3E000000 57415645 4C495354 04000000 6164746C
+             4C495354 0E000000 494E464F 494E414D 02000000 4100
+             4A554E4B 10000000 00000000 00000000 00000000 00000000",
+        );
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"LIST"), b"INFO").unwrap());
+        let data = file.into_inner();
+        assert_eq!(&data[20..24], b"adtl");
+        assert_eq!(&data[32..36], b"INFO");
+        assert_eq!(
+            ids_and_sizes(data),
+            vec![
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"LIST"), 4),
+                (FourCC(*b"JUNK"), 26),
+            ]
+        );
+    }
+
+    #[test]
+    fn inject_replaces_existing_chunk() {
+        // fact chunk followed by an odd sized JUNK chunk
+        let input = hex_to_cursor(
+            "52494646 1A000000 57415645 66616374 04000000 E0010000 4A554E4B 01000000 0000",
+        );
+        let mut output = Cursor::new(Vec::new());
+        inject_chunk(input, &mut output, FourCC(*b"fact"), &[1, 0, 0, 0]).unwrap();
+        let data = output.into_inner();
+        assert_eq!(
+            ids_and_sizes(data.clone()),
+            vec![(FourCC(*b"fact"), 4), (FourCC(*b"JUNK"), 1)]
+        );
+        let mut wave = WaveFile::from_reader(Cursor::new(data)).unwrap();
+        let Some(Ok(SizedChunkEnum::Fact(fact))) = wave.iter_chunks().next() else {
+            unreachable!("first chunk should have been fact")
+        };
+        assert_eq!(fact.data.samples, 1);
+    }
+
+    #[test]
+    fn chunk_changes_between_versions() {
+        // fact chunk followed by 16 bytes of JUNK
+        let original = hex_to_cursor(
+            "52494646 28000000 57415645 66616374 04000000 E0010000
+             4A554E4B 10000000 00000000 00000000 00000000 00000000",
+        )
+        .into_inner();
+        let changes_to = |data: &[u8]| {
+            let mut before = WaveFile::from_reader(Cursor::new(original.clone())).unwrap();
+            let mut after = WaveFile::from_reader(Cursor::new(data.to_vec())).unwrap();
+            let changes = chunk_changes(&mut before, &mut after).unwrap();
+            dbg!(&changes);
+            changes.iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        assert!(changes_to(&original).is_empty());
+
+        let mut file = Cursor::new(original.clone());
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[1; 12]).unwrap());
+        assert_eq!(
+            changes_to(file.get_ref()),
+            [
+                "resize fact, 4 -> 12 bytes (+8)",
+                "resize JUNK, 16 -> 8 bytes (-8)"
+            ]
+        );
+
+        let mut file = Cursor::new(original.clone());
+        assert!(inject_chunk_in_place(&mut file, FourCC(*b"fact"), &[1; 4]).unwrap());
+        assert_eq!(changes_to(file.get_ref()), ["modify fact, 4 bytes"]);
+
+        let mut output = Cursor::new(Vec::new());
+        reorder(
+            Cursor::new(original.clone()),
+            &mut output,
+            &WritePolicy::broadcast(),
+        )
+        .unwrap();
+        // new reservation before fact, the old JUNK is kept
+        assert_eq!(
+            changes_to(output.get_ref()),
+            ["resize JUNK, 16 -> 28 bytes (+12)", "add JUNK, 16 bytes"]
+        );
+
+        let mut output = Cursor::new(Vec::new());
+        inject_chunks(
+            Cursor::new(original.clone()),
+            &mut output,
+            &[(FourCC(*b"LIST"), b"INFO")],
+        )
+        .unwrap();
+        assert_eq!(changes_to(output.get_ref()), ["add LIST INFO, 4 bytes"]);
+    }
+}
@@ -0,0 +1,307 @@
+//! Content hashes of chunks and audio data, for finding duplicates and
+//! changes across archives.
+//!
+//! [`fingerprints()`] hashes the bytes of every chunk as stored in the file,
+//! and separately the audio in the `data` chunk. Two files with the same
+//! audio hash contain the same audio, even if their metadata differs.
+//!
+//! Hashes are 64 bit [XXH64](https://xxhash.com/) with seed 0, the same
+//! values as other XXH64 implementations. They are not cryptographic hashes.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let fingerprints = wavrw::fingerprint::fingerprints(&mut wave)?;
+//! for chunk in &fingerprints.chunks {
+//!     println!("{chunk}");
+//! }
+//! assert!(fingerprints.audio.is_some());
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, Read, Seek, SeekFrom};
+
+use crate::chunk::data::Data;
+use crate::{FourCC, KnownChunkID, WaveFile, WaveFileError};
+
+/// Hash of one chunk, see [`fingerprints()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkFingerprint {
+    /// RIFF chunk id.
+    pub id: FourCC,
+
+    /// List type of `LIST` chunks. Ex: `INFO`
+    pub list_type: Option<FourCC>,
+
+    /// Byte offset of the chunk header.
+    pub offset: u64,
+
+    /// RIFF chunk size in bytes.
+    pub size: u32,
+
+    /// XXH64 of the chunk id, size and data, without the padding byte.
+    pub hash: u64,
+}
+
+impl Display for ChunkFingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self.list_type {
+            Some(list_type) => format!("{}-{list_type}", self.id),
+            None => self.id.to_string().trim().to_string(),
+        };
+        write!(f, "{:016x} {name} at {}", self.hash, self.offset)
+    }
+}
+
+/// Hashes of all chunks of a file, see [`fingerprints()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Fingerprints {
+    /// One hash per chunk, in file order.
+    pub chunks: Vec<ChunkFingerprint>,
+
+    /// XXH64 of the audio in the first `data` chunk, without its header.
+    pub audio: Option<u64>,
+}
+
+/// Hash every chunk of `wave`, and the audio in its `data` chunk.
+///
+/// Chunks are not parsed, so chunks wavrw can't parse are hashed too.
+pub fn fingerprints<R>(wave: &mut WaveFile<R>) -> Result<Fingerprints, WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    let mut fingerprints = Fingerprints::default();
+    for lazy in wave.scan_chunks()? {
+        wave.bytes.seek(SeekFrom::Start(lazy.offset))?;
+        let mut header = [0_u8; 8];
+        wave.bytes.read_exact(&mut header)?;
+        let mut hasher = Xxh64::new(0);
+        hasher.update(&header);
+        let mut audio =
+            (lazy.id == Data::ID && fingerprints.audio.is_none()).then(|| Xxh64::new(0));
+
+        // hash what is there if the file is cut short
+        let mut reader = (&mut wave.bytes).take(lazy.large_size());
+        let mut buff = vec![0_u8; 64 * 1024];
+        loop {
+            let len = reader.read(&mut buff)?;
+            if len == 0 {
+                break;
+            }
+            hasher.update(&buff[..len]);
+            if let Some(audio) = &mut audio {
+                audio.update(&buff[..len]);
+            }
+        }
+
+        if let Some(audio) = audio {
+            fingerprints.audio = Some(audio.finish());
+        }
+        fingerprints.chunks.push(ChunkFingerprint {
+            id: lazy.id,
+            list_type: lazy.list_type,
+            offset: lazy.offset,
+            size: lazy.size,
+            hash: hasher.finish(),
+        });
+    }
+    Ok(fingerprints)
+}
+
+const PRIME64_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME64_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME64_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME64_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME64_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Streaming [XXH64](https://github.com/Cyan4973/xxHash/blob/dev/doc/xxhash_spec.md) hasher.
+///
+/// ```
+/// use wavrw::fingerprint::Xxh64;
+///
+/// let mut hasher = Xxh64::new(0);
+/// hasher.update(b"ab");
+/// hasher.update(b"c");
+/// assert_eq!(hasher.finish(), 0x44BC_2CF5_AD77_0999);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Xxh64 {
+    seed: u64,
+    accumulators: [u64; 4],
+    buffer: [u8; 32],
+    buffered: usize,
+    total_len: u64,
+}
+
+impl Xxh64 {
+    /// Create a hasher with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Xxh64 {
+            seed,
+            accumulators: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            buffer: [0; 32],
+            buffered: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Add `bytes` to the hash.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.total_len += bytes.len() as u64;
+        if self.buffered > 0 {
+            let take = bytes.len().min(32 - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&bytes[..take]);
+            self.buffered += take;
+            bytes = &bytes[take..];
+            if self.buffered < 32 {
+                return;
+            }
+            let stripe = self.buffer;
+            self.stripe(&stripe);
+            self.buffered = 0;
+        }
+        let mut stripes = bytes.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.stripe(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    /// The hash of all bytes added so far.
+    pub fn finish(&self) -> u64 {
+        let mut hash = if self.total_len >= 32 {
+            let [v1, v2, v3, v4] = self.accumulators;
+            let mut hash = v1
+                .rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for v in self.accumulators {
+                hash = merge(hash, v);
+            }
+            hash
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        hash = hash.wrapping_add(self.total_len);
+
+        let mut rest = &self.buffer[..self.buffered];
+        while rest.len() >= 8 {
+            let (lane, tail) = rest.split_at(8);
+            hash ^= round(0, read_u64(lane));
+            hash = hash
+                .rotate_left(27)
+                .wrapping_mul(PRIME64_1)
+                .wrapping_add(PRIME64_4);
+            rest = tail;
+        }
+        if rest.len() >= 4 {
+            let (lane, tail) = rest.split_at(4);
+            let lane = u32::from_le_bytes([lane[0], lane[1], lane[2], lane[3]]);
+            hash ^= u64::from(lane).wrapping_mul(PRIME64_1);
+            hash = hash
+                .rotate_left(23)
+                .wrapping_mul(PRIME64_2)
+                .wrapping_add(PRIME64_3);
+            rest = tail;
+        }
+        for byte in rest {
+            hash ^= u64::from(*byte).wrapping_mul(PRIME64_5);
+            hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(PRIME64_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(PRIME64_3);
+        hash ^ (hash >> 32)
+    }
+
+    /// Process one 32 byte stripe.
+    fn stripe(&mut self, stripe: &[u8]) {
+        for (accumulator, lane) in self.accumulators.iter_mut().zip(stripe.chunks_exact(8)) {
+            *accumulator = round(*accumulator, read_u64(lane));
+        }
+    }
+}
+
+fn round(accumulator: u64, lane: u64) -> u64 {
+    accumulator
+        .wrapping_add(lane.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+fn merge(hash: u64, accumulator: u64) -> u64 {
+    (hash ^ round(0, accumulator))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+fn read_u64(lane: &[u8]) -> u64 {
+    let mut bytes = [0_u8; 8];
+    bytes.copy_from_slice(&lane[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+
+    fn xxh64(bytes: &[u8]) -> u64 {
+        let mut hasher = Xxh64::new(0);
+        hasher.update(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn xxh64_reference_values() {
+        assert_eq!(xxh64(b""), 0xEF46_DB37_51D8_E999);
+        assert_eq!(xxh64(b"abc"), 0x44BC_2CF5_AD77_0999);
+        assert_eq!(
+            xxh64(b"Nobody inspects the spammish repetition"),
+            0xFBCE_A83C_8A37_8BF1
+        );
+
+        // same result when fed in pieces
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut hasher = Xxh64::new(0);
+        for piece in bytes.chunks(7) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finish(), xxh64(&bytes));
+    }
+
+    #[test]
+    fn fingerprints_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let fingerprints = fingerprints(&mut wave).unwrap();
+        dbg!(&fingerprints);
+        assert_eq!(fingerprints.chunks.len(), 12);
+        assert_eq!(fingerprints.chunks[0].id, FourCC(*b"fmt "));
+
+        // chunk hash covers the header, audio hash only the samples
+        let data = fingerprints
+            .chunks
+            .iter()
+            .find(|c| c.id == Data::ID)
+            .unwrap();
+        assert_ne!(Some(data.hash), fingerprints.audio);
+        assert_eq!(fingerprints, super::fingerprints(&mut wave).unwrap());
+    }
+}
@@ -1,14 +1,19 @@
 #![doc = include_str!("fixedstring.md")]
 
-use alloc::string::FromUtf8Error;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{FromUtf8Error, String, ToString};
+use alloc::vec::Vec;
 use core::cmp::min;
+use core::error::Error;
 use core::fmt::{Debug, Display, Formatter};
 use core::str::FromStr;
-use std::error::Error;
 
 use binrw::io::{Read, Seek, SeekFrom};
 use binrw::{BinRead, BinResult, BinWrite, Endian};
 
+use crate::codepage;
+
 #[derive(Debug, Clone, PartialEq)]
 /// Errors when creating a [`FixedString`].
 pub enum FixedStringError {
@@ -20,6 +25,12 @@ pub enum FixedStringError {
         len: usize,
     },
 
+    /// Input string contains a zero byte, which would end the string early.
+    InteriorNul {
+        /// Byte offset of the zero byte.
+        index: usize,
+    },
+
     /// Input data not valid UTF-8.
     FromUtf8Error {
         /// Embedded [`alloc::string::FromUtf8Error`];
@@ -32,7 +43,7 @@ pub enum FixedStringError {
 impl Error for FixedStringError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match &self {
-            FixedStringError::Truncated { .. } => None,
+            FixedStringError::Truncated { .. } | FixedStringError::InteriorNul { .. } => None,
             FixedStringError::FromUtf8Error { source, .. } => Some(source),
         }
     }
@@ -44,6 +55,9 @@ impl Display for FixedStringError {
             Self::Truncated { limit, len } => {
                 write!(f, "truncated string of length {} at {}", len, limit)
             }
+            Self::InteriorNul { index } => {
+                write!(f, "string contains a zero byte at {}", index)
+            }
             Self::FromUtf8Error {
                 source: err,
                 context,
@@ -65,16 +79,19 @@ impl From<FromUtf8Error> for FixedStringError {
 
 #[doc = include_str!("fixedstring.md")]
 #[derive(Clone, PartialEq, Eq, Hash)]
-pub struct FixedString<const N: usize>(String);
-// This is only immutable because it would be a lot of work to correctly DeRef
-// to the inner string while still enforcing the length constraint. Design
-// quesion: is it worth the work? Maybe if it turns out to be annoying to work
-// with them?
+pub struct FixedString<const N: usize>(String, Vec<u8>);
+// The second field holds the bytes the string was created from, so text can be
+// decoded again with a different code page.
+//
+// The contents can only be replaced as a whole, via `set()`, because it would
+// be a lot of work to correctly DeRef to the inner string while still
+// enforcing the length constraint. Design quesion: is it worth the work? Maybe
+// if it turns out to be annoying to work with them?
 
 impl<const N: usize> Debug for FixedString<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_tuple(&format!("FixedString::<{}>", N))
-            .field(&self.to_string())
+            .field(&self.0)
             .finish()
     }
 }
@@ -96,6 +113,89 @@ impl<const N: usize> FixedString<N> {
         !N == 0
     }
 
+    /// Create a `FixedString` from `s`, checking it fits.
+    ///
+    /// Returns [`FixedStringError::Truncated`] if `s` is longer than N bytes
+    /// and [`FixedStringError::InteriorNul`] if it contains a zero byte,
+    /// which would end the string early when read back. Same as
+    /// [`FromStr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::fixedstring::{FixedString, FixedStringError};
+    ///
+    /// let fs = FixedString::<6>::try_from_str("abc")?;
+    /// assert_eq!(fs.to_string(), "abc");
+    ///
+    /// let err = FixedString::<6>::try_from_str("this is too long");
+    /// assert_eq!(err, Err(FixedStringError::Truncated { limit: 6, len: 16 }));
+    ///
+    /// let err = FixedString::<6>::try_from_str("a\0b");
+    /// assert_eq!(err, Err(FixedStringError::InteriorNul { index: 1 }));
+    /// # Ok::<(), wavrw::fixedstring::FixedStringError>(())
+    /// ```
+    pub fn try_from_str(s: &str) -> Result<Self, FixedStringError> {
+        if s.len() > N {
+            return Err(FixedStringError::Truncated {
+                limit: N,
+                len: s.len(),
+            });
+        }
+        if let Some(index) = s.find('\0') {
+            return Err(FixedStringError::InteriorNul { index });
+        }
+        Ok(FixedString(s.to_string(), s.as_bytes().to_vec()))
+    }
+
+    /// Create a `FixedString` from `s`, cutting it to fit.
+    ///
+    /// The string ends at the first zero byte, and is truncated to at most N
+    /// bytes without splitting a character. Prefer
+    /// [`FixedString::try_from_str()`] when losing text matters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::fixedstring::FixedString;
+    ///
+    /// let fs = FixedString::<6>::from_str_lossy("this is too long");
+    /// assert_eq!(fs.to_string(), "this i");
+    ///
+    /// // "é" is two bytes, and is dropped rather than split
+    /// let fs = FixedString::<6>::from_str_lossy("café au lait");
+    /// assert_eq!(fs.to_string(), "café ");
+    /// ```
+    pub fn from_str_lossy(s: &str) -> Self {
+        let s = s.split('\0').next().unwrap_or_default();
+        let mut end = min(s.len(), N);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        let s = s.get(..end).unwrap_or_default();
+        FixedString(s.to_string(), s.as_bytes().to_vec())
+    }
+
+    /// Replace the contents with `s`, see [`FixedString::try_from_str()`].
+    ///
+    /// On error, the contents are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::bext::Bext;
+    ///
+    /// let mut bext = Bext::default();
+    /// bext.description.set("Rain on a tin roof")?;
+    /// assert!(bext.originator.set("a name which is longer than 32 bytes").is_err());
+    /// assert_eq!(bext.originator.to_string(), "");
+    /// # Ok::<(), wavrw::fixedstring::FixedStringError>(())
+    /// ```
+    pub fn set(&mut self, s: &str) -> Result<(), FixedStringError> {
+        *self = Self::try_from_str(s)?;
+        Ok(())
+    }
+
     /// Convert UTF-8 bytes into a `FixedString`.
     ///
     /// # Examples
@@ -140,13 +240,91 @@ impl<const N: usize> FixedString<N> {
         }
         let s = alloc::string::String::from_utf8(vec)?;
         let s = s.trim_end_matches('\0').to_string();
-        Ok(Self(s))
+        let bytes = s.as_bytes().to_vec();
+        Ok(Self(s, bytes))
+    }
+
+    /// Convert bytes into a `FixedString`, decoding invalid UTF-8 as Latin-1.
+    ///
+    /// The original bytes are kept, see [`FixedString::decode()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::fixedstring::FixedString;
+    ///
+    /// // "café" in Latin-1
+    /// let fs = FixedString::<6>::from_bytes(vec![99, 97, 102, 233])?;
+    /// assert_eq!(fs.to_string(), "café");
+    /// # Ok::<(), wavrw::fixedstring::FixedStringError>(())
+    /// ```
+    pub fn from_bytes(mut vec: Vec<u8>) -> Result<Self, FixedStringError> {
+        if vec.len() > N {
+            return Err(FixedStringError::Truncated {
+                limit: N,
+                len: vec.len(),
+            });
+        }
+        let end = vec.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+        vec.truncate(end);
+        Ok(Self(codepage::decode(&vec, None), vec))
+    }
+
+    /// The bytes this string was created from, without padding.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.1
+    }
+
+    /// Decode the original bytes using `code_page`, see [`codepage::decode()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::fixedstring::FixedString;
+    ///
+    /// // "Привет" in Windows-1251
+    /// let fs = FixedString::<6>::from_bytes(vec![0xCF, 0xF0, 0xE8, 0xE2, 0xE5, 0xF2])?;
+    /// assert_eq!(fs.decode(1251), "Привет");
+    /// # Ok::<(), wavrw::fixedstring::FixedStringError>(())
+    /// ```
+    pub fn decode(&self, code_page: u16) -> String {
+        codepage::decode(&self.1, Some(code_page))
+    }
+
+    /// Guess the encoding of the original bytes, see [`codepage::detect()`].
+    ///
+    /// Returns `None` for valid UTF-8, and if a code page changed the
+    /// decoded text, see [`FixedString::decode()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::codepage::WINDOWS_1252;
+    /// use wavrw::fixedstring::FixedString;
+    ///
+    /// // "café" in Windows-1252
+    /// let fs = FixedString::<6>::from_bytes(vec![99, 97, 102, 233])?;
+    /// assert_eq!(fs.detect_encoding().unwrap().code_page, WINDOWS_1252);
+    /// # Ok::<(), wavrw::fixedstring::FixedStringError>(())
+    /// ```
+    pub fn detect_encoding(&self) -> Option<codepage::Detection> {
+        if core::str::from_utf8(&self.1).is_ok() || codepage::decode(&self.1, None) != self.0 {
+            return None;
+        }
+        codepage::detect(&self.1)
+    }
+
+    /// Replace the string with the original bytes decoded using `code_page`.
+    #[cfg_attr(not(feature = "broadcast"), allow(dead_code))]
+    pub(crate) fn set_code_page(&mut self, code_page: u16) {
+        self.0 = self.decode(code_page);
     }
 
     /// Create a new [u8; N] from &self
     ///
-    /// The array contains UTF-8 encoded bytes from the string followed by enough
-    /// zero padding to fill the array.
+    /// The array contains the bytes the string was created from (UTF-8 unless
+    /// read from data in another encoding) followed by enough zero padding to
+    /// fill the array.
     ///
     /// # Examples
     ///
@@ -163,7 +341,7 @@ impl<const N: usize> FixedString<N> {
     /// ```
     pub fn to_bytes(&self) -> [u8; N] {
         let mut array_tmp = [0u8; N];
-        let bytes = self.0.as_bytes();
+        let bytes = &self.1;
         let l = min(bytes.len(), N);
         array_tmp[..l].copy_from_slice(&bytes[..l]);
         array_tmp
@@ -172,7 +350,7 @@ impl<const N: usize> FixedString<N> {
 
 impl<const N: usize> Default for FixedString<N> {
     fn default() -> Self {
-        FixedString::<N>(String::new())
+        FixedString::<N>(String::new(), Vec::new())
     }
 }
 
@@ -180,13 +358,7 @@ impl<const N: usize> FromStr for FixedString<N> {
     type Err = FixedStringError;
 
     fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
-        if s.len() > N {
-            return Err(FixedStringError::Truncated {
-                limit: N,
-                len: s.len(),
-            });
-        }
-        Ok(FixedString(s.to_string()))
+        Self::try_from_str(s)
     }
 }
 
@@ -203,7 +375,7 @@ impl<const N: usize> BinRead for FixedString<N> {
 
         loop {
             if index >= N {
-                return match Self::from_utf8(values.to_vec()) {
+                return match Self::from_bytes(values.to_vec()) {
                     Ok(fs) => Ok(fs),
                     Err(err) => Err(binrw::Error::Custom {
                         pos: index as u64,
@@ -217,7 +389,7 @@ impl<const N: usize> BinRead for FixedString<N> {
                 return match offset {
                     Ok(offset) => {
                         reader.seek(SeekFrom::Current(offset))?;
-                        match Self::from_utf8(values.to_vec()) {
+                        match Self::from_bytes(values.to_vec()) {
                             Ok(fs) => Ok(fs),
                             Err(err) => Err(binrw::Error::Custom {
                                 pos: index as u64,
@@ -240,13 +412,13 @@ impl<const N: usize> BinRead for FixedString<N> {
 impl<const N: usize> BinWrite for FixedString<N> {
     type Args<'a> = ();
 
-    fn write_options<W: std::io::prelude::Write + Seek>(
+    fn write_options<W: binrw::io::Write + Seek>(
         &self,
         writer: &mut W,
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        let bytes = self.0.as_bytes();
+        let bytes = &self.1;
         let padding_size = N - bytes.len();
         bytes.write_options(writer, endian, args)?;
         for _ in 0..padding_size {
@@ -266,7 +438,7 @@ mod test {
 
     #[test]
     fn fixed_string() {
-        let fs = FixedString::<6>("abc".to_string());
+        let fs = FixedString::<6>("abc".to_string(), b"abc".to_vec());
         assert_eq!(6, fs.len());
         let s = fs.to_string();
         assert_eq!("abc".to_string(), s);
@@ -289,10 +461,11 @@ mod test {
     fn fixed_string_long() {
         // strings longer than fixed size should get truncated
 
-        // initializing with ::new() truncates without error
-        // let long_str = "this is a longer str";
-        // let fs = FixedString::<6>::new(long_str);
-        // assert_eq!(fs.to_string(), "this i");
+        // initializing with ::from_str_lossy() truncates without error
+        let long_str = "this is a longer str";
+        let fs = FixedString::<6>::from_str_lossy(long_str);
+        assert_eq!(fs.to_string(), "this i");
+        assert_eq!(fs.to_bytes(), *b"this i");
 
         // via FromStr returns an error
         let long_str = "this is a longer str";
@@ -304,6 +477,21 @@ mod test {
         assert_eq!(err, Err(FixedStringError::Truncated { limit: 6, len: 20 }));
     }
 
+    #[test]
+    fn fixed_string_interior_nul() {
+        let err = FixedString::<6>::from_str("ab\0cd");
+        assert_eq!(err, Err(FixedStringError::InteriorNul { index: 2 }));
+
+        let fs = FixedString::<6>::from_str_lossy("ab\0cd");
+        assert_eq!(fs.to_string(), "ab");
+
+        let mut fs = FixedString::<6>::from_str("abc").unwrap();
+        assert!(fs.set("ab\0cd").is_err());
+        assert_eq!(fs.to_string(), "abc");
+        fs.set("xyz").unwrap();
+        assert_eq!(fs.to_string(), "xyz");
+    }
+
     #[test]
     fn parse_fixedstr_data_after_zero() {
         // REAPER had (still has?) a bug where data from other BEXT fields
@@ -370,4 +558,17 @@ mod test {
             1
         );
     }
+
+    #[test]
+    fn parse_fixedstr_not_utf8() {
+        // "Größe" in Windows-1252, followed by padding
+        let mut buff = hex_to_cursor("4772F6DF 65000000");
+        let mut fs = FixedString::<8>::read_options(&mut buff, binrw::Endian::Big, ())
+            .expect("error parsing FixedString");
+        assert_eq!(fs.to_string(), "Größe");
+        assert_eq!(fs.as_bytes(), [0x47, 0x72, 0xF6, 0xDF, 0x65]);
+        assert_eq!(fs.to_bytes(), [0x47, 0x72, 0xF6, 0xDF, 0x65, 0, 0, 0]);
+        fs.set_code_page(1251);
+        assert_eq!(fs.to_string(), "GrцЯe");
+    }
 }
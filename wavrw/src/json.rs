@@ -0,0 +1,377 @@
+//! Minimal JSON reader and writer for metadata templates and sidecars.
+//!
+//! Objects keep their keys in document order and allow duplicates, the
+//! first one wins in [`Value::get()`]. Numbers are kept as their text, so
+//! 64 bit sample counts survive unchanged.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error;
+use core::fmt::{Display, Formatter};
+
+/// A JSON value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    /// The number as written in the document.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    /// (key, value) pairs in document order.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parse `document`, which must hold exactly one value.
+    pub fn parse(document: &str) -> Result<Value, JsonError> {
+        let mut parser = Parser {
+            input: document,
+            pos: 0,
+        };
+        parser.skip_whitespace();
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos < parser.input.len() {
+            return Err(parser.error("content after value"));
+        }
+        Ok(value)
+    }
+
+    /// Value of the first `key` of an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) | Value::Array(_) => {
+                None
+            }
+        }
+    }
+
+    /// Text of a string, number or bool.
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            Value::String(text) | Value::Number(text) => Some(text.clone()),
+            Value::Bool(value) => Some(value.to_string()),
+            Value::Null | Value::Array(_) | Value::Object(_) => None,
+        }
+    }
+
+    /// Write this value as a document, nested values indented by two
+    /// spaces.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Value::Number(text) => out.push_str(text),
+            Value::String(text) => write_string(out, text),
+            Value::Array(values) if values.is_empty() => out.push_str("[]"),
+            Value::Array(values) => {
+                out.push_str("[\n");
+                for (index, value) in values.iter().enumerate() {
+                    out.push_str(&indent);
+                    value.write(out, depth + 1);
+                    out.push_str(if index + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(depth));
+                out.push(']');
+            }
+            Value::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Value::Object(entries) => {
+                out.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(&indent);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                    out.push_str(if index + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                out.push_str(&"  ".repeat(depth));
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Write `text` as a quoted JSON string.
+fn write_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Error from [`Value::parse()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JsonError {
+    /// Byte offset in the document.
+    pub pos: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid JSON at byte {}: {}", self.pos, self.message)
+    }
+}
+
+impl error::Error for JsonError {}
+
+/// Nesting limit, to fail cleanly on hostile documents.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        self.input.get(self.pos..).unwrap_or_default()
+    }
+
+    fn error(&self, message: &str) -> JsonError {
+        JsonError {
+            pos: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consume `token` if the rest starts with it.
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        match self.rest().chars().next() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('-' | '0'..='9') => Ok(Value::Number(self.number()?)),
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            _ if self.eat("null") => Ok(Value::Null),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, JsonError> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Value::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if !self.rest().starts_with('"') {
+                return Err(self.error("expected a quoted key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("expected ':' after key"));
+            }
+            self.skip_whitespace();
+            entries.push((key, self.value(depth + 1)?));
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Value::Object(entries));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, JsonError> {
+        self.pos += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Value::Array(values));
+        }
+        loop {
+            self.skip_whitespace();
+            values.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Value::Array(values));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<String, JsonError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(rest.len());
+        let text = rest.get(..len).unwrap_or_default();
+        if text.parse::<f64>().is_err() {
+            return Err(self.error(&format!("invalid number {text:?}")));
+        }
+        let text = text.to_string();
+        self.pos += len;
+        Ok(text)
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let mut chars = self.rest().chars();
+            match chars.next() {
+                None => return Err(self.error("missing closing '\"'")),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    let escape = chars.next();
+                    self.pos += 2;
+                    match escape {
+                        Some('"') => text.push('"'),
+                        Some('\\') => text.push('\\'),
+                        Some('/') => text.push('/'),
+                        Some('b') => text.push('\u{8}'),
+                        Some('f') => text.push('\u{c}'),
+                        Some('n') => text.push('\n'),
+                        Some('r') => text.push('\r'),
+                        Some('t') => text.push('\t'),
+                        Some('u') => text.push(self.unicode_escape()?),
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                }
+                Some(c) if u32::from(c) < 0x20 => {
+                    return Err(self.error("control character in string"));
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    /// The character of a `\u` escape, after the `u`, including surrogate
+    /// pairs.
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.eat("\\u") {
+                return Err(self.error("missing low surrogate"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid low surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let digits = self.rest().get(..4).unwrap_or_default();
+        let value = u32::from_str_radix(digits, 16)
+            .map_err(|_| self.error("expected 4 hex digits"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let value = Value::parse(
+            r#" {"bext": {"description": "a \"b\"\n\u00e9\ud83d\ude00", "time_reference": 18446744073709551615},
+                "list": [1, -2.5e3, true, false, null], "empty": {}} "#,
+        )
+        .unwrap();
+        dbg!(&value);
+        let bext = value.get("bext").unwrap();
+        assert_eq!(
+            bext.get("description").unwrap().as_text().unwrap(),
+            "a \"b\"\né😀"
+        );
+        assert_eq!(
+            bext.get("time_reference").unwrap().as_text().unwrap(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            value.get("list"),
+            Some(&Value::Array(vec![
+                Value::Number("1".into()),
+                Value::Number("-2.5e3".into()),
+                Value::Bool(true),
+                Value::Bool(false),
+                Value::Null,
+            ]))
+        );
+        assert_eq!(value.get("empty"), Some(&Value::Object(vec![])));
+
+        assert!(Value::parse("{\"a\": 1,}").is_err());
+        assert!(Value::parse("[1] 2").is_err());
+        assert!(Value::parse("\"\\ud83d\"").is_err());
+        assert!(Value::parse(&"[".repeat(100)).is_err());
+    }
+
+    #[test]
+    fn write_document() {
+        let value = Value::Object(vec![
+            ("text".into(), Value::String("tab\there \"q\" \u{1}".into())),
+            (
+                "list".into(),
+                Value::Array(vec![Value::Number("1".into()), Value::Null]),
+            ),
+            ("empty".into(), Value::Array(vec![])),
+        ]);
+        let json = value.to_json();
+        dbg!(&json);
+        assert_eq!(
+            json,
+            "{\n  \"text\": \"tab\\there \\\"q\\\" \\u0001\",\n  \"list\": [\n    1,\n    null\n  ],\n  \"empty\": []\n}\n"
+        );
+        assert_eq!(Value::parse(&json).unwrap(), value);
+    }
+}
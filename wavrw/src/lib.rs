@@ -63,46 +63,156 @@
 //! characters are valid UTF8, and writing UTF8 strings appears to be common
 //! practice in applications which write metadata.
 //!
-//! WARNING: This library does not attempt to interpret strings according to code
-//! page settings specified via CSET. Setting character set information in CSET
-//! chunks appears to be very rare, however if a file *did* specify an extended
-//! codepage, text would likely be misinterpreted when decoded as UTF8. If you
-//! do run into this situation, please consider filing an issue and if possible,
-//! sharing sample files to test against so I can improve codepage handling.
+//! Text which is not valid UTF8 is decoded as Latin-1 instead, with a warning
+//! naming the likely encoding, see [`codepage::detect()`]. If a file has a
+//! `CSET` chunk, `INFO` and `bext` text is decoded using its code page, which
+//! can also be set with [`ParseOptions::code_page`]. See [`codepage`] for
+//! supported code pages. Setting character set information in CSET chunks
+//! appears to be very rare. If you run into files using an unsupported code
+//! page, please consider filing an issue and if possible, sharing sample files
+//! to test against so I can improve codepage handling.
+//!
+//! The `std` feature, enabled by default, provides [`WaveFile`] and the file
+//! based modules: [`edit`], [`marker`] and [`scan`]. Without it, the chunk
+//! parsers, [`SizedChunkEnum`] and the [`metadata`] and [`resolve`] helpers
+//! work in `no_std` environments with `alloc`.
+//!
+//! Chunk families can be left out of small builds, these features are also
+//! enabled by default: `broadcast` (`bext`), `info` (`LIST-INFO`) and `ixml`
+//! (`iXML` and its XML parser). Chunks of disabled families are parsed as
+//! [`UnknownChunk`]s.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::default::Default;
 use core::fmt::{Debug, Display, Formatter};
-use std::error;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
 use std::io::BufRead;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 use binrw::io::TakeSeekExt;
+#[cfg(feature = "std")]
 use binrw::io::{Read, Seek};
-use binrw::{binrw, io::SeekFrom, BinRead, BinWrite, PosValue};
-use tracing::{instrument, warn};
+use binrw::{binrw, io::SeekFrom, BinRead, BinResult, BinWrite, PosValue};
+use itertools::Itertools;
+#[cfg(feature = "std")]
+use tracing::{debug_span, instrument, warn};
 
+pub mod aiff;
+pub mod catalog;
 pub mod chunk;
+pub mod codepage;
+#[cfg(feature = "std")]
+pub mod container;
+#[cfg(feature = "std")]
+pub use container::{open, AudioFile, Container};
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod display;
 use crate::chunk::adtl::ListAdtlChunk;
+#[cfg(feature = "broadcast")]
 use crate::chunk::bext::BextChunk;
+#[cfg(feature = "std")]
+use crate::chunk::cset::Cset;
 use crate::chunk::cset::CsetChunk;
 use crate::chunk::cue::CueChunk;
+#[cfg(feature = "std")]
+use crate::chunk::data::Data;
 use crate::chunk::data::DataChunk;
+use crate::chunk::disp::DispChunk;
+use crate::chunk::ds64::Ds64Chunk;
+#[cfg(feature = "std")]
+use crate::chunk::ds64::{Ds64, LargeSizes};
 use crate::chunk::fact::FactChunk;
 use crate::chunk::fmt::FmtChunk;
+#[cfg(feature = "info")]
 use crate::chunk::info::ListInfoChunk;
 use crate::chunk::inst::InstChunk;
+#[cfg(feature = "ixml")]
 use crate::chunk::ixml::IxmlChunk;
 use crate::chunk::junk::FllrChunk;
 use crate::chunk::junk::JunkChunk;
 use crate::chunk::junk::PadChunk;
+use crate::chunk::levl::LevlChunk;
 use crate::chunk::md5::Md5Chunk;
 use crate::chunk::plst::PlstChunk;
+#[cfg(feature = "std")]
 use crate::chunk::riff::RiffChunk;
 use crate::chunk::smpl::SmplChunk;
+use crate::chunk::soundminer::{SmedChunk, SmrdChunk};
 use crate::chunk::wavl::ListWavlChunk;
+use crate::warning::Code;
+pub use display::DisplayOptions;
+#[cfg(feature = "std")]
+pub mod edit;
+#[cfg(feature = "std")]
+pub mod fingerprint;
 pub mod fixedstring;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "loudness")]
+pub mod loudness;
+#[cfg(feature = "std")]
+pub mod marker;
+#[cfg(feature = "std")]
+pub mod merge;
+pub mod metadata;
+#[cfg(all(feature = "std", feature = "broadcast", feature = "info"))]
+pub mod metaedit;
+#[cfg(feature = "std")]
+pub mod overview;
+#[cfg(feature = "std")]
+pub use overview::Overview;
+#[cfg(feature = "std")]
+pub mod peaks;
+#[cfg(feature = "broadcast")]
+pub mod profile;
+pub mod query;
+#[cfg(feature = "std")]
+pub use marker::{markers, Marker};
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod report;
+pub use metadata::WaveMetadata;
+pub mod resolve;
+pub use resolve::ResolvedWave;
+#[cfg(feature = "std")]
+pub mod roundtrip;
+#[cfg(feature = "std")]
+pub mod samples;
+pub mod sanitize;
+#[cfg(feature = "std")]
+pub mod scan;
+pub mod schema;
+pub use schema::{schema, schema_version};
+#[cfg(feature = "std")]
+pub mod sidecar;
+#[cfg(feature = "std")]
+pub mod split;
+pub mod stats;
+pub use stats::ParseStats;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod template;
 pub mod testing;
+pub mod time;
+pub mod ucs;
+pub mod umid;
+pub mod warning;
+pub use warning::Warning;
+#[cfg(any(feature = "ixml", feature = "std"))]
+mod xml;
 
 // helper types
 // ----
@@ -138,6 +248,47 @@ pub trait SizedChunk: Summarizable + Debug {
 
     /// The byte offset from the start of the read data stream.
     fn offset(&self) -> Option<u64>;
+
+    /// The size in bytes, 64 bit sizes from a `ds64` chunk included.
+    ///
+    /// In RF64 files chunks larger than 4 GiB have a `size()` of 0xFFFFFFFF
+    /// and their real size in the `ds64` chunk, see
+    /// [`chunk::ds64::LargeSizes`]. Otherwise the same as `size()`.
+    fn large_size(&self) -> u64 {
+        u64::from(self.size())
+    }
+
+    /// The byte offset of the chunk data, after the 8 byte id and size
+    /// header.
+    fn data_offset(&self) -> Option<u64> {
+        self.offset().map(|offset| offset + 8)
+    }
+
+    /// The number of bytes the chunk takes up in the file: header, data and
+    /// the padding byte after odd sized chunks.
+    ///
+    /// The next chunk starts at `offset() + total_size()`. Chunks read from
+    /// a file without their padding byte don't count it.
+    fn total_size(&self) -> u64 {
+        let size = self.large_size();
+        8 + size + size % 2
+    }
+
+    /// The padding byte after an odd sized chunk, usually 0, which keeps
+    /// the next chunk on an even offset.
+    ///
+    /// `None` for even sized chunks, chunks not read from a file and odd
+    /// sized chunks without a padding byte, see
+    /// [`ParseOptions::allow_missing_padding`].
+    fn padding(&self) -> Option<u8> {
+        None
+    }
+}
+
+/// [`SizedChunk::total_size()`] of a chunk which records its padding byte.
+fn padded_total_size(large_size: u64, offset: Option<u64>, padding: Option<u8>) -> u64 {
+    let missing = offset.is_some() && padding.is_none();
+    8 + large_size + if missing { 0 } else { large_size % 2 }
 }
 
 /// Utility methods for describing any chunk.
@@ -145,6 +296,15 @@ pub trait Summarizable: ChunkID {
     /// Returns a short text summary of the contents of the chunk.
     fn summary(&self) -> String;
 
+    /// Returns [`summary()`](Summarizable::summary) rendered with `options`.
+    ///
+    /// The default only applies [`DisplayOptions::max_string_length`].
+    /// Chunks which show sample counts override it to apply
+    /// [`DisplayOptions::units`].
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        options.text(&self.summary())
+    }
+
     /// User friendly name of the chunk, usually the chunk id
     ///
     /// An ascii friendly chunk name, with whitespace removed. Chunks with
@@ -156,6 +316,8 @@ pub trait Summarizable: ChunkID {
 
     /// Returns an iterator over a sequence of contents of the
     /// chunk as strings (field, value).
+    ///
+    /// Field names are stable, see [`schema`].
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         Box::new(core::iter::empty())
     }
@@ -210,6 +372,25 @@ impl<'a> PartialEq<&'a [u8; 4]> for FourCC {
     }
 }
 
+/// Parse a `FourCC` from a string of up to four ASCII characters.
+///
+/// Shorter ids are padded with spaces, so `"cue"` becomes `cue `.
+impl FromStr for FourCC {
+    type Err = WaveFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.is_ascii() || s.is_empty() || s.len() > 4 {
+            return Err(WaveFileError::parse(
+                None,
+                format!("chunk id must be 1 to 4 ASCII characters, found: {s:?}"),
+            ));
+        }
+        let mut id = [b' '; 4];
+        id[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(FourCC(id))
+    }
+}
+
 // needed for assert in br() attribute
 impl<'a> PartialEq<&'a FourCC> for FourCC {
     fn eq(&self, other: &&'a FourCC) -> bool {
@@ -217,7 +398,7 @@ impl<'a> PartialEq<&'a FourCC> for FourCC {
     }
 }
 
-impl<'a> PartialEq<FourCC> for &'a FourCC {
+impl PartialEq<FourCC> for &FourCC {
     fn eq(&self, other: &FourCC) -> bool {
         *self == other
     }
@@ -241,67 +422,146 @@ pub enum WaveFileError {
 
     /// An error occurred in the underlying reader while reading or seeking to data.
     ///
-    /// Contains an [`std::io::Error`]
-    Io(std::io::Error),
+    /// Contains a [`binrw::io::Error`], which is [`std::io::Error`] with the
+    /// `std` feature.
+    Io(binrw::io::Error),
 
     /// An error occured while parsing wav chunk data.
     ///
-    /// A string representation of the underlying error.
+    /// A string representation of the underlying error, with the chunk being
+    /// parsed when known. The underlying error, if any, is available from
+    /// [`Error::source()`](core::error::Error::source).
     Parse {
         /// The byte position of the unparsable data in the reader.
         pos: Option<u64>,
 
         /// Summary of the underlying parsing error.
         message: String,
+
+        /// Id of the chunk being parsed.
+        id: Option<FourCC>,
+
+        /// Byte offset of the chunk being parsed.
+        offset: Option<u64>,
+
+        /// The underlying error.
+        source: Option<Box<dyn core::error::Error + Send + Sync>>,
     },
 }
 
-impl error::Error for WaveFileError {}
+impl WaveFileError {
+    /// Create a [`WaveFileError::Parse`] without chunk context or source.
+    pub fn parse(pos: Option<u64>, message: impl Into<String>) -> Self {
+        WaveFileError::Parse {
+            pos,
+            message: message.into(),
+            id: None,
+            offset: None,
+            source: None,
+        }
+    }
+
+    /// Record the chunk being parsed, for [`WaveFileError::Parse`] errors
+    /// which don't have one yet. Other errors are returned unchanged.
+    #[must_use]
+    pub fn with_chunk(mut self, chunk_id: FourCC, chunk_offset: u64) -> Self {
+        if let WaveFileError::Parse { id, offset, .. } = &mut self {
+            if id.is_none() {
+                *id = Some(chunk_id);
+                *offset = Some(chunk_offset);
+            }
+        }
+        self
+    }
+
+    /// Stable identifier of the kind of error, see [`Code`].
+    pub fn code(&self) -> Code {
+        match self {
+            WaveFileError::UnknownFourCC { .. } => Code::UnknownChunkId,
+            WaveFileError::Io(_) => Code::Io,
+            WaveFileError::Parse { .. } => Code::Parse,
+        }
+    }
+
+    /// Id and offset of the chunk the error is about, when known.
+    pub fn chunk(&self) -> (Option<FourCC>, Option<u64>) {
+        match self {
+            WaveFileError::UnknownFourCC { found, .. } => (Some(*found), None),
+            WaveFileError::Io(_) => (None, None),
+            WaveFileError::Parse { id, offset, .. } => (*id, *offset),
+        }
+    }
+}
+
+impl core::error::Error for WaveFileError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            WaveFileError::Io(err) => Some(err),
+            // binrw's no_std io::Error doesn't implement Error
+            #[cfg(not(feature = "std"))]
+            WaveFileError::Io(_) => None,
+            WaveFileError::Parse {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            WaveFileError::UnknownFourCC { .. } | WaveFileError::Parse { source: None, .. } => None,
+        }
+    }
+}
 
 impl Display for WaveFileError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             WaveFileError::UnknownFourCC { message, .. } => write!(f, "UnknownFourCC: {}", message),
             WaveFileError::Io(err) => write!(f, "Io: {}", err),
+            WaveFileError::Parse {
+                message,
+                id: Some(id),
+                offset: Some(offset),
+                ..
+            } => write!(f, "Parse: {} at {:#X}: {}", id, offset, message),
             WaveFileError::Parse { message, .. } => write!(f, "Parse: {}", message),
         }
     }
 }
 
-impl From<std::io::Error> for WaveFileError {
-    fn from(err: std::io::Error) -> Self {
+impl From<binrw::io::Error> for WaveFileError {
+    fn from(err: binrw::io::Error) -> Self {
         WaveFileError::Io(err)
     }
 }
 
 /// Map `binrw::Error` to Parse, to avoid introducing external dependencies on it.
+///
+/// With the `std` feature, the `binrw::Error` is kept as the source.
 impl From<binrw::Error> for WaveFileError {
     fn from(err: binrw::Error) -> Self {
         #[allow(clippy::match_same_arms)] // so _ is its own case
-        match err {
+        let (pos, message) = match &err {
             binrw::Error::BadMagic { pos, .. }
             | binrw::Error::Custom { pos, .. }
             | binrw::Error::EnumErrors { pos, .. }
-            | binrw::Error::NoVariantMatch { pos } => WaveFileError::Parse {
-                pos: Some(pos),
-                message: err.to_string(),
-            },
-            binrw::Error::AssertFail { pos, message } => WaveFileError::Parse {
-                pos: Some(pos),
-                message,
-            },
-            binrw::Error::Io(_) | binrw::Error::Backtrace(_) => WaveFileError::Parse {
-                pos: None,
-                message: err.to_string(),
-            },
-            _ => WaveFileError::Parse {
-                pos: None,
-                message: err.to_string(),
-            },
+            | binrw::Error::NoVariantMatch { pos } => (Some(*pos), err.to_string()),
+            binrw::Error::AssertFail { pos, message } => (Some(*pos), message.clone()),
+            binrw::Error::Io(_) | binrw::Error::Backtrace(_) => (None, err.to_string()),
+            _ => (None, err.to_string()),
+        };
+        #[cfg(feature = "std")]
+        let source: Option<Box<dyn core::error::Error + Send + Sync>> = Some(Box::new(err));
+        #[cfg(not(feature = "std"))]
+        let source = None;
+        WaveFileError::Parse {
+            pos,
+            message,
+            id: None,
+            offset: None,
+            source,
         }
     }
 }
 
+#[cfg(feature = "std")]
 /// Implements `Wave.iter_chunks()`
 #[derive(Debug)]
 pub struct WaveFileIterator<'a, R>
@@ -309,16 +569,33 @@ where
     R: Read + Seek + Debug + BufRead,
 {
     reader: &'a mut R,
-    riff_size: u32,
+    // offset where chunk data ends, from RIFF size or stream length
+    end: u64,
+    options: ParseOptions,
     finished: bool,
+    // position of the first chunk, seeked to before parsing it
+    start: Option<u64>,
+    // code page used to decode text, None uses the UTF-8/Latin-1 heuristic
+    code_page: Option<u16>,
+    warnings: &'a mut Vec<Warning>,
+    stats: &'a mut ParseStats,
+    // only chunks with these ids are parsed, others are skipped
+    ids: Option<Vec<FourCC>>,
+    // single instance chunk ids seen so far, to detect duplicates
+    seen: HashSet<FourCC>,
+    // 64 bit sizes from the ds64 chunk, for chunks with 0xFFFFFFFF sizes
+    large_sizes: LargeSizes,
 }
 
+#[cfg(feature = "std")]
 impl<'a, R> WaveFileIterator<'a, R>
 where
     R: Read + Seek + Debug + BufRead,
 {
-    fn parse_next_chunk(&mut self) -> Result<(SizedChunkEnum, u64), WaveFileError> {
-        let mut offset = self.reader.stream_position()?;
+    /// Parse the chunk at the current position, returns the chunk and the
+    /// offset of the next chunk. The chunk is `None` if its id is filtered out.
+    fn parse_next_chunk(&mut self) -> Result<(Option<SizedChunkEnum>, u64), WaveFileError> {
+        let start = self.reader.stream_position()?;
         let mut buff: [u8; 4] = [0; 4];
 
         let chunk_id = {
@@ -330,15 +607,109 @@ where
             u32::from_le_bytes(buff)
         };
 
-        self.reader.seek(SeekFrom::Current(-8))?;
+        let span = debug_span!("chunk", id = %FourCC(chunk_id), offset = start, size = chunk_size);
+        let _entered = span.enter();
 
-        let chunk = SizedChunkEnum::read(&mut self.reader)?;
+        let large_size = self.large_sizes.resolve(FourCC(chunk_id), chunk_size);
+        let chunk_end = start + large_size.unwrap_or(u64::from(chunk_size)) + 8;
+        let wanted = self.ids.as_ref().is_none_or(|ids| {
+            ids.contains(&FourCC(chunk_id))
+                || (self.options.canonical_id(FourCC(chunk_id))).is_some_and(|id| ids.contains(&id))
+        });
+        if let Some(max) = self.options.max_chunk_size.filter(|_| wanted) {
+            if chunk_size > max {
+                self.finished = true;
+                return Err(WaveFileError::parse(
+                    Some(start),
+                    format!(
+                        "{} chunk size ({chunk_size}) larger than max_chunk_size ({max})",
+                        FourCC(chunk_id)
+                    ),
+                ));
+            }
+        }
+        if self.options.strictness == Strictness::Strict && chunk_end > self.end {
+            return Err(WaveFileError::parse(
+                Some(start),
+                format!(
+                    "{} chunk size ({chunk_size}) extends past the end of the RIFF data",
+                    FourCC(chunk_id)
+                ),
+            ));
+        }
+
+        // ds64 is read even when filtered out, for the sizes of later chunks
+        let mut chunk = if wanted || FourCC(chunk_id) == Ds64::ID {
+            self.reader.seek(SeekFrom::Current(-8))?;
+            // don't read the bytes of chunks larger than 4 GiB into memory
+            let skip_extra_bytes = !self.options.keep_extra_bytes || large_size.is_some();
+            let mut chunk = read_chunk(
+                &mut *self.reader,
+                FourCC(chunk_id),
+                &self.options,
+                skip_extra_bytes,
+            )
+            .map_err(|err| WaveFileError::from(err).with_chunk(FourCC(chunk_id), start))?;
+            if let Some(alias) = chunk.alias() {
+                self.warn(
+                    Code::ChunkIdAlias,
+                    alias,
+                    start,
+                    format!("chunk id parsed as {}", chunk.id()),
+                );
+            }
+            if let Some(size) = large_size {
+                chunk.set_large_size(size);
+            }
+            if let SizedChunkEnum::Ds64(ds64) = &chunk {
+                self.large_sizes = LargeSizes::from(&ds64.data);
+            }
+            wanted.then_some(chunk)
+        } else {
+            None
+        };
 
         // setup for next iteration
-        offset += chunk_size as u64 + 8;
+        let mut offset = chunk_end;
+        // streaming encoders may leave placeholder sizes, so the data chunk
+        // runs to the end of the stream
+        if FourCC(chunk_id) == Data::ID
+            && large_size.is_none()
+            && (chunk_size == u32::MAX
+                || (chunk_size == 0 && !self.chunk_id_at(offset)? && offset < self.end))
+        {
+            self.warn(
+                Code::PlaceholderSize,
+                FourCC(chunk_id),
+                start,
+                format!(
+                    "chunk size ({chunk_size}) is a placeholder, assuming data runs to the end"
+                ),
+            );
+            offset = self.end;
+        }
+        if offset > self.end {
+            self.warn(
+                Code::ChunkPastEnd,
+                FourCC(chunk_id),
+                start,
+                format!("chunk size ({chunk_size}) extends past the end of the data"),
+            );
+        }
         // RIFF offsets must be on word boundaries (divisible by 2)
+        let mut padding_offset = None;
         if offset % 2 == 1 {
-            offset += 1;
+            if self.padding_missing(offset)? {
+                self.warn(
+                    Code::MissingPadding,
+                    FourCC(chunk_id),
+                    start,
+                    "missing padding byte after odd sized chunk",
+                );
+            } else {
+                padding_offset = Some(offset);
+                offset += 1;
+            }
         };
 
         // Returning after parsing a chunk would cause a missing chunk.
@@ -350,44 +721,429 @@ where
         // if/when fixed, update docs on iter_chunks()
         let stream_position = self.reader.stream_position()?;
         if offset != stream_position {
-            warn!("{:?}: parsed less data than chunk size", FourCC(chunk_id));
             self.reader.seek(SeekFrom::Start(offset))?;
+            if !wanted {
+                return Ok((None, offset));
+            }
+            // reading past the chunk end is expected when the padding byte
+            // is missing, alignment reads the first byte of the next chunk
+            if stream_position < chunk_end && large_size.is_none() {
+                if self.options.strictness == Strictness::Strict {
+                    return Err(WaveFileError::parse(
+                        Some(stream_position),
+                        format!("{}: parsed less data than chunk size", FourCC(chunk_id)),
+                    ));
+                }
+                self.warn(
+                    Code::ShortParse,
+                    FourCC(chunk_id),
+                    start,
+                    "parsed less data than chunk size",
+                );
+            }
+        }
+        // the parser reads whatever byte follows, also the next chunk's
+        // first byte when the padding is missing
+        if let Some(chunk) = chunk.as_mut() {
+            let padding = match padding_offset {
+                Some(padding_offset) => self.byte_at(padding_offset)?,
+                None => None,
+            };
+            chunk.set_padding(padding);
+        }
+
+        #[cfg(feature = "broadcast")]
+        if let Some(SizedChunkEnum::Bext(bext)) = &chunk {
+            let fixed_size = chunk::bext::Bext::FIXED_SIZE;
+            if chunk_size < fixed_size {
+                self.warn(
+                    Code::TruncatedFields,
+                    FourCC(chunk_id),
+                    start,
+                    format!("chunk size ({chunk_size}) smaller than its {fixed_size} byte fixed fields, missing fields are empty"),
+                );
+            }
+            if self.code_page.is_none() {
+                for (field, guess) in bext.data.detect_encodings() {
+                    self.warn(
+                        Code::InvalidText,
+                        FourCC(chunk_id),
+                        start,
+                        format!(
+                            "{field} text is not valid UTF-8, decoded as Latin-1, likely {guess}"
+                        ),
+                    );
+                }
+            }
+        }
+
+        #[cfg(feature = "info")]
+        if let Some(SizedChunkEnum::Info(list)) = &chunk {
+            if self.code_page.is_none() {
+                for info in &list.data.chunks {
+                    let Some(guess) = info.detect_encoding() else {
+                        continue;
+                    };
+                    self.warn(
+                        Code::InvalidText,
+                        FourCC(chunk_id),
+                        start,
+                        format!(
+                            "{} text is not valid UTF-8, decoded as Latin-1, likely {guess}",
+                            info.id()
+                        ),
+                    );
+                }
+            }
+        }
+
+        if SINGLE_INSTANCE_IDS.contains(&FourCC(chunk_id)) && !self.seen.insert(FourCC(chunk_id)) {
+            self.warn(
+                Code::DuplicateChunk,
+                FourCC(chunk_id),
+                start,
+                "duplicate chunk, only one is expected per file",
+            );
         }
 
         Ok((chunk, offset))
     }
+
+    /// Log and record a [`Warning`].
+    fn warn(&mut self, code: Code, id: FourCC, offset: u64, message: impl Into<String>) {
+        let warning = Warning::new(id, Some(offset), message).with_code(code);
+        warn!("{warning}");
+        self.warnings.push(warning);
+    }
+
+    /// The byte at `offset`, `None` at the end of the stream. Leaves the
+    /// reader after it.
+    fn byte_at(&mut self, offset: u64) -> Result<Option<u8>, WaveFileError> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut byte = [0_u8];
+        match self.reader.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Returns true if the padding byte after an odd sized chunk ending at
+    /// `offset` appears to be missing.
+    ///
+    /// Only checked when [`ParseOptions::allow_missing_padding`] is set. The
+    /// padding is considered missing if a valid looking chunk id starts at
+    /// `offset` but not at `offset + 1`.
+    fn padding_missing(&mut self, offset: u64) -> Result<bool, WaveFileError> {
+        if !self.options.allow_missing_padding {
+            return Ok(false);
+        }
+        padding_missing_at(self.reader, offset, self.end)
+    }
+
+    /// Returns true if the 4 bytes at `offset` look like a chunk id.
+    fn chunk_id_at(&mut self, offset: u64) -> Result<bool, WaveFileError> {
+        let position = self.reader.stream_position()?;
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut buff = [0_u8; 4];
+        let result = self.reader.read_exact(&mut buff);
+        self.reader.seek(SeekFrom::Start(position))?;
+        Ok(result.is_ok() && looks_like_chunk_id(&buff))
+    }
+}
+
+#[cfg(feature = "std")]
+/// Chunks which should appear at most once per file.
+const SINGLE_INSTANCE_IDS: [FourCC; 12] = [
+    FmtChunk::ID,
+    DataChunk::ID,
+    FactChunk::ID,
+    CueChunk::ID,
+    CsetChunk::ID,
+    PlstChunk::ID,
+    InstChunk::ID,
+    SmplChunk::ID,
+    FourCC(*b"bext"),
+    Md5Chunk::ID,
+    LevlChunk::ID,
+    FourCC(*b"iXML"),
+];
+
+#[cfg(feature = "std")]
+/// Returns true if a valid looking chunk id starts at `offset` but not at
+/// `offset + 1`. The position of `reader` is restored.
+fn padding_missing_at<R: Read + Seek>(
+    reader: &mut R,
+    offset: u64,
+    end: u64,
+) -> Result<bool, WaveFileError> {
+    if offset + 8 > end {
+        return Ok(false);
+    }
+    let position = reader.stream_position()?;
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut buff = [0_u8; 5];
+    let result = reader.read_exact(&mut buff);
+    reader.seek(SeekFrom::Start(position))?;
+    if result.is_err() {
+        return Ok(false);
+    }
+    Ok(looks_like_chunk_id(&buff[..4]) && !looks_like_chunk_id(&buff[1..5]))
+}
+
+#[cfg(feature = "std")]
+/// Chunk ids are conventionally printable ASCII, padded with spaces.
+fn looks_like_chunk_id(id: &[u8]) -> bool {
+    id.iter().all(|b| b.is_ascii_graphic() || *b == b' ')
 }
 
+#[cfg(feature = "std")]
 impl<'a, R> Iterator for WaveFileIterator<'a, R>
 where
     R: Read + Seek + Debug + BufRead,
 {
     type Item = Result<SizedChunkEnum, WaveFileError>;
 
-    #[instrument]
+    #[instrument(skip_all)]
     fn next(&mut self) -> Option<Self::Item> {
         if self.finished {
             return None;
         }
 
-        let (chunk, offset) = match self.parse_next_chunk() {
-            Ok(v) => v,
-            Err(err) => {
-                // TODO... hmmm... would be great to continue after normal errors
-                // but if we remove this, we get an infinite loop on files
-                // with a larger riff.size than disk size.
+        if let Some(start) = self.start.take() {
+            if let Err(err) = self.reader.seek(SeekFrom::Start(start)) {
                 self.finished = true;
-                return Some(Err(err));
+                return Some(Err(err.into()));
             }
-        };
+        }
 
-        if offset >= self.riff_size as u64 {
-            self.finished = true;
-        };
-        Some(Ok(chunk))
+        loop {
+            let started = Instant::now();
+            let (chunk, offset) = match self.parse_next_chunk() {
+                Ok(v) => v,
+                Err(err) => {
+                    // TODO... hmmm... would be great to continue after normal errors,
+                    // but the next chunk offset is unknown if the header couldn't
+                    // be read.
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            };
+
+            if offset + 8 >= self.end {
+                self.finished = true;
+            };
+            // filtered out by iter_chunks_filtered()
+            let Some(mut chunk) = chunk else {
+                if self.finished {
+                    return None;
+                }
+                continue;
+            };
+            if let Some(code_page) = self.code_page {
+                chunk.set_code_page(code_page);
+            }
+            self.stats
+                .record(chunk.name(), chunk.size(), started.elapsed());
+            return Some(Ok(chunk));
+        }
+    }
+}
+
+/// How strictly to enforce the RIFF specification while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Strictness {
+    /// Report problems which can be recovered from as warnings (via
+    /// `tracing`) and continue parsing.
+    #[default]
+    Lenient,
+    /// Return an error for chunks which are larger than the RIFF data or
+    /// contain more data than could be parsed.
+    Strict,
+}
+
+/// Options controlling how a [`WaveFile`] is parsed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::fs::File;
+/// # use std::io::BufReader;
+/// use wavrw::{ParseOptions, WaveFile};
+///
+/// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+/// let options = ParseOptions::default().with_code_page(1252);
+/// let mut wave = WaveFile::from_reader_with_options(file, options)?;
+/// assert_eq!(wave.code_page(), Some(1252));
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Code page used to decode text, overrides any `CSET` chunk.
+    ///
+    /// See [`codepage`] for supported code pages.
+    pub code_page: Option<u16>,
+
+    /// How strictly to enforce the specification. Default: [`Strictness::Lenient`].
+    pub strictness: Strictness,
+
+    /// Chunks with a larger size are returned as errors and end iteration,
+    /// before any of their data is read. Text fields like `bext`
+    /// `coding_history` and the sub-chunks of `LIST` chunks are read within
+    /// their chunk, so this also limits their size. Set this when parsing
+    /// untrusted files. Default: no limit.
+    pub max_chunk_size: Option<u32>,
+
+    /// Accept odd sized chunks which are not followed by a padding byte, as
+    /// written by some applications. Default: true.
+    ///
+    /// The padding byte is assumed to be missing if a plausible chunk id
+    /// starts right after the chunk, but not one byte later. A warning is
+    /// recorded, see [`WaveFile::warnings()`].
+    pub allow_missing_padding: bool,
+
+    /// Use the RIFF header size to find the end of the chunks. When false,
+    /// the stream length is used instead. Default: true.
+    pub trust_riff_size: bool,
+
+    /// Keep bytes which aren't parsed into chunk fields:
+    /// [`KnownChunk::extra_bytes`], [`UnknownChunk::raw`] and the contents
+    /// of padding chunks. When false, they are skipped and left empty,
+    /// which is faster for files with large unknown or padding chunks, but
+    /// those chunks can't be written back. Default: true.
+    pub keep_extra_bytes: bool,
+
+    /// Parse chunks whose id only differs in case from a supported chunk
+    /// id with that chunk's parser. Ex: `BEXT` as `bext`, `junk` as `JUNK`.
+    /// Default: true.
+    ///
+    /// The id found in the file is kept in [`KnownChunk::alias`] and written
+    /// back unchanged. A warning is recorded, see [`WaveFile::warnings()`].
+    pub case_insensitive_ids: bool,
+
+    /// Chunk ids to parse as another chunk id, as (from, to) pairs. Checked
+    /// before [`ParseOptions::case_insensitive_ids`]. Default: empty.
+    ///
+    /// As with case insensitive ids, the id found in the file is kept in
+    /// [`KnownChunk::alias`] and a warning is recorded.
+    pub id_aliases: Vec<(FourCC, FourCC)>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            code_page: None,
+            strictness: Strictness::default(),
+            max_chunk_size: None,
+            allow_missing_padding: true,
+            trust_riff_size: true,
+            keep_extra_bytes: true,
+            case_insensitive_ids: true,
+            id_aliases: Vec::new(),
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options for archival use: strict parsing, no guessing of missing
+    /// padding bytes and exact chunk ids.
+    pub fn strict() -> Self {
+        ParseOptions {
+            strictness: Strictness::Strict,
+            allow_missing_padding: false,
+            case_insensitive_ids: false,
+            ..Default::default()
+        }
+    }
+
+    /// Set [`ParseOptions::strictness`].
+    #[must_use]
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Set [`ParseOptions::max_chunk_size`].
+    #[must_use]
+    pub fn with_max_chunk_size(mut self, max_chunk_size: u32) -> Self {
+        self.max_chunk_size = Some(max_chunk_size);
+        self
+    }
+
+    /// Set [`ParseOptions::allow_missing_padding`].
+    #[must_use]
+    pub fn with_allow_missing_padding(mut self, allow: bool) -> Self {
+        self.allow_missing_padding = allow;
+        self
+    }
+
+    /// Set [`ParseOptions::trust_riff_size`].
+    #[must_use]
+    pub fn with_trust_riff_size(mut self, trust: bool) -> Self {
+        self.trust_riff_size = trust;
+        self
+    }
+
+    /// Set [`ParseOptions::keep_extra_bytes`].
+    #[must_use]
+    pub fn with_keep_extra_bytes(mut self, keep: bool) -> Self {
+        self.keep_extra_bytes = keep;
+        self
+    }
+
+    /// Decode text using `code_page`, ignoring any `CSET` chunk.
+    #[must_use]
+    pub fn with_code_page(mut self, code_page: u16) -> Self {
+        self.code_page = Some(code_page);
+        self
+    }
+
+    /// Set [`ParseOptions::case_insensitive_ids`].
+    #[must_use]
+    pub fn with_case_insensitive_ids(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive_ids = case_insensitive;
+        self
+    }
+
+    /// Parse chunks with id `from` as `to`, see [`ParseOptions::id_aliases`].
+    #[must_use]
+    pub fn with_id_alias(mut self, from: FourCC, to: FourCC) -> Self {
+        self.id_aliases.push((from, to));
+        self
+    }
+
+    /// Chunk id to parse a chunk with id `id` as, from
+    /// [`ParseOptions::id_aliases`] or [`ParseOptions::case_insensitive_ids`].
+    /// `None` if the chunk is parsed as `id`.
+    ///
+    /// ```
+    /// use wavrw::{FourCC, ParseOptions};
+    ///
+    /// let options = ParseOptions::default().with_id_alias(FourCC(*b"bxt "), FourCC(*b"bext"));
+    /// assert_eq!(options.canonical_id(FourCC(*b"bxt ")), Some(FourCC(*b"bext")));
+    /// assert_eq!(options.canonical_id(FourCC(*b"FMT ")), Some(FourCC(*b"fmt ")));
+    /// assert_eq!(options.canonical_id(FourCC(*b"fmt ")), None);
+    /// assert_eq!(ParseOptions::strict().canonical_id(FourCC(*b"FMT ")), None);
+    /// ```
+    pub fn canonical_id(&self, id: FourCC) -> Option<FourCC> {
+        if let Some((_, to)) = self.id_aliases.iter().find(|(from, _)| *from == id) {
+            return Some(*to).filter(|to| *to != id);
+        }
+        if !self.case_insensitive_ids {
+            return None;
+        }
+        let mut supported = chunk::SUPPORTED
+            .iter()
+            .filter(|c| c.parent.is_none())
+            .map(|c| c.id);
+        if supported.clone().any(|s| s == id) {
+            return None;
+        }
+        supported.find(|s| s.0.eq_ignore_ascii_case(&id.0))
     }
 }
 
+#[cfg(feature = "std")]
 /// Wrapper around RIFF-WAVE binary data.
 pub struct WaveFile<R>
 where
@@ -395,16 +1151,43 @@ where
 {
     bytes: R,
     riff: RiffChunk,
+    chunks_start: u64,
+    options: ParseOptions,
+    // code page from options or CSET, looked up on first use
+    code_page: Option<u16>,
+    code_page_checked: bool,
+    warnings: Vec<Warning>,
+    stats: ParseStats,
+    // all successfully parsed chunks, loaded on first use
+    chunks: Option<Vec<SizedChunkEnum>>,
 }
 
+#[cfg(feature = "std")]
 impl<R> WaveFile<R>
 where
     R: Read + Seek + Debug + BufRead,
 {
     /// Create a new `WaveFile` from a reader. This keeps a reference to the
     /// data until dropped.
-    pub fn from_reader(mut reader: R) -> Result<Self, WaveFileError> {
+    pub fn from_reader(reader: R) -> Result<Self, WaveFileError> {
+        Self::from_reader_with_options(reader, ParseOptions::default())
+    }
+
+    /// Create a new `WaveFile` from a reader, parsing according to `options`.
+    pub fn from_reader_with_options(
+        mut reader: R,
+        options: ParseOptions,
+    ) -> Result<Self, WaveFileError> {
         let riff = RiffChunk::read(&mut reader).map_err(std::io::Error::other)?;
+        if riff.id == aiff::FormChunk::ID {
+            return Err(WaveFileError::UnknownFourCC {
+                found: riff.form_type,
+                message: format!(
+                    "not a wave file. Found IFF form_type {}, read AIFF files with wavrw::open() or aiff::AiffFile",
+                    riff.form_type
+                ),
+            });
+        }
         if riff.form_type != FourCC(*b"WAVE") {
             return Err(WaveFileError::UnknownFourCC {
                 found: riff.form_type,
@@ -414,32 +1197,521 @@ where
                 ),
             });
         }
+        let chunks_start = reader.stream_position()?;
         Ok(Self {
             bytes: reader,
             riff,
+            chunks_start,
+            options,
+            code_page: None,
+            code_page_checked: false,
+            warnings: Vec::new(),
+            stats: ParseStats::default(),
+            chunks: None,
         })
     }
 
+    /// Code page used to decode text.
+    ///
+    /// Returns the code page from [`ParseOptions`] if set, otherwise from the
+    /// `CSET` chunk. `None` if neither is present or the `CSET` code page is
+    /// 0, in which case text is decoded as UTF-8 when valid and Latin-1
+    /// otherwise. See [`codepage`].
+    pub fn code_page(&mut self) -> Option<u16> {
+        if !self.code_page_checked {
+            self.code_page = self
+                .options
+                .code_page
+                .or_else(|| self.find_cset_code_page())
+                .filter(|cp| *cp != 0);
+            self.code_page_checked = true;
+        }
+        self.code_page
+    }
+
+    /// Offset where the chunks end.
+    ///
+    /// Uses the RIFF size, unless it is a placeholder (0 or 0xFFFFFFFF), larger
+    /// than the stream or [`ParseOptions::trust_riff_size`] is false. In those
+    /// cases the stream length is used.
+    fn chunks_end(&mut self) -> u64 {
+        let riff_end = 8 + u64::from(self.riff.size);
+        // fall back to the RIFF size if the stream length is unavailable
+        let Ok(stream_len) = self.bytes.seek(SeekFrom::End(0)) else {
+            return riff_end;
+        };
+        if !self.options.trust_riff_size {
+            return stream_len;
+        }
+        if self.riff.size == 0 || self.riff.size == u32::MAX || riff_end > stream_len {
+            return stream_len;
+        }
+        riff_end
+    }
+
+    /// Walk chunk headers looking for `CSET`, without parsing other chunks.
+    fn find_cset_code_page(&mut self) -> Option<u16> {
+        let chunks = self.scan_chunks().ok()?;
+        let cset = chunks.iter().find(|c| c.id == Cset::ID)?;
+        match cset.data.as_deref()? {
+            [a, b, ..] => Some(u16::from_le_bytes([*a, *b])),
+            _ => None,
+        }
+    }
+
+    /// Read only the chunk headers, for a fast overview of large files.
+    ///
+    /// Chunk data is not parsed, use [`LazyChunk::parse()`] for the chunks
+    /// which are needed. Data of chunks up to [`LazyChunk::INLINE_SIZE`]
+    /// bytes is read during the scan. Unlike [`WaveFile::iter_chunks()`] no
+    /// [`Warning`]s are recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::{FourCC, SizedChunkEnum};
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// let chunks = wave.scan_chunks()?;
+    /// let fmt = chunks.iter().find(|c| c.id == FourCC(*b"fmt ")).unwrap();
+    /// let SizedChunkEnum::Fmt(fmt) = fmt.parse(&mut wave)? else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(fmt.data.samples_per_sec(), 48000);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn scan_chunks(&mut self) -> Result<Vec<LazyChunk>, WaveFileError> {
+        let end = self.chunks_end();
+        let mut offset = self.chunks_start;
+        let mut chunks = Vec::new();
+        let mut buff = [0_u8; 8];
+        let mut large_sizes = LargeSizes::default();
+        while offset + 8 <= end {
+            self.bytes.seek(SeekFrom::Start(offset))?;
+            self.bytes.read_exact(&mut buff)?;
+            let [a, b, c, d, s0, s1, s2, s3] = buff;
+            let id = FourCC([a, b, c, d]);
+            let size = u32::from_le_bytes([s0, s1, s2, s3]);
+            let large_size = large_sizes.resolve(id, size);
+            let mut data = None;
+            if size <= LazyChunk::INLINE_SIZE || id == Ds64::ID {
+                let mut bytes = Vec::new();
+                (&mut self.bytes)
+                    .take(u64::from(size))
+                    .read_to_end(&mut bytes)?;
+                if id == Ds64::ID {
+                    if let Ok(ds64) =
+                        Ds64::read_le_args(&mut binrw::io::Cursor::new(&bytes), (size,))
+                    {
+                        large_sizes = LargeSizes::from(&ds64);
+                    }
+                }
+                if size <= LazyChunk::INLINE_SIZE {
+                    data = Some(bytes);
+                }
+            }
+            let mut list_type = None;
+            if id == FourCC(*b"LIST") && size >= 4 {
+                let mut form = [0_u8; 4];
+                self.bytes.seek(SeekFrom::Start(offset + 8))?;
+                self.bytes.read_exact(&mut form)?;
+                list_type = Some(FourCC(form));
+            }
+            chunks.push(LazyChunk {
+                offset,
+                id,
+                size,
+                large_size,
+                list_type,
+                data,
+            });
+
+            offset += 8 + large_size.unwrap_or(u64::from(size));
+            if offset % 2 == 1
+                && !(self.options.allow_missing_padding
+                    && padding_missing_at(&mut self.bytes, offset, end)?)
+            {
+                offset += 1;
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Reader over the bytes of chunk number `index` with id `id`, counting
+    /// from 0, exactly as stored: the 8 byte header followed by the chunk
+    /// data, without the padding byte after odd sized chunks.
+    ///
+    /// Chunks are found as with [`WaveFile::scan_chunks()`]. The chunk is not
+    /// parsed or buffered, so it can be copied verbatim to another file, even
+    /// a large `data` chunk, without depending on how wavrw writes chunks.
+    /// Chunks written by wavrw are added with
+    /// [`edit::inject_chunk()`](crate::edit::inject_chunk).
+    ///
+    /// Returns `None` if there are fewer than `index + 1` chunks with `id`.
+    /// The reader ends early if the chunk extends past the end of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::FourCC;
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// let mut bytes = Vec::new();
+    /// if let Some(mut chunk) = wave.raw_chunk_bytes(FourCC(*b"bext"), 0)? {
+    ///     std::io::copy(&mut chunk, &mut bytes)?;
+    /// }
+    /// assert_eq!(&bytes[..4], b"bext");
+    /// let size = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    /// assert_eq!(bytes.len(), 8 + size as usize);
+    ///
+    /// assert!(wave.raw_chunk_bytes(FourCC(*b"bext"), 1)?.is_none());
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn raw_chunk_bytes(
+        &mut self,
+        id: FourCC,
+        index: usize,
+    ) -> Result<Option<std::io::Take<&mut R>>, WaveFileError> {
+        let Some(chunk) = self
+            .scan_chunks()?
+            .into_iter()
+            .filter(|c| c.id == id)
+            .nth(index)
+        else {
+            return Ok(None);
+        };
+        self.bytes.seek(SeekFrom::Start(chunk.offset))?;
+        Ok(Some((&mut self.bytes).take(8 + chunk.large_size())))
+    }
+
+    /// Format, length and chunk ids for a one line listing, see [`Overview`].
+    ///
+    /// Only the chunk headers and the first `fmt ` and `fact` chunks are
+    /// read, much faster than [`WaveFile::chunks()`] for large files.
+    pub fn overview(&mut self) -> Result<Overview, WaveFileError> {
+        let scanned = self.scan_chunks()?;
+        let mut parsed = Vec::new();
+        for id in [FmtChunk::ID, FactChunk::ID] {
+            if let Some(chunk) = scanned.iter().find(|c| c.id == id) {
+                parsed.push(chunk.parse(self)?);
+            }
+        }
+        Ok(Overview::new(self.riff.id, &scanned, &parsed))
+    }
+
     /// Parses WAV (RIFF-WAVE) data, returns iterator over all known
     /// chunks. Each iteration returns a
     /// `Result<`[`SizedChunkEnum`]`, `[`WaveFileError`]`>`
     ///
+    /// Each call starts again from the first chunk.
+    ///
     /// It attempts to continue parsing even if some chunks have parsing errors.
     /// In some cases, it may return before reading all chunks, such as:
     ///
     /// * when an error results from parsing the RIFF container
     /// * the data is not a WAVE form type
     /// * an IO error occurs while seeking before or after parsing chunk data
+    ///
+    /// Problems which don't stop parsing are recorded as [`Warning`]s, see
+    /// [`WaveFile::warnings()`].
     #[instrument]
     pub fn iter_chunks<'a>(&'a mut self) -> WaveFileIterator<'a, R> {
+        let code_page = self.code_page();
+        let end = self.chunks_end();
+        self.warnings.clear();
+        self.stats.clear();
+        let riff_end = 8 + u64::from(self.riff.size);
+        if end != riff_end && self.options.trust_riff_size {
+            let warning = Warning::new(
+                self.riff.id,
+                Some(0),
+                format!(
+                    "RIFF size ({}) does not match the stream length, using stream length",
+                    self.riff.size
+                ),
+            )
+            .with_code(Code::RiffSizeMismatch);
+            warn!("{warning}");
+            self.warnings.push(warning);
+        }
         WaveFileIterator {
             reader: &mut self.bytes,
-            riff_size: self.riff.size,
+            end,
+            options: self.options.clone(),
             finished: false,
+            start: Some(self.chunks_start),
+            code_page,
+            warnings: &mut self.warnings,
+            stats: &mut self.stats,
+            ids: None,
+            seen: HashSet::new(),
+            large_sizes: LargeSizes::default(),
+        }
+    }
+
+    /// Like [`WaveFile::iter_chunks()`], but only chunks with one of `ids`
+    /// are parsed and returned. Other chunks are skipped by seeking past
+    /// them, without reading their data.
+    ///
+    /// `LIST` matches all `LIST` chunks, whatever their list type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::{ChunkID, FourCC};
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// let ids = [FourCC(*b"bext"), FourCC(*b"iXML")];
+    /// let chunks: Vec<_> = wave.iter_chunks_filtered(&ids).collect::<Result<_, _>>()?;
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[1].id(), FourCC(*b"iXML"));
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn iter_chunks_filtered<'a>(&'a mut self, ids: &[FourCC]) -> WaveFileIterator<'a, R> {
+        let mut iter = self.iter_chunks();
+        iter.ids = Some(ids.to_vec());
+        iter
+    }
+
+    /// The `RIFF` header. Its id is `RF64` or `BW64` for files with 64 bit
+    /// sizes.
+    pub fn riff(&self) -> &RiffChunk {
+        &self.riff
+    }
+
+    /// Warnings recorded during the most recent iteration over the chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// for _ in wave.iter_chunks() {}
+    /// for warning in wave.warnings() {
+    ///     println!("{warning}");
+    /// }
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Chunk counts and parse durations per chunk type, recorded during the
+    /// most recent iteration over the chunks. See [`stats`].
+    pub fn parse_stats(&self) -> &ParseStats {
+        &self.stats
+    }
+
+    /// All successfully parsed chunks, in file order.
+    ///
+    /// Chunks are parsed on first use and kept in memory. Chunks with parsing
+    /// errors are skipped. After parsing, references between chunks are
+    /// resolved, for example `plst` segments are labeled from `LIST-adtl`.
+    pub fn chunks(&mut self) -> &[SizedChunkEnum] {
+        if self.chunks.is_none() {
+            let mut chunks: Vec<SizedChunkEnum> =
+                self.iter_chunks().filter_map(Result::ok).collect();
+            resolve_references(&mut chunks);
+            self.chunks = Some(chunks);
+        }
+        self.chunks.as_deref().unwrap_or_default()
+    }
+
+    /// All chunks with chunk id `id`, in file order.
+    ///
+    /// Files edited by several applications sometimes contain duplicates of
+    /// chunks which should only appear once, such as `fmt ` or `bext`. These
+    /// are reported by [`WaveFile::warnings()`]. When a single chunk is
+    /// needed, use the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::FourCC;
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// assert_eq!(wave.chunks_by_id(FourCC(*b"fmt ")).len(), 1);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn chunks_by_id(&mut self, id: FourCC) -> Vec<&SizedChunkEnum> {
+        self.chunks().iter().filter(|c| c.id() == id).collect()
+    }
+
+    /// Typed getters for common metadata, see [`WaveMetadata`].
+    pub fn metadata(&mut self) -> WaveMetadata<'_> {
+        WaveMetadata::new(self.chunks())
+    }
+
+    /// Chunks with the references between them resolved, see [`ResolvedWave`].
+    pub fn resolve(&mut self) -> ResolvedWave<'_> {
+        ResolvedWave::new(self.chunks())
+    }
+}
+
+#[cfg(feature = "std")]
+/// Read the chunk starting at the current position of `reader`, which has
+/// id `id`. Chunks with an alias in `options` are parsed as their
+/// canonical id, see [`ParseOptions::canonical_id()`].
+fn read_chunk<R>(
+    reader: &mut R,
+    id: FourCC,
+    options: &ParseOptions,
+    skip_extra_bytes: bool,
+) -> BinResult<SizedChunkEnum>
+where
+    R: Read + Seek,
+{
+    let args = (skip_extra_bytes, options.max_chunk_size);
+    let Some(canonical) = options.canonical_id(id) else {
+        return SizedChunkEnum::read_args(reader, args);
+    };
+    let start = reader.stream_position()?;
+    let mut reader = RenamedReader {
+        inner: reader,
+        start,
+        id: canonical,
+    };
+    let mut chunk = SizedChunkEnum::read_args(&mut reader, args)?;
+    chunk.set_alias(id);
+    Ok(chunk)
+}
+
+#[cfg(feature = "std")]
+/// Reader which replaces the chunk id at `start` with `id`, so binrw
+/// dispatches the chunk to the parser for `id`.
+struct RenamedReader<'r, R> {
+    inner: &'r mut R,
+    start: u64,
+    id: FourCC,
+}
+
+#[cfg(feature = "std")]
+impl<R> Read for RenamedReader<'_, R>
+where
+    R: Read + Seek,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let pos = self.inner.stream_position()?;
+        let count = self.inner.read(buf)?;
+        for (i, byte) in buf[..count].iter_mut().enumerate() {
+            let offset = (pos + i as u64).wrapping_sub(self.start);
+            if let Some(id_byte) = usize::try_from(offset).ok().and_then(|o| self.id.0.get(o)) {
+                *byte = *id_byte;
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> Seek for RenamedReader<'_, R>
+where
+    R: Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[cfg(feature = "std")]
+/// A chunk found by [`WaveFile::scan_chunks()`], with its data not parsed yet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LazyChunk {
+    /// Byte offset of the chunk header from the beginning of the data stream.
+    pub offset: u64,
+
+    /// RIFF chunk id.
+    pub id: FourCC,
+
+    /// RIFF chunk size in bytes.
+    pub size: u32,
+
+    /// 64 bit size from a `ds64` chunk, when `size` is 0xFFFFFFFF.
+    pub large_size: Option<u64>,
+
+    /// List type of `LIST` chunks. Ex: `INFO`
+    pub list_type: Option<FourCC>,
+
+    /// Unparsed chunk data, only for chunks up to
+    /// [`INLINE_SIZE`](LazyChunk::INLINE_SIZE) bytes.
+    pub data: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "std")]
+impl LazyChunk {
+    /// Chunks up to this size are read during the scan. Large enough for
+    /// `fmt `, `fact`, `CSET` and other fixed size chunks.
+    pub const INLINE_SIZE: u32 = 64;
+
+    /// Parse the chunk from `wave`, the file it was scanned from.
+    ///
+    /// Text is decoded using [`WaveFile::code_page()`].
+    pub fn parse<R>(&self, wave: &mut WaveFile<R>) -> Result<SizedChunkEnum, WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let code_page = wave.code_page();
+        wave.bytes.seek(SeekFrom::Start(self.offset))?;
+        let skip_extra_bytes = !wave.options.keep_extra_bytes || self.large_size.is_some();
+        let mut chunk = read_chunk(&mut wave.bytes, self.id, &wave.options, skip_extra_bytes)
+            .map_err(|err| WaveFileError::from(err).with_chunk(self.id, self.offset))?;
+        if let Some(code_page) = code_page {
+            chunk.set_code_page(code_page);
+        }
+        if let Some(size) = self.large_size {
+            chunk.set_large_size(size);
+        }
+        Ok(chunk)
+    }
+
+    /// The size in bytes, like [`SizedChunk::large_size()`].
+    pub fn large_size(&self) -> u64 {
+        self.large_size.unwrap_or(u64::from(self.size))
+    }
+
+    /// Chunk id with list type for `LIST` chunks, like [`Summarizable::name()`].
+    /// Ex: `LIST-INFO`
+    pub fn name(&self) -> String {
+        match self.list_type {
+            Some(list_type) => format!("{}-{}", self.id, list_type),
+            None => self.id.to_string().trim().to_string(),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl ChunkID for LazyChunk {
+    fn id(&self) -> FourCC {
+        self.id
+    }
+}
+
+#[cfg(feature = "std")]
+/// Fill in fields which depend on other chunks in the same file.
+fn resolve_references(chunks: &mut [SizedChunkEnum]) {
+    let labels = WaveMetadata::new(chunks).cue_labels();
+    for chunk in chunks {
+        if let SizedChunkEnum::Plst(plst) = chunk {
+            plst.data.set_labels(&labels);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl<R> Debug for WaveFile<R>
 where
     R: Read + Seek + Debug + BufRead,
@@ -457,7 +1729,7 @@ type KCArgs = (u32,);
 /// A generic wrapper around chunk data, handling ID, size and padding.
 #[binrw]
 #[brw(little)]
-#[br(stream = r)]
+#[br(stream = r, import(skip_extra_bytes: bool, max_size: Option<u32>))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KnownChunk<
     T: for<'a> BinRead<Args<'a> = KCArgs> + for<'a> BinWrite<Args<'a> = ()> + KnownChunkID,
@@ -471,13 +1743,29 @@ pub struct KnownChunk<
 
     /// RIFF chunk id.
     #[br(temp, assert(id == T::ID))]
-    #[bw(calc = T::ID)]
+    #[bw(calc = alias.unwrap_or(T::ID))]
     pub id: FourCC,
 
     // TODO: calc by querying content + extra_bytes.len() when writing, or seeking back after you know
     /// RIFF chunk size in bytes.
+    #[br(assert(max_size.is_none_or(|max| size <= max), "{} chunk size ({}) larger than max_chunk_size ({})", T::ID, size, max_size.unwrap_or_default()))]
     pub size: u32,
 
+    /// 64 bit size from a `ds64` chunk, when `size` is 0xFFFFFFFF. See
+    /// [`SizedChunk::large_size()`].
+    ///
+    /// Set by [`WaveFile::iter_chunks()`], ignored when reading and writing.
+    #[brw(ignore)]
+    pub large_size: Option<u64>,
+
+    /// Chunk id as found in the file, when it was parsed as `T::ID` through
+    /// [`ParseOptions::id_aliases`] or
+    /// [`ParseOptions::case_insensitive_ids`]. Written instead of `T::ID`.
+    ///
+    /// Set by [`WaveFile::iter_chunks()`], ignored when reading.
+    #[brw(ignore)]
+    pub alias: Option<FourCC>,
+
     #[br(temp)]
     #[bw(ignore)]
     begin_pos: PosValue<()>,
@@ -495,12 +1783,27 @@ pub struct KnownChunk<
     end_pos: PosValue<()>,
 
     // calculate how much was read, then read...
-    /// Any extra bytes in the chunk after parsing.
+    /// Bytes of the chunk after the part `data` was parsed from, ex:
+    /// fields added by a later version of the specification.
+    ///
+    /// `size` counts them and they are written unchanged after `data`, so a
+    /// chunk round trips as read. Remove them with
+    /// [`KnownChunk::drop_extra_bytes()`], or parse them with a more complete
+    /// parser with [`KnownChunk::reparse()`]. The RIFF padding byte after odd
+    /// sized chunks is not included.
     ///
-    /// May include RIFF padding byte.
-    #[brw(align_after = 2)]
-    #[br(count = size as u64 - (end_pos.pos - begin_pos.pos))]
+    /// Empty when parsed with [`ParseOptions::keep_extra_bytes`] disabled,
+    /// `size` still counts them then.
+    #[br(parse_with = read_extra_bytes, args(size as u64 - (end_pos.pos - begin_pos.pos), skip_extra_bytes))]
     pub extra_bytes: Vec<u8>,
+
+    /// The RIFF padding byte after an odd sized chunk, as read. See
+    /// [`SizedChunk::padding()`].
+    ///
+    /// Written after odd sized chunks, as 0 when `None`.
+    #[br(parse_with = read_padding)]
+    #[bw(write_with = write_padding)]
+    pub padding: Option<u8>,
 }
 
 impl<T> Display for KnownChunk<T>
@@ -522,6 +1825,129 @@ where
     const ID: FourCC = T::ID;
 }
 
+impl<T> KnownChunk<T>
+where
+    T: for<'a> BinRead<Args<'a> = KCArgs> + for<'a> BinWrite<Args<'a> = ()> + KnownChunkID,
+{
+    /// Wrap chunk `data` for writing, calculating `size` from its written
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::cue::{Cue, CueChunk};
+    ///
+    /// let cue = Cue::builder().point(1, 480).build()?;
+    /// let chunk = CueChunk::new(cue)?;
+    /// assert_eq!(chunk.size, 4 + 24);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn new(data: T) -> Result<Self, WaveFileError> {
+        let mut buff = binrw::io::Cursor::new(Vec::new());
+        data.write_le(&mut buff)?;
+        let size = u32::try_from(buff.get_ref().len()).map_err(|_| {
+            WaveFileError::parse(None, format!("{} data too large for a RIFF chunk", T::ID))
+        })?;
+        Ok(KnownChunk {
+            offset: None,
+            size,
+            large_size: None,
+            alias: None,
+            data,
+            extra_bytes: Vec::new(),
+            padding: None,
+        })
+    }
+
+    /// Parse the chunk contents again as `U`: the written `data` followed by
+    /// `extra_bytes`. Bytes `U` doesn't read become the `extra_bytes` of the
+    /// result, `size` is the total length.
+    ///
+    /// Picks up fields an earlier parser left in `extra_bytes`, ex: with a
+    /// parser for a newer version of a chunk than the one the file was read
+    /// with. `U` is usually a type with the same id, `T` itself updates
+    /// `extra_bytes` after changing `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::fact::Fact;
+    /// use wavrw::chunk::junk::Junk;
+    /// use wavrw::KnownChunk;
+    ///
+    /// let mut fact = KnownChunk::new(Fact { samples: 480 })?;
+    /// fact.extra_bytes = vec![1, 2];
+    /// fact.size += 2;
+    ///
+    /// // padding chunks parse no fields, everything is extra
+    /// let junk: KnownChunk<Junk> = fact.reparse()?;
+    /// assert_eq!(junk.extra_bytes, vec![0xE0, 0x01, 0, 0, 1, 2]);
+    /// assert_eq!(junk.size, 6);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn reparse<U>(&self) -> Result<KnownChunk<U>, WaveFileError>
+    where
+        U: for<'a> BinRead<Args<'a> = KCArgs> + for<'a> BinWrite<Args<'a> = ()> + KnownChunkID,
+    {
+        let mut payload = binrw::io::Cursor::new(Vec::new());
+        self.data.write_le(&mut payload)?;
+        let mut payload = payload.into_inner();
+        payload.extend_from_slice(&self.extra_bytes);
+        let size = u32::try_from(payload.len()).map_err(|_| {
+            WaveFileError::parse(
+                self.offset,
+                format!("{} data too large for a RIFF chunk", T::ID),
+            )
+        })?;
+        let mut bytes = Vec::with_capacity(payload.len() + 8);
+        bytes.extend_from_slice(&U::ID.0);
+        bytes.extend_from_slice(&size.to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        let mut chunk =
+            KnownChunk::<U>::read_le_args(&mut binrw::io::Cursor::new(bytes), (false, None))
+                .map_err(|err| WaveFileError::parse(self.offset, err.to_string()))?;
+        chunk.offset = self.offset;
+        chunk.large_size = self.large_size;
+        chunk.alias = self.alias;
+        chunk.padding = self.padding.filter(|_| size % 2 == 1);
+        Ok(chunk)
+    }
+
+    /// Remove `extra_bytes` and subtract their length from `size`, so the
+    /// chunk is written without them. Returns the removed bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::chunk::fact::Fact;
+    /// use wavrw::KnownChunk;
+    ///
+    /// let mut fact = KnownChunk::new(Fact { samples: 480 })?;
+    /// fact.extra_bytes = vec![1, 2];
+    /// fact.size += 2;
+    ///
+    /// assert_eq!(fact.drop_extra_bytes(), vec![1, 2]);
+    /// assert_eq!(fact.size, 4);
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn drop_extra_bytes(&mut self) -> Vec<u8> {
+        let extra_bytes = core::mem::take(&mut self.extra_bytes);
+        let len = u32::try_from(extra_bytes.len()).unwrap_or(u32::MAX);
+        self.size = self.size.saturating_sub(len);
+        if let Some(large_size) = self.large_size.as_mut() {
+            *large_size = large_size.saturating_sub(u64::from(len));
+        }
+        if self
+            .large_size
+            .unwrap_or(u64::from(self.size))
+            .is_multiple_of(2)
+        {
+            self.padding = None;
+        }
+        extra_bytes
+    }
+}
+
 impl<T> SizedChunk for KnownChunk<T>
 where
     T: for<'a> BinRead<Args<'a> = KCArgs>
@@ -537,6 +1963,18 @@ where
     fn offset(&self) -> Option<u64> {
         self.offset
     }
+
+    fn large_size(&self) -> u64 {
+        self.large_size.unwrap_or(u64::from(self.size))
+    }
+
+    fn total_size(&self) -> u64 {
+        padded_total_size(self.large_size(), self.offset, self.padding)
+    }
+
+    fn padding(&self) -> Option<u8> {
+        self.padding
+    }
 }
 
 impl<T> Summarizable for KnownChunk<T>
@@ -549,6 +1987,11 @@ where
     fn summary(&self) -> String {
         self.data.summary()
     }
+
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        self.data.summary_with(options)
+    }
+
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         self.data.items()
     }
@@ -571,10 +2014,71 @@ where
 // {
 // }
 
+/// Read `count` bytes, or seek past them if `skip` is set.
+///
+/// Avoids allocating for the common case of chunks without extra bytes.
+#[binrw::parser(reader, endian)]
+fn read_extra_bytes(count: u64, skip: bool) -> BinResult<Vec<u8>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    if skip {
+        reader.seek(SeekFrom::Current(count as i64))?;
+        return Ok(Vec::new());
+    }
+    read_bytes(reader, endian, (count,))
+}
+
+/// Read the padding byte if the stream is at an odd position, for chunks
+/// with an odd size. `None` at the end of the stream, where the position
+/// still moves past it, like `align_after`.
+#[binrw::parser(reader)]
+pub(crate) fn read_padding() -> BinResult<Option<u8>> {
+    if reader.stream_position()? % 2 == 0 {
+        return Ok(None);
+    }
+    let mut byte = [0_u8];
+    match reader.read(&mut byte)? {
+        0 => {
+            reader.seek(SeekFrom::Current(1))?;
+            Ok(None)
+        }
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+/// Write `padding`, or 0, if the stream is at an odd position.
+#[binrw::writer(writer)]
+fn write_padding(padding: &Option<u8>) -> BinResult<()> {
+    if writer.stream_position()? % 2 == 1 {
+        writer.write_all(&[padding.unwrap_or(0)])?;
+    }
+    Ok(())
+}
+
+/// Read `count` bytes.
+///
+/// Unlike `#[br(count = ...)]`, the buffer grows in steps, so a corrupt
+/// size fails at the end of the stream instead of allocating it all up
+/// front.
+#[binrw::parser(reader)]
+pub(crate) fn read_bytes(count: u64) -> BinResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut remaining = count;
+    while remaining > 0 {
+        let len = remaining.min(64 * 1024);
+        let start = bytes.len();
+        bytes.resize(start + len as usize, 0);
+        reader.read_exact(&mut bytes[start..])?;
+        remaining -= len;
+    }
+    Ok(bytes)
+}
+
 /// Raw chunk data container for unrecognized chunks
 #[binrw]
 #[brw(little)]
-#[br(stream = r)]
+#[br(stream = r, import(skip_raw: bool, max_size: Option<u32>))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UnknownChunk {
     /// Calculated offset from the beginning of the data stream this chunk is from or None.
@@ -588,12 +2092,29 @@ pub struct UnknownChunk {
     pub id: FourCC,
 
     /// RIFF chunk size in bytes.
+    #[br(assert(max_size.is_none_or(|max| size <= max), "{} chunk size ({}) larger than max_chunk_size ({})", id, size, max_size.unwrap_or_default()))]
     pub size: u32,
 
+    /// 64 bit size from a `ds64` chunk, when `size` is 0xFFFFFFFF. See
+    /// [`SizedChunk::large_size()`].
+    ///
+    /// Set by [`WaveFile::iter_chunks()`], ignored when reading and writing.
+    #[brw(ignore)]
+    pub large_size: Option<u64>,
+
     /// Unparsed chunk data as bytes.
-    #[brw(align_after = 2)]
-    #[br(count = size )]
+    ///
+    /// Empty when parsed with [`ParseOptions::keep_extra_bytes`] disabled.
+    #[br(parse_with = read_extra_bytes, args(size.into(), skip_raw))]
     pub raw: Vec<u8>,
+
+    /// The RIFF padding byte after an odd sized chunk, as read. See
+    /// [`SizedChunk::padding()`].
+    ///
+    /// Written after odd sized chunks, as 0 when `None`.
+    #[br(parse_with = read_padding)]
+    #[bw(write_with = write_padding)]
+    pub padding: Option<u8>,
 }
 
 impl Display for UnknownChunk {
@@ -607,7 +2128,9 @@ impl Default for UnknownChunk {
         Self {
             id: FourCC(*b"UNKN"),
             size: 0,
+            large_size: None,
             raw: Vec::new(),
+            padding: None,
             offset: None,
         }
     }
@@ -627,40 +2150,284 @@ impl SizedChunk for UnknownChunk {
     fn offset(&self) -> Option<u64> {
         self.offset
     }
+
+    fn large_size(&self) -> u64 {
+        self.large_size.unwrap_or(u64::from(self.size))
+    }
+
+    fn total_size(&self) -> u64 {
+        padded_total_size(self.large_size(), self.offset, self.padding)
+    }
+
+    fn padding(&self) -> Option<u8> {
+        self.padding
+    }
+}
+
+impl UnknownChunk {
+    /// Bytes per row of [`UnknownChunk::hexdump()`].
+    pub const HEXDUMP_WIDTH: usize = 16;
+
+    /// Rows shown by [`Summarizable::items()`], the first 256 bytes.
+    pub const HEXDUMP_ROWS: usize = 16;
+
+    /// Hex and ASCII rows of `raw`, labeled with their offset in the chunk
+    /// data. At most `max_rows` rows, followed by a row with the number of
+    /// bytes left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::UnknownChunk;
+    ///
+    /// let chunk = UnknownChunk {
+    ///     raw: b"wavrw\x00\x01".to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// let rows: Vec<_> = chunk.hexdump(None).collect();
+    /// assert_eq!(rows[0].0, "0000");
+    /// assert!(rows[0].1.starts_with("77 61 76 72 77 00 01"));
+    /// assert!(rows[0].1.ends_with("|wavrw..|"));
+    /// ```
+    pub fn hexdump(&self, max_rows: Option<usize>) -> impl Iterator<Item = (String, String)> + '_ {
+        let rows = self.raw.chunks(Self::HEXDUMP_WIDTH);
+        let total = rows.len();
+        let shown = max_rows.unwrap_or(total).min(total);
+        let hidden = self.raw.len() - (shown * Self::HEXDUMP_WIDTH).min(self.raw.len());
+        rows.take(shown)
+            .enumerate()
+            .map(|(i, row)| {
+                let hex = row.iter().map(|b| format!("{b:02X}")).join(" ");
+                let ascii: String = row
+                    .iter()
+                    .map(|b| {
+                        if b.is_ascii_graphic() || *b == b' ' {
+                            char::from(*b)
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                (
+                    format!("{:04X}", i * Self::HEXDUMP_WIDTH),
+                    format!(
+                        "{hex:width$} |{ascii}|",
+                        width = Self::HEXDUMP_WIDTH * 3 - 1
+                    ),
+                )
+            })
+            .chain((hidden > 0).then(|| ("...".to_string(), format!("{hidden} more bytes"))))
+    }
 }
 
 impl Summarizable for UnknownChunk {
     fn summary(&self) -> String {
-        "...".to_string()
+        match registry::known(self.id) {
+            Some(known) => format!("{} ({}, unparsed)", self.id.to_string().trim(), known.name),
+            None => "...".to_string(),
+        }
+    }
+
+    fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
+        Box::new(self.hexdump(Some(Self::HEXDUMP_ROWS)))
+    }
+
+    fn item_summary_header(&self) -> String {
+        "offset: hex |ascii|".to_string()
     }
 }
 
 // impl Chunk for UnknownChunk {}
 
 /// All chunk structs as an enum
+///
+/// Variants of chunk families disabled by cargo features are left out,
+/// those chunks are parsed as [`SizedChunkEnum::Unknown`].
 #[allow(missing_docs)]
-#[binrw]
+// derive instead of #[binrw], so #[cfg] variants are removed before binrw sees them
+#[derive(BinRead, BinWrite, Debug, Clone, PartialEq, Eq, Hash)]
 #[brw(little)]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[br(import(skip_extra_bytes: bool, max_chunk_size: Option<u32>))]
 pub enum SizedChunkEnum {
-    Fmt(FmtChunk),
-    Data(DataChunk),
-    Fact(FactChunk),
-    Cue(CueChunk),
-    Info(ListInfoChunk),
-    Adtl(ListAdtlChunk),
-    Wavl(ListWavlChunk),
-    Cset(CsetChunk),
-    Plst(PlstChunk),
-    Inst(InstChunk),
-    Smpl(SmplChunk),
-    Bext(Box<BextChunk>),
-    Md5(Md5Chunk),
-    Fllr(FllrChunk),
-    Junk(JunkChunk),
-    Pad(PadChunk),
-    Ixml(IxmlChunk),
-    Unknown(UnknownChunk),
+    Fmt(#[br(args(skip_extra_bytes, max_chunk_size))] FmtChunk),
+    Data(#[br(args(skip_extra_bytes, max_chunk_size))] DataChunk),
+    Ds64(#[br(args(skip_extra_bytes, max_chunk_size))] Ds64Chunk),
+    Fact(#[br(args(skip_extra_bytes, max_chunk_size))] FactChunk),
+    Cue(#[br(args(skip_extra_bytes, max_chunk_size))] CueChunk),
+    #[cfg(feature = "info")]
+    Info(#[br(args(skip_extra_bytes, max_chunk_size))] ListInfoChunk),
+    Adtl(#[br(args(skip_extra_bytes, max_chunk_size))] ListAdtlChunk),
+    Wavl(#[br(args(skip_extra_bytes, max_chunk_size))] ListWavlChunk),
+    Cset(#[br(args(skip_extra_bytes, max_chunk_size))] CsetChunk),
+    Plst(#[br(args(skip_extra_bytes, max_chunk_size))] PlstChunk),
+    Inst(#[br(args(skip_extra_bytes, max_chunk_size))] InstChunk),
+    Smpl(#[br(args(skip_extra_bytes, max_chunk_size))] SmplChunk),
+    #[cfg(feature = "broadcast")]
+    Bext(#[br(args(skip_extra_bytes, max_chunk_size))] Box<BextChunk>),
+    Md5(#[br(args(skip_extra_bytes, max_chunk_size))] Md5Chunk),
+    Levl(#[br(args(skip_extra_bytes, max_chunk_size))] LevlChunk),
+    Fllr(#[br(args(skip_extra_bytes, max_chunk_size))] FllrChunk),
+    Junk(#[br(args(skip_extra_bytes, max_chunk_size))] JunkChunk),
+    Pad(#[br(args(skip_extra_bytes, max_chunk_size))] PadChunk),
+    Smed(#[br(args(skip_extra_bytes, max_chunk_size))] SmedChunk),
+    Smrd(#[br(args(skip_extra_bytes, max_chunk_size))] SmrdChunk),
+    Disp(#[br(args(skip_extra_bytes, max_chunk_size))] DispChunk),
+    #[cfg(feature = "ixml")]
+    Ixml(#[br(args(skip_extra_bytes, max_chunk_size))] Box<IxmlChunk>),
+    Unknown(#[br(args(skip_extra_bytes, max_chunk_size))] UnknownChunk),
+}
+
+impl SizedChunkEnum {
+    /// Decode text in chunks using `code_page`, see [`codepage`].
+    ///
+    /// [`WaveFile`] does this for each chunk, using the code page from
+    /// [`ParseOptions::code_page`] or the `CSET` chunk. Useful when parsing
+    /// chunks directly.
+    #[cfg_attr(
+        not(any(feature = "info", feature = "broadcast")),
+        allow(unused_variables)
+    )]
+    pub fn set_code_page(&mut self, code_page: u16) {
+        #[cfg(feature = "info")]
+        if let SizedChunkEnum::Info(info) = self {
+            info.data.set_code_page(code_page);
+        }
+        #[cfg(feature = "broadcast")]
+        if let SizedChunkEnum::Bext(bext) = self {
+            bext.data.set_code_page(code_page);
+        }
+    }
+
+    /// Set the 64 bit size from a `ds64` chunk, see
+    /// [`SizedChunk::large_size()`].
+    pub fn set_large_size(&mut self, size: u64) {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.large_size = Some(size),
+            SizedChunkEnum::Data(e) => e.large_size = Some(size),
+            SizedChunkEnum::Fact(e) => e.large_size = Some(size),
+            SizedChunkEnum::Cue(e) => e.large_size = Some(size),
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.large_size = Some(size),
+            SizedChunkEnum::Adtl(e) => e.large_size = Some(size),
+            SizedChunkEnum::Wavl(e) => e.large_size = Some(size),
+            SizedChunkEnum::Cset(e) => e.large_size = Some(size),
+            SizedChunkEnum::Inst(e) => e.large_size = Some(size),
+            SizedChunkEnum::Smpl(e) => e.large_size = Some(size),
+            SizedChunkEnum::Plst(e) => e.large_size = Some(size),
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.large_size = Some(size),
+            SizedChunkEnum::Md5(e) => e.large_size = Some(size),
+            SizedChunkEnum::Levl(e) => e.large_size = Some(size),
+            SizedChunkEnum::Ds64(e) => e.large_size = Some(size),
+            SizedChunkEnum::Fllr(e) => e.large_size = Some(size),
+            SizedChunkEnum::Junk(e) => e.large_size = Some(size),
+            SizedChunkEnum::Pad(e) => e.large_size = Some(size),
+            SizedChunkEnum::Smed(e) => e.large_size = Some(size),
+            SizedChunkEnum::Smrd(e) => e.large_size = Some(size),
+            SizedChunkEnum::Disp(e) => e.large_size = Some(size),
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.large_size = Some(size),
+            SizedChunkEnum::Unknown(e) => e.large_size = Some(size),
+        }
+    }
+
+    /// Set the padding byte read after the chunk, see [`KnownChunk::padding`].
+    pub fn set_padding(&mut self, padding: Option<u8>) {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.padding = padding,
+            SizedChunkEnum::Data(e) => e.padding = padding,
+            SizedChunkEnum::Fact(e) => e.padding = padding,
+            SizedChunkEnum::Cue(e) => e.padding = padding,
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.padding = padding,
+            SizedChunkEnum::Adtl(e) => e.padding = padding,
+            SizedChunkEnum::Wavl(e) => e.padding = padding,
+            SizedChunkEnum::Cset(e) => e.padding = padding,
+            SizedChunkEnum::Inst(e) => e.padding = padding,
+            SizedChunkEnum::Smpl(e) => e.padding = padding,
+            SizedChunkEnum::Plst(e) => e.padding = padding,
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.padding = padding,
+            SizedChunkEnum::Md5(e) => e.padding = padding,
+            SizedChunkEnum::Levl(e) => e.padding = padding,
+            SizedChunkEnum::Ds64(e) => e.padding = padding,
+            SizedChunkEnum::Fllr(e) => e.padding = padding,
+            SizedChunkEnum::Junk(e) => e.padding = padding,
+            SizedChunkEnum::Pad(e) => e.padding = padding,
+            SizedChunkEnum::Smed(e) => e.padding = padding,
+            SizedChunkEnum::Smrd(e) => e.padding = padding,
+            SizedChunkEnum::Disp(e) => e.padding = padding,
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.padding = padding,
+            SizedChunkEnum::Unknown(e) => e.padding = padding,
+        }
+    }
+
+    /// Set the id found in the file, for chunks parsed as another id. See
+    /// [`KnownChunk::alias`]. Unknown chunks keep `alias` as their id.
+    pub fn set_alias(&mut self, alias: FourCC) {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.alias = Some(alias),
+            SizedChunkEnum::Data(e) => e.alias = Some(alias),
+            SizedChunkEnum::Fact(e) => e.alias = Some(alias),
+            SizedChunkEnum::Cue(e) => e.alias = Some(alias),
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.alias = Some(alias),
+            SizedChunkEnum::Adtl(e) => e.alias = Some(alias),
+            SizedChunkEnum::Wavl(e) => e.alias = Some(alias),
+            SizedChunkEnum::Cset(e) => e.alias = Some(alias),
+            SizedChunkEnum::Inst(e) => e.alias = Some(alias),
+            SizedChunkEnum::Smpl(e) => e.alias = Some(alias),
+            SizedChunkEnum::Plst(e) => e.alias = Some(alias),
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.alias = Some(alias),
+            SizedChunkEnum::Md5(e) => e.alias = Some(alias),
+            SizedChunkEnum::Levl(e) => e.alias = Some(alias),
+            SizedChunkEnum::Ds64(e) => e.alias = Some(alias),
+            SizedChunkEnum::Fllr(e) => e.alias = Some(alias),
+            SizedChunkEnum::Junk(e) => e.alias = Some(alias),
+            SizedChunkEnum::Pad(e) => e.alias = Some(alias),
+            SizedChunkEnum::Smed(e) => e.alias = Some(alias),
+            SizedChunkEnum::Smrd(e) => e.alias = Some(alias),
+            SizedChunkEnum::Disp(e) => e.alias = Some(alias),
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.alias = Some(alias),
+            SizedChunkEnum::Unknown(e) => e.id = alias,
+        }
+    }
+
+    /// Id found in the file, when the chunk was parsed as another id. See
+    /// [`KnownChunk::alias`].
+    pub fn alias(&self) -> Option<FourCC> {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.alias,
+            SizedChunkEnum::Data(e) => e.alias,
+            SizedChunkEnum::Fact(e) => e.alias,
+            SizedChunkEnum::Cue(e) => e.alias,
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.alias,
+            SizedChunkEnum::Adtl(e) => e.alias,
+            SizedChunkEnum::Wavl(e) => e.alias,
+            SizedChunkEnum::Cset(e) => e.alias,
+            SizedChunkEnum::Inst(e) => e.alias,
+            SizedChunkEnum::Smpl(e) => e.alias,
+            SizedChunkEnum::Plst(e) => e.alias,
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.alias,
+            SizedChunkEnum::Md5(e) => e.alias,
+            SizedChunkEnum::Levl(e) => e.alias,
+            SizedChunkEnum::Ds64(e) => e.alias,
+            SizedChunkEnum::Fllr(e) => e.alias,
+            SizedChunkEnum::Junk(e) => e.alias,
+            SizedChunkEnum::Pad(e) => e.alias,
+            SizedChunkEnum::Smed(e) => e.alias,
+            SizedChunkEnum::Smrd(e) => e.alias,
+            SizedChunkEnum::Disp(e) => e.alias,
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.alias,
+            SizedChunkEnum::Unknown(_) => None,
+        }
+    }
 }
 
 impl Display for SizedChunkEnum {
@@ -670,6 +2437,7 @@ impl Display for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.to_string(),
             SizedChunkEnum::Fact(e) => e.to_string(),
             SizedChunkEnum::Cue(e) => e.to_string(),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.to_string(),
             SizedChunkEnum::Adtl(e) => e.to_string(),
             SizedChunkEnum::Wavl(e) => e.to_string(),
@@ -677,11 +2445,18 @@ impl Display for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.to_string(),
             SizedChunkEnum::Smpl(e) => e.to_string(),
             SizedChunkEnum::Plst(e) => e.to_string(),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.to_string(),
             SizedChunkEnum::Md5(e) => e.to_string(),
+            SizedChunkEnum::Levl(e) => e.to_string(),
+            SizedChunkEnum::Ds64(e) => e.to_string(),
             SizedChunkEnum::Fllr(e) => e.to_string(),
             SizedChunkEnum::Junk(e) => e.to_string(),
             SizedChunkEnum::Pad(e) => e.to_string(),
+            SizedChunkEnum::Smed(e) => e.to_string(),
+            SizedChunkEnum::Smrd(e) => e.to_string(),
+            SizedChunkEnum::Disp(e) => e.to_string(),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.to_string(),
             SizedChunkEnum::Unknown(e) => e.to_string(),
         };
@@ -696,6 +2471,7 @@ impl ChunkID for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.id(),
             SizedChunkEnum::Fact(e) => e.id(),
             SizedChunkEnum::Cue(e) => e.id(),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.id(),
             SizedChunkEnum::Adtl(e) => e.id(),
             SizedChunkEnum::Wavl(e) => e.id(),
@@ -703,11 +2479,18 @@ impl ChunkID for SizedChunkEnum {
             SizedChunkEnum::Plst(e) => e.id(),
             SizedChunkEnum::Inst(e) => e.id(),
             SizedChunkEnum::Smpl(e) => e.id(),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.id(),
             SizedChunkEnum::Md5(e) => e.id(),
+            SizedChunkEnum::Levl(e) => e.id(),
+            SizedChunkEnum::Ds64(e) => e.id(),
             SizedChunkEnum::Fllr(e) => e.id(),
             SizedChunkEnum::Junk(e) => e.id(),
             SizedChunkEnum::Pad(e) => e.id(),
+            SizedChunkEnum::Smed(e) => e.id(),
+            SizedChunkEnum::Smrd(e) => e.id(),
+            SizedChunkEnum::Disp(e) => e.id(),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.id(),
             SizedChunkEnum::Unknown(e) => e.id(),
         }
@@ -721,6 +2504,7 @@ impl SizedChunk for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.size,
             SizedChunkEnum::Fact(e) => e.size,
             SizedChunkEnum::Cue(e) => e.size,
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.size,
             SizedChunkEnum::Adtl(e) => e.size,
             SizedChunkEnum::Wavl(e) => e.size,
@@ -728,11 +2512,18 @@ impl SizedChunk for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.size,
             SizedChunkEnum::Smpl(e) => e.size,
             SizedChunkEnum::Plst(e) => e.size,
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.size,
             SizedChunkEnum::Md5(e) => e.size,
+            SizedChunkEnum::Levl(e) => e.size,
+            SizedChunkEnum::Ds64(e) => e.size,
             SizedChunkEnum::Fllr(e) => e.size,
             SizedChunkEnum::Junk(e) => e.size,
             SizedChunkEnum::Pad(e) => e.size,
+            SizedChunkEnum::Smed(e) => e.size,
+            SizedChunkEnum::Smrd(e) => e.size,
+            SizedChunkEnum::Disp(e) => e.size,
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.size,
             SizedChunkEnum::Unknown(e) => e.size,
         }
@@ -744,6 +2535,7 @@ impl SizedChunk for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.offset,
             SizedChunkEnum::Fact(e) => e.offset,
             SizedChunkEnum::Cue(e) => e.offset,
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.offset,
             SizedChunkEnum::Adtl(e) => e.offset,
             SizedChunkEnum::Wavl(e) => e.offset,
@@ -751,15 +2543,115 @@ impl SizedChunk for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.offset,
             SizedChunkEnum::Smpl(e) => e.offset,
             SizedChunkEnum::Plst(e) => e.offset,
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.offset,
             SizedChunkEnum::Md5(e) => e.offset,
+            SizedChunkEnum::Levl(e) => e.offset,
+            SizedChunkEnum::Ds64(e) => e.offset,
             SizedChunkEnum::Fllr(e) => e.offset,
             SizedChunkEnum::Junk(e) => e.offset,
             SizedChunkEnum::Pad(e) => e.offset,
+            SizedChunkEnum::Smed(e) => e.offset,
+            SizedChunkEnum::Smrd(e) => e.offset,
+            SizedChunkEnum::Disp(e) => e.offset,
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.offset,
             SizedChunkEnum::Unknown(e) => e.offset,
         }
     }
+
+    fn large_size(&self) -> u64 {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.large_size(),
+            SizedChunkEnum::Data(e) => e.large_size(),
+            SizedChunkEnum::Fact(e) => e.large_size(),
+            SizedChunkEnum::Cue(e) => e.large_size(),
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.large_size(),
+            SizedChunkEnum::Adtl(e) => e.large_size(),
+            SizedChunkEnum::Wavl(e) => e.large_size(),
+            SizedChunkEnum::Cset(e) => e.large_size(),
+            SizedChunkEnum::Inst(e) => e.large_size(),
+            SizedChunkEnum::Smpl(e) => e.large_size(),
+            SizedChunkEnum::Plst(e) => e.large_size(),
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.large_size(),
+            SizedChunkEnum::Md5(e) => e.large_size(),
+            SizedChunkEnum::Levl(e) => e.large_size(),
+            SizedChunkEnum::Ds64(e) => e.large_size(),
+            SizedChunkEnum::Fllr(e) => e.large_size(),
+            SizedChunkEnum::Junk(e) => e.large_size(),
+            SizedChunkEnum::Pad(e) => e.large_size(),
+            SizedChunkEnum::Smed(e) => e.large_size(),
+            SizedChunkEnum::Smrd(e) => e.large_size(),
+            SizedChunkEnum::Disp(e) => e.large_size(),
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.large_size(),
+            SizedChunkEnum::Unknown(e) => e.large_size(),
+        }
+    }
+
+    fn total_size(&self) -> u64 {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.total_size(),
+            SizedChunkEnum::Data(e) => e.total_size(),
+            SizedChunkEnum::Fact(e) => e.total_size(),
+            SizedChunkEnum::Cue(e) => e.total_size(),
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.total_size(),
+            SizedChunkEnum::Adtl(e) => e.total_size(),
+            SizedChunkEnum::Wavl(e) => e.total_size(),
+            SizedChunkEnum::Cset(e) => e.total_size(),
+            SizedChunkEnum::Inst(e) => e.total_size(),
+            SizedChunkEnum::Smpl(e) => e.total_size(),
+            SizedChunkEnum::Plst(e) => e.total_size(),
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.total_size(),
+            SizedChunkEnum::Md5(e) => e.total_size(),
+            SizedChunkEnum::Levl(e) => e.total_size(),
+            SizedChunkEnum::Ds64(e) => e.total_size(),
+            SizedChunkEnum::Fllr(e) => e.total_size(),
+            SizedChunkEnum::Junk(e) => e.total_size(),
+            SizedChunkEnum::Pad(e) => e.total_size(),
+            SizedChunkEnum::Smed(e) => e.total_size(),
+            SizedChunkEnum::Smrd(e) => e.total_size(),
+            SizedChunkEnum::Disp(e) => e.total_size(),
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.total_size(),
+            SizedChunkEnum::Unknown(e) => e.total_size(),
+        }
+    }
+
+    fn padding(&self) -> Option<u8> {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.padding,
+            SizedChunkEnum::Data(e) => e.padding,
+            SizedChunkEnum::Fact(e) => e.padding,
+            SizedChunkEnum::Cue(e) => e.padding,
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.padding,
+            SizedChunkEnum::Adtl(e) => e.padding,
+            SizedChunkEnum::Wavl(e) => e.padding,
+            SizedChunkEnum::Cset(e) => e.padding,
+            SizedChunkEnum::Inst(e) => e.padding,
+            SizedChunkEnum::Smpl(e) => e.padding,
+            SizedChunkEnum::Plst(e) => e.padding,
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.padding,
+            SizedChunkEnum::Md5(e) => e.padding,
+            SizedChunkEnum::Levl(e) => e.padding,
+            SizedChunkEnum::Ds64(e) => e.padding,
+            SizedChunkEnum::Fllr(e) => e.padding,
+            SizedChunkEnum::Junk(e) => e.padding,
+            SizedChunkEnum::Pad(e) => e.padding,
+            SizedChunkEnum::Smed(e) => e.padding,
+            SizedChunkEnum::Smrd(e) => e.padding,
+            SizedChunkEnum::Disp(e) => e.padding,
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.padding,
+            SizedChunkEnum::Unknown(e) => e.padding,
+        }
+    }
 }
 
 impl Summarizable for SizedChunkEnum {
@@ -769,6 +2661,7 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.summary(),
             SizedChunkEnum::Fact(e) => e.summary(),
             SizedChunkEnum::Cue(e) => e.summary(),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.summary(),
             SizedChunkEnum::Adtl(e) => e.summary(),
             SizedChunkEnum::Wavl(e) => e.summary(),
@@ -776,20 +2669,59 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.summary(),
             SizedChunkEnum::Smpl(e) => e.summary(),
             SizedChunkEnum::Plst(e) => e.summary(),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.summary(),
             SizedChunkEnum::Md5(e) => e.summary(),
+            SizedChunkEnum::Levl(e) => e.summary(),
+            SizedChunkEnum::Ds64(e) => e.summary(),
             SizedChunkEnum::Fllr(e) => e.summary(),
             SizedChunkEnum::Junk(e) => e.summary(),
             SizedChunkEnum::Pad(e) => e.summary(),
+            SizedChunkEnum::Smed(e) => e.summary(),
+            SizedChunkEnum::Smrd(e) => e.summary(),
+            SizedChunkEnum::Disp(e) => e.summary(),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.summary(),
             SizedChunkEnum::Unknown(e) => e.summary(),
         }
     }
 
+    fn summary_with(&self, options: &DisplayOptions) -> String {
+        match self {
+            SizedChunkEnum::Fmt(e) => e.summary_with(options),
+            SizedChunkEnum::Data(e) => e.summary_with(options),
+            SizedChunkEnum::Fact(e) => e.summary_with(options),
+            SizedChunkEnum::Cue(e) => e.summary_with(options),
+            #[cfg(feature = "info")]
+            SizedChunkEnum::Info(e) => e.summary_with(options),
+            SizedChunkEnum::Adtl(e) => e.summary_with(options),
+            SizedChunkEnum::Wavl(e) => e.summary_with(options),
+            SizedChunkEnum::Cset(e) => e.summary_with(options),
+            SizedChunkEnum::Inst(e) => e.summary_with(options),
+            SizedChunkEnum::Smpl(e) => e.summary_with(options),
+            SizedChunkEnum::Plst(e) => e.summary_with(options),
+            #[cfg(feature = "broadcast")]
+            SizedChunkEnum::Bext(e) => e.summary_with(options),
+            SizedChunkEnum::Md5(e) => e.summary_with(options),
+            SizedChunkEnum::Levl(e) => e.summary_with(options),
+            SizedChunkEnum::Ds64(e) => e.summary_with(options),
+            SizedChunkEnum::Fllr(e) => e.summary_with(options),
+            SizedChunkEnum::Junk(e) => e.summary_with(options),
+            SizedChunkEnum::Pad(e) => e.summary_with(options),
+            SizedChunkEnum::Smed(e) => e.summary_with(options),
+            SizedChunkEnum::Smrd(e) => e.summary_with(options),
+            SizedChunkEnum::Disp(e) => e.summary_with(options),
+            #[cfg(feature = "ixml")]
+            SizedChunkEnum::Ixml(e) => e.summary_with(options),
+            SizedChunkEnum::Unknown(e) => e.summary_with(options),
+        }
+    }
+
     fn items<'a>(&'a self) -> Box<dyn Iterator<Item = (String, String)> + 'a> {
         match self {
             SizedChunkEnum::Fmt(e) => Box::new(e.items()),
             SizedChunkEnum::Cue(e) => Box::new(e.items()),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => Box::new(e.items()),
             SizedChunkEnum::Adtl(e) => Box::new(e.items()),
             SizedChunkEnum::Wavl(e) => Box::new(e.items()),
@@ -797,15 +2729,22 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => Box::new(e.items()),
             SizedChunkEnum::Smpl(e) => Box::new(e.items()),
             SizedChunkEnum::Plst(e) => Box::new(e.items()),
+            SizedChunkEnum::Ds64(e) => Box::new(e.items()),
+            SizedChunkEnum::Levl(e) => Box::new(e.items()),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => Box::new(e.items()),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => Box::new(e.items()),
+            SizedChunkEnum::Smed(e) => Box::new(e.items()),
+            SizedChunkEnum::Smrd(e) => Box::new(e.items()),
+            SizedChunkEnum::Disp(e) => Box::new(e.items()),
+            SizedChunkEnum::Unknown(e) => e.items(),
             SizedChunkEnum::Data(_)
             | SizedChunkEnum::Fact(_)
             | SizedChunkEnum::Md5(_)
             | SizedChunkEnum::Fllr(_)
             | SizedChunkEnum::Junk(_)
-            | SizedChunkEnum::Pad(_)
-            | SizedChunkEnum::Unknown(_) => Box::new(core::iter::empty()),
+            | SizedChunkEnum::Pad(_) => Box::new(core::iter::empty()),
         }
     }
 
@@ -815,6 +2754,7 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.name(),
             SizedChunkEnum::Fact(e) => e.name(),
             SizedChunkEnum::Cue(e) => e.name(),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.name(),
             SizedChunkEnum::Adtl(e) => e.name(),
             SizedChunkEnum::Wavl(e) => e.name(),
@@ -822,11 +2762,18 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.name(),
             SizedChunkEnum::Smpl(e) => e.name(),
             SizedChunkEnum::Plst(e) => e.name(),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.name(),
             SizedChunkEnum::Md5(e) => e.name(),
+            SizedChunkEnum::Levl(e) => e.name(),
+            SizedChunkEnum::Ds64(e) => e.name(),
             SizedChunkEnum::Fllr(e) => e.name(),
             SizedChunkEnum::Junk(e) => e.name(),
             SizedChunkEnum::Pad(e) => e.name(),
+            SizedChunkEnum::Smed(e) => e.name(),
+            SizedChunkEnum::Smrd(e) => e.name(),
+            SizedChunkEnum::Disp(e) => e.name(),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.name(),
             SizedChunkEnum::Unknown(e) => e.name(),
         }
@@ -838,6 +2785,7 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Data(e) => e.item_summary_header(),
             SizedChunkEnum::Fact(e) => e.item_summary_header(),
             SizedChunkEnum::Cue(e) => e.item_summary_header(),
+            #[cfg(feature = "info")]
             SizedChunkEnum::Info(e) => e.item_summary_header(),
             SizedChunkEnum::Adtl(e) => e.item_summary_header(),
             SizedChunkEnum::Wavl(e) => e.item_summary_header(),
@@ -845,11 +2793,18 @@ impl Summarizable for SizedChunkEnum {
             SizedChunkEnum::Inst(e) => e.item_summary_header(),
             SizedChunkEnum::Smpl(e) => e.item_summary_header(),
             SizedChunkEnum::Plst(e) => e.item_summary_header(),
+            #[cfg(feature = "broadcast")]
             SizedChunkEnum::Bext(e) => e.item_summary_header(),
             SizedChunkEnum::Md5(e) => e.item_summary_header(),
+            SizedChunkEnum::Levl(e) => e.item_summary_header(),
+            SizedChunkEnum::Ds64(e) => e.item_summary_header(),
             SizedChunkEnum::Fllr(e) => e.item_summary_header(),
             SizedChunkEnum::Junk(e) => e.item_summary_header(),
             SizedChunkEnum::Pad(e) => e.item_summary_header(),
+            SizedChunkEnum::Smed(e) => e.item_summary_header(),
+            SizedChunkEnum::Smrd(e) => e.item_summary_header(),
+            SizedChunkEnum::Disp(e) => e.item_summary_header(),
+            #[cfg(feature = "ixml")]
             SizedChunkEnum::Ixml(e) => e.item_summary_header(),
             SizedChunkEnum::Unknown(e) => e.item_summary_header(),
         }
@@ -873,6 +2828,456 @@ mod test {
         assert_eq!(format!("{f:?}"), r#"FourCC(*b"TST "=[84, 83, 84, 32])"#);
     }
 
+    #[test]
+    fn fourcc_from_str() {
+        assert_eq!(FourCC::from_str("iXML").unwrap(), FourCC(*b"iXML"));
+        assert_eq!("cue".parse::<FourCC>().unwrap(), FourCC(*b"cue "));
+        assert!(FourCC::from_str("").is_err());
+        assert!(FourCC::from_str("toolong").is_err());
+        assert!(FourCC::from_str("é").is_err());
+    }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn code_page_from_cset() {
+        // CSET with code page 1251, followed by INAM "Привет" in Windows-1251
+        let data = "52494646 30000000 57415645 43534554 08000000 E3040000 00000000 4C495354 14000000 494E464F 494E414D 07000000 CFF0E8E2 E5F20000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        assert_eq!(wave.code_page(), Some(1251));
+        let Some(Ok(SizedChunkEnum::Info(info))) = wave.iter_chunks().nth(1) else {
+            unreachable!("second chunk should have been LIST-INFO")
+        };
+        assert_eq!(info.data.chunks[0].text(), "Привет");
+
+        // options override CSET
+        let options = ParseOptions::default().with_code_page(1252);
+        let mut wave =
+            WaveFile::from_reader_with_options(crate::testing::hex_to_cursor(data), options)
+                .unwrap();
+        let Some(Ok(SizedChunkEnum::Info(info))) = wave.iter_chunks().nth(1) else {
+            unreachable!("second chunk should have been LIST-INFO")
+        };
+        assert_eq!(info.data.chunks[0].text(), "Ïðèâåò");
+    }
+
+    #[test]
+    fn parse_error_chunk_context() {
+        use core::error::Error;
+
+        // zzzz chunk claims 256 bytes, only 4 are present
+        let data = "52494646 10000000 57415645 7A7A7A7A 00010000 01020304";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let Some(Err(err)) = wave.iter_chunks().next() else {
+            unreachable!("chunk should not parse")
+        };
+        dbg!(&err, err.to_string());
+        let WaveFileError::Parse { id, offset, .. } = &err else {
+            unreachable!("expected a Parse error")
+        };
+        assert_eq!(*id, Some(FourCC(*b"zzzz")));
+        assert_eq!(*offset, Some(12));
+        assert!(err.to_string().starts_with("Parse: zzzz at 0xC: "));
+        assert!(err.source().is_some());
+
+        // context is only recorded once
+        let err = WaveFileError::parse(None, "test").with_chunk(FourCC(*b"fmt "), 12);
+        let err = err.with_chunk(FourCC(*b"LIST"), 36);
+        assert_eq!(err.to_string(), "Parse: fmt  at 0xC: test");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn chunk_data_offset_and_total_size() {
+        // fact, odd sized zzzz with padding, fact
+        let data = "52494646 28000000 57415645 66616374 04000000 01000000 \
+            7A7A7A7A 03000000 01020300 66616374 04000000 02000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks);
+        let unknown = &chunks[1];
+        assert_eq!(unknown.size(), 3);
+        assert_eq!(unknown.offset(), Some(24));
+        assert_eq!(unknown.data_offset(), Some(32));
+        assert_eq!(unknown.total_size(), 12);
+        assert_eq!(
+            unknown.offset().map(|o| o + unknown.total_size()),
+            chunks[2].offset()
+        );
+        assert_eq!(chunks[0].total_size(), 12);
+
+        let fact = KnownChunk::new(crate::chunk::fact::Fact { samples: 1 }).unwrap();
+        assert_eq!(fact.data_offset(), None);
+        assert_eq!(fact.total_size(), 12);
+    }
+
+    #[test]
+    fn chunk_padding() {
+        // fact, odd sized zzzz with a non zero padding byte, fact
+        let data = "52494646 28000000 57415645 66616374 04000000 01000000 \
+            7A7A7A7A 03000000 010203FF 66616374 04000000 02000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks);
+        assert_eq!(chunks[0].padding(), None);
+        assert_eq!(chunks[1].padding(), Some(0xFF));
+        assert_eq!(chunks[1].total_size(), 12);
+        let mut written = binrw::io::Cursor::new(Vec::new());
+        chunks[1].write_le(&mut written).unwrap();
+        assert_eq!(written.into_inner(), b"zzzz\x03\0\0\0\x01\x02\x03\xFF");
+
+        // the same without the padding byte
+        let data = "52494646 27000000 57415645 66616374 04000000 01000000 \
+            7A7A7A7A 03000000 010203 66616374 04000000 02000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1].padding(), None);
+        assert_eq!(chunks[1].total_size(), 11);
+        assert_eq!(chunks[1].offset().map(|o| o + 11), chunks[2].offset());
+        assert_eq!(wave.warnings()[0].code, Code::MissingPadding);
+        // written with the padding byte it was missing
+        let mut written = binrw::io::Cursor::new(Vec::new());
+        chunks[1].write_le(&mut written).unwrap();
+        assert_eq!(written.into_inner(), b"zzzz\x03\0\0\0\x01\x02\x03\0");
+    }
+
+    #[test]
+    fn iter_chunks_filtered() {
+        // fact, zzzz (skipped), fact
+        let data = "52494646 28000000 57415645 66616374 04000000 01000000 \
+            7A7A7A7A 04000000 01020304 66616374 04000000 02000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave
+            .iter_chunks_filtered(&[FourCC(*b"fact")])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        dbg!(&chunks);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c.id() == FourCC(*b"fact")));
+        assert_eq!(chunks[1].offset(), Some(36));
+        assert_eq!(wave.parse_stats().total().count, 2);
+
+        // nothing matches
+        let chunks: Vec<_> = wave.iter_chunks_filtered(&[FourCC(*b"bext")]).collect();
+        assert!(chunks.is_empty());
+
+        // unfiltered iteration is unchanged
+        assert_eq!(wave.iter_chunks().count(), 3);
+    }
+
+    #[test]
+    fn iter_chunks_large_size() {
+        // RF64 with ds64, data with a 0xFFFFFFFF size, fact
+        let data = "52463634 FFFFFFFF 57415645 \
+            64733634 1C000000 40000000 00000000 04000000 00000000 01000000 00000000 00000000 \
+            64617461 FFFFFFFF 01020304 66616374 04000000 01000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks, wave.warnings());
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].id(), FourCC(*b"ds64"));
+        assert_eq!(chunks[1].size(), u32::MAX);
+        assert_eq!(chunks[1].large_size(), 4);
+        assert_eq!(chunks[1].total_size(), 12);
+        assert_eq!(chunks[2].id(), FourCC(*b"fact"));
+        assert_eq!(chunks[2].offset(), Some(60));
+        // only the placeholder RF64 size is reported
+        assert_eq!(wave.warnings().len(), 1);
+        assert_eq!(wave.warnings()[0].id, FourCC(*b"RF64"));
+
+        // ds64 is used even if filtered out
+        let chunks: Vec<_> = wave
+            .iter_chunks_filtered(&[FourCC(*b"fact")])
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+
+        let lazy = wave.scan_chunks().unwrap();
+        assert_eq!(lazy[1].large_size(), 4);
+        assert_eq!(lazy[2].offset, 60);
+    }
+
+    fn chunk_ids(data: &str, options: ParseOptions) -> Vec<Result<FourCC, WaveFileError>> {
+        let mut wave =
+            WaveFile::from_reader_with_options(crate::testing::hex_to_cursor(data), options)
+                .unwrap();
+        wave.iter_chunks().map(|c| c.map(|c| c.id())).collect()
+    }
+
+    #[test]
+    fn parse_options_missing_padding() {
+        // odd sized JUNK chunk without padding byte, followed by fact
+        let data = "52494646 19000000 57415645 4A554E4B 01000000 00 66616374 04000000 E0010000";
+        let ids = chunk_ids(data, ParseOptions::default());
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[1].as_ref().unwrap(), &FourCC(*b"fact"));
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        assert_eq!(wave.iter_chunks().count(), 2);
+        dbg!(wave.warnings());
+        assert_eq!(wave.warnings().len(), 1);
+        assert_eq!(wave.warnings()[0].id, FourCC(*b"JUNK"));
+
+        let ids = chunk_ids(
+            data,
+            ParseOptions::default().with_allow_missing_padding(false),
+        );
+        assert_ne!(
+            ids.get(1).map(|r| r.as_ref().ok()),
+            Some(Some(&FourCC(*b"fact")))
+        );
+    }
+
+    #[test]
+    fn parse_options_trust_riff_size() {
+        // RIFF size only covers the fact chunk, a JUNK chunk follows
+        let data = "52494646 10000000 57415645 66616374 04000000 E0010000 4A554E4B 02000000 0000";
+        assert_eq!(chunk_ids(data, ParseOptions::default()).len(), 1);
+        let ids = chunk_ids(data, ParseOptions::default().with_trust_riff_size(false));
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[1].as_ref().unwrap(), &FourCC(*b"JUNK"));
+    }
+
+    #[test]
+    fn parse_options_case_insensitive_ids() {
+        // FACT chunk, should be fact
+        let data = "52494646 10000000 57415645 46414354 04000000 E0010000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks, wave.warnings());
+        let SizedChunkEnum::Fact(fact) = &chunks[0] else {
+            panic!("FACT not parsed as fact: {:?}", chunks[0]);
+        };
+        assert_eq!(fact.data.samples, 480);
+        assert_eq!(chunks[0].alias(), Some(FourCC(*b"FACT")));
+        assert_eq!(wave.warnings().len(), 1);
+        assert_eq!(wave.warnings()[0].id, FourCC(*b"FACT"));
+
+        // the original id is written back
+        let mut buff = binrw::io::Cursor::new(Vec::new());
+        chunks[0].write(&mut buff).unwrap();
+        assert_eq!(
+            buff.into_inner(),
+            crate::testing::hex_to_cursor("46414354 04000000 E0010000").into_inner()
+        );
+
+        let filtered: Vec<_> = wave
+            .iter_chunks_filtered(&[crate::chunk::fact::Fact::ID])
+            .collect();
+        assert_eq!(filtered.len(), 1);
+
+        let mut lazy = wave.scan_chunks().unwrap();
+        assert_eq!(lazy[0].id, FourCC(*b"FACT"));
+        let parsed = lazy.remove(0).parse(&mut wave).unwrap();
+        assert_eq!(parsed.alias(), Some(FourCC(*b"FACT")));
+
+        let ids = chunk_ids(data, ParseOptions::strict());
+        assert_eq!(ids[0].as_ref().unwrap(), &FourCC(*b"FACT"));
+        let mut wave = WaveFile::from_reader_with_options(
+            crate::testing::hex_to_cursor(data),
+            ParseOptions::strict(),
+        )
+        .unwrap();
+        let chunk = wave.iter_chunks().next().unwrap().unwrap();
+        assert!(matches!(chunk, SizedChunkEnum::Unknown(_)));
+        assert!(wave.warnings().is_empty());
+    }
+
+    #[test]
+    fn parse_options_id_aliases() {
+        // fct_ chunk, aliased to fact, then an alias to an unsupported id
+        let data = "52494646 1A000000 57415645 6663745F 04000000 E0010000 78787878 02000000 0000";
+        let options = ParseOptions::strict()
+            .with_id_alias(FourCC(*b"fct_"), FourCC(*b"fact"))
+            .with_id_alias(FourCC(*b"xxxx"), FourCC(*b"yyyy"));
+        let mut wave =
+            WaveFile::from_reader_with_options(crate::testing::hex_to_cursor(data), options)
+                .unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        dbg!(&chunks, wave.warnings());
+        assert_eq!(chunks[0].id(), FourCC(*b"fact"));
+        assert_eq!(chunks[0].alias(), Some(FourCC(*b"fct_")));
+        assert_eq!(chunks[1].id(), FourCC(*b"xxxx"));
+        assert_eq!(chunks[1].alias(), None);
+        assert_eq!(wave.warnings().len(), 1);
+    }
+
+    #[test]
+    fn unknown_chunk_hexdump() {
+        let chunk = UnknownChunk {
+            raw: (0..=255).chain(0..=9).collect(),
+            ..Default::default()
+        };
+        let rows: Vec<_> = chunk.items().collect();
+        dbg!(&rows);
+        assert_eq!(rows.len(), UnknownChunk::HEXDUMP_ROWS + 1);
+        assert_eq!(rows[2].0, "0020");
+        assert_eq!(
+            rows[2].1,
+            format!(
+                "{} | !\"#$%&'()*+,-./|",
+                (0x20..0x30).map(|b| format!("{b:02X}")).join(" ")
+            )
+        );
+        assert_eq!(rows[16], ("...".to_string(), "10 more bytes".to_string()));
+
+        let rows: Vec<_> = chunk.hexdump(None).collect();
+        assert_eq!(rows.len(), 17);
+        assert_eq!(
+            rows[16].1,
+            format!("{:47} |..........|", "00 01 02 03 04 05 06 07 08 09")
+        );
+        assert_eq!(UnknownChunk::default().items().count(), 0);
+    }
+
+    #[test]
+    fn parse_options_keep_extra_bytes() {
+        // JUNK with 4 bytes, unknown odd sized chunk with padding, then fact
+        let data = "52494646 28000000 57415645 4A554E4B 04000000 01020304 61626364 03000000 01020300 66616374 04000000 E0010000";
+        for keep in [true, false] {
+            let options = ParseOptions::default().with_keep_extra_bytes(keep);
+            let mut wave =
+                WaveFile::from_reader_with_options(crate::testing::hex_to_cursor(data), options)
+                    .unwrap();
+            let chunks: Vec<_> = wave.iter_chunks().map(Result::unwrap).collect();
+            dbg!(&chunks);
+            assert_eq!(chunks.len(), 3);
+            let SizedChunkEnum::Junk(junk) = &chunks[0] else {
+                unreachable!("should have been JUNK")
+            };
+            assert_eq!(junk.extra_bytes.len(), if keep { 4 } else { 0 });
+            let SizedChunkEnum::Unknown(unknown) = &chunks[1] else {
+                unreachable!("should have been unknown")
+            };
+            assert_eq!(unknown.raw.len(), if keep { 3 } else { 0 });
+            assert_eq!(chunks[2].id(), FourCC(*b"fact"));
+            assert!(wave.warnings().is_empty());
+        }
+    }
+
+    /// `fact` with a second field, standing in for a newer version of a chunk.
+    #[binrw]
+    #[brw(little)]
+    #[br(import(_size: u32))]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct FactV2 {
+        samples: u32,
+        more: u16,
+    }
+
+    impl KnownChunkID for FactV2 {
+        const ID: FourCC = FourCC(*b"fact");
+    }
+
+    #[test]
+    fn reparse_extra_bytes() {
+        // fact with 2 bytes more than its single field
+        let data = "52494646 12000000 57415645 66616374 06000000 E0010000 0700";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let SizedChunkEnum::Fact(fact) = wave.iter_chunks().next().unwrap().unwrap() else {
+            unreachable!("should have been fact")
+        };
+        assert_eq!(fact.extra_bytes, vec![7, 0]);
+        let mut written = binrw::io::Cursor::new(Vec::new());
+        fact.write_le(&mut written).unwrap();
+        assert_eq!(
+            written.into_inner(),
+            crate::testing::hex_to_cursor(&data[27..]).into_inner()
+        );
+
+        let v2: KnownChunk<FactV2> = fact.reparse().unwrap();
+        dbg!(&v2);
+        assert_eq!(
+            v2.data,
+            FactV2 {
+                samples: 480,
+                more: 7
+            }
+        );
+        assert!(v2.extra_bytes.is_empty());
+        assert_eq!((v2.offset, v2.size), (Some(12), 6));
+        assert_eq!(v2.reparse::<chunk::fact::Fact>().unwrap(), fact);
+
+        let mut fact = fact;
+        assert_eq!(fact.drop_extra_bytes(), vec![7, 0]);
+        assert_eq!(fact.size, 4);
+        assert!(fact.reparse::<FactV2>().is_err());
+    }
+
+    #[test]
+    fn placeholder_riff_size() {
+        for riff_size in ["00000000", "FFFFFFFF", "FF000000"] {
+            let data = format!(
+                "52494646 {riff_size} 57415645 66616374 04000000 E0010000 4A554E4B 02000000 0000"
+            );
+            let ids = chunk_ids(&data, ParseOptions::default());
+            assert_eq!(ids.len(), 2, "RIFF size: {riff_size}");
+            assert_eq!(ids[1].as_ref().unwrap(), &FourCC(*b"JUNK"));
+        }
+    }
+
+    #[test]
+    fn placeholder_data_size() {
+        // data chunk sizes 0 and 0xFFFFFFFF, followed by audio data
+        for data_size in ["00000000", "FFFFFFFF"] {
+            let data = format!(
+                "52494646 FFFFFFFF 57415645 66616374 04000000 E0010000 64617461 {data_size} 0102FE04 05060708"
+            );
+            let ids = chunk_ids(&data, ParseOptions::default());
+            dbg!(&ids);
+            assert_eq!(ids.len(), 2, "data size: {data_size}");
+            assert_eq!(ids[1].as_ref().unwrap(), &FourCC(*b"data"));
+        }
+    }
+
+    #[test]
+    fn duplicate_chunks() {
+        // two fact chunks
+        let data =
+            "52494646 1C000000 57415645 66616374 04000000 E0010000 66616374 04000000 01000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let facts = wave.chunks_by_id(FourCC(*b"fact"));
+        assert_eq!(facts.len(), 2);
+        let SizedChunkEnum::Fact(fact) = facts[1] else {
+            unreachable!("should have been fact")
+        };
+        assert_eq!(fact.data.samples, 1);
+        assert!(wave.chunks_by_id(FourCC(*b"bext")).is_empty());
+
+        let warnings = wave.warnings();
+        dbg!(warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, FourCC(*b"fact"));
+        assert_eq!(warnings[0].offset, Some(24));
+    }
+
+    #[test]
+    fn parse_options_strict() {
+        // RIFF size is 4 bytes too small for the fact chunk
+        let data = "52494646 0C000000 57415645 66616374 04000000 E0010000";
+        assert!(chunk_ids(data, ParseOptions::default())[0].is_ok());
+        assert!(chunk_ids(data, ParseOptions::strict())[0].is_err());
+
+        let data = "52494646 10000000 57415645 66616374 04000000 E0010000";
+        assert!(chunk_ids(data, ParseOptions::default().with_max_chunk_size(2))[0].is_err());
+    }
+
+    #[test]
+    fn max_chunk_size_args() {
+        let mut buff = crate::testing::hex_to_cursor("66616374 04000000 E0010000");
+        assert!(SizedChunkEnum::read_args(&mut buff, (false, Some(4))).is_ok());
+        buff.set_position(0);
+        let err = SizedChunkEnum::read_args(&mut buff, (false, Some(2))).unwrap_err();
+        dbg!(&err);
+        assert!(WaveFileError::from(err)
+            .to_string()
+            .contains("max_chunk_size"));
+
+        // unknown chunk claiming 0xFFFFFFF0 bytes
+        let mut buff = crate::testing::hex_to_cursor("61626364 F0FFFFFF 00");
+        assert!(SizedChunkEnum::read_args(&mut buff, (false, Some(1024))).is_err());
+    }
+
     #[test]
     fn knownchunk_as_trait() {
         let md5 = Md5Chunk {
@@ -880,6 +3285,9 @@ mod test {
             size: 16,
             data: chunk::md5::Md5 { md5: 0 },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         };
         // ensure trait bounds are satisfied
         let mut _trt: Box<dyn SizedChunk> = Box::new(md5);
@@ -892,6 +3300,9 @@ mod test {
             size: 16,
             data: chunk::md5::Md5 { md5: 0 },
             extra_bytes: vec![],
+            padding: None,
+            large_size: None,
+            alias: None,
         });
         // ensure trait bounds are satisfied
         let mut _trt: Box<dyn SizedChunk> = Box::new(md5);
@@ -911,4 +3322,58 @@ mod test {
         // this Enum transitively ensures the traits of all subchunks
         has_standard_traits::<SizedChunkEnum>();
     }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn scan_chunks_lazy() {
+        let file =
+            std::io::BufReader::new(std::fs::File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let scanned = wave.scan_chunks().unwrap();
+        dbg!(&scanned);
+        let parsed: Vec<SizedChunkEnum> = wave.iter_chunks().map(Result::unwrap).collect();
+        assert_eq!(
+            scanned.iter().map(LazyChunk::name).collect::<Vec<_>>(),
+            parsed.iter().map(Summarizable::name).collect::<Vec<_>>()
+        );
+        for (lazy, chunk) in scanned.iter().zip(&parsed) {
+            assert_eq!(lazy.offset, chunk.offset().unwrap());
+            assert_eq!(lazy.size, chunk.size());
+            assert_eq!(lazy.data.is_some(), lazy.size <= LazyChunk::INLINE_SIZE);
+        }
+        let ixml = scanned.iter().find(|c| c.id == FourCC(*b"iXML")).unwrap();
+        assert_eq!(ixml.parse(&mut wave).unwrap(), parsed[7]);
+    }
+
+    #[test]
+    fn raw_chunk_bytes() {
+        let path = "../test_wavs/example_a.wav";
+        let file_bytes = std::fs::read(path).unwrap();
+        let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let scanned = wave.scan_chunks().unwrap();
+        for (i, chunk) in scanned.iter().enumerate() {
+            let index = scanned[..i].iter().filter(|c| c.id == chunk.id).count();
+            let mut raw = Vec::new();
+            wave.raw_chunk_bytes(chunk.id, index)
+                .unwrap()
+                .unwrap()
+                .read_to_end(&mut raw)
+                .unwrap();
+            let start = usize::try_from(chunk.offset).unwrap();
+            let end = start + 8 + usize::try_from(chunk.large_size()).unwrap();
+            assert_eq!(raw, &file_bytes[start..end], "{}", chunk.id);
+        }
+        assert!(wave.raw_chunk_bytes(FourCC(*b"nope"), 0).unwrap().is_none());
+
+        // duplicate chunks are counted separately
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(
+            "52494646 18000000 57415645 4A554E4B 01000000 AA00 4A554E4B 02000000 BBCC",
+        ))
+        .unwrap();
+        let mut raw = Vec::new();
+        let mut chunk = wave.raw_chunk_bytes(FourCC(*b"JUNK"), 1).unwrap().unwrap();
+        chunk.read_to_end(&mut raw).unwrap();
+        assert_eq!(raw, [b'J', b'U', b'N', b'K', 2, 0, 0, 0, 0xBB, 0xCC]);
+    }
 }
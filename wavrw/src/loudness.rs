@@ -0,0 +1,452 @@
+//! EBU R 128 loudness of the audio in the `data` chunk.
+//!
+//! [`measure()`] decodes the audio with [`Samples`] and measures it as
+//! described in ITU-R BS.1770-4 and EBU Tech 3341/3342: integrated loudness,
+//! loudness range, true peak and the highest momentary and short-term
+//! loudness. These are the values stored in the loudness fields of a
+//! version 2 `bext` chunk, see [`Loudness::fill_bext()`].
+//!
+//! Requires the `loudness` feature.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let loudness = wavrw::loudness::measure(&mut wave)?;
+//! println!("{loudness}");
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::f64::consts::PI;
+use core::fmt::{Debug, Display, Formatter};
+use std::io::{BufRead, Read, Seek};
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::{Bext, BextBuilder};
+use crate::peaks::TruePeak;
+use crate::samples::Samples;
+use crate::{WaveFile, WaveFileError};
+
+/// Loudness below which blocks are ignored, in LUFS.
+const ABSOLUTE_GATE: f64 = -70.0;
+
+/// Momentary loudness window, in 100 ms steps.
+const MOMENTARY_STEPS: usize = 4;
+
+/// Short-term loudness window, in 100 ms steps.
+const SHORT_TERM_STEPS: usize = 30;
+
+/// Loudness measurements of a file, see the [module documentation](self).
+///
+/// Values are `f64::NEG_INFINITY` when there is no audio above the gate,
+/// or the audio is shorter than the measurement window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// Integrated loudness in LUFS, gated as in BS.1770-4.
+    pub integrated: f64,
+
+    /// Loudness range in LU, as in EBU Tech 3342. 0.0 without audio.
+    pub range: f64,
+
+    /// Maximum true peak level in dBTP.
+    pub true_peak: f64,
+
+    /// Highest momentary (400 ms) loudness in LUFS.
+    pub max_momentary: f64,
+
+    /// Highest short-term (3 s) loudness in LUFS.
+    pub max_short_term: f64,
+}
+
+impl Loudness {
+    /// Set the loudness values of a `bext` chunk being built.
+    ///
+    /// Values below the `bext` range, ex: `-inf` for silence, are stored as
+    /// the lowest value the field can hold.
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::chunk::bext::Bext;
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// let loudness = wavrw::loudness::measure(&mut wave)?;
+    /// let bext = loudness.fill_bext(Bext::builder()).build()?;
+    /// assert!(bext.has_loudness());
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    #[cfg(feature = "broadcast")]
+    #[must_use]
+    pub fn fill_bext(&self, builder: BextBuilder) -> BextBuilder {
+        builder
+            .loudness_value(bext_value(self.integrated))
+            .loudness_range(bext_value(self.range))
+            .max_true_peak_level(bext_value(self.true_peak))
+            .max_momentary_loudness(bext_value(self.max_momentary))
+            .max_short_term_loudness(bext_value(self.max_short_term))
+    }
+
+    /// Set the loudness fields of an existing `bext` chunk, upgrading it to
+    /// version 2 if needed. See [`Loudness::fill_bext()`].
+    #[cfg(feature = "broadcast")]
+    pub fn apply_to(&self, bext: &mut Bext) -> Result<(), WaveFileError> {
+        let filled = self.fill_bext(Bext::builder()).build()?;
+        bext.version = bext.version.max(2);
+        bext.loudness_value = filled.loudness_value;
+        bext.loudness_range = filled.loudness_range;
+        bext.max_true_peak_level = filled.max_true_peak_level;
+        bext.max_momentary_loudness = filled.max_momentary_loudness;
+        bext.max_short_term_loudness = filled.max_short_term_loudness;
+        Ok(())
+    }
+}
+
+/// `value` limited to the range of a `bext` loudness field.
+#[cfg(feature = "broadcast")]
+fn bext_value(value: f64) -> f64 {
+    value.clamp(f64::from(i16::MIN) / 100.0, f64::from(i16::MAX) / 100.0)
+}
+
+impl Display for Loudness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "integrated: {:.1} LUFS, range: {:.1} LU, true peak: {:.1} dBTP, \
+            max momentary: {:.1} LUFS, max short-term: {:.1} LUFS",
+            self.integrated, self.range, self.true_peak, self.max_momentary, self.max_short_term
+        )
+    }
+}
+
+/// Measure the loudness of the audio in `wave`.
+///
+/// Returns an error if the audio can't be decoded, see [`Samples::new()`].
+pub fn measure<R>(wave: &mut WaveFile<R>) -> Result<Loudness, WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    let mut samples = Samples::new(wave)?;
+    let mut meter = LoudnessMeter::new(samples.channels(), samples.sample_rate());
+    let mut frames = Vec::new();
+    while samples.read_frames(&mut frames, 4096)? > 0 {
+        meter.add_frames(&frames);
+    }
+    Ok(meter.loudness())
+}
+
+/// Loudness meter fed with interleaved frames, used by [`measure()`].
+///
+/// ```
+/// use wavrw::loudness::LoudnessMeter;
+///
+/// let mut meter = LoudnessMeter::new(1, 48000);
+/// meter.add_frames(&vec![0.0; 48000]);
+/// assert_eq!(meter.loudness().integrated, f64::NEG_INFINITY);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    channels: usize,
+    weights: Vec<f64>,
+    filters: Vec<KWeighting>,
+    peaks: Vec<TruePeak>,
+    // frames per 100 ms step
+    step_len: usize,
+    // frames and per channel sums of squares of the current step
+    step_pos: usize,
+    step_sums: Vec<f64>,
+    // channel weighted mean square of every complete step
+    steps: Vec<f64>,
+}
+
+impl LoudnessMeter {
+    /// Meter for audio with `channels` channels at `sample_rate` frames per
+    /// second.
+    ///
+    /// Channels are weighted 1.0, except the surround channels of 6 channel
+    /// (5.1) audio, 1.41, and its LFE channel, which is ignored.
+    pub fn new(channels: usize, sample_rate: u32) -> Self {
+        let weights = (0..channels)
+            .map(|channel| match (channels, channel) {
+                (6, 3) => 0.0,
+                (6, 4 | 5) => 1.41,
+                _ => 1.0,
+            })
+            .collect();
+        let sample_rate = f64::from(sample_rate.max(1));
+        LoudnessMeter {
+            channels,
+            weights,
+            filters: vec![KWeighting::new(sample_rate); channels],
+            peaks: vec![TruePeak::new(sample_rate); channels],
+            // sample rates are far below usize::MAX
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            step_len: ((sample_rate / 10.0).round() as usize).max(1),
+            step_pos: 0,
+            step_sums: vec![0.0; channels],
+            steps: Vec::new(),
+        }
+    }
+
+    /// Add interleaved frames. A partial frame at the end is ignored.
+    pub fn add_frames(&mut self, frames: &[f64]) {
+        if self.channels == 0 {
+            return;
+        }
+        for frame in frames.chunks_exact(self.channels) {
+            for (channel, sample) in frame.iter().enumerate() {
+                self.peaks[channel].add(*sample);
+                let filtered = self.filters[channel].process(*sample);
+                self.step_sums[channel] += filtered * filtered;
+            }
+            self.step_pos += 1;
+            if self.step_pos == self.step_len {
+                #[allow(clippy::cast_precision_loss)]
+                let len = self.step_len as f64;
+                let energy = self
+                    .step_sums
+                    .iter()
+                    .zip(&self.weights)
+                    .map(|(sum, weight)| weight * sum / len)
+                    .sum();
+                self.steps.push(energy);
+                self.step_sums.fill(0.0);
+                self.step_pos = 0;
+            }
+        }
+    }
+
+    /// Loudness of the frames added so far. A partial 100 ms step at the
+    /// end counts for the true peak only.
+    pub fn loudness(&self) -> Loudness {
+        let momentary = self.windows(MOMENTARY_STEPS);
+        let short_term = self.windows(SHORT_TERM_STEPS);
+        let true_peak = self.peaks.iter().map(TruePeak::peak).fold(0.0, f64::max);
+        Loudness {
+            integrated: integrated(&momentary),
+            range: range(&short_term),
+            true_peak: 20.0 * true_peak.log10(),
+            max_momentary: max_loudness(&momentary),
+            max_short_term: max_loudness(&short_term),
+        }
+    }
+
+    /// Mean energy of every window of `len` steps, one per step.
+    fn windows(&self, len: usize) -> Vec<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        let len_f64 = len as f64;
+        self.steps
+            .windows(len)
+            .map(|window| window.iter().sum::<f64>() / len_f64)
+            .collect()
+    }
+}
+
+/// Loudness in LUFS of a channel weighted mean square.
+fn lufs(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+/// Mean of `energies` above `gate` LUFS, `None` if there are none.
+fn gated_mean(energies: &[f64], gate: f64) -> Option<f64> {
+    let (sum, count) = energies
+        .iter()
+        .filter(|e| lufs(**e) > gate)
+        .fold((0.0, 0_u32), |(sum, count), e| (sum + e, count + 1));
+    (count > 0).then(|| sum / f64::from(count))
+}
+
+/// Integrated loudness of 400 ms blocks, BS.1770-4 section 2.8.
+fn integrated(blocks: &[f64]) -> f64 {
+    let Some(ungated) = gated_mean(blocks, ABSOLUTE_GATE) else {
+        return f64::NEG_INFINITY;
+    };
+    let gate = (lufs(ungated) - 10.0).max(ABSOLUTE_GATE);
+    gated_mean(blocks, gate).map_or(f64::NEG_INFINITY, lufs)
+}
+
+/// Loudness range of 3 s blocks, EBU Tech 3342.
+fn range(blocks: &[f64]) -> f64 {
+    let Some(ungated) = gated_mean(blocks, ABSOLUTE_GATE) else {
+        return 0.0;
+    };
+    let gate = (lufs(ungated) - 20.0).max(ABSOLUTE_GATE);
+    let mut levels: Vec<f64> = blocks
+        .iter()
+        .map(|e| lufs(*e))
+        .filter(|l| *l > gate)
+        .collect();
+    if levels.is_empty() {
+        return 0.0;
+    }
+    levels.sort_by(f64::total_cmp);
+    let percentile = |p: f64| {
+        // index within levels, so no truncation or sign loss
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let index = ((levels.len() - 1) as f64 * p).round() as usize;
+        levels[index]
+    };
+    percentile(0.95) - percentile(0.10)
+}
+
+fn max_loudness(blocks: &[f64]) -> f64 {
+    blocks
+        .iter()
+        .map(|e| lufs(*e))
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Biquad filter, transposed direct form II.
+#[derive(Debug, Clone, Copy)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    state: [f64; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.state[0];
+        self.state[0] = self.b[1] * x - self.a[0] * y + self.state[1];
+        self.state[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// K-weighting pre-filter of BS.1770-4: a high shelf then a high pass.
+///
+/// The coefficients are calculated for the sample rate, matching the 48 kHz
+/// values of the standard.
+#[derive(Debug, Clone, Copy)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: f64) -> Self {
+        let f0 = 1_681.974_450_955_533;
+        let gain = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (PI * f0 / sample_rate).tan();
+        let vh = 10_f64.powf(gain / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            state: [0.0; 2],
+        };
+
+        let f0 = 38.135_470_876_024_44;
+        let q = 0.500_327_037_323_877_3;
+        let k = (PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            state: [0.0; 2],
+        };
+        KWeighting { shelf, high_pass }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(x))
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Interleaved stereo sine of `seconds` at 48 kHz.
+    fn sine(frequency: f64, amplitude: f64, phase: f64, seconds: u32) -> Vec<f64> {
+        (0..48_000 * seconds)
+            .flat_map(|n| {
+                let t = f64::from(n) / 48_000.0;
+                let sample = amplitude * (2.0 * PI * frequency * t + phase).sin();
+                [sample, sample]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sine_1k() {
+        // like EBU Tech 3341 test case 1: a stereo 1 kHz sine at N dBFS
+        // measures N LUFS
+        let amplitude = 10_f64.powf(-20.0 / 20.0);
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        meter.add_frames(&sine(1_000.0, amplitude, 0.0, 20));
+        let loudness = meter.loudness();
+        dbg!(&loudness);
+        assert!((loudness.integrated - -20.0).abs() < 0.1);
+        assert!((loudness.max_momentary - loudness.integrated).abs() < 0.1);
+        assert!((loudness.max_short_term - loudness.integrated).abs() < 0.1);
+        assert!(loudness.range < 0.1);
+        assert!((loudness.true_peak - -20.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn true_peak_between_samples() {
+        // 12 kHz at 45 degrees: every sample is 0.707 of the real peak
+        let mut meter = LoudnessMeter::new(2, 48_000);
+        meter.add_frames(&sine(12_000.0, 0.5, PI / 4.0, 1));
+        let loudness = meter.loudness();
+        dbg!(&loudness);
+        assert!((loudness.true_peak - 20.0 * 0.5_f64.log10()).abs() < 0.5);
+    }
+
+    #[test]
+    fn silence_and_range() {
+        let mut meter = LoudnessMeter::new(1, 48_000);
+        meter.add_frames(&vec![0.0; 48_000]);
+        let loudness = meter.loudness();
+        assert_eq!(loudness.integrated, f64::NEG_INFINITY);
+        assert_eq!(loudness.max_short_term, f64::NEG_INFINITY);
+        assert!(loudness.range.abs() < f64::EPSILON);
+
+        // 10 s at -20 dBFS then 10 s at -30 dBFS, mono
+        let mut meter = LoudnessMeter::new(1, 48_000);
+        let loud = sine(1_000.0, 0.1, 0.0, 10);
+        let quiet = sine(1_000.0, 0.031_622_776_601_683_79, 0.0, 10);
+        meter.add_frames(&loud.iter().step_by(2).copied().collect::<Vec<_>>());
+        meter.add_frames(&quiet.iter().step_by(2).copied().collect::<Vec<_>>());
+        let loudness = meter.loudness();
+        dbg!(&loudness);
+        assert!((loudness.range - 10.0).abs() < 0.2);
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn fill_bext() {
+        let loudness = Loudness {
+            integrated: -23.004,
+            range: 5.5,
+            true_peak: -1.0,
+            max_momentary: -18.25,
+            max_short_term: f64::NEG_INFINITY,
+        };
+        let bext = loudness.fill_bext(Bext::builder()).build().unwrap();
+        assert_eq!(bext.loudness_value, -2300);
+        assert_eq!(bext.loudness_range, 550);
+        assert_eq!(bext.max_true_peak_level, -100);
+        assert_eq!(bext.max_momentary_loudness, -1825);
+        assert_eq!(bext.max_short_term_loudness, i16::MIN);
+
+        let mut bext = Bext::builder().build().unwrap();
+        bext.version = 1;
+        loudness.apply_to(&mut bext).unwrap();
+        assert_eq!(bext.version, 2);
+        assert_eq!(bext.loudness_value, -2300);
+    }
+}
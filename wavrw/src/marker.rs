@@ -0,0 +1,533 @@
+//! Markers: cue points, their labels and sample loops as a single timeline.
+//!
+//! WAVE files spread marker information across several chunks. Positions
+//! are stored in `cue `, their text in `LIST-adtl` (`labl`, `note`, `ltxt`)
+//! and sampler loops in `smpl`. [`markers()`] joins them into [`Marker`]s.
+//!
+//! Annotations whose cue point is missing are kept as orphaned markers, see
+//! [`Marker::is_orphaned()`].
+//!
+//! [`CueEditor`] creates, moves and deletes cue points, keeping their
+//! annotations and `plst` segments in sync.
+
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use std::io::{BufRead, SeekFrom, Write};
+
+use binrw::io::{Read, Seek};
+
+use crate::chunk::adtl::{AdtlEnum, Labl, LablChunk, ListAdtl};
+use crate::chunk::cue::{Cue, CuePoint};
+use crate::chunk::data::Data;
+use crate::chunk::plst::Plst;
+use crate::chunk::smpl::Smpl;
+use crate::edit::{chunk_data, inject_chunks};
+use crate::{FourCC, KnownChunkID, SizedChunkEnum, WaveFile, WaveFileError};
+
+/// A position (or range) in the audio data with its associated text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Marker {
+    /// Cue point name, or loop identifier for `smpl` loops.
+    pub name: u32,
+
+    /// Position of the marker in samples, 0 for orphaned markers.
+    pub position: u32,
+
+    /// Length of the marked region in samples, if the marker is a region.
+    pub duration: Option<u32>,
+
+    /// Text from the `labl` chunk with the same name.
+    pub label: Option<String>,
+
+    /// Text from the `note` chunk with the same name.
+    pub note: Option<String>,
+
+    /// Text from the `ltxt` chunk with the same name.
+    pub text: Option<String>,
+
+    /// Chunk id of the chunk defining the position: `cue ` or `smpl`.
+    /// `adtl` for orphaned markers.
+    pub source: FourCC,
+}
+
+impl Marker {
+    fn new(name: u32, position: u32, source: FourCC) -> Self {
+        Marker {
+            name,
+            position,
+            duration: None,
+            label: None,
+            note: None,
+            text: None,
+            source,
+        }
+    }
+
+    /// True for `LIST-adtl` annotations without a cue point or `smpl` loop
+    /// of the same name. Their position is unknown.
+    ///
+    /// [`ResolvedWave::dangling_references()`](crate::ResolvedWave::dangling_references)
+    /// reports a warning for each of these annotations.
+    pub fn is_orphaned(&self) -> bool {
+        self.source == ListAdtl::LIST_TYPE
+    }
+}
+
+/// Collect all cue points and sample loops from `wave`, merged with their
+/// `LIST-adtl` annotations and sorted by position.
+///
+/// Annotations without a cue point or loop follow as orphaned markers,
+/// sorted by name.
+///
+/// Chunks with parsing errors are skipped. See
+/// [`ResolvedWave`](crate::ResolvedWave) for the underlying cue point and
+/// annotation lookups.
+///
+/// # Examples
+///
+/// ```
+/// # use std::fs::File;
+/// # use std::io::BufReader;
+/// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+/// let mut wave = wavrw::WaveFile::from_reader(file)?;
+///
+/// for marker in wavrw::markers(&mut wave) {
+///     println!("{} {:?}", marker.position, marker.label);
+/// }
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+pub fn markers<R>(wave: &mut WaveFile<R>) -> Vec<Marker>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    let resolved = wave.resolve();
+    let mut markers: Vec<Marker> = Vec::new();
+    for cue in resolved.cues() {
+        let mut marker = Marker::new(cue.point.name, cue.point.position, Cue::ID);
+        marker.duration = cue.ltxt.map(|l| l.sample_length).filter(|l| *l > 0);
+        markers.push(marker);
+    }
+    for sample_loop in resolved.sample_loops() {
+        let mut marker = Marker::new(sample_loop.identifier, sample_loop.start, Smpl::ID);
+        marker.duration = Some(sample_loop.sample_count());
+        markers.push(marker);
+    }
+    for name in resolved.orphans() {
+        let mut marker = Marker::new(name, 0, ListAdtl::LIST_TYPE);
+        marker.duration = resolved
+            .ltxt(name)
+            .map(|l| l.sample_length)
+            .filter(|l| *l > 0);
+        markers.push(marker);
+    }
+    for marker in &mut markers {
+        marker.label = resolved.label(marker.name).map(Into::into);
+        marker.note = resolved.note(marker.name).map(Into::into);
+        marker.text = resolved
+            .ltxt(marker.name)
+            .map(|l| l.text.clone())
+            .filter(|t| !t.is_empty());
+    }
+
+    markers.sort_by_key(|m| (m.is_orphaned(), m.position, m.name));
+    markers
+}
+
+/// Create, move and delete cue points, before writing them to a file.
+///
+/// Holds the `cue `, `LIST-adtl` and `plst` chunks of a file. After each
+/// change, cue points are sorted by position and named 1, 2, 3, etc. The
+/// `labl`, `note`, `ltxt` and `file` annotations and `plst` segments are
+/// renamed with their cue point. Annotations and segments without a cue
+/// point keep their order, with names after the last cue point.
+///
+/// # Examples
+///
+/// ```
+/// # use std::fs::File;
+/// # use std::io::{BufReader, Cursor};
+/// use wavrw::marker::CueEditor;
+///
+/// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+/// let mut wave = wavrw::WaveFile::from_reader(file)?;
+/// let mut editor = CueEditor::from_wave(&mut wave);
+///
+/// // between the cue points at 0 and 240
+/// let name = editor.insert(120, Some("Middle"))?;
+/// assert_eq!(name, 2);
+/// assert_eq!(editor.cue().points.len(), 4);
+///
+/// let mut output = Cursor::new(Vec::new());
+/// editor.apply(&mut wave, &mut output)?;
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueEditor {
+    cue: Cue,
+    adtl: Option<ListAdtl>,
+    plst: Option<Plst>,
+    /// The chunks came with a `cue ` chunk, which must be replaced even
+    /// when all points are removed.
+    has_cue: bool,
+}
+
+impl CueEditor {
+    /// Edit `cue` and the annotations and segments of its cue points. Names
+    /// are kept until the first change.
+    pub fn new(cue: Cue, adtl: Option<ListAdtl>, plst: Option<Plst>) -> Self {
+        CueEditor {
+            cue,
+            adtl,
+            plst,
+            has_cue: true,
+        }
+    }
+
+    /// Edit the first `cue `, `LIST-adtl` and `plst` chunks of `chunks`.
+    pub fn from_chunks(chunks: &[SizedChunkEnum]) -> Self {
+        let mut editor = CueEditor::default();
+        for chunk in chunks {
+            if let SizedChunkEnum::Cue(cue) = chunk {
+                if !editor.has_cue {
+                    editor.cue = cue.data.clone();
+                    editor.has_cue = true;
+                }
+            } else if let SizedChunkEnum::Adtl(adtl) = chunk {
+                editor.adtl.get_or_insert_with(|| adtl.data.clone());
+            } else if let SizedChunkEnum::Plst(plst) = chunk {
+                editor.plst.get_or_insert_with(|| plst.data.clone());
+            }
+        }
+        editor
+    }
+
+    /// Edit the cue points of `wave`, see [`CueEditor::from_chunks()`].
+    pub fn from_wave<R>(wave: &mut WaveFile<R>) -> Self
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        Self::from_chunks(wave.chunks())
+    }
+
+    /// The edited `cue ` chunk.
+    pub fn cue(&self) -> &Cue {
+        &self.cue
+    }
+
+    /// The edited `LIST-adtl` chunk, if there is one.
+    pub fn adtl(&self) -> Option<&ListAdtl> {
+        self.adtl.as_ref()
+    }
+
+    /// The edited `plst` chunk, if there is one.
+    pub fn plst(&self) -> Option<&Plst> {
+        self.plst.as_ref()
+    }
+
+    /// Add a cue point at sample `position` of the `data` chunk, with an
+    /// optional `labl` text. Returns the name of the new cue point, which
+    /// follows existing cue points at the same position.
+    pub fn insert(&mut self, position: u32, label: Option<&str>) -> Result<u32, WaveFileError> {
+        let Some(name) = self.unused_name() else {
+            return Err(WaveFileError::parse(
+                None,
+                "too many cue points".to_string(),
+            ));
+        };
+        self.cue.points.push(CuePoint {
+            name,
+            position,
+            chunk_id: Data::ID,
+            chunk_start: 0,
+            block_start: 0,
+            sample_offset: position,
+        });
+        let name = self.renumber()[&name];
+        if let Some(label) = label {
+            self.set_label(name, label)?;
+        }
+        Ok(name)
+    }
+
+    /// Move cue point `name` to sample `position`. Returns the new name of
+    /// the cue point.
+    pub fn move_to(&mut self, name: u32, position: u32) -> Result<u32, WaveFileError> {
+        let point = self.point_mut(name)?;
+        point.position = position;
+        if point.chunk_id == Data::ID {
+            point.sample_offset = position;
+        }
+        Ok(self.renumber()[&name])
+    }
+
+    /// Delete cue point `name` with its annotations and `plst` segments.
+    pub fn remove(&mut self, name: u32) -> Result<(), WaveFileError> {
+        self.point_mut(name)?;
+        self.cue.points.retain(|p| p.name != name);
+        if let Some(adtl) = &mut self.adtl {
+            adtl.chunks.retain(|c| annotation_name(c) != Some(name));
+        }
+        if let Some(plst) = &mut self.plst {
+            plst.segments.retain(|s| s.name != name);
+        }
+        self.renumber();
+        Ok(())
+    }
+
+    /// Set the `labl` text of cue point `name`, adding a `LIST-adtl` chunk
+    /// if needed. An empty `label` removes the `labl` chunk.
+    pub fn set_label(&mut self, name: u32, label: &str) -> Result<(), WaveFileError> {
+        self.point_mut(name)?;
+        let adtl = self.adtl.get_or_insert_with(|| ListAdtl {
+            list_type: ListAdtl::LIST_TYPE,
+            chunks: Vec::new(),
+        });
+        let index = adtl
+            .chunks
+            .iter()
+            .position(|c| matches!(c, AdtlEnum::Labl(l) if l.data.name == name));
+        if label.is_empty() {
+            if let Some(index) = index {
+                adtl.chunks.remove(index);
+            }
+            return Ok(());
+        }
+        let labl = AdtlEnum::Labl(LablChunk::new(Labl {
+            name,
+            text: label.to_string(),
+        })?);
+        match index {
+            Some(index) => adtl.chunks[index] = labl,
+            None => adtl.chunks.push(labl),
+        }
+        Ok(())
+    }
+
+    /// Write a copy of `wave` to `writer` with the edited chunks.
+    ///
+    /// The `cue ` chunk is written if `wave` had one or there are cue
+    /// points, `LIST-adtl` and `plst` if they exist.
+    pub fn apply<R, W>(&self, wave: &mut WaveFile<R>, writer: W) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        let mut chunks = Vec::new();
+        if self.has_cue || !self.cue.points.is_empty() {
+            chunks.push((Cue::ID, chunk_data(&self.cue)?));
+        }
+        if let Some(adtl) = &self.adtl {
+            chunks.push((ListAdtl::ID, chunk_data(adtl)?));
+        }
+        if let Some(plst) = &self.plst {
+            chunks.push((Plst::ID, chunk_data(plst)?));
+        }
+        wave.bytes.seek(SeekFrom::Start(wave.chunks_start - 12))?;
+        let chunks: Vec<(FourCC, &[u8])> =
+            chunks.iter().map(|(id, data)| (*id, &data[..])).collect();
+        inject_chunks(&mut wave.bytes, writer, &chunks)
+    }
+
+    fn point_mut(&mut self, name: u32) -> Result<&mut CuePoint, WaveFileError> {
+        self.cue
+            .points
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| WaveFileError::parse(None, format!("no cue point named {name}")))
+    }
+
+    /// A name not used by any cue point, annotation or segment.
+    fn unused_name(&self) -> Option<u32> {
+        self.names()
+            .max()
+            .map_or(Some(1), |name| name.checked_add(1))
+    }
+
+    /// Names of all cue points, then of annotations and segments.
+    fn names(&self) -> impl Iterator<Item = u32> + '_ {
+        let adtl = self.adtl.iter().flat_map(|a| &a.chunks);
+        let plst = self.plst.iter().flat_map(|p| &p.segments);
+        self.cue
+            .points
+            .iter()
+            .map(|p| p.name)
+            .chain(adtl.filter_map(annotation_name))
+            .chain(plst.map(|s| s.name))
+    }
+
+    /// Sort cue points by position and name them from 1, renaming their
+    /// annotations and segments. Returns the new name of each old name.
+    fn renumber(&mut self) -> BTreeMap<u32, u32> {
+        self.cue.points.sort_by_key(|p| p.position);
+        let mut renamed = BTreeMap::new();
+        let mut next = 1_u32;
+        for name in self.names().collect::<Vec<_>>() {
+            renamed.entry(name).or_insert_with(|| {
+                next = next.saturating_add(1);
+                next - 1
+            });
+        }
+        for point in &mut self.cue.points {
+            point.name = renamed[&point.name];
+        }
+        self.cue.cue_points = u32::try_from(self.cue.points.len()).unwrap_or(u32::MAX);
+        if let Some(adtl) = &mut self.adtl {
+            for chunk in &mut adtl.chunks {
+                match chunk {
+                    AdtlEnum::Labl(c) => c.data.name = renamed[&c.data.name],
+                    AdtlEnum::Note(c) => c.data.name = renamed[&c.data.name],
+                    AdtlEnum::Ltxt(c) => c.data.name = renamed[&c.data.name],
+                    AdtlEnum::File(c) => c.data.name = renamed[&c.data.name],
+                    AdtlEnum::Unknown { .. } => (),
+                }
+            }
+        }
+        if let Some(plst) = &mut self.plst {
+            for segment in &mut plst.segments {
+                segment.name = renamed[&segment.name];
+            }
+            plst.segment_count = u32::try_from(plst.segments.len()).unwrap_or(u32::MAX);
+        }
+        renamed
+    }
+}
+
+/// Cue point name of a `LIST-adtl` sub-chunk, `None` for unknown chunks.
+fn annotation_name(chunk: &AdtlEnum) -> Option<u32> {
+    match chunk {
+        AdtlEnum::Labl(c) => Some(c.data.name),
+        AdtlEnum::Note(c) => Some(c.data.name),
+        AdtlEnum::Ltxt(c) => Some(c.data.name),
+        AdtlEnum::File(c) => Some(c.data.name),
+        AdtlEnum::Unknown { .. } => None,
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::{BufReader, Cursor};
+
+    use super::*;
+
+    #[test]
+    fn markers_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let markers = markers(&mut wave);
+        dbg!(&markers);
+        assert_eq!(markers.len(), 3);
+        assert_eq!(markers[1].position, 240);
+        assert_eq!(markers[1].label, Some("Marker 01".to_string()));
+        assert_eq!(markers[1].source, FourCC(*b"cue "));
+
+        // calling again restarts iteration from the first chunk
+        assert_eq!(super::markers(&mut wave), markers);
+        assert!(!markers.iter().any(Marker::is_orphaned));
+    }
+
+    #[test]
+    fn orphaned_annotations() {
+        // cue point 1 at 16, labl "A" for 1, labl "B" for 2 and ltxt for 3
+        // (rgn, 8 samples, "C"), neither 2 nor 3 have a cue point
+        let data = "52494646 76000000 57415645 63756520 1C000000 01000000 01000000 10000000 \
+            64617461 00000000 00000000 10000000 64617461 00000000 4C495354 3E000000 \
+            6164746C 6C61626C 06000000 01000000 41006C61 626C0600 00000200 00004200 \
+            6C747874 16000000 03000000 08000000 72676E20 00000000 00000000 4300";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let markers = markers(&mut wave);
+        dbg!(&markers);
+        assert_eq!(markers.len(), 3);
+        assert_eq!(markers[0].label, Some("A".to_string()));
+        assert!(!markers[0].is_orphaned());
+        assert!(markers[1].is_orphaned());
+        assert_eq!(markers[1].label, Some("B".to_string()));
+        assert_eq!(markers[2].name, 3);
+        assert_eq!(markers[2].duration, Some(8));
+        assert_eq!(markers[2].text, Some("C".to_string()));
+        assert_eq!(markers[2].source, FourCC(*b"adtl"));
+        assert_eq!(wave.resolve().dangling_references().len(), 2);
+    }
+
+    #[test]
+    fn edit_cue_points() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let before = markers(&mut wave);
+        let mut editor = CueEditor::from_wave(&mut wave);
+        editor.plst = Some(Plst {
+            segment_count: 1,
+            segments: vec![crate::chunk::plst::PlstSegment {
+                name: before[1].name,
+                length: 10,
+                loops: 2,
+                label: None,
+            }],
+        });
+
+        // a new point renames the points after it
+        assert_eq!(editor.insert(100, Some("Mid")).unwrap(), 2);
+        let points = &editor.cue().points;
+        dbg!(points);
+        assert_eq!(editor.cue().cue_points, 4);
+        assert_eq!(
+            points.iter().map(|p| p.name).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        let labels = editor.adtl().unwrap().labels();
+        assert_eq!(labels[&2], "Mid");
+        assert_eq!(labels[&3], before[1].label.clone().unwrap());
+        assert_eq!(editor.plst().unwrap().segments[0].name, 3);
+
+        // moving past the last point
+        let last = editor.cue().points[3].position;
+        assert_eq!(editor.move_to(3, last + 1).unwrap(), 4);
+        assert_eq!(
+            editor.adtl().unwrap().labels()[&4],
+            before[1].label.clone().unwrap()
+        );
+        assert_eq!(editor.plst().unwrap().segments[0].name, 4);
+
+        editor.remove(4).unwrap();
+        assert_eq!(editor.cue().cue_points, 3);
+        assert_eq!(editor.plst().unwrap().segment_count, 0);
+        assert!(editor.remove(4).is_err());
+        assert!(editor.set_label(9, "nope").is_err());
+        editor.set_label(2, "").unwrap();
+
+        let mut output = Cursor::new(Vec::new());
+        editor.apply(&mut wave, &mut output).unwrap();
+        output.set_position(0);
+        let mut edited = WaveFile::from_reader(BufReader::new(output)).unwrap();
+        let after = markers(&mut edited);
+        dbg!(&after);
+        assert_eq!(after.len(), 3);
+        assert_eq!(after[1].position, 100);
+        assert_eq!(after[0].label, before[0].label);
+        assert_eq!(after[1].label, None);
+        assert_eq!(after[2].label, before[2].label);
+        assert!(edited.resolve().dangling_references().is_empty());
+    }
+
+    #[test]
+    fn edit_keeps_orphans() {
+        let adtl = ListAdtl {
+            list_type: ListAdtl::LIST_TYPE,
+            chunks: vec![AdtlEnum::Labl(
+                LablChunk::new(Labl {
+                    name: 1,
+                    text: "orphan".to_string(),
+                })
+                .unwrap(),
+            )],
+        };
+        let mut editor = CueEditor::new(Cue::default(), Some(adtl), None);
+        assert_eq!(editor.insert(5, None).unwrap(), 1);
+        assert_eq!(editor.adtl().unwrap().labels()[&2], "orphan");
+        assert_eq!(editor.insert(1, Some("first")).unwrap(), 1);
+        let labels = editor.adtl().unwrap().labels();
+        assert_eq!(labels[&1], "first");
+        assert_eq!(labels[&3], "orphan");
+    }
+}
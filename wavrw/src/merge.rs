@@ -0,0 +1,387 @@
+//! Merge mono WAVE files into one multichannel file.
+//!
+//! Recorders often write each track of a take to its own mono file, with
+//! the same iXML `FILE_SET` `FAMILY_UID`. [`merge()`] interleaves the audio
+//! of such files into one polyphonic file, the inverse of
+//! [`split()`](crate::split::split). Inputs with more than one channel are
+//! joined in the same way.
+//!
+//! All inputs must have the same sample rate, sample format and number of
+//! frames, and the same `FAMILY_UID` when they have one. The new file gets:
+//!
+//! - `fmt ` of the first input with the total channel count, block align
+//!   and byte rate. A `WAVE_FORMAT_EXTENSIBLE` channel mask is cleared, as
+//!   the speaker positions are unknown.
+//! - `data` with the channels of each input in turn.
+//! - `iXML` of the first input, with a `TRACK_LIST` of the tracks of all
+//!   inputs and a `FILE_SET` of one file. See
+//!   [`Ixml::with_merged_tracks()`][crate::chunk::ixml::Ixml::with_merged_tracks].
+//!
+//! `ds64`, `levl` and `MD5 ` describe the original audio and are left out.
+//! All other chunks, like `bext`, are copied from the first input. The file
+//! is written as `RIFF`, so must be smaller than 4 GiB.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! # use wavrw::testing::hex_to_cursor;
+//! // mono 16 bit PCM with 2 frames
+//! let mono = "52494646 28000000 57415645 \
+//!     666D7420 10000000 0100 0100 80BB0000 00770100 0200 1000 \
+//!     64617461 04000000 0100 0200";
+//! let mut inputs = vec![
+//!     wavrw::WaveFile::from_reader(hex_to_cursor(mono))?,
+//!     wavrw::WaveFile::from_reader(hex_to_cursor(mono))?,
+//! ];
+//! let mut output = Cursor::new(Vec::new());
+//! wavrw::merge::merge(&mut inputs, &mut output)?;
+//!
+//! output.set_position(0);
+//! let mut stereo = wavrw::WaveFile::from_reader(output)?;
+//! assert_eq!(stereo.metadata().channels(), Some(2));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use binrw::BinWrite;
+
+use crate::chunk::data::Data;
+use crate::chunk::ds64::Ds64;
+use crate::chunk::fmt::{FmtChunk, FmtEnum, Tag};
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::Ixml;
+use crate::chunk::levl::Levl;
+use crate::chunk::md5::Md5;
+#[cfg(feature = "ixml")]
+use crate::edit::write_chunk;
+use crate::edit::{copy_chunk_data, finish_riff};
+use crate::samples::SampleFormat;
+use crate::split::fmt_for_channels;
+use crate::{KnownChunkID, LazyChunk, WaveFile, WaveFileError};
+
+/// The audio and metadata of one input needed to merge it.
+struct Source {
+    fmt: FmtEnum,
+    format: SampleFormat,
+    channels: usize,
+    data: LazyChunk,
+    frames: u64,
+    #[cfg(feature = "ixml")]
+    ixml: Option<Ixml>,
+}
+
+impl Source {
+    fn new<R>(wave: &mut WaveFile<R>) -> Result<Self, WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let metadata = wave.metadata();
+        let fmt = metadata
+            .fmt()
+            .cloned()
+            .ok_or_else(|| WaveFileError::parse(None, "no fmt chunk"))?;
+        #[cfg(feature = "ixml")]
+        let ixml = metadata.ixml().cloned();
+        let format = SampleFormat::from_fmt(&fmt).ok_or_else(|| {
+            WaveFileError::parse(None, format!("can't merge {} samples", fmt.format_tag()))
+        })?;
+        let channels = usize::from(fmt.channels());
+        let data = wave
+            .scan_chunks()?
+            .into_iter()
+            .find(|c| c.id == Data::ID)
+            .ok_or_else(|| WaveFileError::parse(None, "no data chunk"))?;
+        let frames = data.large_size() / (format.bytes() * channels) as u64;
+        Ok(Source {
+            fmt,
+            format,
+            channels,
+            data,
+            frames,
+            #[cfg(feature = "ixml")]
+            ixml,
+        })
+    }
+}
+
+/// Write the channels of all `inputs`, in order, to one file. See the
+/// [module documentation](self).
+pub fn merge<R, W>(inputs: &mut [WaveFile<R>], mut writer: W) -> Result<(), WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+    W: Write + Seek,
+{
+    let mut sources = Vec::with_capacity(inputs.len());
+    for (index, wave) in inputs.iter_mut().enumerate() {
+        let source = Source::new(wave).map_err(|mut err| {
+            if let WaveFileError::Parse { message, .. } = &mut err {
+                *message = format!("file {}: {message}", index + 1);
+            }
+            err
+        })?;
+        sources.push(source);
+    }
+    let Some(first) = sources.first() else {
+        return Err(WaveFileError::parse(None, "no files to merge"));
+    };
+    for (index, source) in sources.iter().enumerate().skip(1) {
+        let mismatch = if source.fmt.samples_per_sec() != first.fmt.samples_per_sec() {
+            Some("sample rate")
+        } else if source.format != first.format {
+            Some("sample format")
+        } else if source.frames != first.frames {
+            Some("length")
+        } else {
+            None
+        };
+        if let Some(mismatch) = mismatch {
+            return Err(WaveFileError::parse(
+                None,
+                format!("file {}: {mismatch} differs from file 1", index + 1),
+            ));
+        }
+    }
+    #[cfg(feature = "ixml")]
+    {
+        let families: Vec<&str> = sources
+            .iter()
+            .filter_map(|s| s.ixml.as_ref()?.file_set.as_ref()?.family_uid.as_deref())
+            .collect();
+        if families.windows(2).any(|w| w[0] != w[1]) {
+            return Err(WaveFileError::parse(
+                None,
+                "files are from different iXML families (FAMILY_UID)",
+            ));
+        }
+    }
+    let channel_count: usize = sources.iter().map(|s| s.channels).sum();
+
+    let start = writer.stream_position()?;
+    writer.write_all(b"RIFF")?;
+    // placeholder, updated after all chunks are written
+    writer.write_all(&0_u32.to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+
+    for chunk in inputs[0].scan_chunks()? {
+        match chunk.id {
+            id if id == Ds64::ID || id == Levl::ID || id == Md5::ID => (),
+            id if id == FmtEnum::ID => {
+                let fmt = fmt_for_channels(&first.fmt, channel_count, first.format, |_| 0)?;
+                FmtChunk::new(fmt)?.write(&mut writer)?;
+            }
+            id if id == Data::ID => merge_data(inputs, &sources, &mut writer)?,
+            #[cfg(feature = "ixml")]
+            id if id == Ixml::ID && first.ixml.is_some() => {
+                let ixml = first.ixml.as_ref().expect("checked by match guard");
+                let files: Vec<_> = sources
+                    .iter()
+                    .map(|s| (s.ixml.as_ref(), s.channels))
+                    .collect();
+                let index = ixml
+                    .file_set
+                    .as_ref()
+                    .and_then(|f| f.file_set_index.as_deref())
+                    .unwrap_or("1");
+                let edited = ixml
+                    .with_merged_tracks(&files)
+                    .and_then(|ixml| ixml.with_file_set_index(index, 1));
+                // keep documents wavrw can't parse as they are
+                let bytes = edited.map_or_else(|_| ixml.raw_bytes.clone(), |e| e.raw_bytes);
+                let size = u32::try_from(bytes.len())
+                    .map_err(|_| WaveFileError::parse(None, "iXML too large"))?;
+                write_chunk(&mut writer, Ixml::ID, size, &bytes)?;
+            }
+            id => {
+                let bytes = &mut inputs[0].bytes;
+                bytes.seek(SeekFrom::Start(chunk.offset + 8))?;
+                writer.write_all(&id.0)?;
+                writer.write_all(&chunk.size.to_le_bytes())?;
+                copy_chunk_data(bytes, &mut writer, chunk.offset, id, chunk.size)?;
+            }
+        }
+    }
+
+    finish_riff(&mut writer, start)
+}
+
+/// Write a `data` chunk with the frames of all `inputs` interleaved.
+fn merge_data<R, W>(
+    inputs: &mut [WaveFile<R>],
+    sources: &[Source],
+    writer: &mut W,
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+    W: Write,
+{
+    let sample_len = sources[0].format.bytes();
+    let frames = sources[0].frames;
+    let frame_lens: Vec<usize> = sources.iter().map(|s| s.channels * sample_len).collect();
+    let size = u32::try_from(frames * frame_lens.iter().sum::<usize>() as u64)
+        .map_err(|_| WaveFileError::parse(None, "merged data too large for a RIFF file"))?;
+    writer.write_all(&Data::ID.0)?;
+    writer.write_all(&size.to_le_bytes())?;
+
+    let mut readers = Vec::with_capacity(inputs.len());
+    for (wave, source) in inputs.iter_mut().zip(sources) {
+        wave.bytes.seek(SeekFrom::Start(source.data.offset + 8))?;
+        readers.push(&mut wave.bytes);
+    }
+    let mut buffs = vec![Vec::new(); readers.len()];
+    let mut out = Vec::new();
+    let mut remaining = frames;
+    while remaining > 0 {
+        #[allow(clippy::cast_possible_truncation)] // at most 4096
+        let count = remaining.min(4096) as usize;
+        for ((reader, buff), frame_len) in readers.iter_mut().zip(&mut buffs).zip(&frame_lens) {
+            buff.resize(count * frame_len, 0);
+            reader.read_exact(buff)?;
+        }
+        out.clear();
+        for frame in 0..count {
+            for (buff, frame_len) in buffs.iter().zip(&frame_lens) {
+                out.extend_from_slice(&buff[frame * frame_len..(frame + 1) * frame_len]);
+            }
+        }
+        writer.write_all(&out)?;
+        remaining -= count as u64;
+    }
+    if size % 2 == 1 {
+        writer.write_all(&[0])?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Mono 16 bit PCM at `rate` with `samples`, and iXML if given.
+    fn mono(rate: u32, samples: &[u16], ixml: Option<&str>) -> Cursor<Vec<u8>> {
+        let mut body = b"WAVE".to_vec();
+        let mut chunk = |id: &[u8; 4], data: &[u8]| {
+            body.extend_from_slice(id);
+            body.extend_from_slice(&u32::try_from(data.len()).unwrap().to_le_bytes());
+            body.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                body.push(0);
+            }
+        };
+        let mut fmt = hex::decode("01000100").unwrap();
+        fmt.extend_from_slice(&rate.to_le_bytes());
+        fmt.extend_from_slice(&(rate * 2).to_le_bytes());
+        fmt.extend_from_slice(&hex::decode("02001000").unwrap());
+        chunk(b"fmt ", &fmt);
+        if let Some(ixml) = ixml {
+            chunk(b"iXML", ixml.as_bytes());
+        }
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        chunk(b"data", &data);
+        let mut riff = b"RIFF".to_vec();
+        riff.extend_from_slice(&u32::try_from(body.len()).unwrap().to_le_bytes());
+        riff.extend(body);
+        Cursor::new(riff)
+    }
+
+    #[cfg(feature = "ixml")]
+    fn ixml(family: &str, index: &str, channel: &str, name: &str) -> String {
+        format!(
+            "<BWFXML><FILE_SET><TOTAL_FILES>2</TOTAL_FILES><FAMILY_UID>{family}</FAMILY_UID>\
+            <FILE_SET_INDEX>{index}</FILE_SET_INDEX></FILE_SET>\
+            <TRACK_LIST><TRACK_COUNT>1</TRACK_COUNT><TRACK><CHANNEL_INDEX>{channel}</CHANNEL_INDEX>\
+            <INTERLEAVE_INDEX>1</INTERLEAVE_INDEX><NAME>{name}</NAME></TRACK></TRACK_LIST></BWFXML>"
+        )
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn merge_family() {
+        use crate::testing::hex_to_cursor;
+
+        let mut inputs = vec![
+            WaveFile::from_reader(mono(48000, &[1, 2, 3], Some(&ixml("F1", "A", "1", "Boom"))))
+                .unwrap(),
+            WaveFile::from_reader(mono(48000, &[4, 5, 6], Some(&ixml("F1", "B", "3", "Lav"))))
+                .unwrap(),
+            WaveFile::from_reader(mono(48000, &[7, 8, 9], None)).unwrap(),
+        ];
+        let mut output = Cursor::new(Vec::new());
+        merge(&mut inputs, &mut output).unwrap();
+
+        output.set_position(0);
+        let mut poly = WaveFile::from_reader(output).unwrap();
+        let metadata = poly.metadata();
+        dbg!(&metadata);
+        let fmt = metadata.fmt().unwrap();
+        assert_eq!(fmt.channels(), 3);
+        assert_eq!(fmt.block_align(), 6);
+        assert_eq!(fmt.avg_bytes_per_sec(), 288_000);
+        let ixml = metadata.ixml().unwrap();
+        let file_set = ixml.file_set.clone().unwrap();
+        assert_eq!(file_set.total_files.as_deref(), Some("1"));
+        assert_eq!(file_set.file_set_index.as_deref(), Some("A"));
+        let track_list = ixml.track_list.clone().unwrap();
+        assert_eq!(track_list.track_count.as_deref(), Some("3"));
+        let tracks: Vec<_> = track_list
+            .tracks
+            .iter()
+            .map(|t| {
+                (
+                    t.channel_index.as_deref(),
+                    t.interleave_index.as_deref(),
+                    t.name.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tracks,
+            [
+                (Some("1"), Some("1"), Some("Boom")),
+                (Some("3"), Some("2"), Some("Lav")),
+                (Some("3"), Some("3"), None),
+            ]
+        );
+
+        let bytes = poly.bytes.into_inner();
+        let data = hex_to_cursor("64617461 12000000 0100 0400 0700 0200 0500 0800 0300 0600 0900")
+            .into_inner();
+        assert!(bytes.ends_with(&data));
+    }
+
+    #[test]
+    fn merge_mismatch() {
+        let mut inputs = vec![
+            WaveFile::from_reader(mono(48000, &[1, 2], None)).unwrap(),
+            WaveFile::from_reader(mono(44100, &[1, 2], None)).unwrap(),
+        ];
+        let err = merge(&mut inputs, Cursor::new(Vec::new())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse: file 2: sample rate differs from file 1"
+        );
+
+        let mut inputs = vec![
+            WaveFile::from_reader(mono(48000, &[1, 2], None)).unwrap(),
+            WaveFile::from_reader(mono(48000, &[1, 2, 3], None)).unwrap(),
+        ];
+        let err = merge(&mut inputs, Cursor::new(Vec::new())).unwrap_err();
+        assert_eq!(err.to_string(), "Parse: file 2: length differs from file 1");
+
+        let mut inputs: Vec<WaveFile<Cursor<Vec<u8>>>> = vec![];
+        assert!(merge(&mut inputs, Cursor::new(Vec::new())).is_err());
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn merge_different_families() {
+        let mut inputs = vec![
+            WaveFile::from_reader(mono(48000, &[1], Some(&ixml("F1", "A", "1", "Boom")))).unwrap(),
+            WaveFile::from_reader(mono(48000, &[2], Some(&ixml("F2", "B", "2", "Lav")))).unwrap(),
+        ];
+        assert!(merge(&mut inputs, Cursor::new(Vec::new())).is_err());
+    }
+}
@@ -0,0 +1,493 @@
+//! Typed access to common metadata without matching on chunks.
+//!
+//! [`WaveFile::iter_chunks()`](crate::WaveFile::iter_chunks) exposes every
+//! chunk, which is flexible but verbose when only a few values are needed.
+//! [`WaveMetadata`] answers the common questions directly. When a file has
+//! duplicate chunks, the first one is used.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let metadata = wave.metadata();
+//!
+//! assert_eq!(metadata.sample_rate(), Some(48000));
+//! assert_eq!(metadata.channels(), Some(1));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::Bext;
+use crate::chunk::data::DataChunk;
+use crate::chunk::fact::FactChunk;
+use crate::chunk::fmt::{FmtChunk, FmtEnum, FormatTag, Tag};
+#[cfg(feature = "info")]
+use crate::chunk::info::ListInfo;
+use crate::chunk::inst::Inst;
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::Ixml;
+use crate::chunk::smpl::Smpl;
+use crate::warning::Code;
+#[cfg(feature = "info")]
+use crate::{ChunkID, FourCC};
+use crate::{KnownChunkID, SizedChunk, SizedChunkEnum, Warning};
+
+/// Typed getters for common metadata, see [`WaveFile::metadata()`](crate::WaveFile::metadata).
+#[derive(Debug, Clone, Copy)]
+pub struct WaveMetadata<'a> {
+    chunks: &'a [SizedChunkEnum],
+}
+
+impl<'a> WaveMetadata<'a> {
+    /// Create from a list of parsed chunks.
+    pub fn new(chunks: &'a [SizedChunkEnum]) -> Self {
+        WaveMetadata { chunks }
+    }
+
+    /// The first `fmt ` chunk.
+    pub fn fmt(&self) -> Option<&'a FmtEnum> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Fmt(fmt) = c {
+                Some(&fmt.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Sample rate in samples per second, from `fmt `.
+    pub fn sample_rate(&self) -> Option<u32> {
+        self.fmt().map(FmtEnum::samples_per_sec)
+    }
+
+    /// Number of channels, from `fmt `.
+    pub fn channels(&self) -> Option<u16> {
+        self.fmt().map(FmtEnum::channels)
+    }
+
+    /// Bits per sample, from `fmt `.
+    pub fn bit_depth(&self) -> Option<u16> {
+        self.fmt().map(FmtEnum::bits_per_sample)
+    }
+
+    /// Size of the first `data` chunk in bytes, from `ds64` in RF64 files.
+    pub fn data_size(&self) -> Option<u64> {
+        self.chunks
+            .iter()
+            .find(|c| matches!(c, SizedChunkEnum::Data(_)))
+            .map(SizedChunk::large_size)
+    }
+
+    /// Sample count from the first `fact` chunk.
+    pub fn fact_samples(&self) -> Option<u32> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Fact(fact) = c {
+                Some(fact.data.samples)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Length of the audio in samples (per channel).
+    ///
+    /// For uncompressed formats this is calculated from the `data` size,
+    /// otherwise the `fact` chunk is used.
+    pub fn duration_samples(&self) -> Option<u64> {
+        duration_samples(self.fmt()?, self.data_size(), self.fact_samples())
+    }
+
+    /// Number of blocks (`block_align` bytes each) in the `data` chunk.
+    ///
+    /// For uncompressed formats, a block is one sample for every channel, so
+    /// this equals [`duration_samples()`](WaveMetadata::duration_samples). For
+    /// compressed formats a block holds many samples.
+    pub fn frame_count(&self) -> Option<u64> {
+        let block_align = self.fmt()?.block_align();
+        if block_align == 0 {
+            return None;
+        }
+        Some(self.data_size()? / u64::from(block_align))
+    }
+
+    /// Length of the audio in seconds.
+    pub fn duration_seconds(&self) -> Option<f64> {
+        let sample_rate = self.sample_rate().filter(|r| *r > 0)?;
+        // precision loss only matters for files longer than 2^52 samples
+        #[allow(clippy::cast_precision_loss)]
+        Some(self.duration_samples()? as f64 / f64::from(sample_rate))
+    }
+
+    /// Average bytes per second, from `fmt `.
+    pub fn byte_rate(&self) -> Option<u32> {
+        self.fmt().map(FmtEnum::avg_bytes_per_sec)
+    }
+
+    /// Check the `fmt `, `fact` and `data` chunks agree with each other.
+    ///
+    /// For uncompressed formats, checks the `data` size is a multiple of
+    /// `block_align`, `fact` samples (if present) match the `data` length
+    /// and `avg_bytes_per_sec` matches `samples_per_sec` x `block_align`.
+    ///
+    /// For compressed formats, checks a `fact` chunk is present and its
+    /// duration (samples / `samples_per_sec`) matches the `data` duration
+    /// (size / `avg_bytes_per_sec`), within 5% or one block.
+    ///
+    /// Also checks `inst` values, see [`Inst::validate()`] and
+    /// [`Inst::check_smpl()`].
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        if let Some(inst) = self.inst() {
+            warnings.extend(inst.validate());
+            if let Some(smpl) = self.smpl() {
+                warnings.extend(inst.check_smpl(smpl));
+            }
+        }
+        let Some(fmt) = self.fmt() else {
+            return warnings;
+        };
+        if !is_uncompressed(fmt.format_tag()) {
+            warnings.extend(self.validate_compressed(fmt));
+            return warnings;
+        }
+        let block_align = u64::from(fmt.block_align());
+        let expected_rate = u64::from(fmt.samples_per_sec()) * block_align;
+        if expected_rate != u64::from(fmt.avg_bytes_per_sec()) {
+            warnings.push(Warning::new(
+                FmtChunk::ID,
+                None,
+                format!(
+                    "avg_bytes_per_sec ({}) does not match samples_per_sec x block_align ({expected_rate})",
+                    fmt.avg_bytes_per_sec()
+                ),
+            ).with_code(Code::FormatMismatch));
+        }
+        let Some(data_size) = self.data_size() else {
+            return warnings;
+        };
+        if block_align > 0 && data_size % block_align != 0 {
+            warnings.push(
+                Warning::new(
+                    DataChunk::ID,
+                    None,
+                    format!("size ({data_size}) is not a multiple of block_align ({block_align})"),
+                )
+                .with_code(Code::DataNotBlockAligned),
+            );
+        }
+        if let (Some(fact_samples), Some(frames)) = (self.fact_samples(), self.frame_count()) {
+            if u64::from(fact_samples) != frames {
+                warnings.push(
+                    Warning::new(
+                        FactChunk::ID,
+                        None,
+                        format!(
+                        "samples ({fact_samples}) does not match data length ({frames} samples)"
+                    ),
+                    )
+                    .with_code(Code::FactMismatch),
+                );
+            }
+        }
+        warnings
+    }
+
+    /// `fact` checks for compressed formats, see [`WaveMetadata::validate()`].
+    // precision loss only matters for data larger than 2^52 bytes
+    #[allow(clippy::cast_precision_loss)]
+    fn validate_compressed(&self, fmt: &FmtEnum) -> Option<Warning> {
+        let Some(fact_samples) = self.fact_samples() else {
+            return Some(
+                Warning::new(
+                    FactChunk::ID,
+                    None,
+                    format!(
+                        "missing, required for compressed format {}",
+                        fmt.format_tag()
+                    ),
+                )
+                .with_code(Code::MissingFact),
+            );
+        };
+        let data_size = self.data_size()?;
+        let sample_rate = f64::from(fmt.samples_per_sec());
+        let byte_rate = f64::from(fmt.avg_bytes_per_sec());
+        if sample_rate == 0.0 || byte_rate == 0.0 {
+            return None;
+        }
+        let fact_seconds = f64::from(fact_samples) / sample_rate;
+        let data_seconds = data_size as f64 / byte_rate;
+        let block_seconds = f64::from(fmt.block_align()) / byte_rate;
+        let tolerance = (data_seconds * COMPRESSED_DURATION_TOLERANCE).max(block_seconds);
+        if (fact_seconds - data_seconds).abs() <= tolerance {
+            return None;
+        }
+        Some(Warning::new(
+            FactChunk::ID,
+            None,
+            format!(
+                "samples ({fact_samples}) last {fact_seconds:.3} s, data ({data_size} bytes) lasts {data_seconds:.3} s at avg_bytes_per_sec"
+            ),
+        ).with_code(Code::FactMismatch))
+    }
+
+    /// The first `inst` chunk.
+    pub fn inst(&self) -> Option<&'a Inst> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Inst(inst) = c {
+                Some(&inst.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The first `smpl` chunk.
+    pub fn smpl(&self) -> Option<&'a Smpl> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Smpl(smpl) = c {
+                Some(&smpl.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The first `bext` chunk.
+    #[cfg(feature = "broadcast")]
+    pub fn bext(&self) -> Option<&'a Bext> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Bext(bext) = c {
+                Some(&bext.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// `labl` text from all `LIST-adtl` chunks, keyed by cue point name.
+    ///
+    /// If a name is labeled more than once, the last label wins.
+    pub fn cue_labels(&self) -> BTreeMap<u32, String> {
+        self.chunks
+            .iter()
+            .filter_map(|c| {
+                if let SizedChunkEnum::Adtl(adtl) = c {
+                    Some(adtl.data.labels())
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The first `LIST-INFO` chunk.
+    #[cfg(feature = "info")]
+    pub fn list_info(&self) -> Option<&'a ListInfo> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Info(info) = c {
+                Some(&info.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Text of the first `LIST-INFO` subchunk with `id`. Ex: `INAM`
+    #[cfg(feature = "info")]
+    pub fn info(&self, id: FourCC) -> Option<String> {
+        self.chunks
+            .iter()
+            .filter_map(|c| {
+                if let SizedChunkEnum::Info(info) = c {
+                    Some(&info.data.chunks)
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .find(|c| c.id() == id)
+            .map(|c| c.text())
+    }
+
+    /// The first `iXML` chunk.
+    #[cfg(feature = "ixml")]
+    pub fn ixml(&self) -> Option<&'a Ixml> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Ixml(ixml) = c {
+                Some(&ixml.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Sample rate the audio was recorded at, from the iXML `SPEED`
+    /// `DIGITIZER_SAMPLE_RATE`, when it differs from the `fmt ` sample rate.
+    ///
+    /// Overcranked or pulled down recordings are stored with the sample rate
+    /// they should play at. Ex: recorded at 48048 Hz and stored as 48000 Hz.
+    /// Sample positions divided by this rate give real time, see
+    /// [`SampleTime::at_sample_rate()`](crate::time::SampleTime::at_sample_rate).
+    /// `None` if the rates match or either one is missing.
+    #[cfg(feature = "ixml")]
+    pub fn real_time_sample_rate(&self) -> Option<u32> {
+        let sample_rate = self.sample_rate()?;
+        let speed = self.ixml()?.speed.as_ref()?;
+        let digitizer = speed.parsed_digitizer_sample_rate().ok()?;
+        (digitizer != 0 && digitizer != sample_rate).then_some(digitizer)
+    }
+}
+
+/// Allowed difference between `fact` and `data` durations of compressed
+/// formats, as a fraction of the `data` duration.
+const COMPRESSED_DURATION_TOLERANCE: f64 = 0.05;
+
+/// Formats where each block holds exactly one sample per channel.
+/// Length of the audio in samples (per channel), from the `data` size for
+/// uncompressed formats and `fact` otherwise.
+pub(crate) fn duration_samples(
+    fmt: &FmtEnum,
+    data_size: Option<u64>,
+    fact_samples: Option<u32>,
+) -> Option<u64> {
+    if is_uncompressed(fmt.format_tag()) && fmt.block_align() > 0 {
+        if let Some(size) = data_size {
+            return Some(size / u64::from(fmt.block_align()));
+        }
+    }
+    fact_samples.map(u64::from)
+}
+
+pub(crate) fn is_uncompressed(tag: FormatTag) -> bool {
+    matches!(
+        tag,
+        FormatTag::Pcm
+            | FormatTag::IeeeFloat
+            | FormatTag::Alaw
+            | FormatTag::Mulaw
+            | FormatTag::Extensible
+    )
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::{FourCC, WaveFile};
+
+    #[test]
+    fn metadata_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let metadata = wave.metadata();
+        dbg!(&metadata);
+        assert_eq!(metadata.sample_rate(), Some(48000));
+        assert_eq!(metadata.channels(), Some(1));
+        assert_eq!(metadata.bit_depth(), Some(24));
+        assert_eq!(metadata.duration_samples(), Some(480));
+        assert_eq!(metadata.frame_count(), Some(480));
+        assert_eq!(metadata.duration_seconds(), Some(0.01));
+        assert_eq!(metadata.byte_rate(), Some(144_000));
+        assert!(metadata.validate().is_empty());
+        #[cfg(feature = "broadcast")]
+        assert!(metadata.bext().is_some());
+        #[cfg(feature = "info")]
+        {
+            assert_eq!(
+                metadata.info(FourCC(*b"ISFT")),
+                Some("Soundminer".to_string())
+            );
+            assert_eq!(metadata.info(FourCC(*b"IENG")), None);
+        }
+        #[cfg(feature = "ixml")]
+        assert_eq!(metadata.real_time_sample_rate(), None);
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn real_time_sample_rate() {
+        // PCM mono 16 bit 48000 Hz, recorded at `digitizer` Hz
+        let wave = |digitizer: &str| {
+            let xml = format!(
+                "<BWFXML><SPEED><DIGITIZER_SAMPLE_RATE>{digitizer}</DIGITIZER_SAMPLE_RATE>\
+                </SPEED></BWFXML>"
+            );
+            let ixml = format!(
+                "69584D4C {:08X} {}",
+                (xml.len() as u32).swap_bytes(),
+                hex::encode(&xml)
+            );
+            let chunks = format!(
+                "57415645 666D7420 10000000 01000100 80BB0000 00770100 02001000 \
+                {ixml} 64617461 00000000"
+            );
+            let size = chunks.replace(' ', "").len() / 2;
+            let hex = format!("52494646 {:08X} {chunks}", (size as u32).swap_bytes());
+            WaveFile::from_reader(crate::testing::hex_to_cursor(&hex)).unwrap()
+        };
+        assert_eq!(
+            wave("48048").metadata().real_time_sample_rate(),
+            Some(48048)
+        );
+        assert_eq!(wave("48000").metadata().real_time_sample_rate(), None);
+        assert_eq!(wave("").metadata().real_time_sample_rate(), None);
+    }
+
+    #[test]
+    fn validate_fact_mismatch() {
+        // PCM mono 16 bit 8000 Hz, fact says 3 samples, data holds 2
+        let data = "52494646 34000000 57415645 666D7420 10000000 01000100 401F0000 803E0000 02001000 66616374 04000000 03000000 64617461 04000000 00000000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let warnings = wave.metadata().validate();
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, FourCC(*b"fact"));
+    }
+
+    #[test]
+    fn validate_fact_compressed() {
+        // MP3 stereo 44100 Hz 128 kbps, 16000 bytes of data is 1 second
+        let fmt =
+            "666D7420 1E000000 55000200 44AC0000 803E0000 01000000 0C000100 02000000 A1010100 7105";
+        let data = "64617461 803E0000";
+        let wave = |fact: &str| {
+            let chunks = format!("57415645 {fmt} {fact} {data}");
+            let size = chunks.replace(' ', "").len() / 2 + 16000;
+            let mut hex = format!("52494646 {:08X} {chunks}", (size as u32).swap_bytes());
+            hex.push_str(&"00".repeat(16000));
+            let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(&hex)).unwrap();
+            wave.metadata().validate()
+        };
+
+        // fact missing
+        let warnings = wave("");
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, FourCC(*b"fact"));
+        assert!(warnings[0].message.starts_with("missing"));
+
+        // 44100 samples, 1 second
+        assert!(wave("66616374 04000000 44AC0000").is_empty());
+        // 2% short is within tolerance
+        assert!(wave("66616374 04000000 E4A80000").is_empty());
+        // 22050 samples, half a second
+        let warnings = wave("66616374 04000000 22560000");
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "samples (22050) last 0.500 s, data (16000 bytes) lasts 1.000 s at avg_bytes_per_sec"
+        );
+    }
+}
@@ -0,0 +1,558 @@
+//! BWF MetaEdit "Core" CSV documents.
+//!
+//! [BWF MetaEdit](https://mediaarea.net/BWFMetaEdit) exchanges `bext` and
+//! `LIST-INFO` metadata as CSV with one row per file, the "Core" document.
+//! Many archival pipelines use it to review and correct metadata in a
+//! spreadsheet. [`write_core_csv()`] writes rows read with
+//! [`CoreRow::from_wave()`] and [`read_core_csv()`] reads them back, using
+//! the same column names ([`CORE_COLUMNS`]) and RFC 4180 quoting: fields
+//! containing commas, quotes or line breaks are quoted, quotes are doubled.
+//!
+//! Values are written the way BWF MetaEdit shows them: `UMID` as hex,
+//! loudness in LUFS, LU or dBTP with two decimals, and
+//! `TimeReference (translated)` as hh:mm:ss.mmm. The translated time is
+//! ignored when applying a row, `TimeReference` is used instead.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::{BufReader, Cursor};
+//! use wavrw::metaedit::{read_core_csv, write_core_csv, CoreRow};
+//!
+//! let path = "../test_wavs/example_a.wav";
+//! let mut wave = wavrw::WaveFile::from_reader(BufReader::new(File::open(path)?))?;
+//! let mut row = CoreRow::from_wave(path, &mut wave)?;
+//! assert_eq!(row.get("Description"), Some("BWDescription"));
+//!
+//! row.set("Description", "Dawn chorus, take 2");
+//! let csv = write_core_csv(&[row]);
+//!
+//! let rows = read_core_csv(&csv)?;
+//! let mut output = Cursor::new(Vec::new());
+//! rows[0].apply(&mut wave, &mut output)?;
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use crate::chunk::bext::Bext;
+use crate::chunk::info::ListInfo;
+use crate::edit::{chunk_data, inject_chunks};
+use crate::fixedstring::FixedString;
+use crate::sanitize::Sanitizer;
+use crate::{ChunkID, FourCC, KnownChunkID, WaveFile, WaveFileError};
+
+/// Columns of a Core document, in BWF MetaEdit order.
+pub const CORE_COLUMNS: [&str; 33] = [
+    "FileName",
+    "Description",
+    "Originator",
+    "OriginatorReference",
+    "OriginationDate",
+    "OriginationTime",
+    "TimeReference (translated)",
+    "TimeReference",
+    "BextVersion",
+    "UMID",
+    "LoudnessValue",
+    "LoudnessRange",
+    "MaxTruePeakLevel",
+    "MaxMomentaryLoudness",
+    "MaxShortTermLoudness",
+    "CodingHistory",
+    "IARL",
+    "IART",
+    "ICMS",
+    "ICMT",
+    "ICOP",
+    "ICRD",
+    "IENG",
+    "IGNR",
+    "IKEY",
+    "IMED",
+    "INAM",
+    "IPRD",
+    "ISBJ",
+    "ISFT",
+    "ISRC",
+    "ISRF",
+    "ITCH",
+];
+
+/// Index of the first `LIST-INFO` column in [`CORE_COLUMNS`].
+const FIRST_INFO_COLUMN: usize = 16;
+
+/// One row of a Core document: the metadata of one file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CoreRow {
+    /// Path of the file, as given. Ex: `archive/dawn_01.wav`
+    pub file_name: String,
+
+    /// (column, value) pairs, except `FileName`. Columns not listed are
+    /// left unchanged by [`CoreRow::apply()`], empty values clear the field.
+    pub fields: Vec<(String, String)>,
+}
+
+impl CoreRow {
+    /// Read the `bext` and `LIST-INFO` values of `wave`, with all columns.
+    pub fn from_wave<R>(file_name: &str, wave: &mut WaveFile<R>) -> Result<CoreRow, WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let metadata = wave.metadata();
+        let samples_per_sec = metadata.sample_rate();
+        let bext = metadata.bext();
+        let info = metadata.list_info();
+        let mut row = CoreRow {
+            file_name: file_name.to_string(),
+            fields: Vec::with_capacity(CORE_COLUMNS.len() - 1),
+        };
+        for column in &CORE_COLUMNS[1..FIRST_INFO_COLUMN] {
+            let value = bext.map_or_else(String::new, |bext| {
+                bext_value(bext, column, samples_per_sec)
+            });
+            row.set(column, &value);
+        }
+        for column in &CORE_COLUMNS[FIRST_INFO_COLUMN..] {
+            let id = FourCC(column.as_bytes().try_into().unwrap_or(*b"    "));
+            let value = info
+                .and_then(|info| info.chunks.iter().find(|c| c.id() == id))
+                .map(|c| c.text())
+                .unwrap_or_default();
+            row.set(column, &value);
+        }
+        Ok(row)
+    }
+
+    /// Value of `column`, `None` if the row doesn't have it. Ex: `INAM`
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(c, _)| c == column)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set the value of `column`, adding it if missing.
+    pub fn set(&mut self, column: &str, value: &str) {
+        if let Some(field) = self.fields.iter_mut().find(|(c, _)| c == column) {
+            field.1 = value.to_string();
+        } else {
+            self.fields.push((column.to_string(), value.to_string()));
+        }
+    }
+
+    /// Write a copy of `wave` to `writer` with the values of this row.
+    ///
+    /// Starts from the existing `bext` and `LIST-INFO` chunks. A missing
+    /// chunk is only added if one of its columns has a value. Returns an
+    /// error before anything is written if a value is invalid or doesn't
+    /// fit its field.
+    ///
+    /// Non-ASCII text is written unchanged, see [`CoreRow::apply_sanitized()`]
+    /// to choose a [`TextPolicy`](crate::sanitize::TextPolicy).
+    pub fn apply<R, W>(&self, wave: &mut WaveFile<R>, writer: W) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        self.apply_sanitized(wave, writer, &mut Sanitizer::default())
+    }
+
+    /// Same as [`CoreRow::apply()`], applying the policy of `sanitizer` to
+    /// the text of the written `bext` and `LIST-INFO` chunks.
+    ///
+    /// Fields with non-ASCII text are recorded in
+    /// [`Sanitizer::warnings()`]. With
+    /// [`TextPolicy::Strict`](crate::sanitize::TextPolicy::Strict), returns an
+    /// error for non-ASCII text before anything is written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::{BufReader, Cursor};
+    /// use wavrw::metaedit::CoreRow;
+    /// use wavrw::sanitize::{Sanitizer, TextPolicy};
+    ///
+    /// let path = "../test_wavs/example_a.wav";
+    /// let mut wave = wavrw::WaveFile::from_reader(BufReader::new(File::open(path)?))?;
+    /// let mut row = CoreRow::from_wave(path, &mut wave)?;
+    /// row.set("INAM", "Chœur à l’aube");
+    ///
+    /// let mut sanitizer = Sanitizer::new(TextPolicy::Replace);
+    /// row.apply_sanitized(&mut wave, Cursor::new(Vec::new()), &mut sanitizer)?;
+    /// assert_eq!(sanitizer.warnings().len(), 1);
+    ///
+    /// let mut sanitizer = Sanitizer::new(TextPolicy::Strict);
+    /// assert!(row.apply_sanitized(&mut wave, Cursor::new(Vec::new()), &mut sanitizer).is_err());
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn apply_sanitized<R, W>(
+        &self,
+        wave: &mut WaveFile<R>,
+        writer: W,
+        sanitizer: &mut Sanitizer,
+    ) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        let metadata = wave.metadata();
+        let is_set = |columns: &[&str]| {
+            self.fields
+                .iter()
+                .any(|(c, v)| columns.contains(&c.as_str()) && !v.is_empty())
+        };
+        let mut chunks = Vec::new();
+
+        let bext = match metadata.bext() {
+            Some(bext) => Some(bext.clone()),
+            None if is_set(&CORE_COLUMNS[1..FIRST_INFO_COLUMN]) => Some(Bext::builder().build()?),
+            None => None,
+        };
+        if let Some(mut bext) = bext {
+            for (column, value) in &self.fields {
+                if CORE_COLUMNS[1..FIRST_INFO_COLUMN].contains(&column.as_str()) {
+                    set_bext_value(&mut bext, column, value)?;
+                }
+            }
+            sanitizer.bext(&mut bext)?;
+            chunks.push((Bext::ID, chunk_data(&bext)?));
+        }
+
+        let info = match metadata.list_info() {
+            Some(info) => Some(info.clone()),
+            None if is_set(&CORE_COLUMNS[FIRST_INFO_COLUMN..]) => {
+                Some(ListInfo::builder().build()?)
+            }
+            None => None,
+        };
+        if let Some(mut info) = info {
+            for (column, value) in &self.fields {
+                if !CORE_COLUMNS[FIRST_INFO_COLUMN..].contains(&column.as_str()) {
+                    continue;
+                }
+                let id = FourCC(column.as_bytes().try_into().unwrap_or(*b"    "));
+                if value.is_empty() {
+                    info.remove(id);
+                } else {
+                    info.set(id, value)?;
+                }
+            }
+            sanitizer.list_info(&mut info)?;
+            chunks.push((ListInfo::ID, chunk_data(&info)?));
+        }
+
+        wave.bytes.seek(SeekFrom::Start(wave.chunks_start - 12))?;
+        let chunks: Vec<(FourCC, &[u8])> =
+            chunks.iter().map(|(id, data)| (*id, &data[..])).collect();
+        inject_chunks(&mut wave.bytes, writer, &chunks)
+    }
+}
+
+/// Text of a `bext` column as BWF MetaEdit shows it.
+fn bext_value(bext: &Bext, column: &str, samples_per_sec: Option<u32>) -> String {
+    let loudness = |value: i16| {
+        if bext.has_loudness() {
+            let sign = if value < 0 { "-" } else { "" };
+            let hundredths = value.unsigned_abs();
+            format!("{sign}{}.{:02}", hundredths / 100, hundredths % 100)
+        } else {
+            String::new()
+        }
+    };
+    match column {
+        "Description" => bext.description.to_string(),
+        "Originator" => bext.originator.to_string(),
+        "OriginatorReference" => bext.originator_reference.to_string(),
+        "OriginationDate" => bext.origination_date.to_string(),
+        "OriginationTime" => bext.origination_time.to_string(),
+        "TimeReference (translated)" => samples_per_sec
+            .filter(|rate| *rate > 0)
+            .map(|rate| bext.time_reference_time(rate).to_string())
+            .unwrap_or_default(),
+        "TimeReference" => bext.time_reference.to_string(),
+        "BextVersion" => bext.version.to_string(),
+        "UMID" => bext
+            .parsed_umid()
+            .filter(|umid| umid.iter().any(|b| *b != 0))
+            .map(hex::encode_upper)
+            .unwrap_or_default(),
+        "LoudnessValue" => loudness(bext.loudness_value),
+        "LoudnessRange" => loudness(bext.loudness_range),
+        "MaxTruePeakLevel" => loudness(bext.max_true_peak_level),
+        "MaxMomentaryLoudness" => loudness(bext.max_momentary_loudness),
+        "MaxShortTermLoudness" => loudness(bext.max_short_term_loudness),
+        "CodingHistory" => bext.coding_history.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Set a `bext` field from the text of its column.
+fn set_bext_value(bext: &mut Bext, column: &str, value: &str) -> Result<(), WaveFileError> {
+    fn fixed<const N: usize>(column: &str, value: &str) -> Result<FixedString<N>, WaveFileError> {
+        FixedString::try_from_str(value)
+            .map_err(|err| WaveFileError::parse(None, format!("{column}: {err}")))
+    }
+    fn invalid(column: &str, value: &str) -> WaveFileError {
+        WaveFileError::parse(None, format!("{column}: invalid value {value:?}"))
+    }
+    let loudness = |bext: &mut Bext| -> Result<i16, WaveFileError> {
+        if value.is_empty() {
+            return Ok(0);
+        }
+        let number: f64 = value.trim().parse().map_err(|_| invalid(column, value))?;
+        let scaled = (number * 100.0).round();
+        if !(f64::from(i16::MIN)..=f64::from(i16::MAX)).contains(&scaled) {
+            return Err(invalid(column, value));
+        }
+        bext.version = bext.version.max(2);
+        // range checked above
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(scaled as i16)
+    };
+
+    match column {
+        "Description" => bext.description = fixed(column, value)?,
+        "Originator" => bext.originator = fixed(column, value)?,
+        "OriginatorReference" => bext.originator_reference = fixed(column, value)?,
+        "OriginationDate" => bext.origination_date = fixed(column, value)?,
+        "OriginationTime" => bext.origination_time = fixed(column, value)?,
+        "TimeReference" if value.is_empty() => bext.time_reference = 0,
+        "TimeReference" => {
+            bext.time_reference = value.trim().parse().map_err(|_| invalid(column, value))?;
+        }
+        "BextVersion" if value.is_empty() => {}
+        "BextVersion" => bext.version = value.trim().parse().map_err(|_| invalid(column, value))?,
+        "UMID" => {
+            let bytes = hex::decode(value.trim())
+                .ok()
+                .filter(|b| b.len() <= 64)
+                .ok_or_else(|| invalid(column, value))?;
+            bext.umid = [0_u8; 64];
+            bext.umid[..bytes.len()].copy_from_slice(&bytes);
+            if !bytes.is_empty() {
+                bext.version = bext.version.max(1);
+            }
+        }
+        "LoudnessValue" => bext.loudness_value = loudness(bext)?,
+        "LoudnessRange" => bext.loudness_range = loudness(bext)?,
+        "MaxTruePeakLevel" => bext.max_true_peak_level = loudness(bext)?,
+        "MaxMomentaryLoudness" => bext.max_momentary_loudness = loudness(bext)?,
+        "MaxShortTermLoudness" => bext.max_short_term_loudness = loudness(bext)?,
+        "CodingHistory" => bext.coding_history = value.to_string(),
+        // derived from TimeReference
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Write `rows` as a Core document with all [`CORE_COLUMNS`].
+///
+/// Columns a row doesn't have are written empty. Lines end in `\r\n`.
+pub fn write_core_csv(rows: &[CoreRow]) -> String {
+    let mut out = String::new();
+    write_record(&mut out, CORE_COLUMNS.iter().copied());
+    for row in rows {
+        let values = CORE_COLUMNS.iter().map(|column| match *column {
+            "FileName" => row.file_name.as_str(),
+            column => row.get(column).unwrap_or_default(),
+        });
+        write_record(&mut out, values);
+    }
+    out
+}
+
+fn write_record<'a>(out: &mut String, values: impl Iterator<Item = &'a str>) {
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        if value.contains([',', '"', '\r', '\n']) {
+            out.push('"');
+            out.push_str(&value.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(value);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+/// Read a Core document, as written by BWF MetaEdit or [`write_core_csv()`].
+///
+/// The header must contain `FileName`, other columns are optional and may
+/// be in any order. Returns an error for unknown columns.
+pub fn read_core_csv(document: &str) -> Result<Vec<CoreRow>, WaveFileError> {
+    let mut records = parse_records(document)?.into_iter();
+    let Some(header) = records.next() else {
+        return Err(WaveFileError::parse(None, "Core CSV: missing header"));
+    };
+    if let Some(unknown) = header.iter().find(|c| !CORE_COLUMNS.contains(&c.as_str())) {
+        return Err(WaveFileError::parse(
+            None,
+            format!("Core CSV: unknown column {unknown:?}"),
+        ));
+    }
+    let Some(file_name_index) = header.iter().position(|c| c == "FileName") else {
+        return Err(WaveFileError::parse(
+            None,
+            "Core CSV: missing FileName column",
+        ));
+    };
+
+    let mut rows = Vec::new();
+    for (line, record) in records.enumerate() {
+        if record.iter().all(String::is_empty) {
+            continue;
+        }
+        if record.len() != header.len() {
+            return Err(WaveFileError::parse(
+                None,
+                format!(
+                    "Core CSV: row {} has {} values, expected {}",
+                    line + 1,
+                    record.len(),
+                    header.len()
+                ),
+            ));
+        }
+        let mut row = CoreRow::default();
+        for (index, (column, value)) in header.iter().zip(record).enumerate() {
+            if index == file_name_index {
+                row.file_name = value;
+            } else {
+                row.fields.push((column.clone(), value));
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Split `document` into records of unquoted values.
+fn parse_records(document: &str) -> Result<Vec<Vec<String>>, WaveFileError> {
+    // spreadsheet programs often start UTF-8 files with a byte order mark
+    let document = document.strip_prefix('\u{feff}').unwrap_or(document);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut value = String::new();
+    let mut chars = document.chars().peekable();
+    let mut at_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if value.is_empty() => loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        value.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => value.push(c),
+                    None => {
+                        return Err(WaveFileError::parse(
+                            None,
+                            format!("Core CSV: missing closing quote in row {}", records.len()),
+                        ))
+                    }
+                }
+            },
+            ',' => record.push(core::mem::take(&mut value)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(core::mem::take(&mut value));
+                records.push(core::mem::take(&mut record));
+                at_start = true;
+                continue;
+            }
+            c => value.push(c),
+        }
+        at_start = false;
+    }
+    if !at_start {
+        record.push(value);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::{BufReader, Cursor};
+
+    use super::*;
+
+    #[test]
+    fn csv_quoting() {
+        let mut row = CoreRow {
+            file_name: "a, \"b\".wav".to_string(),
+            fields: Vec::new(),
+        };
+        row.set("Description", "line 1\r\nline 2");
+        row.set("INAM", "plain");
+        let csv = write_core_csv(&[row.clone()]);
+        dbg!(&csv);
+        assert!(csv.starts_with("FileName,Description,Originator,"));
+        assert!(csv.contains("\r\n\"a, \"\"b\"\".wav\",\"line 1\r\nline 2\",,"));
+
+        let rows = read_core_csv(&csv).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].file_name, row.file_name);
+        assert_eq!(rows[0].get("Description"), row.get("Description"));
+        assert_eq!(rows[0].get("INAM"), Some("plain"));
+        assert_eq!(rows[0].get("IART"), Some(""));
+
+        let rows = read_core_csv("\u{feff}INAM,FileName\n\"x\",a.wav\n\n").unwrap();
+        assert_eq!(rows[0].file_name, "a.wav");
+        assert_eq!(rows[0].fields, vec![("INAM".into(), "x".into())]);
+    }
+
+    #[test]
+    fn csv_errors() {
+        let err = |text: &str| read_core_csv(text).unwrap_err().to_string();
+        assert!(err("").contains("missing header"));
+        assert!(err("INAM\nx\n").contains("missing FileName"));
+        assert!(err("FileName,Title\n").contains("unknown column \"Title\""));
+        assert!(err("FileName,INAM\na.wav\n").contains("row 1 has 1 values, expected 2"));
+        assert!(err("FileName\n\"a.wav\n").contains("missing closing quote"));
+    }
+
+    #[test]
+    fn apply_example_a() {
+        let path = "../test_wavs/example_a.wav";
+        let mut wave = WaveFile::from_reader(BufReader::new(File::open(path).unwrap())).unwrap();
+        let row = CoreRow::from_wave(path, &mut wave).unwrap();
+        dbg!(&row);
+        assert_eq!(row.fields.len(), CORE_COLUMNS.len() - 1);
+        assert_eq!(row.get("TimeReference (translated)"), Some("00:00:00.000"));
+        assert_eq!(row.get("BextVersion"), Some("1"));
+        assert_eq!(row.get("LoudnessValue"), Some(""));
+        assert_eq!(row.get("INAM"), Some("TrackTitle"));
+
+        let mut changed = row.clone();
+        changed.set("Originator", "Archive");
+        changed.set("LoudnessValue", "-23.5");
+        changed.set("UMID", "");
+        changed.set("INAM", "");
+        changed.set("ISRC", "Field recording");
+        let mut output = Cursor::new(Vec::new());
+        changed.apply(&mut wave, &mut output).unwrap();
+
+        output.set_position(0);
+        let mut wave = WaveFile::from_reader(output).unwrap();
+        let result = CoreRow::from_wave(path, &mut wave).unwrap();
+        assert_eq!(result.get("Originator"), Some("Archive"));
+        assert_eq!(result.get("BextVersion"), Some("2"));
+        assert_eq!(result.get("LoudnessValue"), Some("-23.50"));
+        assert_eq!(result.get("INAM"), Some(""));
+        assert_eq!(result.get("ISRC"), Some("Field recording"));
+        assert_eq!(result.get("IART"), row.get("IART"));
+
+        let mut bad = row;
+        bad.set("Originator", &"x".repeat(33));
+        let mut output = Cursor::new(Vec::new());
+        assert!(bad.apply(&mut wave, &mut output).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Compact per file summaries for quick listings.
+//!
+//! [`WaveFile::overview()`](crate::WaveFile::overview) answers the questions
+//! of a directory listing, format, length and which chunks are present,
+//! without parsing every chunk. Only the chunk headers are read, plus the
+//! small `fmt ` and `fact` chunks, see
+//! [`WaveFile::scan_chunks()`](crate::WaveFile::scan_chunks).
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let overview = wave.overview()?;
+//!
+//! assert_eq!(overview.sample_rate, Some(48000));
+//! assert_eq!(overview.duration_samples, Some(480));
+//! assert!(overview.has_bext && overview.has_ixml);
+//! assert_eq!(
+//!     overview.to_string(),
+//!     "PCM (0x0001), 1 chan, 24/48000, 00:00:00.010, bext iXML"
+//! );
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::chunk::data::Data;
+use crate::chunk::fmt::Tag;
+use crate::metadata::duration_samples;
+use crate::time::SampleTime;
+use crate::{FourCC, KnownChunkID, LazyChunk, SizedChunkEnum, WaveMetadata};
+
+/// Format, length and chunk ids of a file, see the
+/// [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Overview {
+    /// Id of the header chunk: `RIFF`, `RF64` or `BW64`.
+    pub riff_id: FourCC,
+    /// Format tag from `fmt ` without the `WAVE_FORMAT_` prefix.
+    /// Ex: `PCM (0x0001)`
+    pub format: Option<String>,
+    /// Number of channels, from `fmt `.
+    pub channels: Option<u16>,
+    /// Sample rate in samples per second, from `fmt `.
+    pub sample_rate: Option<u32>,
+    /// Bits per sample, from `fmt `.
+    pub bit_depth: Option<u16>,
+    /// Length of the audio in samples (per channel), see
+    /// [`WaveMetadata::duration_samples()`].
+    pub duration_samples: Option<u64>,
+    /// Ids of all chunks, in file order.
+    pub chunk_ids: Vec<FourCC>,
+    /// A `bext` chunk is present.
+    pub has_bext: bool,
+    /// An `iXML` chunk is present.
+    pub has_ixml: bool,
+}
+
+impl Overview {
+    /// Overview from scanned chunk headers and the parsed `fmt ` and `fact`
+    /// chunks. When a file has duplicate chunks, the first one is used.
+    pub fn new(riff_id: FourCC, scanned: &[LazyChunk], parsed: &[SizedChunkEnum]) -> Self {
+        let metadata = WaveMetadata::new(parsed);
+        let fmt = metadata.fmt();
+        let data_size = scanned
+            .iter()
+            .find(|c| c.id == Data::ID)
+            .map(LazyChunk::large_size);
+        let has_id = |id: &[u8; 4]| scanned.iter().any(|c| c.id == FourCC(*id));
+        Overview {
+            riff_id,
+            format: fmt.map(|fmt| fmt.format_tag().to_string().replace("WAVE_FORMAT_", "")),
+            channels: metadata.channels(),
+            sample_rate: metadata.sample_rate(),
+            bit_depth: metadata.bit_depth(),
+            duration_samples: fmt
+                .and_then(|fmt| duration_samples(fmt, data_size, metadata.fact_samples())),
+            chunk_ids: scanned.iter().map(|c| c.id).collect(),
+            has_bext: has_id(b"bext"),
+            has_ixml: has_id(b"iXML"),
+        }
+    }
+
+    /// Length of the audio as a time, `None` without a `fmt ` chunk or if
+    /// the length is unknown.
+    pub fn duration(&self) -> Option<SampleTime> {
+        Some(SampleTime::new(self.duration_samples?, self.sample_rate?))
+    }
+}
+
+impl Display for Overview {
+    /// One line: format, channels, bits/rate, duration and the `bext` and
+    /// `iXML` flags.
+    /// Ex: `PCM (0x0001), 2 chan, 24/48000, 00:01:02.500, bext iXML`
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut parts = Vec::new();
+        match (
+            &self.format,
+            self.channels,
+            self.bit_depth,
+            self.sample_rate,
+        ) {
+            (Some(format), Some(channels), Some(bits), Some(rate)) => {
+                parts.push(format.clone());
+                parts.push(format!("{channels} chan"));
+                parts.push(format!("{bits}/{rate}"));
+            }
+            _ => parts.push("no fmt".to_string()),
+        }
+        match self.duration() {
+            Some(duration) => parts.push(duration.to_string()),
+            None => parts.push("unknown length".to_string()),
+        }
+        let flags: Vec<_> = [(self.has_bext, "bext"), (self.has_ixml, "iXML")]
+            .into_iter()
+            .filter_map(|(present, name)| present.then_some(name))
+            .collect();
+        if !flags.is_empty() {
+            parts.push(flags.join(" "));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overview() {
+        let mut wave = crate::WaveFile::from_reader(crate::testing::hex_to_cursor(
+            "52494646 2C000000 57415645
+             666D7420 10000000 01000200 80BB0000 00EE0200 04001000
+             64617461 08000000 00000000 00000000",
+        ))
+        .unwrap();
+        let overview = wave.overview().unwrap();
+        dbg!(&overview);
+        assert_eq!(overview.riff_id, FourCC(*b"RIFF"));
+        assert_eq!(overview.format.as_deref(), Some("PCM (0x0001)"));
+        assert_eq!(overview.channels, Some(2));
+        assert_eq!(overview.bit_depth, Some(16));
+        assert_eq!(overview.duration_samples, Some(2));
+        assert_eq!(overview.chunk_ids, [FourCC(*b"fmt "), FourCC(*b"data")]);
+        assert!(!overview.has_bext && !overview.has_ixml);
+        assert_eq!(
+            overview.to_string(),
+            "PCM (0x0001), 2 chan, 16/48000, 00:00:00.000"
+        );
+
+        let overview = Overview::new(FourCC(*b"RIFF"), &[], &[]);
+        assert_eq!(overview.to_string(), "no fmt, unknown length");
+    }
+}
@@ -0,0 +1,302 @@
+//! Sample peaks, true peaks and peak envelopes of the audio in the `data`
+//! chunk.
+//!
+//! [`scan()`] reads the audio once and returns a [`PeakEnvelope`]: the
+//! highest and lowest sample of each channel for every block of frames,
+//! and the sample and true peak of the whole file. Editors show the
+//! envelope as a waveform overview, [`PeakEnvelope::to_levl()`] stores it
+//! in a `levl` chunk.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::chunk::levl::{LevlChunk, PeakFormat};
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let envelope = wavrw::peaks::scan(&mut wave, wavrw::peaks::DEFAULT_BLOCK_SIZE)?;
+//! println!("peak: {:.1} dBFS", envelope.peak_dbfs());
+//! let levl = LevlChunk::new(envelope.to_levl(PeakFormat::UnsignedShort, 2)?)?;
+//! assert_eq!(levl.data.num_peak_frames, 2);
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::f64::consts::PI;
+use core::fmt::Debug;
+use std::io::{BufRead, Read, Seek};
+
+use crate::chunk::levl::{Levl, PeakFormat};
+use crate::samples::Samples;
+use crate::{WaveFile, WaveFileError};
+
+/// Frames per block suggested for `levl` chunks.
+pub const DEFAULT_BLOCK_SIZE: u32 = 256;
+
+/// Peak levels of a file, see [`scan()`].
+///
+/// Levels are linear, full scale is 1.0.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PeakEnvelope {
+    /// Frames per block.
+    pub block_size: u32,
+
+    /// Number of channels.
+    pub channels: usize,
+
+    /// Highest sample of each block and channel: the channels of the first
+    /// block, then of the second and so on. The last block may be partial.
+    pub max: Vec<f64>,
+
+    /// Lowest sample of each block and channel, ordered like `max`.
+    pub min: Vec<f64>,
+
+    /// Highest absolute sample value.
+    pub peak: f64,
+
+    /// Frame of the first sample at `peak`, `None` without audio.
+    pub peak_frame: Option<u64>,
+
+    /// Highest absolute value between samples, oversampled as in
+    /// ITU-R BS.1770-4 annex 2.
+    pub true_peak: f64,
+}
+
+impl PeakEnvelope {
+    /// Number of blocks.
+    pub fn block_count(&self) -> usize {
+        self.max.len().checked_div(self.channels).unwrap_or(0)
+    }
+
+    /// Sample peak in dBFS, `-inf` for silence.
+    pub fn peak_dbfs(&self) -> f64 {
+        20.0 * self.peak.log10()
+    }
+
+    /// True peak in dBTP, `-inf` for silence.
+    pub fn true_peak_dbtp(&self) -> f64 {
+        20.0 * self.true_peak.log10()
+    }
+
+    /// A `levl` chunk with `points_per_value` points of `format` per block
+    /// and channel: 1 for the absolute peak, 2 for the positive and negative
+    /// peaks.
+    ///
+    /// The timestamp is left empty. Returns an error for other formats and
+    /// point counts, or more blocks than a `levl` chunk can hold.
+    pub fn to_levl(
+        &self,
+        format: PeakFormat,
+        points_per_value: u32,
+    ) -> Result<Levl, WaveFileError> {
+        let Some(full_scale) = format.full_scale() else {
+            return Err(WaveFileError::parse(
+                None,
+                format!("unsupported levl format: {format}"),
+            ));
+        };
+        if !(1..=2).contains(&points_per_value) {
+            return Err(WaveFileError::parse(
+                None,
+                format!("levl points per value must be 1 or 2, found: {points_per_value}"),
+            ));
+        }
+        let too_large = |_| WaveFileError::parse(None, "too many blocks for a levl chunk");
+        let point = |level: f64| {
+            // clamped to full_scale, so no truncation or sign loss
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let point = (level.clamp(0.0, 1.0) * f64::from(full_scale)).round() as u16;
+            point
+        };
+        let mut peak_data = Vec::new();
+        for (max, min) in self.max.iter().zip(&self.min) {
+            let points = if points_per_value == 1 {
+                vec![point(max.abs().max(min.abs()))]
+            } else {
+                vec![point(*max), point(-min)]
+            };
+            for p in points {
+                match format {
+                    PeakFormat::UnsignedChar => peak_data.extend_from_slice(&p.to_le_bytes()[..1]),
+                    PeakFormat::UnsignedShort | PeakFormat::Other(_) => {
+                        peak_data.extend_from_slice(&p.to_le_bytes());
+                    }
+                }
+            }
+        }
+        Ok(Levl {
+            format,
+            points_per_value,
+            block_size: self.block_size,
+            peak_channels: u32::try_from(self.channels).map_err(too_large)?,
+            num_peak_frames: u32::try_from(self.block_count()).map_err(too_large)?,
+            pos_peak_of_peaks: self
+                .peak_frame
+                .and_then(|frame| u32::try_from(frame).ok())
+                .unwrap_or(u32::MAX),
+            peak_data,
+            ..Levl::default()
+        })
+    }
+}
+
+/// Scan the audio of `wave` in blocks of `block_size` frames.
+///
+/// Returns an error if `block_size` is 0 or the audio can't be decoded,
+/// see [`Samples::new()`].
+pub fn scan<R>(wave: &mut WaveFile<R>, block_size: u32) -> Result<PeakEnvelope, WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    if block_size == 0 {
+        return Err(WaveFileError::parse(None, "peak block size must not be 0"));
+    }
+    let mut samples = Samples::new(wave)?;
+    let channels = samples.channels();
+    let sample_rate = f64::from(samples.sample_rate().max(1));
+    let mut true_peaks = vec![TruePeak::new(sample_rate); channels];
+    let mut envelope = PeakEnvelope {
+        block_size,
+        channels,
+        ..PeakEnvelope::default()
+    };
+
+    let mut frame: u64 = 0;
+    let mut frames = Vec::new();
+    while samples.read_frames(&mut frames, 4096)? > 0 {
+        for samples in frames.chunks_exact(channels) {
+            if frame.is_multiple_of(u64::from(block_size)) {
+                envelope.max.extend(samples);
+                envelope.min.extend(samples);
+            }
+            let start = envelope.max.len() - channels;
+            for (channel, sample) in samples.iter().enumerate() {
+                envelope.max[start + channel] = envelope.max[start + channel].max(*sample);
+                envelope.min[start + channel] = envelope.min[start + channel].min(*sample);
+                true_peaks[channel].add(*sample);
+                if sample.abs() > envelope.peak || envelope.peak_frame.is_none() {
+                    envelope.peak = sample.abs().max(envelope.peak);
+                    envelope.peak_frame = Some(frame);
+                }
+            }
+            frame += 1;
+        }
+    }
+    envelope.true_peak = true_peaks
+        .iter()
+        .map(TruePeak::peak)
+        .fold(envelope.peak, f64::max);
+    Ok(envelope)
+}
+
+/// Samples on each side used to interpolate between two samples.
+const TRUE_PEAK_TAPS: usize = 8;
+
+/// Highest absolute value of one channel, oversampled as in BS.1770-4
+/// annex 2: 4x below 96 kHz, 2x below 192 kHz.
+#[derive(Debug, Clone)]
+pub(crate) struct TruePeak {
+    // windowed sinc coefficients, one row per interpolated phase
+    phases: Vec<[f64; 2 * TRUE_PEAK_TAPS]>,
+    history: [f64; 2 * TRUE_PEAK_TAPS],
+    peak: f64,
+}
+
+impl TruePeak {
+    pub(crate) fn new(sample_rate: f64) -> Self {
+        let factor: u32 = if sample_rate < 96_000.0 {
+            4
+        } else if sample_rate < 192_000.0 {
+            2
+        } else {
+            1
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let half = TRUE_PEAK_TAPS as f64;
+        let phases = (1..factor)
+            .map(|phase| {
+                let offset = f64::from(phase) / f64::from(factor);
+                let mut coefficients = [0.0; 2 * TRUE_PEAK_TAPS];
+                // distance from the interpolated point to each history sample
+                let mut t = half - 1.0 + offset;
+                for c in &mut coefficients {
+                    let sinc = if t == 0.0 {
+                        1.0
+                    } else {
+                        (PI * t).sin() / (PI * t)
+                    };
+                    let window = 0.5 + 0.5 * (PI * t / half).cos();
+                    *c = sinc * window;
+                    t -= 1.0;
+                }
+                coefficients
+            })
+            .collect();
+        TruePeak {
+            phases,
+            history: [0.0; 2 * TRUE_PEAK_TAPS],
+            peak: 0.0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, sample: f64) {
+        self.peak = self.peak.max(sample.abs());
+        self.history.copy_within(1.., 0);
+        self.history[2 * TRUE_PEAK_TAPS - 1] = sample;
+        for coefficients in &self.phases {
+            let value: f64 = coefficients
+                .iter()
+                .zip(&self.history)
+                .map(|(c, x)| c * x)
+                .sum();
+            self.peak = self.peak.max(value.abs());
+        }
+    }
+
+    pub(crate) fn peak(&self) -> f64 {
+        self.peak
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chunk::levl::LevlChunk;
+    use crate::testing::hex_to_cursor;
+
+    #[test]
+    fn scan_blocks() {
+        // stereo 16 bit PCM, 5 frames in blocks of 2
+        let data = "52494646 38000000 57415645 \
+            666D7420 10000000 0100 0200 80BB0000 00EE0200 0400 1000 \
+            64617461 14000000 0040 0000 00C0 0020 0000 0000 FF7F 0080 0010 00F0";
+        let mut wave = WaveFile::from_reader(hex_to_cursor(data)).unwrap();
+        let envelope = scan(&mut wave, 2).unwrap();
+        dbg!(&envelope);
+        assert_eq!(envelope.block_count(), 3);
+        assert_eq!(
+            envelope.max,
+            [0.5, 0.25, 32767.0 / 32768.0, 0.0, 0.125, -0.125]
+        );
+        assert_eq!(envelope.min, [-0.5, 0.0, 0.0, -1.0, 0.125, -0.125]);
+        assert!((envelope.peak - 1.0).abs() < f64::EPSILON);
+        assert_eq!(envelope.peak_frame, Some(3));
+        assert!(envelope.peak_dbfs().abs() < f64::EPSILON);
+        assert!(envelope.true_peak >= envelope.peak);
+
+        let levl = envelope.to_levl(PeakFormat::UnsignedChar, 2).unwrap();
+        assert_eq!(levl.peak_channels, 2);
+        assert_eq!(levl.num_peak_frames, 3);
+        assert_eq!(levl.pos_peak_of_peaks, 3);
+        assert_eq!(levl.peak_data[..4], [64, 64, 32, 0]);
+        let chunk = LevlChunk::new(levl).unwrap();
+        assert_eq!(chunk.size, Levl::HEADER_SIZE + 12);
+
+        let levl = envelope.to_levl(PeakFormat::UnsignedShort, 1).unwrap();
+        assert_eq!(levl.points().len(), 6);
+        assert!((levl.points()[0] - 0.5).abs() < 0.001);
+        assert!(envelope.to_levl(PeakFormat::Other(3), 1).is_err());
+        assert!(envelope.to_levl(PeakFormat::UnsignedShort, 3).is_err());
+        assert!(scan(&mut wave, 0).is_err());
+    }
+}
@@ -0,0 +1,514 @@
+//! Conformance profiles for deliverable specifications.
+//!
+//! [`ResolvedWave::validate()`](crate::ResolvedWave::validate) checks that a
+//! file agrees with itself. Delivery specifications go further and require
+//! chunks and fields which are optional in the WAVE format: a broadcaster may
+//! insist on a `bext` originator and coding history, an object based audio
+//! workflow on `chna` and `axml` chunks. A [`Profile`] checks one such set of
+//! requirements and reports each unmet one as a [`Warning`].
+//!
+//! - [`Profile::Riff`]: plain `RIFF` `WAVE`, `fmt ` before `data`.
+//! - [`Profile::EbuR98`]: Broadcast Wave Format (EBU Tech 3285) with a
+//!   `CodingHistory` in the EBU R 98 format.
+//! - [`Profile::EbuR99`]: Broadcast Wave Format with an `OriginatorReference`
+//!   in the EBU R 99 unique source identifier format.
+//! - [`Profile::AdmBw64`]: ITU-R BS.2088 `BW64` with ADM `chna` and `axml`
+//!   chunks.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::profile::Profile;
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let riff_id = wave.riff().id;
+//!
+//! assert!(Profile::Riff.check(riff_id, wave.chunks()).is_empty());
+//! // no coding history
+//! assert!(!Profile::EbuR98.check(riff_id, wave.chunks()).is_empty());
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use crate::chunk::bext::Bext;
+use crate::chunk::data::DataChunk;
+use crate::chunk::ds64::Ds64;
+use crate::chunk::fmt::FmtChunk;
+use crate::umid::Umid;
+use crate::warning::Code;
+use crate::{ChunkID, FourCC, KnownChunkID, SizedChunk, SizedChunkEnum, Warning, WaveFileError};
+
+/// A set of requirements from a deliverable specification, see the
+/// [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Profile {
+    /// Plain `RIFF` `WAVE` file.
+    Riff,
+    /// Broadcast Wave Format with an EBU R 98 `CodingHistory`.
+    EbuR98,
+    /// Broadcast Wave Format with an EBU R 99 `OriginatorReference`.
+    EbuR99,
+    /// ITU-R BS.2088 `BW64` file with ADM metadata.
+    AdmBw64,
+}
+
+impl Profile {
+    /// All profiles.
+    pub const ALL: [Profile; 4] = [
+        Profile::Riff,
+        Profile::EbuR98,
+        Profile::EbuR99,
+        Profile::AdmBw64,
+    ];
+
+    /// Name of the profile, as parsed by [`Profile::from_str()`]. Ex: `ebu-r98`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Profile::Riff => "riff",
+            Profile::EbuR98 => "ebu-r98",
+            Profile::EbuR99 => "ebu-r99",
+            Profile::AdmBw64 => "adm-bw64",
+        }
+    }
+
+    /// Check the chunks of a file against the profile. `riff_id` is the id
+    /// of the file header, see [`WaveFile::riff()`](crate::WaveFile::riff).
+    ///
+    /// All profiles require a `fmt ` chunk followed by a `data` chunk.
+    pub fn check(&self, riff_id: FourCC, chunks: &[SizedChunkEnum]) -> Vec<Warning> {
+        let mut warnings = check_fmt_data(chunks);
+        match self {
+            Profile::Riff => {
+                if riff_id != FourCC(*b"RIFF") {
+                    warnings.push(
+                        Warning::new(riff_id, Some(0), "file header should be RIFF")
+                            .with_code(Code::InvalidValue),
+                    );
+                }
+            }
+            Profile::EbuR98 => {
+                if let Some((bext, offset)) = check_bwf(chunks, &mut warnings) {
+                    warnings.extend(check_coding_history(bext, offset));
+                }
+            }
+            Profile::EbuR99 => {
+                if let Some((bext, offset)) = check_bwf(chunks, &mut warnings) {
+                    warnings.extend(check_usid(bext, offset));
+                }
+            }
+            Profile::AdmBw64 => warnings.extend(check_adm(riff_id, chunks)),
+        }
+        warnings
+    }
+}
+
+impl FromStr for Profile {
+    type Err = WaveFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Profile::ALL
+            .into_iter()
+            .find(|p| p.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Profile::ALL.iter().map(Profile::as_str).collect();
+                WaveFileError::parse(
+                    None,
+                    format!(
+                        "unknown profile {s:?}, expected one of {}",
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Position in `chunks` and offset of the first chunk with `id`.
+fn find(chunks: &[SizedChunkEnum], id: FourCC) -> Option<(usize, Option<u64>)> {
+    chunks
+        .iter()
+        .position(|c| c.id() == id)
+        .map(|i| (i, chunks[i].offset()))
+}
+
+fn missing_chunk(id: FourCC) -> Warning {
+    Warning::new(id, None, "missing, required by profile").with_code(Code::MissingChunk)
+}
+
+/// `fmt ` and `data` are present, in that order.
+fn check_fmt_data(chunks: &[SizedChunkEnum]) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    match (find(chunks, FmtChunk::ID), find(chunks, DataChunk::ID)) {
+        (Some((fmt, _)), Some((data, offset))) if data < fmt => warnings.push(
+            Warning::new(DataChunk::ID, offset, "should come after fmt ")
+                .with_code(Code::ChunkOrder),
+        ),
+        (fmt, data) => {
+            if fmt.is_none() {
+                warnings.push(missing_chunk(FmtChunk::ID));
+            }
+            if data.is_none() {
+                warnings.push(missing_chunk(DataChunk::ID));
+            }
+        }
+    }
+    warnings
+}
+
+/// Broadcast Wave Format requirements shared by the EBU profiles. Returns
+/// the `bext` chunk and its offset for further checks.
+fn check_bwf<'a>(
+    chunks: &'a [SizedChunkEnum],
+    warnings: &mut Vec<Warning>,
+) -> Option<(&'a Bext, Option<u64>)> {
+    let Some((bext, offset)) = chunks.iter().find_map(|c| {
+        if let SizedChunkEnum::Bext(bext) = c {
+            Some((&bext.data, bext.offset))
+        } else {
+            None
+        }
+    }) else {
+        warnings.push(missing_chunk(Bext::ID));
+        return None;
+    };
+    let mut missing = |field: &str| {
+        warnings.push(
+            Warning::new(Bext::ID, offset, format!("{field} is empty"))
+                .with_code(Code::MissingField),
+        );
+    };
+    let originator = bext.originator.to_string();
+    let originator_reference = bext.originator_reference.to_string();
+    let date = bext.origination_date.to_string();
+    let time = bext.origination_time.to_string();
+    for (field, value) in [
+        ("originator", &originator),
+        ("originator_reference", &originator_reference),
+        ("origination_date", &date),
+        ("origination_time", &time),
+    ] {
+        if value.trim().is_empty() {
+            missing(field);
+        }
+    }
+    let mut invalid = |message: String| {
+        warnings.push(Warning::new(Bext::ID, offset, message).with_code(Code::InvalidValue));
+    };
+    if !date.trim().is_empty() && bext.parsed_origination_date().is_none() {
+        invalid(format!("origination_date {date:?} is not yyyy-mm-dd"));
+    }
+    if !time.trim().is_empty() && bext.parsed_origination_time().is_none() {
+        invalid(format!("origination_time {time:?} is not hh:mm:ss"));
+    }
+    if bext.has_umid() {
+        if let Some(problem) = umid_problem(&bext.umid) {
+            invalid(format!("umid {problem}"));
+        }
+    } else {
+        warnings.push(
+            Warning::new(
+                Bext::ID,
+                offset,
+                format!(
+                    "umid requires version 1 or later, version is {}",
+                    bext.version
+                ),
+            )
+            .with_code(Code::MissingField),
+        );
+    }
+    Some((bext, offset))
+}
+
+/// Why `umid` is not a valid basic or extended SMPTE UMID.
+fn umid_problem(umid: &[u8; 64]) -> Option<String> {
+    if umid.iter().all(|&b| b == 0) {
+        return Some("is empty".to_string());
+    }
+    if Umid::from_bytes(umid).is_none() {
+        return Some("does not start with the SMPTE UMID label".to_string());
+    }
+    match umid[12] {
+        Umid::BASIC_LENGTH if umid[32..].iter().any(|&b| b != 0) => {
+            Some("is a basic UMID followed by non zero bytes".to_string())
+        }
+        Umid::BASIC_LENGTH | Umid::EXTENDED_LENGTH => None,
+        length => Some(format!(
+            "length byte is 0x{length:02X}, expected 0x{:02X} or 0x{:02X}",
+            Umid::BASIC_LENGTH,
+            Umid::EXTENDED_LENGTH
+        )),
+    }
+}
+
+/// `coding_history` is present and each line is a list of EBU R 98
+/// `X=value` fields, starting with the algorithm `A=`.
+fn check_coding_history(bext: &Bext, offset: Option<u64>) -> Vec<Warning> {
+    let warning =
+        |message: String, code: Code| Warning::new(Bext::ID, offset, message).with_code(code);
+    if bext.coding_history.is_empty() {
+        return vec![warning(
+            "coding_history is empty".to_string(),
+            Code::MissingField,
+        )];
+    }
+    let mut warnings = Vec::new();
+    if !bext.coding_history.ends_with("\r\n")
+        || bext
+            .coding_history
+            .split("\r\n")
+            .any(|l| l.contains(['\r', '\n']))
+    {
+        warnings.push(warning(
+            "coding_history lines should end with CR LF".to_string(),
+            Code::InvalidValue,
+        ));
+    }
+    for (number, line) in bext.coding_history.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(problem) = coding_history_line_problem(line) {
+            warnings.push(warning(
+                format!("coding_history line {} {problem}: {line:?}", number + 1),
+                Code::InvalidValue,
+            ));
+        }
+    }
+    warnings
+}
+
+/// Why `line` is not an EBU R 98 coding history line.
+fn coding_history_line_problem(line: &str) -> Option<&'static str> {
+    if !line.starts_with("A=") {
+        return Some("does not start with A=");
+    }
+    for field in line.split(',') {
+        // free text runs to the end of the line, and may contain commas
+        if field.starts_with("T=") {
+            break;
+        }
+        match field.split_once('=') {
+            Some(("A" | "F" | "B" | "W" | "M", _)) => (),
+            _ => return Some("has a field which is not A=, F=, B=, W=, M= or T="),
+        }
+    }
+    None
+}
+
+/// `originator_reference` is an EBU R 99 unique source identifier:
+/// country, organisation, serial number, origination time and random
+/// number, 32 characters in total.
+fn check_usid(bext: &Bext, offset: Option<u64>) -> Option<Warning> {
+    let usid = bext.originator_reference.to_string();
+    if usid.trim().is_empty() {
+        // reported by check_bwf
+        return None;
+    }
+    let problem = usid_problem(usid.as_bytes())?;
+    Some(
+        Warning::new(
+            Bext::ID,
+            offset,
+            format!("originator_reference {usid:?} is not an EBU R 99 USID: {problem}"),
+        )
+        .with_code(Code::InvalidValue),
+    )
+}
+
+/// Why `usid` is not a valid `CCOOONNNNNNNNNNNNHHMMSSRRRRRRRRR` identifier.
+fn usid_problem(usid: &[u8]) -> Option<&'static str> {
+    if usid.len() != 32 {
+        return Some("should be 32 characters");
+    }
+    let digits = |range: core::ops::Range<usize>| usid[range].iter().all(u8::is_ascii_digit);
+    let number = |i: usize| (usid[i] - b'0') * 10 + (usid[i + 1] - b'0');
+    if !usid[..2].iter().all(u8::is_ascii_uppercase) {
+        Some("country code should be 2 uppercase letters")
+    } else if !usid[2..17].iter().all(u8::is_ascii_alphanumeric) {
+        Some("organisation code and serial number should be alphanumeric")
+    } else if !digits(17..23) || number(17) > 23 || number(19) > 59 || number(21) > 59 {
+        Some("origination time should be hhmmss")
+    } else if !digits(23..32) {
+        Some("random number should be 9 digits")
+    } else {
+        None
+    }
+}
+
+/// ITU-R BS.2088 requirements: a `BW64` header, `ds64` as the first chunk,
+/// and ADM `chna` and `axml` chunks.
+fn check_adm(riff_id: FourCC, chunks: &[SizedChunkEnum]) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    if riff_id != FourCC(*b"BW64") {
+        warnings.push(
+            Warning::new(riff_id, Some(0), "file header should be BW64")
+                .with_code(Code::InvalidValue),
+        );
+    }
+    match find(chunks, Ds64::ID) {
+        None => warnings.push(missing_chunk(Ds64::ID)),
+        Some((index, offset)) if index > 0 => warnings.push(
+            Warning::new(Ds64::ID, offset, "should be the first chunk").with_code(Code::ChunkOrder),
+        ),
+        Some(_) => (),
+    }
+    for id in [FourCC(*b"chna"), FourCC(*b"axml")] {
+        if find(chunks, id).is_none() {
+            warnings.push(missing_chunk(id));
+        }
+    }
+    warnings
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::chunk::bext::BextChunk;
+    use crate::WaveFile;
+
+    fn example_a() -> Vec<SizedChunkEnum> {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        wave.chunks().to_vec()
+    }
+
+    /// `example_a.wav` with its `bext` chunk replaced by `bext`.
+    fn with_bext(bext: &Bext) -> Vec<SizedChunkEnum> {
+        let mut chunks = example_a();
+        for chunk in &mut chunks {
+            if let SizedChunkEnum::Bext(_) = chunk {
+                *chunk = SizedChunkEnum::Bext(Box::new(BextChunk::new(bext.clone()).unwrap()));
+            }
+        }
+        chunks
+    }
+
+    fn codes(warnings: &[Warning]) -> Vec<Code> {
+        warnings.iter().map(|w| w.code).collect()
+    }
+
+    #[test]
+    fn parse_profile_names() {
+        for profile in Profile::ALL {
+            assert_eq!(profile.to_string().parse::<Profile>().unwrap(), profile);
+        }
+        assert_eq!("EBU-R99".parse::<Profile>().unwrap(), Profile::EbuR99);
+        let err = "r128".parse::<Profile>().unwrap_err();
+        dbg!(&err);
+        assert!(err.to_string().contains("ebu-r98"));
+    }
+
+    #[test]
+    fn riff_profile() {
+        let chunks = example_a();
+        assert!(Profile::Riff.check(FourCC(*b"RIFF"), &chunks).is_empty());
+        let warnings = Profile::Riff.check(FourCC(*b"RF64"), &chunks[1..]);
+        dbg!(&warnings);
+        assert_eq!(codes(&warnings), [Code::MissingChunk, Code::InvalidValue]);
+        assert_eq!(warnings[0].id, FmtChunk::ID);
+
+        let mut reordered = chunks.clone();
+        reordered.swap(0, 2);
+        let warnings = Profile::Riff.check(FourCC(*b"RIFF"), &reordered);
+        assert_eq!(codes(&warnings), [Code::ChunkOrder]);
+    }
+
+    #[test]
+    fn ebu_profiles() {
+        let bext = Bext::builder()
+            .originator("Sound Archive")
+            .originator_reference("GBBBC0000000012A1030000123456789")
+            .origination_date("2024-03-01".parse().unwrap())
+            .origination_time("10:30:00".parse().unwrap())
+            .umid(Umid::new([7; 16]).to_bext())
+            .coding_history("A=PCM,F=48000,W=24,M=mono,T=Field recorder, take 2\r\n")
+            .build()
+            .unwrap();
+        let chunks = with_bext(&bext);
+        let riff = FourCC(*b"RIFF");
+        assert!(Profile::EbuR98.check(riff, &chunks).is_empty());
+        assert!(Profile::EbuR99.check(riff, &chunks).is_empty());
+
+        let mut bad = bext.clone();
+        bad.originator = "".parse().unwrap();
+        bad.originator_reference = "GB-BBC-42".parse().unwrap();
+        bad.umid[0] = 0;
+        bad.coding_history = "PCM 48k\nA=PCM,X=1\r\n".to_string();
+        let chunks = with_bext(&bad);
+        let warnings = Profile::EbuR98.check(riff, &chunks);
+        dbg!(&warnings);
+        assert_eq!(
+            codes(&warnings),
+            [
+                Code::MissingField,
+                Code::InvalidValue,
+                Code::InvalidValue,
+                Code::InvalidValue,
+                Code::InvalidValue
+            ]
+        );
+        assert!(warnings[1].message.contains("SMPTE UMID label"));
+        assert!(warnings[4].message.contains("line 2"));
+        let warnings = Profile::EbuR99.check(riff, &chunks);
+        dbg!(&warnings);
+        assert!(warnings
+            .last()
+            .unwrap()
+            .message
+            .contains("should be 32 characters"));
+
+        let mut old = bext;
+        old.version = 0;
+        let warnings = Profile::EbuR99.check(riff, &with_bext(&old));
+        assert_eq!(codes(&warnings), [Code::MissingField]);
+
+        let mut chunks = example_a();
+        chunks.retain(|c| c.id() != Bext::ID);
+        let warnings = Profile::EbuR98.check(riff, &chunks);
+        assert_eq!(codes(&warnings), [Code::MissingChunk]);
+    }
+
+    #[test]
+    fn usid_format() {
+        assert_eq!(usid_problem(b"GBBBC0000000012A1030000123456789"), None);
+        assert!(usid_problem(b"gbBBC0000000012A1030000123456789").is_some());
+        assert!(usid_problem(b"GBBBC0000000012A2530000123456789").is_some());
+        assert!(usid_problem(b"GBBBC0000000012A103000012345678X").is_some());
+        assert!(usid_problem(b"GBBBC-000000012A1030000123456789").is_some());
+    }
+
+    #[test]
+    fn adm_profile() {
+        let warnings = Profile::AdmBw64.check(FourCC(*b"RIFF"), &example_a());
+        dbg!(&warnings);
+        assert_eq!(
+            codes(&warnings),
+            [
+                Code::InvalidValue,
+                Code::MissingChunk,
+                Code::MissingChunk,
+                Code::MissingChunk
+            ]
+        );
+        assert_eq!(warnings[3].id, FourCC(*b"axml"));
+    }
+}
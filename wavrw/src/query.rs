@@ -0,0 +1,449 @@
+//! Filter expressions over chunk items, for selecting files.
+//!
+//! A [`Query`] compares fields, written as `chunk.key`, with values. The
+//! chunk is matched by name, id or list type, ignoring case, so `fmt`,
+//! `LIST-INFO` and `INFO` all work. The key is an item key from
+//! [`Summarizable::items()`], also ignoring case. A chunk name without a key
+//! is true if the chunk exists.
+//!
+//! Operators, from highest to lowest precedence:
+//!
+//! - `( ... )` grouping
+//! - `!` not
+//! - `==`, `!=`, `<`, `<=`, `>`, `>=` compare as numbers when both sides
+//!   are numbers, otherwise as text
+//! - `~` contains, ignoring case
+//! - `&&` and
+//! - `||` or
+//!
+//! Values are numbers, words or "quoted text". A comparison is true if any
+//! value of the field matches, for example any `labl` of a `LIST-adtl`, and
+//! false if the field is missing. `a != b` is the same as `!(a == b)`.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::query::Query;
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//!
+//! let query: Query = r#"fmt.samples_per_sec >= 44100 && bext.originator ~ "bworig""#.parse()?;
+//! assert!(query.matches(wave.chunks()));
+//! let query: Query = "fmt.samples_per_sec != 48000 || !iXML".parse()?;
+//! assert!(!query.matches(wave.chunks()));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use crate::{ChunkID, SizedChunkEnum, Summarizable, WaveFileError};
+
+/// A parsed filter expression, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    text: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Exists(Field),
+    Compare(Field, Op, String),
+}
+
+/// Field of a comparison, `key` is `None` for a chunk name on its own.
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    chunk: String,
+    key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let op = match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Contains => "~",
+        };
+        write!(f, "{op}")
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{word}`"),
+            Token::Text(text) => write!(f, "\"{text}\""),
+            Token::Op(op) => write!(f, "`{op}`"),
+            Token::And => write!(f, "`&&`"),
+            Token::Or => write!(f, "`||`"),
+            Token::Not => write!(f, "`!`"),
+            Token::Open => write!(f, "`(`"),
+            Token::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+/// Description of `token` for error messages.
+fn describe(token: Option<&Token>) -> String {
+    token.map_or("end of query".to_string(), Token::to_string)
+}
+
+impl Query {
+    /// True if the expression matches a file with `chunks`.
+    pub fn matches(&self, chunks: &[SizedChunkEnum]) -> bool {
+        self.expr.matches(chunks)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Query {
+    type Err = WaveFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(query_error(format!("unexpected {token}")));
+        }
+        Ok(Query {
+            text: s.to_owned(),
+            expr,
+        })
+    }
+}
+
+fn query_error(message: impl Display) -> WaveFileError {
+    WaveFileError::parse(None, format!("invalid query: {message}"))
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, WaveFileError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '~' => Token::Op(Op::Contains),
+            '&' | '|' => {
+                if chars.next_if_eq(&c).is_none() {
+                    return Err(query_error(format!("expected `{c}{c}`")));
+                }
+                if c == '&' {
+                    Token::And
+                } else {
+                    Token::Or
+                }
+            }
+            '=' => {
+                if chars.next_if_eq(&'=').is_none() {
+                    return Err(query_error("expected `==`"));
+                }
+                Token::Op(Op::Eq)
+            }
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Le),
+            '<' => Token::Op(Op::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ge),
+            '>' => Token::Op(Op::Gt),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => text.extend(chars.next()),
+                        Some(c) => text.push(c),
+                        None => return Err(query_error("unterminated text")),
+                    }
+                }
+                Token::Text(text)
+            }
+            c if is_word_char(c) => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| is_word_char(*c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            c => return Err(query_error(format!("unexpected `{c}`"))),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || "_-.+:".contains(c)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, WaveFileError> {
+        let mut expr = self.and()?;
+        while self.next_if(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, WaveFileError> {
+        let mut expr = self.unary()?;
+        while self.next_if(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, WaveFileError> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                if !self.next_if(&Token::Close) {
+                    return Err(query_error("expected `)`"));
+                }
+                Ok(expr)
+            }
+            Some(Token::Word(word)) => {
+                let field = match word.split_once('.') {
+                    Some((chunk, key)) => Field {
+                        chunk: chunk.to_string(),
+                        key: Some(key.to_string()),
+                    },
+                    None => Field {
+                        chunk: word,
+                        key: None,
+                    },
+                };
+                let Some(Token::Op(op)) = self.tokens.get(self.pos).cloned() else {
+                    return Ok(Expr::Exists(field));
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Word(value) | Token::Text(value)) => {
+                        Ok(Expr::Compare(field, op, value))
+                    }
+                    other => Err(query_error(format!(
+                        "expected a value, found {}",
+                        describe(other.as_ref())
+                    ))),
+                }
+            }
+            other => Err(query_error(format!(
+                "expected a field, found {}",
+                describe(other.as_ref())
+            ))),
+        }
+    }
+}
+
+impl Field {
+    /// True if `chunk` is the chunk named by this field.
+    fn is_chunk(&self, chunk: &SizedChunkEnum) -> bool {
+        let name = chunk.name();
+        let id = chunk.id().to_string();
+        let list_type = name.split_once('-').map(|(_, list_type)| list_type);
+        name.eq_ignore_ascii_case(&self.chunk)
+            || id.trim_end().eq_ignore_ascii_case(&self.chunk)
+            || list_type.is_some_and(|l| l.eq_ignore_ascii_case(&self.chunk))
+    }
+
+    /// Values of this field in `chunks`.
+    fn values(&self, chunks: &[SizedChunkEnum]) -> Vec<String> {
+        let Some(key) = &self.key else {
+            return Vec::new();
+        };
+        chunks
+            .iter()
+            .filter(|c| self.is_chunk(c))
+            .flat_map(|c| c.items())
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+            .collect()
+    }
+}
+
+impl Op {
+    fn compare(self, value: &str, expected: &str) -> bool {
+        if self == Op::Contains {
+            return value.to_lowercase().contains(&expected.to_lowercase());
+        }
+        let ordering = match (value.trim().parse::<f64>(), expected.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(value.cmp(expected)),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+        match self {
+            Op::Eq => ordering == Ordering::Equal,
+            Op::Ne => ordering != Ordering::Equal,
+            Op::Lt => ordering == Ordering::Less,
+            Op::Le => ordering != Ordering::Greater,
+            Op::Gt => ordering == Ordering::Greater,
+            Op::Ge => ordering != Ordering::Less,
+            Op::Contains => unreachable!("handled above"),
+        }
+    }
+}
+
+impl Expr {
+    fn matches(&self, chunks: &[SizedChunkEnum]) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(chunks) && b.matches(chunks),
+            Expr::Or(a, b) => a.matches(chunks) || b.matches(chunks),
+            Expr::Not(a) => !a.matches(chunks),
+            Expr::Exists(field) if field.key.is_none() => chunks.iter().any(|c| field.is_chunk(c)),
+            Expr::Exists(field) => !field.values(chunks).is_empty(),
+            Expr::Compare(field, Op::Ne, expected) => {
+                !Expr::Compare(field.clone(), Op::Eq, expected.clone()).matches(chunks)
+            }
+            Expr::Compare(field, op, expected) => field
+                .values(chunks)
+                .iter()
+                .any(|value| op.compare(value, expected)),
+        }
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(feature = "broadcast")]
+    use crate::chunk::bext::{Bext, BextChunk};
+    #[cfg(feature = "broadcast")]
+    use crate::chunk::fact::{Fact, FactChunk};
+
+    #[cfg(feature = "broadcast")]
+    fn chunks() -> Vec<SizedChunkEnum> {
+        let bext = Bext {
+            originator: "Zoom F8n".parse().unwrap(),
+            version: 1,
+            ..Default::default()
+        };
+        vec![
+            SizedChunkEnum::Fact(FactChunk::new(Fact { samples: 480 }).unwrap()),
+            SizedChunkEnum::Bext(Box::new(BextChunk::new(bext).unwrap())),
+        ]
+    }
+
+    #[cfg(feature = "broadcast")]
+    fn matches(query: &str) -> bool {
+        let query: Query = query.parse().unwrap();
+        dbg!(&query);
+        query.matches(&chunks())
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn compare() {
+        assert!(matches(r#"bext.originator ~ "zoom""#));
+        assert!(matches("bext.originator == \"Zoom F8n\""));
+        assert!(matches("bext.originator != Sound"));
+        assert!(!matches("bext.originator ~ Sound"));
+        assert!(matches("BEXT.Originator ~ f8"));
+        assert!(matches("bext.version >= 1 && bext.version < 2"));
+        assert!(!matches("bext.version > 1"));
+        // numbers compare as numbers, not text
+        assert!(matches("bext.version < 10"));
+        // missing fields never match, but != does
+        assert!(!matches("fmt.samples_per_sec == 48000"));
+        assert!(matches("fmt.samples_per_sec != 48000"));
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn logic() {
+        assert!(matches("bext && !fmt"));
+        assert!(matches("bext.originator"));
+        assert!(!matches("bext.missing_key"));
+        assert!(matches("fmt || bext"));
+        assert!(!matches("!(fmt || bext)"));
+        // && binds tighter than ||
+        assert!(matches("bext || fmt && iXML"));
+        assert!(!matches("(bext || fmt) && iXML"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        for query in [
+            "",
+            "bext.version =",
+            "bext.version = 1",
+            "bext.originator ~ \"zoom",
+            "(bext",
+            "bext)",
+            "bext & fmt",
+            "== 1",
+        ] {
+            let err = query.parse::<Query>().unwrap_err();
+            dbg!(query, &err);
+            assert!(err.to_string().contains("invalid query"));
+        }
+    }
+}
@@ -0,0 +1,500 @@
+//! Parsers for chunks wavrw doesn't know, registered by downstream crates.
+//!
+//! Built in chunks are parsed into [`SizedChunkEnum`] variants. Any other
+//! chunk becomes [`SizedChunkEnum::Unknown`], holding the raw bytes. A
+//! [`ChunkRegistry`] maps chunk ids to [`ChunkParser`] functions which turn
+//! those unknown chunks into custom types, for example to support
+//! proprietary chunks.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::registry::ChunkRegistry;
+//! use wavrw::{ChunkID, FourCC, SizedChunk, Summarizable, UnknownChunk, WaveFileError};
+//!
+//! /// Adobe XMP `_PMX` chunk, only the size is known.
+//! #[derive(Debug)]
+//! struct Xmp {
+//!     offset: Option<u64>,
+//!     size: u32,
+//! }
+//!
+//! impl ChunkID for Xmp {
+//!     fn id(&self) -> FourCC {
+//!         FourCC(*b"_PMX")
+//!     }
+//! }
+//!
+//! impl Summarizable for Xmp {
+//!     fn summary(&self) -> String {
+//!         "XMP metadata".to_string()
+//!     }
+//! }
+//!
+//! impl SizedChunk for Xmp {
+//!     fn size(&self) -> u32 {
+//!         self.size
+//!     }
+//!
+//!     fn offset(&self) -> Option<u64> {
+//!         self.offset
+//!     }
+//! }
+//!
+//! fn parse_xmp(chunk: &UnknownChunk) -> Result<Box<dyn SizedChunk>, WaveFileError> {
+//!     Ok(Box::new(Xmp {
+//!         offset: chunk.offset,
+//!         size: chunk.size,
+//!     }))
+//! }
+//!
+//! let mut registry = ChunkRegistry::new();
+//! registry.register(FourCC(*b"_PMX"), parse_xmp);
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let summaries: Vec<String> = registry
+//!     .iter_chunks(&mut wave)
+//!     .map(|chunk| chunk.map(|c| c.summary()))
+//!     .collect::<Result<_, _>>()?;
+//! assert!(summaries.contains(&"XMP metadata".to_string()));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+//!
+//! [`KNOWN`] lists chunk ids found in the wild, including ones wavrw doesn't
+//! parse, with a name, description and link to the specification. Summaries
+//! of unknown chunks use it to name the chunk.
+//!
+//! ```
+//! use wavrw::registry;
+//! use wavrw::FourCC;
+//!
+//! let cart = registry::known(FourCC(*b"cart")).unwrap();
+//! assert_eq!(cart.name, "AES46 CartChunk");
+//! assert!(!cart.parsed);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::io::{BufRead, Read, Seek};
+
+#[cfg(feature = "std")]
+use crate::WaveFile;
+use crate::{FourCC, SizedChunk, SizedChunkEnum, UnknownChunk, WaveFileError};
+
+/// Parse the raw bytes of an unknown chunk into a custom chunk type.
+///
+/// [`UnknownChunk::raw`] holds the chunk data without the id and size.
+pub type ChunkParser = fn(&UnknownChunk) -> Result<Box<dyn SizedChunk>, WaveFileError>;
+
+/// Chunk parsers for ids wavrw doesn't parse itself. See the
+/// [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct ChunkRegistry {
+    parsers: Vec<(FourCC, ChunkParser)>,
+}
+
+impl ChunkRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse chunks with `id` using `parser`, replacing any parser already
+    /// registered for `id`.
+    ///
+    /// Only chunks wavrw doesn't parse are passed to registered parsers.
+    /// Registering a built in id, ex: `fmt `, has no effect.
+    pub fn register(&mut self, id: FourCC, parser: ChunkParser) -> &mut Self {
+        self.parsers.retain(|(i, _)| *i != id);
+        self.parsers.push((id, parser));
+        self
+    }
+
+    /// Parser registered for `id`.
+    pub fn get(&self, id: FourCC) -> Option<ChunkParser> {
+        self.parsers
+            .iter()
+            .find_map(|(i, parser)| (*i == id).then_some(*parser))
+    }
+
+    /// Ids with a registered parser, in registration order.
+    pub fn ids(&self) -> impl Iterator<Item = FourCC> + '_ {
+        self.parsers.iter().map(|(id, _)| *id)
+    }
+
+    /// Parse `chunk` with the registered parser if it is an unknown chunk
+    /// with a registered id, otherwise box it unchanged. Unregistered
+    /// unknown chunks stay [`SizedChunkEnum::Unknown`].
+    pub fn parse(&self, chunk: SizedChunkEnum) -> Result<Box<dyn SizedChunk>, WaveFileError> {
+        if let SizedChunkEnum::Unknown(unknown) = &chunk {
+            if let Some(parser) = self.get(unknown.id) {
+                return parser(unknown);
+            }
+        }
+        Ok(Box::new(chunk))
+    }
+
+    /// Iterate over the chunks of `wave` like [`WaveFile::iter_chunks()`],
+    /// with chunks parsed by registered parsers where available.
+    #[cfg(feature = "std")]
+    pub fn iter_chunks<'a, R>(
+        &'a self,
+        wave: &'a mut WaveFile<R>,
+    ) -> impl Iterator<Item = Result<Box<dyn SizedChunk>, WaveFileError>> + 'a
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        wave.iter_chunks()
+            .map(|result| result.and_then(|chunk| self.parse(chunk)))
+    }
+}
+
+/// Name, description and specification of a chunk id. See [`KNOWN`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KnownFourCC {
+    /// RIFF chunk id.
+    pub id: FourCC,
+    /// Short name. Ex: `AES46 CartChunk`
+    pub name: &'static str,
+    /// One line description.
+    pub description: &'static str,
+    /// Specification or documentation of the chunk.
+    pub url: &'static str,
+    /// True if wavrw, with the enabled cargo features, parses the chunk.
+    pub parsed: bool,
+}
+
+impl KnownFourCC {
+    const fn new(
+        id: &[u8; 4],
+        name: &'static str,
+        description: &'static str,
+        url: &'static str,
+        parsed: bool,
+    ) -> Self {
+        KnownFourCC {
+            id: FourCC(*id),
+            name,
+            description,
+            url,
+            parsed,
+        }
+    }
+}
+
+/// Every chunk id known to wavrw, parsed or not, in alphabetical order.
+///
+/// Parsed chunks are described in more detail by [`crate::chunk::SUPPORTED`].
+pub const KNOWN: &[KnownFourCC] = &[
+    KnownFourCC::new(
+        b"_PMX",
+        "Adobe XMP",
+        "Extensible Metadata Platform packet, RDF/XML.",
+        "https://www.adobe.com/devnet/xmp.html",
+        false,
+    ),
+    KnownFourCC::new(
+        b"axml",
+        "EBU axml",
+        "XML metadata, usually the Audio Definition Model (ADM).",
+        "https://tech.ebu.ch/docs/tech/tech3285s5.pdf",
+        false,
+    ),
+    KnownFourCC::new(
+        b"bext",
+        "Broadcast Extension",
+        "Broadcast Extension for motion picture, radio and television production.",
+        "https://wavref.til.cafe/spec/bext1996/",
+        cfg!(feature = "broadcast"),
+    ),
+    KnownFourCC::new(
+        b"bxml",
+        "BW64 bxml",
+        "Gzip compressed XML metadata.",
+        "https://www.itu.int/rec/R-REC-BS.2088/en",
+        false,
+    ),
+    KnownFourCC::new(
+        b"cart",
+        "AES46 CartChunk",
+        "Radio traffic data: title, artist, cue id, timers and segue points.",
+        "https://www.aes.org/publications/standards/search.cfm?docID=41",
+        false,
+    ),
+    KnownFourCC::new(
+        b"chna",
+        "BW64 chna",
+        "Channel allocation, maps tracks to ADM track and pack ids in axml.",
+        "https://www.itu.int/rec/R-REC-BS.2088/en",
+        false,
+    ),
+    KnownFourCC::new(
+        b"CSET",
+        "Character Set",
+        "Character set information. Code page, language, etc. Very Rare.",
+        "https://wavref.til.cafe/chunk/cset/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"cue ",
+        "Cue Points",
+        "A series of positions in the waveform data chunk.",
+        "https://wavref.til.cafe/chunk/cue/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"data",
+        "Data",
+        "Audio samples.",
+        "https://wavref.til.cafe/spec/riff1991/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"dbmd",
+        "Dolby Metadata",
+        "Dolby audio metadata for Dolby E, Digital and Digital Plus.",
+        "https://tech.ebu.ch/docs/tech/tech3285s6.pdf",
+        false,
+    ),
+    KnownFourCC::new(
+        b"DISP",
+        "Display",
+        "Data to display the file, usually a title. Rare.",
+        "https://wavref.til.cafe/spec/riff1991/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"ds64",
+        "RF64 Sizes",
+        "64 bit sizes of the RIFF, data and other chunks in RF64 files.",
+        "https://tech.ebu.ch/docs/tech/tech3306-2009.pdf",
+        true,
+    ),
+    KnownFourCC::new(
+        b"fact",
+        "Fact",
+        "Number of samples for compressed audio in data.",
+        "https://wavref.til.cafe/chunk/fact/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"FLLR",
+        "Filler",
+        "Padding, filler or outdated information.",
+        "https://wavref.til.cafe/chunk/fllr/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"fmt ",
+        "Format",
+        "Format of audio samples in data.",
+        "https://wavref.til.cafe/chunk/fmt/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"id3 ",
+        "ID3",
+        "ID3v2 tag, common in files from music software.",
+        "https://id3.org/",
+        false,
+    ),
+    KnownFourCC::new(
+        b"inst",
+        "Instrument",
+        "Pitch, volume, and velocity for playback by sampler.",
+        "https://wavref.til.cafe/chunk/inst/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"iXML",
+        "iXML",
+        "Production workflow file & project metadata.",
+        "https://wavref.til.cafe/spec/ixml2021/",
+        cfg!(feature = "ixml"),
+    ),
+    KnownFourCC::new(
+        b"JUNK",
+        "Junk",
+        "Padding, filler or outdated information.",
+        "https://wavref.til.cafe/chunk/junk/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"levl",
+        "EBU Peak Envelope",
+        "Peak envelope, a waveform overview of the audio.",
+        "https://tech.ebu.ch/docs/tech/tech3285s3.pdf",
+        true,
+    ),
+    KnownFourCC::new(
+        b"LIST",
+        "List",
+        "A list of sub-chunks, identified by a list type. Ex: INFO",
+        "https://wavref.til.cafe/spec/riff1991/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"MD5 ",
+        "MD5",
+        "Checksum of audio data of the WAVE.",
+        "https://wavref.til.cafe/chunk/md5/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"mext",
+        "EBU MPEG Extension",
+        "MPEG audio extension: frame size, ancillary data and flags.",
+        "https://tech.ebu.ch/docs/tech/tech3285s1.pdf",
+        false,
+    ),
+    KnownFourCC::new(
+        b"PAD ",
+        "Padding",
+        "Padding, filler or outdated information.",
+        "https://wavref.til.cafe/chunk/pad/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"plst",
+        "Playlist",
+        "Play order for cue points. Very rare.",
+        "https://wavref.til.cafe/chunk/plst/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"RIFF",
+        "RIFF",
+        "Container structure for multimedia data.",
+        "https://wavref.til.cafe/chunk/wave/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"SMED",
+        "Soundminer SMED",
+        "Soundminer metadata, proprietary. Header and readable text only.",
+        "https://soundminer.com/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"smpl",
+        "Sampler",
+        "Information needed for use as a sampling instrument.",
+        "https://wavref.til.cafe/chunk/smpl/",
+        true,
+    ),
+    KnownFourCC::new(
+        b"SMRD",
+        "Soundminer SMRD",
+        "Soundminer metadata, proprietary. Header and readable text only.",
+        "https://soundminer.com/",
+        true,
+    ),
+];
+
+/// Look up `id` in [`KNOWN`].
+pub fn known(id: FourCC) -> Option<&'static KnownFourCC> {
+    KNOWN.iter().find(|k| k.id == id)
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::chunk::fact::Fact;
+    use crate::{KnownChunk, KnownChunkID, Summarizable};
+
+    fn parse_as_fact(chunk: &UnknownChunk) -> Result<Box<dyn SizedChunk>, WaveFileError> {
+        let [a, b, c, d] = chunk.raw[..] else {
+            return Err(WaveFileError::parse(
+                chunk.offset,
+                "expected 4 bytes".to_string(),
+            ));
+        };
+        let fact = KnownChunk::new(Fact {
+            samples: u32::from_le_bytes([a, b, c, d]),
+        })?;
+        Ok(Box::new(fact))
+    }
+
+    #[test]
+    fn registry_parse() {
+        let mut registry = ChunkRegistry::new();
+        registry.register(FourCC(*b"zzzz"), parse_as_fact);
+        assert!(registry.get(FourCC(*b"zzzz")).is_some());
+        assert!(registry.get(FourCC(*b"yyyy")).is_none());
+
+        let unknown = UnknownChunk {
+            id: FourCC(*b"zzzz"),
+            size: 4,
+            raw: vec![1, 0, 0, 0],
+            offset: Some(12),
+            large_size: None,
+            padding: None,
+        };
+        let chunk = registry.parse(SizedChunkEnum::Unknown(unknown)).unwrap();
+        dbg!(&chunk);
+        assert_eq!(chunk.id(), Fact::ID);
+        assert_eq!(chunk.summary(), "1 samples");
+
+        // unregistered and built in chunks are boxed unchanged
+        let unknown = UnknownChunk {
+            id: FourCC(*b"yyyy"),
+            ..Default::default()
+        };
+        let chunk = registry.parse(SizedChunkEnum::Unknown(unknown)).unwrap();
+        assert_eq!(chunk.id(), FourCC(*b"yyyy"));
+
+        let bad = UnknownChunk {
+            id: FourCC(*b"zzzz"),
+            size: 2,
+            raw: vec![1, 0],
+            offset: None,
+            large_size: None,
+            padding: None,
+        };
+        assert!(registry.parse(SizedChunkEnum::Unknown(bad)).is_err());
+
+        // registering again replaces the parser
+        registry.register(FourCC(*b"zzzz"), parse_as_fact);
+        assert_eq!(registry.ids().count(), 1);
+    }
+
+    #[test]
+    fn known_fourccs() {
+        let cart = known(FourCC(*b"cart")).unwrap();
+        dbg!(cart);
+        assert!(!cart.parsed);
+        assert!(known(FourCC(*b"zzzz")).is_none());
+
+        // every top level chunk wavrw parses is known
+        for supported in crate::chunk::SUPPORTED
+            .iter()
+            .filter(|c| c.parent.is_none())
+        {
+            let k = known(supported.id).unwrap();
+            assert!(k.parsed, "{}", k.id);
+        }
+
+        // ids are unique
+        for (i, k) in KNOWN.iter().enumerate() {
+            assert!(KNOWN[..i].iter().all(|other| other.id != k.id), "{}", k.id);
+        }
+
+        let unknown = UnknownChunk {
+            id: FourCC(*b"cart"),
+            ..Default::default()
+        };
+        assert_eq!(unknown.summary(), "cart (AES46 CartChunk, unparsed)");
+        let unknown = UnknownChunk {
+            id: FourCC(*b"yyyy"),
+            ..Default::default()
+        };
+        assert_eq!(unknown.summary(), "...");
+    }
+}
@@ -0,0 +1,394 @@
+//! Owned summary of a whole file as plain data.
+//!
+//! Chunk structs mirror the binary layout and need the traits in this crate
+//! to display. A [`Report`] holds the same information as strings and
+//! numbers, ready to hand to other code, like a user interface.
+//! [`Report::from_bytes()`] works on a file which is already in memory, so
+//! no file system is needed.
+//!
+//! ```
+//! let bytes = std::fs::read("../test_wavs/example_a.wav")?;
+//! let report = wavrw::report::Report::from_bytes(&bytes)?;
+//!
+//! assert_eq!(report.sample_rate, Some(48000));
+//! assert_eq!(report.chunks[0].name, "fmt");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`ProblemReport`] lists only the errors and warnings of a file, each with
+//! a stable [`Code`], and [`problems_to_json()`] writes them for conformance
+//! checks in delivery pipelines.
+
+use core::fmt::Debug;
+use std::io::{BufRead, Cursor};
+
+use binrw::io::{Read, Seek};
+
+use crate::json::Value;
+use crate::warning::Code;
+use crate::{
+    resolve_references, FourCC, ParseOptions, ResolvedWave, SizedChunk, SizedChunkEnum,
+    Summarizable, Warning, WaveFile, WaveFileError,
+};
+
+/// Summary of a file: format, chunks and problems found while parsing.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
+pub struct Report {
+    /// Sample rate in samples per second, from `fmt `.
+    pub sample_rate: Option<u32>,
+
+    /// Number of channels, from `fmt `.
+    pub channels: Option<u16>,
+
+    /// Bits per sample, from `fmt `.
+    pub bit_depth: Option<u16>,
+
+    /// Length of the audio in samples (per channel).
+    pub duration_samples: Option<u64>,
+
+    /// Length of the audio in seconds.
+    pub duration_seconds: Option<f64>,
+
+    /// Successfully parsed chunks, in file order.
+    pub chunks: Vec<ChunkReport>,
+
+    /// Error which stopped parsing before the end of the file.
+    pub error: Option<String>,
+
+    /// Warnings from parsing and consistency checks, see
+    /// [`ResolvedWave::validate()`].
+    pub warnings: Vec<String>,
+}
+
+/// Summary of one chunk, see [`Report`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct ChunkReport {
+    /// Byte offset of the chunk from the beginning of the file.
+    pub offset: Option<u64>,
+
+    /// Chunk name, see [`Summarizable::name()`]. Ex: `LIST-INFO`
+    pub name: String,
+
+    /// Chunk size in bytes.
+    pub size: u32,
+
+    /// One line summary of the chunk contents.
+    pub summary: String,
+
+    /// Header for the item values, see [`Summarizable::item_summary_header()`].
+    pub item_summary_header: String,
+
+    /// Chunk fields as (name, value) pairs.
+    pub items: Vec<(String, String)>,
+}
+
+impl ChunkReport {
+    /// Summarize `chunk`.
+    pub fn new(chunk: &SizedChunkEnum) -> Self {
+        ChunkReport {
+            offset: chunk.offset(),
+            name: chunk.name(),
+            size: chunk.size(),
+            summary: chunk.summary(),
+            item_summary_header: chunk.item_summary_header(),
+            items: chunk.items().collect(),
+        }
+    }
+}
+
+impl Report {
+    /// Parse a file from `bytes` with default options.
+    ///
+    /// Returns an error if the RIFF header can't be parsed. Errors while
+    /// parsing chunks are kept in [`Report::error`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WaveFileError> {
+        Self::from_bytes_with_options(bytes, ParseOptions::default())
+    }
+
+    /// Parse a file from `bytes`, according to `options`.
+    pub fn from_bytes_with_options(
+        bytes: &[u8],
+        options: ParseOptions,
+    ) -> Result<Self, WaveFileError> {
+        let mut wave = WaveFile::from_reader_with_options(Cursor::new(bytes), options)?;
+        Ok(Self::from_wave(&mut wave))
+    }
+
+    /// Parse all chunks of `wave`.
+    pub fn from_wave<R>(wave: &mut WaveFile<R>) -> Self
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let mut chunks = Vec::new();
+        let mut error = None;
+        for result in wave.iter_chunks() {
+            match result {
+                Ok(chunk) => chunks.push(chunk),
+                Err(err) => error = Some(err.to_string()),
+            }
+        }
+        resolve_references(&mut chunks);
+
+        let resolved = ResolvedWave::new(&chunks);
+        let metadata = resolved.metadata();
+        let warnings = wave
+            .warnings()
+            .iter()
+            .chain(&resolved.validate())
+            .map(ToString::to_string)
+            .collect();
+        Report {
+            sample_rate: metadata.sample_rate(),
+            channels: metadata.channels(),
+            bit_depth: metadata.bit_depth(),
+            duration_samples: metadata.duration_samples(),
+            duration_seconds: metadata.duration_seconds(),
+            chunks: chunks.iter().map(ChunkReport::new).collect(),
+            error,
+            warnings,
+        }
+    }
+}
+
+/// Version of the document written by [`problems_to_json()`]. Increased
+/// when fields are removed or change meaning, not when fields are added.
+pub const PROBLEMS_JSON_VERSION: u32 = 1;
+
+/// An error or warning, see [`ProblemReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Problem {
+    /// Kind of problem.
+    pub code: Code,
+
+    /// Id of the chunk the problem is about, when known.
+    pub id: Option<FourCC>,
+
+    /// Byte offset of the chunk, when known.
+    pub offset: Option<u64>,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl From<&Warning> for Problem {
+    fn from(warning: &Warning) -> Self {
+        Problem {
+            code: warning.code,
+            id: Some(warning.id),
+            offset: warning.offset,
+            message: warning.message.clone(),
+        }
+    }
+}
+
+impl From<&WaveFileError> for Problem {
+    fn from(err: &WaveFileError) -> Self {
+        let (id, offset) = err.chunk();
+        Problem {
+            code: err.code(),
+            id,
+            offset,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl Problem {
+    fn to_value(&self) -> Value {
+        let id = self
+            .id
+            .map_or(Value::Null, |id| Value::String(id.to_string()));
+        let offset = self
+            .offset
+            .map_or(Value::Null, |offset| Value::Number(offset.to_string()));
+        Value::Object(vec![
+            ("code".to_string(), Value::String(self.code.to_string())),
+            ("chunk_id".to_string(), id),
+            ("offset".to_string(), offset),
+            ("message".to_string(), Value::String(self.message.clone())),
+        ])
+    }
+}
+
+/// Errors and warnings of one file, for machine readable output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct ProblemReport {
+    /// Path or name of the file.
+    pub path: String,
+
+    /// Errors which stopped parsing the file or a chunk.
+    pub errors: Vec<Problem>,
+
+    /// Warnings from parsing and consistency checks, see
+    /// [`ResolvedWave::validate()`].
+    pub warnings: Vec<Problem>,
+}
+
+impl ProblemReport {
+    /// Parse all chunks of `wave` and collect the problems found.
+    ///
+    /// ```
+    /// # use std::fs::File;
+    /// # use std::io::BufReader;
+    /// use wavrw::report::{problems_to_json, ProblemReport};
+    ///
+    /// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+    /// let mut wave = wavrw::WaveFile::from_reader(file)?;
+    /// let report = ProblemReport::from_wave("example_a.wav", &mut wave);
+    /// assert!(report.errors.is_empty());
+    /// assert!(problems_to_json(&[report]).contains(r#""path": "example_a.wav""#));
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn from_wave<R>(path: impl Into<String>, wave: &mut WaveFile<R>) -> Self
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let mut chunks = Vec::new();
+        let mut errors = Vec::new();
+        for result in wave.iter_chunks() {
+            match result {
+                Ok(chunk) => chunks.push(chunk),
+                Err(err) => errors.push(Problem::from(&err)),
+            }
+        }
+        resolve_references(&mut chunks);
+
+        let resolved = ResolvedWave::new(&chunks);
+        let warnings = wave
+            .warnings()
+            .iter()
+            .chain(&resolved.validate())
+            .map(Problem::from)
+            .collect();
+        ProblemReport {
+            path: path.into(),
+            errors,
+            warnings,
+        }
+    }
+
+    /// Report a file which couldn't be opened or parsed at all.
+    pub fn from_error(path: impl Into<String>, err: &WaveFileError) -> Self {
+        ProblemReport {
+            path: path.into(),
+            errors: vec![Problem::from(err)],
+            warnings: Vec::new(),
+        }
+    }
+
+    /// True if there are no errors and no warnings.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// Write `reports` as a JSON document.
+///
+/// The document is an object with the `version` of the format, see
+/// [`PROBLEMS_JSON_VERSION`], and `files`, an array with the `path`,
+/// `errors` and `warnings` of each file. Each error and warning has a
+/// `code` (see [`Code::as_str()`]), `chunk_id`, `offset` and `message`.
+pub fn problems_to_json(reports: &[ProblemReport]) -> String {
+    let files = reports
+        .iter()
+        .map(|report| {
+            Value::Object(vec![
+                ("path".to_string(), Value::String(report.path.clone())),
+                (
+                    "errors".to_string(),
+                    Value::Array(report.errors.iter().map(Problem::to_value).collect()),
+                ),
+                (
+                    "warnings".to_string(),
+                    Value::Array(report.warnings.iter().map(Problem::to_value).collect()),
+                ),
+            ])
+        })
+        .collect();
+    Value::Object(vec![
+        (
+            "version".to_string(),
+            Value::Number(PROBLEMS_JSON_VERSION.to_string()),
+        ),
+        ("files".to_string(), Value::Array(files)),
+    ])
+    .to_json()
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn report_example_a() {
+        let bytes = std::fs::read("../test_wavs/example_a.wav").unwrap();
+        let report = Report::from_bytes(&bytes).unwrap();
+        dbg!(&report);
+        assert_eq!(report.channels, Some(1));
+        assert_eq!(report.duration_samples, Some(480));
+        assert_eq!(report.chunks.len(), 12);
+        assert_eq!(report.chunks[6].name, "LIST-INFO");
+        assert!(report.chunks[6]
+            .items
+            .contains(&("ISFT".to_string(), "Soundminer".to_string())));
+        assert_eq!(report.error, None);
+        assert!(report.warnings.is_empty());
+
+        assert!(Report::from_bytes(b"RIFF").is_err());
+    }
+
+    #[test]
+    fn problems_json() {
+        // odd sized JUNK chunk without padding byte, followed by fact
+        let data = "52494646 19000000 57415645 4A554E4B 01000000 00 66616374 04000000 E0010000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let report = ProblemReport::from_wave("odd.wav", &mut wave);
+        dbg!(&report);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings[0].code, Code::MissingPadding);
+        assert_eq!(report.warnings[0].id, Some(FourCC(*b"JUNK")));
+        assert_eq!(report.warnings[0].offset, Some(12));
+
+        let err = WaveFileError::parse(Some(4), "bad").with_chunk(FourCC(*b"bext"), 36);
+        let failed = ProblemReport::from_error("bad.wav", &err);
+        assert_eq!(failed.errors[0].code, Code::Parse);
+        assert!(!failed.is_clean());
+
+        let json = problems_to_json(&[report, failed]);
+        dbg!(&json);
+        let value = Value::parse(&json).unwrap();
+        assert_eq!(value.get("version").and_then(Value::as_text).unwrap(), "1");
+        let Some(Value::Array(files)) = value.get("files") else {
+            panic!("files missing");
+        };
+        let Some(Value::Array(warnings)) = files[0].get("warnings") else {
+            panic!("warnings missing");
+        };
+        let warning = &warnings[0];
+        assert_eq!(
+            warning.get("code").and_then(Value::as_text).unwrap(),
+            "missing-padding"
+        );
+        assert_eq!(
+            warning.get("chunk_id").and_then(Value::as_text).unwrap(),
+            "JUNK"
+        );
+        assert_eq!(
+            warning.get("offset").and_then(Value::as_text).unwrap(),
+            "12"
+        );
+        let Some(Value::Array(errors)) = files[1].get("errors") else {
+            panic!("errors missing");
+        };
+        assert_eq!(
+            errors[0].get("chunk_id").and_then(Value::as_text).unwrap(),
+            "bext"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Relationships between chunks: cue points and their annotations, playlist
+//! segments and their cue points, `fact` and `fmt `, `bext` and its `iXML`
+//! copy.
+//!
+//! Most chunks only make full sense together with others from the same file.
+//! A `plst` segment refers to a cue point by name, the cue point's label is
+//! in `LIST-adtl`, and a `fact` sample count is only meaningful next to the
+//! `fmt ` and `data` chunks. [`ResolvedWave`] wires these up once, so callers
+//! don't have to.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let resolved = wave.resolve();
+//!
+//! for cue in resolved.cues() {
+//!     println!("{} {:?}", cue.point.position, cue.label);
+//! }
+//! assert!(resolved.validate().is_empty());
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::chunk::adtl::{AdtlEnum, Ltxt};
+use crate::chunk::cue::CuePoint;
+use crate::chunk::fmt::Tag;
+use crate::chunk::plst::{Plst, PlstSegment};
+use crate::chunk::smpl::SmplLoop;
+use crate::metadata::is_uncompressed;
+use crate::warning::Code;
+use crate::{ChunkID, KnownChunkID, SizedChunkEnum, Warning, WaveMetadata};
+
+/// A cue point with its `LIST-adtl` annotations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedCue<'a> {
+    /// The cue point from the `cue ` chunk.
+    pub point: &'a CuePoint,
+    /// Text of the `labl` chunk with the same name.
+    pub label: Option<&'a str>,
+    /// Text of the `note` chunk with the same name.
+    pub note: Option<&'a str>,
+    /// The `ltxt` chunk with the same name.
+    pub ltxt: Option<&'a Ltxt>,
+}
+
+/// A `plst` segment with the cue point it refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedSegment<'a> {
+    /// The segment from the `plst` chunk.
+    pub segment: &'a PlstSegment,
+    /// The cue point named by the segment, `None` if missing.
+    pub cue: Option<&'a CuePoint>,
+    /// Text of the `labl` chunk for the cue point.
+    pub label: Option<&'a str>,
+}
+
+/// Parsed chunks with the references between them resolved, see
+/// [`WaveFile::resolve()`](crate::WaveFile::resolve).
+///
+/// When chunks which should appear once are duplicated, the first one is
+/// used, except for annotations where the last one for a name wins.
+#[derive(Debug, Clone)]
+pub struct ResolvedWave<'a> {
+    chunks: &'a [SizedChunkEnum],
+    cue_points: Vec<&'a CuePoint>,
+    labels: BTreeMap<u32, &'a str>,
+    notes: BTreeMap<u32, &'a str>,
+    ltxts: BTreeMap<u32, &'a Ltxt>,
+}
+
+impl<'a> ResolvedWave<'a> {
+    /// Resolve references between `chunks`.
+    pub fn new(chunks: &'a [SizedChunkEnum]) -> Self {
+        let mut resolved = ResolvedWave {
+            chunks,
+            cue_points: Vec::new(),
+            labels: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            ltxts: BTreeMap::new(),
+        };
+        for chunk in chunks {
+            if let SizedChunkEnum::Cue(cue) = chunk {
+                resolved.cue_points.extend(&cue.data.points);
+            } else if let SizedChunkEnum::Adtl(adtl) = chunk {
+                for sub in &adtl.data.chunks {
+                    match sub {
+                        AdtlEnum::Labl(labl) => {
+                            resolved.labels.insert(labl.data.name, &labl.data.text);
+                        }
+                        AdtlEnum::Note(note) => {
+                            resolved.notes.insert(note.data.name, &note.data.text);
+                        }
+                        AdtlEnum::Ltxt(ltxt) => {
+                            resolved.ltxts.insert(ltxt.data.name, &ltxt.data);
+                        }
+                        AdtlEnum::File(_) | AdtlEnum::Unknown { .. } => (),
+                    }
+                }
+            }
+        }
+        resolved
+    }
+
+    /// The chunks this was resolved from.
+    pub fn chunks(&self) -> &'a [SizedChunkEnum] {
+        self.chunks
+    }
+
+    /// Typed getters for common metadata, see [`WaveMetadata`].
+    pub fn metadata(&self) -> WaveMetadata<'a> {
+        WaveMetadata::new(self.chunks)
+    }
+
+    /// The cue point named `name`.
+    pub fn cue(&self, name: u32) -> Option<&'a CuePoint> {
+        self.cue_points.iter().copied().find(|p| p.name == name)
+    }
+
+    /// Text of the `labl` chunk for cue point or loop `name`.
+    pub fn label(&self, name: u32) -> Option<&'a str> {
+        self.labels.get(&name).copied()
+    }
+
+    /// Text of the `note` chunk for cue point or loop `name`.
+    pub fn note(&self, name: u32) -> Option<&'a str> {
+        self.notes.get(&name).copied()
+    }
+
+    /// The `ltxt` chunk for cue point or loop `name`.
+    pub fn ltxt(&self, name: u32) -> Option<&'a Ltxt> {
+        self.ltxts.get(&name).copied()
+    }
+
+    /// All cue points with their annotations, in file order.
+    pub fn cues(&self) -> Vec<ResolvedCue<'a>> {
+        self.cue_points
+            .iter()
+            .map(|point| ResolvedCue {
+                point,
+                label: self.label(point.name),
+                note: self.note(point.name),
+                ltxt: self.ltxt(point.name),
+            })
+            .collect()
+    }
+
+    /// Names of `labl`, `note` and `ltxt` annotations which belong to
+    /// neither a cue point nor a `smpl` loop, in ascending order.
+    ///
+    /// Editors sometimes delete cue points but leave their annotations.
+    pub fn orphans(&self) -> Vec<u32> {
+        let loops: BTreeSet<u32> = self.sample_loops().iter().map(|l| l.identifier).collect();
+        let annotated: BTreeSet<u32> = self
+            .labels
+            .keys()
+            .chain(self.notes.keys())
+            .chain(self.ltxts.keys())
+            .copied()
+            .collect();
+        annotated
+            .into_iter()
+            .filter(|name| self.cue(*name).is_none() && !loops.contains(name))
+            .collect()
+    }
+
+    /// All `smpl` loops, in file order.
+    pub fn sample_loops(&self) -> Vec<&'a SmplLoop> {
+        self.chunks
+            .iter()
+            .filter_map(|c| {
+                if let SizedChunkEnum::Smpl(smpl) = c {
+                    Some(&smpl.data.sample_loops)
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Segments of the first `plst` chunk with their cue points, in play order.
+    pub fn playlist(&self) -> Vec<ResolvedSegment<'a>> {
+        let Some(plst) = self.plst() else {
+            return Vec::new();
+        };
+        plst.segments
+            .iter()
+            .map(|segment| ResolvedSegment {
+                segment,
+                cue: self.cue(segment.name),
+                label: self.label(segment.name),
+            })
+            .collect()
+    }
+
+    fn plst(&self) -> Option<&'a Plst> {
+        self.chunks.iter().find_map(|c| {
+            if let SizedChunkEnum::Plst(plst) = c {
+                Some(&plst.data)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Whether `fact` samples match the length of `data` as described by
+    /// `fmt `.
+    ///
+    /// `None` if there is no `fact` chunk, or for compressed formats where
+    /// the length can't be calculated from `fmt `.
+    pub fn fact_matches_fmt(&self) -> Option<bool> {
+        let metadata = self.metadata();
+        let fact_samples = metadata.fact_samples()?;
+        if !is_uncompressed(metadata.fmt()?.format_tag()) {
+            return None;
+        }
+        Some(metadata.frame_count()? == u64::from(fact_samples))
+    }
+
+    /// References to cue points which don't exist.
+    ///
+    /// Checks `plst` segments and `LIST-adtl` annotations. Annotations may
+    /// also refer to `smpl` loops.
+    pub fn dangling_references(&self) -> Vec<Warning> {
+        let names: BTreeSet<u32> = self.cue_points.iter().map(|p| p.name).collect();
+        let loops: BTreeSet<u32> = self.sample_loops().iter().map(|l| l.identifier).collect();
+        let mut warnings = Vec::new();
+        for resolved in self.playlist() {
+            if resolved.cue.is_none() {
+                warnings.push(
+                    Warning::new(
+                        Plst::ID,
+                        None,
+                        format!(
+                            "segment refers to missing cue point {}",
+                            resolved.segment.name
+                        ),
+                    )
+                    .with_code(Code::MissingCuePoint),
+                );
+            }
+        }
+        let annotated = self
+            .chunks
+            .iter()
+            .filter_map(|c| {
+                if let SizedChunkEnum::Adtl(adtl) = c {
+                    Some(&adtl.data.chunks)
+                } else {
+                    None
+                }
+            })
+            .flatten();
+        for sub in annotated {
+            let name = match sub {
+                AdtlEnum::Labl(labl) => labl.data.name,
+                AdtlEnum::Note(note) => note.data.name,
+                AdtlEnum::Ltxt(ltxt) => ltxt.data.name,
+                AdtlEnum::File(file) => file.data.name,
+                AdtlEnum::Unknown { .. } => continue,
+            };
+            if !names.contains(&name) && !loops.contains(&name) {
+                warnings.push(
+                    Warning::new(
+                        sub.id(),
+                        None,
+                        format!("refers to missing cue point {name}"),
+                    )
+                    .with_code(Code::MissingCuePoint),
+                );
+            }
+        }
+        warnings
+    }
+
+    /// Differences between the `bext` chunk and its copy in the `iXML`
+    /// `BEXT` section, see
+    /// [`IxmlBext::compare()`](crate::chunk::ixml::IxmlBext::compare).
+    #[cfg(all(feature = "ixml", feature = "broadcast"))]
+    pub fn bext_mismatches(&self) -> Vec<Warning> {
+        let metadata = self.metadata();
+        let ixml_bext = metadata.ixml().and_then(|i| i.bext.as_ref());
+        match (ixml_bext, metadata.bext()) {
+            (Some(ixml_bext), Some(bext)) => ixml_bext.compare(bext),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All consistency checks: [`WaveMetadata::validate()`],
+    /// [`dangling_references()`](ResolvedWave::dangling_references) and
+    /// [`bext_mismatches()`](ResolvedWave::bext_mismatches).
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = self.metadata().validate();
+        warnings.extend(self.dangling_references());
+        #[cfg(all(feature = "ixml", feature = "broadcast"))]
+        warnings.extend(self.bext_mismatches());
+        warnings
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::{FourCC, WaveFile};
+
+    #[test]
+    fn resolve_example_a() {
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let resolved = wave.resolve();
+        let cues = resolved.cues();
+        dbg!(&cues);
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[1].label, Some("Marker 01"));
+        assert_eq!(resolved.cue(cues[1].point.name), Some(cues[1].point));
+        assert_eq!(resolved.fact_matches_fmt(), None);
+        assert!(resolved.playlist().is_empty());
+        assert!(resolved.orphans().is_empty());
+        #[cfg(all(feature = "ixml", feature = "broadcast"))]
+        assert!(resolved.bext_mismatches().is_empty());
+        assert!(resolved.validate().is_empty());
+    }
+
+    #[test]
+    fn resolve_playlist() {
+        // cue point 1, labeled "A", and a plst referring to cue points 1 and 2
+        let data = "52494646 66000000 57415645 63756520 1C000000 01000000 01000000 10000000 64617461 00000000 00000000 00000000 4C495354 12000000 6164746C 6C61626C 06000000 01000000 4100706C 73741C00 00000200 00000100 00000800 00000100 00000200 00000800 00000200 0000";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let resolved = wave.resolve();
+        let playlist = resolved.playlist();
+        dbg!(&playlist);
+        assert_eq!(playlist.len(), 2);
+        assert_eq!(playlist[0].label, Some("A"));
+        assert_eq!(playlist[0].cue.map(|c| c.position), Some(16));
+        assert_eq!(playlist[1].cue, None);
+
+        let warnings = resolved.dangling_references();
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].id, FourCC(*b"plst"));
+        assert!(resolved.orphans().is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+//! Check that parsed chunks write back to the bytes they were read from.
+//!
+//! Before letting wavrw rewrite a file, [`verify()`] parses every chunk,
+//! writes it again and compares the result with the original bytes. Some
+//! differences are expected and not reported:
+//!
+//! * The audio in `data` chunks is not parsed, only the chunk header is
+//!   compared.
+//! * The RIFF padding byte after odd sized chunks is always written as zero,
+//!   its value is not compared.
+//! * Zero bytes at the end of a chunk, after the parsed fields, may be
+//!   dropped. Some applications pad `bext` this way.
+//! * Bytes after the last chunk which fits in the RIFF data are ignored.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! for difference in wavrw::roundtrip::verify(&mut wave)? {
+//!     println!("{difference}");
+//! }
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use std::io::{BufRead, Cursor, Read, Seek, SeekFrom};
+
+use binrw::{BinRead, BinWrite};
+
+use crate::chunk::data::Data;
+use crate::warning::Code;
+use crate::{KnownChunkID, SizedChunkEnum, Warning, WaveFile, WaveFileError};
+
+/// Parse and write every chunk of `wave`, returning one [`Warning`] per
+/// chunk which doesn't write back to its original bytes.
+///
+/// Chunks which can't be parsed are reported as differences too. An empty
+/// result means the file can be rewritten without changes, apart from the
+/// exceptions listed in the [module documentation](self).
+pub fn verify<R>(wave: &mut WaveFile<R>) -> Result<Vec<Warning>, WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    let mut differences = Vec::new();
+    for lazy in wave.scan_chunks()? {
+        if lazy.large_size.is_some() && lazy.id != Data::ID {
+            differences.push(Warning::new(
+                lazy.id,
+                Some(lazy.offset),
+                "larger than 4 GiB, not compared",
+            ));
+            continue;
+        }
+        wave.bytes.seek(SeekFrom::Start(lazy.offset))?;
+        let chunk = match SizedChunkEnum::read_args(
+            &mut wave.bytes,
+            (false, wave.options.max_chunk_size),
+        ) {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                differences.push(
+                    Warning::new(
+                        lazy.id,
+                        Some(lazy.offset),
+                        format!("can't be parsed: {}", WaveFileError::from(err)),
+                    )
+                    .with_code(Code::Parse),
+                );
+                continue;
+            }
+        };
+        let mut buff = Cursor::new(Vec::new());
+        chunk.write(&mut buff)?;
+        let mut written = buff.into_inner();
+
+        let len = if lazy.id == Data::ID {
+            // audio data is skipped when parsing
+            written.truncate(8);
+            8
+        } else {
+            // padding byte
+            if lazy.size % 2 == 1 && written.len() % 2 == 0 {
+                written.pop();
+            }
+            8 + u64::from(lazy.size)
+        };
+        wave.bytes.seek(SeekFrom::Start(lazy.offset))?;
+        let mut original = Vec::new();
+        (&mut wave.bytes).take(len).read_to_end(&mut original)?;
+
+        if let Some(message) = compare(&original, &written) {
+            differences.push(
+                Warning::new(lazy.id, Some(lazy.offset), message)
+                    .with_code(Code::RoundtripMismatch),
+            );
+        }
+    }
+    Ok(differences)
+}
+
+/// Describe the first difference between `original` and `written` chunk bytes.
+fn compare(original: &[u8], written: &[u8]) -> Option<String> {
+    let position = original.iter().zip(written).position(|(a, b)| a != b);
+    match position {
+        Some(i) => Some(format!(
+            "byte {i} of the chunk written as {:#04X}, read as {:#04X}",
+            written[i], original[i]
+        )),
+        // zero padding at the end of the chunk
+        None if original.len() > written.len()
+            && original[written.len()..].iter().all(|b| *b == 0) =>
+        {
+            None
+        }
+        None if original.len() != written.len() => Some(format!(
+            "written as {} bytes, read as {} bytes",
+            written.len(),
+            original.len()
+        )),
+        None => None,
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+#[cfg(feature = "broadcast")]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::FourCC;
+
+    #[test]
+    fn verify_test_wavs() {
+        for name in [
+            "example_a.wav",
+            "1khz_sine_48k_mono_region_marker-bext_metadata.wav",
+        ] {
+            let file = BufReader::new(File::open(format!("../test_wavs/{name}")).unwrap());
+            let mut wave = WaveFile::from_reader(file).unwrap();
+            let differences = verify(&mut wave).unwrap();
+            dbg!(name, &differences);
+            assert!(differences.is_empty());
+        }
+
+        // REAPER leaves old text after the zero byte ending bext originator
+        let path = "../test_wavs/1khz_sine_48k_mono_region_marker.wav";
+        let mut wave = WaveFile::from_reader(BufReader::new(File::open(path).unwrap())).unwrap();
+        let differences = verify(&mut wave).unwrap();
+        dbg!(&differences);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].id, FourCC(*b"bext"));
+    }
+
+    #[test]
+    fn verify_reports_difference() {
+        // truncated bext chunk, written back with all fixed fields
+        let data = "52494646 0E000000 57415645 62657874 02000000 4142";
+        let mut wave = WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let differences = verify(&mut wave).unwrap();
+        dbg!(&differences);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].id, FourCC(*b"bext"));
+        assert_eq!(differences[0].offset, Some(12));
+
+        assert_eq!(compare(b"ab", b"ab"), None);
+        assert!(compare(b"ab", b"abc").is_some());
+        assert_eq!(compare(b"ab\0\0", b"ab"), None);
+        assert!(compare(b"ab\0c", b"ab").is_some());
+    }
+}
@@ -0,0 +1,224 @@
+//! Decode PCM and IEEE float audio from the `data` chunk.
+//!
+//! [`Samples`] reads the audio of a [`WaveFile`] as interleaved `f64`
+//! frames scaled to -1.0..=1.0, for measurements like loudness and peaks.
+//! Compressed formats are not supported.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use wavrw::samples::Samples;
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let mut samples = Samples::new(&mut wave)?;
+//! let mut frames = Vec::new();
+//! let mut peak: f64 = 0.0;
+//! while samples.read_frames(&mut frames, 4096)? > 0 {
+//!     peak = frames.iter().fold(peak, |peak, s| peak.max(s.abs()));
+//! }
+//! assert!(peak <= 1.0);
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use std::io::{BufRead, Read, Seek, SeekFrom, Take};
+
+use crate::chunk::data::Data;
+use crate::chunk::fmt::{FmtEnum, Tag};
+use crate::{KnownChunkID, WaveFile, WaveFileError};
+
+/// Encoding of one sample in the `data` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleFormat {
+    /// Little-endian integer PCM stored in this many bytes. 8 bit samples
+    /// are unsigned, all others signed.
+    Int(usize),
+    /// Little-endian IEEE float stored in 4 or 8 bytes.
+    Float(usize),
+}
+
+impl SampleFormat {
+    /// Sample format of `fmt`, `None` for compressed or unusual formats.
+    ///
+    /// `WAVE_FORMAT_EXTENSIBLE` uses the format tag in its sub format GUID.
+    /// Samples are read in containers of `block_align / channels` bytes, so
+    /// 20 bit audio in 3 byte containers is read as 24 bit.
+    pub fn from_fmt(fmt: &FmtEnum) -> Option<Self> {
+        let channels = usize::from(fmt.channels());
+        let bytes = usize::from(fmt.block_align()).checked_div(channels)?;
+        let tag = match (u16::from(fmt.format_tag()), fmt) {
+            // sub format GUID after valid bits and channel mask
+            (0xFFFE, FmtEnum::Extended(ext)) => {
+                u16::from_le_bytes([*ext.extra_bytes.get(6)?, *ext.extra_bytes.get(7)?])
+            }
+            (tag, _) => tag,
+        };
+        match (tag, bytes) {
+            (0x0001, 1..=4) => Some(SampleFormat::Int(bytes)),
+            (0x0003, 4 | 8) => Some(SampleFormat::Float(bytes)),
+            _ => None,
+        }
+    }
+
+    /// Bytes per sample.
+    pub fn bytes(&self) -> usize {
+        match self {
+            SampleFormat::Int(bytes) | SampleFormat::Float(bytes) => *bytes,
+        }
+    }
+
+    /// Decode one sample from the start of `bytes`, scaled to -1.0..=1.0.
+    ///
+    /// ```
+    /// use wavrw::samples::SampleFormat;
+    ///
+    /// assert_eq!(SampleFormat::Int(2).decode(&[0x00, 0xC0]), -0.5);
+    /// assert_eq!(SampleFormat::Int(1).decode(&[0xC0]), 0.5);
+    /// assert_eq!(SampleFormat::Float(4).decode(&0.25_f32.to_le_bytes()), 0.25);
+    /// ```
+    pub fn decode(&self, bytes: &[u8]) -> f64 {
+        match *self {
+            SampleFormat::Int(1) => (f64::from(bytes[0]) - 128.0) / 128.0,
+            SampleFormat::Int(len) => {
+                // sign extend from the top byte
+                let mut buff = [0_u8; 4];
+                buff[4 - len..].copy_from_slice(&bytes[..len]);
+                f64::from(i32::from_le_bytes(buff)) / 2_147_483_648.0
+            }
+            SampleFormat::Float(4) => {
+                f64::from(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            }
+            SampleFormat::Float(_) => {
+                let mut buff = [0_u8; 8];
+                buff.copy_from_slice(&bytes[..8]);
+                f64::from_le_bytes(buff)
+            }
+        }
+    }
+}
+
+/// Reads the audio of the first `data` chunk of a [`WaveFile`] as `f64`
+/// frames. See the [module documentation](self).
+#[derive(Debug)]
+pub struct Samples<'a, R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    reader: Take<&'a mut R>,
+    format: SampleFormat,
+    channels: usize,
+    sample_rate: u32,
+    buff: Vec<u8>,
+}
+
+impl<'a, R> Samples<'a, R>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    /// Prepare to read the audio of `wave`.
+    ///
+    /// Returns an error if `wave` has no `fmt ` or `data` chunk, or the
+    /// samples are compressed.
+    pub fn new(wave: &'a mut WaveFile<R>) -> Result<Self, WaveFileError> {
+        let fmt = wave
+            .metadata()
+            .fmt()
+            .cloned()
+            .ok_or_else(|| WaveFileError::parse(None, "no fmt chunk"))?;
+        let format = SampleFormat::from_fmt(&fmt).ok_or_else(|| {
+            WaveFileError::parse(None, format!("can't decode {} samples", fmt.format_tag()))
+        })?;
+        let data = wave
+            .scan_chunks()?
+            .into_iter()
+            .find(|c| c.id == Data::ID)
+            .ok_or_else(|| WaveFileError::parse(None, "no data chunk"))?;
+        wave.bytes.seek(SeekFrom::Start(data.offset + 8))?;
+        Ok(Samples {
+            reader: (&mut wave.bytes).take(data.large_size()),
+            format,
+            channels: usize::from(fmt.channels()),
+            sample_rate: fmt.samples_per_sec(),
+            buff: Vec::new(),
+        })
+    }
+
+    /// Sample format of the audio.
+    pub fn format(&self) -> SampleFormat {
+        self.format
+    }
+
+    /// Number of channels, the samples per frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Frames per second.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Replace the contents of `frames` with up to `max_frames` interleaved
+    /// frames. Returns the number of frames read, 0 at the end of the data.
+    ///
+    /// A partial frame at the end of the data is ignored.
+    pub fn read_frames(
+        &mut self,
+        frames: &mut Vec<f64>,
+        max_frames: usize,
+    ) -> Result<usize, WaveFileError> {
+        let frame_len = self.format.bytes() * self.channels;
+        self.buff.clear();
+        (&mut self.reader)
+            .take((max_frames * frame_len) as u64)
+            .read_to_end(&mut self.buff)?;
+        let count = self.buff.len() / frame_len;
+        frames.clear();
+        frames.extend(
+            self.buff[..count * frame_len]
+                .chunks_exact(self.format.bytes())
+                .map(|sample| self.format.decode(sample)),
+        );
+        Ok(count)
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::hex_to_cursor;
+
+    #[test]
+    fn read_frames() {
+        // stereo 24 bit PCM, 3 frames and a partial frame
+        let data = "52494646 38000000 57415645 \
+            666D7420 10000000 0100 0200 80BB0000 00650400 0600 1800 \
+            64617461 14000000 000080 FFFF7F 000040 000000 FFFFFF 010000 0000";
+        let mut wave = WaveFile::from_reader(hex_to_cursor(data)).unwrap();
+        let mut samples = Samples::new(&mut wave).unwrap();
+        assert_eq!(samples.format(), SampleFormat::Int(3));
+        assert_eq!(samples.channels(), 2);
+        assert_eq!(samples.sample_rate(), 48000);
+
+        let mut frames = Vec::new();
+        assert_eq!(samples.read_frames(&mut frames, 2).unwrap(), 2);
+        dbg!(&frames);
+        assert_eq!(frames[..3], [-1.0, 8_388_607.0 / 8_388_608.0, 0.5]);
+        assert_eq!(samples.read_frames(&mut frames, 2).unwrap(), 1);
+        assert_eq!(frames, [-1.0 / 8_388_608.0, 1.0 / 8_388_608.0]);
+        assert_eq!(samples.read_frames(&mut frames, 2).unwrap(), 0);
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn compressed_not_supported() {
+        // fmt with format tag 0x0002 (ADPCM) and no extra bytes
+        let data = "52494646 24000000 57415645 \
+            666D7420 10000000 0200 0100 80BB0000 00770100 0200 0400 \
+            64617461 00000000";
+        let mut wave = WaveFile::from_reader(hex_to_cursor(data)).unwrap();
+        assert!(Samples::new(&mut wave).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+//! Check text for chunks specified as ASCII before writing.
+//!
+//! The `bext` strings and `LIST-INFO` text are specified as ASCII, but many
+//! tools write UTF-8 or a Windows code page instead. Some target systems
+//! show such text garbled or reject the file, others handle it fine. A
+//! [`Sanitizer`] makes the choice explicit with a [`TextPolicy`]: fail on
+//! non-ASCII text, replace it with ASCII, or write it as is. Each changed or
+//! allowed field is recorded as a [`Warning`] with [`Code::InvalidText`].
+//!
+//! ```
+//! use wavrw::chunk::bext::Bext;
+//! use wavrw::sanitize::{Sanitizer, TextPolicy};
+//!
+//! let mut bext = Bext::builder().description("Café – Montréal").build()?;
+//!
+//! let mut sanitizer = Sanitizer::new(TextPolicy::Replace);
+//! sanitizer.bext(&mut bext)?;
+//! assert_eq!(bext.description.to_string(), "Cafe - Montreal");
+//! assert_eq!(sanitizer.warnings().len(), 1);
+//!
+//! let mut bext = Bext::builder().description("Café").build()?;
+//! assert!(Sanitizer::new(TextPolicy::Strict).bext(&mut bext).is_err());
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::Bext;
+#[cfg(feature = "info")]
+use crate::chunk::info::{InfoEnum, ListInfo};
+#[cfg(feature = "broadcast")]
+use crate::fixedstring::FixedString;
+use crate::warning::Code;
+#[cfg(feature = "info")]
+use crate::ChunkID;
+#[cfg(feature = "broadcast")]
+use crate::KnownChunkID;
+use crate::{FourCC, Warning, WaveFileError};
+
+/// What to do with non-ASCII text in fields specified as ASCII.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextPolicy {
+    /// Return an error.
+    Strict,
+    /// Replace with similar ASCII characters, see [`transliterate()`].
+    Replace,
+    /// Write the text unchanged. Text set by wavrw is UTF-8, text as read is
+    /// written back as read.
+    #[default]
+    AllowUtf8,
+}
+
+/// Applies a [`TextPolicy`] to chunks before writing, and records a
+/// [`Warning`] for each field with non-ASCII text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sanitizer {
+    policy: TextPolicy,
+    warnings: Vec<Warning>,
+}
+
+impl Sanitizer {
+    /// Create a [`Sanitizer`] applying `policy`.
+    pub fn new(policy: TextPolicy) -> Self {
+        Sanitizer {
+            policy,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The policy applied.
+    pub fn policy(&self) -> TextPolicy {
+        self.policy
+    }
+
+    /// Fields with non-ASCII text found so far, replaced or allowed.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Apply the policy to `text` of `field` in chunk `id`.
+    ///
+    /// Returns `None` if `text` is ASCII or allowed unchanged, otherwise the
+    /// replacement text. `raw` is the bytes which would be written.
+    fn check(
+        &mut self,
+        id: FourCC,
+        field: &str,
+        text: &str,
+        raw: &[u8],
+    ) -> Result<Option<String>, WaveFileError> {
+        if raw.is_ascii() && text.is_ascii() {
+            return Ok(None);
+        }
+        match self.policy {
+            TextPolicy::Strict => Err(WaveFileError::parse(
+                None,
+                format!("{id} {field} contains non-ASCII text: {text:?}"),
+            )),
+            TextPolicy::Replace => {
+                let replaced = transliterate(text);
+                self.warnings.push(
+                    Warning::new(
+                        id,
+                        None,
+                        format!("{field}: replaced non-ASCII text {text:?} with {replaced:?}"),
+                    )
+                    .with_code(Code::InvalidText),
+                );
+                Ok(Some(replaced))
+            }
+            TextPolicy::AllowUtf8 => {
+                self.warnings.push(
+                    Warning::new(id, None, format!("{field}: non-ASCII text {text:?}"))
+                        .with_code(Code::InvalidText),
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Apply the policy to `value` of `field` in chunk `id`.
+    ///
+    /// Returns an error for non-ASCII text with [`TextPolicy::Strict`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wavrw::sanitize::{Sanitizer, TextPolicy};
+    /// use wavrw::FourCC;
+    ///
+    /// let mut sanitizer = Sanitizer::new(TextPolicy::Replace);
+    /// let text = sanitizer.text(FourCC(*b"INAM"), "text", "Smørrebrød")?;
+    /// assert_eq!(text, "Smorrebrod");
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn text(&mut self, id: FourCC, field: &str, value: &str) -> Result<String, WaveFileError> {
+        Ok(self
+            .check(id, field, value, value.as_bytes())?
+            .unwrap_or_else(|| value.to_string()))
+    }
+
+    /// Apply the policy to the strings and `coding_history` of `bext`.
+    ///
+    /// Returns an error for non-ASCII text with [`TextPolicy::Strict`], or if
+    /// replaced text no longer fits its field. On error, `bext` may be
+    /// partly changed.
+    #[cfg(feature = "broadcast")]
+    pub fn bext(&mut self, bext: &mut Bext) -> Result<(), WaveFileError> {
+        self.fixed(&mut bext.description, "description")?;
+        self.fixed(&mut bext.originator, "originator")?;
+        self.fixed(&mut bext.originator_reference, "originator_reference")?;
+        self.fixed(&mut bext.origination_date, "origination_date")?;
+        self.fixed(&mut bext.origination_time, "origination_time")?;
+        bext.coding_history = self.text(Bext::ID, "coding_history", &bext.coding_history)?;
+        Ok(())
+    }
+
+    /// Apply the policy to a fixed length `bext` string.
+    #[cfg(feature = "broadcast")]
+    fn fixed<const N: usize>(
+        &mut self,
+        value: &mut FixedString<N>,
+        field: &str,
+    ) -> Result<(), WaveFileError> {
+        let text = value.to_string();
+        if let Some(replaced) = self.check(Bext::ID, field, &text, value.as_bytes())? {
+            value
+                .set(&replaced)
+                .map_err(|err| WaveFileError::parse(None, format!("bext {field}: {err}")))?;
+        }
+        Ok(())
+    }
+
+    /// Apply the policy to the text of each subchunk of `info`.
+    ///
+    /// Returns an error for non-ASCII text with [`TextPolicy::Strict`]. On
+    /// error, `info` may be partly changed.
+    #[cfg(feature = "info")]
+    pub fn list_info(&mut self, info: &mut ListInfo) -> Result<(), WaveFileError> {
+        for chunk in &mut info.chunks {
+            let id = chunk.id();
+            if let Some(replaced) = self.check(id, "text", &chunk.text(), chunk.raw())? {
+                *chunk = InfoEnum::new(id, &replaced)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<Sanitizer> for Vec<Warning> {
+    fn from(sanitizer: Sanitizer) -> Self {
+        sanitizer.warnings
+    }
+}
+
+/// Replace non-ASCII characters in `text` with similar ASCII characters.
+///
+/// Latin letters lose their accents, ligatures are spelled out and
+/// typographic quotes, dashes and spaces become their plain versions.
+/// Other characters are replaced with `?`. Replacements are never longer in
+/// bytes than the UTF-8 character they replace.
+///
+/// # Examples
+///
+/// ```
+/// use wavrw::sanitize::transliterate;
+///
+/// assert_eq!(transliterate("“Straße” — Œuvre"), "\"Strasse\" - OEuvre");
+/// assert_eq!(transliterate("東京"), "??");
+/// ```
+pub fn transliterate(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else {
+            ascii.push_str(ascii_for(c));
+        }
+    }
+    ascii
+}
+
+/// ASCII replacement for a non-ASCII character.
+fn ascii_for(c: char) -> &'static str {
+    match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Č' => "C",
+        'ç' | 'ć' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'Ğ' => "G",
+        'ğ' => "g",
+        'Ì'..='Ï' | 'Ī' | 'İ' => "I",
+        'ì'..='ï' | 'ī' | 'ı' => "i",
+        'Ł' => "L",
+        'ł' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ř' => "R",
+        'ř' => "r",
+        'Ś' | 'Ş' | 'Š' => "S",
+        'ś' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ť' => "T",
+        'ť' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+        'ù'..='ü' | 'ū' | 'ů' | 'ű' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\u{a0}' | '\u{2002}'..='\u{200a}' | '\u{202f}' => " ",
+        '‐'..='—' | '−' => "-",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' => "\"",
+        '«' => "<<",
+        '»' => ">>",
+        '…' => "...",
+        '×' => "x",
+        '€' => "EUR",
+        _ => "?",
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::dbg_macro)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn replacements_fit() {
+        for c in '\u{80}'..='\u{2fff}' {
+            assert!(ascii_for(c).len() <= c.len_utf8(), "{c:?}");
+            assert!(ascii_for(c).is_ascii(), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn policies() {
+        let id = FourCC(*b"ICMT");
+        let mut strict = Sanitizer::new(TextPolicy::Strict);
+        assert_eq!(strict.text(id, "text", "plain").unwrap(), "plain");
+        assert!(strict.text(id, "text", "naïve").is_err());
+        assert!(strict.warnings().is_empty());
+
+        let mut replace = Sanitizer::new(TextPolicy::Replace);
+        assert_eq!(replace.text(id, "text", "naïve").unwrap(), "naive");
+        let mut allow = Sanitizer::default();
+        assert_eq!(allow.text(id, "text", "naïve").unwrap(), "naïve");
+
+        for sanitizer in [replace, allow] {
+            let warnings: Vec<Warning> = sanitizer.into();
+            dbg!(&warnings);
+            assert_eq!(warnings.len(), 1);
+            assert_eq!(warnings[0].code, Code::InvalidText);
+            assert_eq!(warnings[0].id, id);
+        }
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn sanitize_bext() {
+        // "Münster" in Latin-1, as read from a file
+        let mut bext = Bext {
+            originator: FixedString::from_bytes(b"M\xFCnster".to_vec()).unwrap(),
+            coding_history: "A=PCM,T=Zoom F8n – take 2\r\n".to_string(),
+            ..Default::default()
+        };
+        let mut sanitizer = Sanitizer::new(TextPolicy::Replace);
+        sanitizer.bext(&mut bext).unwrap();
+        assert_eq!(bext.originator.as_bytes(), b"Munster");
+        assert_eq!(bext.coding_history, "A=PCM,T=Zoom F8n - take 2\r\n");
+        assert_eq!(sanitizer.warnings().len(), 2);
+
+        // "ß" is one byte in Latin-1, two in ASCII
+        let mut bext = Bext {
+            origination_time: FixedString::from_bytes(b"1234567\xDF".to_vec()).unwrap(),
+            ..Default::default()
+        };
+        assert!(sanitizer.bext(&mut bext).is_err());
+    }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn sanitize_list_info() {
+        let mut info = ListInfo::builder()
+            .text(FourCC(*b"INAM"), "Rain")
+            .text(FourCC(*b"IART"), "Björk")
+            .text(FourCC(*b"IXYZ"), "déjà vu")
+            .build()
+            .unwrap();
+
+        let mut allowed = info.clone();
+        Sanitizer::default().list_info(&mut allowed).unwrap();
+        assert_eq!(allowed, info);
+        assert!(Sanitizer::new(TextPolicy::Strict)
+            .list_info(&mut info.clone())
+            .is_err());
+
+        let mut sanitizer = Sanitizer::new(TextPolicy::Replace);
+        sanitizer.list_info(&mut info).unwrap();
+        assert_eq!(info.chunks[0].text(), "Rain");
+        assert_eq!(info.chunks[1].text(), "Bjork");
+        assert_eq!(info.chunks[2].raw(), b"deja vu");
+        assert_eq!(sanitizer.warnings().len(), 2);
+    }
+}
@@ -0,0 +1,281 @@
+//! Parse many files in parallel.
+//!
+//! Inventories of large sound libraries spend most of their time waiting on
+//! disk reads, one file at a time. [`scan_paths()`] spreads the files over
+//! several threads and returns the results in the order the paths were given.
+//!
+//! ```
+//! use wavrw::scan::{scan_paths, ScanOptions};
+//!
+//! let paths = ["../test_wavs/example_a.wav", "../test_wavs/missing.wav"];
+//! let results = scan_paths(&paths, &ScanOptions::default());
+//!
+//! assert_eq!(results.len(), 2);
+//! assert!(results[0].chunks.as_ref().is_ok_and(|c| !c.is_empty()));
+//! assert!(results[1].chunks.is_err());
+//! ```
+//!
+//! Scans of large libraries, or of single very large files, can take a long
+//! time. [`scan_paths_with_progress()`] reports each parsed chunk and file to
+//! a [`ScanProgress`].
+//!
+//! ```
+//! use std::path::Path;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use wavrw::scan::{scan_paths_with_progress, ScanOptions, ScanProgress};
+//!
+//! #[derive(Default)]
+//! struct Bytes(AtomicU64);
+//!
+//! impl ScanProgress for Bytes {
+//!     fn chunk_parsed(&self, _path: &Path, bytes: u64) {
+//!         self.0.fetch_add(bytes, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let progress = Bytes::default();
+//! let paths = ["../test_wavs/example_a.wav"];
+//! scan_paths_with_progress(&paths, &ScanOptions::default(), &progress);
+//! assert!(progress.0.load(Ordering::Relaxed) > 0);
+//! ```
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::{ParseOptions, SizedChunk, SizedChunkEnum, Warning, WaveFile, WaveFileError};
+
+/// Options for [`scan_paths()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ScanOptions {
+    /// Number of threads. Default: `None`, the available parallelism.
+    pub threads: Option<usize>,
+
+    /// Options used to parse each file. Default: [`ParseOptions::default()`]
+    /// without [`ParseOptions::keep_extra_bytes`].
+    pub parse_options: ParseOptions,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            threads: None,
+            parse_options: ParseOptions::default().with_keep_extra_bytes(false),
+        }
+    }
+}
+
+impl ScanOptions {
+    /// Set [`ScanOptions::threads`].
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Set [`ScanOptions::parse_options`].
+    #[must_use]
+    pub fn with_parse_options(mut self, parse_options: ParseOptions) -> Self {
+        self.parse_options = parse_options;
+        self
+    }
+}
+
+/// The chunks parsed from one file, see [`scan_paths()`].
+#[derive(Debug)]
+pub struct ScanResult {
+    /// Path of the file.
+    pub path: PathBuf,
+
+    /// Result of parsing each chunk in file order, as returned by
+    /// [`WaveFile::iter_chunks()`], or the error opening the file.
+    pub chunks: Result<Vec<Result<SizedChunkEnum, WaveFileError>>, WaveFileError>,
+
+    /// Warnings recorded while parsing, see [`WaveFile::warnings()`].
+    pub warnings: Vec<Warning>,
+}
+
+/// Receives the progress of [`scan_paths_with_progress()`].
+///
+/// Files are parsed on several threads, so calls for different files can be
+/// interleaved. All methods do nothing by default, `()` ignores all progress.
+pub trait ScanProgress: Sync {
+    /// Parsing of the file at `path` started, `size` is the length of the
+    /// file in bytes.
+    fn file_started(&self, _path: &Path, _size: u64) {}
+
+    /// A chunk of the file at `path` was parsed. `bytes` is the size of the
+    /// chunk including its header and padding, 0 for chunks which could not
+    /// be parsed.
+    fn chunk_parsed(&self, _path: &Path, _bytes: u64) {}
+
+    /// Parsing of a file finished, also when it could not be opened.
+    fn file_finished(&self, _result: &ScanResult) {}
+}
+
+impl ScanProgress for () {}
+
+/// Parse the files at `paths` in parallel.
+///
+/// Returns one [`ScanResult`] per path, in the same order as `paths`.
+pub fn scan_paths<P>(paths: &[P], options: &ScanOptions) -> Vec<ScanResult>
+where
+    P: AsRef<Path> + Sync,
+{
+    scan_paths_with_progress(paths, options, &())
+}
+
+/// Parse the files at `paths` in parallel like [`scan_paths()`], reporting
+/// progress to `progress`.
+pub fn scan_paths_with_progress<P>(
+    paths: &[P],
+    options: &ScanOptions,
+    progress: &dyn ScanProgress,
+) -> Vec<ScanResult>
+where
+    P: AsRef<Path> + Sync,
+{
+    let threads = options
+        .threads
+        .or_else(|| thread::available_parallelism().ok().map(usize::from))
+        .unwrap_or(1)
+        .clamp(1, paths.len().max(1));
+    if threads == 1 {
+        // no threads needed, also for targets without thread support like wasm32
+        return paths
+            .iter()
+            .map(|path| scan_path(path.as_ref(), &options.parse_options, progress))
+            .collect();
+    }
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<(usize, ScanResult)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            break;
+                        };
+                        let result = scan_path(path.as_ref(), &options.parse_options, progress);
+                        results.push((index, result));
+                    }
+                    results
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Parse all chunks of the file at `path`.
+fn scan_path(path: &Path, options: &ParseOptions, progress: &dyn ScanProgress) -> ScanResult {
+    let mut warnings = Vec::new();
+    let chunks = File::open(path)
+        .map_err(WaveFileError::from)
+        .and_then(|file| {
+            progress.file_started(path, file.metadata()?.len());
+            WaveFile::from_reader_with_options(BufReader::new(file), options.clone())
+        })
+        .map(|mut wave| {
+            let chunks = wave
+                .iter_chunks()
+                .inspect(|chunk| {
+                    let bytes = chunk.as_ref().map_or(0, SizedChunk::total_size);
+                    progress.chunk_parsed(path, bytes);
+                })
+                .collect();
+            warnings = wave.warnings().to_vec();
+            chunks
+        });
+    let result = ScanResult {
+        path: path.to_path_buf(),
+        chunks,
+        warnings,
+    };
+    progress.file_finished(&result);
+    result
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scan_keeps_order() {
+        let paths: Vec<&str> = (0..9)
+            .map(|i| {
+                if i % 3 == 0 {
+                    "../test_wavs/missing.wav"
+                } else {
+                    "../test_wavs/example_a.wav"
+                }
+            })
+            .collect();
+        let results = scan_paths(&paths, &ScanOptions::default().with_threads(4));
+        dbg!(&results[0]);
+        assert_eq!(results.len(), paths.len());
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.path, PathBuf::from(paths[i]));
+            assert_eq!(result.chunks.is_err(), i % 3 == 0);
+        }
+        assert!(scan_paths::<&str>(&[], &ScanOptions::default()).is_empty());
+    }
+
+    #[derive(Debug, Default)]
+    struct Counts {
+        started: AtomicUsize,
+        chunks: AtomicUsize,
+        bytes: AtomicUsize,
+        finished: AtomicUsize,
+    }
+
+    impl ScanProgress for Counts {
+        fn file_started(&self, _path: &Path, _size: u64) {
+            self.started.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn chunk_parsed(&self, _path: &Path, bytes: u64) {
+            self.chunks.fetch_add(1, Ordering::Relaxed);
+            self.bytes
+                .fetch_add(usize::try_from(bytes).unwrap(), Ordering::Relaxed);
+        }
+
+        fn file_finished(&self, _result: &ScanResult) {
+            self.finished.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn scan_progress() {
+        let paths = [
+            "../test_wavs/example_a.wav",
+            "../test_wavs/missing.wav",
+            "../test_wavs/example_a.wav",
+        ];
+        let progress = Counts::default();
+        let options = ScanOptions::default().with_threads(2);
+        let results = scan_paths_with_progress(&paths, &options, &progress);
+        dbg!(&progress);
+        let chunks = results[0].chunks.as_ref().unwrap();
+        assert_eq!(progress.started.load(Ordering::Relaxed), 2);
+        assert_eq!(progress.finished.load(Ordering::Relaxed), 3);
+        assert_eq!(progress.chunks.load(Ordering::Relaxed), chunks.len() * 2);
+
+        // every byte after the RIFF header is in a chunk
+        let size = std::fs::metadata(paths[0]).unwrap().len();
+        let bytes = u64::try_from(progress.bytes.load(Ordering::Relaxed)).unwrap();
+        assert_eq!(bytes, (size - 12) * 2);
+    }
+}
@@ -0,0 +1,795 @@
+//! Names of the keys returned by [`Summarizable::items()`].
+//!
+//! Items are shown by the CLI, and stored in catalogs, sidecars and reports
+//! read by other programs. Their keys are part of the public interface: each
+//! chunk has an [`ItemKeys`] constant listing the keys it can return, which
+//! tests check all chunks against. Renaming, removing or reordering keys
+//! increments [`SCHEMA_VERSION`], so consumers can detect the change.
+//!
+//! Items are returned in a fixed order. Fixed keys follow the order of
+//! [`ItemKeys::keys`], keys missing from a chunk are skipped, ex: `umid` in
+//! `bext` before version 1. Keys depending on the contents of a chunk, like
+//! the index of a cue point, match one of [`ItemKeys::patterns`] and follow
+//! the order of the data they describe.
+//!
+//! ```
+//! use wavrw::schema::{self, item_keys};
+//!
+//! assert_eq!(schema::schema_version(), 2);
+//! let cue = item_keys("cue").unwrap();
+//! assert!(cue.contains("[0] position"));
+//! assert!(!cue.contains("position"));
+//! ```
+//!
+//! [`Summarizable::items()`]: crate::Summarizable::items
+//!
+//! The fields behind the items are described by [`schema()`]: every chunk
+//! data type with the names, Rust types and documentation of its fields,
+//! generated from the type definitions when building wavrw. Frontends can
+//! build metadata forms from it, or from the JSON Schema written by
+//! [`to_json_schema()`].
+//!
+//! ```
+//! let cue_point = wavrw::schema::type_schema("CuePoint").unwrap();
+//! assert_eq!(cue_point.module, "cue");
+//! let position = cue_point.member("position").unwrap();
+//! assert_eq!(position.ty, "u32");
+//! assert!(!position.doc.is_empty());
+//! ```
+
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use alloc::{format, vec};
+
+#[cfg(feature = "std")]
+use crate::json::Value;
+
+/// Version of the item keys, incremented whenever keys listed in this module
+/// change.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Returns [`SCHEMA_VERSION`].
+pub const fn schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Keys of the items of one kind of chunk.
+///
+/// Patterns contain one placeholder: `{n}` matches decimal digits, `{hex}`
+/// hex digits, `{id}` a four character chunk id and `{text}` any text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemKeys {
+    /// Chunk name, see [`Summarizable::name()`](crate::Summarizable::name).
+    pub name: &'static str,
+    /// Fixed keys, in the order they are returned.
+    pub keys: &'static [&'static str],
+    /// Patterns of keys depending on the chunk contents.
+    pub patterns: &'static [&'static str],
+}
+
+impl ItemKeys {
+    /// True if `key` is one of [`ItemKeys::keys`] or matches one of
+    /// [`ItemKeys::patterns`].
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys.contains(&key) || self.patterns.iter().any(|p| matches(p, key))
+    }
+}
+
+/// True if `key` matches `pattern`, see [`ItemKeys`].
+fn matches(pattern: &str, key: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('{') else {
+        return pattern == key;
+    };
+    let Some((placeholder, suffix)) = rest.split_once('}') else {
+        return false;
+    };
+    let Some(value) = key
+        .strip_prefix(prefix)
+        .and_then(|k| k.strip_suffix(suffix))
+    else {
+        return false;
+    };
+    match placeholder {
+        "n" => !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()),
+        "hex" => !value.is_empty() && value.bytes().all(|b| b.is_ascii_hexdigit()),
+        "id" => value.chars().count() == 4,
+        "text" => !value.is_empty(),
+        _ => false,
+    }
+}
+
+/// `fmt `, all format variants. Each variant returns the first six keys
+/// followed by its own.
+pub const FMT: ItemKeys = ItemKeys {
+    name: "fmt",
+    keys: &[
+        "format_tag",
+        "channels",
+        "samples_per_sec",
+        "avg_bytes_per_sec",
+        "block_align",
+        "bits_per_sample",
+        "extra_size",
+        "samples_per_block",
+        "coefficient_count",
+        "coefficients",
+        "head_layer",
+        "head_bitrate",
+        "head_mode",
+        "head_mode_ext",
+        "head_emphasis",
+        "head_flags",
+        "pts_low",
+        "pts_high",
+        "mpeg_id",
+        "flags",
+        "block_size",
+        "frames_per_block",
+        "codec_delay",
+        "extra_bytes",
+    ],
+    patterns: &[],
+};
+
+/// `bext`. Text fields which are not valid UTF-8 are followed by their
+/// guessed encoding. Ex: `description.encoding`
+pub const BEXT: ItemKeys = ItemKeys {
+    name: "bext",
+    keys: &[
+        "description",
+        "description.encoding",
+        "originator",
+        "originator.encoding",
+        "originator_reference",
+        "originator_reference.encoding",
+        "origination_date",
+        "origination_date.encoding",
+        "origination_time",
+        "origination_time.encoding",
+        "time_reference",
+        "version",
+        "umid",
+        "loudness_value",
+        "loudness_range",
+        "max_true_peak_level",
+        "max_momentary_loudness",
+        "max_short_term_loudness",
+        "coding_history",
+    ],
+    patterns: &[],
+};
+
+/// `cue `, six keys per cue point, prefixed with the index of the point.
+pub const CUE: ItemKeys = ItemKeys {
+    name: "cue",
+    keys: &[],
+    patterns: &[
+        "[{n}] name",
+        "[{n}] position",
+        "[{n}] chunk_id",
+        "[{n}] chunk_start",
+        "[{n}] block_start",
+        "[{n}] sample_offset",
+    ],
+};
+
+/// `CSET`
+pub const CSET: ItemKeys = ItemKeys {
+    name: "CSET",
+    keys: &["code_page", "country_code", "language", "dialect"],
+    patterns: &[],
+};
+
+/// `DISP`, `text` for text formats, otherwise `data`.
+pub const DISP: ItemKeys = ItemKeys {
+    name: "DISP",
+    keys: &["format", "text", "data"],
+    patterns: &[],
+};
+
+/// `ds64`, followed by the id of each table entry.
+pub const DS64: ItemKeys = ItemKeys {
+    name: "ds64",
+    keys: &["riff_size", "data_size", "sample_count"],
+    patterns: &["{id}"],
+};
+
+/// `inst`
+pub const INST: ItemKeys = ItemKeys {
+    name: "inst",
+    keys: &[
+        "unshifted_note",
+        "fine_tune",
+        "gain",
+        "low_note",
+        "high_note",
+        "low_velocity",
+        "high_velocity",
+    ],
+    patterns: &[],
+};
+
+/// `iXML`, keys are `section.field` of the parsed sections.
+pub const IXML: ItemKeys = ItemKeys {
+    name: "iXML",
+    keys: &[
+        "raw_bytes",
+        "history.original_filename",
+        "history.parent_filename",
+        "history.parent_uid",
+        "file_set.total_files",
+        "file_set.family_uid",
+        "file_set.family_name",
+        "file_set.file_set_index",
+        "loudness.loudness_value",
+        "loudness.loudness_range",
+        "loudness.max_true_peak_level",
+        "loudness.max_momentary_loudness",
+        "loudness.max_short_term_loudness",
+        "speed.note",
+        "speed.master_speed",
+        "speed.current_speed",
+        "speed.timecode_rate",
+        "speed.timecode_flag",
+        "speed.file_sample_rate",
+        "speed.audio_bit_depth",
+        "speed.digitizer_sample_rate",
+        "speed.timestamp_samples_since_midnight_hi",
+        "speed.timestamp_samples_since_midnight_lo",
+        "speed.timestamp_sample_rate",
+        "speed.start_timecode",
+        "track_list.track_count",
+        "bext.description",
+        "bext.originator",
+        "bext.originator_reference",
+        "bext.origination_date",
+        "bext.origination_time",
+        "bext.time_reference_low",
+        "bext.time_reference_high",
+        "bext.version",
+        "bext.umid",
+        "bext.coding_history",
+    ],
+    patterns: &["track_list.track.{n}", "user.{text}"],
+};
+
+/// `levl`
+pub const LEVL: ItemKeys = ItemKeys {
+    name: "levl",
+    keys: &[
+        "version",
+        "format",
+        "points_per_value",
+        "block_size",
+        "peak_channels",
+        "num_peak_frames",
+        "pos_peak_of_peaks",
+        "offset_to_peaks",
+        "timestamp",
+        "peak_data",
+    ],
+    patterns: &[],
+};
+
+/// `LIST-adtl`, the id of each sub-chunk.
+pub const LIST_ADTL: ItemKeys = ItemKeys {
+    name: "LIST-adtl",
+    keys: &[],
+    patterns: &["{id}"],
+};
+
+/// `LIST-INFO`, the id of each sub-chunk. Ex: `INAM`. Text which is not
+/// valid UTF-8 is followed by its guessed encoding. Ex: `INAM.encoding`
+pub const LIST_INFO: ItemKeys = ItemKeys {
+    name: "LIST-INFO",
+    keys: &[],
+    patterns: &["{id}", "{id}.encoding"],
+};
+
+/// `LIST-wavl`, the id of each segment followed by the totals.
+pub const LIST_WAVL: ItemKeys = ItemKeys {
+    name: "LIST-wavl",
+    keys: &["data size", "silence"],
+    patterns: &["{id}"],
+};
+
+/// `plst`, one key per segment, numbered from 1.
+pub const PLST: ItemKeys = ItemKeys {
+    name: "plst",
+    keys: &[],
+    patterns: &["segment {n}"],
+};
+
+/// `smpl`, `loop identifier` is followed by the identifier of each loop.
+pub const SMPL: ItemKeys = ItemKeys {
+    name: "smpl",
+    keys: &[
+        "manufacturer",
+        "product",
+        "sample_period",
+        "midi_unity_note",
+        "midi_pitch_fraction",
+        "smpte_format",
+        "smpte_offset",
+        "sample_loop_count",
+        "sampler_data_size",
+        "loop identifier",
+    ],
+    patterns: &["{n}"],
+};
+
+const SOUNDMINER_KEYS: &[&str] = &["version", "length", "blocks", "raw"];
+const SOUNDMINER_PATTERNS: &[&str] = &["text at {n}"];
+
+/// `SMED`, followed by the offset of each readable text.
+pub const SMED: ItemKeys = ItemKeys {
+    name: "SMED",
+    keys: SOUNDMINER_KEYS,
+    patterns: SOUNDMINER_PATTERNS,
+};
+
+/// `SMRD`, same as [`SMED`].
+pub const SMRD: ItemKeys = ItemKeys {
+    name: "SMRD",
+    keys: SOUNDMINER_KEYS,
+    patterns: SOUNDMINER_PATTERNS,
+};
+
+/// Chunks without items: `data`, `fact`, `FLLR`, `JUNK`, `MD5 ` and `PAD `.
+pub const NO_ITEMS: &[&str] = &["data", "fact", "FLLR", "JUNK", "MD5", "PAD"];
+
+/// Unknown chunks, a hex dump of the first bytes keyed by offset, and `...`
+/// if there are more bytes. The name is the chunk id.
+pub const UNKNOWN: ItemKeys = ItemKeys {
+    name: "",
+    keys: &["..."],
+    patterns: &["{hex}"],
+};
+
+/// Keys of every chunk with items.
+pub const ITEM_KEYS: &[ItemKeys] = &[
+    BEXT, CSET, CUE, DISP, DS64, FMT, INST, IXML, LEVL, LIST_INFO, LIST_ADTL, LIST_WAVL, PLST,
+    SMED, SMPL, SMRD,
+];
+
+/// Keys of the chunk named `name`. Returns `None` for chunks without items
+/// and unknown chunks, see [`NO_ITEMS`] and [`UNKNOWN`].
+pub fn item_keys(name: &str) -> Option<&'static ItemKeys> {
+    ITEM_KEYS.iter().find(|k| k.name == name)
+}
+
+/// Whether a [`TypeSchema`] describes a struct or an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    /// Members are the public fields.
+    Struct,
+    /// Members are the variants.
+    Enum,
+}
+
+/// A chunk data type, generated from its definition in [`crate::chunk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeSchema {
+    /// Module in [`crate::chunk`]. Ex: `bext`
+    pub module: &'static str,
+    /// Type name, without generic parameters. Ex: `Bext`
+    pub name: &'static str,
+    /// Struct or enum.
+    pub kind: TypeKind,
+    /// Doc comment, markdown.
+    pub doc: &'static str,
+    /// Public fields of a struct or variants of an enum, in definition order.
+    pub members: &'static [MemberSchema],
+}
+
+impl TypeSchema {
+    /// The field or variant named `name`.
+    pub fn member(&self, name: &str) -> Option<&'static MemberSchema> {
+        self.members.iter().find(|m| m.name == name)
+    }
+}
+
+/// A struct field or enum variant of a [`TypeSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemberSchema {
+    /// Field or variant name.
+    pub name: &'static str,
+    /// Rust type of a field. For variants the payload, ex: `FmtPcm` or
+    /// `{ offset: Option<u64>, size: u32 }`, empty for unit variants.
+    pub ty: &'static str,
+    /// Doc comment, markdown.
+    pub doc: &'static str,
+}
+
+/// First paragraph of a doc comment, on one line.
+pub fn summary(doc: &str) -> String {
+    doc.split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Every public chunk data type, in module order. Generated by the build
+/// script from the structs and enums in `src/chunk` deriving `Hash`, which
+/// skips iterators and builders.
+pub const TYPES: &[TypeSchema] = include!(concat!(env!("OUT_DIR"), "/chunk_schema.rs"));
+
+/// Returns [`TYPES`], a description of every chunk data type.
+pub const fn schema() -> &'static [TypeSchema] {
+    TYPES
+}
+
+/// The type named `name`, ex: `Bext`.
+pub fn type_schema(name: &str) -> Option<&'static TypeSchema> {
+    TYPES.iter().find(|t| t.name == name)
+}
+
+/// A JSON Schema (draft 2020-12) document with a definition for each of
+/// `types`, under `$defs`.
+///
+/// Structs are objects with a property for each field, enums a `oneOf` with
+/// a `const` for unit variants and the payload of other variants. The Rust
+/// type is kept as `x-rust-type`. Fields of types in `types` refer to their
+/// definition, types without a JSON equivalent only have `x-rust-type`.
+#[cfg(feature = "std")]
+pub fn to_json_schema(types: &[TypeSchema]) -> String {
+    let definitions = types
+        .iter()
+        .map(|ty| (ty.name.to_string(), json_definition(ty, types)))
+        .collect();
+    Value::Object(vec![
+        (
+            "$schema".to_string(),
+            Value::String("https://json-schema.org/draft/2020-12/schema".to_string()),
+        ),
+        (
+            "title".to_string(),
+            Value::String("wavrw chunk types".to_string()),
+        ),
+        ("$defs".to_string(), Value::Object(definitions)),
+    ])
+    .to_json()
+}
+
+#[cfg(feature = "std")]
+fn json_definition(ty: &TypeSchema, types: &[TypeSchema]) -> Value {
+    let mut entries = vec![
+        ("description".to_string(), Value::String(ty.doc.to_string())),
+        (
+            "x-rust-module".to_string(),
+            Value::String(ty.module.to_string()),
+        ),
+    ];
+    match ty.kind {
+        TypeKind::Struct => {
+            let properties = ty
+                .members
+                .iter()
+                .map(|m| (m.name.to_string(), json_member(m, types)))
+                .collect();
+            let required = ty
+                .members
+                .iter()
+                .filter(|m| !m.ty.starts_with("Option<"))
+                .map(|m| Value::String(m.name.to_string()))
+                .collect();
+            entries.push(("type".to_string(), Value::String("object".to_string())));
+            entries.push(("properties".to_string(), Value::Object(properties)));
+            entries.push(("required".to_string(), Value::Array(required)));
+        }
+        TypeKind::Enum => {
+            let variants = ty
+                .members
+                .iter()
+                .map(|m| {
+                    if m.ty.is_empty() {
+                        Value::Object(vec![
+                            ("const".to_string(), Value::String(m.name.to_string())),
+                            ("description".to_string(), Value::String(m.doc.to_string())),
+                        ])
+                    } else {
+                        let Value::Object(mut entries) = json_member(m, types) else {
+                            unreachable!("json_member returns an object")
+                        };
+                        entries.insert(0, ("title".to_string(), Value::String(m.name.to_string())));
+                        Value::Object(entries)
+                    }
+                })
+                .collect();
+            entries.push(("oneOf".to_string(), Value::Array(variants)));
+        }
+    }
+    Value::Object(entries)
+}
+
+/// Property for a field, or the payload of a variant.
+#[cfg(feature = "std")]
+fn json_member(member: &MemberSchema, types: &[TypeSchema]) -> Value {
+    let mut entries = vec![(
+        "description".to_string(),
+        Value::String(member.doc.to_string()),
+    )];
+    entries.extend(json_type(member.ty, types));
+    entries.push((
+        "x-rust-type".to_string(),
+        Value::String(member.ty.to_string()),
+    ));
+    Value::Object(entries)
+}
+
+/// JSON Schema keywords for the Rust type `ty`.
+#[cfg(feature = "std")]
+fn json_type(ty: &str, types: &[TypeSchema]) -> Vec<(String, Value)> {
+    let text = |value: &str| Value::String(value.to_string());
+    let number = |value: &str| Value::Number(value.to_string());
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return json_type(inner, types);
+    }
+    if let Some(inner) = ty.strip_prefix("Vec<").and_then(|t| t.strip_suffix('>')) {
+        return vec![
+            ("type".to_string(), text("array")),
+            ("items".to_string(), Value::Object(json_type(inner, types))),
+        ];
+    }
+    if let Some((inner, len)) = ty
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .and_then(|t| t.rsplit_once(';'))
+    {
+        return vec![
+            ("type".to_string(), text("array")),
+            ("items".to_string(), Value::Object(json_type(inner, types))),
+            ("minItems".to_string(), number(len.trim())),
+            ("maxItems".to_string(), number(len.trim())),
+        ];
+    }
+    if let Some(len) = ty
+        .strip_prefix("FixedString<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        return vec![
+            ("type".to_string(), text("string")),
+            ("maxLength".to_string(), number(len)),
+        ];
+    }
+    match ty {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => vec![
+            ("type".to_string(), text("integer")),
+            ("minimum".to_string(), number("0")),
+        ],
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => {
+            vec![("type".to_string(), text("integer"))]
+        }
+        "f32" | "f64" => vec![("type".to_string(), text("number"))],
+        "bool" => vec![("type".to_string(), text("boolean"))],
+        "String" | "char" | "FourCC" => vec![("type".to_string(), text("string"))],
+        _ if types.iter().any(|t| t.name == ty) => {
+            vec![("$ref".to_string(), Value::String(format!("#/$defs/{ty}")))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::chunk::cset::Cset;
+    use crate::chunk::cue::Cue;
+    use crate::chunk::disp::Disp;
+    use crate::chunk::ds64::Ds64;
+    use crate::chunk::fmt::FmtEnum;
+    use crate::chunk::inst::Inst;
+    use crate::chunk::levl::Levl;
+    use crate::chunk::plst::Plst;
+    use crate::chunk::smpl::Smpl;
+    use crate::chunk::wavl::ListWavl;
+    use crate::fingerprint::Xxh64;
+    use crate::testing::{Arbitrary, Rng};
+    use crate::{SizedChunkEnum, Summarizable, WaveFile};
+
+    /// Check the keys of `chunk` against `schema`.
+    fn check_keys(chunk: &impl Summarizable, schema: &ItemKeys) {
+        let keys: Vec<String> = chunk.items().map(|(key, _)| key).collect();
+        let again: Vec<String> = chunk.items().map(|(key, _)| key).collect();
+        assert_eq!(keys, again, "{} items are not deterministic", chunk.name());
+
+        let mut last = None;
+        for key in &keys {
+            assert!(
+                schema.contains(key),
+                "{}: unlisted key {key:?}",
+                chunk.name()
+            );
+            if let Some(index) = schema.keys.iter().position(|k| k == key) {
+                assert!(
+                    last.is_none_or(|last| index > last),
+                    "{}: key {key:?} out of order in {keys:?}",
+                    chunk.name()
+                );
+                last = Some(index);
+            }
+        }
+    }
+
+    fn check_arbitrary<T>(name: &str)
+    where
+        T: Arbitrary + Summarizable,
+    {
+        let schema = item_keys(name).unwrap();
+        let mut rng = Rng::new(0x5C4E_0000);
+        for _ in 0..64 {
+            let chunk = T::arbitrary(&mut rng);
+            assert_eq!(chunk.name(), name);
+            check_keys(&chunk, schema);
+        }
+    }
+
+    #[test]
+    fn items_follow_schema() {
+        check_arbitrary::<Cset>("CSET");
+        check_arbitrary::<Cue>("cue");
+        check_arbitrary::<Disp>("DISP");
+        check_arbitrary::<Ds64>("ds64");
+        check_arbitrary::<FmtEnum>("fmt");
+        check_arbitrary::<Inst>("inst");
+        check_arbitrary::<Levl>("levl");
+        check_arbitrary::<Plst>("plst");
+        check_arbitrary::<Smpl>("smpl");
+        check_arbitrary::<ListWavl>("LIST-wavl");
+        check_arbitrary::<crate::chunk::adtl::ListAdtl>("LIST-adtl");
+        #[cfg(feature = "broadcast")]
+        check_arbitrary::<crate::chunk::bext::Bext>("bext");
+        #[cfg(feature = "info")]
+        check_arbitrary::<crate::chunk::info::ListInfo>("LIST-INFO");
+
+        for path in [
+            "../test_wavs/example_a.wav",
+            "../test_wavs/1khz_sine_48k_mono_region_marker.wav",
+            "../test_wavs/1khz_sine_48k_mono_region_marker-bext_metadata.wav",
+        ] {
+            let file = BufReader::new(File::open(path).unwrap());
+            let mut wave = WaveFile::from_reader(file).unwrap();
+            for chunk in wave.iter_chunks() {
+                let chunk = chunk.unwrap();
+                dbg!(chunk.name());
+                if let SizedChunkEnum::Unknown(unknown) = &chunk {
+                    check_keys(unknown, &UNKNOWN);
+                } else if NO_ITEMS.contains(&chunk.name().as_str()) {
+                    assert_eq!(chunk.items().count(), 0, "{}", chunk.name());
+                } else {
+                    check_keys(&chunk, item_keys(&chunk.name()).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn patterns() {
+        assert!(matches("[{n}] name", "[12] name"));
+        assert!(!matches("[{n}] name", "[] name"));
+        assert!(!matches("[{n}] name", "[1] names"));
+        assert!(matches("{id}", "cue "));
+        assert!(!matches("{id}", "cue"));
+        assert!(matches("{hex}", "00F0"));
+        assert!(!matches("{hex}", "..."));
+        assert!(matches("user.{text}", "user.CATID"));
+        assert!(!matches("user.{text}", "user."));
+        assert!(matches("raw", "raw"));
+    }
+
+    /// Changing any listed key must increment `SCHEMA_VERSION`: update the
+    /// version and this hash together.
+    #[test]
+    fn schema_version_pinned() {
+        let mut hasher = Xxh64::new(0);
+        for schema in ITEM_KEYS.iter().chain([&UNKNOWN]) {
+            hasher.update(schema.name.as_bytes());
+            for key in schema.keys.iter().chain(schema.patterns) {
+                hasher.update(b"\0");
+                hasher.update(key.as_bytes());
+            }
+            hasher.update(b"\n");
+        }
+        for name in NO_ITEMS {
+            hasher.update(name.as_bytes());
+        }
+        let hash = hasher.finish();
+        dbg!(format!("{hash:#018X}"));
+        assert_eq!((SCHEMA_VERSION, hash), (2, 0xD26F_EC12_8CBA_0021));
+    }
+
+    #[test]
+    fn every_supported_chunk_listed() {
+        for chunk in crate::chunk::SUPPORTED
+            .iter()
+            .filter(|c| c.parent.is_none())
+        {
+            let name = chunk.name();
+            if name == "RIFF" {
+                continue;
+            }
+            assert!(
+                item_keys(&name).is_some() || NO_ITEMS.contains(&name.as_str()),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn chunk_types() {
+        let cset = type_schema("Cset").unwrap();
+        dbg!(cset);
+        assert_eq!((cset.module, cset.kind), ("cset", TypeKind::Struct));
+        let names: Vec<_> = cset.members.iter().map(|m| m.name).collect();
+        assert_eq!(names, ["code_page", "country_code", "language", "dialect"]);
+        assert_eq!(cset.member("country_code").unwrap().ty, "RiffCountryCode");
+        assert_eq!(
+            summary(cset.member("code_page").unwrap().doc),
+            "Specifies the code page used for file elements."
+        );
+
+        let loop_type = type_schema("LoopType").unwrap();
+        assert_eq!(loop_type.kind, TypeKind::Enum);
+        assert_eq!(loop_type.member("Forward").unwrap().ty, "");
+        assert_eq!(loop_type.member("Other").unwrap().ty, "u32");
+        let segment = type_schema("WavlSegment").unwrap();
+        assert_eq!(
+            segment.member("Data").unwrap().ty,
+            "{ offset: Option<u64>, size: u32 }"
+        );
+
+        // generic parameters are dropped, attributes and comments skipped
+        #[cfg(feature = "info")]
+        assert_eq!(type_schema("Info").unwrap().members.len(), 2);
+        #[cfg(feature = "broadcast")]
+        {
+            let bext = type_schema("Bext").unwrap();
+            let history = bext.member("coding_history").unwrap();
+            assert_eq!((history.ty, history.doc), ("String", "History coding"));
+            assert_eq!(bext.member("umid").unwrap().ty, "[u8; 64]");
+        }
+
+        // not data types
+        assert!(type_schema("CueBuilder").is_none());
+        assert!(type_schema("FmtPcmIterator").is_none());
+        assert!(
+            schema().iter().all(|t| !t.doc.is_empty()),
+            "undocumented type"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn json_schema() {
+        let json = to_json_schema(TYPES);
+        let document = crate::json::Value::parse(&json).unwrap();
+        let definitions = document.get("$defs").unwrap();
+        let code_page = definitions
+            .get("Cset")
+            .and_then(|c| c.get("properties"))
+            .and_then(|p| p.get("code_page"))
+            .unwrap();
+        dbg!(code_page);
+        assert_eq!(code_page.get("type").unwrap().as_text().unwrap(), "integer");
+        let country = definitions
+            .get("Cset")
+            .and_then(|c| c.get("properties"))
+            .and_then(|p| p.get("country_code"))
+            .unwrap();
+        assert_eq!(
+            country.get("$ref").unwrap().as_text().unwrap(),
+            "#/$defs/RiffCountryCode"
+        );
+        assert!(definitions.get("LoopType").unwrap().get("oneOf").is_some());
+    }
+}
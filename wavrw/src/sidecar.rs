@@ -0,0 +1,755 @@
+//! Export metadata to a sidecar document and import it onto a file.
+//!
+//! A [`Sidecar`] holds every metadata chunk of a file, in file order, so
+//! audio and metadata can travel separately. `bext` and `LIST-INFO` are
+//! kept as named text fields and `iXML` as its document, so they can be
+//! read and edited by hand. Other chunks, including ones wavrw doesn't
+//! know, are kept as base64 encoded bytes.
+//!
+//! Chunks which describe the audio itself are not exported: `fmt `,
+//! `fact`, `data`, `ds64`, `levl` and `MD5 `, as well as `JUNK`, `FLLR`
+//! and `PAD ` padding.
+//!
+//! Sidecars are written as JSON with [`Sidecar::to_json()`] or XML with
+//! [`Sidecar::to_xml()`], and read from either with [`Sidecar::from_str()`].
+//!
+//! ```json
+//! {
+//!   "wavrw_sidecar": 1,
+//!   "chunks": [
+//!     {"id": "bext", "fields": {"description": "Rain", "time_reference": "0"}},
+//!     {"id": "LIST", "list_type": "INFO", "fields": {"INAM": "Rain"}},
+//!     {"id": "iXML", "text": "<BWFXML>...</BWFXML>"},
+//!     {"id": "cue ", "base64": "AQAAAA..."}
+//!   ]
+//! }
+//! ```
+//!
+//! ```xml
+//! <WAVRW_SIDECAR version="1">
+//!   <CHUNK id="bext">
+//!     <FIELD name="description" value="Rain"/>
+//!   </CHUNK>
+//!   <CHUNK id="iXML">
+//!     <TEXT>&lt;BWFXML&gt;...&lt;/BWFXML&gt;</TEXT>
+//!   </CHUNK>
+//!   <CHUNK id="cue ">
+//!     <BASE64>AQAAAA...</BASE64>
+//!   </CHUNK>
+//! </WAVRW_SIDECAR>
+//! ```
+//!
+//! [`Sidecar::apply()`] writes a copy of a file with the sidecar chunks,
+//! replacing chunks with the same id (and list type for `LIST`) and adding
+//! the others. Chunks of the file not in the sidecar are kept.
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::{BufReader, Cursor};
+//! use wavrw::sidecar::Sidecar;
+//!
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! let json = Sidecar::from_wave(&mut wave)?.to_json();
+//!
+//! let sidecar: Sidecar = json.parse()?;
+//! let mut output = Cursor::new(Vec::new());
+//! sidecar.apply(&mut wave, &mut output)?;
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use core::str::FromStr;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::Bext;
+#[cfg(feature = "info")]
+use crate::chunk::info::ListInfo;
+#[cfg(any(feature = "broadcast", feature = "info"))]
+use crate::edit::chunk_data;
+use crate::edit::inject_chunks;
+#[cfg(feature = "broadcast")]
+use crate::fixedstring::FixedString;
+use crate::json::Value;
+use crate::xml::Element;
+#[cfg(feature = "info")]
+use crate::ChunkID;
+#[cfg(any(feature = "broadcast", feature = "info"))]
+use crate::SizedChunkEnum;
+use crate::{FourCC, WaveFile, WaveFileError};
+
+/// Format version written to and required in sidecar documents.
+pub const VERSION: u32 = 1;
+
+/// Chunks describing the audio or padding, not exported.
+const SKIPPED: [FourCC; 9] = [
+    FourCC(*b"fmt "),
+    FourCC(*b"fact"),
+    FourCC(*b"data"),
+    FourCC(*b"ds64"),
+    FourCC(*b"levl"),
+    FourCC(*b"MD5 "),
+    FourCC(*b"JUNK"),
+    FourCC(*b"FLLR"),
+    FourCC(*b"PAD "),
+];
+
+const LIST: FourCC = FourCC(*b"LIST");
+const INFO: FourCC = FourCC(*b"INFO");
+const IXML: FourCC = FourCC(*b"iXML");
+const BEXT: FourCC = FourCC(*b"bext");
+
+/// Metadata chunks of a file, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sidecar {
+    /// Chunks in file order.
+    pub chunks: Vec<SidecarChunk>,
+}
+
+/// One chunk of a [`Sidecar`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SidecarChunk {
+    /// `bext` fields as (name, value), named like the [`Bext`](crate::chunk::bext::Bext)
+    /// fields. Loudness values are hundredths, as stored, `umid` is hex.
+    Bext(Vec<(String, String)>),
+
+    /// `LIST-INFO` subchunk ids and text.
+    Info(Vec<(FourCC, String)>),
+
+    /// `iXML` document text.
+    Ixml(String),
+
+    /// Any other chunk and its data. `LIST` data starts with the list type.
+    Raw {
+        /// Chunk id.
+        id: FourCC,
+        /// Chunk data, without chunk id and size.
+        data: Vec<u8>,
+    },
+}
+
+impl Sidecar {
+    /// Read the metadata chunks of `wave`.
+    ///
+    /// `bext` and `LIST-INFO` chunks which can't be parsed, or whose parsers
+    /// are disabled, are exported as bytes.
+    pub fn from_wave<R>(wave: &mut WaveFile<R>) -> Result<Sidecar, WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+    {
+        let mut chunks = Vec::new();
+        for lazy in wave.scan_chunks()? {
+            if SKIPPED.contains(&lazy.id) {
+                continue;
+            }
+            if let Some(chunk) = parsed_chunk(wave, &lazy) {
+                chunks.push(chunk);
+                continue;
+            }
+
+            if lazy.large_size.is_some() {
+                return Err(WaveFileError::parse(
+                    Some(lazy.offset),
+                    format!("{} chunk too large to export", lazy.name()),
+                ));
+            }
+            wave.bytes.seek(SeekFrom::Start(lazy.offset + 8))?;
+            let mut data = Vec::new();
+            (&mut wave.bytes)
+                .take(u64::from(lazy.size))
+                .read_to_end(&mut data)?;
+            if data.len() != lazy.size as usize {
+                return Err(WaveFileError::parse(
+                    Some(lazy.offset),
+                    format!("{} chunk data is truncated", lazy.name()),
+                ));
+            }
+            let chunk = match String::from_utf8(data) {
+                Ok(text) if lazy.id == IXML => SidecarChunk::Ixml(text),
+                Ok(text) => SidecarChunk::Raw {
+                    id: lazy.id,
+                    data: text.into_bytes(),
+                },
+                Err(err) => SidecarChunk::Raw {
+                    id: lazy.id,
+                    data: err.into_bytes(),
+                },
+            };
+            chunks.push(chunk);
+        }
+        Ok(Sidecar { chunks })
+    }
+
+    /// Write a copy of `wave` to `writer` with the sidecar chunks, see the
+    /// [module documentation](self).
+    ///
+    /// Returns an error before anything is written if a field doesn't fit
+    /// its chunk.
+    pub fn apply<R, W>(&self, wave: &mut WaveFile<R>, writer: W) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        let chunks = self
+            .chunks
+            .iter()
+            .map(SidecarChunk::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let chunks: Vec<(FourCC, &[u8])> =
+            chunks.iter().map(|(id, data)| (*id, &data[..])).collect();
+        wave.bytes.seek(SeekFrom::Start(wave.chunks_start - 12))?;
+        inject_chunks(&mut wave.bytes, writer, &chunks)
+    }
+
+    /// Write as a JSON document.
+    pub fn to_json(&self) -> String {
+        let text = |text: &str| Value::String(text.to_string());
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let mut entries = vec![("id".to_string(), text(&chunk.id().to_string()))];
+                match chunk {
+                    SidecarChunk::Bext(fields) => {
+                        let fields = fields.iter().map(|(n, v)| (n.clone(), text(v))).collect();
+                        entries.push(("fields".to_string(), Value::Object(fields)));
+                    }
+                    SidecarChunk::Info(fields) => {
+                        let fields = fields
+                            .iter()
+                            .map(|(id, v)| (id.to_string(), text(v)))
+                            .collect();
+                        entries.push(("list_type".to_string(), text("INFO")));
+                        entries.push(("fields".to_string(), Value::Object(fields)));
+                    }
+                    SidecarChunk::Ixml(document) => {
+                        entries.push(("text".to_string(), text(document)));
+                    }
+                    SidecarChunk::Raw { data, .. } => {
+                        entries.push(("base64".to_string(), text(&base64_encode(data))));
+                    }
+                }
+                Value::Object(entries)
+            })
+            .collect();
+        Value::Object(vec![
+            (
+                "wavrw_sidecar".to_string(),
+                Value::Number(VERSION.to_string()),
+            ),
+            ("chunks".to_string(), Value::Array(chunks)),
+        ])
+        .to_json()
+    }
+
+    /// Write as an XML document.
+    ///
+    /// Field values are written as attributes, so leading and trailing
+    /// whitespace is kept.
+    pub fn to_xml(&self) -> String {
+        let element = |name: &str, attributes: &[(&str, &str)], text: &str| Element {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+                .collect(),
+            text: text.to_string(),
+            children: Vec::new(),
+        };
+        let field =
+            |name: &str, value: &str| element("FIELD", &[("name", name), ("value", value)], "");
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let id = chunk.id().to_string();
+                let mut chunk_element = element("CHUNK", &[("id", &id)], "");
+                match chunk {
+                    SidecarChunk::Bext(fields) => {
+                        chunk_element.children = fields.iter().map(|(n, v)| field(n, v)).collect();
+                    }
+                    SidecarChunk::Info(fields) => {
+                        chunk_element
+                            .attributes
+                            .push(("list_type".to_string(), "INFO".to_string()));
+                        chunk_element.children = fields
+                            .iter()
+                            .map(|(id, v)| field(&id.to_string(), v))
+                            .collect();
+                    }
+                    SidecarChunk::Ixml(document) => {
+                        chunk_element.children = vec![element("TEXT", &[], document)];
+                    }
+                    SidecarChunk::Raw { data, .. } => {
+                        chunk_element.children = vec![element("BASE64", &[], &base64_encode(data))];
+                    }
+                }
+                chunk_element
+            })
+            .collect();
+        Element {
+            name: "WAVRW_SIDECAR".to_string(),
+            attributes: vec![("version".to_string(), VERSION.to_string())],
+            text: String::new(),
+            children: chunks,
+        }
+        .to_xml()
+    }
+
+    /// Read a JSON document written by [`Sidecar::to_json()`].
+    pub fn from_json(document: &str) -> Result<Sidecar, WaveFileError> {
+        let root = Value::parse(document).map_err(|err| sidecar_error(&err.to_string()))?;
+        check_version(root.get("wavrw_sidecar").and_then(Value::as_text))?;
+        let Some(Value::Array(values)) = root.get("chunks") else {
+            return Err(sidecar_error("missing chunks list"));
+        };
+        let mut chunks = Vec::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            let text = |key: &str| value.get(key).and_then(Value::as_text);
+            let fields = match value.get("fields") {
+                Some(Value::Object(entries)) => Some(
+                    entries
+                        .iter()
+                        .map(|(name, value)| {
+                            let value = value.as_text().ok_or_else(|| {
+                                sidecar_error(&format!(
+                                    "chunk {}: field {name} is not text",
+                                    index + 1
+                                ))
+                            })?;
+                            Ok((name.clone(), value))
+                        })
+                        .collect::<Result<Vec<_>, WaveFileError>>()?,
+                ),
+                _ => None,
+            };
+            let chunk = SidecarChunk::new(
+                text("id"),
+                text("list_type"),
+                fields,
+                text("text"),
+                text("base64"),
+            )
+            .map_err(|err| sidecar_error(&format!("chunk {}: {err}", index + 1)))?;
+            chunks.push(chunk);
+        }
+        Ok(Sidecar { chunks })
+    }
+
+    /// Read an XML document written by [`Sidecar::to_xml()`].
+    pub fn from_xml(document: &str) -> Result<Sidecar, WaveFileError> {
+        let root = Element::parse(document).map_err(|err| sidecar_error(&err.to_string()))?;
+        if root.name != "WAVRW_SIDECAR" {
+            return Err(sidecar_error("root element is not WAVRW_SIDECAR"));
+        }
+        check_version(attribute(&root, "version"))?;
+        let mut chunks = Vec::new();
+        for (index, element) in root
+            .children
+            .iter()
+            .filter(|e| e.name == "CHUNK")
+            .enumerate()
+        {
+            let fields: Vec<(String, String)> = element
+                .children
+                .iter()
+                .filter(|e| e.name == "FIELD")
+                .map(|e| {
+                    (
+                        attribute(e, "name").unwrap_or_default(),
+                        attribute(e, "value").unwrap_or_default(),
+                    )
+                })
+                .collect();
+            let chunk = SidecarChunk::new(
+                attribute(element, "id"),
+                attribute(element, "list_type"),
+                (!fields.is_empty()).then_some(fields),
+                element.child("TEXT").map(|e| e.text.clone()),
+                element.child("BASE64").map(|e| e.text.clone()),
+            )
+            .map_err(|err| sidecar_error(&format!("chunk {}: {err}", index + 1)))?;
+            chunks.push(chunk);
+        }
+        Ok(Sidecar { chunks })
+    }
+}
+
+impl FromStr for Sidecar {
+    type Err = WaveFileError;
+
+    /// Read a sidecar from XML, or JSON if `s` starts with `{`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start().starts_with('{') {
+            Sidecar::from_json(s)
+        } else {
+            Sidecar::from_xml(s)
+        }
+    }
+}
+
+impl SidecarChunk {
+    /// Chunk id. Ex: `LIST`
+    pub fn id(&self) -> FourCC {
+        match self {
+            SidecarChunk::Bext(_) => BEXT,
+            SidecarChunk::Info(_) => LIST,
+            SidecarChunk::Ixml(_) => IXML,
+            SidecarChunk::Raw { id, .. } => *id,
+        }
+    }
+
+    /// Chunk from the parts of a sidecar document entry.
+    fn new(
+        id: Option<String>,
+        list_type: Option<String>,
+        fields: Option<Vec<(String, String)>>,
+        text: Option<String>,
+        base64: Option<String>,
+    ) -> Result<SidecarChunk, String> {
+        let id = FourCC::from_str(&id.ok_or("missing id")?).map_err(|err| err.to_string())?;
+        let list_type = list_type
+            .map(|t| FourCC::from_str(&t).map_err(|err| err.to_string()))
+            .transpose()?;
+        match (fields, text, base64) {
+            (Some(fields), None, None) if id == BEXT => Ok(SidecarChunk::Bext(fields)),
+            (Some(fields), None, None) if id == LIST && list_type == Some(INFO) => {
+                let fields = fields
+                    .into_iter()
+                    .map(|(id, text)| Ok((FourCC::from_str(&id).map_err(|e| e.to_string())?, text)))
+                    .collect::<Result<_, String>>()?;
+                Ok(SidecarChunk::Info(fields))
+            }
+            (Some(_), None, None) => Err(format!("{id} chunk can't be written from fields")),
+            (None, Some(text), None) if id == IXML => Ok(SidecarChunk::Ixml(text)),
+            (None, Some(_), None) => Err(format!("{id} chunk can't be written from text")),
+            (None, None, Some(base64)) => Ok(SidecarChunk::Raw {
+                id,
+                data: base64_decode(&base64)?,
+            }),
+            (None, None, None) => Err(format!("{id} chunk has no fields, text or base64 data")),
+            _ => Err(format!(
+                "{id} chunk has more than one of fields, text and base64 data"
+            )),
+        }
+    }
+
+    /// Chunk id and data.
+    fn to_bytes(&self) -> Result<(FourCC, Vec<u8>), WaveFileError> {
+        match self {
+            SidecarChunk::Bext(fields) => bext_bytes(fields).map(|data| (BEXT, data)),
+            SidecarChunk::Info(fields) => info_bytes(fields).map(|data| (LIST, data)),
+            SidecarChunk::Ixml(document) => Ok((IXML, document.as_bytes().to_vec())),
+            SidecarChunk::Raw { id, data } => Ok((*id, data.clone())),
+        }
+    }
+}
+
+/// `bext` or `LIST-INFO` as fields, `None` for other chunks or if they
+/// can't be parsed.
+#[cfg(any(feature = "broadcast", feature = "info"))]
+fn parsed_chunk<R>(wave: &mut WaveFile<R>, lazy: &crate::LazyChunk) -> Option<SidecarChunk>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    if lazy.id != BEXT && lazy.list_type != Some(INFO) {
+        return None;
+    }
+    match lazy.parse(wave).ok()? {
+        #[cfg(feature = "broadcast")]
+        SizedChunkEnum::Bext(bext) => Some(SidecarChunk::Bext(bext_fields(&bext.data))),
+        #[cfg(feature = "info")]
+        SizedChunkEnum::Info(info) => Some(SidecarChunk::Info(
+            info.data
+                .chunks
+                .iter()
+                .map(|c| (c.id(), c.text()))
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(feature = "broadcast", feature = "info")))]
+fn parsed_chunk<R>(_wave: &mut WaveFile<R>, _lazy: &crate::LazyChunk) -> Option<SidecarChunk>
+where
+    R: Read + Seek + Debug + BufRead,
+{
+    None
+}
+
+/// All `bext` fields except reserved bytes, as stored.
+#[cfg(feature = "broadcast")]
+fn bext_fields(bext: &Bext) -> Vec<(String, String)> {
+    [
+        ("description", bext.description.to_string()),
+        ("originator", bext.originator.to_string()),
+        (
+            "originator_reference",
+            bext.originator_reference.to_string(),
+        ),
+        ("origination_date", bext.origination_date.to_string()),
+        ("origination_time", bext.origination_time.to_string()),
+        ("time_reference", bext.time_reference.to_string()),
+        ("version", bext.version.to_string()),
+        ("umid", hex::encode(bext.umid)),
+        ("loudness_value", bext.loudness_value.to_string()),
+        ("loudness_range", bext.loudness_range.to_string()),
+        ("max_true_peak_level", bext.max_true_peak_level.to_string()),
+        (
+            "max_momentary_loudness",
+            bext.max_momentary_loudness.to_string(),
+        ),
+        (
+            "max_short_term_loudness",
+            bext.max_short_term_loudness.to_string(),
+        ),
+        ("coding_history", bext.coding_history.clone()),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value))
+    .collect()
+}
+
+/// `bext` chunk data from fields, missing fields are empty or zero.
+#[cfg(feature = "broadcast")]
+fn bext_bytes(fields: &[(String, String)]) -> Result<Vec<u8>, WaveFileError> {
+    fn fixed<const N: usize>(name: &str, value: &str) -> Result<FixedString<N>, WaveFileError> {
+        FixedString::try_from_str(value)
+            .map_err(|err| sidecar_error(&format!("bext {name}: {err}")))
+    }
+    fn number<T: FromStr>(name: &str, value: &str) -> Result<T, WaveFileError> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| sidecar_error(&format!("bext {name}: invalid number {value:?}")))
+    }
+
+    let mut bext = Bext::default();
+    for (name, value) in fields {
+        match name.as_str() {
+            "description" => bext.description = fixed(name, value)?,
+            "originator" => bext.originator = fixed(name, value)?,
+            "originator_reference" => bext.originator_reference = fixed(name, value)?,
+            "origination_date" => bext.origination_date = fixed(name, value)?,
+            "origination_time" => bext.origination_time = fixed(name, value)?,
+            "time_reference" => bext.time_reference = number(name, value)?,
+            "version" => bext.version = number(name, value)?,
+            "umid" => {
+                let bytes = hex::decode(value)
+                    .ok()
+                    .filter(|b| b.len() <= 64)
+                    .ok_or_else(|| sidecar_error("bext umid: expected up to 64 hex bytes"))?;
+                bext.umid = [0_u8; 64];
+                bext.umid[..bytes.len()].copy_from_slice(&bytes);
+            }
+            "loudness_value" => bext.loudness_value = number(name, value)?,
+            "loudness_range" => bext.loudness_range = number(name, value)?,
+            "max_true_peak_level" => bext.max_true_peak_level = number(name, value)?,
+            "max_momentary_loudness" => bext.max_momentary_loudness = number(name, value)?,
+            "max_short_term_loudness" => bext.max_short_term_loudness = number(name, value)?,
+            "coding_history" => bext.coding_history.clone_from(value),
+            _ => return Err(sidecar_error(&format!("unknown bext field {name:?}"))),
+        }
+    }
+    chunk_data(&bext)
+}
+
+#[cfg(not(feature = "broadcast"))]
+fn bext_bytes(_fields: &[(String, String)]) -> Result<Vec<u8>, WaveFileError> {
+    Err(sidecar_error("bext support is not enabled"))
+}
+
+/// `LIST-INFO` chunk data from subchunk ids and text.
+#[cfg(feature = "info")]
+fn info_bytes(fields: &[(FourCC, String)]) -> Result<Vec<u8>, WaveFileError> {
+    let mut info = ListInfo::builder().build()?;
+    for (id, text) in fields {
+        info.set(*id, text)?;
+    }
+    chunk_data(&info)
+}
+
+#[cfg(not(feature = "info"))]
+fn info_bytes(_fields: &[(FourCC, String)]) -> Result<Vec<u8>, WaveFileError> {
+    Err(sidecar_error("LIST-INFO support is not enabled"))
+}
+
+fn sidecar_error(message: &str) -> WaveFileError {
+    WaveFileError::parse(None, format!("sidecar: {message}"))
+}
+
+fn check_version(version: Option<String>) -> Result<(), WaveFileError> {
+    match version {
+        Some(version) if version == VERSION.to_string() => Ok(()),
+        Some(version) => Err(sidecar_error(&format!(
+            "unsupported version {version}, expected {VERSION}"
+        ))),
+        None => Err(sidecar_error("missing version")),
+    }
+}
+
+fn attribute(element: &Element, name: &str) -> Option<String> {
+    element
+        .attributes
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.clone())
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64 with padding.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for group in data.chunks(3) {
+        let bytes = [
+            group[0],
+            group.get(1).copied().unwrap_or(0),
+            group.get(2).copied().unwrap_or(0),
+        ];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            if index <= group.len() {
+                let sextet = (bits >> (18 - 6 * index)) & 0x3F;
+                out.push(char::from(BASE64[sextet as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decode standard base64, ignoring whitespace.
+fn base64_decode(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let mut bits = 0_u32;
+    let mut count = 0;
+    let mut padding = 0;
+    for c in text.chars().filter(|c| !c.is_ascii_whitespace()) {
+        if c == '=' {
+            padding += 1;
+            continue;
+        }
+        let value = BASE64
+            .iter()
+            .position(|b| char::from(*b) == c)
+            .filter(|_| padding == 0)
+            .ok_or_else(|| format!("invalid base64 character {c:?}"))?;
+        // position in a 64 entry table
+        #[allow(clippy::cast_possible_truncation)]
+        let value = value as u32;
+        bits = (bits << 6) | value;
+        count += 1;
+        if count == 4 {
+            out.extend_from_slice(&bits.to_be_bytes()[1..]);
+            bits = 0;
+            count = 0;
+        }
+    }
+    match (count, padding) {
+        (0, 0) => {}
+        (2, 0 | 2) => out.push((bits >> 4) as u8),
+        (3, 0 | 1) => out.extend_from_slice(&(bits >> 2).to_be_bytes()[2..]),
+        _ => return Err("invalid base64 length".to_string()),
+    }
+    Ok(out)
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base64() {
+        for (data, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"\xFF\xEF\x00", "/+8A"),
+        ] {
+            assert_eq!(base64_encode(data), text);
+            assert_eq!(base64_decode(text).unwrap(), data);
+        }
+        assert_eq!(base64_decode("Zm9v\n Yg").unwrap(), b"foob");
+        assert!(base64_decode("Zm9v!").is_err());
+        assert!(base64_decode("Z").is_err());
+        assert!(base64_decode("Zg==Zg").is_err());
+    }
+
+    #[cfg(all(feature = "broadcast", feature = "info"))]
+    #[test]
+    fn roundtrip_example_a() {
+        use std::fs::File;
+        use std::io::{BufReader, Cursor};
+
+        use crate::testing::hex_to_cursor;
+
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let sidecar = Sidecar::from_wave(&mut wave).unwrap();
+        dbg!(&sidecar
+            .chunks
+            .iter()
+            .map(SidecarChunk::id)
+            .collect::<Vec<_>>());
+        assert!(matches!(sidecar.chunks[0], SidecarChunk::Bext(_)));
+        assert!(sidecar
+            .chunks
+            .iter()
+            .any(|c| matches!(c, SidecarChunk::Info(_))));
+        assert!(sidecar
+            .chunks
+            .iter()
+            .any(|c| matches!(c, SidecarChunk::Ixml(_))));
+        assert!(!sidecar.chunks.iter().any(|c| SKIPPED.contains(&c.id())));
+
+        let json = sidecar.to_json();
+        assert_eq!(Sidecar::from_str(&json).unwrap(), sidecar);
+        let xml = sidecar.to_xml();
+        assert_eq!(Sidecar::from_str(&xml).unwrap(), sidecar);
+
+        // RIFF WAVE with only a `fmt ` chunk
+        let target = hex_to_cursor(
+            "52494646 24000000 57415645 666D7420 10000000 01000100 80BB0000 80BB0000 02001000",
+        );
+        let mut target = WaveFile::from_reader(target).unwrap();
+        let mut output = Cursor::new(Vec::new());
+        sidecar.apply(&mut target, &mut output).unwrap();
+        output.set_position(0);
+        let mut imported = WaveFile::from_reader(output).unwrap();
+        assert_eq!(Sidecar::from_wave(&mut imported).unwrap(), sidecar);
+        assert_eq!(imported.metadata().sample_rate(), Some(48000));
+    }
+
+    #[test]
+    fn read_errors() {
+        let err = |text: &str| Sidecar::from_str(text).unwrap_err().to_string();
+        assert!(err(r#"{"wavrw_sidecar": 2, "chunks": []}"#).contains("unsupported version 2"));
+        assert!(err(r#"{"chunks": []}"#).contains("missing version"));
+        assert_eq!(
+            err(r#"{"wavrw_sidecar": 1, "chunks": [{"id": "cue ", "fields": {}}]}"#),
+            "Parse: sidecar: chunk 1: cue  chunk can't be written from fields"
+        );
+        assert!(err(r#"{"wavrw_sidecar": 1, "chunks": [{"id": "cue "}]}"#).contains("no fields"));
+        assert!(err(r#"<WAVRW_SIDECAR version="1"><CHUNK id="smpl"><BASE64>*</BASE64></CHUNK></WAVRW_SIDECAR>"#)
+            .contains("invalid base64"));
+        assert!(err("<OTHER/>").contains("not WAVRW_SIDECAR"));
+    }
+
+    #[cfg(feature = "broadcast")]
+    #[test]
+    fn bext_field_errors() {
+        let fields = vec![("originator".to_string(), "x".repeat(33))];
+        assert!(bext_bytes(&fields).is_err());
+        let fields = vec![("version".to_string(), "two".to_string())];
+        assert!(bext_bytes(&fields).is_err());
+        let fields = vec![("unknown".to_string(), String::new())];
+        assert!(bext_bytes(&fields).is_err());
+    }
+}
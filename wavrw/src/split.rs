@@ -0,0 +1,380 @@
+//! Split multichannel WAVE files into mono files, or extract channels.
+//!
+//! [`split()`] writes selected channels of a [`WaveFile`] to new files,
+//! reading the audio only once. Metadata is kept and adjusted to the new
+//! channels:
+//!
+//! - `fmt ` gets the new channel count, block align and byte rate. For
+//!   `WAVE_FORMAT_EXTENSIBLE` the channel mask keeps the speaker positions
+//!   of the selected channels.
+//! - `data` holds only the selected channels, in the order given.
+//! - `iXML` `TRACK_LIST` keeps the tracks of the selected channels. When
+//!   writing several files, `FILE_SET` numbers them. See
+//!   [`Ixml::with_channels()`][crate::chunk::ixml::Ixml::with_channels].
+//! - `ds64`, `levl` and `MD5 ` describe the original audio and are left out.
+//!
+//! All other chunks, including `bext`, `fact` and `cue `, are copied
+//! unchanged, positions in sample frames are the same in every file.
+//! Only PCM and IEEE float audio can be split. Files are written as `RIFF`,
+//! so each must be smaller than 4 GiB.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! # use wavrw::testing::hex_to_cursor;
+//! // stereo 16 bit PCM with 2 frames
+//! let input = hex_to_cursor(
+//!     "52494646 2C000000 57415645 \
+//!     666D7420 10000000 0100 0200 80BB0000 00EE0200 0400 1000 \
+//!     64617461 08000000 0100 0200 0300 0400",
+//! );
+//! let mut wave = wavrw::WaveFile::from_reader(input)?;
+//! let mut outputs = vec![(vec![0], Cursor::new(Vec::new())), (vec![1], Cursor::new(Vec::new()))];
+//! wavrw::split::split(&mut wave, &mut outputs)?;
+//!
+//! let (_, mut right) = outputs.remove(1);
+//! right.set_position(0);
+//! let mut right = wavrw::WaveFile::from_reader(right)?;
+//! assert_eq!(right.metadata().channels(), Some(1));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use binrw::BinWrite;
+
+use crate::chunk::data::Data;
+use crate::chunk::ds64::Ds64;
+use crate::chunk::fmt::{FmtChunk, FmtEnum, Tag};
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::Ixml;
+use crate::chunk::levl::Levl;
+use crate::chunk::md5::Md5;
+#[cfg(feature = "ixml")]
+use crate::edit::write_chunk;
+use crate::edit::{copy_chunk_data, finish_riff};
+use crate::samples::SampleFormat;
+use crate::{KnownChunkID, WaveFile, WaveFileError};
+
+/// Write the `channels` of `wave` to each writer of `outputs`.
+///
+/// Channels are counted from 0 and may be listed in any order. See the
+/// [module documentation](self).
+pub fn split<R, W>(
+    wave: &mut WaveFile<R>,
+    outputs: &mut [(Vec<usize>, W)],
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+    W: Write + Seek,
+{
+    let metadata = wave.metadata();
+    let fmt = metadata
+        .fmt()
+        .cloned()
+        .ok_or_else(|| WaveFileError::parse(None, "no fmt chunk"))?;
+    #[cfg(feature = "ixml")]
+    let ixml = metadata.ixml().cloned();
+    let format = SampleFormat::from_fmt(&fmt).ok_or_else(|| {
+        WaveFileError::parse(None, format!("can't split {} samples", fmt.format_tag()))
+    })?;
+    let channel_count = usize::from(fmt.channels());
+    if outputs.is_empty() {
+        return Err(WaveFileError::parse(None, "no outputs"));
+    }
+    for (channels, _) in outputs.iter() {
+        if channels.is_empty() {
+            return Err(WaveFileError::parse(None, "no channels selected"));
+        }
+        if let Some(channel) = channels.iter().find(|c| **c >= channel_count) {
+            return Err(WaveFileError::parse(
+                None,
+                format!("channel {channel} out of range, the file has {channel_count} channels"),
+            ));
+        }
+    }
+
+    let mut starts = Vec::with_capacity(outputs.len());
+    for (_, writer) in outputs.iter_mut() {
+        starts.push(writer.stream_position()?);
+        writer.write_all(b"RIFF")?;
+        // placeholder, updated after all chunks are written
+        writer.write_all(&0_u32.to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+    }
+
+    for chunk in wave.scan_chunks()? {
+        match chunk.id {
+            id if id == Ds64::ID || id == Levl::ID || id == Md5::ID => (),
+            id if id == FmtEnum::ID => {
+                for (channels, writer) in outputs.iter_mut() {
+                    let fmt = fmt_for_channels(&fmt, channels.len(), format, |mask| {
+                        // channel n has the nth set bit of the mask, if any
+                        let speakers: Vec<u32> = (0..32)
+                            .map(|bit| 1 << bit)
+                            .filter(|b| mask & b != 0)
+                            .collect();
+                        channels
+                            .iter()
+                            .filter_map(|c| speakers.get(*c))
+                            .fold(0, |mask, speaker| mask | speaker)
+                    })?;
+                    let fmt = FmtChunk::new(fmt)?;
+                    fmt.write(writer)?;
+                }
+            }
+            id if id == Data::ID => {
+                wave.bytes.seek(SeekFrom::Start(chunk.offset + 8))?;
+                let data = (&mut wave.bytes).take(chunk.large_size());
+                split_data(data, outputs, channel_count, format)?;
+            }
+            #[cfg(feature = "ixml")]
+            id if id == Ixml::ID && ixml.is_some() => {
+                let ixml = ixml.as_ref().expect("checked by match guard");
+                let total_files = outputs.len();
+                for (index, (channels, writer)) in outputs.iter_mut().enumerate() {
+                    let mut edited = ixml.with_channels(channels);
+                    if total_files > 1 {
+                        edited = edited.and_then(|ixml| {
+                            ixml.with_file_set_index(&(index + 1).to_string(), total_files)
+                        });
+                    }
+                    // keep documents wavrw can't parse as they are
+                    let bytes = edited.map_or_else(|_| ixml.raw_bytes.clone(), |e| e.raw_bytes);
+                    let size = u32::try_from(bytes.len())
+                        .map_err(|_| WaveFileError::parse(None, "iXML too large"))?;
+                    write_chunk(writer, Ixml::ID, size, &bytes)?;
+                }
+            }
+            id => {
+                for (_, writer) in outputs.iter_mut() {
+                    wave.bytes.seek(SeekFrom::Start(chunk.offset + 8))?;
+                    writer.write_all(&id.0)?;
+                    writer.write_all(&chunk.size.to_le_bytes())?;
+                    copy_chunk_data(&mut wave.bytes, writer, chunk.offset, id, chunk.size)?;
+                }
+            }
+        }
+    }
+
+    for ((_, writer), start) in outputs.iter_mut().zip(starts) {
+        finish_riff(writer, start)?;
+    }
+    Ok(())
+}
+
+/// Write the `channels` of `wave` to `writer`, see [`split()`].
+pub fn extract_channels<R, W>(
+    wave: &mut WaveFile<R>,
+    channels: &[usize],
+    writer: W,
+) -> Result<(), WaveFileError>
+where
+    R: Read + Seek + Debug + BufRead,
+    W: Write + Seek,
+{
+    split(wave, &mut [(channels.to_vec(), writer)])
+}
+
+/// `fmt` with `count` channels of `format` samples. For
+/// `WAVE_FORMAT_EXTENSIBLE`, `channel_mask` maps the old speaker positions
+/// to the new ones.
+pub(crate) fn fmt_for_channels(
+    fmt: &FmtEnum,
+    count: usize,
+    format: SampleFormat,
+    channel_mask: impl FnOnce(u32) -> u32,
+) -> Result<FmtEnum, WaveFileError> {
+    let block_align = u16::try_from(format.bytes() * count)
+        .map_err(|_| WaveFileError::parse(None, "too many channels"))?;
+    let count =
+        u16::try_from(count).map_err(|_| WaveFileError::parse(None, "too many channels"))?;
+    let avg_bytes_per_sec = fmt
+        .samples_per_sec()
+        .checked_mul(u32::from(block_align))
+        .ok_or_else(|| WaveFileError::parse(None, "byte rate too large"))?;
+    match fmt.clone() {
+        FmtEnum::Pcm(mut pcm) => {
+            pcm.channels = count;
+            pcm.block_align = block_align;
+            pcm.avg_bytes_per_sec = avg_bytes_per_sec;
+            Ok(FmtEnum::Pcm(pcm))
+        }
+        FmtEnum::Extended(mut ext) => {
+            ext.channels = count;
+            ext.block_align = block_align;
+            ext.avg_bytes_per_sec = avg_bytes_per_sec;
+            if u16::from(ext.format_tag) == 0xFFFE && ext.extra_bytes.len() >= 6 {
+                let mask = u32::from_le_bytes([
+                    ext.extra_bytes[2],
+                    ext.extra_bytes[3],
+                    ext.extra_bytes[4],
+                    ext.extra_bytes[5],
+                ]);
+                let mask = channel_mask(mask);
+                ext.extra_bytes[2..6].copy_from_slice(&mask.to_le_bytes());
+            }
+            Ok(FmtEnum::Extended(ext))
+        }
+        FmtEnum::Adpcm(_) | FmtEnum::DviAdpcm(_) | FmtEnum::Mpeg(_) | FmtEnum::MpegLayer3(_) => {
+            Err(WaveFileError::parse(
+                None,
+                format!("can't change the channels of {} samples", fmt.format_tag()),
+            ))
+        }
+    }
+}
+
+/// Write a `data` chunk with the `channels` of each output, reading whole
+/// frames from `data`. A partial frame at the end is left out.
+fn split_data<R, W>(
+    mut data: R,
+    outputs: &mut [(Vec<usize>, W)],
+    channel_count: usize,
+    format: SampleFormat,
+) -> Result<(), WaveFileError>
+where
+    R: Read,
+    W: Write + Seek,
+{
+    let mut starts = Vec::with_capacity(outputs.len());
+    for (_, writer) in outputs.iter_mut() {
+        starts.push(writer.stream_position()?);
+        writer.write_all(&Data::ID.0)?;
+        // placeholder, updated once the number of frames is known
+        writer.write_all(&0_u32.to_le_bytes())?;
+    }
+
+    let sample_len = format.bytes();
+    let frame_len = sample_len * channel_count;
+    let mut frames: u64 = 0;
+    let mut buff = Vec::new();
+    let mut out = Vec::new();
+    loop {
+        buff.clear();
+        (&mut data)
+            .take(4096 * frame_len as u64)
+            .read_to_end(&mut buff)?;
+        let count = buff.len() / frame_len;
+        if count == 0 {
+            break;
+        }
+        frames += count as u64;
+        for (channels, writer) in outputs.iter_mut() {
+            out.clear();
+            for frame in buff.chunks_exact(frame_len) {
+                for channel in channels.iter() {
+                    out.extend_from_slice(&frame[channel * sample_len..(channel + 1) * sample_len]);
+                }
+            }
+            writer.write_all(&out)?;
+        }
+    }
+
+    for ((channels, writer), start) in outputs.iter_mut().zip(starts) {
+        let size = u32::try_from(frames * (sample_len * channels.len()) as u64)
+            .map_err(|_| WaveFileError::parse(None, "split data too large for a RIFF file"))?;
+        if size % 2 == 1 {
+            writer.write_all(&[0])?;
+        }
+        let end = writer.stream_position()?;
+        writer.seek(SeekFrom::Start(start + 4))?;
+        writer.write_all(&size.to_le_bytes())?;
+        writer.seek(SeekFrom::Start(end))?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::testing::hex_to_cursor;
+
+    #[cfg(feature = "ixml")]
+    fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut buff = id.to_vec();
+        buff.extend_from_slice(&u32::try_from(data.len()).unwrap().to_le_bytes());
+        buff.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            buff.push(0);
+        }
+        buff
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn split_stereo() {
+        use crate::FourCC;
+
+        let ixml = br"<BWFXML><FILE_SET><TOTAL_FILES>1</TOTAL_FILES></FILE_SET><TRACK_LIST>
+<TRACK_COUNT>2</TRACK_COUNT>
+<TRACK><CHANNEL_INDEX>1</CHANNEL_INDEX><INTERLEAVE_INDEX>1</INTERLEAVE_INDEX><NAME>Boom</NAME></TRACK>
+<TRACK><CHANNEL_INDEX>2</CHANNEL_INDEX><INTERLEAVE_INDEX>2</INTERLEAVE_INDEX><NAME>Lav</NAME></TRACK>
+</TRACK_LIST></BWFXML>";
+        let mut body = b"WAVE".to_vec();
+        body.extend(chunk(
+            b"fmt ",
+            &hex::decode("0100020080BB000000EE02000400 1000".replace(' ', "")).unwrap(),
+        ));
+        body.extend(chunk(b"abcd", b"xyz"));
+        body.extend(chunk(b"iXML", ixml));
+        // 3 frames and a partial frame
+        body.extend(chunk(
+            b"data",
+            &hex::decode("0100FF7F 0200FE7F 0300FD7F 0400".replace(' ', "")).unwrap(),
+        ));
+        let input = chunk(b"RIFF", &body);
+        let mut wave = WaveFile::from_reader(Cursor::new(input)).unwrap();
+
+        let mut outputs = vec![
+            (vec![1], Cursor::new(Vec::new())),
+            (vec![0], Cursor::new(Vec::new())),
+        ];
+        split(&mut wave, &mut outputs).unwrap();
+
+        let (_, mut right) = outputs.remove(0);
+        right.set_position(0);
+        let mut right = WaveFile::from_reader(right).unwrap();
+        let metadata = right.metadata();
+        dbg!(&metadata);
+        let fmt = metadata.fmt().unwrap();
+        assert_eq!(fmt.channels(), 1);
+        assert_eq!(fmt.block_align(), 2);
+        assert_eq!(fmt.avg_bytes_per_sec(), 96_000);
+        let track_list = metadata.ixml().unwrap().track_list.clone().unwrap();
+        assert_eq!(track_list.track_count.as_deref(), Some("1"));
+        assert_eq!(track_list.tracks[0].name.as_deref(), Some("Lav"));
+        assert_eq!(track_list.tracks[0].interleave_index.as_deref(), Some("1"));
+        let file_set = metadata.ixml().unwrap().file_set.clone().unwrap();
+        assert_eq!(file_set.file_set_index.as_deref(), Some("1"));
+        assert_eq!(file_set.total_files.as_deref(), Some("2"));
+        let ids: Vec<_> = right.scan_chunks().unwrap().iter().map(|c| c.id).collect();
+        assert_eq!(ids, [FmtEnum::ID, FourCC(*b"abcd"), Ixml::ID, Data::ID]);
+
+        let bytes = outputs.remove(0).1.into_inner();
+        let data = chunk(b"data", &hex::decode("010002000300").unwrap());
+        assert!(bytes.ends_with(&data));
+        let riff_size = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        assert_eq!(riff_size as usize, bytes.len() - 8);
+    }
+
+    #[test]
+    fn split_errors() {
+        let data = "52494646 2C000000 57415645 \
+            666D7420 10000000 0100 0200 80BB0000 00EE0200 0400 1000 \
+            64617461 08000000 0100 0200 0300 0400";
+        let mut wave = WaveFile::from_reader(hex_to_cursor(data)).unwrap();
+        let mut writer = Cursor::new(Vec::new());
+        assert!(extract_channels(&mut wave, &[2], &mut writer).is_err());
+        assert!(extract_channels(&mut wave, &[], &mut writer).is_err());
+        assert!(writer.into_inner().is_empty());
+
+        let mut writer = Cursor::new(Vec::new());
+        extract_channels(&mut wave, &[1, 0], &mut writer).unwrap();
+        let bytes = writer.into_inner();
+        assert!(bytes.ends_with(&hex::decode("0200010004000300").unwrap()));
+    }
+}
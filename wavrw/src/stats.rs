@@ -0,0 +1,295 @@
+//! Chunk counts and parse durations, for diagnosing slow files, and byte
+//! accounting for storage planning.
+//!
+//! Each chunk is parsed inside a `chunk` [`tracing`] span with the chunk `id`,
+//! `offset` and `size`. For a summary without a tracing subscriber, see
+//! [`WaveFile::parse_stats()`](crate::WaveFile::parse_stats).
+//!
+//! ```
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+//! let mut wave = wavrw::WaveFile::from_reader(file)?;
+//! for _ in wave.iter_chunks() {}
+//! let stats = wave.parse_stats();
+//! assert_eq!(stats.chunks["fmt"].count, 1);
+//! println!("{stats}");
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+//!
+//! [`SizeStats`] splits the bytes of one or more files into audio, headers,
+//! padding and metadata by chunk type.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+
+use crate::chunk::junk::{FllrChunk, JunkChunk, PadChunk};
+use crate::{ChunkID, KnownChunkID, SizedChunk, SizedChunkEnum, Summarizable};
+
+/// Count, size and parse duration of the chunks of one type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ChunkStats {
+    /// Number of chunks parsed.
+    pub count: usize,
+
+    /// Sum of the chunk sizes in bytes.
+    pub bytes: u64,
+
+    /// Time spent reading and parsing the chunks.
+    pub duration: Duration,
+}
+
+/// [`ChunkStats`] per chunk type, recorded while iterating over chunks.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParseStats {
+    /// Stats by chunk name, as returned by
+    /// [`Summarizable::name()`](crate::Summarizable::name). Ex: `LIST-INFO`
+    pub chunks: BTreeMap<String, ChunkStats>,
+}
+
+impl ParseStats {
+    /// Add a parsed chunk.
+    pub fn record(&mut self, name: String, size: u32, duration: Duration) {
+        let stats = self.chunks.entry(name).or_default();
+        stats.count += 1;
+        stats.bytes += u64::from(size);
+        stats.duration += duration;
+    }
+
+    /// Stats of all chunk types combined.
+    pub fn total(&self) -> ChunkStats {
+        self.chunks
+            .values()
+            .fold(ChunkStats::default(), |total, stats| ChunkStats {
+                count: total.count + stats.count,
+                bytes: total.bytes + stats.bytes,
+                duration: total.duration + stats.duration,
+            })
+    }
+
+    /// Remove all recorded stats.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+}
+
+impl Display for ParseStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (name, stats) in &self.chunks {
+            writeln!(
+                f,
+                "{name:12} {:>4} chunks {:>12} bytes {:>12?}",
+                stats.count, stats.bytes, stats.duration
+            )?;
+        }
+        let total = self.total();
+        write!(
+            f,
+            "{:12} {:>4} chunks {:>12} bytes {:>12?}",
+            "total", total.count, total.bytes, total.duration
+        )
+    }
+}
+
+/// Bytes used by audio, headers, padding and metadata, for one or more
+/// files.
+///
+/// ```
+/// # use std::fs::File;
+/// # use std::io::BufReader;
+/// use wavrw::stats::SizeStats;
+///
+/// let file = BufReader::new(File::open("../test_wavs/example_a.wav")?);
+/// let mut wave = wavrw::WaveFile::from_reader(file)?;
+/// let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>()?;
+/// let stats = SizeStats::from_chunks(&chunks);
+/// assert_eq!(stats.audio, 1440);
+/// assert_eq!(stats.total(), 21634);
+/// println!("{stats}");
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SizeStats {
+    /// Number of files added.
+    pub files: usize,
+
+    /// Audio data bytes, the size of `data` chunks.
+    pub audio: u64,
+
+    /// The 12 byte `RIFF` header of each file, plus the id and size of
+    /// `data` chunks.
+    pub header: u64,
+
+    /// `JUNK`, `FLLR` and `PAD ` chunks, including their headers.
+    pub padding: u64,
+
+    /// Padding bytes after odd sized chunks, see
+    /// [`SizedChunk::padding()`](crate::SizedChunk::padding). Missing
+    /// padding bytes are not counted.
+    pub alignment: u64,
+
+    /// All other chunks by name, including their headers. Ex: `LIST-INFO`
+    pub metadata: BTreeMap<String, u64>,
+}
+
+impl SizeStats {
+    /// Size of the `RIFF` header: id, size and `WAVE` form type.
+    const RIFF_HEADER: u64 = 12;
+
+    /// Byte accounting of a single file from its parsed chunks.
+    pub fn from_chunks(chunks: &[SizedChunkEnum]) -> Self {
+        let mut stats = SizeStats {
+            files: 1,
+            header: Self::RIFF_HEADER,
+            ..Default::default()
+        };
+        for chunk in chunks {
+            let alignment = chunk.total_size() - 8 - chunk.large_size();
+            stats.alignment += alignment;
+            let chunk_size = chunk.total_size() - alignment;
+            if let SizedChunkEnum::Data(_) = chunk {
+                let size = u64::from(chunk.size());
+                stats.audio += size;
+                stats.header += chunk_size - size;
+            } else if [JunkChunk::ID, FllrChunk::ID, PadChunk::ID].contains(&chunk.id()) {
+                stats.padding += chunk_size;
+            } else {
+                *stats.metadata.entry(chunk.name()).or_default() += chunk_size;
+            }
+        }
+        stats
+    }
+
+    /// Add the bytes of `other`, for totals across files.
+    pub fn add(&mut self, other: &SizeStats) {
+        self.files += other.files;
+        self.audio += other.audio;
+        self.header += other.header;
+        self.padding += other.padding;
+        self.alignment += other.alignment;
+        for (name, bytes) in &other.metadata {
+            *self.metadata.entry(name.clone()).or_default() += bytes;
+        }
+    }
+
+    /// Bytes of all metadata chunks.
+    pub fn metadata_total(&self) -> u64 {
+        self.metadata.values().sum()
+    }
+
+    /// Total bytes.
+    pub fn total(&self) -> u64 {
+        self.audio + self.header + self.padding + self.alignment + self.metadata_total()
+    }
+
+    /// Percent of the total bytes which are not audio.
+    pub fn overhead_percent(&self) -> f64 {
+        self.percent(self.total() - self.audio)
+    }
+
+    /// `bytes` as a percent of the total bytes.
+    // precision loss only matters for totals over 2^52 bytes
+    #[allow(clippy::cast_precision_loss)]
+    fn percent(&self, bytes: u64) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => bytes as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+impl Display for SizeStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let rows = [
+            ("audio", self.audio),
+            ("header", self.header),
+            ("padding", self.padding),
+            ("alignment", self.alignment),
+        ];
+        let metadata = self
+            .metadata
+            .iter()
+            .map(|(name, bytes)| (name.as_str(), *bytes));
+        for (name, bytes) in rows.into_iter().chain(metadata) {
+            writeln!(
+                f,
+                "{name:12} {bytes:>12} bytes {:>5.1}%",
+                self.percent(bytes)
+            )?;
+        }
+        write!(
+            f,
+            "{:12} {:>12} bytes, {:.1}% overhead",
+            "total",
+            self.total(),
+            self.overhead_percent()
+        )
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn parse_stats_total() {
+        let mut stats = ParseStats::default();
+        stats.record("fmt".to_string(), 16, Duration::from_micros(2));
+        stats.record("LIST-INFO".to_string(), 100, Duration::from_micros(5));
+        stats.record("LIST-INFO".to_string(), 50, Duration::from_micros(1));
+        dbg!(&stats);
+        assert_eq!(stats.chunks["LIST-INFO"].count, 2);
+        assert_eq!(stats.chunks["LIST-INFO"].bytes, 150);
+        let total = stats.total();
+        assert_eq!(total.count, 3);
+        assert_eq!(total.bytes, 166);
+        assert_eq!(total.duration, Duration::from_micros(8));
+        assert!(stats.to_string().ends_with(&format!(
+            "{:12} {:>4} chunks {:>12} bytes {:>12?}",
+            "total",
+            3,
+            166,
+            Duration::from_micros(8)
+        )));
+
+        stats.clear();
+        assert_eq!(stats.total(), ChunkStats::default());
+    }
+
+    #[test]
+    fn size_stats() {
+        // fact, odd sized data with padding, JUNK
+        let data = "52494646 26000000 57415645 66616374 04000000 03000000 \
+            64617461 03000000 01020300 4A554E4B 02000000 0000";
+        let mut wave = crate::WaveFile::from_reader(crate::testing::hex_to_cursor(data)).unwrap();
+        let chunks: Vec<_> = wave.iter_chunks().collect::<Result<_, _>>().unwrap();
+        let stats = SizeStats::from_chunks(&chunks);
+        dbg!(&stats);
+        assert_eq!(stats.audio, 3);
+        // RIFF header and data header
+        assert_eq!(stats.header, 12 + 8);
+        assert_eq!(stats.padding, 10);
+        assert_eq!(stats.alignment, 1);
+        assert_eq!(stats.metadata["fact"], 12);
+        assert_eq!(stats.total(), 46);
+
+        let mut total = SizeStats::default();
+        total.add(&stats);
+        total.add(&stats);
+        assert_eq!(total.files, 2);
+        assert_eq!(total.metadata["fact"], 24);
+        assert_eq!(total.total(), 92);
+        assert!(total.to_string().ends_with(&format!(
+            "{:12} {:>12} bytes, {:.1}% overhead",
+            "total",
+            92,
+            100.0 * 86.0 / 92.0
+        )));
+    }
+}
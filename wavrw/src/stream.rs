@@ -0,0 +1,385 @@
+//! Parse chunks from streams which can't seek, like stdin or a network
+//! connection.
+//!
+//! [`WaveFile`](crate::WaveFile) seeks back and forth in its reader, to find
+//! the `CSET` code page, check padding and revisit chunks.
+//! [`metadata_chunks_streaming()`] reads the stream once from start to end
+//! instead. The data of each chunk is read into memory and parsed, except
+//! for `data`, whose audio is skipped with bounded reads, so memory use
+//! depends on the size of the metadata and not on the length of the audio.
+//!
+//! ```
+//! # use std::fs::File;
+//! use wavrw::stream::metadata_chunks_streaming;
+//! use wavrw::Summarizable;
+//!
+//! // any Read, ex: std::io::stdin() or an HTTP response body
+//! let reader = File::open("../test_wavs/example_a.wav")?;
+//! for chunk in metadata_chunks_streaming(reader)? {
+//!     let chunk = chunk?;
+//!     println!("{:8} {}", chunk.name(), chunk.summary());
+//! }
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom};
+
+use binrw::BinRead;
+use tracing::warn;
+
+use crate::chunk::data::Data;
+use crate::chunk::ds64::LargeSizes;
+use crate::chunk::riff::RiffChunk;
+use crate::warning::Code;
+use crate::{
+    aiff, looks_like_chunk_id, read_chunk, ChunkID, FourCC, KnownChunkID, ParseOptions,
+    SizedChunkEnum, Warning, WaveFileError,
+};
+
+/// Parse the chunks of a RIFF-WAVE stream, reading it only once.
+///
+/// Returns an error if the stream doesn't start with a RIFF-WAVE header.
+/// See [`metadata_chunks_streaming_with_options()`] for the options used.
+pub fn metadata_chunks_streaming<R: Read>(reader: R) -> Result<StreamingChunks<R>, WaveFileError> {
+    metadata_chunks_streaming_with_options(reader, ParseOptions::default())
+}
+
+/// Parse the chunks of a RIFF-WAVE stream according to `options`, reading
+/// it only once.
+///
+/// Without seeking, the `CSET` code page only applies to the chunks after
+/// it, and [`ParseOptions::trust_riff_size`] is ignored: chunks are read up
+/// to the RIFF size or the end of the stream, whichever comes first.
+pub fn metadata_chunks_streaming_with_options<R: Read>(
+    reader: R,
+    options: ParseOptions,
+) -> Result<StreamingChunks<R>, WaveFileError> {
+    let mut reader = BufReader::new(reader);
+    let mut header = [0_u8; 12];
+    reader.read_exact(&mut header)?;
+    let riff = RiffChunk::read(&mut Cursor::new(header)).map_err(std::io::Error::other)?;
+    if riff.id == aiff::FormChunk::ID {
+        return Err(WaveFileError::UnknownFourCC {
+            found: riff.form_type,
+            message: format!(
+                "not a wave file. Found IFF form_type {}, AIFF streams are not supported",
+                riff.form_type
+            ),
+        });
+    }
+    if riff.form_type != FourCC(*b"WAVE") {
+        return Err(WaveFileError::UnknownFourCC {
+            found: riff.form_type,
+            message: format!(
+                "not a wave file. Expected RIFF form_type 'WAVE', found: {}",
+                riff.form_type
+            ),
+        });
+    }
+    // placeholder sizes left by streaming encoders
+    let end = (riff.size != 0 && riff.size != u32::MAX).then(|| 8 + u64::from(riff.size));
+    Ok(StreamingChunks {
+        reader,
+        code_page: options.code_page.filter(|cp| *cp != 0),
+        riff,
+        offset: 12,
+        end,
+        options,
+        large_sizes: LargeSizes::default(),
+        warnings: Vec::new(),
+        finished: false,
+    })
+}
+
+/// Iterator over the chunks of a stream, see [`metadata_chunks_streaming()`].
+///
+/// Each iteration returns a
+/// `Result<`[`SizedChunkEnum`]`, `[`WaveFileError`]`>`. Iteration ends after
+/// the first error, since the position of the next chunk is unknown once
+/// the stream has been partly read.
+#[derive(Debug)]
+pub struct StreamingChunks<R: Read> {
+    reader: BufReader<R>,
+    riff: RiffChunk,
+    // offset of the next chunk from the start of the stream
+    offset: u64,
+    // end of the RIFF data, None for placeholder sizes
+    end: Option<u64>,
+    options: ParseOptions,
+    // code page from options or CSET, once seen
+    code_page: Option<u16>,
+    // 64 bit sizes from the ds64 chunk, for chunks with 0xFFFFFFFF sizes
+    large_sizes: LargeSizes,
+    warnings: Vec<Warning>,
+    finished: bool,
+}
+
+impl<R: Read> StreamingChunks<R> {
+    /// The RIFF header of the stream.
+    pub fn riff(&self) -> &RiffChunk {
+        &self.riff
+    }
+
+    /// Warnings recorded for the chunks read so far.
+    ///
+    /// Covers the problems found while reading the stream: chunk id aliases,
+    /// placeholder sizes, chunks past the end and missing padding.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Log and record a [`Warning`].
+    fn warn(&mut self, code: Code, id: FourCC, offset: u64, message: impl Into<String>) {
+        let warning = Warning::new(id, Some(offset), message).with_code(code);
+        warn!("{warning}");
+        self.warnings.push(warning);
+    }
+
+    /// Skip up to `len` bytes, returns the number of bytes skipped.
+    fn skip(&mut self, len: u64) -> Result<u64, WaveFileError> {
+        Ok(std::io::copy(
+            &mut (&mut self.reader).take(len),
+            &mut std::io::sink(),
+        )?)
+    }
+
+    /// Read and parse the next chunk. `None` at the end of the stream.
+    fn parse_next_chunk(&mut self) -> Result<Option<SizedChunkEnum>, WaveFileError> {
+        let start = self.offset;
+        if self.end.is_some_and(|end| start + 8 > end) || self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+        let mut header = [0_u8; 8];
+        self.reader.read_exact(&mut header)?;
+        let [a, b, c, d, s0, s1, s2, s3] = header;
+        let id = FourCC([a, b, c, d]);
+        let size = u32::from_le_bytes([s0, s1, s2, s3]);
+        if let Some(max) = self.options.max_chunk_size {
+            if size > max {
+                return Err(WaveFileError::parse(
+                    Some(start),
+                    format!("{id} chunk size ({size}) larger than max_chunk_size ({max})"),
+                ));
+            }
+        }
+        let large_size = self.large_sizes.resolve(id, size);
+        let len = large_size.unwrap_or(u64::from(size));
+
+        let mut bytes = header.to_vec();
+        let is_data = id == Data::ID || self.options.canonical_id(id) == Some(Data::ID);
+        let read = if is_data {
+            // placeholder sizes left by streaming encoders, the audio runs to
+            // the end of the stream
+            if large_size.is_none()
+                && (size == u32::MAX || (size == 0 && self.end.is_none() && self.audio_next()?))
+            {
+                self.warn(
+                    Code::PlaceholderSize,
+                    id,
+                    start,
+                    format!("chunk size ({size}) is a placeholder, assuming data runs to the end"),
+                );
+                self.skip(u64::MAX)?;
+                self.finished = true;
+                len
+            } else {
+                self.skip(len)?
+            }
+        } else {
+            (&mut self.reader).take(len).read_to_end(&mut bytes)? as u64
+        };
+        if read < len || self.end.is_some_and(|end| start + 8 + len > end) {
+            self.warn(
+                Code::ChunkPastEnd,
+                id,
+                start,
+                format!("chunk size ({size}) extends past the end of the data"),
+            );
+        }
+        if read < len {
+            self.finished = true;
+        }
+
+        // don't read the bytes of chunks larger than 4 GiB into memory
+        let skip_extra_bytes = !self.options.keep_extra_bytes || large_size.is_some();
+        let mut chunk = read_chunk(
+            &mut OffsetCursor {
+                inner: Cursor::new(bytes),
+                start,
+            },
+            id,
+            &self.options,
+            skip_extra_bytes,
+        )
+        .map_err(|err| WaveFileError::from(err).with_chunk(id, start))?;
+        if let Some(alias) = chunk.alias() {
+            self.warn(
+                Code::ChunkIdAlias,
+                alias,
+                start,
+                format!("chunk id parsed as {}", chunk.id()),
+            );
+        }
+        if let Some(size) = large_size {
+            chunk.set_large_size(size);
+        }
+
+        // RIFF offsets must be on word boundaries (divisible by 2)
+        let mut padding = None;
+        self.offset = start + 8 + len;
+        if len % 2 == 1 && !self.finished {
+            if self.padding_missing()? {
+                self.warn(
+                    Code::MissingPadding,
+                    id,
+                    start,
+                    "missing padding byte after odd sized chunk",
+                );
+            } else {
+                let mut byte = [0_u8];
+                if self.reader.read(&mut byte)? == 1 {
+                    padding = Some(byte[0]);
+                }
+                self.offset += 1;
+            }
+        }
+        chunk.set_padding(padding);
+
+        if let SizedChunkEnum::Ds64(ds64) = &chunk {
+            self.large_sizes = LargeSizes::from(&ds64.data);
+        }
+        if let SizedChunkEnum::Cset(cset) = &chunk {
+            if self.options.code_page.is_none() {
+                self.code_page = Some(cset.data.code_page).filter(|cp| *cp != 0);
+            }
+        }
+        if let Some(code_page) = self.code_page {
+            chunk.set_code_page(code_page);
+        }
+        Ok(Some(chunk))
+    }
+
+    /// Returns true if bytes follow which don't look like a chunk id.
+    fn audio_next(&mut self) -> Result<bool, WaveFileError> {
+        let buff = self.reader.fill_buf()?;
+        Ok(match buff.get(..4) {
+            Some(id) => !looks_like_chunk_id(id),
+            None => !buff.is_empty(),
+        })
+    }
+
+    /// Returns true if the padding byte after an odd sized chunk appears to
+    /// be missing, see [`ParseOptions::allow_missing_padding`].
+    ///
+    /// Only the bytes already buffered are checked, without reading further.
+    fn padding_missing(&mut self) -> Result<bool, WaveFileError> {
+        if !self.options.allow_missing_padding {
+            return Ok(false);
+        }
+        let buff = self.reader.fill_buf()?;
+        Ok(buff.len() >= 5 && looks_like_chunk_id(&buff[..4]) && !looks_like_chunk_id(&buff[1..5]))
+    }
+}
+
+impl<R: Read> Iterator for StreamingChunks<R> {
+    type Item = Result<SizedChunkEnum, WaveFileError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.parse_next_chunk();
+        if !matches!(result, Ok(Some(_))) {
+            self.finished = true;
+        }
+        result.transpose()
+    }
+}
+
+/// Chunk bytes read from the stream, with positions counted from the start
+/// of the stream so chunk offsets are correct.
+struct OffsetCursor {
+    inner: Cursor<Vec<u8>>,
+    start: u64,
+}
+
+impl Read for OffsetCursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Seek for OffsetCursor {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => SeekFrom::Start(pos.saturating_sub(self.start)),
+            pos @ (SeekFrom::End(_) | SeekFrom::Current(_)) => pos,
+        };
+        Ok(self.inner.seek(pos)? + self.start)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::dbg_macro)]
+mod test {
+    use super::*;
+    use crate::testing::hex_to_cursor;
+    use crate::{SizedChunk, WaveFile};
+
+    /// Reader without Seek, to make sure none is needed.
+    struct ReadOnly<R>(R);
+
+    impl<R: Read> Read for ReadOnly<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn same_as_wavefile() {
+        for path in [
+            "../test_wavs/example_a.wav",
+            "../test_wavs/1khz_sine_48k_mono_region_marker.wav",
+            "../test_wavs/1khz_sine_48k_mono_region_marker-bext_metadata.wav",
+        ] {
+            let file = std::fs::File::open(path).unwrap();
+            let streamed: Vec<SizedChunkEnum> = metadata_chunks_streaming(ReadOnly(file))
+                .unwrap()
+                .map(Result::unwrap)
+                .collect();
+            let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+            let mut wave = WaveFile::from_reader(file).unwrap();
+            let parsed: Vec<SizedChunkEnum> = wave.iter_chunks().map(Result::unwrap).collect();
+            assert_eq!(streamed, parsed, "{path}");
+        }
+    }
+
+    #[test]
+    fn placeholder_and_padding() {
+        // RIFF and data sizes are placeholders, odd sized JUNK is not padded
+        let data = "52494646 FFFFFFFF 57415645 4A554E4B 01000000 AA 64617461 FFFFFFFF 01020304";
+        let mut chunks = metadata_chunks_streaming(ReadOnly(hex_to_cursor(data))).unwrap();
+        let junk = chunks.next().unwrap().unwrap();
+        assert_eq!(junk.offset(), Some(12));
+        let data = chunks.next().unwrap().unwrap();
+        assert_eq!(data.offset(), Some(21));
+        assert!(chunks.next().is_none());
+        dbg!(chunks.warnings());
+        let codes: Vec<Code> = chunks.warnings().iter().map(|w| w.code).collect();
+        assert_eq!(codes, [Code::MissingPadding, Code::PlaceholderSize]);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(metadata_chunks_streaming(ReadOnly(hex_to_cursor("52494646"))).is_err());
+        let aiff = "464F524D 04000000 41494646";
+        assert!(metadata_chunks_streaming(ReadOnly(hex_to_cursor(aiff))).is_err());
+
+        let data = "52494646 18000000 57415645 4A554E4B 02000000 AAAA 4A554E4B 02000000 BBBB";
+        let options = ParseOptions::default().with_max_chunk_size(1);
+        let mut chunks =
+            metadata_chunks_streaming_with_options(ReadOnly(hex_to_cursor(data)), options).unwrap();
+        assert!(chunks.next().unwrap().is_err());
+        assert!(chunks.next().is_none());
+    }
+}
@@ -0,0 +1,788 @@
+//! Apply collection level metadata to many files from a template.
+//!
+//! A [`Template`] lists `bext`, `LIST-INFO` and `iXML` values to write. It
+//! is read from a small subset of TOML: `[bext]`, `[info]` and `[ixml]`
+//! tables of `key = "string"` lines, with `#` comments. `[ixml]` keys are
+//! paths of elements below the root, separated by dots.
+//!
+//! ```toml
+//! [bext]
+//! description = "Dawn chorus, {stem}"
+//! originator = "Sound Archive"
+//! origination_date = "{date}"
+//!
+//! [info]
+//! ICOP = "(c) Sound Archive"
+//!
+//! [ixml]
+//! PROJECT = "Dawn chorus"
+//! USER.COLLECTION = "{dir}"
+//! ```
+//!
+//! The same template as JSON has an object per section, `ixml` paths are
+//! nested objects: `{"ixml": {"USER": {"COLLECTION": "{dir}"}}}`.
+//!
+//! Values can contain placeholders, filled in for each file from
+//! [`Placeholders`]: `{filename}`, `{stem}`, `{dir}`, `{path}`, `{index}`,
+//! `{date}` and `{time}`. Write `{{` and `}}` for literal braces.
+//!
+//! [`Template::apply()`] writes a copy of a file with the values set. Other
+//! fields and chunks are kept, missing chunks are added.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! # use std::str::FromStr;
+//! # use wavrw::testing::hex_to_cursor;
+//! use wavrw::template::{Placeholders, Template};
+//!
+//! let template = Template::from_str("[info]\nINAM = \"{stem}\"")?;
+//! let mut placeholders = Placeholders::new();
+//! placeholders.set("stem", "dawn_01");
+//!
+//! // RIFF WAVE containing only a `fact` chunk
+//! let input = hex_to_cursor("52494646 10000000 57415645 66616374 04000000 E0010000");
+//! let mut wave = wavrw::WaveFile::from_reader(input)?;
+//! let mut output = Cursor::new(Vec::new());
+//! template.apply(&mut wave, &mut output, &placeholders)?;
+//!
+//! output.set_position(0);
+//! let mut wave = wavrw::WaveFile::from_reader(output)?;
+//! let name = wave.metadata().info(wavrw::FourCC(*b"INAM"));
+//! assert_eq!(name.as_deref(), Some("dawn_01"));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::Debug;
+use core::str::FromStr;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "broadcast")]
+use crate::chunk::bext::Bext;
+#[cfg(feature = "info")]
+use crate::chunk::info::ListInfo;
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::Ixml;
+#[cfg(any(feature = "broadcast", feature = "info"))]
+use crate::edit::chunk_data;
+use crate::edit::inject_chunks;
+#[cfg(feature = "broadcast")]
+use crate::fixedstring::FixedString;
+use crate::json::Value;
+use crate::time::{Date, TimeOfDay};
+#[cfg(feature = "broadcast")]
+use crate::umid::Umid;
+#[cfg(any(feature = "broadcast", feature = "info", feature = "ixml"))]
+use crate::KnownChunkID;
+use crate::{FourCC, WaveFile, WaveFileError, WaveMetadata};
+
+/// `bext` fields a template can set.
+pub const BEXT_FIELDS: [&str; 8] = [
+    "description",
+    "originator",
+    "originator_reference",
+    "origination_date",
+    "origination_time",
+    "time_reference",
+    "umid",
+    "coding_history",
+];
+
+/// Metadata values to write to many files, see the
+/// [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Template {
+    /// `bext` fields and values, in template order. Ex: `("originator", "Archive")`
+    ///
+    /// `origination_date` and `origination_time` are checked and written as
+    /// `yyyy-mm-dd` and `hh:mm:ss`. `umid` is `random` for a new
+    /// [`Umid::random()`](crate::umid::Umid::random) per file, or hex bytes.
+    pub bext: Vec<(String, String)>,
+
+    /// `LIST-INFO` subchunk ids and text. Ex: `(INAM, "{stem}")`
+    pub info: Vec<(FourCC, String)>,
+
+    /// `iXML` element paths below the root and text. Ex: `(["USER", "COLLECTION"], "{dir}")`
+    pub ixml: Vec<(Vec<String>, String)>,
+}
+
+/// Top level tables of a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Bext,
+    Info,
+    Ixml,
+}
+
+impl FromStr for Section {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bext" => Ok(Section::Bext),
+            "info" => Ok(Section::Info),
+            "ixml" => Ok(Section::Ixml),
+            _ => Err(format!(
+                "unknown section {s:?}, expected bext, info or ixml"
+            )),
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = WaveFileError;
+
+    /// Parse a template from TOML, or JSON if `s` starts with `{`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start().starts_with('{') {
+            Template::from_json(s)
+        } else {
+            Template::from_toml(s)
+        }
+    }
+}
+
+impl Template {
+    /// Parse the TOML subset described in the [module documentation](self).
+    pub fn from_toml(text: &str) -> Result<Template, WaveFileError> {
+        let mut template = Template::default();
+        let mut section = None;
+        for (number, line) in text.lines().enumerate() {
+            template.parse_line(line, &mut section).map_err(|err| {
+                WaveFileError::parse(None, format!("template line {}: {err}", number + 1))
+            })?;
+        }
+        Ok(template)
+    }
+
+    /// Parse a JSON template, see the [module documentation](self).
+    pub fn from_json(text: &str) -> Result<Template, WaveFileError> {
+        let err = |message: String| WaveFileError::parse(None, format!("template: {message}"));
+        let root = Value::parse(text).map_err(|e| err(e.to_string()))?;
+        let Value::Object(sections) = root else {
+            return Err(err("expected an object of sections".to_string()));
+        };
+        let mut template = Template::default();
+        for (name, value) in sections {
+            let section = Section::from_str(&name).map_err(err)?;
+            template
+                .add_json(section, &mut Vec::new(), &value)
+                .map_err(err)?;
+        }
+        Ok(template)
+    }
+
+    fn add_json(
+        &mut self,
+        section: Section,
+        path: &mut Vec<String>,
+        value: &Value,
+    ) -> Result<(), String> {
+        if let Value::Object(entries) = value {
+            for (key, value) in entries {
+                path.push(key.clone());
+                self.add_json(section, path, value)?;
+                path.pop();
+            }
+            return Ok(());
+        }
+        let text = value
+            .as_text()
+            .ok_or_else(|| format!("{}: expected a string", path.join(".")))?;
+        self.add(section, path.clone(), text)
+    }
+
+    /// Add a value for `path` in `section`, checking the key.
+    fn add(&mut self, section: Section, path: Vec<String>, value: String) -> Result<(), String> {
+        match (section, path.as_slice()) {
+            (Section::Bext, [field]) if BEXT_FIELDS.contains(&field.as_str()) => {
+                self.bext.push((field.clone(), value));
+            }
+            (Section::Bext, _) => {
+                return Err(format!(
+                    "unknown bext field {:?}, expected one of: {}",
+                    path.join("."),
+                    BEXT_FIELDS.join(", ")
+                ));
+            }
+            (Section::Info, [id]) => {
+                let id = FourCC::from_str(id).map_err(|e| e.to_string())?;
+                self.info.push((id, value));
+            }
+            (Section::Info, _) => {
+                return Err(format!(
+                    "info id {:?} must be 1 to 4 characters",
+                    path.join(".")
+                ));
+            }
+            (Section::Ixml, _) if path.is_empty() || path.iter().any(String::is_empty) => {
+                return Err("empty ixml element name".to_string());
+            }
+            (Section::Ixml, _) => self.ixml.push((path, value)),
+        }
+        Ok(())
+    }
+
+    fn parse_line(&mut self, line: &str, section: &mut Option<Section>) -> Result<(), String> {
+        let mut line = Line(line.trim());
+        if line.at_end() {
+            return Ok(());
+        }
+        if line.eat('[') {
+            let Some((name, rest)) = line.0.split_once(']') else {
+                return Err("missing ']'".to_string());
+            };
+            *section = Some(Section::from_str(name.trim())?);
+            line.0 = rest;
+            return line.end();
+        }
+
+        let mut path = vec![line.key()?];
+        while line.eat('.') {
+            path.push(line.key()?);
+        }
+        if !line.eat('=') {
+            return Err("expected '=' after key".to_string());
+        }
+        let value = line.value()?;
+        line.end()?;
+        let section = section.ok_or("key outside a [bext], [info] or [ixml] section")?;
+        self.add(section, path, value)
+    }
+
+    /// Write a copy of `wave` to `writer` with the template values set.
+    ///
+    /// The new `bext`, `LIST-INFO` and `iXML` chunks replace the first chunk
+    /// of each kind, and start from its values. Returns an error if a value
+    /// doesn't fit its field or has an unknown placeholder, before anything
+    /// is written.
+    pub fn apply<R, W>(
+        &self,
+        wave: &mut WaveFile<R>,
+        writer: W,
+        placeholders: &Placeholders,
+    ) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        let metadata = wave.metadata();
+        let chunks = [
+            self.bext_chunk(&metadata, placeholders)?,
+            self.info_chunk(&metadata, placeholders)?,
+            self.ixml_chunk(&metadata, placeholders)?,
+        ];
+
+        wave.bytes.seek(SeekFrom::Start(wave.chunks_start - 12))?;
+        let chunks: Vec<(FourCC, &[u8])> = chunks
+            .iter()
+            .flatten()
+            .map(|(id, data)| (*id, &data[..]))
+            .collect();
+        inject_chunks(&mut wave.bytes, writer, &chunks)
+    }
+
+    #[cfg(feature = "broadcast")]
+    fn bext_chunk(
+        &self,
+        metadata: &WaveMetadata<'_>,
+        placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        if self.bext.is_empty() {
+            return Ok(None);
+        }
+        let mut bext = match metadata.bext() {
+            Some(bext) => bext.clone(),
+            None => Bext::builder().build()?,
+        };
+        for (field, value) in &self.bext {
+            set_bext_field(&mut bext, field, &placeholders.expand(value)?)?;
+        }
+        Ok(Some((Bext::ID, chunk_data(&bext)?)))
+    }
+
+    #[cfg(not(feature = "broadcast"))]
+    fn bext_chunk(
+        &self,
+        _metadata: &WaveMetadata<'_>,
+        _placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        disabled(&self.bext, "bext")
+    }
+
+    #[cfg(feature = "info")]
+    fn info_chunk(
+        &self,
+        metadata: &WaveMetadata<'_>,
+        placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        if self.info.is_empty() {
+            return Ok(None);
+        }
+        let mut info = match metadata.list_info() {
+            Some(info) => info.clone(),
+            None => ListInfo::builder().build()?,
+        };
+        for (id, value) in &self.info {
+            info.set(*id, &placeholders.expand(value)?)?;
+        }
+        Ok(Some((ListInfo::ID, chunk_data(&info)?)))
+    }
+
+    #[cfg(not(feature = "info"))]
+    fn info_chunk(
+        &self,
+        _metadata: &WaveMetadata<'_>,
+        _placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        disabled(&self.info, "LIST-INFO")
+    }
+
+    #[cfg(feature = "ixml")]
+    fn ixml_chunk(
+        &self,
+        metadata: &WaveMetadata<'_>,
+        placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        if self.ixml.is_empty() {
+            return Ok(None);
+        }
+        let mut ixml = match metadata.ixml() {
+            Some(ixml) => ixml.clone(),
+            None => Ixml::from_bytes(b"<BWFXML/>".to_vec()),
+        };
+        for (path, value) in &self.ixml {
+            let path: Vec<&str> = path.iter().map(String::as_str).collect();
+            ixml = ixml.with_text(&path, &placeholders.expand(value)?)?;
+        }
+        Ok(Some((Ixml::ID, ixml.raw_bytes)))
+    }
+
+    #[cfg(not(feature = "ixml"))]
+    fn ixml_chunk(
+        &self,
+        _metadata: &WaveMetadata<'_>,
+        _placeholders: &Placeholders,
+    ) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+        disabled(&self.ixml, "iXML")
+    }
+}
+
+/// Error if a template section is set but its chunk support is disabled.
+#[cfg(not(all(feature = "broadcast", feature = "info", feature = "ixml")))]
+#[allow(clippy::unnecessary_wraps)] // matches the enabled versions
+fn disabled<T>(section: &[T], name: &str) -> Result<Option<(FourCC, Vec<u8>)>, WaveFileError> {
+    if section.is_empty() {
+        Ok(None)
+    } else {
+        Err(WaveFileError::parse(
+            None,
+            format!("{name} support is not enabled"),
+        ))
+    }
+}
+
+/// Set the `bext` `field` named in [`BEXT_FIELDS`] from `value`.
+#[cfg(feature = "broadcast")]
+pub(crate) fn set_bext_field(
+    bext: &mut Bext,
+    field: &str,
+    value: &str,
+) -> Result<(), WaveFileError> {
+    fn fixed<const N: usize>(field: &str, text: &str) -> Result<FixedString<N>, WaveFileError> {
+        FixedString::try_from_str(text)
+            .map_err(|err| WaveFileError::parse(None, format!("bext {field}: {err}")))
+    }
+    match field {
+        "description" => bext.description = fixed(field, value)?,
+        "originator" => bext.originator = fixed(field, value)?,
+        "originator_reference" => bext.originator_reference = fixed(field, value)?,
+        "origination_date" => {
+            bext.origination_date = fixed(field, &Date::from_str(value)?.to_string())?;
+        }
+        "origination_time" => {
+            bext.origination_time = fixed(field, &TimeOfDay::from_str(value)?.to_string())?;
+        }
+        "time_reference" => {
+            bext.time_reference = value.trim().parse().map_err(|_| {
+                WaveFileError::parse(
+                    None,
+                    format!("bext time_reference: invalid number {value:?}"),
+                )
+            })?;
+        }
+        "umid" => {
+            let umid = if value == "random" {
+                Umid::random().to_bext()
+            } else {
+                let bytes = hex::decode(value.replace(' ', ""))
+                    .ok()
+                    .filter(|b| b.len() <= 64)
+                    .ok_or_else(|| {
+                        WaveFileError::parse(
+                            None,
+                            "bext umid: expected random or up to 64 hex bytes",
+                        )
+                    })?;
+                let mut umid = [0_u8; 64];
+                umid[..bytes.len()].copy_from_slice(&bytes);
+                umid
+            };
+            bext.umid = umid;
+            // UMID was added in version 1
+            bext.version = bext.version.max(1);
+        }
+        "coding_history" => bext.coding_history = value.to_string(),
+        _ => {
+            return Err(WaveFileError::parse(
+                None,
+                format!("unknown bext field {field:?}"),
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// The unparsed rest of a template line.
+struct Line<'a>(&'a str);
+
+impl Line<'_> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    /// Consume `c` after any whitespace.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Only whitespace or a comment is left.
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.0.is_empty() || self.0.starts_with('#')
+    }
+
+    fn end(&mut self) -> Result<(), String> {
+        if self.at_end() {
+            Ok(())
+        } else {
+            Err(format!("unexpected {:?}", self.0))
+        }
+    }
+
+    /// A bare or quoted key.
+    fn key(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        if self.0.starts_with(['"', '\'']) {
+            return self.string();
+        }
+        let len = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.0.len());
+        if len == 0 {
+            return Err("expected a key".to_string());
+        }
+        let (key, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(key.to_string())
+    }
+
+    /// A string, or an integer or bool as text.
+    fn value(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        if self.0.starts_with(['"', '\'']) {
+            return self.string();
+        }
+        let len = self
+            .0
+            .find(|c: char| c.is_whitespace() || c == '#')
+            .unwrap_or(self.0.len());
+        let (value, rest) = self.0.split_at(len);
+        let value = value.replace('_', "");
+        if value.parse::<i64>().is_err() && value != "true" && value != "false" {
+            return Err(format!("expected a quoted string, found {:?}", self.0));
+        }
+        self.0 = rest;
+        Ok(value)
+    }
+
+    /// A basic `"..."` string with escapes, or a literal `'...'` string.
+    fn string(&mut self) -> Result<String, String> {
+        if let Some(rest) = self.0.strip_prefix('\'') {
+            let (text, rest) = rest.split_once('\'').ok_or("missing closing \"'\"")?;
+            self.0 = rest;
+            return Ok(text.to_string());
+        }
+        let mut text = String::new();
+        let mut chars = self.0.char_indices().skip(1);
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &self.0[index + 1..];
+                    return Ok(text);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some('n') => text.push('\n'),
+                    Some('r') => text.push('\r'),
+                    Some('t') => text.push('\t'),
+                    Some(u @ ('u' | 'U')) => {
+                        let len = if u == 'u' { 4 } else { 8 };
+                        let digits: String = chars.by_ref().take(len).map(|(_, c)| c).collect();
+                        let c = u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape \\{u}{digits}"))?;
+                        text.push(c);
+                    }
+                    other => return Err(format!("invalid escape \\{}", other.unwrap_or(' '))),
+                },
+                c => text.push(c),
+            }
+        }
+        Err("missing closing '\"'".to_string())
+    }
+}
+
+/// Values for the placeholders of a [`Template`], see the
+/// [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    values: Vec<(String, String)>,
+}
+
+impl Placeholders {
+    /// Only `{date}` and `{time}`, the current UTC date and time.
+    pub fn new() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut placeholders = Placeholders { values: Vec::new() };
+        placeholders.set("date", &Date::from_unix_days(seconds / 86_400).to_string());
+        placeholders.set("time", &TimeOfDay::from_seconds(seconds).to_string());
+        placeholders
+    }
+
+    /// Placeholders for the file at `path`, number `index` of a batch:
+    /// `{filename}`, `{stem}` (file name without extension), `{dir}` (name
+    /// of the parent directory), `{path}` and `{index}`, plus those of
+    /// [`Placeholders::new()`].
+    pub fn for_file(path: &Path, index: usize) -> Self {
+        let name =
+            |p: Option<&std::ffi::OsStr>| p.unwrap_or_default().to_string_lossy().to_string();
+        let mut placeholders = Placeholders::new();
+        placeholders.set("filename", &name(path.file_name()));
+        placeholders.set("stem", &name(path.file_stem()));
+        placeholders.set("dir", &name(path.parent().and_then(Path::file_name)));
+        placeholders.set("path", &path.to_string_lossy());
+        placeholders.set("index", &index.to_string());
+        placeholders
+    }
+
+    /// Set the value of placeholder `{name}`.
+    pub fn set(&mut self, name: &str, value: &str) {
+        if let Some(entry) = self.values.iter_mut().find(|(n, _)| n == name) {
+            entry.1 = value.to_string();
+        } else {
+            self.values.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    /// The value of placeholder `{name}`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// `text` with placeholders replaced. Returns an error for unknown
+    /// placeholders.
+    ///
+    /// ```
+    /// use wavrw::template::Placeholders;
+    ///
+    /// let mut placeholders = Placeholders::new();
+    /// placeholders.set("stem", "dawn_01");
+    /// assert_eq!(placeholders.expand("{stem} {{raw}}")?, "dawn_01 {raw}");
+    /// assert!(placeholders.expand("{missing}").is_err());
+    /// # Ok::<(), wavrw::WaveFileError>(())
+    /// ```
+    pub fn expand(&self, text: &str) -> Result<String, WaveFileError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(index) = rest.find(['{', '}']) {
+            out.push_str(&rest[..index]);
+            let tail = &rest[index..];
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                out.push_str(&tail[..1]);
+                rest = &tail[2..];
+            } else if let Some(after) = tail.strip_prefix('}') {
+                out.push('}');
+                rest = after;
+            } else {
+                let Some(end) = tail.find('}') else {
+                    return Err(WaveFileError::parse(
+                        None,
+                        format!("missing '}}' in {text:?}"),
+                    ));
+                };
+                let name = &tail[1..end];
+                let value = self.get(name).ok_or_else(|| {
+                    WaveFileError::parse(None, format!("unknown placeholder {{{name}}}"))
+                })?;
+                out.push_str(value);
+                rest = &tail[end + 1..];
+            }
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+impl Default for Placeholders {
+    fn default() -> Self {
+        Placeholders::new()
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const TOML: &str = r#"
+# collection metadata
+[bext]
+description = "Dawn chorus, {stem}"  # trailing comment
+originator = 'Sound Archive'
+origination_date = "{date}"
+time_reference = 48_000
+
+[info]
+ICOP = "(c) \"Archive\"é"
+IART = "{dir}"
+
+[ixml]
+PROJECT = "Dawn chorus"
+USER.COLLECTION = "{dir}"
+"#;
+
+    #[test]
+    fn parse_toml_and_json() {
+        let template = Template::from_str(TOML).unwrap();
+        dbg!(&template);
+        assert_eq!(template.bext.len(), 4);
+        assert_eq!(
+            template.bext[1],
+            ("originator".into(), "Sound Archive".into())
+        );
+        assert_eq!(template.bext[3], ("time_reference".into(), "48000".into()));
+        assert_eq!(
+            template.info[0],
+            (FourCC(*b"ICOP"), "(c) \"Archive\"é".into())
+        );
+        assert_eq!(template.info[1].0, FourCC(*b"IART"));
+        assert_eq!(
+            template.ixml[1],
+            (vec!["USER".into(), "COLLECTION".into()], "{dir}".into())
+        );
+
+        let json = r#"{"bext": {"description": "Dawn chorus, {stem}", "originator": "Sound Archive",
+            "origination_date": "{date}", "time_reference": 48000},
+            "info": {"ICOP": "(c) \"Archive\"é", "IART": "{dir}"},
+            "ixml": {"PROJECT": "Dawn chorus", "USER": {"COLLECTION": "{dir}"}}}"#;
+        assert_eq!(Template::from_str(json).unwrap(), template);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |text: &str| Template::from_str(text).unwrap_err().to_string();
+        assert_eq!(
+            err("\n[bext]\ndescripton = \"x\""),
+            "Parse: template line 3: unknown bext field \"descripton\", expected one of: \
+            description, originator, originator_reference, origination_date, \
+            origination_time, time_reference, umid, coding_history"
+        );
+        assert!(err("INAM = \"x\"").contains("outside a [bext]"));
+        assert!(err("[info]\nINAM = x").contains("expected a quoted string"));
+        assert!(err("[info]\nINAM = \"x").contains("missing closing"));
+        assert!(err("[info]\nINAME = \"x\"").contains("1 to 4"));
+        assert!(err("[other]").contains("unknown section"));
+        assert!(err("{\"info\": {\"INAM\": [1]}}").contains("INAM: expected a string"));
+    }
+
+    #[test]
+    fn placeholders_for_file() {
+        let placeholders = Placeholders::for_file(&PathBuf::from("archive/birds/dawn_01.wav"), 3);
+        let expanded = placeholders
+            .expand("{dir}/{stem} {filename} #{index} {{x}} {path}")
+            .unwrap();
+        assert_eq!(
+            expanded,
+            "birds/dawn_01 dawn_01.wav #3 {x} archive/birds/dawn_01.wav"
+        );
+        assert_eq!(placeholders.get("date").unwrap().len(), 10);
+        assert!(placeholders.expand("{stem").is_err());
+    }
+
+    #[cfg(all(feature = "broadcast", feature = "info", feature = "ixml"))]
+    #[test]
+    fn apply_example_a() {
+        use std::fs::File;
+        use std::io::BufReader;
+
+        let template = Template::from_str(TOML).unwrap();
+        let mut placeholders = Placeholders::for_file(&PathBuf::from("birds/dawn_01.wav"), 1);
+        placeholders.set("date", "2024-05-06");
+
+        let file = BufReader::new(File::open("../test_wavs/example_a.wav").unwrap());
+        let mut wave = WaveFile::from_reader(file).unwrap();
+        let adtl_before = wave.metadata().cue_labels();
+        let mut output = Cursor::new(Vec::new());
+        template
+            .apply(&mut wave, &mut output, &placeholders)
+            .unwrap();
+
+        output.set_position(0);
+        let mut wave = WaveFile::from_reader(output).unwrap();
+        let metadata = wave.metadata();
+        let bext = metadata.bext().unwrap();
+        assert_eq!(bext.description.to_string(), "Dawn chorus, dawn_01");
+        assert_eq!(bext.originator.to_string(), "Sound Archive");
+        assert_eq!(bext.origination_date.to_string(), "2024-05-06");
+        assert_eq!(bext.time_reference, 48_000);
+        assert_eq!(metadata.info(FourCC(*b"IART")).as_deref(), Some("birds"));
+        assert!(metadata.info(FourCC(*b"INAM")).is_some());
+        let ixml = metadata.ixml().unwrap();
+        let user = ixml.user.as_ref().unwrap();
+        assert_eq!(user.get("COLLECTION"), Some("birds"));
+        assert_eq!(metadata.cue_labels(), adtl_before);
+    }
+
+    #[test]
+    fn apply_rejects_bad_values() {
+        let template = Template::from_str("[bext]\norigination_date = \"yesterday\"").unwrap();
+        let input =
+            crate::testing::hex_to_cursor("52494646 10000000 57415645 66616374 04000000 E0010000");
+        let mut wave = WaveFile::from_reader(input).unwrap();
+        let mut output = Cursor::new(Vec::new());
+        assert!(template
+            .apply(&mut wave, &mut output, &Placeholders::new())
+            .is_err());
+        assert!(output.into_inner().is_empty());
+    }
+}
@@ -3,13 +3,135 @@
 #![doc(hidden)]
 #![allow(dead_code)]
 
-use std::io::Cursor;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use binrw::io::Cursor;
+use binrw::{BinRead, BinWrite};
 
 use hex::decode;
 
+use crate::{FourCC, KnownChunk, KnownChunkID};
+
 pub fn hex_to_cursor(data: &str) -> Cursor<Vec<u8>> {
     let data = data.replace(' ', "");
     let data = data.replace('\n', "");
     let data = decode(data).expect("while decoding hex data from string");
     Cursor::new(data)
 }
+
+/// Deterministic pseudo random values for round trip tests, see
+/// `roundtrip_tests!`.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Rng(seed | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    pub fn next_u16(&mut self) -> u16 {
+        (self.next_u64() >> 48) as u16
+    }
+
+    pub fn next_u8(&mut self) -> u8 {
+        (self.next_u64() >> 56) as u8
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            return 0;
+        }
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| self.next_u8()).collect()
+    }
+
+    /// Printable ASCII without zero bytes, which end RIFF strings.
+    pub fn ascii(&mut self, max_len: usize) -> String {
+        let len = self.below(max_len + 1);
+        (0..len)
+            .map(|_| char::from(b' ' + (self.next_u8() % 95)))
+            .collect()
+    }
+
+    pub fn fourcc(&mut self) -> FourCC {
+        FourCC([
+            b'a' + self.next_u8() % 26,
+            b'a' + self.next_u8() % 26,
+            b'a' + self.next_u8() % 26,
+            b'a' + self.next_u8() % 26,
+        ])
+    }
+}
+
+/// Random valid values for round trip tests.
+pub trait Arbitrary: Sized {
+    fn arbitrary(rng: &mut Rng) -> Self;
+}
+
+/// Write `data` as a chunk, read it back and check that writing the result
+/// gives the same bytes. Returns the data read.
+pub fn roundtrip<T>(data: &T) -> T
+where
+    T: for<'a> BinRead<Args<'a> = (u32,)>
+        + for<'a> BinWrite<Args<'a> = ()>
+        + KnownChunkID
+        + Clone
+        + Debug,
+{
+    let chunk = KnownChunk::new(data.clone()).expect("chunk size should fit");
+    let mut buff = Cursor::new(Vec::new());
+    chunk.write(&mut buff).expect("error writing chunk");
+    let bytes = buff.into_inner();
+
+    let mut buff = Cursor::new(bytes.clone());
+    let read = KnownChunk::<T>::read(&mut buff)
+        .unwrap_or_else(|err| panic!("error reading {:?} from {bytes:02X?}: {err}", data));
+    assert_eq!(read.size, chunk.size, "size of {:?}", data);
+    assert!(read.extra_bytes.is_empty(), "extra bytes for {:?}", data);
+
+    let mut buff = Cursor::new(Vec::new());
+    read.write(&mut buff).expect("error writing chunk");
+    assert_eq!(buff.into_inner(), bytes, "bytes for {:?}", data);
+    read.data
+}
+
+/// Generate tests which round trip random chunk data through
+/// [`roundtrip()`] and compare it with the original. Takes a list of
+/// `test_name: Type` pairs, where `Type` implements [`Arbitrary`].
+#[cfg(test)]
+macro_rules! roundtrip_tests {
+    ($($name:ident: $type:ty),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                use $crate::testing::Arbitrary;
+                let mut rng = $crate::testing::Rng::new(0x5EED_0000 + line!() as u64);
+                for _ in 0..64 {
+                    let data = <$type>::arbitrary(&mut rng);
+                    assert_eq!($crate::testing::roundtrip(&data), data);
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+pub(crate) use roundtrip_tests;
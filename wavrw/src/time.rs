@@ -0,0 +1,663 @@
+//! Convert sample offsets into human readable times.
+//!
+//! Many chunks store positions as a count of samples: `cue ` points, `smpl`
+//! loops and the `bext` time reference for example. Converting these to a
+//! time requires the sample rate from the `fmt ` chunk.
+//!
+//! ```
+//! use wavrw::time::SampleTime;
+//!
+//! let time = SampleTime::new(48_000 * 3723 + 12_000, 48_000);
+//! assert_eq!(time.to_string(), "01:02:03.250");
+//! assert_eq!(time.with_frame_rate(25).to_string(), "01:02:03:06 @ 25fps");
+//! ```
+
+use alloc::format;
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, Sub};
+use core::str::FromStr;
+
+use crate::WaveFileError;
+
+/// A sample offset with the sample rate needed to interpret it as a time.
+///
+/// Displays as `hh:mm:ss.mmm`, or as `hh:mm:ss:ff @ NNfps` timecode when a
+/// frame rate is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SampleTime {
+    /// Offset in samples (per channel).
+    pub samples: u64,
+    /// Sample rate from the `fmt ` chunk.
+    pub samples_per_sec: u32,
+    /// Optional timecode frame rate, in whole frames per second.
+    pub frame_rate: Option<u32>,
+}
+
+impl SampleTime {
+    /// Create a time from a sample offset and sample rate.
+    pub fn new(samples: u64, samples_per_sec: u32) -> Self {
+        SampleTime {
+            samples,
+            samples_per_sec,
+            frame_rate: None,
+        }
+    }
+
+    /// Display as timecode with `frame_rate` frames per second.
+    #[must_use]
+    pub fn with_frame_rate(mut self, frame_rate: u32) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    /// The same sample offset at `samples_per_sec`. Ex: real time of a
+    /// recording made at a different rate than the file plays at, see
+    /// [`WaveMetadata::real_time_sample_rate()`](crate::WaveMetadata::real_time_sample_rate).
+    ///
+    /// ```
+    /// use wavrw::time::SampleTime;
+    ///
+    /// let time = SampleTime::new(48_048 * 60, 48_000);
+    /// assert_eq!(time.to_string(), "00:01:00.060");
+    /// assert_eq!(time.at_sample_rate(48_048).to_string(), "00:01:00.000");
+    /// ```
+    #[must_use]
+    pub fn at_sample_rate(mut self, samples_per_sec: u32) -> Self {
+        self.samples_per_sec = samples_per_sec;
+        self
+    }
+
+    /// Whole seconds since sample 0, `None` if the sample rate is 0.
+    pub fn seconds(&self) -> Option<u64> {
+        self.samples.checked_div(self.samples_per_sec.into())
+    }
+
+    /// Samples remaining after the whole [`seconds()`](SampleTime::seconds).
+    fn subsecond_samples(&self) -> u64 {
+        self.samples
+            .checked_rem(self.samples_per_sec.into())
+            .unwrap_or(0)
+    }
+
+    /// Milliseconds past the whole [`seconds()`](SampleTime::seconds).
+    pub fn subsecond_millis(&self) -> u64 {
+        (self.subsecond_samples() * 1000)
+            .checked_div(self.samples_per_sec.into())
+            .unwrap_or(0)
+    }
+
+    /// Frames past the whole [`seconds()`](SampleTime::seconds), `None` if no
+    /// frame rate is set.
+    pub fn subsecond_frames(&self) -> Option<u64> {
+        let frame_rate = u64::from(self.frame_rate?);
+        (self.subsecond_samples() * frame_rate).checked_div(self.samples_per_sec.into())
+    }
+
+    /// Non drop frame [`Timecode`] at the frame rate, `None` if no frame
+    /// rate is set.
+    pub fn timecode(&self) -> Option<Timecode> {
+        let rate = Rational::from(self.frame_rate?);
+        Some(Timecode::from_samples(
+            self.samples,
+            self.samples_per_sec,
+            rate,
+            false,
+        ))
+    }
+}
+
+impl Display for SampleTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Some(seconds) = self.seconds() else {
+            return f.pad(&format!("{} samples", self.samples));
+        };
+        let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        // format first, so width and alignment flags apply to the whole time
+        let text = match (self.frame_rate, self.timecode()) {
+            (Some(fps), Some(timecode)) => format!("{timecode} @ {fps}fps"),
+            _ => format!("{h:02}:{m:02}:{s:02}.{:03}", self.subsecond_millis()),
+        };
+        f.pad(&text)
+    }
+}
+
+/// A rational number like timecode rates and the iXML speeds. Ex: `24000/1001`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    /// Numerator, ex: 24000
+    pub numerator: u32,
+    /// Denominator, ex: 1001
+    pub denominator: u32,
+}
+
+impl Rational {
+    /// Value as a floating point number, ex: 23.976...
+    pub fn as_f64(&self) -> f64 {
+        f64::from(self.numerator) / f64::from(self.denominator)
+    }
+}
+
+impl From<u32> for Rational {
+    /// A whole number as `n/1`.
+    fn from(value: u32) -> Self {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+}
+
+impl FromStr for Rational {
+    type Err = WaveFileError;
+
+    /// Parse `numerator/denominator`, or a whole number as `n/1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || WaveFileError::parse(None, format!("invalid rational: {s:?}"));
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+        let rational = Rational {
+            numerator: numerator.trim().parse().map_err(|_| err())?,
+            denominator: denominator.trim().parse().map_err(|_| err())?,
+        };
+        if rational.denominator == 0 {
+            return Err(err());
+        }
+        Ok(rational)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// A timecode: a count of frames since midnight at a frame rate.
+///
+/// Timecode labels count whole frames at the nominal rate, ex: 30 for
+/// `30000/1001`. Drop frame timecode skips frame numbers 0 and 1 (0 to 3 at
+/// 60 fps) at the start of each minute, except every tenth minute, so the
+/// labels stay close to real time. Timecode wraps at 24 hours.
+///
+/// Displays as `hh:mm:ss:ff`, or `hh:mm:ss;ff` for drop frame timecode.
+///
+/// ```
+/// use wavrw::time::{Rational, Timecode};
+///
+/// let rate = Rational { numerator: 30000, denominator: 1001 };
+/// let timecode = Timecode::parse("00:00:59;29", rate)?;
+/// assert!(timecode.drop_frame);
+/// assert_eq!((timecode + 1).to_string(), "00:01:00;02");
+/// assert_eq!(Timecode::from_samples(2_882_880, 48_000, rate, true), timecode + 1);
+/// # Ok::<(), wavrw::WaveFileError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    /// Timecode frames per second, ex: `30000/1001`
+    pub rate: Rational,
+    /// Drop frame timecode.
+    pub drop_frame: bool,
+    /// Frames since midnight.
+    pub frames: u64,
+}
+
+impl Timecode {
+    /// Timecode `frames` since midnight.
+    pub fn new(rate: Rational, drop_frame: bool, frames: u64) -> Self {
+        Timecode {
+            rate,
+            drop_frame,
+            frames,
+        }
+    }
+
+    /// Timecode of the frame containing sample `samples` since midnight at
+    /// `sample_rate`.
+    pub fn from_samples(samples: u64, sample_rate: u32, rate: Rational, drop_frame: bool) -> Self {
+        let elapsed = u128::from(samples) * u128::from(rate.numerator);
+        let per_frame = u128::from(sample_rate) * u128::from(rate.denominator);
+        let frames = elapsed.checked_div(per_frame).unwrap_or(0);
+        Timecode::new(rate, drop_frame, u64::try_from(frames).unwrap_or(u64::MAX))
+    }
+
+    /// Timecode from the `hours`, `minutes`, `seconds` and `frames` labels.
+    ///
+    /// Returns an error if a label is out of range, or is a frame number
+    /// skipped by drop frame timecode.
+    pub fn from_parts(
+        (hours, minutes, seconds, frames): (u8, u8, u8, u32),
+        rate: Rational,
+        drop_frame: bool,
+    ) -> Result<Self, WaveFileError> {
+        let mut timecode = Timecode::new(rate, drop_frame, 0);
+        let nominal = u64::from(timecode.nominal_rate());
+        let dropped = timecode.dropped_per_minute();
+        let frames = u64::from(frames);
+        let skipped = drop_frame && seconds == 0 && minutes % 10 != 0 && frames < dropped;
+        if hours >= 24 || minutes >= 60 || seconds >= 60 || frames >= nominal || skipped {
+            return Err(WaveFileError::parse(
+                None,
+                format!(
+                    "invalid timecode: {hours:02}:{minutes:02}:{seconds:02}:{frames:02} at {rate}"
+                ),
+            ));
+        }
+        let total_minutes = u64::from(hours) * 60 + u64::from(minutes);
+        let total_seconds = total_minutes * 60 + u64::from(seconds);
+        timecode.frames =
+            total_seconds * nominal + frames - dropped * (total_minutes - total_minutes / 10);
+        Ok(timecode)
+    }
+
+    /// Parse `hh:mm:ss:ff` at `rate`. A `;` or `.` before the frames marks
+    /// drop frame timecode, as in `hh:mm:ss;ff`.
+    pub fn parse(s: &str, rate: Rational) -> Result<Self, WaveFileError> {
+        let trimmed = s.trim();
+        let separator = trimmed
+            .rfind(|c: char| !c.is_ascii_digit())
+            .and_then(|split| trimmed[split..].chars().next());
+        let drop_frame = matches!(separator, Some(';' | '.'));
+        Timecode::parse_with_flag(s, rate, drop_frame)
+    }
+
+    /// Parse `hh:mm:ss:ff` at `rate`, with any single separator before the
+    /// frames. Ex: `ISMP` text like `01:00:00.00`, which doesn't mark drop
+    /// frame timecode.
+    pub fn parse_with_flag(
+        s: &str,
+        rate: Rational,
+        drop_frame: bool,
+    ) -> Result<Self, WaveFileError> {
+        let err = || WaveFileError::parse(None, format!("invalid timecode: {s:?}"));
+        let s = s.trim();
+        let Some(split) = s.rfind(|c: char| !c.is_ascii_digit()) else {
+            return Err(err());
+        };
+        let (time, frames) = s.split_at(split);
+        let Some(separator) = frames.chars().next() else {
+            return Err(err());
+        };
+        let (hours, minutes, seconds) = parse_triple(time, "timecode")?;
+        let hours = u8::try_from(hours).map_err(|_| err())?;
+        let frames = frames[separator.len_utf8()..].parse().map_err(|_| err())?;
+        Timecode::from_parts((hours, minutes, seconds, frames), rate, drop_frame)
+    }
+
+    /// Parse a drop frame flag, `DF` is true and `NDF` is false. Ex: the
+    /// iXML `TIMECODE_FLAG`.
+    pub fn parse_flag(flag: &str) -> Result<bool, WaveFileError> {
+        let flag = flag.trim();
+        if flag.eq_ignore_ascii_case("DF") {
+            Ok(true)
+        } else if flag.eq_ignore_ascii_case("NDF") {
+            Ok(false)
+        } else {
+            Err(WaveFileError::parse(
+                None,
+                format!("invalid timecode flag: {flag:?}"),
+            ))
+        }
+    }
+
+    /// Drop frame flag, `DF` or `NDF`, see [`Timecode::parse_flag()`].
+    pub fn flag(&self) -> &'static str {
+        if self.drop_frame {
+            "DF"
+        } else {
+            "NDF"
+        }
+    }
+
+    /// Frames per second counted by the labels, ex: 30 for `30000/1001`.
+    pub fn nominal_rate(&self) -> u32 {
+        self.rate
+            .numerator
+            .div_ceil(self.rate.denominator.max(1))
+            .max(1)
+    }
+
+    /// Frame numbers skipped at the start of most minutes, 0 for non drop
+    /// frame timecode.
+    fn dropped_per_minute(&self) -> u64 {
+        if self.drop_frame {
+            u64::from(self.nominal_rate() / 15)
+        } else {
+            0
+        }
+    }
+
+    /// Frames in 24 hours of timecode.
+    pub fn frames_per_day(&self) -> u64 {
+        let nominal = u64::from(self.nominal_rate());
+        86_400 * nominal - self.dropped_per_minute() * (1440 - 144)
+    }
+
+    /// First sample of the frame at `sample_rate`, the inverse of
+    /// [`Timecode::from_samples()`].
+    pub fn to_samples(&self, sample_rate: u32) -> u64 {
+        let elapsed =
+            u128::from(self.frames) * u128::from(sample_rate) * u128::from(self.rate.denominator);
+        let samples = elapsed.div_ceil(u128::from(self.rate.numerator.max(1)));
+        u64::try_from(samples).unwrap_or(u64::MAX)
+    }
+
+    /// Hours, minutes, seconds and frames labels, wrapped at 24 hours.
+    pub fn parts(&self) -> (u8, u8, u8, u32) {
+        let nominal = u64::from(self.nominal_rate());
+        let dropped = self.dropped_per_minute();
+        let mut frames = self.frames % self.frames_per_day();
+        if dropped > 0 {
+            let per_minute = nominal * 60 - dropped;
+            let per_ten_minutes = per_minute * 10 + dropped;
+            let remainder = frames % per_ten_minutes;
+            frames += dropped * 9 * (frames / per_ten_minutes);
+            if remainder > dropped {
+                frames += dropped * ((remainder - dropped) / per_minute);
+            }
+        }
+        let seconds = frames / nominal;
+        // values are in range after the modulo
+        let part = |value: u64, modulo: u64| u8::try_from(value % modulo).unwrap_or_default();
+        (
+            part(seconds / 3600, 24),
+            part(seconds / 60, 60),
+            part(seconds, 60),
+            u32::try_from(frames % nominal).unwrap_or_default(),
+        )
+    }
+}
+
+impl Add<u64> for Timecode {
+    type Output = Timecode;
+
+    /// Timecode `frames` later, wrapping at 24 hours.
+    fn add(self, frames: u64) -> Self::Output {
+        let per_day = self.frames_per_day();
+        Timecode {
+            frames: (self.frames % per_day + frames % per_day) % per_day,
+            ..self
+        }
+    }
+}
+
+impl Sub<u64> for Timecode {
+    type Output = Timecode;
+
+    /// Timecode `frames` earlier, wrapping at 24 hours.
+    fn sub(self, frames: u64) -> Self::Output {
+        let per_day = self.frames_per_day();
+        Timecode {
+            frames: (self.frames % per_day + per_day - frames % per_day) % per_day,
+            ..self
+        }
+    }
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (hours, minutes, seconds, frames) = self.parts();
+        let separator = if self.drop_frame { ';' } else { ':' };
+        f.pad(&format!(
+            "{hours:02}:{minutes:02}:{seconds:02}{separator}{frames:02}"
+        ))
+    }
+}
+
+/// Split `s` into three numbers separated by any single non digit character.
+fn parse_triple(s: &str, what: &str) -> Result<(u16, u8, u8), WaveFileError> {
+    let err = || WaveFileError::parse(None, format!("invalid {what}: {s:?}"));
+    let mut parts = s.trim().split(|c: char| !c.is_ascii_digit());
+    let (Some(a), Some(b), Some(c), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(err());
+    };
+    Ok((
+        a.parse().map_err(|_| err())?,
+        b.parse().map_err(|_| err())?,
+        c.parse().map_err(|_| err())?,
+    ))
+}
+
+/// A calendar date, as stored in `bext` `origination_date`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Year, ex: 2024
+    pub year: u16,
+    /// Month, 1 to 12
+    pub month: u8,
+    /// Day of the month, 1 to 31
+    pub day: u8,
+}
+
+impl Date {
+    /// Days in `month` of `year`, 0 if `month` is not 1 to 12.
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            _ => 0,
+        }
+    }
+
+    /// True if `day` exists in `month` of `year`. Ex: false for 2023-02-29.
+    pub fn is_valid(&self) -> bool {
+        (1..=Date::days_in_month(self.year, self.month)).contains(&self.day)
+    }
+
+    /// The date `days` after 1970-01-01, in the proleptic Gregorian
+    /// calendar. Ex: for the current date from [`std::time::SystemTime`].
+    pub fn from_unix_days(days: u64) -> Self {
+        // Howard Hinnant's civil_from_days, shifted to start on 0000-03-01
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        // day and month are in range by construction, years past 65535 saturate
+        Date {
+            year: u16::try_from(year).unwrap_or(u16::MAX),
+            month: u8::try_from(month).unwrap_or_default(),
+            day: u8::try_from(day).unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for Date {
+    type Err = WaveFileError;
+
+    /// Parse `yyyy-mm-dd`. Any single separator character is accepted, the
+    /// `bext` specification allows `-`, `_`, `:`, space and `.`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = parse_triple(s, "date")?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(WaveFileError::parse(None, format!("invalid date: {s:?}")));
+        }
+        Ok(Date { year, month, day })
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day with whole second resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    /// Hour, 0 to 23
+    pub hour: u8,
+    /// Minute, 0 to 59
+    pub minute: u8,
+    /// Second, 0 to 59
+    pub second: u8,
+}
+
+impl TimeOfDay {
+    /// Time of day `seconds` after midnight, wrapping at 24 hours.
+    pub fn from_seconds(seconds: u64) -> Self {
+        let seconds = seconds % 86_400;
+        // values are in range after the modulo above
+        TimeOfDay {
+            hour: u8::try_from(seconds / 3600).unwrap_or_default(),
+            minute: u8::try_from(seconds / 60 % 60).unwrap_or_default(),
+            second: u8::try_from(seconds % 60).unwrap_or_default(),
+        }
+    }
+
+    /// Seconds since midnight.
+    pub fn seconds(&self) -> u32 {
+        u32::from(self.hour) * 3600 + u32::from(self.minute) * 60 + u32::from(self.second)
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = WaveFileError;
+
+    /// Parse `hh:mm:ss`. Any single separator character is accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hour, minute, second) = parse_triple(s, "time")?;
+        match u8::try_from(hour) {
+            Ok(hour) if hour < 24 && minute < 60 && second < 60 => Ok(TimeOfDay {
+                hour,
+                minute,
+                second,
+            }),
+            _ => Err(WaveFileError::parse(None, format!("invalid time: {s:?}"))),
+        }
+    }
+}
+
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+/// A date and time of day plus the exact sample offset since midnight.
+///
+/// `time` is rounded down to the second, `sample_offset` keeps the full
+/// precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Calendar date
+    pub date: Date,
+    /// Time of day, whole seconds
+    pub time: TimeOfDay,
+    /// Samples since midnight
+    pub sample_offset: u64,
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.date, self.time)
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sample_time_display() {
+        assert_eq!(SampleTime::new(0, 48_000).to_string(), "00:00:00.000");
+        assert_eq!(SampleTime::new(240, 48_000).to_string(), "00:00:00.005");
+        let time = SampleTime::new(48_000 * 3723 + 4 * 1920, 48_000).with_frame_rate(25);
+        assert_eq!(time.to_string(), "01:02:03:04 @ 25fps");
+        // larger than u32 sample offsets, as used by bext time_reference
+        let time = SampleTime::new(96_000 * 86_399, 96_000);
+        assert_eq!(time.to_string(), "23:59:59.000");
+        assert_eq!(format!("{time:>14}"), "  23:59:59.000");
+    }
+
+    #[test]
+    fn sample_time_zero_rate() {
+        let time = SampleTime::new(1234, 0).with_frame_rate(25);
+        assert_eq!(time.seconds(), None);
+        assert_eq!(time.to_string(), "1234 samples");
+    }
+
+    #[test]
+    fn timecode() {
+        let rate = Rational::from(25);
+        let timecode = Timecode::parse("01:02:03:04", rate).unwrap();
+        dbg!(&timecode);
+        assert_eq!(timecode.frames, (3723 * 25) + 4);
+        assert_eq!(timecode.to_string(), "01:02:03:04");
+        assert_eq!(timecode.flag(), "NDF");
+        assert_eq!((timecode + 21).to_string(), "01:02:04:00");
+        assert_eq!(
+            (Timecode::new(rate, false, 0) - 1).to_string(),
+            "23:59:59:24"
+        );
+        assert!(Timecode::parse("01:02:03:25", rate).is_err());
+        assert!(Timecode::parse("24:00:00:00", rate).is_err());
+        assert!(Timecode::parse("01:02:03", rate).is_err());
+        assert!(Timecode::parse("01:02:03;04", rate).unwrap().drop_frame);
+        let parsed = Timecode::parse_with_flag("01:02:03.04", rate, false).unwrap();
+        assert_eq!(parsed, timecode);
+
+        // 29.97 drop frame: labels ;00 and ;01 are skipped except every
+        // tenth minute, every frame round trips through its label
+        let rate = Rational {
+            numerator: 30000,
+            denominator: 1001,
+        };
+        let timecode = Timecode::new(rate, true, 0);
+        assert_eq!(timecode.frames_per_day(), 2_589_408);
+        assert!(Timecode::parse("00:01:00;01", rate).is_err());
+        assert_eq!(Timecode::parse("00:10:00;00", rate).unwrap().frames, 17_982);
+        for frames in (0..timecode.frames_per_day()).step_by(7) {
+            let timecode = timecode + frames;
+            let parsed = Timecode::parse(&timecode.to_string(), rate).unwrap();
+            assert_eq!(parsed, timecode);
+            let samples = timecode.to_samples(48_000);
+            assert_eq!(
+                Timecode::from_samples(samples, 48_000, rate, true),
+                timecode
+            );
+        }
+        assert_eq!((timecode - 1).to_string(), "23:59:59;29");
+
+        assert!(Timecode::parse_flag(" df ").unwrap());
+        assert!(!Timecode::parse_flag("NDF").unwrap());
+        assert!(Timecode::parse_flag("").is_err());
+    }
+
+    #[test]
+    fn parse_date_and_time() {
+        let date = Date::from_str("2006/01/02").unwrap();
+        assert_eq!((date.year, date.month, date.day), (2006, 1, 2));
+        assert_eq!(date.to_string(), "2006-01-02");
+        assert!(Date::from_str("2006-13-02").is_err());
+        assert!(Date::from_str("").is_err());
+        assert!(Date::from_str("2024-02-29").unwrap().is_valid());
+        assert!(!Date::from_str("2023-02-29").unwrap().is_valid());
+        assert!(!Date::from_str("1900-02-29").unwrap().is_valid());
+        assert!(!Date::from_str("2023-04-31").unwrap().is_valid());
+        assert_eq!(Date::from_unix_days(0).to_string(), "1970-01-01");
+        assert_eq!(Date::from_unix_days(11_016).to_string(), "2000-02-29");
+        assert_eq!(Date::from_unix_days(19_782).to_string(), "2024-02-29");
+        assert_eq!(Date::from_unix_days(20_742).to_string(), "2026-10-16");
+
+        let time = TimeOfDay::from_str("03:04:05").unwrap();
+        assert_eq!(time.seconds(), 3 * 3600 + 4 * 60 + 5);
+        assert_eq!(TimeOfDay::from_seconds(u64::from(time.seconds())), time);
+        assert!(TimeOfDay::from_str("24:00:00").is_err());
+        assert!(TimeOfDay::from_str("03:04").is_err());
+    }
+}
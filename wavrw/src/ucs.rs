@@ -0,0 +1,313 @@
+//! Universal Category System (UCS) file names.
+//!
+//! [UCS](https://universalcategorysystem.com) names sound effects files
+//! `CatID_FXName_CreatorID_SourceID`, with optional parts:
+//! `CatID-UserCategory_VendorCategory-FXName_CreatorID_SourceID_UserData`.
+//! Ex: `DOORWood_Front Door Slam_JD_GARDEN.wav`
+//!
+//! The same values are often embedded in iXML, in the Soundminer `USER`
+//! section and the ASWG section. [`UcsName::check_ixml()`] reports where
+//! they disagree with the file name and [`UcsName::fill_ixml()`] writes
+//! them.
+//!
+//! ```
+//! use wavrw::ucs::UcsName;
+//!
+//! let name: UcsName = "DOORWood-Mine_Front Door Slam_JD_GARDEN.wav".parse()?;
+//! assert_eq!(name.cat_id, "DOORWood");
+//! assert_eq!(name.user_category.as_deref(), Some("Mine"));
+//! assert_eq!(name.fx_name, "Front Door Slam");
+//! assert_eq!(name.to_string(), "DOORWood-Mine_Front Door Slam_JD_GARDEN");
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+#[cfg(feature = "ixml")]
+use alloc::vec::Vec;
+#[cfg(all(feature = "std", feature = "ixml"))]
+use core::fmt::Debug;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[cfg(all(feature = "std", feature = "ixml"))]
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+#[cfg(feature = "ixml")]
+use crate::chunk::ixml::Ixml;
+#[cfg(feature = "ixml")]
+use crate::warning::Code;
+use crate::WaveFileError;
+#[cfg(all(feature = "std", feature = "ixml"))]
+use crate::{edit::inject_chunks, WaveFile};
+#[cfg(feature = "ixml")]
+use crate::{KnownChunkID, Warning};
+
+/// The parts of a UCS file name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UcsName {
+    /// Category and subcategory abbreviation. Ex: `DOORWood`
+    pub cat_id: String,
+    /// Optional user defined category after the `CatID`.
+    pub user_category: Option<String>,
+    /// Optional vendor category before the `FXName`.
+    pub vendor_category: Option<String>,
+    /// Short description. Ex: `Front Door Slam`
+    pub fx_name: String,
+    /// Sound designer, recordist or vendor. Ex: `JD`
+    pub creator_id: String,
+    /// Project, library or show. Ex: `GARDEN`
+    pub source_id: String,
+    /// Optional user data at the end, may contain `_`.
+    pub user_data: Option<String>,
+}
+
+/// UCS fields with their Soundminer `USER` and ASWG iXML element names.
+#[cfg(feature = "ixml")]
+const IXML_FIELDS: [(&str, Option<&str>, &str); 7] = [
+    ("CatID", Some("CATID"), "catId"),
+    ("UserCategory", Some("USERCATEGORY"), "userCategory"),
+    ("VendorCategory", Some("VENDORCATEGORY"), "vendorCategory"),
+    ("FXName", Some("FXNAME"), "fxName"),
+    ("CreatorID", None, "creatorId"),
+    ("SourceID", None, "sourceId"),
+    ("UserData", None, "userData"),
+];
+
+impl UcsName {
+    /// Value of a field by its UCS name. Ex: `CatID`
+    pub fn field(&self, name: &str) -> Option<&str> {
+        match name {
+            "CatID" => Some(&self.cat_id),
+            "UserCategory" => self.user_category.as_deref(),
+            "VendorCategory" => self.vendor_category.as_deref(),
+            "FXName" => Some(&self.fx_name),
+            "CreatorID" => Some(&self.creator_id),
+            "SourceID" => Some(&self.source_id),
+            "UserData" => self.user_data.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compare with the UCS fields in the Soundminer `USER` and ASWG
+    /// sections of `ixml`, one [`Warning`] per field which differs.
+    ///
+    /// Fields missing from `ixml` are not reported.
+    #[cfg(feature = "ixml")]
+    pub fn check_ixml(&self, ixml: &Ixml) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        for (name, user, aswg) in IXML_FIELDS {
+            let expected = self.field(name).unwrap_or_default();
+            let elements = user
+                .map(|user| ["USER", user])
+                .into_iter()
+                .chain([["ASWG", aswg]]);
+            for path in elements {
+                let Some(text) = ixml.text(&path) else {
+                    continue;
+                };
+                if text != expected {
+                    warnings.push(
+                        Warning::new(
+                            Ixml::ID,
+                            None,
+                            format!(
+                            "{} {text:?} does not match UCS {name} {expected:?} of the file name",
+                            path.join("/")
+                        ),
+                        )
+                        .with_code(Code::MetadataMismatch),
+                    );
+                }
+            }
+        }
+        warnings
+    }
+
+    /// A copy of `ixml` with the UCS fields set in the Soundminer `USER`
+    /// and ASWG sections. Existing elements of optional fields which are
+    /// not part of the name are emptied.
+    #[cfg(feature = "ixml")]
+    pub fn fill_ixml(&self, ixml: &Ixml) -> Result<Ixml, WaveFileError> {
+        let mut ixml = ixml.clone();
+        for (name, user, aswg) in IXML_FIELDS {
+            let value = self.field(name);
+            let elements = user
+                .map(|user| ["USER", user])
+                .into_iter()
+                .chain([["ASWG", aswg]]);
+            for path in elements {
+                match value {
+                    Some(value) => ixml = ixml.with_text(&path, value)?,
+                    None if ixml.text(&path).is_some() => ixml = ixml.with_text(&path, "")?,
+                    None => (),
+                }
+            }
+        }
+        Ok(ixml)
+    }
+
+    /// Write a copy of `wave` to `writer` with the UCS fields set in its
+    /// iXML chunk, see [`UcsName::fill_ixml()`]. Adds an iXML chunk if the
+    /// file has none.
+    #[cfg(all(feature = "std", feature = "ixml"))]
+    pub fn apply<R, W>(&self, wave: &mut WaveFile<R>, writer: W) -> Result<(), WaveFileError>
+    where
+        R: Read + Seek + Debug + BufRead,
+        W: Write + Seek,
+    {
+        let ixml = match wave.metadata().ixml() {
+            Some(ixml) => self.fill_ixml(ixml)?,
+            None => self.fill_ixml(&Ixml::from_bytes(b"<BWFXML/>".to_vec()))?,
+        };
+        wave.bytes.seek(SeekFrom::Start(wave.chunks_start - 12))?;
+        inject_chunks(&mut wave.bytes, writer, &[(Ixml::ID, &ixml.raw_bytes)])
+    }
+}
+
+/// True if `cat_id` looks like a UCS `CatID`: an upper case category
+/// abbreviation followed by a capitalized subcategory. Ex: `DOORWood`,
+/// `AMBForst`
+///
+/// Only the form is checked, not the list of categories.
+pub fn is_cat_id(cat_id: &str) -> bool {
+    let upper = cat_id.chars().take_while(char::is_ascii_uppercase).count();
+    let rest = &cat_id[upper..];
+    upper >= 3
+        && !rest.is_empty()
+        && rest.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && rest.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+impl FromStr for UcsName {
+    type Err = WaveFileError;
+
+    /// Parse a file name, with or without directory and extension.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |message: &str| WaveFileError::parse(None, format!("UCS name {s:?}: {message}"));
+        let name = s.rsplit(['/', '\\']).next().unwrap_or(s);
+        let stem = match name.rsplit_once('.') {
+            Some((stem, ext)) if !ext.is_empty() && !ext.contains(' ') => stem,
+            _ => name,
+        };
+
+        let mut parts = stem.splitn(5, '_');
+        let (Some(category), Some(fx_name), Some(creator_id), Some(source_id)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(err("expected CatID_FXName_CreatorID_SourceID"));
+        };
+        let user_data = parts.next().map(str::to_string);
+
+        let (cat_id, user_category) = match category.split_once('-') {
+            Some((cat_id, user_category)) => (cat_id, Some(user_category.to_string())),
+            None => (category, None),
+        };
+        if !is_cat_id(cat_id) {
+            return Err(err(&format!("invalid CatID {cat_id:?}")));
+        }
+        let (vendor_category, fx_name) = match fx_name.split_once('-') {
+            Some((vendor, fx_name)) => (Some(vendor.trim().to_string()), fx_name.trim()),
+            None => (None, fx_name),
+        };
+        if fx_name.is_empty() {
+            return Err(err("empty FXName"));
+        }
+        if creator_id.is_empty() || source_id.is_empty() {
+            return Err(err("empty CreatorID or SourceID"));
+        }
+
+        Ok(UcsName {
+            cat_id: cat_id.to_string(),
+            user_category,
+            vendor_category,
+            fx_name: fx_name.to_string(),
+            creator_id: creator_id.to_string(),
+            source_id: source_id.to_string(),
+            user_data,
+        })
+    }
+}
+
+impl Display for UcsName {
+    /// The file name without extension.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.cat_id)?;
+        if let Some(user_category) = &self.user_category {
+            write!(f, "-{user_category}")?;
+        }
+        f.write_str("_")?;
+        if let Some(vendor_category) = &self.vendor_category {
+            write!(f, "{vendor_category}-")?;
+        }
+        write!(f, "{}_{}_{}", self.fx_name, self.creator_id, self.source_id)?;
+        if let Some(user_data) = &self.user_data {
+            write!(f, "_{user_data}")?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_names() {
+        let name =
+            UcsName::from_str("lib/VEHCar-Test_Ford-Pass By Fast_JD_HIGHWAY_take 2_b.WAV").unwrap();
+        dbg!(&name);
+        assert_eq!(name.cat_id, "VEHCar");
+        assert_eq!(name.user_category.as_deref(), Some("Test"));
+        assert_eq!(name.vendor_category.as_deref(), Some("Ford"));
+        assert_eq!(name.fx_name, "Pass By Fast");
+        assert_eq!(name.creator_id, "JD");
+        assert_eq!(name.source_id, "HIGHWAY");
+        assert_eq!(name.user_data.as_deref(), Some("take 2_b"));
+        assert_eq!(
+            name.to_string(),
+            "VEHCar-Test_Ford-Pass By Fast_JD_HIGHWAY_take 2_b"
+        );
+
+        let name = UcsName::from_str("AMBForst_Birds 2.5 kHz_JD_GARDEN").unwrap();
+        assert_eq!(name.fx_name, "Birds 2.5 kHz");
+
+        assert!(is_cat_id("UIClick") && is_cat_id("AMBForst") && is_cat_id("GUN9mm"));
+        assert!(!is_cat_id("DOOR") && !is_cat_id("doorWood") && !is_cat_id("DOOR Wood"));
+
+        let err = |s: &str| UcsName::from_str(s).unwrap_err().to_string();
+        assert!(err("DOORWood_Slam_JD.wav").contains("expected CatID_FXName"));
+        assert!(err("Door_Slam_JD_GARDEN.wav").contains("invalid CatID \"Door\""));
+        assert!(err("DOORWood__JD_GARDEN.wav").contains("empty FXName"));
+        assert!(err("DOORWood_Slam__GARDEN.wav").contains("empty CreatorID"));
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn check_and_fill_ixml() {
+        let ixml = Ixml::from_bytes(
+            b"<BWFXML><USER><CATID>DOORWood</CATID><FXNAME>Back Door</FXNAME>\
+            <USERCATEGORY>Mine</USERCATEGORY></USER>\
+            <ASWG><catId>DOORMetl</catId></ASWG></BWFXML>"
+                .to_vec(),
+        );
+        let name = UcsName::from_str("DOORWood_Front Door_JD_GARDEN.wav").unwrap();
+        let warnings = name.check_ixml(&ixml);
+        dbg!(&warnings);
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(
+            warnings[0].message,
+            "ASWG/catId \"DOORMetl\" does not match UCS CatID \"DOORWood\" of the file name"
+        );
+        assert!(warnings[2].message.starts_with("USER/FXNAME \"Back Door\""));
+
+        let filled = name.fill_ixml(&ixml).unwrap();
+        assert!(name.check_ixml(&filled).is_empty());
+        assert_eq!(
+            filled.text(&["ASWG", "sourceId"]).as_deref(),
+            Some("GARDEN")
+        );
+        assert_eq!(filled.text(&["USER", "USERCATEGORY"]), None);
+        assert_eq!(filled.text(&["ASWG", "userCategory"]), None);
+    }
+}
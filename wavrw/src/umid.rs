@@ -0,0 +1,148 @@
+//! Generate SMPTE 330M basic UMIDs for new `bext` chunks.
+//!
+//! A basic UMID is 32 bytes: a 12 byte universal label, a length byte, a
+//! 3 byte instance number and a 16 byte material number which identifies
+//! the recording. [`Umid::random()`] creates one with a random material
+//! number, [`Umid::to_bext()`] pads it to the 64 byte `bext` field.
+//!
+//! ```
+//! use wavrw::chunk::bext::Bext;
+//! use wavrw::umid::Umid;
+//!
+//! let umid = Umid::random();
+//! let bext = Bext::builder().umid(umid.to_bext()).build()?;
+//! assert_eq!(Umid::from_bytes(&bext.umid), Some(umid));
+//! # Ok::<(), wavrw::WaveFileError>(())
+//! ```
+
+use core::fmt::{Display, Formatter};
+
+/// A SMPTE basic UMID, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Umid(pub [u8; 32]);
+
+impl Umid {
+    /// The first 10 bytes of the universal label, identifying a UMID.
+    pub const LABEL_PREFIX: [u8; 10] = [0x06, 0x0A, 0x2B, 0x34, 0x01, 0x01, 0x01, 0x05, 0x01, 0x01];
+
+    /// Material type byte of the label: audio material.
+    pub const MATERIAL_TYPE_AUDIO: u8 = 0x02;
+
+    /// Number creation method byte of the label: UUID material number,
+    /// locally registered instance number.
+    pub const METHOD_UUID: u8 = 0x10;
+
+    /// Length byte of a basic UMID, the 19 bytes after it.
+    pub const BASIC_LENGTH: u8 = 0x13;
+
+    /// Length byte of an extended UMID, which fills the 64 byte `bext`
+    /// field.
+    pub const EXTENDED_LENGTH: u8 = 0x33;
+
+    /// Basic UMID of audio material with `material_number`, instance 0.
+    ///
+    /// `material_number` should be a UUID, see [`Umid::random()`].
+    pub fn new(material_number: [u8; 16]) -> Self {
+        let mut bytes = [0_u8; 32];
+        bytes[..10].copy_from_slice(&Self::LABEL_PREFIX);
+        bytes[10] = Self::MATERIAL_TYPE_AUDIO;
+        bytes[11] = Self::METHOD_UUID;
+        bytes[12] = Self::BASIC_LENGTH;
+        bytes[16..].copy_from_slice(&material_number);
+        Umid(bytes)
+    }
+
+    /// Basic UMID with a random version 4 UUID as material number.
+    ///
+    /// Randomness comes from the standard library hasher seeds, the time and
+    /// a counter. Unique enough to identify recordings, not suitable for
+    /// cryptographic use.
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        use core::hash::{BuildHasher, Hasher};
+        use core::sync::atomic::{AtomicU64, Ordering};
+        use std::collections::hash_map::RandomState;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let mut material_number = [0_u8; 16];
+        for half in material_number.chunks_exact_mut(8) {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos);
+            hasher.write_u64(count);
+            half.copy_from_slice(&hasher.finish().to_be_bytes());
+        }
+        // UUID version 4 and RFC 4122 variant bits
+        material_number[6] = (material_number[6] & 0x0F) | 0x40;
+        material_number[8] = (material_number[8] & 0x3F) | 0x80;
+        Umid::new(material_number)
+    }
+
+    /// Basic UMID from the first 32 bytes of `bytes`, ex: the `bext` `umid`
+    /// field. `None` if they don't start with the UMID label.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 32] = bytes.get(..32)?.try_into().ok()?;
+        bytes
+            .starts_with(&Self::LABEL_PREFIX)
+            .then_some(Umid(bytes))
+    }
+
+    /// The 16 byte material number.
+    pub fn material_number(&self) -> [u8; 16] {
+        let mut material_number = [0_u8; 16];
+        material_number.copy_from_slice(&self.0[16..]);
+        material_number
+    }
+
+    /// The 64 byte `bext` `umid` field: the basic UMID followed by zeros.
+    pub fn to_bext(&self) -> [u8; 64] {
+        let mut bytes = [0_u8; 64];
+        bytes[..32].copy_from_slice(&self.0);
+        bytes
+    }
+}
+
+impl Display for Umid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", hex::encode_upper(self.0))
+    }
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn umid_layout() {
+        let umid = Umid::new([0xAB; 16]);
+        dbg!(&umid);
+        assert_eq!(
+            umid.to_string(),
+            "060A2B34010101050101021013000000ABABABABABABABABABABABABABABABAB"
+        );
+        assert_eq!(umid.material_number(), [0xAB; 16]);
+        assert_eq!(Umid::from_bytes(&umid.to_bext()), Some(umid));
+        assert_eq!(Umid::from_bytes(&[0_u8; 64]), None);
+        assert_eq!(Umid::from_bytes(&umid.0[..16]), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn umid_random() {
+        let a = Umid::random();
+        let b = Umid::random();
+        dbg!(a.to_string(), b.to_string());
+        assert_ne!(a, b);
+        assert_eq!(a.0[..16], Umid::new([0; 16]).0[..16]);
+        let material_number = a.material_number();
+        assert_eq!(material_number[6] >> 4, 4);
+        assert_eq!(material_number[8] >> 6, 2);
+    }
+}
@@ -0,0 +1,163 @@
+//! Non fatal problems found while parsing.
+//!
+//! Many WAVE files bend the specification in ways which can be recovered
+//! from: incorrect sizes, missing padding, duplicate chunks and so on. Instead
+//! of failing, parsing continues and a [`Warning`] is recorded. After
+//! iterating, retrieve them with [`WaveFile::warnings()`](crate::WaveFile::warnings).
+//!
+//! Each warning has a [`Code`], which stays the same across releases, for
+//! tools which act on specific problems. Errors have codes too, see
+//! [`WaveFileError::code()`](crate::WaveFileError::code).
+
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+use crate::FourCC;
+
+/// Stable identifier of a kind of warning or error.
+///
+/// [`Code::as_str()`] returns the identifier used in reports. Identifiers
+/// are never changed or reused, new ones may be added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Code {
+    /// Not classified yet.
+    Other,
+
+    // Errors
+    /// Reading from the underlying stream failed.
+    Io,
+    /// A chunk id was not recognized where one was required.
+    UnknownChunkId,
+    /// Chunk data could not be parsed.
+    Parse,
+
+    // Warnings while parsing
+    /// RIFF size doesn't match the stream length.
+    RiffSizeMismatch,
+    /// Chunk size is a placeholder left by a streaming encoder.
+    PlaceholderSize,
+    /// Chunk extends past the end of the data.
+    ChunkPastEnd,
+    /// Odd sized chunk is not followed by a padding byte.
+    MissingPadding,
+    /// Parser read less data than the chunk size.
+    ShortParse,
+    /// Chunk is smaller than its fixed fields.
+    TruncatedFields,
+    /// Text is not valid in its expected encoding.
+    InvalidText,
+    /// More than one of a chunk which should only appear once.
+    DuplicateChunk,
+    /// Chunk id differs from the id of the parser used, see
+    /// [`ParseOptions::case_insensitive_ids`](crate::ParseOptions::case_insensitive_ids).
+    ChunkIdAlias,
+
+    // Consistency checks
+    /// `fmt ` fields disagree with each other.
+    FormatMismatch,
+    /// `data` size is not a whole number of blocks.
+    DataNotBlockAligned,
+    /// `fact` is missing for a compressed format.
+    MissingFact,
+    /// `fact` sample count disagrees with the `data` length.
+    FactMismatch,
+    /// A reference to a cue point which doesn't exist.
+    MissingCuePoint,
+    /// A field value is out of range.
+    InvalidValue,
+    /// Fields in two chunks which should match disagree.
+    MetadataMismatch,
+    /// A chunk doesn't write back to the bytes it was read from.
+    RoundtripMismatch,
+
+    // Conformance profiles
+    /// A chunk required by a [`Profile`](crate::profile::Profile) is missing.
+    MissingChunk,
+    /// Chunks are not in the order required by a profile.
+    ChunkOrder,
+    /// A field required by a profile is empty.
+    MissingField,
+}
+
+impl Code {
+    /// Identifier of the code, in kebab case. Ex: `missing-padding`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Code::Other => "other",
+            Code::Io => "io",
+            Code::UnknownChunkId => "unknown-chunk-id",
+            Code::Parse => "parse",
+            Code::RiffSizeMismatch => "riff-size-mismatch",
+            Code::PlaceholderSize => "placeholder-size",
+            Code::ChunkPastEnd => "chunk-past-end",
+            Code::MissingPadding => "missing-padding",
+            Code::ShortParse => "short-parse",
+            Code::TruncatedFields => "truncated-fields",
+            Code::InvalidText => "invalid-text",
+            Code::DuplicateChunk => "duplicate-chunk",
+            Code::ChunkIdAlias => "chunk-id-alias",
+            Code::FormatMismatch => "format-mismatch",
+            Code::DataNotBlockAligned => "data-not-block-aligned",
+            Code::MissingFact => "missing-fact",
+            Code::FactMismatch => "fact-mismatch",
+            Code::MissingCuePoint => "missing-cue-point",
+            Code::InvalidValue => "invalid-value",
+            Code::MetadataMismatch => "metadata-mismatch",
+            Code::RoundtripMismatch => "roundtrip-mismatch",
+            Code::MissingChunk => "missing-chunk",
+            Code::ChunkOrder => "chunk-order",
+            Code::MissingField => "missing-field",
+        }
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A problem found while parsing which did not stop parsing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Warning {
+    /// Kind of problem.
+    pub code: Code,
+
+    /// Id of the chunk the warning is about.
+    pub id: FourCC,
+
+    /// Byte offset of the chunk in the data stream, if known.
+    pub offset: Option<u64>,
+
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Warning {
+    /// Create a new [`Warning`], with [`Code::Other`].
+    pub fn new(id: FourCC, offset: Option<u64>, message: impl Into<String>) -> Self {
+        Warning {
+            code: Code::Other,
+            id,
+            offset,
+            message: message.into(),
+        }
+    }
+
+    /// Set [`Warning::code`].
+    #[must_use]
+    pub fn with_code(mut self, code: Code) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{} at {}: {}", self.id, offset, self.message),
+            None => write!(f, "{}: {}", self.id, self.message),
+        }
+    }
+}
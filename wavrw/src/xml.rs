@@ -0,0 +1,380 @@
+//! Minimal XML reader for the small documents embedded in chunks, like `iXML`.
+//!
+//! Only elements, attributes and text are kept. Processing instructions,
+//! comments and doctypes are skipped, CDATA sections are read as text and
+//! the predefined and numeric entities are decoded. Namespaces and DTDs are
+//! not supported.
+//!
+//! [`Element::to_xml()`] writes elements back as a document, used to edit
+//! sections of `iXML` and to write sidecars. Skipped parts like comments
+//! are not written.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error;
+use core::fmt::{Display, Formatter};
+
+/// An XML element with its attributes, text and child elements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub(crate) struct Element {
+    /// Tag name.
+    pub name: String,
+    /// Attributes as (name, value) pairs, in document order.
+    pub attributes: Vec<(String, String)>,
+    /// Text directly inside this element, trimmed.
+    pub text: String,
+    /// Child elements in document order.
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    /// Parse `document` and return the root element.
+    pub fn parse(document: &str) -> Result<Element, XmlError> {
+        let mut parser = Parser {
+            input: document,
+            pos: 0,
+        };
+        parser.skip_misc()?;
+        let root = parser.element()?;
+        parser.skip_misc()?;
+        if parser.pos < parser.input.len() {
+            return Err(parser.error("content after root element"));
+        }
+        Ok(root)
+    }
+
+    /// The first child element named `name`.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Text of the first child element named `name`, `None` if missing or empty.
+    #[cfg(feature = "ixml")]
+    pub fn child_text(&self, name: &str) -> Option<String> {
+        self.child(name)
+            .map(|c| c.text.clone())
+            .filter(|t| !t.is_empty())
+    }
+
+    /// The first child element named `name`, mutable.
+    #[cfg(feature = "ixml")]
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Element> {
+        self.children.iter_mut().find(|c| c.name == name)
+    }
+
+    /// Set the text of the first child element named `name`, appending
+    /// the child if missing.
+    #[cfg(feature = "ixml")]
+    pub fn set_child_text(&mut self, name: &str, text: &str) {
+        if let Some(child) = self.child_mut(name) {
+            child.text = text.to_string();
+        } else {
+            self.children.push(Element {
+                name: name.to_string(),
+                text: text.to_string(),
+                ..Element::default()
+            });
+        }
+    }
+
+    /// Write this element as an XML document with an XML declaration,
+    /// children indented by two spaces.
+    pub fn to_xml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.write(&mut out, 0);
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        out.push_str(&format!("{indent}<{}", self.name));
+        for (name, value) in &self.attributes {
+            out.push_str(&format!(" {name}=\"{}\"", encode_attribute(value)));
+        }
+        if self.text.is_empty() && self.children.is_empty() {
+            out.push_str("/>\n");
+            return;
+        }
+        out.push('>');
+        out.push_str(&encode_entities(&self.text));
+        if !self.children.is_empty() {
+            out.push('\n');
+            for child in &self.children {
+                child.write(out, depth + 1);
+            }
+            out.push_str(&indent);
+        }
+        out.push_str(&format!("</{}>\n", self.name));
+    }
+}
+
+/// Error from [`Element::parse()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct XmlError {
+    /// Byte offset in the document.
+    pub pos: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Display for XmlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid XML at byte {}: {}", self.pos, self.message)
+    }
+}
+
+impl error::Error for XmlError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        self.input.get(self.pos..).unwrap_or_default()
+    }
+
+    fn error(&self, message: &str) -> XmlError {
+        XmlError {
+            pos: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip past the next `end`, returning the text before it.
+    fn take_until(&mut self, end: &str) -> Result<&str, XmlError> {
+        let Some(index) = self.rest().find(end) else {
+            return Err(self.error(&format!("missing {end:?}")));
+        };
+        let start = self.pos;
+        self.pos += index + end.len();
+        Ok(self.input.get(start..start + index).unwrap_or_default())
+    }
+
+    /// Skip whitespace, processing instructions, comments and doctypes.
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.take_until("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if rest.starts_with("<!DOCTYPE") {
+                self.take_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, XmlError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '='))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = rest.get(..len).unwrap_or_default().to_string();
+        self.pos += len;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, XmlError> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected '<'"));
+        }
+        self.pos += 1;
+        let mut element = Element {
+            name: self.name()?,
+            ..Element::default()
+        };
+
+        // attributes
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected '=' after attribute name"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error("expected quoted attribute value")),
+            };
+            self.pos += 1;
+            let value = decode_entities(self.take_until(&quote.to_string())?);
+            element.attributes.push((name, value));
+        }
+
+        // content
+        let mut text = String::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return Err(self.error(&format!("missing </{}>", element.name)));
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(
+                        self.error(&format!("expected </{}>, found </{name}>", element.name))
+                    );
+                }
+                self.skip_whitespace();
+                self.take_until(">")?;
+                break;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                text.push_str(self.take_until("]]>")?);
+            } else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                self.skip_misc()?;
+            } else if rest.starts_with('<') {
+                element.children.push(self.element()?);
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                text.push_str(&decode_entities(rest.get(..len).unwrap_or_default()));
+                self.pos += len;
+            }
+        }
+        element.text = text.trim().to_string();
+        Ok(element)
+    }
+}
+
+/// Escape characters which can't appear as is in text or attribute values.
+fn encode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape an attribute value, including tabs and line breaks which
+/// readers would otherwise replace with spaces.
+fn encode_attribute(value: &str) -> String {
+    encode_entities(value)
+        .replace('\t', "&#9;")
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+}
+
+/// Replace predefined and numeric character entities.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(rest.get(..start).unwrap_or_default());
+        rest = rest.get(start..).unwrap_or_default();
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = rest.get(1..end).unwrap_or_default();
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        if let Some(c) = decoded {
+            out.push(c);
+            rest = rest.get(end + 1..).unwrap_or_default();
+        } else {
+            // not an entity, keep the '&' as is
+            out.push('&');
+            rest = rest.get(1..).unwrap_or_default();
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[allow(clippy::dbg_macro)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn parse_document() {
+        let root = Element::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- comment -->
+            <BWFXML>
+                <PROJECT>a &amp; b &#65;&#x42;</PROJECT>
+                <EMPTY/>
+                <TRACK index='1'><NAME><![CDATA[<boom>]]></NAME></TRACK>
+            </BWFXML>"#,
+        )
+        .unwrap();
+        dbg!(&root);
+        assert_eq!(root.name, "BWFXML");
+        assert_eq!(root.child_text("PROJECT"), Some("a & b AB".to_string()));
+        assert_eq!(root.child_text("EMPTY"), None);
+        let track = root.child("TRACK").unwrap();
+        assert_eq!(track.attributes, vec![("index".into(), "1".into())]);
+        assert_eq!(track.child_text("NAME"), Some("<boom>".to_string()));
+    }
+
+    #[cfg(feature = "ixml")]
+    #[test]
+    fn write_document() {
+        let mut root =
+            Element::parse("<BWFXML><NOTE a='x&quot;&#10;'>1 &lt; 2</NOTE><EMPTY/></BWFXML>")
+                .unwrap();
+        root.set_child_text("EMPTY", "filled");
+        root.set_child_text("NEW", "");
+        let xml = root.to_xml();
+        dbg!(&xml);
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <BWFXML>\n  \
+            <NOTE a=\"x&quot;&#10;\">1 &lt; 2</NOTE>\n  \
+            <EMPTY>filled</EMPTY>\n  \
+            <NEW/>\n\
+            </BWFXML>\n"
+        );
+        assert_eq!(Element::parse(&xml).unwrap(), root);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Element::parse("<A><B></A>").is_err());
+        assert!(Element::parse("<A>").is_err());
+        assert!(Element::parse("").is_err());
+        assert!(Element::parse("<A/><B/>").is_err());
+    }
+}